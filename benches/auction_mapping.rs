@@ -0,0 +1,81 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use auctions_api::api::handlers::auctions::map_auction_to_detail_model;
+use auctions_api::domain::commands::CreateAuctionCommand;
+use auctions_api::domain::models::{Amount, Auction, AuctionFactory, BidData, CurrencyCode, UserId};
+use chrono::{Duration, TimeZone, Utc};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Counts allocations, so that the effect of borrowing in the mappers shows up directly.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn auction_with_bids(bids: i64, open_bidders: bool) -> Auction {
+    let starts_at = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+    let mut auction = AuctionFactory::create_auction(
+        CreateAuctionCommand {
+            title: "benchmark".to_string(),
+            currency: CurrencyCode::SEK,
+            starts_at,
+            ends_at: starts_at + Duration::days(30),
+            min_raise: Some(1),
+            open_bidders,
+            ..CreateAuctionCommand::default()
+        },
+        UserId::new("seller"),
+    )
+    .unwrap();
+    for i in 1..=bids {
+        let at = starts_at + Duration::minutes(i);
+        let bid = BidData {
+            user: UserId::new(format!("bidder-{}", i % 50)),
+            amount: Amount::new(i * 10, CurrencyCode::SEK),
+            at,
+            quantity: 1,
+            metadata: None,
+        };
+        auction.try_add_bid(at, bid).unwrap();
+    }
+    auction
+}
+
+fn allocations_per_mapping(auction: &Auction) -> usize {
+    let now = auction.starts_at() + Duration::days(1);
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(map_auction_to_detail_model(auction, now));
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn mapping(c: &mut Criterion) {
+    for (name, open_bidders) in [("open bidders", true), ("bidder aliases", false)] {
+        let auction = auction_with_bids(1000, open_bidders);
+        let now = auction.starts_at() + Duration::days(1);
+        println!(
+            "map_auction_to_detail_model, 1000 bids, {}: {} allocations",
+            name,
+            allocations_per_mapping(&auction)
+        );
+        c.bench_function(&format!("map_auction_to_detail_model 1000 bids, {}", name), |b| {
+            b.iter(|| map_auction_to_detail_model(black_box(&auction), now))
+        });
+    }
+}
+
+criterion_group!(benches, mapping);
+criterion_main!(benches);
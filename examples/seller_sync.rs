@@ -0,0 +1,91 @@
+// examples/seller_sync.rs
+//
+// Keeps the auctions of a seller in step with the catalogue of their shop: lists the published
+// auctions, and creates those of the catalogue that are not listed yet, matched on their external
+// reference:
+//
+//     cargo run --example seller_sync -- [--url=http://127.0.0.1:8080] [--user=seller]
+use auctions_api::api::client::ApiClient;
+use auctions_api::api::models::CreateAuctionModel;
+use auctions_api::domain::models::{Amount, CurrencyCode, User, UserId};
+use chrono::{Duration, Utc};
+use std::collections::HashSet;
+
+/// What the shop sells, by its own article numbers, with reserve prices in whole kronor
+const CATALOGUE: &[(&str, &str, i64)] = &[
+    ("ART-1001", "Oak dining table", 2500),
+    ("ART-1002", "Set of four chairs", 1200),
+    ("ART-1003", "Brass floor lamp", 400),
+];
+
+fn sek(kronor: i64) -> Amount {
+    Amount::new(kronor * 100, CurrencyCode::SEK)
+}
+
+#[tokio::main]
+async fn main() {
+    let mut url = "http://127.0.0.1:8080".to_string();
+    let mut user = "seller".to_string();
+    for arg in std::env::args().skip(1) {
+        match arg.split_once('=') {
+            Some(("--url", value)) => url = value.to_string(),
+            Some(("--user", value)) => user = value.to_string(),
+            _ => {
+                eprintln!("Usage: seller_sync [--url=http://127.0.0.1:8080] [--user=seller]");
+                std::process::exit(2);
+            }
+        }
+    }
+    let client = ApiClient::new(url).as_user(&User::new_buyer_or_seller(UserId::new(&user), None::<String>));
+
+    let mut listed = HashSet::new();
+    let mut pages = client.auctions(50);
+    while let Some(auction) = pages.next().await {
+        match auction {
+            Ok(auction) => listed.extend(auction.external_reference),
+            Err(e) => {
+                eprintln!("Could not list the auctions: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let now = Utc::now();
+    for (reference, title, reserve_price) in CATALOGUE {
+        if listed.contains(*reference) {
+            println!("{} is already listed", reference);
+            continue;
+        }
+        let model = CreateAuctionModel {
+            title: title.to_string(),
+            description: None,
+            currency: CurrencyCode::SEK,
+            starts_at: now,
+            ends_at: now + Duration::days(7),
+            min_raise: Some(sek(10)),
+            reserve_price: Some(sek(*reserve_price)),
+            time_frame: None,
+            buy_now_price: None,
+            starting_price: None,
+            max_extension: None,
+            increments: Vec::new(),
+            single_sealed_bid_options: None,
+            tie_break: None,
+            vickrey_pricing: None,
+            open_bidders: false,
+            requires_registration: false,
+            deposit: None,
+            relist: None,
+            quantity: None,
+            external_reference: Some(reference.to_string()),
+            lots: Vec::new(),
+            draft: false,
+            fees: None,
+            tax_rate_bps: None,
+        };
+        match client.create_auction(&model).await {
+            Ok(auction) => println!("Listed {} as auction {}", reference, auction.id),
+            Err(e) => eprintln!("Could not list {}: {}", reference, e),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+// examples/sniper_bot.rs
+//
+// Watches an auction and bids the lowest accepted amount in its last seconds, as long as that
+// stays within the most the bidder is willing to pay:
+//
+//     cargo run --example sniper_bot -- <auction id> <max bid, e.g. SEK500> [--url=...] [--user=...]
+use auctions_api::api::client::ApiClient;
+use auctions_api::api::models::CreateBidModel;
+use auctions_api::domain::models::{Amount, User, UserId};
+use chrono::{Duration as ChronoDuration, Utc};
+use std::time::Duration;
+
+const USAGE: &str = "Usage: sniper_bot <auction id> <max bid> [--url=http://127.0.0.1:8080] [--user=sniper]";
+/// How long before the end to bid, so that others have no time to answer
+const SNIPE_WINDOW: ChronoDuration = ChronoDuration::seconds(5);
+
+#[tokio::main]
+async fn main() {
+    let mut positional = Vec::new();
+    let mut url = "http://127.0.0.1:8080".to_string();
+    let mut user = "sniper".to_string();
+    for arg in std::env::args().skip(1) {
+        match arg.split_once('=') {
+            Some(("--url", value)) => url = value.to_string(),
+            Some(("--user", value)) => user = value.to_string(),
+            _ => positional.push(arg),
+        }
+    }
+    let (auction_id, max_bid) = match positional.as_slice() {
+        [id, max] => match (id.parse::<i64>(), max.parse::<Amount>()) {
+            (Ok(id), Ok(max)) => (id, max),
+            _ => exit_with_usage(),
+        },
+        _ => exit_with_usage(),
+    };
+    let client = ApiClient::new(url).as_user(&User::new_buyer_or_seller(UserId::new(&user), None::<String>));
+
+    // Still leading while the current price is what was last bid
+    let mut last_bid: Option<Amount> = None;
+    // To wake up when outbid
+    let mut placed_bids = client.placed_bids(auction_id);
+    loop {
+        let auction = match client.get_auction(auction_id).await {
+            Ok(auction) => auction,
+            Err(e) => {
+                eprintln!("Could not read auction {}: {}", auction_id, e);
+                std::process::exit(1);
+            }
+        };
+        if auction.has_ended {
+            println!("Auction {} has ended, won by {}", auction_id, auction.winner.as_deref().unwrap_or("nobody"));
+            return;
+        }
+        let left = auction.expiry - Utc::now();
+        if left > SNIPE_WINDOW {
+            // Sleep until the window opens, but look again now and then as the end may move
+            let wait = (left - SNIPE_WINDOW).to_std().unwrap_or_default().min(Duration::from_secs(30));
+            tokio::time::sleep(wait).await;
+            continue;
+        }
+        if last_bid.is_some() && auction.current_price == last_bid {
+            // Leading, until someone bids more or the auction ends
+            if let Some(Err(e)) = placed_bids.next().await {
+                eprintln!("Could not follow the bids on auction {}: {}", auction_id, e);
+                std::process::exit(1);
+            }
+            continue;
+        }
+        let Some(amount) = auction.next_minimum_bid else {
+            println!("Auction {} does not show the lowest accepted bid, giving up", auction_id);
+            return;
+        };
+        if amount > max_bid {
+            println!("The lowest accepted bid {} is more than {}, giving up", amount, max_bid);
+            return;
+        }
+        let bid = CreateBidModel { amount: amount.clone(), quantity: None, max_bid: false };
+        match client.place_bid(auction_id, &bid).await {
+            Ok(()) => {
+                println!("Bid {} on auction {}", amount, auction_id);
+                last_bid = Some(amount);
+            }
+            // Someone else bid first, look again
+            Err(e) => println!("Bid {} was not accepted: {}", amount, e),
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+fn exit_with_usage() -> ! {
+    eprintln!("{}", USAGE);
+    std::process::exit(2);
+}
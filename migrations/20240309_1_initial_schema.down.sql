@@ -0,0 +1,5 @@
+DROP TRIGGER update_auctions_updated_at ON auctions;
+DROP FUNCTION update_updated_at_column();
+
+DROP TABLE bids;
+DROP TABLE auctions;
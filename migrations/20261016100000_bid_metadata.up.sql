@@ -0,0 +1,2 @@
+-- Provenance of bids (channel, user agent, hashed client address) for audit purposes
+ALTER TABLE bids ADD COLUMN metadata JSONB;
@@ -0,0 +1,17 @@
+-- Work done for an auction after the request that caused it, so that it survives restarts and
+-- support can retry, cancel or reschedule it
+CREATE TABLE background_jobs (
+    id BIGSERIAL PRIMARY KEY,
+    kind VARCHAR(50) NOT NULL,
+    auction_id BIGINT NOT NULL,
+    state VARCHAR(50) NOT NULL,
+    run_at TIMESTAMPTZ NOT NULL,
+    attempts INT NOT NULL DEFAULT 0,
+    -- Error of the last attempt, empty when it succeeded
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_background_jobs_due ON background_jobs(run_at) WHERE state = 'Pending';
+CREATE INDEX idx_background_jobs_auction_id ON background_jobs(auction_id);
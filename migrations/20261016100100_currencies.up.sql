@@ -0,0 +1,13 @@
+-- Reference data for currencies, adjustable by operators without a redeploy
+CREATE TABLE currencies (
+    code VARCHAR(3) PRIMARY KEY,
+    numeric_code INTEGER NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    minor_units SMALLINT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE
+);
+
+INSERT INTO currencies (code, numeric_code, name, minor_units) VALUES
+    ('VAC', 1001, 'Virtual auction currency', 0),
+    ('SEK', 752, 'Swedish krona', 2),
+    ('DKK', 208, 'Danish krone', 2);
@@ -0,0 +1,4 @@
+DROP TABLE auction_audit_log;
+
+ALTER TABLE auctions DROP COLUMN void_reason;
+ALTER TABLE auctions DROP COLUMN voided_at;
@@ -0,0 +1,14 @@
+ALTER TABLE auctions ADD COLUMN voided_at TIMESTAMPTZ;
+ALTER TABLE auctions ADD COLUMN void_reason TEXT;
+
+-- Who did what to an auction outside of the normal bidding flow, and why
+CREATE TABLE auction_audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    auction_id BIGINT NOT NULL REFERENCES auctions(id) ON DELETE CASCADE,
+    action VARCHAR(50) NOT NULL,
+    user_id VARCHAR(2000) NOT NULL,
+    reason TEXT,
+    at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_auction_audit_log_auction_id ON auction_audit_log(auction_id);
@@ -0,0 +1,2 @@
+ALTER TABLE bids DROP COLUMN quantity;
+ALTER TABLE auctions DROP COLUMN quantity;
@@ -0,0 +1,3 @@
+-- Multi-unit (Yankee) auctions: number of identical items on offer and units requested per bid
+ALTER TABLE auctions ADD COLUMN quantity INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE bids ADD COLUMN quantity INTEGER NOT NULL DEFAULT 1;
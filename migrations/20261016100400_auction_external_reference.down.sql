@@ -0,0 +1,2 @@
+DROP INDEX idx_auctions_user_id_external_reference;
+ALTER TABLE auctions DROP COLUMN external_reference;
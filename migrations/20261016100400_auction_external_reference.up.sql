@@ -0,0 +1,6 @@
+-- Client-supplied reference that makes auction creation idempotent per seller
+ALTER TABLE auctions ADD COLUMN external_reference VARCHAR(200);
+
+CREATE UNIQUE INDEX idx_auctions_user_id_external_reference
+    ON auctions(user_id, external_reference)
+    WHERE external_reference IS NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE auctions ADD COLUMN description TEXT;
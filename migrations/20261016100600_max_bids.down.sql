@@ -0,0 +1 @@
+DROP TABLE max_bids;
@@ -0,0 +1,9 @@
+-- Maximum (proxy) bids, one per bidder and auction. Not visible as bids.
+CREATE TABLE max_bids (
+    auction_id BIGINT NOT NULL REFERENCES auctions(id) ON DELETE CASCADE,
+    user_id VARCHAR(2000) NOT NULL,
+    amount_value BIGINT NOT NULL,
+    amount_currency VARCHAR(3) NOT NULL,
+    at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (auction_id, user_id)
+);
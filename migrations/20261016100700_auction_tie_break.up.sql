@@ -0,0 +1,3 @@
+-- Which of several equally high sealed bids wins. NULL for timed ascending auctions.
+ALTER TABLE auctions ADD COLUMN tie_break VARCHAR(20);
+UPDATE auctions SET tie_break = 'EarliestBid' WHERE auction_type = 'SingleSealedBid';
@@ -0,0 +1,8 @@
+-- Lifecycle status, see AuctionStatus. Existing auctions get the status their timestamps imply.
+ALTER TABLE auctions ADD COLUMN status VARCHAR(20) NOT NULL DEFAULT 'Scheduled';
+UPDATE auctions SET status = CASE
+    WHEN voided_at IS NOT NULL THEN 'Cancelled'
+    WHEN coalesce(ends_at, expiry) < now() THEN 'Closed'
+    WHEN starts_at <= now() THEN 'Open'
+    ELSE 'Scheduled'
+END;
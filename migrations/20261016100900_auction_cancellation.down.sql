@@ -0,0 +1,2 @@
+ALTER TABLE auctions DROP COLUMN cancel_reason;
+ALTER TABLE auctions DROP COLUMN cancelled_at;
@@ -0,0 +1,3 @@
+-- Auctions withdrawn by their seller or by support
+ALTER TABLE auctions ADD COLUMN cancelled_at TIMESTAMPTZ;
+ALTER TABLE auctions ADD COLUMN cancel_reason TEXT;
@@ -0,0 +1,3 @@
+DROP TABLE auction_summaries;
+DROP TABLE projection_checkpoints;
+DROP TABLE auction_events;
@@ -0,0 +1,25 @@
+-- Outbox of auction changes, written in the same transaction as the change itself
+CREATE TABLE auction_events (
+    id BIGSERIAL PRIMARY KEY,
+    auction_id BIGINT NOT NULL REFERENCES auctions(id),
+    event_type TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Last event each projection has applied
+CREATE TABLE projection_checkpoints (
+    name TEXT PRIMARY KEY,
+    position BIGINT NOT NULL
+);
+
+-- Read model maintained by the projection worker
+CREATE TABLE auction_summaries (
+    auction_id BIGINT PRIMARY KEY REFERENCES auctions(id),
+    status TEXT NOT NULL,
+    bid_count INTEGER NOT NULL DEFAULT 0,
+    highest_amount_value BIGINT,
+    highest_amount_currency TEXT,
+    last_bid_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,3 @@
+-- Without the column every row is a standing bid, so the retracted ones go
+DELETE FROM bids WHERE retracted_at IS NOT NULL;
+ALTER TABLE bids DROP COLUMN retracted_at;
@@ -0,0 +1,2 @@
+-- Bids withdrawn by their bidder are kept, flagged with when they were retracted
+ALTER TABLE bids ADD COLUMN retracted_at TIMESTAMPTZ;
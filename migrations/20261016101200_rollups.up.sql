@@ -0,0 +1,10 @@
+-- Pre-aggregated time series maintained by the projection worker.
+-- currency is empty for metrics that are not amounts.
+CREATE TABLE rollups (
+    metric TEXT NOT NULL,
+    granularity TEXT NOT NULL,
+    bucket TIMESTAMPTZ NOT NULL,
+    currency TEXT NOT NULL DEFAULT '',
+    value BIGINT NOT NULL,
+    PRIMARY KEY (metric, granularity, bucket, currency)
+);
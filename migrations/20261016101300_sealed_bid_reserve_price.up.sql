@@ -0,0 +1,2 @@
+-- Reserve of single sealed bid auctions. NULL for timed ascending auctions, which keep theirs in options.
+ALTER TABLE auctions ADD COLUMN reserve_price BIGINT;
@@ -0,0 +1,2 @@
+ALTER TABLE auctions DROP COLUMN extension_reason;
+ALTER TABLE auctions DROP COLUMN extended_at;
@@ -0,0 +1,3 @@
+-- Last expiry change made by support, who made it is in auction_audit_log
+ALTER TABLE auctions ADD COLUMN extended_at TIMESTAMPTZ;
+ALTER TABLE auctions ADD COLUMN extension_reason TEXT;
@@ -0,0 +1 @@
+ALTER TABLE rollups DROP COLUMN contributors;
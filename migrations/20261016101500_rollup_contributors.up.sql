@@ -0,0 +1,4 @@
+-- Number of events behind each bucket, used to hold back small cohorts from public stats.
+-- Counts are their own number of contributors. Earlier gmv buckets are unknown and stay at 0.
+ALTER TABLE rollups ADD COLUMN contributors BIGINT NOT NULL DEFAULT 0;
+UPDATE rollups SET contributors = value WHERE metric <> 'gmv';
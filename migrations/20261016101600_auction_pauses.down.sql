@@ -0,0 +1 @@
+DROP TABLE auction_pauses;
@@ -0,0 +1,7 @@
+-- Intervals during which support stopped an auction from taking bids
+CREATE TABLE auction_pauses (
+    auction_id BIGINT NOT NULL REFERENCES auctions(id) ON DELETE CASCADE,
+    paused_at TIMESTAMPTZ NOT NULL,
+    resumed_at TIMESTAMPTZ,
+    PRIMARY KEY (auction_id, paused_at)
+);
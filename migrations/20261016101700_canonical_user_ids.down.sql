@@ -0,0 +1,2 @@
+-- The spelling user ids had before they were made canonical is not kept, and canonical ids are
+-- read as they are, so there is nothing to undo
@@ -0,0 +1,28 @@
+-- User ids are stored in canonical form: trimmed and in lower case, see UserId::parse.
+-- Ids that only differed in case now belong to the same user.
+
+-- A seller may have used an external reference under two spellings of their id: keep the oldest
+UPDATE auctions a
+SET external_reference = NULL
+WHERE external_reference IS NOT NULL
+  AND EXISTS (
+    SELECT 1 FROM auctions other
+    WHERE lower(btrim(other.user_id)) = lower(btrim(a.user_id))
+      AND other.external_reference = a.external_reference
+      AND other.id < a.id
+  );
+UPDATE auctions SET user_id = lower(btrim(user_id)) WHERE user_id <> lower(btrim(user_id));
+
+UPDATE bids SET user_id = lower(btrim(user_id)) WHERE user_id <> lower(btrim(user_id));
+
+-- One maximum bid per bidder: keep the latest
+DELETE FROM max_bids m
+WHERE EXISTS (
+    SELECT 1 FROM max_bids other
+    WHERE other.auction_id = m.auction_id
+      AND lower(btrim(other.user_id)) = lower(btrim(m.user_id))
+      AND (other.at, other.user_id) > (m.at, m.user_id)
+);
+UPDATE max_bids SET user_id = lower(btrim(user_id)) WHERE user_id <> lower(btrim(user_id));
+
+UPDATE auction_audit_log SET user_id = lower(btrim(user_id)) WHERE user_id <> lower(btrim(user_id));
@@ -0,0 +1,2 @@
+-- Which of NONE and None the placeholder was written as is not kept, and XXX is a currency code
+-- of its own, so there is nothing to undo
@@ -0,0 +1,13 @@
+-- Currencies are stored as ISO 4217 alphabetic codes. The no-currency placeholder used to be
+-- written as NONE or None depending on the layer, it is XXX now.
+UPDATE auctions SET currency = 'XXX' WHERE currency IN ('NONE', 'None');
+UPDATE bids SET amount_currency = 'XXX' WHERE amount_currency IN ('NONE', 'None');
+UPDATE max_bids SET amount_currency = 'XXX' WHERE amount_currency IN ('NONE', 'None');
+UPDATE rollups SET currency = 'XXX' WHERE currency IN ('NONE', 'None');
+
+UPDATE auction_events
+SET payload = jsonb_set(payload, '{amountCurrency}', '"XXX"')
+WHERE payload->>'amountCurrency' IN ('NONE', 'None');
+UPDATE auction_events
+SET payload = jsonb_set(payload, '{currency}', '"XXX"')
+WHERE payload->>'currency' IN ('NONE', 'None');
@@ -0,0 +1,2 @@
+DROP TABLE auction_registrations;
+ALTER TABLE auctions DROP COLUMN requires_registration;
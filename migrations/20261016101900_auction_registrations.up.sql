@@ -0,0 +1,11 @@
+-- Auctions where only bidders approved by the seller may bid
+ALTER TABLE auctions ADD COLUMN requires_registration BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE auction_registrations (
+    auction_id BIGINT NOT NULL REFERENCES auctions(id) ON DELETE CASCADE,
+    user_id VARCHAR(2000) NOT NULL,
+    status VARCHAR(20) NOT NULL,
+    registered_at TIMESTAMPTZ NOT NULL,
+    decided_at TIMESTAMPTZ,
+    PRIMARY KEY (auction_id, user_id)
+);
@@ -0,0 +1,2 @@
+DROP TABLE auction_deposits;
+ALTER TABLE auctions DROP COLUMN deposit;
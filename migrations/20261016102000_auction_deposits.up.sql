@@ -0,0 +1,11 @@
+-- Auctions where bidders must deposit an amount, in the currency of the auction, before bidding
+ALTER TABLE auctions ADD COLUMN deposit BIGINT;
+
+CREATE TABLE auction_deposits (
+    auction_id BIGINT NOT NULL REFERENCES auctions(id) ON DELETE CASCADE,
+    user_id VARCHAR(2000) NOT NULL,
+    amount_value BIGINT NOT NULL,
+    amount_currency VARCHAR(3) NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (auction_id, user_id)
+);
@@ -0,0 +1,3 @@
+-- Version of the record in the options column. Rows written before the column existed hold
+-- version 1, and are upgraded when read.
+ALTER TABLE auctions ADD COLUMN options_version INTEGER NOT NULL DEFAULT 1;
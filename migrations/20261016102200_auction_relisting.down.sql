@@ -0,0 +1,5 @@
+ALTER TABLE auctions DROP COLUMN relist_count;
+ALTER TABLE auctions DROP COLUMN relisted_as;
+ALTER TABLE auctions DROP COLUMN relisted_from;
+ALTER TABLE auctions DROP COLUMN max_relists;
+ALTER TABLE auctions DROP COLUMN relist_duration_seconds;
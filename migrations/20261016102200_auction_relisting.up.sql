@@ -0,0 +1,6 @@
+-- Relisting of auctions that end unsold. Without a duration the seller did not opt in.
+ALTER TABLE auctions ADD COLUMN relist_duration_seconds BIGINT;
+ALTER TABLE auctions ADD COLUMN max_relists INTEGER;
+ALTER TABLE auctions ADD COLUMN relisted_from BIGINT REFERENCES auctions(id);
+ALTER TABLE auctions ADD COLUMN relisted_as BIGINT REFERENCES auctions(id);
+ALTER TABLE auctions ADD COLUMN relist_count INTEGER NOT NULL DEFAULT 0;
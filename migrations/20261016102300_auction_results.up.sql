@@ -0,0 +1,9 @@
+-- Outcome of each closed auction, recorded once when it is first seen closed
+CREATE TABLE auction_results (
+    auction_id BIGINT PRIMARY KEY REFERENCES auctions(id) ON DELETE CASCADE,
+    winner VARCHAR(2000),
+    amount_value BIGINT,
+    amount_currency VARCHAR(3),
+    closed_at TIMESTAMPTZ NOT NULL,
+    reserve_met BOOLEAN NOT NULL
+);
@@ -0,0 +1,5 @@
+-- Without the columns every row is a standing bid, so the voided ones go
+DELETE FROM bids WHERE voided_at IS NOT NULL;
+ALTER TABLE bids DROP COLUMN void_reason;
+ALTER TABLE bids DROP COLUMN voided_by;
+ALTER TABLE bids DROP COLUMN voided_at;
@@ -0,0 +1,4 @@
+-- Bids removed by support are kept, flagged with when, by whom and why they were voided
+ALTER TABLE bids ADD COLUMN voided_at TIMESTAMPTZ;
+ALTER TABLE bids ADD COLUMN voided_by TEXT;
+ALTER TABLE bids ADD COLUMN void_reason TEXT;
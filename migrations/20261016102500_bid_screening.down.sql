@@ -0,0 +1,2 @@
+DROP INDEX bids_screened_idx;
+ALTER TABLE auctions DROP COLUMN seller_ip_hash;
@@ -0,0 +1,5 @@
+-- Bids are screened against where the seller created the auction from
+ALTER TABLE auctions ADD COLUMN seller_ip_hash TEXT;
+
+-- Screening verdicts are kept in the bid metadata; flagged bids are looked up for review
+CREATE INDEX bids_screened_idx ON bids (auction_id) WHERE metadata ? 'screening';
@@ -0,0 +1,2 @@
+ALTER TABLE bids DROP COLUMN lot_id;
+ALTER TABLE auctions DROP COLUMN lots;
@@ -0,0 +1,5 @@
+-- Auctions may sell several lots in one listing, each with its own bids and winners
+ALTER TABLE auctions ADD COLUMN lots JSONB NOT NULL DEFAULT '[]';
+
+-- The lot bid on, for auctions with lots
+ALTER TABLE bids ADD COLUMN lot_id INT;
@@ -0,0 +1,2 @@
+-- Fees of an auction, when it does not use the ones configured for all auctions
+ALTER TABLE auctions ADD COLUMN fee_policy JSONB;
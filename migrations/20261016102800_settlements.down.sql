@@ -0,0 +1,2 @@
+DROP TABLE settlements;
+ALTER TABLE auctions DROP COLUMN tax_rate_bps;
@@ -0,0 +1,10 @@
+-- Tax on the fees of an auction, in basis points
+ALTER TABLE auctions ADD COLUMN tax_rate_bps BIGINT;
+
+-- Settlement of each closed auction, recorded once when it is first asked for, so that
+-- invoices read the same after fees or tax rates change
+CREATE TABLE settlements (
+    auction_id BIGINT PRIMARY KEY REFERENCES auctions(id) ON DELETE CASCADE,
+    settlement JSONB NOT NULL,
+    settled_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,2 @@
+-- How a Vickrey auction prices its winning bids, the second price as is when NULL
+ALTER TABLE auctions ADD COLUMN vickrey_pricing JSONB;
@@ -0,0 +1,70 @@
+-- Amounts go back to whole units of their currency, dropping any fraction of a unit. Divides
+-- every stored amount by the minor units of its currency. The functions are temporary to the
+-- session, which may be the one that applied the migration and still holds them.
+
+CREATE OR REPLACE FUNCTION pg_temp.minor_unit_factor(currency TEXT) RETURNS BIGINT AS $$
+    SELECT coalesce((SELECT power(10, minor_units)::BIGINT FROM currencies WHERE code = currency), 1)
+$$ LANGUAGE SQL STABLE;
+
+CREATE OR REPLACE FUNCTION pg_temp.unscale_amounts(doc JSONB) RETURNS JSONB AS $$
+    SELECT CASE
+        WHEN jsonb_typeof(doc) = 'object' AND doc ? 'value' AND doc ? 'currency' THEN
+            jsonb_set(doc, '{value}', to_jsonb((doc->>'value')::BIGINT / pg_temp.minor_unit_factor(doc->>'currency')))
+        WHEN jsonb_typeof(doc) = 'object' THEN
+            (SELECT coalesce(jsonb_object_agg(key, pg_temp.unscale_amounts(value)), '{}'::JSONB) FROM jsonb_each(doc))
+        WHEN jsonb_typeof(doc) = 'array' THEN
+            (SELECT coalesce(jsonb_agg(pg_temp.unscale_amounts(value) ORDER BY position), '[]'::JSONB)
+             FROM jsonb_array_elements(doc) WITH ORDINALITY AS element(value, position))
+        ELSE doc
+    END
+$$ LANGUAGE SQL STABLE;
+
+UPDATE bids SET amount_value = amount_value / pg_temp.minor_unit_factor(amount_currency);
+UPDATE max_bids SET amount_value = amount_value / pg_temp.minor_unit_factor(amount_currency);
+UPDATE auction_deposits SET amount_value = amount_value / pg_temp.minor_unit_factor(amount_currency);
+UPDATE auction_results SET amount_value = amount_value / pg_temp.minor_unit_factor(amount_currency)
+WHERE amount_value IS NOT NULL;
+UPDATE auction_summaries SET highest_amount_value = highest_amount_value / pg_temp.minor_unit_factor(highest_amount_currency)
+WHERE highest_amount_value IS NOT NULL;
+UPDATE rollups SET value = value / pg_temp.minor_unit_factor(currency) WHERE currency <> '';
+
+UPDATE auctions SET
+    reserve_price = reserve_price / pg_temp.minor_unit_factor(currency),
+    deposit = deposit / pg_temp.minor_unit_factor(currency)
+WHERE pg_temp.minor_unit_factor(currency) > 1;
+
+UPDATE auctions SET options = options
+    || jsonb_build_object(
+        'reserve_price', (options->>'reserve_price')::BIGINT / pg_temp.minor_unit_factor(currency),
+        'min_raise', (options->>'min_raise')::BIGINT / pg_temp.minor_unit_factor(currency),
+        'starting_price', coalesce((options->>'starting_price')::BIGINT, 0) / pg_temp.minor_unit_factor(currency),
+        'buy_now_price', (options->>'buy_now_price')::BIGINT / pg_temp.minor_unit_factor(currency),
+        'increments', coalesce((
+            SELECT jsonb_agg(jsonb_build_object(
+                'below', (step->>'below')::BIGINT / pg_temp.minor_unit_factor(currency),
+                'increment', (step->>'increment')::BIGINT / pg_temp.minor_unit_factor(currency)
+            ) ORDER BY position)
+            FROM jsonb_array_elements(options->'increments') WITH ORDINALITY AS step_at(step, position)
+        ), '[]'::JSONB)
+    )
+WHERE auction_type = 'TimedAscending' AND pg_temp.minor_unit_factor(currency) > 1;
+
+UPDATE auctions SET fee_policy = fee_policy
+    || jsonb_build_object(
+        'buyer_fee', coalesce((fee_policy->>'buyer_fee')::BIGINT, 0) / pg_temp.minor_unit_factor(currency),
+        'seller_fee', coalesce((fee_policy->>'seller_fee')::BIGINT, 0) / pg_temp.minor_unit_factor(currency)
+    )
+WHERE fee_policy IS NOT NULL AND pg_temp.minor_unit_factor(currency) > 1;
+
+UPDATE auction_events SET payload = jsonb_set(
+    payload,
+    '{amountValue}',
+    to_jsonb((payload->>'amountValue')::BIGINT / pg_temp.minor_unit_factor(payload->>'amountCurrency'))
+)
+WHERE payload ? 'amountValue';
+
+UPDATE settlements SET settlement = pg_temp.unscale_amounts(settlement);
+
+DROP FUNCTION pg_temp.unscale_amounts(JSONB);
+DROP FUNCTION IF EXISTS pg_temp.scale_amounts(JSONB);
+DROP FUNCTION pg_temp.minor_unit_factor(TEXT);
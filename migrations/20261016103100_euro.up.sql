@@ -0,0 +1,3 @@
+-- Euro, to show prices converted for bidders who think in euro
+INSERT INTO currencies (code, numeric_code, name, minor_units) VALUES
+    ('EUR', 978, 'Euro', 2);
@@ -0,0 +1,7 @@
+-- Users as first seen by the API, to show their display names rather than their ids
+CREATE TABLE users (
+    id VARCHAR(2000) PRIMARY KEY,
+    display_name TEXT,
+    role VARCHAR(20) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,2 @@
+DROP TABLE auction_stream_events;
+DROP TABLE auction_streams;
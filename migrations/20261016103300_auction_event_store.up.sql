@@ -0,0 +1,25 @@
+-- Event-sourced auctions, see EventSourcedAuctionRepository: each auction is rebuilt from the
+-- events of its stream, with the columns auctions are looked up and counted by on the stream
+CREATE TABLE auction_streams (
+    id BIGSERIAL PRIMARY KEY,
+    user_id VARCHAR(2000) NOT NULL,
+    external_reference VARCHAR(200),
+    status VARCHAR(20) NOT NULL,
+    bid_count BIGINT NOT NULL DEFAULT 0,
+    participants TEXT[] NOT NULL DEFAULT '{}',
+    version INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX idx_auction_streams_user_id ON auction_streams(user_id);
+CREATE INDEX idx_auction_streams_participants ON auction_streams USING GIN (participants);
+CREATE UNIQUE INDEX idx_auction_streams_user_id_external_reference
+    ON auction_streams(user_id, external_reference);
+
+CREATE TABLE auction_stream_events (
+    auction_id BIGINT NOT NULL REFERENCES auction_streams(id),
+    version INTEGER NOT NULL,
+    event_type TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (auction_id, version)
+);
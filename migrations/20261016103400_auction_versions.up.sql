@@ -0,0 +1,3 @@
+-- Bumped each time the auction is updated, so that an update made from an auction read before
+-- someone else changed it is refused, see AuctionRepository::update_auction
+ALTER TABLE auctions ADD COLUMN version INTEGER NOT NULL DEFAULT 0;
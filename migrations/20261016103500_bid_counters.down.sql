@@ -0,0 +1 @@
+ALTER TABLE auctions DROP COLUMN last_bid_id;
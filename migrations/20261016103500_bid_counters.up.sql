@@ -0,0 +1,4 @@
+-- The id of the last bid placed on each auction, so that bid ids are handed out by the stored
+-- auction rather than counted from the bids read, see Auction::set_last_bid_id
+ALTER TABLE auctions ADD COLUMN last_bid_id BIGINT NOT NULL DEFAULT 0;
+UPDATE auctions SET last_bid_id = coalesce((SELECT max(id) FROM bids WHERE bids.auction_id = auctions.id), 0);
@@ -0,0 +1,29 @@
+-- Archived auctions have left the tables the rows below refer to, put them back first
+DO $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM auctions_archive) THEN
+        RAISE EXCEPTION 'auctions_archive is not empty, restore the archived auctions before rolling back';
+    END IF;
+END
+$$;
+
+ALTER TABLE settlements ADD CONSTRAINT settlements_auction_id_fkey
+    FOREIGN KEY (auction_id) REFERENCES auctions(id) ON DELETE CASCADE;
+ALTER TABLE auction_results ADD CONSTRAINT auction_results_auction_id_fkey
+    FOREIGN KEY (auction_id) REFERENCES auctions(id) ON DELETE CASCADE;
+ALTER TABLE auction_deposits ADD CONSTRAINT auction_deposits_auction_id_fkey
+    FOREIGN KEY (auction_id) REFERENCES auctions(id) ON DELETE CASCADE;
+ALTER TABLE auction_registrations ADD CONSTRAINT auction_registrations_auction_id_fkey
+    FOREIGN KEY (auction_id) REFERENCES auctions(id) ON DELETE CASCADE;
+ALTER TABLE auction_audit_log ADD CONSTRAINT auction_audit_log_auction_id_fkey
+    FOREIGN KEY (auction_id) REFERENCES auctions(id) ON DELETE CASCADE;
+ALTER TABLE auction_summaries ADD CONSTRAINT auction_summaries_auction_id_fkey
+    FOREIGN KEY (auction_id) REFERENCES auctions(id);
+ALTER TABLE auction_events ADD CONSTRAINT auction_events_auction_id_fkey
+    FOREIGN KEY (auction_id) REFERENCES auctions(id);
+ALTER TABLE auctions ADD CONSTRAINT auctions_relisted_as_fkey
+    FOREIGN KEY (relisted_as) REFERENCES auctions(id);
+ALTER TABLE auctions ADD CONSTRAINT auctions_relisted_from_fkey
+    FOREIGN KEY (relisted_from) REFERENCES auctions(id);
+
+DROP TABLE auctions_archive;
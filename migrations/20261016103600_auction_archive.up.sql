@@ -0,0 +1,24 @@
+-- Auctions that ended long ago, moved out of the tables of running auctions as one document each,
+-- bids included, see PgAuctionArchive
+CREATE TABLE auctions_archive (
+    id BIGINT PRIMARY KEY,
+    -- Everyone the auction refers to, to anonymize users
+    participants TEXT[] NOT NULL,
+    ended_at TIMESTAMPTZ NOT NULL,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    auction JSONB NOT NULL
+);
+
+CREATE INDEX idx_auctions_archive_participants ON auctions_archive USING GIN (participants);
+
+-- What is kept about an auction besides its bids, maximum bids and pauses goes on referring to
+-- it once archived
+ALTER TABLE auctions DROP CONSTRAINT IF EXISTS auctions_relisted_from_fkey;
+ALTER TABLE auctions DROP CONSTRAINT IF EXISTS auctions_relisted_as_fkey;
+ALTER TABLE auction_events DROP CONSTRAINT IF EXISTS auction_events_auction_id_fkey;
+ALTER TABLE auction_summaries DROP CONSTRAINT IF EXISTS auction_summaries_auction_id_fkey;
+ALTER TABLE auction_audit_log DROP CONSTRAINT IF EXISTS auction_audit_log_auction_id_fkey;
+ALTER TABLE auction_registrations DROP CONSTRAINT IF EXISTS auction_registrations_auction_id_fkey;
+ALTER TABLE auction_deposits DROP CONSTRAINT IF EXISTS auction_deposits_auction_id_fkey;
+ALTER TABLE auction_results DROP CONSTRAINT IF EXISTS auction_results_auction_id_fkey;
+ALTER TABLE settlements DROP CONSTRAINT IF EXISTS settlements_auction_id_fkey;
@@ -0,0 +1,2 @@
+ALTER TABLE bids ADD CONSTRAINT bids_auction_id_fkey
+    FOREIGN KEY (auction_id) REFERENCES auctions(id);
@@ -0,0 +1,3 @@
+-- Bids referred to their auction twice, once without cascading, which kept archiving from
+-- removing an auction with bids, see PgAuctionArchive. fk_auction cascades and stays.
+ALTER TABLE bids DROP CONSTRAINT IF EXISTS bids_auction_id_fkey;
@@ -0,0 +1,2 @@
+CREATE INDEX idx_bids_auction_id ON bids(auction_id);
+DROP INDEX idx_bids_auction_id_id;
@@ -0,0 +1,4 @@
+-- Pages of bids are read latest first after a bid id, see get_bids_page. The primary key leads
+-- with the bid id, so it cannot serve them, and this index covers what idx_bids_auction_id did
+CREATE INDEX idx_bids_auction_id_id ON bids(auction_id, id DESC);
+DROP INDEX IF EXISTS idx_bids_auction_id;
@@ -0,0 +1,77 @@
+-- Auctions as documents: the aggregate is stored as JSON, with the columns auctions are looked up
+-- and counted by next to it
+CREATE TABLE auctions (
+    id BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    user_id VARCHAR(254) NOT NULL,
+    external_reference VARCHAR(200),
+    status VARCHAR(20) NOT NULL,
+    bid_count BIGINT NOT NULL DEFAULT 0,
+    auction LONGTEXT NOT NULL,
+    INDEX idx_auctions_user_id (user_id),
+    UNIQUE INDEX idx_auctions_user_id_external_reference (user_id, external_reference)
+) CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
+
+CREATE TABLE currencies (
+    code VARCHAR(3) NOT NULL PRIMARY KEY,
+    numeric_code INT NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    minor_units SMALLINT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE
+);
+
+INSERT INTO currencies (code, numeric_code, name, minor_units) VALUES
+    ('VAC', 1001, 'Virtual auction currency', 0),
+    ('SEK', 752, 'Swedish krona', 2),
+    ('DKK', 208, 'Danish krone', 2),
+    ('EUR', 978, 'Euro', 2);
+
+CREATE TABLE auction_registrations (
+    auction_id BIGINT NOT NULL,
+    user_id VARCHAR(254) NOT NULL,
+    status VARCHAR(20) NOT NULL,
+    registered_at DATETIME(6) NOT NULL,
+    decided_at DATETIME(6),
+    PRIMARY KEY (auction_id, user_id)
+) CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
+
+CREATE TABLE auction_deposits (
+    auction_id BIGINT NOT NULL,
+    user_id VARCHAR(254) NOT NULL,
+    amount_value BIGINT NOT NULL,
+    amount_currency VARCHAR(3) NOT NULL,
+    recorded_at DATETIME(6) NOT NULL,
+    PRIMARY KEY (auction_id, user_id)
+) CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
+
+CREATE TABLE auction_results (
+    auction_id BIGINT NOT NULL PRIMARY KEY,
+    winner VARCHAR(254),
+    amount_value BIGINT,
+    amount_currency VARCHAR(3),
+    closed_at DATETIME(6) NOT NULL,
+    reserve_met BOOLEAN NOT NULL
+) CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
+
+-- Settlements as JSON, as they were first worked out
+CREATE TABLE settlements (
+    auction_id BIGINT NOT NULL PRIMARY KEY,
+    settlement LONGTEXT NOT NULL,
+    settled_at DATETIME(6) NOT NULL
+) CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
+
+CREATE TABLE auction_audit_log (
+    id BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    auction_id BIGINT NOT NULL,
+    action VARCHAR(50) NOT NULL,
+    user_id VARCHAR(254) NOT NULL,
+    reason TEXT,
+    at DATETIME(6) NOT NULL,
+    INDEX idx_auction_audit_log_auction_id (auction_id)
+) CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
+
+CREATE TABLE users (
+    id VARCHAR(254) NOT NULL PRIMARY KEY,
+    display_name VARCHAR(100),
+    role VARCHAR(20) NOT NULL,
+    created_at DATETIME(6) NOT NULL
+) CHARACTER SET utf8mb4 COLLATE utf8mb4_bin;
@@ -0,0 +1,80 @@
+-- Auctions as documents: the aggregate is stored as JSON, with the columns auctions are looked up
+-- and counted by next to it
+CREATE TABLE auctions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id TEXT NOT NULL,
+    external_reference TEXT,
+    status TEXT NOT NULL,
+    bid_count INTEGER NOT NULL DEFAULT 0,
+    auction TEXT NOT NULL
+);
+
+CREATE INDEX idx_auctions_user_id ON auctions(user_id);
+CREATE UNIQUE INDEX idx_auctions_user_id_external_reference
+    ON auctions(user_id, external_reference);
+
+CREATE TABLE currencies (
+    code TEXT PRIMARY KEY,
+    numeric_code INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    minor_units INTEGER NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE
+);
+
+INSERT INTO currencies (code, numeric_code, name, minor_units) VALUES
+    ('VAC', 1001, 'Virtual auction currency', 0),
+    ('SEK', 752, 'Swedish krona', 2),
+    ('DKK', 208, 'Danish krone', 2),
+    ('EUR', 978, 'Euro', 2);
+
+CREATE TABLE auction_registrations (
+    auction_id INTEGER NOT NULL,
+    user_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    registered_at TEXT NOT NULL,
+    decided_at TEXT,
+    PRIMARY KEY (auction_id, user_id)
+);
+
+CREATE TABLE auction_deposits (
+    auction_id INTEGER NOT NULL,
+    user_id TEXT NOT NULL,
+    amount_value INTEGER NOT NULL,
+    amount_currency TEXT NOT NULL,
+    recorded_at TEXT NOT NULL,
+    PRIMARY KEY (auction_id, user_id)
+);
+
+CREATE TABLE auction_results (
+    auction_id INTEGER PRIMARY KEY,
+    winner TEXT,
+    amount_value INTEGER,
+    amount_currency TEXT,
+    closed_at TEXT NOT NULL,
+    reserve_met BOOLEAN NOT NULL
+);
+
+-- Settlements as JSON, as they were first worked out
+CREATE TABLE settlements (
+    auction_id INTEGER PRIMARY KEY,
+    settlement TEXT NOT NULL,
+    settled_at TEXT NOT NULL
+);
+
+CREATE TABLE auction_audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    auction_id INTEGER NOT NULL,
+    action TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    reason TEXT,
+    at TEXT NOT NULL
+);
+
+CREATE INDEX idx_auction_audit_log_auction_id ON auction_audit_log(auction_id);
+
+CREATE TABLE users (
+    id TEXT PRIMARY KEY,
+    display_name TEXT,
+    role TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
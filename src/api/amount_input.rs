@@ -0,0 +1,137 @@
+use serde::{de, Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::domain::models::{Amount, CurrencyCode, Error};
+
+/// The ways clients may write an amount in a payload, in whole units with up to as many decimals
+/// as the currency has, see [`Amount`].
+pub const ACCEPTED_FORMATS: &str =
+    r#""SEK100.50", "sek100.50", "SEK 100.50", {"value": 100.50, "currency": "SEK"} or {"value": "100.50", "currency": "SEK"}"#;
+
+const CURRENCIES: &[CurrencyCode] = &[CurrencyCode::None, CurrencyCode::VAC, CurrencyCode::SEK, CurrencyCode::DKK, CurrencyCode::EUR];
+
+/// Reads a currency code regardless of case and surrounding whitespace.
+pub fn parse_currency(code: &str) -> Result<CurrencyCode, String> {
+    code.trim().to_ascii_uppercase().parse().map_err(|_| {
+        let known: Vec<&str> = CURRENCIES.iter().map(|c| c.code()).collect();
+        format!("Unknown currency code {}, expected one of {}", code.trim(), known.join(", "))
+    })
+}
+
+/// Reads an amount such as `SEK100.50`, `sek100` or `SEK 100`.
+pub fn parse_amount(amount: &str) -> Result<Amount, String> {
+    let amount = amount.trim();
+    let split = amount
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(amount.len());
+    let (currency, value) = amount.split_at(split);
+    if currency.is_empty() {
+        return Err(format!("Missing currency in amount {:?}, expected {}", amount, ACCEPTED_FORMATS));
+    }
+    let currency = parse_currency(currency)?;
+    parse_value(value.trim(), currency, amount)
+}
+
+fn parse_value(value: &str, currency: CurrencyCode, amount: &str) -> Result<Amount, String> {
+    Amount::parse_value(value, currency).map_err(|e| match e {
+        Error::InvalidAmount(reason) => format!("{} in amount {:?}, expected {}", reason, amount, ACCEPTED_FORMATS),
+        other => other.to_string(),
+    })
+}
+
+fn amount_from_value(value: Value) -> Result<Amount, String> {
+    match value {
+        Value::String(amount) => parse_amount(&amount),
+        Value::Object(mut fields) => {
+            let currency = match fields.remove("currency") {
+                Some(Value::String(currency)) => parse_currency(&currency)?,
+                _ => return Err(format!("Amount is missing a currency, expected {}", ACCEPTED_FORMATS)),
+            };
+            // Whole units like the strings, so that {"value": 100, "currency": "SEK"} is SEK100
+            match fields.remove("value") {
+                Some(Value::Number(value)) => {
+                    let value = value.to_string();
+                    parse_value(&value, currency, &format!("{}{}", currency, value))
+                }
+                // As written in responses by amount_output
+                Some(Value::String(value)) => parse_value(value.trim(), currency, &format!("{}{}", currency, value)),
+                _ => Err(format!("Amount needs a non-negative value, expected {}", ACCEPTED_FORMATS)),
+            }
+        }
+        _ => Err(format!("Invalid amount {}, expected {}", value, ACCEPTED_FORMATS)),
+    }
+}
+
+/// Deserializes an amount in any of the [`ACCEPTED_FORMATS`], for payload fields sent by clients:
+/// `#[serde(deserialize_with = "amount_input::deserialize")]`.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Amount, D::Error> {
+    amount_from_value(Value::deserialize(deserializer)?).map_err(de::Error::custom)
+}
+
+/// Deserializes a currency code regardless of case.
+pub fn deserialize_currency<'de, D: Deserializer<'de>>(deserializer: D) -> Result<CurrencyCode, D::Error> {
+    parse_currency(&String::deserialize(deserializer)?).map_err(de::Error::custom)
+}
+
+#[cfg(test)]
+mod amount_input_tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize)]
+    struct Payload {
+        #[serde(deserialize_with = "deserialize")]
+        amount: Amount,
+    }
+
+    fn read(amount: Value) -> Result<Amount, String> {
+        serde_json::from_value::<Payload>(json!({ "amount": amount }))
+            .map(|payload| payload.amount)
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_reads_every_accepted_format() {
+        let expected = Amount::new(10050, CurrencyCode::SEK);
+        for amount in [
+            json!("SEK100.50"),
+            json!("sek100.5"),
+            json!("SEK 100.50"),
+            json!(" Sek 100.50 "),
+            json!({ "value": 100.50, "currency": "SEK" }),
+            json!({ "value": 100.5, "currency": "sek" }),
+            json!({ "value": "100.50", "currency": "SEK" }),
+        ] {
+            assert_eq!(read(amount.clone()), Ok(expected.clone()), "{}", amount);
+        }
+    }
+
+    #[test]
+    fn test_lists_accepted_formats_when_reading_fails() {
+        for amount in [json!("100"), json!("SEK"), json!("SEK 10.505"), json!(100), json!({ "value": 100 })] {
+            let error = read(amount.clone()).unwrap_err();
+            assert!(error.contains(ACCEPTED_FORMATS), "{}: {}", amount, error);
+        }
+    }
+
+    #[test]
+    fn test_names_unknown_currencies() {
+        let error = read(json!("xyz100")).unwrap_err();
+        assert!(error.contains("Unknown currency code xyz, expected one of XXX, VAC, SEK, DKK, EUR"), "{}", error);
+    }
+
+    #[test]
+    fn test_rejects_negative_values() {
+        assert!(read(json!("SEK-100")).is_err());
+        assert!(read(json!({ "value": -100, "currency": "SEK" })).is_err());
+    }
+
+    #[test]
+    fn test_reads_objects_in_whole_units_like_strings() {
+        let expected = Ok(Amount::new(10000, CurrencyCode::SEK));
+        assert_eq!(read(json!({ "value": 100, "currency": "SEK" })), expected);
+        assert_eq!(read(json!("SEK 100")), expected);
+        assert!(read(json!({ "value": 100.505, "currency": "SEK" })).is_err());
+        assert!(read(json!({ "value": 10.5, "currency": "VAC" })).is_err());
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::{Borrow, Cow};
+
+use crate::api::amount_input;
+use crate::domain::models::{Amount, CurrencyCode};
+use crate::infrastructure::web::CURRENT_AMOUNT_FORMAT;
+use crate::infrastructure::AmountFormat;
+
+/// How amounts are written in the response being handled, set by the
+/// [`crate::infrastructure::web::amount_format`] middleware. Objects outside of a request.
+pub fn format() -> AmountFormat {
+    CURRENT_AMOUNT_FORMAT.try_with(|format| *format).unwrap_or_default()
+}
+
+/// Writes an amount in the configured [`AmountFormat`] and reads it in any of
+/// [`amount_input::ACCEPTED_FORMATS`], for response fields:
+/// `#[serde(with = "amount_output")]`.
+pub fn serialize<A: Borrow<Amount>, S: Serializer>(amount: &A, serializer: S) -> Result<S::Ok, S::Error> {
+    write(amount.borrow(), format(), serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Amount, D::Error> {
+    amount_input::deserialize(deserializer)
+}
+
+fn write<S: Serializer>(amount: &Amount, format: AmountFormat, serializer: S) -> Result<S::Ok, S::Error> {
+    match format {
+        AmountFormat::Object => {
+            // Whole units like the payloads, so that clients can send back what they read, as a
+            // decimal string since a JSON number would be read as a float by most clients
+            AmountObject { value: amount.format_value(), currency: amount.currency() }.serialize(serializer)
+        }
+        AmountFormat::Compact => serializer.collect_str(amount),
+    }
+}
+
+#[derive(Serialize)]
+struct AmountObject {
+    value: String,
+    currency: CurrencyCode,
+}
+
+/// For optional amounts: `#[serde(default, with = "amount_output::option")]`.
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(amount: &Option<Amount>, serializer: S) -> Result<S::Ok, S::Error> {
+        match amount {
+            Some(amount) => super::serialize(amount, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Amount>, D::Error> {
+        #[derive(Deserialize)]
+        struct Wrapped(#[serde(deserialize_with = "amount_input::deserialize")] Amount);
+
+        Ok(Option::<Wrapped>::deserialize(deserializer)?.map(|Wrapped(amount)| amount))
+    }
+}
+
+/// For amounts borrowed from the auction: `#[serde(with = "amount_output::cow")]`.
+pub mod cow {
+    use super::*;
+
+    pub use super::serialize;
+
+    pub fn deserialize<'de, 'a, D: Deserializer<'de>>(deserializer: D) -> Result<Cow<'a, Amount>, D::Error> {
+        amount_input::deserialize(deserializer).map(Cow::Owned)
+    }
+}
+
+#[cfg(test)]
+mod amount_output_tests {
+    use super::*;
+    use crate::domain::models::CurrencyCode;
+    use serde_json::json;
+
+    fn written(format: AmountFormat) -> serde_json::Value {
+        write(&Amount::new(10050, CurrencyCode::SEK), format, serde_json::value::Serializer).unwrap()
+    }
+
+    #[test]
+    fn test_writes_the_configured_format() {
+        assert_eq!(written(AmountFormat::Object), json!({ "value": "100.50", "currency": "SEK" }));
+        assert_eq!(written(AmountFormat::Compact), json!("SEK100.50"));
+    }
+
+    #[test]
+    fn test_reads_back_what_it_writes() {
+        #[derive(Deserialize)]
+        struct Model {
+            #[serde(default, with = "option")]
+            price: Option<Amount>,
+        }
+
+        for format in [AmountFormat::Object, AmountFormat::Compact] {
+            let model: Model = serde_json::from_value(json!({ "price": written(format) })).unwrap();
+            assert_eq!(model.price, Some(Amount::new(10050, CurrencyCode::SEK)));
+        }
+        let model: Model = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(model.price, None);
+    }
+
+    #[test]
+    fn test_writes_values_exactly_from_the_minor_units() {
+        #[derive(Deserialize)]
+        struct Model {
+            #[serde(with = "super")]
+            price: Amount,
+        }
+
+        for (amount, value) in [
+            (Amount::new(30, CurrencyCode::SEK), "0.30"),
+            (Amount::new(10, CurrencyCode::VAC), "10"),
+            (Amount::new(i64::MAX, CurrencyCode::SEK), "92233720368547758.07"),
+        ] {
+            let currency = amount.currency();
+            let written = write(&amount, AmountFormat::Object, serde_json::value::Serializer).unwrap();
+            assert_eq!(written, json!({ "value": value, "currency": currency }));
+            let model: Model = serde_json::from_value(json!({ "price": written })).unwrap();
+            assert_eq!(model.price, amount);
+        }
+    }
+}
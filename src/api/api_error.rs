@@ -0,0 +1,140 @@
+use std::fmt;
+
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError};
+
+use crate::api::models::{map_error, ErrorModel};
+use crate::domain::models::{AuctionRemoval, Error, Errors, RemovalKind};
+
+/// An error as answered by the API, so that handlers can use `?`. The status follows from the
+/// kind of error and the body lists what went wrong, see [`ErrorModel`].
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    errors: Vec<ErrorModel>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &str, message: impl Into<String>) -> Self {
+        ApiError {
+            status,
+            errors: vec![ErrorModel::new(code, message)],
+        }
+    }
+
+    /// A request the API itself cannot make sense of, such as a malformed query.
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "INVALID_REQUEST", message)
+    }
+
+    pub fn not_found() -> Self {
+        Self::new(StatusCode::NOT_FOUND, "NOT_FOUND", "Not found")
+    }
+
+    /// An auction that existed but was taken down, as opposed to one that never did.
+    pub fn gone(removal: AuctionRemoval) -> Self {
+        let mut error = match removal.kind {
+            RemovalKind::Voided => ErrorModel::new("AUCTION_VOIDED", "The auction was voided"),
+            RemovalKind::Cancelled => ErrorModel::new("AUCTION_CANCELLED", "The auction was cancelled"),
+        };
+        error.removed_at = Some(removal.at);
+        error.reason = removal.reason;
+        ApiError {
+            status: StatusCode::GONE,
+            errors: vec![error],
+        }
+    }
+}
+
+/// Rules about what was asked for that mean it is not there.
+const UNKNOWN: Errors = Errors::UnknownAuction
+    .union(Errors::UnknownLot)
+    .union(Errors::UnknownBid);
+
+fn status_of(error: &Error) -> StatusCode {
+    match error {
+        Error::Validation(errors) if UNKNOWN.contains(*errors) => StatusCode::NOT_FOUND,
+        Error::Validation(_)
+        | Error::Violations(_)
+        | Error::InvalidAmount(_)
+        | Error::CurrencyMismatch(_, _)
+        | Error::InvalidUser(_) => StatusCode::BAD_REQUEST,
+        Error::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+        Error::Forbidden(_) => StatusCode::FORBIDDEN,
+        Error::NotFound(_) => StatusCode::NOT_FOUND,
+        Error::Conflict(_) => StatusCode::CONFLICT,
+        Error::Domain(_) | Error::Repository(_) | Error::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        let status = status_of(&error);
+        if status.is_server_error() {
+            log::error!("Internal server error: {:?}", error);
+        }
+        ApiError {
+            status,
+            errors: map_error(&error),
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages: Vec<&str> = self.errors.iter().map(|error| error.message.as_str()).collect();
+        write!(f, "{}", messages.join(", "))
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        self.status
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status).json(&self.errors)
+    }
+}
+
+#[cfg(test)]
+mod api_error_tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_errors_map_to_statuses() {
+        let status = |error: Error| ApiError::from(error).status_code();
+        assert_eq!(status(Error::Validation(Errors::UnknownAuction)), StatusCode::NOT_FOUND);
+        assert_eq!(status(Error::Validation(Errors::UnknownLot)), StatusCode::NOT_FOUND);
+        assert_eq!(status(Error::Validation(Errors::AuctionHasEnded)), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            status(Error::Validation(Errors::UnknownAuction | Errors::AuctionHasEnded)),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(status(Error::Unauthorized("who?".to_string())), StatusCode::UNAUTHORIZED);
+        assert_eq!(status(Error::Forbidden("no".to_string())), StatusCode::FORBIDDEN);
+        assert_eq!(status(Error::Conflict("changed".to_string())), StatusCode::CONFLICT);
+        assert_eq!(status(Error::Repository("down".to_string())), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn test_body_lists_the_coded_errors() {
+        let error = ApiError::from(Error::Validation(Errors::AuctionHasEnded | Errors::InvalidQuantity));
+        let codes: Vec<&str> = error.errors.iter().map(|error| error.code.as_str()).collect();
+        assert_eq!(codes, vec!["AUCTION_HAS_ENDED", "INVALID_QUANTITY"]);
+    }
+
+    #[test]
+    fn test_removed_auctions_are_gone_with_when_and_why() {
+        let at = chrono::DateTime::parse_from_rfc3339("2026-10-16T10:00:00Z").unwrap().with_timezone(&chrono::Utc);
+        let error = ApiError::gone(AuctionRemoval { kind: RemovalKind::Voided, at, reason: Some("fraud".to_string()) });
+        assert_eq!(error.status_code(), StatusCode::GONE);
+        assert_eq!(error.errors[0].code, "AUCTION_VOIDED");
+        assert_eq!(error.errors[0].removed_at, Some(at));
+        assert_eq!(error.errors[0].reason.as_deref(), Some("fraud"));
+
+        let error = ApiError::gone(AuctionRemoval { kind: RemovalKind::Cancelled, at, reason: None });
+        assert_eq!(error.status_code(), StatusCode::GONE);
+        assert_eq!(error.errors[0].code, "AUCTION_CANCELLED");
+    }
+}
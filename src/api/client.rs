@@ -0,0 +1,261 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::api::models::{
+    AuctionDetailModel, AuctionSummaryModel, BidModel, CreateAuctionModel, CreateBidModel, ErrorModel,
+};
+use crate::domain::models::User;
+use crate::infrastructure::jwt_payload_handling::{encode_jwt_payload, X_JWT_PAYLOAD};
+
+/// What went wrong calling the API: it could not be reached, or it answered with errors.
+#[derive(Debug)]
+pub enum ClientError {
+    Http(reqwest::Error),
+    Api(StatusCode, Vec<ErrorModel>),
+    /// A page that does not have the expected fields
+    InvalidPage(serde_json::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Http(e) => write!(f, "{}", e),
+            ClientError::Api(status, errors) => {
+                let codes: Vec<&str> = errors.iter().map(|error| error.code.as_str()).collect();
+                write!(f, "{}: {}", status, codes.join(", "))
+            }
+            ClientError::InvalidPage(e) => write!(f, "invalid page: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(error: reqwest::Error) -> Self {
+        ClientError::Http(error)
+    }
+}
+
+/// Typed calls to the API, for scripts and bots, see `examples/`. Calls are anonymous until
+/// made [`ApiClient::as_user`].
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    http: reqwest::Client,
+    base_url: String,
+    token: Option<String>,
+}
+
+impl ApiClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        ApiClient {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: None,
+        }
+    }
+
+    /// A client calling as `user`, with the header the gateway sends once it has verified them.
+    /// Only for servers that are not behind a gateway, such as local ones.
+    pub fn as_user(&self, user: &User) -> Self {
+        ApiClient {
+            token: Some(encode_jwt_payload(user)),
+            ..self.clone()
+        }
+    }
+
+    pub async fn get_auction(&self, auction_id: i64) -> Result<AuctionDetailModel<'static>, ClientError> {
+        read(self.request(self.http.get(self.url(&format!("/auctions/{}", auction_id)))).await?).await
+    }
+
+    pub async fn create_auction(&self, model: &CreateAuctionModel) -> Result<AuctionDetailModel<'static>, ClientError> {
+        read(self.request(self.http.post(self.url("/auction")).json(model)).await?).await
+    }
+
+    pub async fn place_bid(&self, auction_id: i64, model: &CreateBidModel) -> Result<(), ClientError> {
+        let url = self.url(&format!("/auctions/{}/bids", auction_id));
+        check(self.request(self.http.post(url).json(model)).await?).await.map(|_| ())
+    }
+
+    /// The published auctions, `page_size` at a time.
+    pub fn auctions(&self, page_size: i64) -> Pages<AuctionSummaryModel<'static>> {
+        Pages::new(self.clone(), "/auctions".to_string(), PageCursor::OFFSET, Some(page_size))
+    }
+
+    /// The bids on an auction, latest first, `page_size` at a time.
+    pub fn bids(&self, auction_id: i64, page_size: i64) -> Pages<BidModel<'static>> {
+        Pages::new(self.clone(), format!("/auctions/{}/bids", auction_id), PageCursor::AFTER, Some(page_size))
+    }
+
+    /// The bids on an auction as they are placed, until it ends. Waits for the server to have
+    /// new bids before reading on.
+    pub fn placed_bids(&self, auction_id: i64) -> Pages<BidModel<'static>> {
+        Pages::new(self.clone(), format!("/auctions/{}/bids/poll", auction_id), PageCursor::SINCE, None)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    async fn request(&self, request: RequestBuilder) -> Result<Response, ClientError> {
+        let request = match &self.token {
+            Some(token) => request.header(X_JWT_PAYLOAD, token),
+            None => request,
+        };
+        Ok(request.send().await?)
+    }
+}
+
+async fn read<T: DeserializeOwned>(response: Response) -> Result<T, ClientError> {
+    Ok(check(response).await?.json().await?)
+}
+
+async fn check(response: Response) -> Result<Response, ClientError> {
+    let status = response.status();
+    if status.is_success() {
+        Ok(response)
+    } else {
+        Err(ClientError::Api(status, response.json().await.unwrap_or_default()))
+    }
+}
+
+/// How a paged endpoint says where its next page starts.
+#[derive(Debug, Clone, Copy)]
+struct PageCursor {
+    /// The field listing the items of a page
+    items: &'static str,
+    /// The field with the cursor of the next page, empty on the last page
+    next: &'static str,
+    /// The query parameter to send the cursor back in
+    param: &'static str,
+    /// The field that is true on the last page, for endpoints that always have a next cursor
+    last: Option<&'static str>,
+}
+
+impl PageCursor {
+    const SINCE: PageCursor = PageCursor { items: "bids", next: "cursor", param: "since", last: Some("hasEnded") };
+    const OFFSET: PageCursor = PageCursor { items: "auctions", next: "nextOffset", param: "offset", last: None };
+    const AFTER: PageCursor = PageCursor { items: "bids", next: "nextAfter", param: "after", last: None };
+}
+
+/// The items of a paged endpoint one at a time, reading the next page when the previous one has
+/// been gone through.
+pub struct Pages<T> {
+    client: ApiClient,
+    path: String,
+    cursor: PageCursor,
+    page_size: Option<i64>,
+    next: Option<i64>,
+    done: bool,
+    buffer: VecDeque<T>,
+}
+
+impl<T: DeserializeOwned> Pages<T> {
+    fn new(client: ApiClient, path: String, cursor: PageCursor, page_size: Option<i64>) -> Self {
+        Pages {
+            client,
+            path,
+            cursor,
+            page_size,
+            next: None,
+            done: false,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    /// The next item, or `None` after the last one. Stops after an error.
+    pub async fn next(&mut self) -> Option<Result<T, ClientError>> {
+        while self.buffer.is_empty() && !self.done {
+            if let Err(e) = self.read_page().await {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+        self.buffer.pop_front().map(Ok)
+    }
+
+    /// Every remaining item.
+    pub async fn collect(mut self) -> Result<Vec<T>, ClientError> {
+        let mut items = Vec::new();
+        while let Some(item) = self.next().await {
+            items.push(item?);
+        }
+        Ok(items)
+    }
+
+    async fn read_page(&mut self) -> Result<(), ClientError> {
+        let mut query: Vec<(&str, i64)> = self.page_size.map(|page_size| ("limit", page_size)).into_iter().collect();
+        if let Some(next) = self.next {
+            query.push((self.cursor.param, next));
+        }
+        let request = self.client.http.get(self.client.url(&self.path)).query(&query);
+        let mut page: Value = read(self.client.request(request).await?).await?;
+        let items: Vec<T> = serde_json::from_value(page[self.cursor.items].take())
+            .map_err(ClientError::InvalidPage)?;
+        self.next = page[self.cursor.next].as_i64();
+        let last = self.cursor.last.and_then(|last| page[last].as_bool()).unwrap_or(false);
+        self.done = self.next.is_none() || last;
+        self.buffer.extend(items);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod client_tests {
+    use super::*;
+    use actix_web::{web, App, HttpResponse, HttpServer};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    async fn bids_poll(query: web::Query<HashMap<String, i64>>) -> HttpResponse {
+        let bid = |id: i64| {
+            let amount = json!({ "value": id * 10, "currency": "SEK" });
+            json!({ "id": id, "amount": amount, "bidder": null, "at": [id, 0], "quantity": 1 })
+        };
+        match query.get("since") {
+            None => HttpResponse::Ok().json(json!({ "bids": [bid(1), bid(2)], "cursor": 2, "hasEnded": false })),
+            Some(_) => HttpResponse::Ok().json(json!({ "bids": [bid(3)], "cursor": 3, "hasEnded": true })),
+        }
+    }
+
+    async fn bids_page(query: web::Query<HashMap<String, i64>>) -> HttpResponse {
+        let bid = |id: i64| json!({ "id": id, "amount": "SEK10", "bidder": null, "at": [id, 0], "quantity": 1 });
+        match query.get("after") {
+            None => HttpResponse::Ok().json(json!({ "bids": [bid(3), bid(2)], "nextAfter": 2 })),
+            Some(_) => HttpResponse::Ok().json(json!({ "bids": [bid(1)], "nextAfter": null })),
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_pages_read_on_until_the_last_page() {
+        let server = HttpServer::new(|| App::new().route("/auctions/1/bids/poll", web::get().to(bids_poll)))
+            .workers(1)
+            .bind("127.0.0.1:0")
+            .unwrap();
+        let addr = server.addrs()[0];
+        actix_web::rt::spawn(server.run());
+
+        let client = ApiClient::new(format!("http://{}/", addr));
+        let bids = client.placed_bids(1).collect().await.unwrap();
+        let amounts: Vec<String> = bids.iter().map(|bid| bid.amount.to_string()).collect();
+        assert_eq!(amounts, vec!["SEK10.00", "SEK20.00", "SEK30.00"]);
+    }
+
+    #[actix_web::test]
+    async fn test_pages_follow_the_next_cursor() {
+        let server = HttpServer::new(|| App::new().route("/auctions/1/bids", web::get().to(bids_page)))
+            .workers(1)
+            .bind("127.0.0.1:0")
+            .unwrap();
+        let addr = server.addrs()[0];
+        actix_web::rt::spawn(server.run());
+
+        let client = ApiClient::new(format!("http://{}/", addr));
+        let ids: Vec<i64> = client.bids(1, 2).collect().await.unwrap().iter().map(|bid| bid.id).collect();
+        assert_eq!(ids, vec![3, 2, 1]);
+    }
+}
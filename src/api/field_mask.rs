@@ -0,0 +1,208 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::api::realtime::auction_snapshot;
+use crate::domain::models::Auction;
+
+/// Fields of [`crate::api::models::AuctionSummaryModel`].
+pub const SUMMARY_FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "startsAt",
+    "expiry",
+    "seller",
+    "currency",
+    "bids",
+    "bidCount",
+    "currentPrice",
+    "nextMinimumBid",
+    "displayPrice",
+    "quantity",
+    "status",
+    "hasEnded",
+    "requiresRegistration",
+    "deposit",
+    "buyNowPrice",
+    "externalReference",
+];
+
+/// Fields of [`crate::api::models::AuctionDetailModel`].
+pub const DETAIL_FIELDS: &[&str] = &[
+    "id",
+    "startsAt",
+    "title",
+    "description",
+    "expiry",
+    "seller",
+    "currency",
+    "bids",
+    "bidsNextAfter",
+    "bidCount",
+    "currentPrice",
+    "nextMinimumBid",
+    "displayPrice",
+    "price",
+    "winner",
+    "quantity",
+    "winners",
+    "charges",
+    "result",
+    "status",
+    "hasEnded",
+    "requiresRegistration",
+    "deposit",
+    "relist",
+    "relistedFrom",
+    "relistedAs",
+    "voidedAt",
+    "cancelledAt",
+    "cancelReason",
+    "extendedAt",
+    "extensionReason",
+    "pausedAt",
+    "externalReference",
+    "buyNowPrice",
+    "startingPrice",
+    "lots",
+];
+
+/// Fields only found in [`crate::api::models::AuctionSnapshotModel`], for either model.
+const SNAPSHOT_FIELDS: &[&str] = &["leader", "endsAt"];
+
+/// Summary fields worked out from the bids, which summaries read without them leave out.
+const BID_FIELDS: &[&str] = &["bids", "nextMinimumBid", "buyNowPrice"];
+
+/// The auction fields a client asked for with `?fields=id,title,currentPrice,endsAt`, so that
+/// clients on slow connections do not download the bid history of every auction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldMask {
+    fields: Vec<String>,
+    model_fields: &'static [&'static str],
+}
+
+impl FieldMask {
+    /// Reads a comma separated list of fields of the model with `model_fields` or of the snapshot,
+    /// rejecting unknown ones.
+    pub fn parse(fields: &str, model_fields: &'static [&'static str]) -> Result<Self, String> {
+        let mut mask = Vec::new();
+        for field in fields.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            if !model_fields.contains(&field) && !SNAPSHOT_FIELDS.contains(&field) {
+                return Err(format!(
+                    "Unknown field {}, expected some of {}",
+                    field,
+                    [model_fields, SNAPSHOT_FIELDS].concat().join(", ")
+                ));
+            }
+            if !mask.iter().any(|f| f == field) {
+                mask.push(field.to_string());
+            }
+        }
+        if mask.is_empty() {
+            return Err("At least one field must be requested".to_string());
+        }
+        Ok(Self { fields: mask, model_fields })
+    }
+
+    fn wants_any(&self, fields: &[&str]) -> bool {
+        self.fields.iter().any(|f| fields.contains(&f.as_str()))
+    }
+
+    /// Whether any field asked for takes the whole auction rather than a summary of it, see
+    /// [`FieldMask::apply_to_summary`].
+    pub fn needs_auction(&self) -> bool {
+        self.needs_snapshot() || self.wants_any(BID_FIELDS)
+    }
+
+    /// Whether any field asked for is only in the snapshot, which takes the bids to work out.
+    pub fn needs_snapshot(&self) -> bool {
+        self.wants_any(SNAPSHOT_FIELDS)
+    }
+
+    /// The requested fields of the model of the auction, or of its snapshot.
+    pub fn apply(&self, model: &impl Serialize, auction: &Auction, now: DateTime<Utc>) -> Value {
+        let mut shown = self.shown(model);
+        if self.wants_any(SNAPSHOT_FIELDS) {
+            if let Ok(Value::Object(snapshot)) = serde_json::to_value(auction_snapshot(auction, now)) {
+                shown.extend(snapshot.into_iter().filter(|(field, _)| SNAPSHOT_FIELDS.contains(&field.as_str())));
+            }
+        }
+        shown.retain(|field, _| self.fields.contains(field));
+        Value::Object(shown)
+    }
+
+    /// The requested fields of a model made from a summary, for masks that do not need the
+    /// auction.
+    pub fn apply_to_summary(&self, model: &impl Serialize) -> Value {
+        let mut shown = self.shown(model);
+        shown.retain(|field, _| self.fields.contains(field));
+        Value::Object(shown)
+    }
+
+    fn shown(&self, model: &impl Serialize) -> Map<String, Value> {
+        match serde_json::to_value(model) {
+            Ok(Value::Object(model)) if self.wants_any(self.model_fields) => model,
+            _ => Map::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod field_mask_tests {
+    use super::*;
+    use crate::api::handlers::auctions::{map_auction_to_detail_model, map_auction_to_summary_model};
+    use crate::domain::models::{Amount, CurrencyCode};
+    use crate::domain::test_support::{auction, lamp, starts_at};
+    use chrono::Duration;
+
+    #[test]
+    fn test_shows_only_requested_fields() {
+        let mask = FieldMask::parse("id, title,currentPrice,endsAt", DETAIL_FIELDS).unwrap();
+        let auction = auction(lamp());
+
+        let shown = mask.apply(&map_auction_to_detail_model(&auction, starts_at()), &auction, starts_at());
+
+        assert_eq!(
+            shown,
+            serde_json::json!({
+                "id": 1,
+                "title": "Lamp",
+                "currentPrice": null,
+                "endsAt": starts_at() + Duration::days(7),
+            })
+        );
+    }
+
+    #[test]
+    fn test_summaries_serve_fields_not_worked_out_from_the_bids() {
+        assert!(!FieldMask::parse("id,title,currentPrice,bidCount", SUMMARY_FIELDS).unwrap().needs_auction());
+        assert!(FieldMask::parse("id,nextMinimumBid", SUMMARY_FIELDS).unwrap().needs_auction());
+        assert!(FieldMask::parse("id,endsAt", SUMMARY_FIELDS).unwrap().needs_auction());
+    }
+
+    #[test]
+    fn test_rejects_unknown_and_missing_fields() {
+        assert!(FieldMask::parse("id,secret", DETAIL_FIELDS).is_err());
+        assert!(FieldMask::parse("id,winners", SUMMARY_FIELDS).is_err());
+        assert!(FieldMask::parse(" , ", SUMMARY_FIELDS).is_err());
+    }
+
+    fn assert_knows_every_field(model: Value, model_fields: &[&str]) {
+        let fields: Vec<&String> = model.as_object().unwrap().keys().collect();
+        assert_eq!(fields.len(), model_fields.len());
+        assert!(fields.iter().all(|field| model_fields.contains(&field.as_str())));
+    }
+
+    #[test]
+    fn test_knows_every_model_field() {
+        let auction = auction(lamp());
+
+        // The display price is only shown when asked for, so ask for it
+        let mut detail = map_auction_to_detail_model(&auction, starts_at());
+        detail.display_price = Some(Amount::zero(CurrencyCode::EUR));
+        assert_knows_every_field(serde_json::to_value(detail).unwrap(), DETAIL_FIELDS);
+        let mut summary = map_auction_to_summary_model(&auction, starts_at(), true);
+        summary.display_price = Some(Amount::zero(CurrencyCode::EUR));
+        assert_knows_every_field(serde_json::to_value(summary).unwrap(), SUMMARY_FIELDS);
+    }
+}
@@ -0,0 +1,304 @@
+use actix_web::http::{header, StatusCode};
+use actix_web::{get, post, web, HttpRequest, HttpResponse, Scope};
+
+use crate::api::api_error::ApiError;
+use crate::api::handlers::auctions::map_auction_to_detail_model;
+use crate::api::models::{AdminActionModel, AnonymizedUserModel, BidMetadataModel, ExtendAuctionModel, JobModel, JobQuery, RescheduleJobModel};
+use crate::domain::commands::{AdminAuctionCommand, AnonymizeUserCommand, ExtendAuctionCommand};
+use crate::domain::models::{Auction, AuctionId, Error, User, UserId};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::data::{AuctionRepository, Job, JobRepository};
+use crate::infrastructure::jwt_payload_handling;
+use crate::infrastructure::services::{
+    AdminAuctionCommandHandler, AnonymizeUserCommandHandler, ExtendAuctionCommandHandler, TrafficRecorder,
+};
+
+async fn handle_admin_command(
+    req: &HttpRequest,
+    command: AdminAuctionCommand,
+    clock: &dyn SystemClock,
+    handler: &dyn AdminAuctionCommandHandler,
+) -> Result<HttpResponse, ApiError> {
+    // TODO: Move to configurable middleware
+    let user = jwt_payload_handling::user_from_request(req);
+
+    admin_response(handler.handle(user, command).await, clock)
+}
+
+fn admin_response(result: Result<Auction, Error>, clock: &dyn SystemClock) -> Result<HttpResponse, ApiError> {
+    Ok(HttpResponse::Ok().json(map_auction_to_detail_model(&result?, clock.now())))
+}
+
+// End an auction immediately
+#[post("/auctions/{auction_id}/force-end")]
+pub async fn force_end_auction(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    model: web::Json<AdminActionModel>,
+    clock: web::Data<Box<dyn SystemClock>>,
+    handler: web::Data<Box<dyn AdminAuctionCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    let command = AdminAuctionCommand::ForceEnd {
+        auction_id: AuctionId::new(*auction_id),
+        reason: model.reason.clone(),
+    };
+    handle_admin_command(&req, command, clock.as_ref().as_ref(), handler.as_ref().as_ref()).await
+}
+
+// Annul the result of an auction
+#[post("/auctions/{auction_id}/void")]
+pub async fn void_auction(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    model: web::Json<AdminActionModel>,
+    clock: web::Data<Box<dyn SystemClock>>,
+    handler: web::Data<Box<dyn AdminAuctionCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    let command = AdminAuctionCommand::Void {
+        auction_id: AuctionId::new(*auction_id),
+        reason: model.reason.clone(),
+    };
+    handle_admin_command(&req, command, clock.as_ref().as_ref(), handler.as_ref().as_ref()).await
+}
+
+// Show where the bids on an auction came from, for fraud investigations
+#[get("/auctions/{auction_id}/bids/metadata")]
+pub async fn get_bid_metadata(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    repository: web::Data<Box<dyn AuctionRepository>>,
+) -> Result<HttpResponse, ApiError> {
+    match jwt_payload_handling::user_from_request(&req) {
+        Some(User::Support { .. }) => {}
+        Some(_) => {
+            return Err(ApiError::new(StatusCode::FORBIDDEN, "FORBIDDEN", "Only support users may see bid metadata"))
+        }
+        None => {
+            return Err(ApiError::new(StatusCode::UNAUTHORIZED, "UNAUTHORIZED", "User must be logged in to see bid metadata"))
+        }
+    }
+    let auction = repository
+        .get_auction(AuctionId::new(*auction_id))
+        .await?
+        .ok_or_else(ApiError::not_found)?;
+    let bids: Vec<BidMetadataModel> = auction
+        .bids()
+        .iter()
+        .map(|bid| BidMetadataModel {
+            id: bid.id,
+            bidder: bid.data.user.to_string(),
+            amount: bid.data.amount.clone(),
+            at: bid.data.at,
+            metadata: bid.data.metadata.clone(),
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(bids))
+}
+
+fn require_support(req: &HttpRequest, action: &str) -> Result<(), ApiError> {
+    match jwt_payload_handling::user_from_request(req) {
+        Some(User::Support { .. }) => Ok(()),
+        Some(_) => Err(ApiError::new(StatusCode::FORBIDDEN, "FORBIDDEN", format!("Only support users may {}", action))),
+        None => Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "UNAUTHORIZED",
+            format!("User must be logged in to {}", action),
+        )),
+    }
+}
+
+fn map_job_to_model(job: Job) -> JobModel {
+    JobModel {
+        id: job.id,
+        kind: job.kind,
+        auction_id: job.auction_id.value(),
+        state: job.state,
+        run_at: job.run_at,
+        attempts: job.attempts,
+        last_error: job.last_error,
+        created_at: job.created_at,
+        updated_at: job.updated_at,
+    }
+}
+
+// Answers a support action on a job, which only applies to jobs in some states
+async fn job_action_response(
+    id: i64,
+    action: &str,
+    result: Option<Job>,
+    jobs: &dyn JobRepository,
+) -> Result<HttpResponse, ApiError> {
+    match result {
+        Some(job) => {
+            log::info!("Job {} was {}", id, action);
+            Ok(HttpResponse::Ok().json(map_job_to_model(job)))
+        }
+        None => {
+            let job = jobs.get_job(id).await?.ok_or_else(ApiError::not_found)?;
+            Err(ApiError::new(
+                StatusCode::CONFLICT,
+                "JOB_STATE_CONFLICT",
+                format!("Job {} is {}, so it cannot be {}", id, job.state, action),
+            ))
+        }
+    }
+}
+
+// List the background jobs, optionally only those in a state
+#[get("/jobs")]
+pub async fn get_jobs(
+    req: HttpRequest,
+    query: web::Query<JobQuery>,
+    jobs: web::Data<Box<dyn JobRepository>>,
+) -> Result<HttpResponse, ApiError> {
+    require_support(&req, "see background jobs")?;
+    let jobs = jobs.get_jobs(query.state).await?;
+    Ok(HttpResponse::Ok().json(jobs.into_iter().map(map_job_to_model).collect::<Vec<_>>()))
+}
+
+// Run a failed, cancelled or stuck job again right away
+#[post("/jobs/{id}/retry")]
+pub async fn retry_job(
+    req: HttpRequest,
+    id: web::Path<i64>,
+    jobs: web::Data<Box<dyn JobRepository>>,
+    clock: web::Data<Box<dyn SystemClock>>,
+) -> Result<HttpResponse, ApiError> {
+    require_support(&req, "retry background jobs")?;
+    let result = jobs.retry(*id, clock.now()).await?;
+    job_action_response(*id, "retried", result, jobs.as_ref().as_ref()).await
+}
+
+// Stop a job that has not run, or that failed, from running
+#[post("/jobs/{id}/cancel")]
+pub async fn cancel_job(
+    req: HttpRequest,
+    id: web::Path<i64>,
+    jobs: web::Data<Box<dyn JobRepository>>,
+    clock: web::Data<Box<dyn SystemClock>>,
+) -> Result<HttpResponse, ApiError> {
+    require_support(&req, "cancel background jobs")?;
+    let result = jobs.cancel(*id, clock.now()).await?;
+    job_action_response(*id, "cancelled", result, jobs.as_ref().as_ref()).await
+}
+
+// Run a job that has not succeeded at another time
+#[post("/jobs/{id}/reschedule")]
+pub async fn reschedule_job(
+    req: HttpRequest,
+    id: web::Path<i64>,
+    model: web::Json<RescheduleJobModel>,
+    jobs: web::Data<Box<dyn JobRepository>>,
+    clock: web::Data<Box<dyn SystemClock>>,
+) -> Result<HttpResponse, ApiError> {
+    require_support(&req, "reschedule background jobs")?;
+    let result = jobs.reschedule(*id, model.run_at, clock.now()).await?;
+    job_action_response(*id, "rescheduled", result, jobs.as_ref().as_ref()).await
+}
+
+// Stop an open auction from taking bids
+#[post("/auctions/{auction_id}/pause")]
+pub async fn pause_auction(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    model: web::Json<AdminActionModel>,
+    clock: web::Data<Box<dyn SystemClock>>,
+    handler: web::Data<Box<dyn AdminAuctionCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    let command = AdminAuctionCommand::Pause {
+        auction_id: AuctionId::new(*auction_id),
+        reason: model.reason.clone(),
+    };
+    handle_admin_command(&req, command, clock.as_ref().as_ref(), handler.as_ref().as_ref()).await
+}
+
+// Take bids again, extending the auction by the time it was paused
+#[post("/auctions/{auction_id}/resume")]
+pub async fn resume_auction(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    model: web::Json<AdminActionModel>,
+    clock: web::Data<Box<dyn SystemClock>>,
+    handler: web::Data<Box<dyn AdminAuctionCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    let command = AdminAuctionCommand::Resume {
+        auction_id: AuctionId::new(*auction_id),
+        reason: model.reason.clone(),
+    };
+    handle_admin_command(&req, command, clock.as_ref().as_ref(), handler.as_ref().as_ref()).await
+}
+
+// Move the expiry of a running auction, e.g. after an outage
+#[post("/auctions/{auction_id}/extend")]
+pub async fn extend_auction(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    model: web::Json<ExtendAuctionModel>,
+    clock: web::Data<Box<dyn SystemClock>>,
+    handler: web::Data<Box<dyn ExtendAuctionCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    let user = jwt_payload_handling::user_from_request(&req);
+    let command = ExtendAuctionCommand {
+        auction_id: AuctionId::new(*auction_id),
+        expiry: model.expiry,
+        reason: model.reason.clone(),
+    };
+    admin_response(handler.handle(user, command).await, clock.as_ref().as_ref())
+}
+
+// Erase a user, e.g. on a GDPR request: their auctions and bids stay under a pseudonym
+#[post("/users/{user_id}/anonymize")]
+pub async fn anonymize_user(
+    req: HttpRequest,
+    user_id: web::Path<String>,
+    handler: web::Data<Box<dyn AnonymizeUserCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    let user = jwt_payload_handling::user_from_request(&req);
+    let user_id = UserId::parse(&user_id)
+        .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, "INVALID_USER", e.to_string()))?;
+    let anonymized = handler.handle(user, AnonymizeUserCommand { user_id }).await?;
+    Ok(HttpResponse::Ok().json(AnonymizedUserModel {
+        pseudonym: anonymized.pseudonym.to_string(),
+        auctions: anonymized.auctions.iter().map(|id| id.value()).collect(),
+    }))
+}
+
+// Download recently captured, anonymized traffic
+#[get("/traffic")]
+pub async fn get_traffic(
+    req: HttpRequest,
+    recorder: Option<web::Data<TrafficRecorder>>,
+) -> Result<HttpResponse, ApiError> {
+    match jwt_payload_handling::user_from_request(&req) {
+        Some(User::Support { .. }) => {}
+        Some(_) => {
+            return Err(ApiError::new(StatusCode::FORBIDDEN, "FORBIDDEN", "Only support users may download traffic"))
+        }
+        None => {
+            return Err(ApiError::new(StatusCode::UNAUTHORIZED, "UNAUTHORIZED", "User must be logged in to download traffic"))
+        }
+    }
+    match recorder {
+        Some(recorder) if recorder.is_enabled() => Ok(HttpResponse::Ok()
+            .insert_header((header::CONTENT_DISPOSITION, "attachment; filename=\"traffic.json\""))
+            .json(recorder.recent())),
+        _ => Err(ApiError::new(StatusCode::NOT_FOUND, "NOT_FOUND", "Traffic capture is not enabled")),
+    }
+}
+
+// Configure routes
+pub fn get_scope() -> Scope {
+    web::scope("/admin")
+            .service(force_end_auction)
+            .service(void_auction)
+            .service(get_bid_metadata)
+            .service(get_jobs)
+            .service(retry_job)
+            .service(cancel_job)
+            .service(reschedule_job)
+            .service(pause_auction)
+            .service(resume_auction)
+            .service(extend_auction)
+            .service(anonymize_user)
+            .service(get_traffic)
+}
@@ -0,0 +1,21 @@
+use actix_web::{get, HttpResponse, Responder};
+
+use crate::api::models::{ApiChangeModel, ApiChangesModel};
+use crate::infrastructure::api_deprecations;
+
+// Upcoming removals, for clients to check programmatically
+#[get("/.well-known/api-changes")]
+pub async fn get_api_changes() -> impl Responder {
+    let deprecations = api_deprecations()
+        .into_iter()
+        .map(|d| ApiChangeModel {
+            method: d.method.to_string(),
+            path: d.path.to_string(),
+            field: d.field.map(str::to_string),
+            deprecated_at: d.deprecated_at,
+            sunset: d.sunset,
+            description: d.description.to_string(),
+        })
+        .collect();
+    HttpResponse::Ok().json(ApiChangesModel { deprecations })
+}
@@ -1,84 +1,980 @@
-use actix_web::{get, post, web, HttpRequest, HttpResponse, Responder, Scope};
+use actix_web::http::StatusCode;
+use actix_web::{delete, get, patch, post, web, HttpRequest, HttpResponse, Scope};
 use chrono::{DateTime, Utc};
-use log::error;
-
-use crate::api::models::{AuctionModel, CreateAuctionModel, CreateBidModel};
-use crate::domain::commands::{CreateAuctionCommand, CreateBidCommand};
-use crate::domain::models::{Auction, AuctionId, Error, Errors, SingleSealedBidOptions};
-use crate::domain::services::SystemClock;
-use crate::infrastructure::{jwt_payload_handling, AuctionRepository};
-use crate::infrastructure::services::{CreateAuctionCommandHandler, CreateBidCommandHandler};
-
-pub fn map_auction_to_model (auction:&Auction, now:DateTime<Utc>) -> AuctionModel {
-    let has_ended = auction.has_ended(now);
-    let winner_info = auction.try_get_amount_and_winner(now);
-    
-    AuctionModel {
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::api::models::{
+    AuctionBatchModel, AuctionDetailModel, AuctionPageModel, AuctionResultModel, AuctionSummaryModel, AuctionsQuery, BidModel, BidPageModel, BidPollModel, BidPollQuery, BidsQuery, BuyerSettlementModel, CancelAuctionQuery, ChargeItemModel,
+    ChargeModel, CreateAuctionModel, CreateBidModel, DepositModel, FieldsQuery, LotModel, RecordDepositModel, RegistrationModel, RelistPolicyModel, SettlementModel, VoidBidQuery,
+    RemovedAuctionModel,
+    WinnerModel,
+};
+use crate::api::amount_input;
+use crate::api::api_error::ApiError;
+use crate::api::field_mask::{FieldMask, DETAIL_FIELDS, SUMMARY_FIELDS};
+use crate::api::realtime::{auction_snapshot, display_bidder, standing_bid};
+use crate::domain::commands::{
+    AdminAuctionCommand, CancelAuctionCommand, CreateAuctionCommand, CreateBidCommand, PatchAuctionCommand,
+    PublishAuctionCommand, RecordDepositCommand, RegistrationCommand, RetractBidCommand,
+};
+use crate::domain::models::{Amount, Auction, AuctionId, AuctionRemoval, AuctionResult, AuctionStatus, Bid, BidIncrement, ChargeItem, CurrencyCode, Error, FeePolicy, Registration, RelistPolicy, Settlement, SingleSealedBidOptions, TieBreak, UserId, VickreyPricing};
+use crate::domain::services::{CurrencyConverter, SystemClock};
+use crate::infrastructure::{
+    bid_metadata_from_request, ip_hash_from_request, jwt_payload_handling, AuctionFilter, AuctionLookup, AuctionRepository, AuctionStandings, AuctionSummary, BidPage,
+};
+use crate::infrastructure::services::{
+    AdminAuctionCommandHandler, AuctionResultRecorder, SettlementRecorder, BidEvents, CancelAuctionCommandHandler, CreateAuctionCommandHandler, CreateBidCommandHandler,
+    PatchAuctionCommandHandler, PublishAuctionCommandHandler, RecordDepositCommandHandler, RegistrationCommandHandler, RetractBidCommandHandler, UserDirectory,
+};
+
+const MAX_POLL_TIMEOUT_SECONDS: u64 = 60;
+const MAX_BATCH_IDS: usize = 100;
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 500;
+
+fn map_bids_to_models<'a>(
+    auction: &'a Auction,
+    now: DateTime<Utc>,
+    bidder_aliases: Option<&HashMap<UserId, String>>,
+) -> Vec<BidModel<'a>> {
+    auction.get_bids(now).map_or_else(Vec::new, |bids| bids.iter().map(|bid| {
+        BidModel {
+            id: bid.id,
+            amount: Cow::Borrowed(bid.amount()),
+            // Open bidders are shown as is, without copying their ids
+            bidder: Some(match bidder_aliases {
+                Some(aliases) => Cow::Owned(aliases.get(bid.user()).cloned().unwrap_or_default()),
+                None => Cow::Borrowed(bid.user().value()),
+            }),
+            at: bid.at() - auction.starts_at(),
+            quantity: bid.quantity(),
+            lot: bid.lot,
+        }
+    }).collect())
+}
+
+/// The bids of the page, their bidders shown like [`map_bids_to_models`] shows them.
+fn map_bid_page_to_models(page: &BidPage, starts_at: DateTime<Utc>, open_bidders: bool) -> Vec<BidModel<'_>> {
+    page.bids
+        .iter()
+        .map(|bid| BidModel {
+            id: bid.id,
+            amount: Cow::Borrowed(bid.amount()),
+            bidder: Some(if open_bidders {
+                Cow::Borrowed(bid.user().value())
+            } else {
+                Cow::Owned(page.bidder_aliases.get(bid.user()).cloned().unwrap_or_default())
+            }),
+            at: bid.at() - starts_at,
+            quantity: bid.quantity(),
+            lot: bid.lot,
+        })
+        .collect()
+}
+
+/// Each lot with the bids on it, and its price and winner as if it were sold on its own.
+fn map_lots_to_models<'a>(
+    auction: &Auction,
+    bids: &[BidModel<'a>],
+    now: DateTime<Utc>,
+    bidder_name: impl Fn(&UserId) -> String,
+) -> Vec<LotModel<'a>> {
+    auction
+        .lots()
+        .iter()
+        .filter_map(|lot| auction.lot_view(lot.id).map(|view| (lot, view)))
+        .map(|(lot, view)| {
+            let winner = view.try_get_winners(now).into_iter().next();
+            LotModel {
+                id: lot.id,
+                title: lot.title.clone(),
+                bids: bids.iter().filter(|bid| bid.lot == Some(lot.id)).cloned().collect(),
+                bid_count: view.bids().len(),
+                current_price: standing_bid(&view, now).map(|(amount, _)| amount),
+                next_minimum_bid: view.next_minimum_bid(now),
+                price: winner.as_ref().map(|(amount, _, _)| amount.clone()),
+                winner: winner.as_ref().map(|(_, user, _)| bidder_name(user)),
+            }
+        })
+        .collect()
+}
+
+/// Bidders are shown by alias, or as is without aliases.
+fn show_bidder(bidder_aliases: Option<&HashMap<UserId, String>>, user: &UserId) -> String {
+    match bidder_aliases {
+        Some(aliases) => aliases.get(user).cloned().unwrap_or_default(),
+        None => user.to_string(),
+    }
+}
+
+fn buy_now_price(auction: &Auction) -> Option<Amount> {
+    match auction {
+        Auction::TimedAscending { options, .. } => options
+            .buy_now_price
+            .map(|price| Amount::new(price, auction.currency())),
+        Auction::SingleSealedBid { .. } => None,
+    }
+}
+
+/// The auction as shown in lists, with the bids only when asked for and not sealed.
+pub fn map_auction_to_summary_model(auction: &Auction, now: DateTime<Utc>, include_bids: bool) -> AuctionSummaryModel<'_> {
+    let status = auction.status_at(now);
+    let has_ended = status.has_ended();
+    let bids = (include_bids && !bids_sealed(auction, now)).then(|| {
+        let bidder_aliases = if auction.open_bidders() { None } else { Some(auction.bidder_aliases()) };
+        map_bids_to_models(auction, now, bidder_aliases.as_ref())
+    });
+
+    AuctionSummaryModel {
+        id: auction.auction_id().value(),
+        title: auction.title().to_string(),
+        starts_at: auction.starts_at(),
+        expiry: auction.expiry(),
+        seller: Some(auction.user().to_string()),
+        currency: auction.currency(),
+        bids,
+        bid_count: auction.bids().len(),
+        current_price: standing_bid(auction, now).map(|(amount, _)| amount),
+        next_minimum_bid: auction.next_minimum_bid(now),
+        display_price: None,
+        quantity: auction.quantity(),
+        status,
+        has_ended,
+        requires_registration: auction.requires_registration(),
+        deposit: auction.deposit(),
+        buy_now_price: buy_now_price(auction),
+        external_reference: auction.external_reference().map(str::to_string),
+    }
+}
+
+pub fn map_auction_to_detail_model (auction:&Auction, now:DateTime<Utc>) -> AuctionDetailModel<'_> {
+    map_standings_to_detail_model(auction, now, &auction.bidder_aliases())
+}
+
+/// [`map_auction_to_detail_model`] of an auction read with only the bids deciding its outcome,
+/// see [`AuctionStandings`], given the aliases of all its bidders.
+fn map_standings_to_detail_model<'a>(
+    auction: &'a Auction,
+    now: DateTime<Utc>,
+    bidder_aliases: &HashMap<UserId, String>,
+) -> AuctionDetailModel<'a> {
+    let status = auction.status_at(now);
+    let has_ended = status.has_ended();
+    let winners = auction.try_get_winners(now);
+    let winner_info = winners.first();
+    let bidder_aliases = (!auction.open_bidders()).then_some(bidder_aliases);
+    let display_bidder = |user: &UserId| show_bidder(bidder_aliases, user);
+
+    let bids = map_bids_to_models(auction, now, bidder_aliases);
+    let lots = map_lots_to_models(auction, &bids, now, display_bidder);
+
+    AuctionDetailModel {
         id: auction.auction_id().value(),
         starts_at: auction.starts_at(),
         title: auction.title().to_string(),
+        description: auction.description().map(str::to_string),
         expiry: auction.expiry(),
         seller: Some(auction.user().to_string()),
         currency: auction.currency(),
-        bids: auction.get_bids(now).map_or_else(|| {Vec::new()},|bids| {bids.iter().map(|bid| {
-            // In a real application, we'd use a proper mapper service
-            // that takes care of bidder representation based on open_bidders setting
-            crate::api::models::BidModel {
-                amount: bid.amount(),
-                bidder: Some(bid.user().to_string()),
-                at: bid.at() - auction.starts_at(),
+        bids,
+        bids_next_after: None,
+        bid_count: auction.bids().len(),
+        current_price: standing_bid(auction, now).map(|(amount, _)| amount),
+        next_minimum_bid: auction.next_minimum_bid(now),
+        display_price: None,
+        price: winner_info.map(|(amount, _, _)| amount.clone()),
+        winner: winner_info.map(|(_, user, _)| display_bidder(user)),
+        quantity: auction.quantity(),
+        winners: winners.iter().map(|(amount, user, quantity)| WinnerModel {
+            price: amount.clone(),
+            winner: display_bidder(user),
+            quantity: *quantity,
+        }).collect(),
+        charges: auction.try_get_charges(now).into_iter().map(|(amount, user)| ChargeModel {
+            amount,
+            bidder: display_bidder(&user),
+        }).collect(),
+        result: None,
+        status,
+        has_ended,
+        requires_registration: auction.requires_registration(),
+        deposit: auction.deposit(),
+        relist: auction.relist_policy().map(|policy| RelistPolicyModel {
+            duration: policy.duration_seconds,
+            max_relists: policy.max_relists,
+        }),
+        relisted_from: auction.relisted_from().map(|id| id.value()),
+        relisted_as: auction.relisted_as().map(|id| id.value()),
+        voided_at: auction.voided_at(),
+        cancelled_at: auction.cancelled_at(),
+        cancel_reason: auction.cancel_reason().map(str::to_string),
+        extended_at: auction.extended_at(),
+        extension_reason: auction.extension_reason().map(str::to_string),
+        paused_at: auction.paused_at(),
+        external_reference: auction.external_reference().map(str::to_string),
+        buy_now_price: buy_now_price(auction),
+        starting_price: match auction {
+            Auction::TimedAscending { options, .. } if options.starting_price > 0 => {
+                Some(Amount::new(options.starting_price, auction.currency()))
+            }
+            _ => None,
+        },
+        lots,
+    }
+}
+
+fn map_removal_to_model(auction_id: AuctionId, removal: AuctionRemoval) -> RemovedAuctionModel {
+    RemovedAuctionModel {
+        id: auction_id.value(),
+        removal: removal.kind,
+        removed_at: removal.at,
+        reason: removal.reason,
+    }
+}
+
+/// The auction, answering 404 when it never existed and 410 Gone when it was taken down.
+async fn look_up_auction(query: &dyn AuctionRepository, id: AuctionId) -> Result<Auction, ApiError> {
+    match query.look_up_auction(id).await? {
+        AuctionLookup::Found(auction) => Ok(*auction),
+        AuctionLookup::Removed(removal) => Err(ApiError::gone(removal)),
+        AuctionLookup::Missing => Err(ApiError::not_found()),
+    }
+}
+
+fn map_settlement_to_model(auction: &Auction, settlement: Settlement) -> SettlementModel {
+    SettlementModel {
+        buyers: settlement
+            .buyers
+            .into_iter()
+            .map(|buyer| BuyerSettlementModel {
+                bidder: display_bidder(auction, &buyer.bidder),
+                hammer_price: buyer.hammer_price,
+                premium: buyer.premium,
+                fee: buyer.fee,
+                items: buyer.items.into_iter().map(map_charge_item_to_model).collect(),
+                tax: buyer.tax,
+                total: buyer.total,
+            })
+            .collect(),
+        sold_for: settlement.sold_for,
+        commission: settlement.commission,
+        seller_fee: settlement.seller_fee,
+        seller_items: settlement.seller_items.into_iter().map(map_charge_item_to_model).collect(),
+        tax_rate_bps: settlement.tax_rate_bps,
+        seller_receives: settlement.seller_receives,
+    }
+}
+
+fn map_charge_item_to_model(item: ChargeItem) -> ChargeItemModel {
+    ChargeItemModel {
+        kind: item.kind,
+        net: item.net,
+        tax: item.tax,
+        gross: item.gross,
+    }
+}
+
+fn parse_auction_ids(ids: &str) -> Result<Vec<AuctionId>, String> {
+    let mut auction_ids: Vec<AuctionId> = Vec::new();
+    for id in ids.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+        let id = id
+            .parse::<i64>()
+            .map(AuctionId::new)
+            .map_err(|_| format!("Invalid auction id {}", id))?;
+        if !auction_ids.contains(&id) {
+            auction_ids.push(id);
+        }
+    }
+    if auction_ids.len() > MAX_BATCH_IDS {
+        return Err(format!("At most {} auction ids may be requested at once", MAX_BATCH_IDS));
+    }
+    Ok(auction_ids)
+}
+
+/// Drafts are only for their seller to see until published, anyone else is told there is no
+/// such auction.
+fn hide_draft(caller: Option<&UserId>, status: AuctionStatus, seller: &UserId) -> Result<(), ApiError> {
+    if status == AuctionStatus::Draft && caller != Some(seller) {
+        return Err(ApiError::not_found());
+    }
+    Ok(())
+}
+
+/// The auctions found for `auction_ids` as `map` shows them, listing the ones that were taken
+/// down apart. Drafts hidden from the caller are missing, see [`hide_draft`].
+fn batch_of<'a, T>(
+    auction_ids: &[AuctionId],
+    auctions: &'a [Auction],
+    caller: Option<&UserId>,
+    map: impl Fn(&'a Auction) -> T,
+) -> AuctionBatchModel<T> {
+    let auctions: Vec<&Auction> = auctions
+        .iter()
+        .filter(|auction| hide_draft(caller, auction.status(), auction.user()).is_ok())
+        .collect();
+    let missing = auction_ids
+        .iter()
+        .filter(|id| !auctions.iter().any(|auction| auction.auction_id() == **id))
+        .map(|id| id.value())
+        .collect();
+    let mut batch = AuctionBatchModel {
+        auctions: Vec::new(),
+        removed: Vec::new(),
+        missing,
+    };
+    for auction in auctions {
+        match auction.removal() {
+            Some(removal) => batch.removed.push(map_removal_to_model(auction.auction_id(), removal)),
+            None => batch.auctions.push(map(auction)),
+        }
+    }
+    batch
+}
+
+fn parse_field_mask(
+    fields: Option<&str>,
+    model_fields: &'static [&'static str],
+) -> Result<Option<FieldMask>, ApiError> {
+    fields
+        .map(|fields| FieldMask::parse(fields, model_fields))
+        .transpose()
+        .map_err(ApiError::bad_request)
+}
+
+/// Converts current prices to the currency the caller asked for with `display_currency`.
+struct PriceDisplay<'a> {
+    currency: CurrencyCode,
+    converter: &'a dyn CurrencyConverter,
+}
+
+impl PriceDisplay<'_> {
+    fn price(&self, current_price: Option<&Amount>) -> Option<Amount> {
+        self.converter.convert(current_price?, self.currency)
+    }
+}
+
+fn parse_display_currency<'a>(
+    display_currency: Option<&str>,
+    converter: &'a dyn CurrencyConverter,
+) -> Result<Option<PriceDisplay<'a>>, ApiError> {
+    display_currency
+        .map(|code| amount_input::parse_currency(code).map(|currency| PriceDisplay { currency, converter }))
+        .transpose()
+        .map_err(ApiError::bad_request)
+}
+
+/// Shows the recorded result of a closed auction rather than working it out again.
+fn with_recorded_result<'a>(
+    mut model: AuctionDetailModel<'a>,
+    result: Option<&AuctionResult>,
+    bidder_aliases: Option<&HashMap<UserId, String>>,
+) -> AuctionDetailModel<'a> {
+    if let Some(result) = result {
+        let winner = result.winner.as_ref().map(|user| show_bidder(bidder_aliases, user));
+        model.price = result.amount.clone();
+        model.winner = winner.clone();
+        model.result = Some(AuctionResultModel {
+            winner,
+            amount: result.amount.clone(),
+            closed_at: result.closed_at,
+            reserve_met: result.reserve_met,
+        });
+    }
+    model
+}
+
+async fn recorded_results(
+    recorder: &AuctionResultRecorder,
+    auctions: &[Auction],
+    now: DateTime<Utc>,
+) -> HashMap<AuctionId, AuctionResult> {
+    // The results can still be worked out from the bids, so reads do not fail over them
+    recorder.results(auctions, now).await.unwrap_or_else(|e| {
+        log::error!("Error recording auction results: {:?}", e);
+        HashMap::new()
+    })
+}
+
+/// The display names of the users shown by their ids in the auctions: the sellers, and the
+/// bidders of auctions with open bidders.
+struct DisplayNames(HashMap<UserId, String>);
+
+impl DisplayNames {
+    async fn of(users: &UserDirectory, auctions: &[Auction]) -> Self {
+        let mut ids: Vec<UserId> = auctions.iter().map(|auction| auction.user().clone()).collect();
+        for auction in auctions.iter().filter(|auction| auction.open_bidders()) {
+            ids.extend(auction.bids().iter().map(|bid| bid.user().clone()));
+        }
+        Self::of_users(users, ids).await
+    }
+
+    /// The sellers of the summaries, which show no bidders.
+    async fn of_sellers(users: &UserDirectory, summaries: &[AuctionSummary]) -> Self {
+        Self::of_users(users, summaries.iter().map(|summary| summary.seller.clone()).collect()).await
+    }
+
+    async fn of_users(users: &UserDirectory, mut ids: Vec<UserId>) -> Self {
+        ids.sort_by(|a, b| a.value().cmp(b.value()));
+        ids.dedup();
+        DisplayNames(users.display_names(&ids).await)
+    }
+
+    /// Users without a display name are shown by their id.
+    fn name(&self, user: &UserId) -> String {
+        self.0.get(user).cloned().unwrap_or_else(|| user.to_string())
+    }
+
+    /// Bidders that are shown by alias keep their alias.
+    fn show_bidders(&self, open_bidders: bool, bids: &mut [BidModel]) {
+        if !open_bidders {
+            return;
+        }
+        for bid in bids {
+            if let Some(bidder) = bid.bidder.as_mut() {
+                *bidder = Cow::Owned(self.name(&UserId::new(bidder.as_ref())));
             }
-        }).collect()}),
-        price: winner_info.as_ref().map(|(amount, _)| amount.clone()),
-        winner: winner_info.as_ref().map(|(_, user)| user.to_string()),
+        }
+    }
+}
+
+/// The summaries of the auctions, with only the fields in the mask when there is one.
+fn summarize(
+    auctions: &[Auction],
+    now: DateTime<Utc>,
+    include_bids: bool,
+    results: &HashMap<AuctionId, AuctionResult>,
+    names: &DisplayNames,
+    mask: Option<&FieldMask>,
+    display: Option<&PriceDisplay>,
+) -> Vec<Value> {
+    auctions
+        .iter()
+        .map(|auction| summarize_auction(auction, now, include_bids, results, names, mask, display))
+        .collect()
+}
+
+fn summarize_auction(
+    auction: &Auction,
+    now: DateTime<Utc>,
+    include_bids: bool,
+    results: &HashMap<AuctionId, AuctionResult>,
+    names: &DisplayNames,
+    mask: Option<&FieldMask>,
+    display: Option<&PriceDisplay>,
+) -> Value {
+    let mut model = map_auction_to_summary_model(auction, now, include_bids);
+    if let Some(result) = results.get(&auction.auction_id()) {
+        model.current_price = result.amount.clone();
+    }
+    model.seller = Some(names.name(auction.user()));
+    if let Some(bids) = model.bids.as_mut() {
+        names.show_bidders(auction.open_bidders(), bids);
+    }
+    model.display_price = display.and_then(|display| display.price(model.current_price.as_ref()));
+    match mask {
+        Some(mask) => mask.apply(&model, auction, now),
+        None => serde_json::to_value(&model).unwrap_or_default(),
+    }
+}
+
+/// The services a listing of auctions reads from.
+struct ListingServices<'a> {
+    query: &'a dyn AuctionRepository,
+    recorder: &'a AuctionResultRecorder,
+    users: &'a UserDirectory,
+    clock: &'a dyn SystemClock,
+}
+
+/// The summary of an auction read without its bids, leaving out what takes the bids to work out.
+/// Ended auctions show the price of their recorded result.
+pub fn map_summary_to_model(
+    summary: &AuctionSummary,
+    now: DateTime<Utc>,
+    result: Option<&AuctionResult>,
+) -> AuctionSummaryModel<'static> {
+    let status = summary.status_at(now);
+    let has_ended = status.has_ended();
+    let current_price = match result {
+        Some(result) => result.amount.clone(),
+        None if has_ended || summary.sealed => None,
+        None => summary.high_bid.clone(),
+    };
+
+    AuctionSummaryModel {
+        id: summary.auction_id.value(),
+        title: summary.title.clone(),
+        starts_at: summary.starts_at,
+        expiry: summary.expiry,
+        seller: Some(summary.seller.to_string()),
+        currency: summary.currency,
+        bids: None,
+        bid_count: summary.bid_count as usize,
+        current_price,
+        next_minimum_bid: None,
+        display_price: None,
+        quantity: summary.quantity,
+        status,
         has_ended,
+        requires_registration: summary.requires_registration,
+        deposit: summary.deposit.clone(),
+        buy_now_price: None,
+        external_reference: summary.external_reference.clone(),
     }
 }
 
-// Get all auctions
+/// The models of the summaries, with only the fields in the mask when there is one.
+fn summarize_summaries(
+    summaries: &[AuctionSummary],
+    now: DateTime<Utc>,
+    results: &HashMap<AuctionId, AuctionResult>,
+    names: &DisplayNames,
+    mask: Option<&FieldMask>,
+    display: Option<&PriceDisplay>,
+) -> Vec<Value> {
+    summaries
+        .iter()
+        .map(|summary| {
+            let mut model = map_summary_to_model(summary, now, results.get(&summary.auction_id));
+            model.seller = Some(names.name(&summary.seller));
+            model.display_price = display.and_then(|display| display.price(model.current_price.as_ref()));
+            match mask {
+                Some(mask) => mask.apply_to_summary(&model),
+                None => serde_json::to_value(&model).unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+// Get the requested auctions in one round trip
+async fn get_auctions_by_ids(
+    ids: &str,
+    caller: Option<&UserId>,
+    include_bids: bool,
+    mask: Option<FieldMask>,
+    display: Option<&PriceDisplay<'_>>,
+    services: ListingServices<'_>,
+) -> Result<HttpResponse, ApiError> {
+    let ListingServices { query, recorder, users, clock } = services;
+    let auction_ids = parse_auction_ids(ids).map_err(ApiError::bad_request)?;
+    let auctions = query.get_auctions_by_ids(&auction_ids).await?;
+    let now = clock.now();
+    let results = recorded_results(recorder, &auctions, now).await;
+    let names = DisplayNames::of(users, &auctions).await;
+    Ok(HttpResponse::Ok().json(batch_of(&auction_ids, &auctions, caller, |auction| {
+        summarize_auction(auction, now, include_bids, &results, &names, mask.as_ref(), display)
+    })))
+}
+
+/// The number of bids asked for, the default page size when not asked.
+fn parse_bids_limit(limit: Option<i64>) -> Result<i64, String> {
+    parse_page(limit, None).map(|(limit, _)| limit)
+}
+
+/// Whether showing the auction takes the bids deciding its outcome rather than its highest one:
+/// its outcome once it has ended, or how several units or lots are taken.
+fn takes_deciding_bids(auction: &Auction, now: DateTime<Utc>) -> bool {
+    auction.has_ended(now) || auction.quantity() > 1 || !auction.lots().is_empty()
+}
+
+/// [`Auction::next_minimum_bid`] of a running single unit auction read without its bids, given
+/// its highest bid.
+fn next_minimum_bid_over(auction: &Auction, high_bid: Option<&Amount>, now: DateTime<Utc>) -> Option<Amount> {
+    match auction {
+        Auction::TimedAscending { options, .. } if !auction.has_ended(now) => {
+            Some(Amount::new(options.minimum_next_bid(high_bid.map(Amount::value)), auction.currency()))
+        }
+        _ => None,
+    }
+}
+
+/// The page size and offset asked for, the first page of the default size when not asked.
+fn parse_page(limit: Option<i64>, offset: Option<i64>) -> Result<(i64, i64), String> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        return Err(format!("The limit should be between 1 and {}", MAX_PAGE_SIZE));
+    }
+    let offset = offset.unwrap_or(0);
+    if offset < 0 {
+        return Err("The offset cannot be negative".to_string());
+    }
+    Ok((limit, offset))
+}
+
+fn parse_include(include: Option<&str>) -> Result<bool, String> {
+    let mut include_bids = false;
+    for part in include.unwrap_or_default().split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        match part {
+            "bids" => include_bids = true,
+            other => return Err(format!("Cannot include {}, only bids", other)),
+        }
+    }
+    Ok(include_bids)
+}
+
+// Get a page of the auctions, or only the ones in `ids`, with only the `fields` asked for
 #[get("/auctions")]
 pub async fn get_auctions(
+    req: HttpRequest,
+    params: web::Query<AuctionsQuery>,
     query: web::Data<Box<dyn AuctionRepository>>,
+    recorder: web::Data<AuctionResultRecorder>,
+    users: web::Data<UserDirectory>,
+    converter: web::Data<Box<dyn CurrencyConverter>>,
     clock: web::Data<Box<dyn SystemClock>>,
-) -> impl Responder {
-    match query.get_auctions().await {
-        Ok(auctions) => {
-            let now = clock.now();
-            
-            // Map domain auctions to API models
-           
-            let models: Vec<AuctionModel> = auctions.iter().map(|auction| { 
-                return map_auction_to_model(auction,now)
-            }).collect();
-            HttpResponse::Ok().json(models)
-        },
-        Err(e) => {
-            log::error!("Error getting auctions: {:?}", e);
-            HttpResponse::InternalServerError().json(format!("Internal server error: {}", e))
-        }
+) -> Result<HttpResponse, ApiError> {
+    let include_bids = parse_include(params.include.as_deref()).map_err(ApiError::bad_request)?;
+    let mask = parse_field_mask(params.fields.as_deref(), SUMMARY_FIELDS)?;
+    let display = parse_display_currency(params.display_currency.as_deref(), converter.as_ref().as_ref())?;
+    if let Some(ids) = &params.ids {
+        let services = ListingServices {
+            query: query.as_ref().as_ref(),
+            recorder: &recorder,
+            users: &users,
+            clock: clock.as_ref().as_ref(),
+        };
+        let caller = jwt_payload_handling::from_request(&req);
+        return get_auctions_by_ids(ids, caller.as_ref(), include_bids, mask, display.as_ref(), services).await;
+    }
+    let (limit, offset) = parse_page(params.limit, params.offset).map_err(ApiError::bad_request)?;
+    // Drafts are only for their seller to see until published
+    let published = AuctionFilter { published: true, ..AuctionFilter::default() };
+    let now = clock.now();
+    // Rows that show no bids are read without them
+    if !include_bids && !mask.as_ref().is_some_and(FieldMask::needs_auction) {
+        let page = query.get_auction_summaries(limit, offset, &published).await?;
+        let ids: Vec<AuctionId> = page.auctions.iter().map(|summary| summary.auction_id).collect();
+        let results = recorder.recorded(&ids).await.unwrap_or_else(|e| {
+            log::error!("Error reading auction results: {:?}", e);
+            HashMap::new()
+        });
+        let names = DisplayNames::of_sellers(&users, &page.auctions).await;
+        return Ok(HttpResponse::Ok().json(AuctionPageModel {
+            auctions: summarize_summaries(&page.auctions, now, &results, &names, mask.as_ref(), display.as_ref()),
+            total: page.total,
+            next_offset: page.next_offset,
+        }));
     }
+    let page = query.get_auctions_page(limit, offset, &published).await?;
+    let results = recorded_results(&recorder, &page.auctions, now).await;
+    let names = DisplayNames::of(&users, &page.auctions).await;
+
+    // Map domain auctions to API models
+    Ok(HttpResponse::Ok().json(AuctionPageModel {
+        auctions: summarize(&page.auctions, now, include_bids, &results, &names, mask.as_ref(), display.as_ref()),
+        total: page.total,
+        next_offset: page.next_offset,
+    }))
+}
+
+// The auctions taking bids now, for browsing without the ended ones
+#[get("/auctions/active")]
+pub async fn get_active_auctions(
+    params: web::Query<FieldsQuery>,
+    query: web::Data<Box<dyn AuctionRepository>>,
+    recorder: web::Data<AuctionResultRecorder>,
+    users: web::Data<UserDirectory>,
+    converter: web::Data<Box<dyn CurrencyConverter>>,
+    clock: web::Data<Box<dyn SystemClock>>,
+) -> Result<HttpResponse, ApiError> {
+    let mask = parse_field_mask(params.fields.as_deref(), SUMMARY_FIELDS)?;
+    let display = parse_display_currency(params.display_currency.as_deref(), converter.as_ref().as_ref())?;
+
+    let now = clock.now();
+    let auctions = query.get_active_auctions(now).await?;
+    let results = recorded_results(&recorder, &auctions, now).await;
+    let names = DisplayNames::of(&users, &auctions).await;
+    Ok(HttpResponse::Ok().json(summarize(&auctions, now, false, &results, &names, mask.as_ref(), display.as_ref())))
+}
+
+// The auctions of the signed in seller, drafts included
+#[get("/auctions/mine")]
+pub async fn get_my_auctions(
+    req: HttpRequest,
+    params: web::Query<FieldsQuery>,
+    query: web::Data<Box<dyn AuctionRepository>>,
+    recorder: web::Data<AuctionResultRecorder>,
+    users: web::Data<UserDirectory>,
+    converter: web::Data<Box<dyn CurrencyConverter>>,
+    clock: web::Data<Box<dyn SystemClock>>,
+) -> Result<HttpResponse, ApiError> {
+    // TODO: Move to configurable middleware
+    let seller = jwt_payload_handling::from_request(&req)
+        .ok_or_else(|| Error::Unauthorized("User must be logged in to list their auctions".to_string()))?;
+    let mask = parse_field_mask(params.fields.as_deref(), SUMMARY_FIELDS)?;
+    let display = parse_display_currency(params.display_currency.as_deref(), converter.as_ref().as_ref())?;
+
+    let auctions = query.get_auctions_by_seller(&seller).await?;
+    let now = clock.now();
+    let results = recorded_results(&recorder, &auctions, now).await;
+    let names = DisplayNames::of(&users, &auctions).await;
+    Ok(HttpResponse::Ok().json(summarize(&auctions, now, false, &results, &names, mask.as_ref(), display.as_ref())))
 }
 
 // Get a single auction
 #[get("/auctions/{auction_id}")]
+#[allow(clippy::too_many_arguments)]
 pub async fn get_auction(
+    req: HttpRequest,
     auction_id: web::Path<i64>,
+    params: web::Query<FieldsQuery>,
     query: web::Data<Box<dyn AuctionRepository>>,
+    recorder: web::Data<AuctionResultRecorder>,
+    users: web::Data<UserDirectory>,
+    converter: web::Data<Box<dyn CurrencyConverter>>,
     clock: web::Data<Box<dyn SystemClock>>,
-) -> impl Responder {
+) -> Result<HttpResponse, ApiError> {
     let id = AuctionId::new(*auction_id);
-    
-    match query.get_auction(id).await {
-        Ok(Some(auction)) => {
-            let now = clock.now();
-            let model= map_auction_to_model(&auction,now);            
-            HttpResponse::Ok().json(model)
-        },
-        Ok(None) => HttpResponse::NotFound().finish(),
-        Err(e) => {
-            log::error!("Error getting auction {}: {:?}", auction_id, e);
-            HttpResponse::InternalServerError().json(format!("Internal server error: {}", e))
+    let mask = parse_field_mask(params.fields.as_deref(), DETAIL_FIELDS)?;
+    let display = parse_display_currency(params.display_currency.as_deref(), converter.as_ref().as_ref())?;
+    let bids_limit = parse_bids_limit(params.bids_limit).map_err(ApiError::bad_request)?;
+
+    // Only the latest page of bids is shown, older ones are read a page at a time from get_bids
+    let summary = query.get_auction_summary(id).await?.ok_or_else(ApiError::not_found)?;
+    hide_draft(jwt_payload_handling::from_request(&req).as_ref(), summary.status, &summary.seller)?;
+    if let Some(removal) = summary.removal.clone() {
+        return Err(ApiError::gone(removal));
+    }
+    let now = clock.now();
+    let latest_bids = if summary.bids_sealed(now) {
+        BidPage::default()
+    } else {
+        query.get_bids_page(id, None, bids_limit).await?
+    };
+    let without_bids = query.get_auction_without_bids(id).await?.ok_or_else(ApiError::not_found)?;
+    let standings = if mask.as_ref().is_some_and(FieldMask::needs_snapshot) {
+        // The snapshot counts and ranks every bid
+        Some(AuctionStandings::of(look_up_auction(query.as_ref().as_ref(), id).await?))
+    } else if takes_deciding_bids(&without_bids, now) {
+        Some(query.get_auction_standings(id).await?.ok_or_else(ApiError::not_found)?)
+    } else {
+        None
+    };
+    let deciding = standings.is_some();
+    let AuctionStandings { auction, lot_bid_counts, bidder_aliases } = standings.unwrap_or_else(|| AuctionStandings {
+        auction: without_bids,
+        lot_bid_counts: HashMap::new(),
+        bidder_aliases: HashMap::new(),
+    });
+    let results = recorded_results(&recorder, std::slice::from_ref(&auction), now).await;
+    let mut model = with_recorded_result(
+        map_standings_to_detail_model(&auction, now, &bidder_aliases),
+        results.get(&id),
+        (!auction.open_bidders()).then_some(&bidder_aliases),
+    );
+    model.bids = map_bid_page_to_models(&latest_bids, summary.starts_at, summary.open_bidders);
+    model.bids_next_after = latest_bids.next_after;
+    model.bid_count = summary.bid_count as usize;
+    if !deciding {
+        model.current_price = (!summary.sealed).then(|| summary.high_bid.clone()).flatten();
+        model.next_minimum_bid = next_minimum_bid_over(&auction, summary.high_bid.as_ref(), now);
+    }
+    let mut bidders = vec![summary.seller.clone()];
+    if summary.open_bidders {
+        bidders.extend(latest_bids.bids.iter().map(|bid| bid.user().clone()));
+    }
+    let names = DisplayNames::of_users(&users, bidders).await;
+    model.seller = Some(names.name(&summary.seller));
+    names.show_bidders(summary.open_bidders, &mut model.bids);
+    for lot in &mut model.lots {
+        lot.bids = model.bids.iter().filter(|bid| bid.lot == Some(lot.id)).cloned().collect();
+        lot.bid_count = lot_bid_counts.get(&lot.id).copied().unwrap_or_default() as usize;
+    }
+    model.display_price = display.and_then(|display| display.price(model.current_price.as_ref()));
+    Ok(match mask {
+        Some(mask) => HttpResponse::Ok().json(mask.apply(&model, &auction, now)),
+        None => HttpResponse::Ok().json(model),
+    })
+}
+
+// Get a compact snapshot of an auction
+#[get("/auctions/{auction_id}/snapshot")]
+pub async fn get_auction_snapshot(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    query: web::Data<Box<dyn AuctionRepository>>,
+    clock: web::Data<Box<dyn SystemClock>>,
+) -> Result<HttpResponse, ApiError> {
+    let id = AuctionId::new(*auction_id);
+
+    let auction = look_up_auction(query.as_ref().as_ref(), id).await?;
+    hide_draft(jwt_payload_handling::from_request(&req).as_ref(), auction.status(), auction.user())?;
+    Ok(HttpResponse::Ok().json(auction_snapshot(&auction, clock.now())))
+}
+
+/// Whether the bids of the auction are kept hidden at `now`, which they are for sealed bid
+/// auctions until they have ended.
+fn bids_sealed(auction: &Auction, now: DateTime<Utc>) -> bool {
+    matches!(auction, Auction::SingleSealedBid { .. }) && !auction.has_ended(now)
+}
+
+/// The bids newer than the cursor `since`, none while they are sealed.
+fn bids_since(auction: &Auction, since: i64, now: DateTime<Utc>) -> Vec<&Bid> {
+    if bids_sealed(auction, now) {
+        return Vec::new();
+    }
+    auction
+        .get_bids(now)
+        .map(|bids| bids.iter().filter(|bid| bid.id > since).collect())
+        .unwrap_or_default()
+}
+
+// What the buyers pay and the seller receives, once the auction has closed
+#[get("/auctions/{auction_id}/settlement")]
+pub async fn get_settlement(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    query: web::Data<Box<dyn AuctionRepository>>,
+    recorder: web::Data<SettlementRecorder>,
+    clock: web::Data<Box<dyn SystemClock>>,
+) -> Result<HttpResponse, ApiError> {
+    let id = AuctionId::new(*auction_id);
+
+    let auction = look_up_auction(query.as_ref().as_ref(), id).await?;
+    hide_draft(jwt_payload_handling::from_request(&req).as_ref(), auction.status(), auction.user())?;
+    let settlement = recorder.settlement(&auction, clock.now()).await?.ok_or_else(|| {
+        ApiError::new(StatusCode::CONFLICT, "AUCTION_NOT_CLOSED", "Only closed auctions are settled")
+    })?;
+    Ok(HttpResponse::Ok().json(map_settlement_to_model(&auction, settlement)))
+}
+
+// Get the bids of an auction a page at a time, latest first
+#[get("/auctions/{auction_id}/bids")]
+pub async fn get_bids(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    query: web::Query<BidsQuery>,
+    repository: web::Data<Box<dyn AuctionRepository>>,
+    clock: web::Data<Box<dyn SystemClock>>,
+    users: web::Data<UserDirectory>,
+) -> Result<HttpResponse, ApiError> {
+    let id = AuctionId::new(*auction_id);
+    let limit = parse_bids_limit(query.limit).map_err(ApiError::bad_request)?;
+
+    // The bids are shown without reading the auction with all of them
+    let summary = repository.get_auction_summary(id).await?.ok_or_else(ApiError::not_found)?;
+    hide_draft(jwt_payload_handling::from_request(&req).as_ref(), summary.status, &summary.seller)?;
+    if let Some(removal) = summary.removal.clone() {
+        return Err(ApiError::gone(removal));
+    }
+    // Sealed bids stay hidden until the auction has ended
+    if summary.bids_sealed(clock.now()) {
+        return Ok(HttpResponse::Ok().json(BidPageModel { bids: Vec::new(), next_after: None }));
+    }
+    let page = repository.get_bids_page(id, query.after, limit).await?;
+    let mut bids = map_bid_page_to_models(&page, summary.starts_at, summary.open_bidders);
+    if summary.open_bidders {
+        DisplayNames::of_users(&users, page.bids.iter().map(|bid| bid.user().clone()).collect())
+            .await
+            .show_bidders(true, &mut bids);
+    }
+    Ok(HttpResponse::Ok().json(BidPageModel { bids, next_after: page.next_after }))
+}
+
+// Wait for bids newer than a cursor, for clients that cannot keep a streaming connection open
+#[get("/auctions/{auction_id}/bids/poll")]
+pub async fn poll_bids(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    query: web::Query<BidPollQuery>,
+    repository: web::Data<Box<dyn AuctionRepository>>,
+    clock: web::Data<Box<dyn SystemClock>>,
+    events: web::Data<BidEvents>,
+    users: web::Data<UserDirectory>,
+) -> Result<HttpResponse, ApiError> {
+    let id = AuctionId::new(*auction_id);
+    let caller = jwt_payload_handling::from_request(&req);
+    let since = query.since.unwrap_or(0);
+    let timeout = std::time::Duration::from_secs(query.timeout.unwrap_or(30).min(MAX_POLL_TIMEOUT_SECONDS));
+    let deadline = tokio::time::Instant::now() + timeout;
+    // Subscribe before reading so that a bid placed in between is not missed
+    let mut receiver = events.subscribe();
+
+    loop {
+        let auction = look_up_auction(repository.as_ref().as_ref(), id).await?;
+        hide_draft(caller.as_ref(), auction.status(), auction.user())?;
+        let now = clock.now();
+        let new_bids = bids_since(&auction, since, now);
+        let has_ended = auction.has_ended(now);
+
+        let timed_out = tokio::time::Instant::now() >= deadline;
+        if !new_bids.is_empty() || has_ended || timed_out {
+            let mut bids: Vec<BidModel> = new_bids
+                .iter()
+                .map(|bid| BidModel {
+                    id: bid.id,
+                    amount: Cow::Borrowed(bid.amount()),
+                    bidder: Some(Cow::Owned(display_bidder(&auction, bid.user()))),
+                    at: bid.at() - auction.starts_at(),
+                    quantity: bid.quantity(),
+                    lot: bid.lot,
+                })
+                .collect();
+            DisplayNames::of(&users, std::slice::from_ref(&auction))
+                .await
+                .show_bidders(auction.open_bidders(), &mut bids);
+            return Ok(HttpResponse::Ok().json(BidPollModel {
+                cursor: new_bids.iter().map(|bid| bid.id).max().unwrap_or(since),
+                bids,
+                has_ended,
+            }));
         }
+
+        // Wake up on a bid for this auction; after lagging behind, read again to be safe
+        let _ = tokio::time::timeout_at(deadline, async {
+            loop {
+                match receiver.recv().await {
+                    Ok(changed) if changed == id => break,
+                    Ok(_) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => break,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                        std::future::pending::<()>().await
+                    }
+                }
+            }
+        })
+        .await;
+    }
+}
+
+fn parse_tie_break(tie_break: Option<&str>) -> Result<Option<TieBreak>, String> {
+    match tie_break {
+        None => Ok(None),
+        Some("EarliestBid") => Ok(Some(TieBreak::EarliestBid)),
+        Some("LatestBid") => Ok(Some(TieBreak::LatestBid)),
+        Some(other) => Err(format!("Unknown tieBreak {}, expected EarliestBid or LatestBid", other)),
+    }
+}
+
+/// Vickrey auctions round their price by the minimum raise, so rounding needs one.
+fn parse_vickrey_pricing(pricing: Option<&str>, min_raise: Option<i64>) -> Result<Option<VickreyPricing>, String> {
+    let increment = |pricing: &str| match min_raise {
+        Some(increment) if increment > 0 => Ok(increment),
+        _ => Err(format!("vickreyPricing {} needs a positive minRaise", pricing)),
+    };
+    match pricing {
+        None => Ok(None),
+        Some("SecondPrice") => Ok(Some(VickreyPricing::SecondPrice)),
+        Some("RoundUp") => Ok(Some(VickreyPricing::RoundUp { increment: increment("RoundUp")? })),
+        Some("IncrementOver") => Ok(Some(VickreyPricing::IncrementOver { increment: increment("IncrementOver")? })),
+        Some(other) => Err(format!(
+            "Unknown vickreyPricing {}, expected SecondPrice, RoundUp or IncrementOver",
+            other
+        )),
+    }
+}
+
+/// The value in minor units of a price of the auction, which must be in its currency.
+fn price_value(field: &str, price: Option<&Amount>, currency: CurrencyCode) -> Result<Option<i64>, ApiError> {
+    match price {
+        Some(price) if price.currency() != currency => Err(ApiError::bad_request(format!(
+            "{} is in {}, but the auction is in {}",
+            field,
+            price.currency(),
+            currency
+        ))),
+        price => Ok(price.map(Amount::value)),
     }
 }
 
@@ -89,44 +985,78 @@ pub async fn create_auction(
     model: web::Json<CreateAuctionModel>,
     clock: web::Data<Box<dyn SystemClock>>,
     handler: web::Data<Box<dyn CreateAuctionCommandHandler>>,
-) -> impl Responder {
+) -> Result<HttpResponse, ApiError> {
     // TODO: Move to configurable middleware
     let user = jwt_payload_handling::from_request(&req);
     // Convert API model to domain command
     let single_sealed_bid_options = match model.single_sealed_bid_options.as_deref() {
         Some("Blind") => Some(SingleSealedBidOptions::Blind),
         Some("Vickrey") => Some(SingleSealedBidOptions::Vickrey),
+        Some("AllPay") => Some(SingleSealedBidOptions::AllPay),
         _ => None,
     };
+    let tie_break = parse_tie_break(model.tie_break.as_deref()).map_err(ApiError::bad_request)?;
+    let currency = model.currency;
+    let min_raise = price_value("minRaise", model.min_raise.as_ref(), currency)?;
+    let vickrey_pricing =
+        parse_vickrey_pricing(model.vickrey_pricing.as_deref(), min_raise).map_err(ApiError::bad_request)?;
+    let increments = model
+        .increments
+        .iter()
+        .map(|step| {
+            Ok(BidIncrement {
+                below: price_value("increments", Some(&step.below), currency)?.unwrap_or_default(),
+                increment: price_value("increments", Some(&step.increment), currency)?.unwrap_or_default(),
+            })
+        })
+        .collect::<Result<_, ApiError>>()?;
+    let fee_policy = match &model.fees {
+        Some(fees) => Some(FeePolicy {
+            buyers_premium_bps: fees.buyers_premium_bps,
+            buyer_fee: price_value("buyerFee", fees.buyer_fee.as_ref(), currency)?.unwrap_or_default(),
+            seller_commission_bps: fees.seller_commission_bps,
+            seller_fee: price_value("sellerFee", fees.seller_fee.as_ref(), currency)?.unwrap_or_default(),
+        }),
+        None => None,
+    };
     
     let time_frame = model.time_frame.map(|seconds| chrono::Duration::seconds(seconds));
     
     let command = CreateAuctionCommand {
         title: model.title.clone(),
-        currency: model.currency,
+        description: model.description.clone(),
+        currency,
         starts_at: model.starts_at,
         ends_at: model.ends_at,
-        min_raise: model.min_raise,
-        reserve_price: model.reserve_price,
+        min_raise,
+        reserve_price: price_value("reservePrice", model.reserve_price.as_ref(), currency)?,
         time_frame,
+        buy_now_price: price_value("buyNowPrice", model.buy_now_price.as_ref(), currency)?,
+        starting_price: price_value("startingPrice", model.starting_price.as_ref(), currency)?,
+        max_extension: model.max_extension.map(chrono::Duration::seconds),
+        increments,
         single_sealed_bid_options,
+        tie_break,
         open_bidders: model.open_bidders,
+        requires_registration: model.requires_registration,
+        deposit: price_value("deposit", model.deposit.as_ref(), currency)?,
+        relist_policy: model.relist.as_ref().map(|relist| RelistPolicy {
+            duration_seconds: relist.duration,
+            max_relists: relist.max_relists,
+        }),
+        quantity: model.quantity,
+        external_reference: model.external_reference.clone(),
+        seller_ip_hash: ip_hash_from_request(&req),
+        lots: model.lots.iter().map(|lot| lot.title.clone()).collect(),
+        draft: model.draft,
+        fee_policy,
+        tax_rate_bps: model.tax_rate_bps,
+        vickrey_pricing,
     };
 
-    match handler.handle(user, command).await {
-        Ok(auction) => {
-            let now = clock.now();
-            // Return the created auction
-            HttpResponse::Created().json(map_auction_to_model(&auction, now))
-        },
-        Err(Error::Unauthorized(msg)) => {
-            HttpResponse::Unauthorized().json(msg)
-        },
-        Err(e) => {
-            error!("Error creating auction: {:?}", e);
-            HttpResponse::InternalServerError().json(format!("Internal server error: {}", e))
-        }
-    }
+    let auction = handler.handle(user, command).await?;
+    // Return the created auction
+    Ok(HttpResponse::Created().json(map_auction_to_detail_model(&auction, clock.now())))
 }
 
 // Create a bid
@@ -136,33 +1066,281 @@ pub async fn create_bid(
     auction_id: web::Path<i64>,
     model: web::Json<CreateBidModel>,
     handler: web::Data<Box<dyn CreateBidCommandHandler>>,
-) -> impl Responder {
+) -> Result<HttpResponse, ApiError> {
+    place_bid(req, AuctionId::new(*auction_id), None, model.into_inner(), handler).await
+}
+
+// Create a bid on one lot of an auction with several
+#[post("/auctions/{auction_id}/lots/{lot}/bids")]
+pub async fn create_lot_bid(
+    req: HttpRequest,
+    path: web::Path<(i64, i32)>,
+    model: web::Json<CreateBidModel>,
+    handler: web::Data<Box<dyn CreateBidCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    let (auction_id, lot) = path.into_inner();
+    place_bid(req, AuctionId::new(auction_id), Some(lot), model.into_inner(), handler).await
+}
+
+async fn place_bid(
+    req: HttpRequest,
+    id: AuctionId,
+    lot: Option<i32>,
+    model: CreateBidModel,
+    handler: web::Data<Box<dyn CreateBidCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
     // TODO: Move to configurable middleware
     let user = jwt_payload_handling::from_request(&req);
 
-    let id = AuctionId::new(*auction_id);
-    
     // Convert API model to domain command
     let command = CreateBidCommand {
-        amount: model.amount.clone(),
+        amount: model.amount,
         auction_id: id,
+        lot,
+        quantity: model.quantity,
+        max_bid: model.max_bid,
+        metadata: Some(bid_metadata_from_request(&req)),
     };
     
-    match handler.handle(user, command).await {
-        Ok(_) => {
-            HttpResponse::Ok().finish()
-        },
-        Err(Error::Validation(Errors::UnknownAuction)) => HttpResponse::NotFound().finish(),
-        Err(Error::Validation(errors)) => HttpResponse::BadRequest().json(errors.to_string()),
+    handler.handle(user, command).await?;
+    Ok(HttpResponse::Ok().finish())
+}
 
-        Err(Error::Unauthorized(msg)) => {
-            HttpResponse::Unauthorized().json(msg)
-        },
-        Err(e) => {
-            error!("Error creating bid: {:?}", e);
-            HttpResponse::InternalServerError().json(format!("Internal server error: {}", e))
+// Retract the caller's latest bid, shortly after placing it
+#[delete("/auctions/{auction_id}/bids")]
+pub async fn retract_bid(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    clock: web::Data<Box<dyn SystemClock>>,
+    handler: web::Data<Box<dyn RetractBidCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    // TODO: Move to configurable middleware
+    let user = jwt_payload_handling::from_request(&req);
+    let command = RetractBidCommand {
+        auction_id: AuctionId::new(*auction_id),
+    };
+
+    let auction = handler.handle(user, command).await?;
+    Ok(HttpResponse::Ok().json(map_auction_to_detail_model(&auction, clock.now())))
+}
+
+// Void a bid, e.g. a fraudulent one, on a running auction; support only
+#[delete("/auctions/{auction_id}/bids/{bid_id}")]
+pub async fn void_bid(
+    req: HttpRequest,
+    path: web::Path<(i64, i64)>,
+    query: web::Query<VoidBidQuery>,
+    clock: web::Data<Box<dyn SystemClock>>,
+    handler: web::Data<Box<dyn AdminAuctionCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    // TODO: Move to configurable middleware
+    let user = jwt_payload_handling::user_from_request(&req);
+    let (auction_id, bid_id) = path.into_inner();
+    let command = AdminAuctionCommand::VoidBid {
+        auction_id: AuctionId::new(auction_id),
+        bid_id,
+        reason: query.into_inner().reason,
+    };
+
+    let auction = handler.handle(user, command).await?;
+    Ok(HttpResponse::Ok().json(map_auction_to_detail_model(&auction, clock.now())))
+}
+
+/// Reads an RFC 7396 merge patch. Only the descriptive fields of an auction may be patched.
+fn parse_merge_patch(auction_id: AuctionId, patch: &Value) -> Result<PatchAuctionCommand, String> {
+    let fields = patch
+        .as_object()
+        .ok_or_else(|| "A merge patch must be a JSON object".to_string())?;
+    let mut command = PatchAuctionCommand {
+        auction_id,
+        title: None,
+        description: None,
+    };
+    for (key, value) in fields {
+        match (key.as_str(), value) {
+            ("title", Value::String(title))
+                if !title.trim().is_empty() && title.chars().count() <= 200 =>
+            {
+                command.title = Some(title.clone())
+            }
+            ("title", _) => {
+                return Err("title must be a non-empty string of at most 200 characters".to_string())
+            }
+            ("description", Value::Null) => command.description = Some(None),
+            ("description", Value::String(description)) => {
+                command.description = Some(Some(description.clone()))
+            }
+            ("description", _) => return Err("description must be a string or null".to_string()),
+            (other, _) => return Err(format!("{} cannot be changed", other)),
         }
     }
+    Ok(command)
+}
+
+// Change the descriptive fields of an auction
+#[patch("/auctions/{auction_id}")]
+pub async fn patch_auction(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    patch: web::Json<Value>,
+    clock: web::Data<Box<dyn SystemClock>>,
+    handler: web::Data<Box<dyn PatchAuctionCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    // TODO: Move to configurable middleware
+    let user = jwt_payload_handling::from_request(&req);
+
+    let command = parse_merge_patch(AuctionId::new(*auction_id), &patch).map_err(ApiError::bad_request)?;
+
+    let auction = handler.handle(user, command).await?;
+    Ok(HttpResponse::Ok().json(map_auction_to_detail_model(&auction, clock.now())))
+}
+
+// Cancel an auction: sellers before any bids are placed, support at any time
+#[delete("/auctions/{auction_id}")]
+pub async fn cancel_auction(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    query: web::Query<CancelAuctionQuery>,
+    clock: web::Data<Box<dyn SystemClock>>,
+    handler: web::Data<Box<dyn CancelAuctionCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    // TODO: Move to configurable middleware
+    let user = jwt_payload_handling::user_from_request(&req);
+    let command = CancelAuctionCommand {
+        auction_id: AuctionId::new(*auction_id),
+        reason: query.into_inner().reason,
+    };
+
+    let auction = handler.handle(user, command).await?;
+    Ok(HttpResponse::Ok().json(map_auction_to_detail_model(&auction, clock.now())))
+}
+
+// Publish a draft auction; seller only
+#[post("/auctions/{auction_id}/publish")]
+pub async fn publish_auction(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    clock: web::Data<Box<dyn SystemClock>>,
+    handler: web::Data<Box<dyn PublishAuctionCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    // TODO: Move to configurable middleware
+    let user = jwt_payload_handling::from_request(&req);
+    let command = PublishAuctionCommand {
+        auction_id: AuctionId::new(*auction_id),
+    };
+
+    let auction = handler.handle(user, command).await?;
+    Ok(HttpResponse::Ok().json(map_auction_to_detail_model(&auction, clock.now())))
+}
+
+fn map_registration_to_model(registration: &Registration) -> RegistrationModel {
+    RegistrationModel {
+        auction_id: registration.auction_id.value(),
+        user: registration.user.to_string(),
+        status: registration.status.to_string(),
+        registered_at: registration.registered_at,
+        decided_at: registration.decided_at,
+    }
+}
+
+fn registration_response(result: Result<Registration, Error>) -> Result<HttpResponse, ApiError> {
+    Ok(HttpResponse::Ok().json(map_registration_to_model(&result?)))
+}
+
+// Ask the seller for approval to bid
+#[post("/auctions/{auction_id}/registrations")]
+pub async fn register_bidder(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    handler: web::Data<Box<dyn RegistrationCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    // TODO: Move to configurable middleware
+    let user = jwt_payload_handling::from_request(&req);
+    let command = RegistrationCommand::Register {
+        auction_id: AuctionId::new(*auction_id),
+    };
+    registration_response(handler.handle(user, command).await)
+}
+
+// The registrations of an auction, for its seller
+#[get("/auctions/{auction_id}/registrations")]
+pub async fn get_registrations(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    handler: web::Data<Box<dyn RegistrationCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    // TODO: Move to configurable middleware
+    let user = jwt_payload_handling::from_request(&req);
+
+    let registrations = handler.registrations(user, AuctionId::new(*auction_id)).await?;
+    Ok(HttpResponse::Ok().json(
+        registrations.iter().map(map_registration_to_model).collect::<Vec<_>>(),
+    ))
+}
+
+/// The bidder of a registration, as it appears in the path.
+fn bidder_from_path(user_id: &str) -> Result<UserId, ApiError> {
+    UserId::parse(user_id).map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, "INVALID_USER", e.to_string()))
+}
+
+// Let a registered bidder bid
+#[post("/auctions/{auction_id}/registrations/{user_id}/approve")]
+pub async fn approve_registration(
+    req: HttpRequest,
+    path: web::Path<(i64, String)>,
+    handler: web::Data<Box<dyn RegistrationCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    // TODO: Move to configurable middleware
+    let user = jwt_payload_handling::from_request(&req);
+    let (auction_id, user_id) = path.into_inner();
+    let bidder = bidder_from_path(&user_id)?;
+    let command = RegistrationCommand::Approve {
+        auction_id: AuctionId::new(auction_id),
+        bidder,
+    };
+    registration_response(handler.handle(user, command).await)
+}
+
+// Turn down a registered bidder
+#[post("/auctions/{auction_id}/registrations/{user_id}/reject")]
+pub async fn reject_registration(
+    req: HttpRequest,
+    path: web::Path<(i64, String)>,
+    handler: web::Data<Box<dyn RegistrationCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    // TODO: Move to configurable middleware
+    let user = jwt_payload_handling::from_request(&req);
+    let (auction_id, user_id) = path.into_inner();
+    let bidder = bidder_from_path(&user_id)?;
+    let command = RegistrationCommand::Reject {
+        auction_id: AuctionId::new(auction_id),
+        bidder,
+    };
+    registration_response(handler.handle(user, command).await)
+}
+
+// Record what the caller has deposited to bid in the auction
+#[post("/auctions/{auction_id}/deposits")]
+pub async fn record_deposit(
+    req: HttpRequest,
+    auction_id: web::Path<i64>,
+    model: web::Json<RecordDepositModel>,
+    handler: web::Data<Box<dyn RecordDepositCommandHandler>>,
+) -> Result<HttpResponse, ApiError> {
+    // TODO: Move to configurable middleware
+    let user = jwt_payload_handling::from_request(&req);
+    let command = RecordDepositCommand {
+        auction_id: AuctionId::new(*auction_id),
+        amount: model.amount.clone(),
+    };
+
+    let deposit = handler.handle(user, command).await?;
+    Ok(HttpResponse::Ok().json(DepositModel {
+        auction_id: deposit.auction_id.value(),
+        user: deposit.user.to_string(),
+        amount: deposit.amount,
+        recorded_at: deposit.recorded_at,
+    }))
 }
 
 // Configure routes
@@ -170,6 +1348,481 @@ pub fn get_scope() -> Scope {
     web::scope("")
             .service(get_auctions)
             .service(create_auction)
+            // Before the auction ids, that would otherwise take `mine` for one
+            .service(get_active_auctions)
+            .service(get_my_auctions)
             .service(get_auction)
+            .service(get_auction_snapshot)
+            .service(get_settlement)
+            .service(poll_bids)
+            .service(get_bids)
+            .service(patch_auction)
+            .service(cancel_auction)
+            .service(publish_auction)
             .service(create_bid)
+            .service(create_lot_bid)
+            .service(retract_bid)
+            .service(void_bid)
+            .service(register_bidder)
+            .service(get_registrations)
+            .service(approve_registration)
+            .service(reject_registration)
+            .service(record_deposit)
+}
+
+#[cfg(test)]
+mod merge_patch_tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_patch_sets_and_removes_fields() {
+        let command = parse_merge_patch(
+            AuctionId::new(1),
+            &json!({"title": "New title", "description": null}),
+        )
+        .unwrap();
+        assert_eq!(command.title, Some("New title".to_string()));
+        assert_eq!(command.description, Some(None));
+    }
+
+    #[test]
+    fn test_merge_patch_leaves_absent_fields() {
+        let command = parse_merge_patch(AuctionId::new(1), &json!({"description": "Mint"})).unwrap();
+        assert_eq!(command.title, None);
+        assert_eq!(command.description, Some(Some("Mint".to_string())));
+    }
+
+    #[test]
+    fn test_merge_patch_rejects_immutable_and_invalid_fields() {
+        assert!(parse_merge_patch(AuctionId::new(1), &json!({"currency": "SEK"})).is_err());
+        assert!(parse_merge_patch(AuctionId::new(1), &json!({"title": null})).is_err());
+        assert!(parse_merge_patch(AuctionId::new(1), &json!({"title": ""})).is_err());
+        assert!(parse_merge_patch(AuctionId::new(1), &json!(["title"])).is_err());
+    }
+}
+
+#[cfg(test)]
+mod poll_tests {
+    use super::*;
+    use crate::domain::test_support::{auction, lamp, starts_at, with_bid};
+    use chrono::Duration;
+
+    fn auction_with_bid(single_sealed_bid_options: Option<SingleSealedBidOptions>) -> Auction {
+        with_bid(auction(CreateAuctionCommand { single_sealed_bid_options, ..lamp() }), "buyer", 10)
+    }
+
+    #[test]
+    fn test_polls_bids_newer_than_the_cursor() {
+        let auction = auction_with_bid(None);
+        let now = starts_at() + Duration::hours(2);
+
+        assert_eq!(bids_since(&auction, 0, now).len(), 1);
+        assert!(bids_since(&auction, 1, now).is_empty());
+    }
+
+    #[test]
+    fn test_polling_never_returns_sealed_bids_of_running_auctions() {
+        let auction = auction_with_bid(Some(SingleSealedBidOptions::Vickrey));
+        let now = starts_at() + Duration::hours(2);
+
+        assert!(bids_sealed(&auction, now));
+        assert!(bids_since(&auction, 0, now).is_empty());
+        assert!(!bids_sealed(&auction, starts_at() + Duration::days(8)));
+    }
+}
+
+#[cfg(test)]
+mod create_auction_tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_unknown_tie_breaks() {
+        assert_eq!(parse_tie_break(None), Ok(None));
+        assert_eq!(parse_tie_break(Some("LatestBid")), Ok(Some(TieBreak::LatestBid)));
+        let error = parse_tie_break(Some("Random")).unwrap_err();
+        assert!(error.contains("EarliestBid") && error.contains("LatestBid"), "{}", error);
+    }
+
+    #[test]
+    fn test_rejects_unknown_vickrey_pricing() {
+        assert_eq!(parse_vickrey_pricing(None, None), Ok(None));
+        assert_eq!(
+            parse_vickrey_pricing(Some("RoundUp"), Some(10)),
+            Ok(Some(VickreyPricing::RoundUp { increment: 10 }))
+        );
+        let error = parse_vickrey_pricing(Some("FirstPrice"), Some(10)).unwrap_err();
+        assert!(error.contains("SecondPrice") && error.contains("IncrementOver"), "{}", error);
+    }
+
+    #[test]
+    fn test_rounding_vickrey_prices_needs_a_positive_min_raise() {
+        assert!(parse_vickrey_pricing(Some("RoundUp"), None).is_err());
+        assert!(parse_vickrey_pricing(Some("IncrementOver"), Some(0)).is_err());
+        assert_eq!(parse_vickrey_pricing(Some("SecondPrice"), None), Ok(Some(VickreyPricing::SecondPrice)));
+    }
+
+    #[test]
+    fn test_reads_prices_in_whole_units_like_bids() {
+        let model: CreateAuctionModel = serde_json::from_value(serde_json::json!({
+            "title": "Lamp",
+            "currency": "SEK",
+            "startsAt": "2026-01-01T00:00:00Z",
+            "endsAt": "2026-01-08T00:00:00Z",
+            "reservePrice": "SEK100.50",
+            "minRaise": { "value": 10, "currency": "SEK" },
+            "increments": [{ "below": "SEK1000", "increment": "SEK50" }],
+        }))
+        .unwrap();
+
+        let currency = model.currency;
+        assert_eq!(price_value("reservePrice", model.reserve_price.as_ref(), currency).unwrap(), Some(10050));
+        assert_eq!(price_value("minRaise", model.min_raise.as_ref(), currency).unwrap(), Some(1000));
+        assert_eq!(model.increments[0].increment, Amount::new(5000, CurrencyCode::SEK));
+        assert_eq!(price_value("deposit", None, currency).unwrap(), None);
+    }
+
+    #[test]
+    fn test_rejects_prices_in_another_currency() {
+        let price = Amount::new(100, CurrencyCode::EUR);
+        assert!(price_value("reservePrice", Some(&price), CurrencyCode::SEK).is_err());
+    }
+}
+
+#[cfg(test)]
+mod auction_ids_tests {
+    use super::*;
+    use crate::domain::test_support::{auction, lamp};
+    use chrono::Duration;
+
+    #[test]
+    fn test_parses_ids_in_order_without_duplicates() {
+        let ids = parse_auction_ids("3, 1,3,,2").unwrap();
+
+        assert_eq!(ids, vec![AuctionId::new(3), AuctionId::new(1), AuctionId::new(2)]);
+    }
+
+    #[test]
+    fn test_pages_default_to_the_first_page() {
+        assert_eq!(parse_page(None, None), Ok((DEFAULT_PAGE_SIZE, 0)));
+        assert_eq!(parse_page(Some(10), Some(20)), Ok((10, 20)));
+        assert!(parse_page(Some(0), None).is_err());
+        assert!(parse_page(Some(MAX_PAGE_SIZE + 1), None).is_err());
+        assert!(parse_page(None, Some(-1)).is_err());
+    }
+
+    #[test]
+    fn test_parses_the_bids_limit() {
+        assert_eq!(parse_bids_limit(None), Ok(DEFAULT_PAGE_SIZE));
+        assert!(parse_bids_limit(Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_rejects_invalid_and_too_many_ids() {
+        assert!(parse_auction_ids("1,two").is_err());
+        let ids: Vec<String> = (1..=MAX_BATCH_IDS as i64 + 1).map(|id| id.to_string()).collect();
+        assert!(parse_auction_ids(&ids.join(",")).is_err());
+    }
+
+    #[test]
+    fn test_lists_removed_auctions_apart_from_missing_ones() {
+        let now = Utc::now();
+        let mut auctions: Vec<Auction> = (1..=2)
+            .map(|id| {
+                let mut auction = auction(CreateAuctionCommand {
+                    starts_at: now - Duration::hours(1),
+                    ends_at: now + Duration::days(7),
+                    ..lamp()
+                });
+                auction.set_auction_id(AuctionId::new(id));
+                auction
+            })
+            .collect();
+        auctions[1].cancel(now, Some("withdrawn".to_string())).unwrap();
+
+        let ids = [AuctionId::new(1), AuctionId::new(2), AuctionId::new(3)];
+        let batch = batch_of(&ids, &auctions, None, |auction| map_auction_to_summary_model(auction, now, false));
+
+        assert_eq!(batch.auctions.iter().map(|auction| auction.id).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(batch.removed.len(), 1);
+        assert_eq!(batch.removed[0].id, 2);
+        assert_eq!(batch.removed[0].reason.as_deref(), Some("withdrawn"));
+        assert_eq!(batch.missing, vec![3]);
+    }
+}
+
+#[cfg(test)]
+mod listing_tests {
+    use super::*;
+    use crate::domain::models::CurrencyCode;
+    use crate::domain::test_support::{auction, lamp, starts_at, with_bid};
+    use chrono::Duration;
+
+    fn auction_with_bid(single_sealed_bid_options: Option<SingleSealedBidOptions>, open_bidders: bool) -> Auction {
+        let command = CreateAuctionCommand { single_sealed_bid_options, open_bidders, ..lamp() };
+        with_bid(auction(command), "buyer", 10)
+    }
+
+    #[test]
+    fn test_listing_leaves_out_bids_unless_included() {
+        let auction = auction_with_bid(None, false);
+        let now = starts_at() + Duration::hours(2);
+
+        let listed = map_auction_to_summary_model(&auction, now, false);
+        assert!(listed.bids.is_none());
+        assert_eq!(listed.bid_count, 1);
+        assert_eq!(listed.current_price, Some(Amount::new(10, CurrencyCode::SEK)));
+
+        let listed = map_auction_to_summary_model(&auction, now, true);
+        assert_eq!(listed.bids.map(|bids| bids.len()), Some(1));
+    }
+
+    #[test]
+    fn test_listing_never_includes_sealed_bids_of_running_auctions() {
+        let auction = auction_with_bid(Some(SingleSealedBidOptions::Vickrey), false);
+
+        let listed = map_auction_to_summary_model(&auction, starts_at() + Duration::hours(2), true);
+
+        assert!(listed.bids.is_none());
+        assert_eq!(listed.current_price, None);
+    }
+
+    #[test]
+    fn test_users_are_shown_by_display_name_unless_bidders_are_aliased() {
+        let names = DisplayNames(HashMap::from([
+            (UserId::new("seller"), "Sally Seller".to_string()),
+            (UserId::new("buyer"), "Bob Buyer".to_string()),
+        ]));
+        let now = starts_at() + Duration::hours(2);
+
+        let auction = auction_with_bid(None, true);
+        let mut bids = map_auction_to_summary_model(&auction, now, true).bids.unwrap();
+        names.show_bidders(auction.open_bidders(), &mut bids);
+        assert_eq!(names.name(auction.user()), "Sally Seller");
+        assert_eq!(bids[0].bidder.as_deref(), Some("Bob Buyer"));
+        assert_eq!(names.name(&UserId::new("unnamed")), "unnamed");
+
+        let auction = auction_with_bid(None, false);
+        let mut bids = map_auction_to_summary_model(&auction, now, true).bids.unwrap();
+        let alias = bids[0].bidder.clone();
+        names.show_bidders(auction.open_bidders(), &mut bids);
+        assert_eq!(bids[0].bidder, alias);
+    }
+
+    #[test]
+    fn test_bid_pages_show_bidders_like_the_auction() {
+        let auction = auction_with_bid(None, false);
+        let page = BidPage::of(&auction, None, 10);
+
+        let bids = map_bid_page_to_models(&page, auction.starts_at(), false);
+        assert_eq!(bids[0].bidder.as_deref(), Some("Bidder A"));
+        assert_eq!(bids[0].at, Duration::hours(1));
+        let bids = map_bid_page_to_models(&page, auction.starts_at(), true);
+        assert_eq!(bids[0].bidder.as_deref(), Some("buyer"));
+    }
+
+    #[test]
+    fn test_only_bids_can_be_included() {
+        assert_eq!(parse_include(None), Ok(false));
+        assert_eq!(parse_include(Some("bids")), Ok(true));
+        assert!(parse_include(Some("bids,secrets")).is_err());
+    }
+}
+
+#[cfg(test)]
+mod draft_tests {
+    use super::*;
+    use crate::domain::models::User;
+    use crate::domain::services::{FixedRateCurrencyConverter, FixedSystemClock};
+    use crate::domain::test_support::{auction, lamp, starts_at};
+    use crate::infrastructure::data::{
+        InMemoryAuctionRepository, InMemoryAuctionResultRepository, InMemorySettlementRepository, InMemoryUserRepository,
+    };
+    use crate::infrastructure::jwt_payload_handling::{encode_jwt_payload, X_JWT_PAYLOAD};
+    use actix_web::test::{call_service, init_service, read_body, TestRequest};
+    use actix_web::App;
+
+    const READS: [&str; 5] = [
+        "/auctions/1",
+        "/auctions/1/snapshot",
+        "/auctions/1/settlement",
+        "/auctions/1/bids",
+        "/auctions/1/bids/poll?timeout=0",
+    ];
+
+    /// Reads `path` of a draft listed by "seller", as `caller` or anonymously.
+    async fn read(path: &str, caller: Option<&str>) -> (StatusCode, Vec<u8>) {
+        let draft = auction(CreateAuctionCommand { draft: true, ..lamp() });
+        let clock = FixedSystemClock(starts_at());
+        let app = init_service(
+            App::new()
+                .app_data(web::Data::new(
+                    Box::new(InMemoryAuctionRepository::new(vec![draft])) as Box<dyn AuctionRepository>
+                ))
+                .app_data(web::Data::new(AuctionResultRecorder::new(Box::new(
+                    InMemoryAuctionResultRepository::default(),
+                ))))
+                .app_data(web::Data::new(SettlementRecorder::new(
+                    Box::new(InMemorySettlementRepository::default()),
+                    FeePolicy::default(),
+                )))
+                .app_data(web::Data::new(UserDirectory::new(
+                    Box::new(InMemoryUserRepository::default()),
+                    Box::new(clock.clone()),
+                )))
+                .app_data(web::Data::new(
+                    Box::new(FixedRateCurrencyConverter::new(HashMap::new())) as Box<dyn CurrencyConverter>
+                ))
+                .app_data(web::Data::new(Box::new(clock) as Box<dyn SystemClock>))
+                .app_data(web::Data::new(BidEvents::new(1)))
+                .service(get_scope()),
+        )
+        .await;
+        let mut req = TestRequest::get().uri(path);
+        if let Some(caller) = caller {
+            let user = User::new_buyer_or_seller(UserId::new(caller), None::<String>);
+            req = req.insert_header((X_JWT_PAYLOAD, encode_jwt_payload(&user)));
+        }
+        let response = call_service(&app, req.to_request()).await;
+        (response.status(), read_body(response).await.to_vec())
+    }
+
+    async fn status_of(path: &str, caller: Option<&str>) -> StatusCode {
+        read(path, caller).await.0
+    }
+
+    async fn missing_from_batch(caller: Option<&str>) -> Vec<i64> {
+        let (_, body) = read("/auctions?ids=1", caller).await;
+        serde_json::from_slice::<AuctionBatchModel<Value>>(&body).unwrap().missing
+    }
+
+    #[actix_web::test]
+    async fn test_drafts_are_not_found_by_anyone_but_their_seller() {
+        for path in READS {
+            assert_eq!(status_of(path, None).await, StatusCode::NOT_FOUND, "{} anonymously", path);
+            assert_eq!(status_of(path, Some("buyer")).await, StatusCode::NOT_FOUND, "{} by another user", path);
+        }
+        assert_eq!(missing_from_batch(None).await, vec![1]);
+        assert_eq!(missing_from_batch(Some("buyer")).await, vec![1]);
+    }
+
+    #[actix_web::test]
+    async fn test_sellers_read_their_drafts() {
+        for path in READS {
+            let expected = match path {
+                // Drafts are never settled
+                "/auctions/1/settlement" => StatusCode::CONFLICT,
+                _ => StatusCode::OK,
+            };
+            assert_eq!(status_of(path, Some("seller")).await, expected, "{} by the seller", path);
+        }
+        assert!(missing_from_batch(Some("seller")).await.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod detail_tests {
+    use super::*;
+    use crate::domain::services::{FixedRateCurrencyConverter, FixedSystemClock};
+    use crate::domain::test_support::{auction, bid, lamp, starts_at};
+    use crate::infrastructure::data::{InMemoryAuctionRepository, InMemoryAuctionResultRepository, InMemoryUserRepository};
+    use async_trait::async_trait;
+    use actix_web::test::{call_service, init_service, read_body_json, TestRequest};
+    use actix_web::App;
+    use chrono::Duration;
+
+    /// Reads only what the detail of an auction shows, failing on reading it with every bid.
+    #[derive(Clone)]
+    struct WithoutWholeReads(InMemoryAuctionRepository);
+
+    #[async_trait]
+    impl AuctionRepository for WithoutWholeReads {
+        async fn get_auction(&self, _: AuctionId) -> Result<Option<Auction>, Error> {
+            panic!("the auction was read with every bid")
+        }
+        async fn get_auction_summary(&self, auction_id: AuctionId) -> Result<Option<AuctionSummary>, Error> {
+            self.0.get_auction_summary(auction_id).await
+        }
+        async fn get_auction_without_bids(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+            self.0.get_auction_without_bids(auction_id).await
+        }
+        async fn get_auction_standings(&self, auction_id: AuctionId) -> Result<Option<AuctionStandings>, Error> {
+            self.0.get_auction_standings(auction_id).await
+        }
+        async fn get_bids_page(&self, auction_id: AuctionId, after_bid_id: Option<i64>, limit: i64) -> Result<BidPage, Error> {
+            self.0.get_bids_page(auction_id, after_bid_id, limit).await
+        }
+        async fn get_auctions(&self) -> Result<Vec<Auction>, Error> {
+            panic!("every auction was read")
+        }
+        async fn get_auctions_by_seller(&self, _: &UserId) -> Result<Vec<Auction>, Error> {
+            unreachable!()
+        }
+        async fn get_auctions_by_ids(&self, _: &[AuctionId]) -> Result<Vec<Auction>, Error> {
+            unreachable!()
+        }
+        async fn get_auction_by_external_reference(&self, _: &UserId, _: &str) -> Result<Option<Auction>, Error> {
+            unreachable!()
+        }
+        async fn create_auction(&self, _: Auction) -> Result<Auction, Error> {
+            unreachable!()
+        }
+        async fn update_auction(&self, _: Auction) -> Result<Auction, Error> {
+            unreachable!()
+        }
+        async fn auction_exists(&self, _: AuctionId) -> Result<bool, Error> {
+            unreachable!()
+        }
+        async fn count_auctions(&self, _: &AuctionFilter) -> Result<i64, Error> {
+            unreachable!()
+        }
+        async fn count_bids(&self, _: AuctionId) -> Result<i64, Error> {
+            unreachable!()
+        }
+        async fn anonymize_user(&self, _: &UserId, _: &UserId) -> Result<Vec<AuctionId>, Error> {
+            unreachable!()
+        }
+    }
+
+    /// The detail of `auction` as shown at `now`, with at most `bids_limit` bids.
+    async fn detail(auction: Auction, now: DateTime<Utc>, bids_limit: i64) -> Value {
+        let clock = FixedSystemClock(now);
+        let repository = WithoutWholeReads(InMemoryAuctionRepository::new(vec![auction]));
+        let app = init_service(
+            App::new()
+                .app_data(web::Data::new(Box::new(repository) as Box<dyn AuctionRepository>))
+                .app_data(web::Data::new(AuctionResultRecorder::new(Box::new(
+                    InMemoryAuctionResultRepository::default(),
+                ))))
+                .app_data(web::Data::new(UserDirectory::new(
+                    Box::new(InMemoryUserRepository::default()),
+                    Box::new(clock.clone()),
+                )))
+                .app_data(web::Data::new(
+                    Box::new(FixedRateCurrencyConverter::new(HashMap::new())) as Box<dyn CurrencyConverter>
+                ))
+                .app_data(web::Data::new(Box::new(clock) as Box<dyn SystemClock>))
+                .service(get_scope()),
+        )
+        .await;
+        let req = TestRequest::get().uri(&format!("/auctions/1?bids_limit={}", bids_limit)).to_request();
+        let response = call_service(&app, req).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        read_body_json(response).await
+    }
+
+    #[actix_web::test]
+    async fn test_ended_auctions_are_shown_from_their_standings() {
+        let mut lamp = auction(lamp());
+        for i in 0..30 {
+            let at = starts_at() + Duration::hours(i + 1);
+            lamp.try_add_bid(at, bid(&format!("buyer{}", i % 3), 100 * (i + 1), at)).unwrap();
+        }
+        let shown = detail(lamp, starts_at() + Duration::days(8), 5).await;
+        assert_eq!(shown["bids"].as_array().unwrap().len(), 5);
+        assert_eq!(shown["bidCount"], 30);
+        assert_eq!(shown["winner"], "Bidder C");
+        assert_eq!(shown["charges"].as_array().unwrap().len(), 1);
+        assert_eq!(shown["result"]["winner"], "Bidder C");
+    }
 }
@@ -0,0 +1,24 @@
+use actix_web::{get, web, HttpResponse};
+
+use crate::api::api_error::ApiError;
+use crate::api::models::CurrencyModel;
+use crate::infrastructure::CurrencyRepository;
+
+// Get the currencies auctions can be listed in
+#[get("/currencies")]
+pub async fn get_currencies(
+    query: web::Data<Box<dyn CurrencyRepository>>,
+) -> Result<HttpResponse, ApiError> {
+    let models: Vec<CurrencyModel> = query
+        .get_currencies()
+        .await?
+        .into_iter()
+        .map(|currency| CurrencyModel {
+            code: currency.code,
+            numeric_code: currency.numeric_code,
+            name: currency.name,
+            minor_units: currency.minor_units,
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(models))
+}
@@ -0,0 +1,25 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use std::collections::HashMap;
+
+use crate::infrastructure::services::QueuedCreateBidCommandHandler;
+
+// Operational metrics
+#[get("/metrics")]
+pub async fn get_metrics(
+    bid_queue: web::Data<Option<QueuedCreateBidCommandHandler>>,
+) -> impl Responder {
+    let bid_queue_depth: HashMap<i64, usize> = bid_queue
+        .get_ref()
+        .as_ref()
+        .map(|queue| {
+            queue
+                .queue_depths()
+                .into_iter()
+                .map(|(auction_id, depth)| (auction_id.value(), depth))
+                .collect()
+        })
+        .unwrap_or_default();
+    HttpResponse::Ok().json(serde_json::json!({
+        "bidQueueDepth": bid_queue_depth,
+    }))
+}
@@ -1 +1,6 @@
-pub mod auctions;
\ No newline at end of file
+pub mod admin;
+pub mod api_changes;
+pub mod auctions;
+pub mod currencies;
+pub mod metrics;
+pub mod stats;
@@ -0,0 +1,48 @@
+//! Public statistics.
+//!
+//! Buckets that only a few listings, bids or sales went into could reveal what a single bidder
+//! or seller did. With `[stats_privacy] enabled = true` the rollups are published with:
+//!
+//! - an aggregation threshold: buckets with fewer than `min_cohort_size` contributing events
+//!   are left out,
+//! - Laplace noise with scale `sensitivity / epsilon` added to every published value, where the
+//!   sensitivity is 1 for counts and `amount_sensitivity` for gmv. Smaller `epsilon` means more
+//!   noise and stronger protection; values are rounded and never negative.
+//!
+//! Noise is drawn anew for every response, so repeating a query and averaging the answers weakens
+//! the protection. Caches in front of the API should be used to hand out the same answer.
+use actix_web::{get, web, HttpResponse};
+
+use crate::api::api_error::ApiError;
+use crate::api::models::{RollupPointModel, RollupQuery};
+use crate::infrastructure::services::StatsPrivacy;
+use crate::infrastructure::{RollupGranularity, RollupMetric, RollupRepository};
+
+// Get a pre-aggregated time series, maintained by the projection worker
+#[get("/stats/rollups")]
+pub async fn get_rollups(
+    query: web::Query<RollupQuery>,
+    repository: web::Data<Box<dyn RollupRepository>>,
+    privacy: web::Data<StatsPrivacy>,
+) -> Result<HttpResponse, ApiError> {
+    let Ok(metric) = query.metric.parse::<RollupMetric>() else {
+        return Err(ApiError::bad_request("metric must be auctions_listed, bids_placed or gmv"));
+    };
+    let granularity = match query.granularity.as_deref().map(str::parse::<RollupGranularity>) {
+        None => RollupGranularity::Day,
+        Some(Ok(granularity)) => granularity,
+        Some(Err(_)) => return Err(ApiError::bad_request("granularity must be hour, day or month")),
+    };
+
+    let points = repository.get_rollups(metric, granularity, query.from, query.to).await?;
+    let models: Vec<RollupPointModel> = privacy
+        .protect(metric, points)
+        .into_iter()
+        .map(|point| RollupPointModel {
+            bucket: point.bucket,
+            currency: point.currency,
+            value: point.value,
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(models))
+}
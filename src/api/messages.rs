@@ -0,0 +1,202 @@
+//! Error messages in the language the caller asks for with `Accept-Language`.
+//!
+//! The domain reports errors in English, along with a stable code. Other languages are catalogs
+//! of messages by code; adding a language is adding a catalog to [`CATALOGS`]. Codes missing
+//! from a catalog keep their English message.
+
+use actix_web::body::{to_bytes, BoxBody, EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{self, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{error, Error, HttpRequest};
+
+use crate::api::models::ErrorModel;
+
+/// The language the domain writes its messages in.
+const DEFAULT_LANGUAGE: &str = "en";
+
+pub struct Catalog {
+    /// Primary language subtag, such as `sv`.
+    pub language: &'static str,
+    messages: &'static [(&'static str, &'static str)],
+}
+
+impl Catalog {
+    pub fn message(&self, code: &str) -> Option<&'static str> {
+        self.messages
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, message)| *message)
+    }
+}
+
+pub const CATALOGS: &[Catalog] = &[SWEDISH];
+
+const SWEDISH: Catalog = Catalog {
+    language: "sv",
+    messages: &[
+        ("UNKNOWN_AUCTION", "Okänd auktion"),
+        ("AUCTION_ALREADY_EXISTS", "Auktionen finns redan"),
+        ("AUCTION_HAS_ENDED", "Auktionen har avslutats"),
+        ("AUCTION_HAS_NOT_STARTED", "Auktionen har inte börjat"),
+        ("AUCTION_NOT_FOUND", "Auktionen hittades inte"),
+        ("SELLER_CANNOT_PLACE_BIDS", "Säljaren kan inte lägga bud"),
+        ("BID_CURRENCY_CONVERSION", "Budet är i en annan valuta än auktionen"),
+        ("INVALID_USER_DATA", "Ogiltiga användaruppgifter"),
+        ("MUST_PLACE_BID_OVER_HIGHEST_BID", "Budet måste vara högre än det högsta budet"),
+        ("ALREADY_PLACED_BID", "Du har redan lagt ett bud"),
+        ("MUST_RAISE_WITH_AT_LEAST", "Budet måste höjas med minst den minsta höjningen"),
+        ("MUST_SPECIFY_AMOUNT", "Ange ett belopp över noll"),
+        ("INVALID_QUANTITY", "Ogiltigt antal"),
+        ("BID_BELOW_STARTING_PRICE", "Budet är lägre än utropspriset"),
+        ("PROXY_BID_NOT_ALLOWED", "Maxbud kan bara läggas i tidsbestämda stigande auktioner med en enhet"),
+        ("ILLEGAL_STATUS_TRANSITION", "Otillåten ändring av auktionens status"),
+        ("BID_RETRACTION_NOT_ALLOWED", "Bud kan bara dras tillbaka i tidsbestämda stigande auktioner"),
+        ("RETRACTION_WINDOW_HAS_PASSED", "Budet kan inte längre dras tillbaka"),
+        ("NO_BID_TO_RETRACT", "Det finns inget bud att dra tillbaka"),
+        ("EXPIRY_MUST_BE_LATER", "Det nya slutdatumet måste vara senare än auktionens nuvarande slut"),
+        ("AUCTION_IS_PAUSED", "Auktionen är pausad"),
+        ("AUCTION_IS_NOT_PAUSED", "Auktionen är inte pausad"),
+        ("BIDDER_NOT_APPROVED", "Budgivaren måste godkännas av säljaren för att buda i den här auktionen"),
+        ("DEPOSIT_REQUIRED", "Budgivaren måste lämna en deposition för att buda i den här auktionen"),
+        ("DEPOSIT_TOO_LOW", "Depositionen måste täcka beloppet som auktionen kräver"),
+        ("AUCTION_CANNOT_BE_RELISTED", "Bara osålda auktioner med en policy för återutläggning kan läggas ut igen"),
+        ("UNKNOWN_BID", "Okänt bud"),
+        ("UNKNOWN_LOT", "Okänt objekt"),
+        ("LOT_REQUIRED", "Bud måste läggas på ett av auktionens objekt"),
+        ("AUCTION_INCOMPLETE", "Auktionen behöver en titel innan den kan publiceras"),
+        ("INVALID_DEPOSIT", "Depositionen måste vara positiv"),
+        ("INVALID_RELIST_POLICY", "Återutläggning kräver en positiv längd och minst en återutläggning"),
+        ("INVALID_LOTS", "Auktioner med objekt säljer ett av varje objekt och kan inte köpas direkt"),
+        ("LOT_TITLE_REQUIRED", "Objekt behöver en titel"),
+        ("INVALID_TAX_RATE", "Momssatsen kan inte vara negativ"),
+        ("TITLE_REQUIRED", "Auktioner behöver en titel"),
+        ("STARTING_PRICE_NOT_ALLOWED", "Utropspris gäller bara tidsbestämda stigande auktioner"),
+        ("INVALID_RESERVE_PRICE", "Reservationspriset kan inte vara negativt"),
+        ("PRICING_NOT_ALLOWED", "Prisregler gäller bara Vickrey-auktioner"),
+        ("INVALID_PRICING_INCREMENT", "Prisregelns steg måste vara positiva"),
+        ("TIE_BREAK_NOT_ALLOWED", "Regler för lika bud gäller bara auktioner med ett slutet bud"),
+        ("INVALID_INCREMENTS", "Budstegen måste ordnas efter pris och kan inte vara negativa"),
+        ("INVALID_MAX_EXTENSION", "Den längsta förlängningen kan inte vara negativ"),
+        ("INVALID_STARTING_PRICE", "Utropspriset kan inte vara negativt"),
+        ("BUY_NOW_BELOW_RESERVE", "Köp nu-priset måste vara minst reservationspriset"),
+        ("UNAUTHORIZED", "Du måste vara inloggad"),
+        ("FORBIDDEN", "Du har inte behörighet"),
+        ("NOT_FOUND", "Hittades inte"),
+        ("AUCTION_NOT_CLOSED", "Bara avslutade auktioner gör upp"),
+    ],
+};
+
+/// The catalog of the most preferred language in an `Accept-Language` header, or `None` when
+/// English is preferred or nothing the caller accepts is available.
+pub fn negotiate(accept_language: &str) -> Option<&'static Catalog> {
+    let mut ranges: Vec<(&str, f32)> = accept_language
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';').map(str::trim);
+            let tag = parts.next().filter(|tag| !tag.is_empty())?;
+            let quality = parts
+                .find_map(|param| param.strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.parse::<f32>().ok())?;
+            Some((tag, quality))
+        })
+        .filter(|(_, quality)| *quality > 0.0)
+        .collect();
+    // Stable, so that equally preferred languages keep the order they were listed in
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranges.into_iter().find_map(|(tag, _)| {
+        let language = tag.split('-').next().unwrap_or(tag);
+        if tag == "*" || language.eq_ignore_ascii_case(DEFAULT_LANGUAGE) {
+            return Some(None);
+        }
+        CATALOGS
+            .iter()
+            .find(|catalog| catalog.language.eq_ignore_ascii_case(language))
+            .map(Some)
+    })?
+}
+
+pub fn catalog_for(req: &HttpRequest) -> Option<&'static Catalog> {
+    req.headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(negotiate)
+}
+
+pub fn localize(errors: &mut [ErrorModel], catalog: &Catalog) {
+    for error in errors {
+        if let Some(message) = catalog.message(&error.code) {
+            error.message = message.to_string();
+        }
+    }
+}
+
+/// Middleware translating the messages of error responses, see [`ErrorModel`].
+pub async fn localize_errors<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    let Some(catalog) = catalog_for(req.request()) else {
+        return Ok(next.call(req).await?.map_into_left_body());
+    };
+    let res = next.call(req).await?;
+    if res.status().is_success() {
+        return Ok(res.map_into_left_body());
+    }
+    let (http_req, res) = res.into_parts();
+    let (mut res, body) = res.into_parts();
+    let body = to_bytes(body).await.map_err(|e| {
+        let e: Box<dyn std::error::Error> = e.into();
+        error::ErrorInternalServerError(e.to_string())
+    })?;
+    let body = match serde_json::from_slice::<Vec<ErrorModel>>(&body) {
+        Ok(mut errors) => {
+            localize(&mut errors, catalog);
+            res.headers_mut()
+                .insert(header::CONTENT_LANGUAGE, HeaderValue::from_static(catalog.language));
+            serde_json::to_vec(&errors).map_err(error::ErrorInternalServerError)?.into()
+        }
+        // Not an error list, such as an empty 404
+        Err(_) => body,
+    };
+    let res = res.set_body(EitherBody::right(BoxBody::new(body)));
+    Ok(ServiceResponse::new(http_req, res))
+}
+
+#[cfg(test)]
+mod messages_tests {
+    use super::*;
+    use crate::domain::models::Errors;
+
+    #[test]
+    fn test_negotiate_picks_the_most_preferred_available_language() {
+        assert_eq!(negotiate("sv-SE,sv;q=0.9,en;q=0.8").map(|c| c.language), Some("sv"));
+        assert_eq!(negotiate("de, sv;q=0.5").map(|c| c.language), Some("sv"));
+        assert_eq!(negotiate("en;q=0.4, sv;q=0.6").map(|c| c.language), Some("sv"));
+        assert!(negotiate("en-GB, sv;q=0.9").is_none());
+        assert!(negotiate("de, fr").is_none());
+        assert!(negotiate("sv;q=0").is_none());
+        assert!(negotiate("*").is_none());
+    }
+
+    #[test]
+    fn test_localize_translates_known_codes_only() {
+        let mut errors = vec![
+            ErrorModel::new("AUCTION_HAS_ENDED", "Auction has ended"),
+            ErrorModel::new("INVALID_REQUEST", "Invalid auction id x"),
+        ];
+        localize(&mut errors, &SWEDISH);
+        assert_eq!(errors[0].message, "Auktionen har avslutats");
+        assert_eq!(errors[1].message, "Invalid auction id x");
+    }
+
+    #[test]
+    fn test_every_domain_error_is_translated() {
+        for catalog in CATALOGS {
+            for flag in Errors::all() {
+                let code = flag.code().unwrap();
+                assert!(catalog.message(code).is_some(), "{} lacks {}", catalog.language, code);
+            }
+        }
+    }
+}
@@ -1,2 +1,9 @@
+pub mod amount_input;
+pub mod amount_output;
+pub mod api_error;
+pub mod client;
+pub mod field_mask;
 pub mod handlers;
+pub mod messages;
 pub mod models;
+pub mod realtime;
@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::amount_output;
+use crate::domain::models::{Amount, BidMetadata};
+use crate::infrastructure::data::{JobKind, JobState};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AdminActionModel {
+    pub reason: Option<String>,
+}
+
+/// Where a bid came from, for support to look into suspected fraud. Never shown to bidders.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BidMetadataModel {
+    pub id: i64,
+    pub bidder: String,
+    #[serde(with = "amount_output")]
+    pub amount: Amount,
+    pub at: DateTime<Utc>,
+    pub metadata: Option<BidMetadata>,
+}
+
+/// A background job, as support sees it to recover from failures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobModel {
+    pub id: i64,
+    pub kind: JobKind,
+    #[serde(rename = "auctionId")]
+    pub auction_id: i64,
+    pub state: JobState,
+    #[serde(rename = "runAt")]
+    pub run_at: DateTime<Utc>,
+    pub attempts: i32,
+    #[serde(rename = "lastError")]
+    pub last_error: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RescheduleJobModel {
+    #[serde(rename = "runAt")]
+    pub run_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobQuery {
+    pub state: Option<JobState>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtendAuctionModel {
+    pub expiry: DateTime<Utc>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CancelAuctionQuery {
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VoidBidQuery {
+    pub reason: Option<String>,
+}
+
+/// Answer to anonymizing a user: the pseudonym that replaced them, and the ids of the auctions
+/// they took part in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnonymizedUserModel {
+    pub pseudonym: String,
+    pub auctions: Vec<i64>,
+}
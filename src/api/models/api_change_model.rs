@@ -0,0 +1,18 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiChangeModel {
+    pub method: String,
+    pub path: String,
+    pub field: Option<String>,
+    #[serde(rename = "deprecatedAt")]
+    pub deprecated_at: DateTime<Utc>,
+    pub sunset: Option<DateTime<Utc>>,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiChangesModel {
+    pub deprecations: Vec<ApiChangeModel>,
+}
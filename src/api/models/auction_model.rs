@@ -1,43 +1,323 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::domain::models::{Amount, CurrencyCode};
+use crate::api::amount_input;
+use crate::api::amount_output;
+use crate::domain::models::{Amount, AuctionStatus, CurrencyCode, RemovalKind};
 
 use crate::api::models::BidModel;
 
+/// An auction as shown in lists, search results and watchlists: what it takes to render a row,
+/// without the outcome and the history of the auction.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AuctionModel {
+pub struct AuctionSummaryModel<'a> {
+    pub id: i64,
+    pub title: String,
+    #[serde(rename = "startsAt")]
+    pub starts_at: DateTime<Utc>,
+    pub expiry: DateTime<Utc>,
+    pub seller: Option<String>,
+    pub currency: CurrencyCode,
+    /// Only when asked for, see [`AuctionsQuery::include`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bids: Option<Vec<BidModel<'a>>>,
+    #[serde(rename = "bidCount")]
+    pub bid_count: usize,
+    /// The highest bid while the auction runs, unless the bids are sealed, then the winning bid
+    #[serde(default, with = "amount_output::option", rename = "currentPrice")]
+    pub current_price: Option<Amount>,
+    /// The lowest bid that would be accepted now, in auctions with open bids. Only in pages of
+    /// all auctions when asked for by `fields`, as it takes reading the bids
+    #[serde(default, with = "amount_output::option", rename = "nextMinimumBid")]
+    pub next_minimum_bid: Option<Amount>,
+    /// The current price in the currency asked for with `display_currency`, when it converts
+    #[serde(default, with = "amount_output::option", rename = "displayPrice", skip_serializing_if = "Option::is_none")]
+    pub display_price: Option<Amount>,
+    pub quantity: i32,
+    pub status: AuctionStatus,
+    #[serde(rename = "hasEnded")]
+    pub has_ended: bool,
+    #[serde(rename = "requiresRegistration")]
+    pub requires_registration: bool,
+    #[serde(default, with = "amount_output::option")]
+    pub deposit: Option<Amount>,
+    /// Like `nextMinimumBid`, only in pages of all auctions when asked for by `fields`
+    #[serde(default, with = "amount_output::option", rename = "buyNowPrice")]
+    pub buy_now_price: Option<Amount>,
+    #[serde(rename = "externalReference")]
+    pub external_reference: Option<String>,
+}
+
+/// An auction with everything there is to show about it, for the detail endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuctionDetailModel<'a> {
     pub id: i64,
     #[serde(rename = "startsAt")]
     pub starts_at: DateTime<Utc>,
     pub title: String,
+    pub description: Option<String>,
     #[serde(rename = "expiry")]
     pub expiry: DateTime<Utc>,
     pub seller: Option<String>,
     pub currency: CurrencyCode,
-    pub bids: Vec<BidModel>,
+    /// The latest bids, see `bids_limit` in [`FieldsQuery`]
+    pub bids: Vec<BidModel<'a>>,
+    /// Where the bids older than those shown start, see [`crate::api::models::BidsQuery`]
+    #[serde(rename = "bidsNextAfter")]
+    pub bids_next_after: Option<i64>,
+    #[serde(rename = "bidCount")]
+    pub bid_count: usize,
+    /// The highest bid while the auction runs, unless the bids are sealed, then the winning bid
+    #[serde(default, with = "amount_output::option", rename = "currentPrice")]
+    pub current_price: Option<Amount>,
+    /// The lowest bid that would be accepted now, in auctions with open bids
+    #[serde(default, with = "amount_output::option", rename = "nextMinimumBid")]
+    pub next_minimum_bid: Option<Amount>,
+    /// The current price in the currency asked for with `display_currency`, when it converts
+    #[serde(default, with = "amount_output::option", rename = "displayPrice", skip_serializing_if = "Option::is_none")]
+    pub display_price: Option<Amount>,
+    #[serde(default, with = "amount_output::option")]
     pub price: Option<Amount>,
     pub winner: Option<String>,
+    pub quantity: i32,
+    pub winners: Vec<WinnerModel>,
+    /// What each bidder owes once the auction has ended
+    pub charges: Vec<ChargeModel>,
+    /// The outcome recorded when the auction was first read closed
+    pub result: Option<AuctionResultModel>,
+    pub status: AuctionStatus,
     #[serde(rename = "hasEnded")]
     pub has_ended: bool,
+    #[serde(rename = "requiresRegistration")]
+    pub requires_registration: bool,
+    #[serde(default, with = "amount_output::option")]
+    pub deposit: Option<Amount>,
+    pub relist: Option<RelistPolicyModel>,
+    /// The unsold auction this one relists
+    #[serde(rename = "relistedFrom")]
+    pub relisted_from: Option<i64>,
+    #[serde(rename = "relistedAs")]
+    pub relisted_as: Option<i64>,
+    #[serde(rename = "voidedAt")]
+    pub voided_at: Option<DateTime<Utc>>,
+    #[serde(rename = "cancelledAt")]
+    pub cancelled_at: Option<DateTime<Utc>>,
+    #[serde(rename = "cancelReason")]
+    pub cancel_reason: Option<String>,
+    #[serde(rename = "extendedAt")]
+    pub extended_at: Option<DateTime<Utc>>,
+    #[serde(rename = "extensionReason")]
+    pub extension_reason: Option<String>,
+    /// Set while support has paused the auction
+    #[serde(rename = "pausedAt")]
+    pub paused_at: Option<DateTime<Utc>>,
+    #[serde(rename = "externalReference")]
+    pub external_reference: Option<String>,
+    #[serde(default, with = "amount_output::option", rename = "buyNowPrice")]
+    pub buy_now_price: Option<Amount>,
+    #[serde(default, with = "amount_output::option", rename = "startingPrice")]
+    pub starting_price: Option<Amount>,
+    /// Items bid on and won separately, empty unless the auction has several
+    pub lots: Vec<LotModel<'a>>,
 }
 
+/// One lot of an auction with several, with the bids and outcome of that lot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LotModel<'a> {
+    pub id: i32,
+    pub title: String,
+    /// The bids on the lot among the latest bids of the auction, older ones are read from
+    /// `GET /auctions/{id}/bids`, which tells the lot of each bid
+    pub bids: Vec<BidModel<'a>>,
+    /// Number of standing bids on the lot, shown or not
+    #[serde(rename = "bidCount")]
+    pub bid_count: usize,
+    #[serde(default, with = "amount_output::option", rename = "currentPrice")]
+    pub current_price: Option<Amount>,
+    #[serde(default, with = "amount_output::option", rename = "nextMinimumBid")]
+    pub next_minimum_bid: Option<Amount>,
+    #[serde(default, with = "amount_output::option")]
+    pub price: Option<Amount>,
+    pub winner: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuctionsQuery {
+    /// Comma separated auction ids, to get only those auctions
+    pub ids: Option<String>,
+    /// `bids` to include the bids of auctions whose bids are not sealed
+    pub include: Option<String>,
+    /// Comma separated fields to show of each auction, see [`crate::api::field_mask::FieldMask`]
+    pub fields: Option<String>,
+    /// Currency to show the current prices in as well, such as `EUR`
+    pub display_currency: Option<String>,
+    /// Number of auctions per page
+    pub limit: Option<i64>,
+    /// Number of auctions to skip, the `nextOffset` of the previous page
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldsQuery {
+    /// Comma separated fields to show, see [`crate::api::field_mask::FieldMask`]
+    pub fields: Option<String>,
+    /// Currency to show the current price in as well, such as `EUR`
+    pub display_currency: Option<String>,
+    /// Number of the latest bids to show of the auction, see [`AuctionDetailModel`]
+    pub bids_limit: Option<i64>,
+}
+
+/// One page of the auctions, how many there are in all, and where the next page starts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuctionPageModel<T> {
+    pub auctions: Vec<T>,
+    pub total: i64,
+    #[serde(rename = "nextOffset")]
+    pub next_offset: Option<i64>,
+}
+
+/// The requested auctions in the requested order, the ones that were taken down, and the ids
+/// that match no auction the caller may see.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuctionBatchModel<T> {
+    pub auctions: Vec<T>,
+    pub removed: Vec<RemovedAuctionModel>,
+    pub missing: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuctionResultModel {
+    pub winner: Option<String>,
+    #[serde(default, with = "amount_output::option")]
+    pub amount: Option<Amount>,
+    #[serde(rename = "closedAt")]
+    pub closed_at: DateTime<Utc>,
+    #[serde(rename = "reserveMet")]
+    pub reserve_met: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WinnerModel {
+    #[serde(with = "amount_output")]
+    pub price: Amount,
+    pub winner: String,
+    pub quantity: i32,
+}
+
+/// An auction that was taken down rather than never existed, see [`AuctionBatchModel`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemovedAuctionModel {
+    pub id: i64,
+    pub removal: RemovalKind,
+    #[serde(rename = "removedAt")]
+    pub removed_at: DateTime<Utc>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChargeModel {
+    #[serde(with = "amount_output")]
+    pub amount: Amount,
+    pub bidder: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BidIncrementModel {
+    #[serde(with = "amount_output")]
+    pub below: Amount,
+    #[serde(with = "amount_output")]
+    pub increment: Amount,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelistPolicyModel {
+    pub duration: i64, // in seconds
+    #[serde(rename = "maxRelists")]
+    pub max_relists: i32,
+}
+
+/// Prices are amounts in the currency of the auction, in any of
+/// [`amount_input::ACCEPTED_FORMATS`] like bids.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateAuctionModel {
+    /// Only drafts may leave the title out, to fill it in before publishing
+    #[serde(default)]
     pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(deserialize_with = "amount_input::deserialize_currency")]
     pub currency: CurrencyCode,
     #[serde(rename = "startsAt")]
     pub starts_at: DateTime<Utc>,
     #[serde(rename = "endsAt")]
     pub ends_at: DateTime<Utc>,
-    #[serde(rename = "minRaise")]
-    pub min_raise: Option<i64>,
-    #[serde(rename = "reservePrice")]
-    pub reserve_price: Option<i64>,
+    #[serde(default, with = "amount_output::option", rename = "minRaise")]
+    pub min_raise: Option<Amount>,
+    /// Lowest accepted price per unit, in timed ascending and single sealed bid auctions
+    #[serde(default, with = "amount_output::option", rename = "reservePrice")]
+    pub reserve_price: Option<Amount>,
     #[serde(rename = "timeFrame")]
     pub time_frame: Option<i64>, // in seconds
+    #[serde(default, with = "amount_output::option", rename = "buyNowPrice")]
+    pub buy_now_price: Option<Amount>,
+    #[serde(default, with = "amount_output::option", rename = "startingPrice")]
+    pub starting_price: Option<Amount>,
+    #[serde(default, rename = "maxExtension")]
+    pub max_extension: Option<i64>, // in seconds
+    #[serde(default)]
+    pub increments: Vec<BidIncrementModel>,
     #[serde(rename = "singleSealedBidOptions")]
     pub single_sealed_bid_options: Option<String>,
+    /// `EarliestBid` (default) or `LatestBid`, single sealed bid auctions only
+    #[serde(default, rename = "tieBreak")]
+    pub tie_break: Option<String>,
+    /// `SecondPrice` (default), `RoundUp` to a multiple of `minRaise` or `IncrementOver` the
+    /// second price by `minRaise`, Vickrey auctions only
+    #[serde(default, rename = "vickreyPricing")]
+    pub vickrey_pricing: Option<String>,
     #[serde(default,rename = "openBidders")]
     pub open_bidders: bool,
+    /// Only bidders approved by the seller may bid
+    #[serde(default, rename = "requiresRegistration")]
+    pub requires_registration: bool,
+    /// Amount bidders must deposit before bidding
+    #[serde(default, with = "amount_output::option")]
+    pub deposit: Option<Amount>,
+    /// Relist the auction when it ends without a winner
+    #[serde(default)]
+    pub relist: Option<RelistPolicyModel>,
+    #[serde(default)]
+    pub quantity: Option<i32>,
+    #[serde(default, rename = "externalReference")]
+    pub external_reference: Option<String>,
+    /// Items to bid on and win separately, to sell several items in one listing
+    #[serde(default)]
+    pub lots: Vec<CreateLotModel>,
+    /// Create the auction as a draft, hidden from bidders until published
+    #[serde(default)]
+    pub draft: bool,
+    /// Fees of this auction, instead of those configured for all auctions
+    #[serde(default)]
+    pub fees: Option<FeePolicyModel>,
+    /// Tax on the fees, in basis points, so that 2500 is 25%
+    #[serde(default, rename = "taxRateBps")]
+    pub tax_rate_bps: Option<i64>,
+}
+
+/// Percentages in basis points, so that 1500 is 15%.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeePolicyModel {
+    #[serde(default, rename = "buyersPremiumBps")]
+    pub buyers_premium_bps: i64,
+    #[serde(default, with = "amount_output::option", rename = "buyerFee")]
+    pub buyer_fee: Option<Amount>,
+    #[serde(default, rename = "sellerCommissionBps")]
+    pub seller_commission_bps: i64,
+    #[serde(default, with = "amount_output::option", rename = "sellerFee")]
+    pub seller_fee: Option<Amount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateLotModel {
+    pub title: String,
 }
@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::amount_output;
+use crate::domain::models::{Amount, AuctionStatus};
+
+/// Compact state of an auction, sent instead of the full bid history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuctionSnapshotModel {
+    pub id: i64,
+    #[serde(default, with = "amount_output::option", rename = "currentPrice")]
+    pub current_price: Option<Amount>,
+    pub leader: Option<String>,
+    #[serde(rename = "bidCount")]
+    pub bid_count: usize,
+    #[serde(rename = "endsAt")]
+    pub ends_at: DateTime<Utc>,
+    pub status: AuctionStatus,
+    #[serde(rename = "hasEnded")]
+    pub has_ended: bool,
+}
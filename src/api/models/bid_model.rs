@@ -1,16 +1,67 @@
 use chrono::Duration;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
+use crate::api::amount_input;
+use crate::api::amount_output;
 use crate::domain::models::Amount;
 
+/// Borrows from the auction it is mapped from where it can, to keep listings cheap.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BidModel {
-    pub amount: Amount,
-    pub bidder: Option<String>,
+pub struct BidModel<'a> {
+    /// The cursor of the bid, see [`BidsQuery`] and [`BidPollQuery`]
+    pub id: i64,
+    #[serde(with = "amount_output::cow")]
+    pub amount: Cow<'a, Amount>,
+    pub bidder: Option<Cow<'a, str>>,
     pub at: Duration,
+    pub quantity: i32,
+    /// The lot bid on, in auctions with several
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lot: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateBidModel {
+    /// Any of [`amount_input::ACCEPTED_FORMATS`]
+    #[serde(deserialize_with = "amount_input::deserialize")]
     pub amount: Amount,
+    #[serde(default)]
+    pub quantity: Option<i32>,
+    /// The amount is the most the bidder is willing to pay, see proxy bidding
+    #[serde(default, rename = "maxBid")]
+    pub max_bid: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BidPollQuery {
+    /// Id of the last bid the client has seen
+    pub since: Option<i64>,
+    /// Seconds to wait for new bids
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BidsQuery {
+    /// Id of the oldest bid of the previous page, the `nextAfter` of that page
+    pub after: Option<i64>,
+    /// Number of bids per page
+    pub limit: Option<i64>,
+}
+
+/// One page of the bids of an auction, latest first, and where the next page starts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BidPageModel<'a> {
+    pub bids: Vec<BidModel<'a>>,
+    #[serde(rename = "nextAfter")]
+    pub next_after: Option<i64>,
+}
+
+/// Bids placed after the cursor the client sent, and the cursor to send next time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BidPollModel<'a> {
+    pub bids: Vec<BidModel<'a>>,
+    pub cursor: i64,
+    #[serde(rename = "hasEnded")]
+    pub has_ended: bool,
 }
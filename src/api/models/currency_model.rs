@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::CurrencyCode;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrencyModel {
+    pub code: CurrencyCode,
+    #[serde(rename = "numericCode")]
+    pub numeric_code: i32,
+    pub name: String,
+    #[serde(rename = "minorUnits")]
+    pub minor_units: i16,
+}
+
+#[cfg(test)]
+mod currency_model_tests {
+    use super::*;
+    use crate::api::models::CreateBidModel;
+    use crate::domain::models::Amount;
+    use serde_json::json;
+
+    #[test]
+    fn test_currencies_are_iso_codes_in_the_api() {
+        let model = CurrencyModel {
+            code: CurrencyCode::DKK,
+            numeric_code: 208,
+            name: "Danish krone".to_string(),
+            minor_units: 2,
+        };
+        assert_eq!(serde_json::to_value(&model).unwrap()["code"], json!("DKK"));
+
+        let bid: CreateBidModel =
+            serde_json::from_value(json!({"amount": {"value": 10, "currency": "VAC"}})).unwrap();
+        assert_eq!(bid.amount, Amount::new(10, CurrencyCode::VAC));
+        assert_eq!(serde_json::to_value(&bid.amount).unwrap(), json!({"value": 10, "currency": "VAC"}));
+    }
+}
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::amount_input;
+use crate::api::amount_output;
+use crate::domain::models::Amount;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordDepositModel {
+    /// Any of [`amount_input::ACCEPTED_FORMATS`]
+    #[serde(deserialize_with = "amount_input::deserialize")]
+    pub amount: Amount,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositModel {
+    #[serde(rename = "auctionId")]
+    pub auction_id: i64,
+    pub user: String,
+    #[serde(with = "amount_output")]
+    pub amount: Amount,
+    #[serde(rename = "recordedAt")]
+    pub recorded_at: DateTime<Utc>,
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::Error;
+
+/// The body of every error response is a list of these, one per thing that went wrong, so that
+/// clients can branch on `code` rather than on the English message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorModel {
+    pub code: String,
+    pub message: String,
+    /// When what was asked for was taken down, for `410 Gone`
+    #[serde(default, rename = "removedAt", skip_serializing_if = "Option::is_none")]
+    pub removed_at: Option<DateTime<Utc>>,
+    /// Why what was asked for was taken down, if a reason was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl ErrorModel {
+    pub fn new(code: &str, message: impl Into<String>) -> Self {
+        ErrorModel {
+            code: code.to_string(),
+            message: message.into(),
+            removed_at: None,
+            reason: None,
+        }
+    }
+}
+
+pub fn map_error(error: &Error) -> Vec<ErrorModel> {
+    error
+        .violations()
+        .into_iter()
+        .map(|(code, message)| ErrorModel::new(code, message))
+        .collect()
+}
@@ -1,5 +1,23 @@
+pub mod admin_model;
+pub mod api_change_model;
 pub mod auction_model;
+pub mod auction_snapshot_model;
 pub mod bid_model;
+pub mod currency_model;
+pub mod deposit_model;
+pub mod error_model;
+pub mod registration_model;
+pub mod settlement_model;
+pub mod stats_model;
 
+pub use admin_model::*;
+pub use api_change_model::*;
 pub use auction_model::*;
+pub use auction_snapshot_model::*;
 pub use bid_model::*;
+pub use currency_model::*;
+pub use deposit_model::*;
+pub use error_model::*;
+pub use registration_model::*;
+pub use settlement_model::*;
+pub use stats_model::*;
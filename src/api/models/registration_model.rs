@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrationModel {
+    #[serde(rename = "auctionId")]
+    pub auction_id: i64,
+    pub user: String,
+    /// `Pending`, `Approved` or `Rejected`
+    pub status: String,
+    #[serde(rename = "registeredAt")]
+    pub registered_at: DateTime<Utc>,
+    #[serde(rename = "decidedAt", skip_serializing_if = "Option::is_none")]
+    pub decided_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api::amount_output;
+use crate::domain::models::{Amount, ChargeKind};
+
+/// What the buyers pay and the seller receives for a closed auction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlementModel {
+    pub buyers: Vec<BuyerSettlementModel>,
+    #[serde(with = "amount_output", rename = "soldFor")]
+    pub sold_for: Amount,
+    #[serde(with = "amount_output")]
+    pub commission: Amount,
+    #[serde(with = "amount_output", rename = "sellerFee")]
+    pub seller_fee: Amount,
+    #[serde(rename = "sellerItems")]
+    pub seller_items: Vec<ChargeItemModel>,
+    #[serde(rename = "taxRateBps")]
+    pub tax_rate_bps: i64,
+    #[serde(with = "amount_output", rename = "sellerReceives")]
+    pub seller_receives: Amount,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuyerSettlementModel {
+    pub bidder: String,
+    #[serde(with = "amount_output", rename = "hammerPrice")]
+    pub hammer_price: Amount,
+    #[serde(with = "amount_output")]
+    pub premium: Amount,
+    #[serde(with = "amount_output")]
+    pub fee: Amount,
+    pub items: Vec<ChargeItemModel>,
+    #[serde(with = "amount_output")]
+    pub tax: Amount,
+    #[serde(with = "amount_output")]
+    pub total: Amount,
+}
+
+/// One line of an invoice, before and after tax.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChargeItemModel {
+    pub kind: ChargeKind,
+    #[serde(with = "amount_output")]
+    pub net: Amount,
+    #[serde(with = "amount_output")]
+    pub tax: Amount,
+    #[serde(with = "amount_output")]
+    pub gross: Amount,
+}
@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::CurrencyCode;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RollupQuery {
+    /// `auctions_listed`, `bids_placed` or `gmv`
+    pub metric: String,
+    /// `hour`, `day` (default) or `month`
+    #[serde(default)]
+    pub granularity: Option<String>,
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollupPointModel {
+    pub bucket: DateTime<Utc>,
+    /// Only set for amounts, which are summed per currency
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<CurrencyCode>,
+    pub value: i64,
+}
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+
+use crate::api::models::AuctionSnapshotModel;
+use crate::domain::models::{Amount, Auction, UserId};
+
+/// Snapshot of an auction for clients that start following it: current price, leader,
+/// bid count and effective end, rather than the full bid history.
+pub fn auction_snapshot(auction: &Auction, now: DateTime<Utc>) -> AuctionSnapshotModel {
+    let status = auction.status_at(now);
+    let has_ended = status.has_ended();
+    let standing = standing_bid(auction, now);
+    let leader = standing.as_ref().map(|(_, user)| display_bidder(auction, user));
+
+    AuctionSnapshotModel {
+        id: auction.auction_id().value(),
+        current_price: standing.map(|(amount, _)| amount),
+        leader,
+        bid_count: auction.bids().len(),
+        ends_at: auction.effective_end(),
+        status,
+        has_ended,
+    }
+}
+
+/// The winning bid once the auction has ended, or the highest bid while it runs.
+/// Sealed bids stay sealed until the auction has ended.
+pub(crate) fn standing_bid(auction: &Auction, now: DateTime<Utc>) -> Option<(Amount, UserId)> {
+    if auction.has_ended(now) {
+        return auction.try_get_amount_and_winner(now);
+    }
+    match auction {
+        Auction::TimedAscending { .. } => auction
+            .highest_bid()
+            .map(|bid| (bid.amount().clone(), bid.user().clone())),
+        Auction::SingleSealedBid { .. } => None,
+    }
+}
+
+pub(crate) fn display_bidder(auction: &Auction, user: &UserId) -> String {
+    if auction.open_bidders() {
+        user.to_string()
+    } else {
+        auction
+            .bidder_aliases()
+            .get(user)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
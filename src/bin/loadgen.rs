@@ -0,0 +1,274 @@
+// src/bin/loadgen.rs
+use auctions_api::domain::models::{User, UserId};
+use auctions_api::infrastructure::jwt_payload_handling::{encode_jwt_payload, X_JWT_PAYLOAD};
+use chrono::{Duration as ChronoDuration, Utc};
+use rand::Rng;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "Usage: loadgen [--url=http://127.0.0.1:8080] [--duration=60] [--concurrency=16] \
+[--mix=reads:70,creates:5,bids:25] [--auction=<id to storm with bids>]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Operation {
+    Read,
+    Create,
+    Bid,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Options {
+    url: String,
+    duration: Duration,
+    concurrency: usize,
+    /// Relative weights of the operations
+    mix: Vec<(Operation, u32)>,
+    auction: Option<i64>,
+}
+
+#[derive(Default)]
+struct Stats {
+    latencies: Vec<Duration>,
+    rejected: usize,
+    errors: usize,
+}
+
+/// Drives the HTTP API with a mix of listing reads, auction creations and a bid storm against
+/// a single auction, then reports latencies and error rates per operation.
+#[tokio::main]
+async fn main() {
+    let options = match parse_options(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    };
+    let client = reqwest::Client::new();
+
+    let auction_id = match options.auction {
+        Some(id) => id,
+        None => match create_auction(&client, &options.url).await {
+            Ok((_, Some(id))) => id,
+            Ok((status, None)) => {
+                eprintln!("Could not create the auction to bid on: {}", status);
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("Could not create the auction to bid on: {}", e);
+                std::process::exit(1);
+            }
+        },
+    };
+    println!(
+        "Running {} workers for {:?} against {}, bidding on auction {}",
+        options.concurrency, options.duration, options.url, auction_id
+    );
+
+    let stats: Arc<Mutex<BTreeMap<Operation, Stats>>> = Arc::default();
+    let next_amount = Arc::new(AtomicI64::new(0));
+    let deadline = Instant::now() + options.duration;
+    let started = Instant::now();
+    let workers: Vec<_> = (0..options.concurrency)
+        .map(|worker| {
+            let client = client.clone();
+            let options = options.clone();
+            let stats = stats.clone();
+            let next_amount = next_amount.clone();
+            tokio::spawn(async move {
+                while Instant::now() < deadline {
+                    let operation = pick(&options.mix, rand::thread_rng().gen_range(0..total_weight(&options.mix)));
+                    let start = Instant::now();
+                    let result = match operation {
+                        Operation::Read => get(&client, &format!("{}/auctions", options.url)).await,
+                        Operation::Create => create_auction(&client, &options.url).await.map(|(status, _)| status),
+                        Operation::Bid => {
+                            let amount = next_amount.fetch_add(10, Ordering::Relaxed) + 10;
+                            place_bid(&client, &options.url, auction_id, worker, amount).await
+                        }
+                    };
+                    let latency = start.elapsed();
+                    let mut stats = stats.lock().unwrap();
+                    let entry = stats.entry(operation).or_default();
+                    entry.latencies.push(latency);
+                    match result {
+                        Ok(status) if status.is_success() => {}
+                        Ok(status) if status.is_client_error() => entry.rejected += 1,
+                        _ => entry.errors += 1,
+                    }
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        let _ = worker.await;
+    }
+
+    report(&stats.lock().unwrap(), started.elapsed());
+}
+
+fn parse_options(args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        url: "http://127.0.0.1:8080".to_string(),
+        duration: Duration::from_secs(60),
+        concurrency: 16,
+        mix: vec![(Operation::Read, 70), (Operation::Create, 5), (Operation::Bid, 25)],
+        auction: None,
+    };
+    for arg in args {
+        let (key, value) = arg
+            .strip_prefix("--")
+            .and_then(|arg| arg.split_once('='))
+            .ok_or_else(|| format!("Unexpected argument {}", arg))?;
+        let invalid = || format!("Invalid value for --{}: {}", key, value);
+        match key {
+            "url" => options.url = value.trim_end_matches('/').to_string(),
+            "duration" => options.duration = Duration::from_secs(value.parse().map_err(|_| invalid())?),
+            "concurrency" => options.concurrency = value.parse().map_err(|_| invalid())?,
+            "auction" => options.auction = Some(value.parse().map_err(|_| invalid())?),
+            "mix" => options.mix = parse_mix(value).ok_or_else(invalid)?,
+            _ => return Err(format!("Unknown option --{}", key)),
+        }
+    }
+    if options.concurrency == 0 || total_weight(&options.mix) == 0 {
+        return Err("Nothing to run".to_string());
+    }
+    Ok(options)
+}
+
+fn parse_mix(value: &str) -> Option<Vec<(Operation, u32)>> {
+    value
+        .split(',')
+        .map(|part| {
+            let (name, weight) = part.split_once(':')?;
+            let operation = match name {
+                "reads" => Operation::Read,
+                "creates" => Operation::Create,
+                "bids" => Operation::Bid,
+                _ => return None,
+            };
+            Some((operation, weight.parse().ok()?))
+        })
+        .collect()
+}
+
+fn total_weight(mix: &[(Operation, u32)]) -> u32 {
+    mix.iter().map(|(_, weight)| weight).sum()
+}
+
+/// The operation whose share of the total weight `roll` falls in.
+fn pick(mix: &[(Operation, u32)], mut roll: u32) -> Operation {
+    for (operation, weight) in mix {
+        if roll < *weight {
+            return *operation;
+        }
+        roll -= weight;
+    }
+    mix.last().map(|(operation, _)| *operation).unwrap_or(Operation::Read)
+}
+
+fn user_header(name: &str) -> String {
+    encode_jwt_payload(&User::new_buyer_or_seller(UserId::new(name), None::<String>))
+}
+
+async fn get(client: &reqwest::Client, url: &str) -> Result<reqwest::StatusCode, reqwest::Error> {
+    Ok(client.get(url).send().await?.status())
+}
+
+async fn create_auction(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<(reqwest::StatusCode, Option<i64>), reqwest::Error> {
+    let now = Utc::now();
+    let response = client
+        .post(format!("{}/auction", url))
+        .header(X_JWT_PAYLOAD, user_header("loadgen-seller"))
+        .json(&json!({
+            "title": "Load test auction",
+            "currency": "SEK",
+            "startsAt": now - ChronoDuration::minutes(1),
+            "endsAt": now + ChronoDuration::days(1),
+        }))
+        .send()
+        .await?;
+    let status = response.status();
+    let id = response.json::<Value>().await.ok().and_then(|auction| auction["id"].as_i64());
+    Ok((status, id))
+}
+
+async fn place_bid(
+    client: &reqwest::Client,
+    url: &str,
+    auction_id: i64,
+    worker: usize,
+    amount: i64,
+) -> Result<reqwest::StatusCode, reqwest::Error> {
+    let response = client
+        .post(format!("{}/auctions/{}/bids", url, auction_id))
+        .header(X_JWT_PAYLOAD, user_header(&format!("loadgen-bidder-{}", worker)))
+        .json(&json!({ "amount": { "value": amount, "currency": "SEK" } }))
+        .send()
+        .await?;
+    Ok(response.status())
+}
+
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    sorted[((sorted.len() - 1) * percent) / 100]
+}
+
+fn report(stats: &BTreeMap<Operation, Stats>, elapsed: Duration) {
+    println!(
+        "{:<8} {:>8} {:>8} {:>9} {:>9} {:>9} {:>9} {:>9} {:>9}",
+        "op", "count", "req/s", "rejected", "errors", "p50 ms", "p95 ms", "p99 ms", "max ms"
+    );
+    for (operation, stats) in stats {
+        let mut latencies = stats.latencies.clone();
+        latencies.sort();
+        let count = latencies.len();
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        println!(
+            "{:<8} {:>8} {:>8.1} {:>8.1}% {:>8.1}% {:>9.1} {:>9.1} {:>9.1} {:>9.1}",
+            format!("{:?}", operation),
+            count,
+            count as f64 / elapsed.as_secs_f64(),
+            100.0 * stats.rejected as f64 / count.max(1) as f64,
+            100.0 * stats.errors as f64 / count.max(1) as f64,
+            ms(percentile(&latencies, 50)),
+            ms(percentile(&latencies, 95)),
+            ms(percentile(&latencies, 99)),
+            ms(latencies.last().copied().unwrap_or_default()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod loadgen_tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_mix_and_options() {
+        let args = ["--mix=reads:1,bids:3", "--auction=7", "--url=http://host/"].map(String::from);
+
+        let options = parse_options(args.into_iter()).unwrap();
+
+        assert_eq!(options.mix, vec![(Operation::Read, 1), (Operation::Bid, 3)]);
+        assert_eq!(options.auction, Some(7));
+        assert_eq!(options.url, "http://host");
+        assert!(parse_options(["--mix=writes:1".to_string()].into_iter()).is_err());
+    }
+
+    #[test]
+    fn test_picks_operations_by_weight() {
+        let mix = [(Operation::Read, 1), (Operation::Bid, 3)];
+
+        assert_eq!(pick(&mix, 0), Operation::Read);
+        assert_eq!(pick(&mix, 1), Operation::Bid);
+        assert_eq!(pick(&mix, 3), Operation::Bid);
+    }
+}
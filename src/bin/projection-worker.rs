@@ -0,0 +1,115 @@
+// src/bin/projection-worker.rs
+use dotenv::dotenv;
+use tokio_util::sync::CancellationToken;
+
+use auctions_api::{
+    domain::services::RealSystemClock,
+    infrastructure::{
+        data::{create_pg_pool, PgAuctionArchive, PgAuctionRepository, PoolSettings, PgOutbox},
+        services::{Archiver, AuctionOpener, ClickHouseSink, ProjectionWorker, Relister, WarehouseExporter},
+        Settings,
+    },
+};
+
+/// Maintains read models from the auction outbox, and optionally exports it to the
+/// warehouse, so that this work scales independently of the HTTP API.
+#[tokio::main]
+async fn main() {
+    // Load environment variables
+    dotenv().ok();
+
+    // Configure logging
+    env_logger::init_from_env(env_logger::Env::default().default_filter_or("info"));
+
+    // Load configuration
+    let config = Settings::new().expect("Failed to load configuration");
+    log::info!("Starting projection worker in {} environment", config.environment);
+
+    // The API process owns the schema and runs the migrations
+    let db_pool = create_pg_pool(&config.database.url, PoolSettings::from(&config.database)).await
+        .expect("Failed to create database pool");
+
+    let shutdown = CancellationToken::new();
+    let outbox = PgOutbox::new(db_pool.clone());
+    let worker = ProjectionWorker::new(
+        outbox.clone(),
+        Box::new(PgAuctionRepository::new(db_pool.clone())),
+        Box::new(RealSystemClock),
+        config.projection_worker.clone(),
+    );
+    let mut running = vec![tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move { worker.run(shutdown).await }
+    })];
+
+    // Analytics get their copy of the data from here rather than from the API database
+    if config.warehouse_export.enabled {
+        log::info!("Exporting to the warehouse every {} seconds", config.warehouse_export.interval);
+        let exporter = WarehouseExporter::new(
+            outbox,
+            Box::new(PgAuctionRepository::new(db_pool.clone())),
+            Box::new(ClickHouseSink::new(config.warehouse_export.clone())),
+            Box::new(RealSystemClock),
+            config.warehouse_export.clone(),
+        );
+        running.push(tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { exporter.run(shutdown).await }
+        }));
+    }
+
+    // Listings filter on the stored status, kept up to date as auctions open and close
+    if config.opening.enabled {
+        log::info!("Opening and closing auctions every {} seconds", config.opening.interval);
+        let opener = AuctionOpener::new(
+            Box::new(PgAuctionRepository::new(db_pool.clone())),
+            Box::new(RealSystemClock),
+            config.opening.clone(),
+        );
+        running.push(tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { opener.run(shutdown).await }
+        }));
+    }
+
+    // Sellers can opt into relisting auctions that end unsold
+    if config.relisting.enabled {
+        log::info!("Relisting unsold auctions every {} seconds", config.relisting.interval);
+        let relister = Relister::new(
+            Box::new(PgAuctionRepository::new(db_pool.clone())),
+            Box::new(RealSystemClock),
+            config.relisting.clone(),
+        );
+        running.push(tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { relister.run(shutdown).await }
+        }));
+    }
+
+    // Auctions that ended long ago are still read by id, from the archive
+    if config.archival.enabled {
+        log::info!(
+            "Archiving auctions {} days after they end, every {} seconds",
+            config.archival.after_days,
+            config.archival.interval
+        );
+        let archiver = Archiver::new(
+            Box::new(PgAuctionArchive::new(db_pool)),
+            Box::new(RealSystemClock),
+            config.archival.clone(),
+        );
+        running.push(tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { archiver.run(shutdown).await }
+        }));
+    }
+
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        log::error!("Failed to listen for shutdown signal: {}", e);
+    }
+    log::info!("Shutting down projection worker");
+    shutdown.cancel();
+    for task in running {
+        let _ = task.await;
+    }
+}
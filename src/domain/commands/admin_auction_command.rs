@@ -0,0 +1,60 @@
+use crate::domain::models::AuctionId;
+
+/// Support-only interventions on an auction.
+#[derive(Debug, Clone)]
+pub enum AdminAuctionCommand {
+    ForceEnd {
+        auction_id: AuctionId,
+        reason: Option<String>,
+    },
+    Void {
+        auction_id: AuctionId,
+        reason: Option<String>,
+    },
+    Pause {
+        auction_id: AuctionId,
+        reason: Option<String>,
+    },
+    Resume {
+        auction_id: AuctionId,
+        reason: Option<String>,
+    },
+    /// Removes a bid, e.g. a fraudulent one, from a running auction
+    VoidBid {
+        auction_id: AuctionId,
+        bid_id: i64,
+        reason: Option<String>,
+    },
+}
+
+impl AdminAuctionCommand {
+    pub fn auction_id(&self) -> AuctionId {
+        match self {
+            AdminAuctionCommand::ForceEnd { auction_id, .. } => *auction_id,
+            AdminAuctionCommand::Void { auction_id, .. } => *auction_id,
+            AdminAuctionCommand::Pause { auction_id, .. } => *auction_id,
+            AdminAuctionCommand::Resume { auction_id, .. } => *auction_id,
+            AdminAuctionCommand::VoidBid { auction_id, .. } => *auction_id,
+        }
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            AdminAuctionCommand::ForceEnd { reason, .. } => reason.as_deref(),
+            AdminAuctionCommand::Void { reason, .. } => reason.as_deref(),
+            AdminAuctionCommand::Pause { reason, .. } => reason.as_deref(),
+            AdminAuctionCommand::Resume { reason, .. } => reason.as_deref(),
+            AdminAuctionCommand::VoidBid { reason, .. } => reason.as_deref(),
+        }
+    }
+
+    pub fn action(&self) -> &'static str {
+        match self {
+            AdminAuctionCommand::ForceEnd { .. } => "force_end",
+            AdminAuctionCommand::Void { .. } => "void",
+            AdminAuctionCommand::Pause { .. } => "pause",
+            AdminAuctionCommand::Resume { .. } => "resume",
+            AdminAuctionCommand::VoidBid { .. } => "void_bid",
+        }
+    }
+}
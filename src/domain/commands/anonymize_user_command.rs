@@ -0,0 +1,7 @@
+use crate::domain::models::UserId;
+
+/// Erases a user on request, e.g. under the GDPR: what they did stays, under a pseudonym.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnonymizeUserCommand {
+    pub user_id: UserId,
+}
@@ -0,0 +1,8 @@
+use crate::domain::models::AuctionId;
+
+/// Withdraws an auction: by its seller before any bids are placed, or by support at any time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CancelAuctionCommand {
+    pub auction_id: AuctionId,
+    pub reason: Option<String>,
+}
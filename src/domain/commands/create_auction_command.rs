@@ -1,15 +1,42 @@
 use chrono::{DateTime, Utc};
-use crate::domain::models::{CurrencyCode, SingleSealedBidOptions};
+use crate::domain::models::{BidIncrement, CurrencyCode, FeePolicy, RelistPolicy, SingleSealedBidOptions, TieBreak, VickreyPricing};
 
-#[derive(Debug, Clone)]
+/// Defaults to an auction with none of the options set, starting and ending at the epoch.
+#[derive(Debug, Clone, Default)]
 pub struct CreateAuctionCommand {
     pub title: String,
+    pub description: Option<String>,
     pub currency: CurrencyCode,
     pub starts_at: DateTime<Utc>,
     pub ends_at: DateTime<Utc>,
     pub min_raise: Option<i64>,
     pub reserve_price: Option<i64>,
     pub time_frame: Option<chrono::Duration>,
+    pub buy_now_price: Option<i64>,
+    pub starting_price: Option<i64>,
+    pub max_extension: Option<chrono::Duration>,
+    pub increments: Vec<BidIncrement>,
     pub single_sealed_bid_options: Option<SingleSealedBidOptions>,
+    pub tie_break: Option<TieBreak>,
     pub open_bidders: bool,
+    /// Only bidders approved by the seller may bid
+    pub requires_registration: bool,
+    /// Required of bidders before they may bid
+    pub deposit: Option<i64>,
+    /// Relist the auction when it ends unsold
+    pub relist_policy: Option<RelistPolicy>,
+    pub quantity: Option<i32>,
+    pub external_reference: Option<String>,
+    /// Hash of the address the seller created the auction from, see [`crate::domain::models::BidMetadata::ip_hash`]
+    pub seller_ip_hash: Option<String>,
+    /// Titles of the lots sold separately within the listing, if more than one item
+    pub lots: Vec<String>,
+    /// Keep the auction from bidders until the seller publishes it
+    pub draft: bool,
+    /// Fees of this auction, instead of those configured for all auctions
+    pub fee_policy: Option<FeePolicy>,
+    /// Tax on the fees of the auction, in basis points
+    pub tax_rate_bps: Option<i64>,
+    /// How Vickrey auctions price the winning bids, the second price as is without it
+    pub vickrey_pricing: Option<VickreyPricing>,
 }
@@ -1,9 +1,19 @@
 use serde::{Deserialize, Serialize};
 
-use crate::domain::models::{Amount, AuctionId};
+use crate::domain::models::{Amount, AuctionId, BidMetadata};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateBidCommand {
     pub amount: Amount,
     pub auction_id: AuctionId,
+    /// The lot to bid on, in auctions with several
+    #[serde(default)]
+    pub lot: Option<i32>,
+    #[serde(default)]
+    pub quantity: Option<i32>,
+    /// Bid on the bidder's behalf up to the amount
+    #[serde(default)]
+    pub max_bid: bool,
+    #[serde(default)]
+    pub metadata: Option<BidMetadata>,
 }
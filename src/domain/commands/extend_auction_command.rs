@@ -0,0 +1,11 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::models::AuctionId;
+
+/// Moves the expiry of a running auction, e.g. after an outage. Support only.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtendAuctionCommand {
+    pub auction_id: AuctionId,
+    pub expiry: DateTime<Utc>,
+    pub reason: Option<String>,
+}
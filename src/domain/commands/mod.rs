@@ -1,5 +1,23 @@
+pub mod admin_auction_command;
+pub mod anonymize_user_command;
+pub mod cancel_auction_command;
 pub mod create_auction_command;
 pub mod create_bid_command;
+pub mod extend_auction_command;
+pub mod patch_auction_command;
+pub mod publish_auction_command;
+pub mod record_deposit_command;
+pub mod registration_command;
+pub mod retract_bid_command;
 
+pub use admin_auction_command::*;
+pub use anonymize_user_command::*;
+pub use cancel_auction_command::*;
 pub use create_auction_command::*;
 pub use create_bid_command::*;
+pub use extend_auction_command::*;
+pub use patch_auction_command::*;
+pub use publish_auction_command::*;
+pub use record_deposit_command::*;
+pub use registration_command::*;
+pub use retract_bid_command::*;
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::AuctionId;
+
+/// Changes to the descriptive fields of an auction. Fields left as `None` are kept as they are;
+/// `description: Some(None)` removes the description.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PatchAuctionCommand {
+    pub auction_id: AuctionId,
+    pub title: Option<String>,
+    pub description: Option<Option<String>>,
+}
@@ -0,0 +1,7 @@
+use crate::domain::models::AuctionId;
+
+/// Makes a draft auction public, see [`crate::domain::models::Auction::publish`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublishAuctionCommand {
+    pub auction_id: AuctionId,
+}
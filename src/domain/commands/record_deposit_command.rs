@@ -0,0 +1,8 @@
+use crate::domain::models::{Amount, AuctionId};
+
+/// Records what the bidder has deposited to be allowed to bid in the auction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordDepositCommand {
+    pub auction_id: AuctionId,
+    pub amount: Amount,
+}
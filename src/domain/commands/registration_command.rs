@@ -0,0 +1,27 @@
+use crate::domain::models::{AuctionId, UserId};
+
+/// Registration of bidders in an auction that requires the seller's approval.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegistrationCommand {
+    Register {
+        auction_id: AuctionId,
+    },
+    Approve {
+        auction_id: AuctionId,
+        bidder: UserId,
+    },
+    Reject {
+        auction_id: AuctionId,
+        bidder: UserId,
+    },
+}
+
+impl RegistrationCommand {
+    pub fn auction_id(&self) -> AuctionId {
+        match self {
+            RegistrationCommand::Register { auction_id } => *auction_id,
+            RegistrationCommand::Approve { auction_id, .. } => *auction_id,
+            RegistrationCommand::Reject { auction_id, .. } => *auction_id,
+        }
+    }
+}
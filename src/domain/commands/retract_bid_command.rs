@@ -0,0 +1,7 @@
+use crate::domain::models::AuctionId;
+
+/// Withdraws the bidder's latest bid, shortly after it was placed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetractBidCommand {
+    pub auction_id: AuctionId,
+}
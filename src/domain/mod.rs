@@ -1,3 +1,5 @@
 pub mod commands;
 pub mod models;
-pub mod services;
\ No newline at end of file
+pub mod services;
+#[cfg(test)]
+pub(crate) mod test_support;
@@ -7,6 +7,8 @@ use std::str::FromStr;
 use super::currency::CurrencyCode;
 use super::errors::Error;
 
+/// An amount of money, held in the minor units of its currency so that 10.50 SEK is 1050.
+/// Written in whole units with decimals, such as `SEK10.50`, see [`CurrencyCode::minor_units`].
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Amount {
     value: i64,
@@ -17,31 +19,66 @@ impl FromStr for Amount {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let amount_regex = Regex::new(r"^(?<currency>[A-Z]+)(?<value>[0-9]+)$").unwrap();
+        let amount_regex = Regex::new(r"^(?<currency>[A-Z]+)(?<value>[0-9.]+)$").unwrap();
 
-        // Parse strings like "SEK100"
+        // Parse strings like "SEK100" or "SEK100.50"
         let captures = amount_regex
             .captures(s)
             .ok_or(Error::InvalidAmount(format!("Invalid amount value: {}", s)))?;
-        let value = captures["value"]
-            .parse()
-            .map_err(|_| Error::InvalidAmount(format!("Invalid amount value: {}", s)))?;
         let currency = captures["currency"]
             .parse()
             .map_err(|_| Error::InvalidAmount(format!("Invalid currency code: {}", s)))?;
-        Ok(Amount::new(value, currency))
+        Amount::parse_value(&captures["value"], currency)
     }
 }
 
 impl Amount {
+    /// An amount of `value` minor units of the currency.
     pub fn new(value: i64, currency: CurrencyCode) -> Self {
         Self { value, currency }
     }
 
+    /// Reads a non-negative value in whole units, with at most as many decimals as the currency
+    /// has minor units, such as `100` or `100.50` for SEK.
+    pub fn parse_value(value: &str, currency: CurrencyCode) -> Result<Self, Error> {
+        let invalid = || Error::InvalidAmount(format!("Invalid amount value: {}", value));
+        let (units, fraction) = value.split_once('.').unwrap_or((value, ""));
+        let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+        if units.is_empty() || !digits(units) || !digits(fraction) || value.ends_with('.') {
+            return Err(invalid());
+        }
+        let scale = currency.minor_units();
+        if fraction.len() > scale as usize {
+            return Err(Error::InvalidAmount(format!(
+                "{} has at most {} decimals: {}",
+                currency, scale, value
+            )));
+        }
+        let minor = format!("{}{:0<width$}", units, fraction, width = scale as usize);
+        minor
+            .parse()
+            .map(|minor| Amount::new(minor, currency))
+            .map_err(|_| Error::InvalidAmount(format!("Amount value is too large: {}", value)))
+    }
+
+    /// Writes the value in whole units with as many decimals as the currency has minor units,
+    /// such as `100.50` for SEK, the inverse of [`Amount::parse_value`].
+    pub fn format_value(&self) -> String {
+        let scale = self.currency.minor_units();
+        let sign = if self.value < 0 { "-" } else { "" };
+        let digits = self.value.unsigned_abs();
+        if scale == 0 {
+            return format!("{}{}", sign, digits);
+        }
+        let unit = 10u64.pow(scale);
+        format!("{}{}.{:0width$}", sign, digits / unit, digits % unit, width = scale as usize)
+    }
+
     pub fn zero(currency: CurrencyCode) -> Self {
         Self::new(0, currency)
     }
 
+    /// In minor units of the currency.
     pub fn value(&self) -> i64 {
         self.value
     }
@@ -50,6 +87,11 @@ impl Amount {
         self.currency
     }
 
+    /// More than zero, as bids, deposits and prices must be.
+    pub fn is_positive(&self) -> bool {
+        self.value > 0
+    }
+
     fn assert_same_currency(&self, other: &Self) -> Result<(), Error> {
         if self.currency != other.currency {
             Err(Error::CurrencyMismatch(
@@ -64,7 +106,7 @@ impl Amount {
 
 impl fmt::Display for Amount {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}{}", self.currency, self.value)
+        write!(f, "{}{}", self.currency, self.format_value())
     }
 }
 
@@ -119,10 +161,21 @@ mod amount_tests {
         let amount: Result<Amount, _> = "SEK100".parse();
         assert!(amount.is_ok());
         let amount = amount.unwrap();
-        assert_eq!(amount.value(), 100);
+        assert_eq!(amount.value(), 10000);
         assert_eq!(amount.currency(), CurrencyCode::SEK);
     }
 
+    #[test]
+    fn test_amount_from_string_in_minor_units() {
+        assert_eq!("SEK100.5".parse::<Amount>().unwrap(), Amount::new(10050, CurrencyCode::SEK));
+        assert_eq!("SEK0.05".parse::<Amount>().unwrap(), Amount::new(5, CurrencyCode::SEK));
+        assert_eq!("VAC100".parse::<Amount>().unwrap(), Amount::new(100, CurrencyCode::VAC));
+        assert!("SEK100.505".parse::<Amount>().is_err());
+        assert!("VAC100.5".parse::<Amount>().is_err());
+        assert!("SEK100.".parse::<Amount>().is_err());
+        assert!("SEK.50".parse::<Amount>().is_err());
+    }
+
     #[test]
     fn test_amount_from_string_invalid_currency() {
         let result: Result<Amount, _> = "XYZ100".parse();
@@ -147,10 +200,26 @@ mod amount_tests {
         }
     }
 
+    #[test]
+    fn test_only_amounts_over_zero_are_positive() {
+        assert!(Amount::new(1, CurrencyCode::SEK).is_positive());
+        assert!(!Amount::zero(CurrencyCode::SEK).is_positive());
+        assert!(!Amount::new(-100, CurrencyCode::SEK).is_positive());
+    }
+
     #[test]
     fn test_amount_display() {
-        let amount = Amount::new(100, CurrencyCode::SEK);
-        assert_eq!(amount.to_string(), "SEK100");
+        assert_eq!(Amount::new(10050, CurrencyCode::SEK).to_string(), "SEK100.50");
+        assert_eq!(Amount::new(5, CurrencyCode::DKK).to_string(), "DKK0.05");
+        assert_eq!(Amount::new(-150, CurrencyCode::SEK).to_string(), "SEK-1.50");
+        assert_eq!(Amount::new(100, CurrencyCode::VAC).to_string(), "VAC100");
+    }
+
+    #[test]
+    fn test_amount_display_round_trips() {
+        for amount in [Amount::new(10050, CurrencyCode::SEK), Amount::new(7, CurrencyCode::VAC)] {
+            assert_eq!(amount.to_string().parse::<Amount>().unwrap(), amount);
+        }
     }
 
     #[test]
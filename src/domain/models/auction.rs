@@ -2,10 +2,16 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use super::amount::Amount;
-use super::bid::Bid;
+use super::auction_result::AuctionResult;
+use super::auction_status::AuctionStatus;
+use super::bid::{Bid, RetractedBid, VoidedBid};
 use super::currency::CurrencyCode;
-use super::errors::Errors;
+use super::domain_event::DomainEvent;
+use super::errors::{Errors, Violation};
+use super::max_bid::MaxBid;
+use super::settlement::{FeePolicy, Settlement};
 use super::user::UserId;
+use std::collections::HashMap;
 use std::fmt;
 use crate::domain::commands::CreateAuctionCommand;
 use crate::domain::models::BidData;
@@ -54,6 +60,59 @@ pub trait AuctionState {
 pub enum SingleSealedBidOptions {
     Blind,
     Vickrey,
+    /// Every bidder pays their bid, only the highest bids win, see [`Auction::try_get_charges`]
+    AllPay,
+}
+
+/// Which of several equally high bids wins. Bids are ordered by id, which reflects the order
+/// they were accepted in and is kept as is by the repositories.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TieBreak {
+    #[default]
+    EarliestBid,
+    LatestBid,
+}
+
+/// What the winners of a Vickrey auction pay, worked out from the highest losing bid, so that
+/// the price can land on a round amount. Winners never pay more than they bid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VickreyPricing {
+    /// The highest losing bid as is
+    #[default]
+    SecondPrice,
+    /// The highest losing bid rounded up to a whole number of increments
+    RoundUp { increment: i64 },
+    /// One increment over the highest losing bid
+    IncrementOver { increment: i64 },
+}
+
+impl VickreyPricing {
+    /// The price of a winning bid of `bid` when the highest losing bid is `second_price`.
+    pub fn price(&self, second_price: i64, bid: i64) -> i64 {
+        let price = match *self {
+            VickreyPricing::SecondPrice => second_price,
+            VickreyPricing::RoundUp { increment } if increment > 0 => {
+                (second_price + increment - 1).div_euclid(increment) * increment
+            }
+            VickreyPricing::IncrementOver { increment } => second_price + increment,
+            VickreyPricing::RoundUp { .. } => second_price,
+        };
+        price.min(bid)
+    }
+
+    fn increment(&self) -> i64 {
+        match *self {
+            VickreyPricing::SecondPrice => 0,
+            VickreyPricing::RoundUp { increment } | VickreyPricing::IncrementOver { increment } => increment,
+        }
+    }
+}
+
+/// Minimum raise for bids while the standing price is below `below`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BidIncrement {
+    pub below: i64,
+    pub increment: i64,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -61,6 +120,41 @@ pub struct TimedAscendingOptions {
     pub reserve_price: i64,
     pub min_raise: i64,
     pub time_frame: chrono::Duration,
+    /// A bid at or above this price ends the auction immediately
+    #[serde(default)]
+    pub buy_now_price: Option<i64>,
+    /// Minimum opening bid
+    #[serde(default)]
+    pub starting_price: i64,
+    /// Late bids do not extend the auction further than this past the scheduled expiry
+    #[serde(default)]
+    pub max_extension: Option<chrono::Duration>,
+    /// Tiered increments, ordered by `below`; `min_raise` applies above the last tier
+    #[serde(default)]
+    pub increments: Vec<BidIncrement>,
+}
+
+impl TimedAscendingOptions {
+    /// How much a bid has to raise the standing price `current` by.
+    pub fn minimum_raise(&self, current: i64) -> i64 {
+        self.increments
+            .iter()
+            .find(|step| current < step.below)
+            .map_or(self.min_raise, |step| step.increment)
+    }
+
+    /// Lowest acceptable bid given the standing price, or the opening price without bids.
+    pub fn minimum_next_bid(&self, current: Option<i64>) -> i64 {
+        match current {
+            Some(value) => value + self.minimum_raise(value).max(1),
+            None => self.starting_price.max(1),
+        }
+    }
+
+    pub fn is_buy_now(&self, amount: &Amount) -> bool {
+        self.buy_now_price
+            .is_some_and(|price| amount.value() >= price)
+    }
 }
 
 impl Default for TimedAscendingOptions {
@@ -69,6 +163,10 @@ impl Default for TimedAscendingOptions {
             reserve_price: 0,
             min_raise: 0,
             time_frame: chrono::Duration::seconds(0),
+            buy_now_price: None,
+            starting_price: 0,
+            max_extension: None,
+            increments: Vec::new(),
         }
     }
 }
@@ -81,12 +179,22 @@ pub enum Auction {
         #[serde(flatten)]
         base: AuctionBase,
         options: SingleSealedBidOptions,
+        #[serde(default)]
+        tie_break: TieBreak,
+        /// Lowest price per unit the seller accepts, without it there is no sale
+        #[serde(default)]
+        reserve_price: Option<i64>,
+        /// Only used by Vickrey auctions
+        #[serde(default)]
+        pricing: VickreyPricing,
     },
     TimedAscending {
         #[serde(flatten)]
         base: AuctionBase,
         options: TimedAscendingOptions,
         ends_at: Option<DateTime<Utc>>,
+        #[serde(default)]
+        max_bids: Vec<MaxBid>,
     },
 }
 
@@ -94,12 +202,129 @@ pub enum Auction {
 pub struct AuctionBase {
     pub auction_id: AuctionId,
     pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
     pub starts_at: DateTime<Utc>,
     pub expiry: DateTime<Utc>,
     pub user: UserId,
     pub currency: CurrencyCode,
     pub bids: Vec<Bid>,
     pub open_bidders: bool,
+    /// Only bidders approved by the seller may bid, see [`super::Registration`]
+    #[serde(default)]
+    pub requires_registration: bool,
+    /// What bidders must have deposited before bidding, in the currency of the auction,
+    /// see [`super::Deposit`]
+    #[serde(default)]
+    pub deposit: Option<i64>,
+    /// Number of identical items sold in the auction
+    #[serde(default = "super::bid::default_quantity")]
+    pub quantity: i32,
+    /// Client-supplied reference, unique per seller
+    #[serde(default)]
+    pub external_reference: Option<String>,
+    /// Hash of the address the seller created the auction from, for screening bids
+    #[serde(default)]
+    pub seller_ip_hash: Option<String>,
+    /// Items bid on and won separately within the listing; empty for an auction of a single item
+    #[serde(default)]
+    pub lots: Vec<Lot>,
+    /// Fees of this auction, instead of those configured for all auctions
+    #[serde(default)]
+    pub fee_policy: Option<FeePolicy>,
+    /// Tax on the fees of the auction, in basis points
+    #[serde(default)]
+    pub tax_rate_bps: Option<i64>,
+    #[serde(default)]
+    pub voided_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub void_reason: Option<String>,
+    /// Status as of the last change, see [`Auction::status_at`]
+    #[serde(default)]
+    pub status: AuctionStatus,
+    #[serde(default)]
+    pub cancelled_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub cancel_reason: Option<String>,
+    #[serde(default)]
+    pub retracted_bids: Vec<RetractedBid>,
+    /// Bids removed by support, see [`Auction::void_bid`]
+    #[serde(default)]
+    pub voided_bids: Vec<VoidedBid>,
+    /// When support last moved the expiry, see [`Auction::extend`]
+    #[serde(default)]
+    pub extended_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub extension_reason: Option<String>,
+    /// Oldest first, see [`Auction::pause`]
+    #[serde(default)]
+    pub pauses: Vec<AuctionPause>,
+    /// Relisting of the auction when it ends unsold, see [`Auction::relist`]
+    #[serde(default)]
+    pub relist_policy: Option<RelistPolicy>,
+    /// The unsold auction this one relists
+    #[serde(default)]
+    pub relisted_from: Option<AuctionId>,
+    /// The auction that relists this one
+    #[serde(default)]
+    pub relisted_as: Option<AuctionId>,
+    /// How many times the item was relisted before this auction
+    #[serde(default)]
+    pub relist_count: i32,
+    /// Changes each time the auction is stored, so that a store can tell when the auction was
+    /// changed by someone else since it was read
+    #[serde(default)]
+    pub version: i32,
+    /// The id of the last bid placed, counted on by the store so that ids are never handed out
+    /// twice, see [`Auction::set_last_bid_id`]
+    #[serde(default)]
+    pub last_bid_id: i64,
+}
+
+/// Interval during which support stopped an auction from taking bids.
+/// `resumed_at` is empty while the auction is paused.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuctionPause {
+    pub paused_at: DateTime<Utc>,
+    pub resumed_at: Option<DateTime<Utc>>,
+}
+
+/// How an auction was taken down, see [`Auction::removal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RemovalKind {
+    Voided,
+    Cancelled,
+}
+
+/// When and why an auction was taken down. It is kept for the record, but is gone for bidders.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuctionRemoval {
+    pub kind: RemovalKind,
+    pub at: DateTime<Utc>,
+    pub reason: Option<String>,
+}
+
+/// One of several items sold in the same listing, each with its own bids and winners.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lot {
+    /// Numbered from 1 in the order the seller listed them
+    pub id: i32,
+    pub title: String,
+}
+
+/// Seller's opt-in to relist an auction that ends without a winner.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelistPolicy {
+    /// Length of the window of each relisted auction, which opens when it is relisted
+    pub duration_seconds: i64,
+    /// How many times the item is relisted at most
+    pub max_relists: i32,
+}
+
+impl RelistPolicy {
+    pub fn duration(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.duration_seconds)
+    }
 }
 
 impl Auction {
@@ -117,6 +342,13 @@ impl Auction {
         }
     }
 
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base.description.as_deref(),
+            Auction::TimedAscending { base, .. } => base.description.as_deref(),
+        }
+    }
+
     pub fn starts_at(&self) -> DateTime<Utc> {
         match self {
             Auction::SingleSealedBid { base, .. } => base.starts_at,
@@ -166,6 +398,446 @@ impl Auction {
         }
     }
 
+    pub fn requires_registration(&self) -> bool {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base.requires_registration,
+            Auction::TimedAscending { base, .. } => base.requires_registration,
+        }
+    }
+
+    pub fn deposit(&self) -> Option<Amount> {
+        let base = match self {
+            Auction::SingleSealedBid { base, .. } => base,
+            Auction::TimedAscending { base, .. } => base,
+        };
+        base.deposit.map(|value| Amount::new(value, base.currency))
+    }
+
+    pub fn quantity(&self) -> i32 {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base.quantity,
+            Auction::TimedAscending { base, .. } => base.quantity,
+        }
+    }
+
+    pub fn external_reference(&self) -> Option<&str> {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base.external_reference.as_deref(),
+            Auction::TimedAscending { base, .. } => base.external_reference.as_deref(),
+        }
+    }
+
+    pub fn seller_ip_hash(&self) -> Option<&str> {
+        self.base().seller_ip_hash.as_deref()
+    }
+
+    pub fn voided_at(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base.voided_at,
+            Auction::TimedAscending { base, .. } => base.voided_at,
+        }
+    }
+
+    pub fn void_reason(&self) -> Option<&str> {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base.void_reason.as_deref(),
+            Auction::TimedAscending { base, .. } => base.void_reason.as_deref(),
+        }
+    }
+
+    /// Set once the auction was taken down, by support voiding it or by its seller cancelling it.
+    pub fn removal(&self) -> Option<AuctionRemoval> {
+        let voided = self.voided_at().map(|at| AuctionRemoval {
+            kind: RemovalKind::Voided,
+            at,
+            reason: self.void_reason().map(str::to_string),
+        });
+        voided.or_else(|| {
+            self.cancelled_at().map(|at| AuctionRemoval {
+                kind: RemovalKind::Cancelled,
+                at,
+                reason: self.cancel_reason().map(str::to_string),
+            })
+        })
+    }
+
+    pub fn cancelled_at(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base.cancelled_at,
+            Auction::TimedAscending { base, .. } => base.cancelled_at,
+        }
+    }
+
+    pub fn cancel_reason(&self) -> Option<&str> {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base.cancel_reason.as_deref(),
+            Auction::TimedAscending { base, .. } => base.cancel_reason.as_deref(),
+        }
+    }
+
+    pub fn extended_at(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base.extended_at,
+            Auction::TimedAscending { base, .. } => base.extended_at,
+        }
+    }
+
+    pub fn extension_reason(&self) -> Option<&str> {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base.extension_reason.as_deref(),
+            Auction::TimedAscending { base, .. } => base.extension_reason.as_deref(),
+        }
+    }
+
+    pub fn pauses(&self) -> &[AuctionPause] {
+        match self {
+            Auction::SingleSealedBid { base, .. } => &base.pauses,
+            Auction::TimedAscending { base, .. } => &base.pauses,
+        }
+    }
+
+    /// Start of the current pause, if the auction is paused.
+    pub fn paused_at(&self) -> Option<DateTime<Utc>> {
+        if self.status().has_ended() {
+            return None;
+        }
+        self.pauses()
+            .last()
+            .filter(|pause| pause.resumed_at.is_none())
+            .map(|pause| pause.paused_at)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at().is_some()
+    }
+
+    pub fn retracted_bids(&self) -> &[RetractedBid] {
+        match self {
+            Auction::SingleSealedBid { base, .. } => &base.retracted_bids,
+            Auction::TimedAscending { base, .. } => &base.retracted_bids,
+        }
+    }
+
+    pub fn voided_bids(&self) -> &[VoidedBid] {
+        &self.base().voided_bids
+    }
+
+    /// The stored status, which may lag behind the clock; see [`Auction::status_at`].
+    pub fn status(&self) -> AuctionStatus {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base.status,
+            Auction::TimedAscending { base, .. } => base.status,
+        }
+    }
+
+    fn set_status(&mut self, status: AuctionStatus) {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base.status = status,
+            Auction::TimedAscending { base, .. } => base.status = status,
+        }
+    }
+
+    /// The stored status moved along the transitions that only depend on time:
+    /// a scheduled auction opens at `starts_at` and an open one closes once it has ended.
+    pub fn status_at(&self, now: DateTime<Utc>) -> AuctionStatus {
+        let status = self.status();
+        let status = match status {
+            AuctionStatus::Scheduled if now >= self.starts_at() => AuctionStatus::Open,
+            _ => status,
+        };
+        match status {
+            AuctionStatus::Open if self.is_past_end(now) => AuctionStatus::Closed,
+            _ => status,
+        }
+    }
+
+    /// Stores the status as of `now`.
+    pub fn advance(&mut self, now: DateTime<Utc>) -> AuctionStatus {
+        let status = self.status_at(now);
+        self.set_status(status);
+        status
+    }
+
+    fn transition_to(&mut self, now: DateTime<Utc>, next: AuctionStatus) -> Result<(), Errors> {
+        let status = self.advance(now).transition_to(next)?;
+        self.set_status(status);
+        Ok(())
+    }
+
+    /// Makes a draft auction public, once it has a title and ends in the future. It opens for bids
+    /// at its start like any scheduled auction.
+    pub fn publish(&mut self, now: DateTime<Utc>) -> Result<(), Errors> {
+        if self.status() != AuctionStatus::Draft {
+            return Err(Errors::IllegalStatusTransition);
+        }
+        if self.title().trim().is_empty() {
+            return Err(Errors::AuctionIncomplete);
+        }
+        if self.expiry() <= now.max(self.starts_at()) {
+            return Err(Errors::ExpiryMustBeLater);
+        }
+        self.transition_to(now, AuctionStatus::Scheduled)
+    }
+
+    /// Ends a running or scheduled auction immediately.
+    pub fn force_end(&mut self, now: DateTime<Utc>) -> Result<(), Errors> {
+        if self.has_ended(now) {
+            return Err(Errors::AuctionHasEnded);
+        }
+        self.transition_to(now, AuctionStatus::Closed)?;
+        match self {
+            Auction::SingleSealedBid { base, .. } => base.expiry = now,
+            Auction::TimedAscending { base, ends_at, .. } => {
+                base.expiry = now;
+                *ends_at = Some(now);
+            }
+        }
+        Ok(())
+    }
+
+    /// Annuls the outcome of the auction, ending it first if it is still running.
+    /// A voided auction is cancelled and has no winner.
+    pub fn void(&mut self, now: DateTime<Utc>, reason: Option<String>) -> Result<(), Errors> {
+        if !self.has_ended(now) {
+            self.force_end(now)?;
+        }
+        self.transition_to(now, AuctionStatus::Cancelled)?;
+        let base = match self {
+            Auction::SingleSealedBid { base, .. } => base,
+            Auction::TimedAscending { base, .. } => base,
+        };
+        base.voided_at = Some(now);
+        base.void_reason = reason;
+        Ok(())
+    }
+
+    /// Withdraws the auction. A cancelled auction takes no more bids and has no winner.
+    pub fn cancel(&mut self, now: DateTime<Utc>, reason: Option<String>) -> Result<(), Errors> {
+        self.transition_to(now, AuctionStatus::Cancelled)?;
+        let base = match self {
+            Auction::SingleSealedBid { base, .. } => base,
+            Auction::TimedAscending { base, .. } => base,
+        };
+        base.cancelled_at = Some(now);
+        base.cancel_reason = reason;
+        Ok(())
+    }
+
+    /// Moves the end of an auction that has not ended yet to `expiry`, e.g. to make up for an outage.
+    /// The new expiry must be later than the current end, including any extension by late bids.
+    pub fn extend(
+        &mut self,
+        now: DateTime<Utc>,
+        expiry: DateTime<Utc>,
+        reason: Option<String>,
+    ) -> Result<(), Errors> {
+        if self.advance(now).has_ended() {
+            return Err(Errors::AuctionHasEnded);
+        }
+        if expiry <= self.effective_end() {
+            return Err(Errors::ExpiryMustBeLater);
+        }
+        let base = match self {
+            Auction::SingleSealedBid { base, .. } => base,
+            Auction::TimedAscending { base, ends_at, .. } => {
+                if ends_at.is_some() {
+                    *ends_at = Some(expiry);
+                }
+                base
+            }
+        };
+        base.expiry = expiry;
+        base.extended_at = Some(now);
+        base.extension_reason = reason;
+        Ok(())
+    }
+
+    /// Stops an open auction from taking bids until it is resumed. The clock stops as well:
+    /// a paused auction does not end.
+    pub fn pause(&mut self, now: DateTime<Utc>) -> Result<(), Errors> {
+        if self.is_paused() {
+            return Err(Errors::AuctionIsPaused);
+        }
+        self.check_accepts_bids(now)?;
+        let base = match self {
+            Auction::SingleSealedBid { base, .. } => base,
+            Auction::TimedAscending { base, .. } => base,
+        };
+        base.pauses.push(AuctionPause {
+            paused_at: now,
+            resumed_at: None,
+        });
+        Ok(())
+    }
+
+    /// Takes bids again, with the end moved by as long as the auction was paused.
+    pub fn resume(&mut self, now: DateTime<Utc>) -> Result<(), Errors> {
+        let paused_at = self.paused_at().ok_or(Errors::AuctionIsNotPaused)?;
+        let paused_for = now - paused_at;
+        let base = match self {
+            Auction::SingleSealedBid { base, .. } => base,
+            Auction::TimedAscending { base, ends_at, .. } => {
+                *ends_at = ends_at.map(|end| end + paused_for);
+                base
+            }
+        };
+        base.expiry += paused_for;
+        if let Some(pause) = base.pauses.last_mut() {
+            pause.resumed_at = Some(now);
+        }
+        Ok(())
+    }
+
+    fn base(&self) -> &AuctionBase {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base,
+            Auction::TimedAscending { base, .. } => base,
+        }
+    }
+
+    fn base_mut(&mut self) -> &mut AuctionBase {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base,
+            Auction::TimedAscending { base, .. } => base,
+        }
+    }
+
+    pub fn lots(&self) -> &[Lot] {
+        &self.base().lots
+    }
+
+    pub fn fee_policy(&self) -> Option<&FeePolicy> {
+        self.base().fee_policy.as_ref()
+    }
+
+    pub fn tax_rate_bps(&self) -> Option<i64> {
+        self.base().tax_rate_bps
+    }
+
+    /// What the buyers pay and the seller receives once the auction has closed, by the fees of
+    /// the auction or else `default_policy`, with tax at the rate of the auction if any.
+    pub fn try_get_settlement(&self, time: DateTime<Utc>, default_policy: &FeePolicy) -> Option<Settlement> {
+        if self.status_at(time) != AuctionStatus::Closed {
+            return None;
+        }
+        let policy = self.fee_policy().unwrap_or(default_policy);
+        Some(policy.settle(self.currency(), self.tax_rate_bps().unwrap_or(0), self.try_get_charges(time)))
+    }
+
+    /// The auction as the bidders on one lot see it, with the bids on that lot only.
+    pub fn lot_view(&self, lot_id: i32) -> Option<Auction> {
+        if !self.lots().iter().any(|lot| lot.id == lot_id) {
+            return None;
+        }
+        let mut view = self.clone();
+        let base = view.base_mut();
+        base.lots = Vec::new();
+        base.bids.retain(|bid| bid.lot == Some(lot_id));
+        Some(view)
+    }
+
+    pub fn relist_policy(&self) -> Option<&RelistPolicy> {
+        self.base().relist_policy.as_ref()
+    }
+
+    pub fn relisted_from(&self) -> Option<AuctionId> {
+        self.base().relisted_from
+    }
+
+    pub fn relisted_as(&self) -> Option<AuctionId> {
+        self.base().relisted_as
+    }
+
+    pub fn relist_count(&self) -> i32 {
+        self.base().relist_count
+    }
+
+    pub fn set_relisted_as(&mut self, id: AuctionId) {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base.relisted_as = Some(id),
+            Auction::TimedAscending { base, .. } => base.relisted_as = Some(id),
+        }
+    }
+
+    /// Whether the auction ended at least `retention` before `now`, long enough ago to be moved
+    /// out of the tables of running auctions.
+    pub fn can_archive(&self, now: DateTime<Utc>, retention: chrono::Duration) -> bool {
+        self.has_ended(now) && self.effective_end() + retention <= now
+    }
+
+    /// Whether the seller opted into relisting and the auction closed without a winner,
+    /// and has not been relisted yet.
+    pub fn can_relist(&self, now: DateTime<Utc>) -> bool {
+        self.relist_policy()
+            .is_some_and(|policy| self.relist_count() < policy.max_relists)
+            && self.relisted_as().is_none()
+            && self.status_at(now) == AuctionStatus::Closed
+            && self.try_get_winners(now).is_empty()
+    }
+
+    /// A new auction for the same item, opening `now` and running for the duration of the
+    /// relist policy. The new auction links back to this one; [`Auction::set_relisted_as`]
+    /// links this one forward once the new one is stored.
+    pub fn relist(&self, now: DateTime<Utc>) -> Result<Auction, Errors> {
+        if !self.can_relist(now) {
+            return Err(Errors::AuctionCannotBeRelisted);
+        }
+        let duration = self.relist_policy().map_or_else(chrono::Duration::zero, RelistPolicy::duration);
+        let mut relisted = self.clone();
+        let base = match &mut relisted {
+            Auction::SingleSealedBid { base, .. } => base,
+            Auction::TimedAscending { base, ends_at, max_bids, .. } => {
+                *ends_at = None;
+                max_bids.clear();
+                base
+            }
+        };
+        base.auction_id = AuctionId::new(0);
+        base.starts_at = now;
+        base.expiry = now + duration;
+        base.bids = Vec::new();
+        // The reference identifies the original listing, unique per seller
+        base.external_reference = None;
+        base.voided_at = None;
+        base.void_reason = None;
+        base.status = AuctionStatus::Scheduled;
+        base.cancelled_at = None;
+        base.cancel_reason = None;
+        base.retracted_bids = Vec::new();
+        base.voided_bids = Vec::new();
+        base.extended_at = None;
+        base.extension_reason = None;
+        base.pauses = Vec::new();
+        base.relisted_from = Some(self.auction_id());
+        base.relisted_as = None;
+        base.relist_count = self.relist_count() + 1;
+        Ok(relisted)
+    }
+
+    /// Changes the descriptive fields of an auction that has not ended yet.
+    /// `description: Some(None)` removes the description.
+    pub fn update_details(
+        &mut self,
+        now: DateTime<Utc>,
+        title: Option<String>,
+        description: Option<Option<String>>,
+    ) -> Result<(), Errors> {
+        if self.advance(now).has_ended() {
+            return Err(Errors::AuctionHasEnded);
+        }
+        let base = match self {
+            Auction::SingleSealedBid { base, .. } => base,
+            Auction::TimedAscending { base, .. } => base,
+        };
+        if let Some(title) = title {
+            base.title = title;
+        }
+        if let Some(description) = description {
+            base.description = description;
+        }
+        Ok(())
+    }
+
     pub fn set_open_bidders(&mut self, open: bool) {
         match self {
             Auction::SingleSealedBid { base, .. } => base.open_bidders = open,
@@ -173,6 +845,27 @@ impl Auction {
         }
     }
 
+    /// The version the auction was read at, see [`AuctionBase::version`].
+    pub fn version(&self) -> i32 {
+        self.base().version
+    }
+
+    pub fn set_version(&mut self, version: i32) {
+        self.base_mut().version = version;
+    }
+
+    /// The id of the last bid placed, see [`AuctionBase::last_bid_id`].
+    pub fn last_bid_id(&self) -> i64 {
+        self.base().last_bid_id
+    }
+
+    /// Continues numbering bids after `id`, as assigned by the store. Ids already handed out are
+    /// never reused, so the counter only moves forward.
+    pub fn set_last_bid_id(&mut self, id: i64) {
+        let base = self.base_mut();
+        base.last_bid_id = base.last_bid_id.max(id);
+    }
+
     pub fn set_auction_id(&mut self, id: AuctionId) {
         match self {
             Auction::SingleSealedBid { base, .. } => base.auction_id = id,
@@ -187,40 +880,119 @@ impl Auction {
         }
     }
 
+    pub fn highest_bid(&self) -> Option<&Bid> {
+        self.bids().iter().max_by_key(|b| b.amount().value())
+    }
+
+    /// The outcome of the auction once it has closed; cancelled auctions have none.
+    pub fn result(&self, now: DateTime<Utc>) -> Option<AuctionResult> {
+        if self.status_at(now) != AuctionStatus::Closed {
+            return None;
+        }
+        let reserve = match self {
+            Auction::SingleSealedBid { reserve_price, .. } => reserve_price.unwrap_or(0),
+            Auction::TimedAscending { options, .. } => options.reserve_price,
+        };
+        let winner = self.try_get_amount_and_winner(now);
+        Some(AuctionResult {
+            auction_id: self.auction_id(),
+            amount: winner.as_ref().map(|(amount, _)| amount.clone()),
+            winner: winner.map(|(_, user)| user),
+            closed_at: self.effective_end(),
+            reserve_met: self.highest_bid().is_some_and(|bid| bid.amount().value() >= reserve),
+        })
+    }
+
+    /// Lowest amount a bid would be accepted for, given the standing bids and the raise rules,
+    /// or none once the auction has ended. Sealed bid auctions have none, as the bids are secret.
+    pub fn next_minimum_bid(&self, now: DateTime<Utc>) -> Option<Amount> {
+        // With several lots, each lot has its own, see [`Auction::lot_view`]
+        if self.status_at(now).has_ended() || !self.lots().is_empty() {
+            return None;
+        }
+        match self {
+            Auction::SingleSealedBid { .. } => None,
+            Auction::TimedAscending { base, options, .. } => {
+                // Once all units are taken, the lowest winning bid is the one to beat
+                let allocation = allocate_units(&base.bids, base.quantity, TieBreak::EarliestBid);
+                let allocated: i32 = allocation.iter().map(|(_, units)| units).sum();
+                let current = allocation
+                    .last()
+                    .filter(|_| allocated >= base.quantity)
+                    .map(|(bid, _)| bid.amount().value());
+                Some(Amount::new(options.minimum_next_bid(current), base.currency))
+            }
+        }
+    }
+
+    /// End time including any extension caused by late bids.
+    pub fn effective_end(&self) -> DateTime<Utc> {
+        match self {
+            Auction::SingleSealedBid { base, .. } => base.expiry,
+            Auction::TimedAscending { base, ends_at, .. } => ends_at.unwrap_or(base.expiry),
+        }
+    }
+
+    /// Stable pseudonyms ("Bidder A", "Bidder B", ...) assigned in order of each bidder's first bid,
+    /// so that clients can follow the bidding without learning identities.
+    pub fn bidder_aliases(&self) -> HashMap<UserId, String> {
+        let mut bids: Vec<&Bid> = self.bids().iter().collect();
+        bids.sort_by_key(|b| b.id);
+        bidder_aliases(bids.into_iter().map(Bid::user))
+    }
+
     // Implementation of validation for bid
     fn validate_bid(&self, bid: &BidData) -> Errors {
         let mut errors = Errors::None;
 
         // Check if seller is bidding on their own auction
         if bid.user == *self.user() {
-            errors = errors | Errors::SellerCannotPlaceBids;
+            errors |= Errors::SellerCannotPlaceBids;
+        }
+
+        // Nothing is bought for free, not even on the first bid
+        if !bid.amount.is_positive() {
+            errors |= Errors::MustSpecifyAmount;
         }
 
         // Check currency match
         if bid.amount.currency() != self.currency() {
-            errors = errors | Errors::BidCurrencyConversion;
+            errors |= Errors::BidCurrencyConversion;
         }
 
         // Check auction timing
         if bid.at < self.starts_at() {
-            errors = errors | Errors::AuctionHasNotStarted;
+            errors |= Errors::AuctionHasNotStarted;
+        }
+        if bid.at > self.effective_end() {
+            errors |= Errors::AuctionHasEnded;
         }
-        if bid.at > self.expiry() {
-            errors = errors | Errors::AuctionHasEnded;
+
+        // Check that the requested units are on offer
+        if bid.quantity < 1 || bid.quantity > self.quantity() {
+            errors |= Errors::InvalidQuantity;
         }
 
         errors
     }
 
     // Implement the state pattern for auction states
-    pub fn try_add_bid(&mut self, time: DateTime<Utc>, bid: BidData) -> Result<bool, Errors> {
+    pub fn try_add_bid(&mut self, time: DateTime<Utc>, bid: BidData) -> Result<Vec<DomainEvent>, Errors> {
+        if self.is_paused() {
+            return Err(Errors::AuctionIsPaused);
+        }
+        if !self.lots().is_empty() {
+            return Err(Errors::LotRequired);
+        }
         let errors = self.validate_bid(&bid);
-        if errors != Errors::None {
+        if !errors.is_none() {
             return Err(errors);
         }
+        self.check_accepts_bids(time)?;
+        let before = self.progress();
 
-        match self {
-            Auction::SingleSealedBid { base, options: _ } => {
+        let added = match self {
+            Auction::SingleSealedBid { base, .. } => {
                 // Single sealed bid auction logic
                 if time > base.expiry {
                     return Err(Errors::AuctionHasEnded);
@@ -231,78 +1003,312 @@ impl Auction {
                 }
 
                 // Check if bidder already placed a bid
-                let user_already_bid = base.bids.iter().any(|b| b.user() == bid.user);
+                let user_already_bid = base.bids.iter().any(|b| *b.user() == bid.user);
                 if user_already_bid {
                     return Err(Errors::AlreadyPlacedBid);
                 }
 
                 // Add bid
-                let next_id = base.bids.len() as i64 + 1;
-                let bid_entity = Bid::new(
-                    next_id,
-                    bid.user.clone(),
-                    bid.amount.clone(),
-                    bid.at,
-                );
-                base.bids.push(bid_entity);
+                let id = next_bid_id(base);
+                base.bids.push(Bid { id, lot: None, data: bid });
                 
-                Ok(true)
+                Ok(())
             },
-            Auction::TimedAscending { base, options, ends_at } => {
+            Auction::TimedAscending { base, options, ends_at, max_bids } => {
                 // Timed ascending auction logic
-                if time > base.expiry {
-                    return Err(Errors::AuctionHasEnded);
+                check_timed_bid(base, options, ends_at, time, &bid)?;
+                push_timed_bid(base, options, ends_at, time, bid);
+
+                // Bidders with a maximum bid respond automatically
+                resolve_proxy_bids(base, options, ends_at, max_bids, time);
+
+                Ok(())
+            },
+        };
+        // A buy-now bid closes the auction
+        self.advance(time);
+        added.map(|()| self.events_since(before, time))
+    }
+
+    fn progress(&self) -> Progress {
+        Progress {
+            bids: self.bids().len(),
+            ends_at: self.effective_end(),
+            ended: self.status().has_ended(),
+        }
+    }
+
+    /// The bids placed, extension and end of the auction since `before`, in that order.
+    fn events_since(&self, before: Progress, time: DateTime<Utc>) -> Vec<DomainEvent> {
+        let auction_id = self.auction_id();
+        let mut events: Vec<DomainEvent> = self.bids()[before.bids..]
+            .iter()
+            .map(|bid| DomainEvent::BidPlaced { auction_id, bid: bid.clone() })
+            .collect();
+        let ended = self.status().has_ended();
+        if ended && !before.ended {
+            events.push(DomainEvent::AuctionEnded { auction_id, at: time });
+        } else if self.effective_end() > before.ends_at {
+            events.push(DomainEvent::AuctionExtended { auction_id, ends_at: self.effective_end() });
+        }
+        events
+    }
+
+    /// Bids on one lot of an auction with several lots, by the rules of the auction as if the lot
+    /// were sold on its own. A late bid on any lot extends the whole auction.
+    pub fn try_add_lot_bid(&mut self, time: DateTime<Utc>, lot_id: i32, bid: BidData) -> Result<Vec<DomainEvent>, Errors> {
+        let mut view = self.lot_view(lot_id).ok_or(Errors::UnknownLot)?;
+        let before = self.progress();
+        let known = view.bids().len();
+        view.try_add_bid(time, bid)?;
+
+        // The view numbers bids among those on the lot, while ids are unique within the auction
+        let placed: Vec<BidData> = view.bids()[known..].iter().map(|bid| bid.data.clone()).collect();
+        let view_ends_at = match &view {
+            Auction::TimedAscending { ends_at, .. } => *ends_at,
+            Auction::SingleSealedBid { .. } => None,
+        };
+        if let Auction::TimedAscending { ends_at, .. } = self {
+            *ends_at = view_ends_at;
+        }
+        let base = self.base_mut();
+        for data in placed {
+            let id = next_bid_id(base);
+            base.bids.push(Bid { id, lot: Some(lot_id), data });
+        }
+        self.advance(time);
+        Ok(self.events_since(before, time))
+    }
+
+    fn check_accepts_bids(&self, time: DateTime<Utc>) -> Result<(), Errors> {
+        match self.status_at(time) {
+            AuctionStatus::Open => Ok(()),
+            AuctionStatus::Draft | AuctionStatus::Scheduled => Err(Errors::AuctionHasNotStarted),
+            AuctionStatus::Closed | AuctionStatus::Cancelled => Err(Errors::AuctionHasEnded),
+        }
+    }
+
+    /// Withdraws the bidder's latest bid, placed at most `window` ago, from a timed ascending auction
+    /// that still accepts bids. Their maximum bid is withdrawn as well. The standing price falls back
+    /// to the highest remaining bid, while any extension of the end time stays.
+    pub fn retract_bid(
+        &mut self,
+        now: DateTime<Utc>,
+        user: &UserId,
+        window: chrono::Duration,
+    ) -> Result<Bid, Errors> {
+        if self.is_paused() {
+            return Err(Errors::AuctionIsPaused);
+        }
+        self.check_accepts_bids(now)?;
+        match self {
+            Auction::SingleSealedBid { .. } => Err(Errors::BidRetractionNotAllowed),
+            Auction::TimedAscending { base, max_bids, .. } => {
+                let (index, latest) = base
+                    .bids
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, b)| b.user() == user)
+                    .max_by_key(|(_, b)| b.id)
+                    .ok_or(Errors::NoBidToRetract)?;
+                if now - latest.at() > window || window <= chrono::Duration::zero() {
+                    return Err(Errors::RetractionWindowHasPassed);
                 }
-                
-                if time < base.starts_at {
-                    return Err(Errors::AuctionHasNotStarted);
+
+                let bid = base.bids.remove(index);
+                max_bids.retain(|max_bid| max_bid.user != *user);
+                base.retracted_bids.push(RetractedBid {
+                    bid: bid.clone(),
+                    retracted_at: now,
+                });
+                Ok(bid)
+            },
+        }
+    }
+
+    /// Removes a bid, e.g. a fraudulent one, from an auction that is still running, on behalf of
+    /// the support user `voided_by`. The bidder's maximum bid is withdrawn as well. The standing
+    /// price falls back to the highest remaining bid, and the end time to what the remaining bids
+    /// extended it to. Proxy bids placed in response to the voided bid stay.
+    pub fn void_bid(
+        &mut self,
+        now: DateTime<Utc>,
+        bid_id: i64,
+        voided_by: &UserId,
+        reason: Option<String>,
+    ) -> Result<Bid, Errors> {
+        self.check_accepts_bids(now)?;
+        let base = match self {
+            Auction::SingleSealedBid { base, .. } => base,
+            Auction::TimedAscending { base, .. } => base,
+        };
+        let index = base
+            .bids
+            .iter()
+            .position(|b| b.id == bid_id)
+            .ok_or(Errors::UnknownBid)?;
+        let bid = base.bids.remove(index);
+        base.voided_bids.push(VoidedBid {
+            bid: bid.clone(),
+            voided_at: now,
+            voided_by: voided_by.clone(),
+            void_reason: reason,
+        });
+        if let Auction::TimedAscending { base, options, ends_at, max_bids } = self {
+            max_bids.retain(|max_bid| max_bid.user != *bid.user());
+            if ends_at.is_some() {
+                *ends_at = Some(extended_end(base, options));
+            }
+        }
+        Ok(bid)
+    }
+
+    /// Applies an event this auction raised earlier, without validating it again, to rebuild
+    /// the auction from stored events. Returns whether the event changes the auction this way:
+    /// only placed bids and extensions do, other events follow from the auction as a whole.
+    pub fn replay(&mut self, event: &DomainEvent) -> bool {
+        match (event, self) {
+            (DomainEvent::BidPlaced { bid, .. }, auction) => {
+                let base = auction.base_mut();
+                base.last_bid_id = base.last_bid_id.max(bid.id);
+                base.bids.push(bid.clone());
+                // Like placing the bid, which pins the end and opens a scheduled auction
+                if let Auction::TimedAscending { base, ends_at, .. } = auction {
+                    ends_at.get_or_insert(base.expiry);
                 }
+                auction.advance(bid.at());
+                true
+            }
+            (DomainEvent::AuctionExtended { ends_at: extended_to, .. }, Auction::TimedAscending { ends_at, .. }) => {
+                *ends_at = Some(*extended_to);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Every user the auction refers to: the seller, bidders, and support voiding bids. These
+    /// are the users [`Auction::anonymize_user`] can replace.
+    pub fn referred_users(&self) -> Vec<UserId> {
+        let base = self.base();
+        let mut users = vec![base.user.clone()];
+        users.extend(base.bids.iter().map(|bid| bid.data.user.clone()));
+        users.extend(base.retracted_bids.iter().map(|retracted| retracted.bid.data.user.clone()));
+        for voided in &base.voided_bids {
+            users.push(voided.bid.data.user.clone());
+            users.push(voided.voided_by.clone());
+        }
+        if let Auction::TimedAscending { max_bids, .. } = self {
+            users.extend(max_bids.iter().map(|max_bid| max_bid.user.clone()));
+        }
+        users.sort_by(|a, b| a.value().cmp(b.value()));
+        users.dedup();
+        users
+    }
+
+    /// Replaces every reference to `user` with `pseudonym`, and forgets where they bid from and
+    /// created the auction from. Amounts, times and the order of the bids stay, so the auction
+    /// has the same outcome. Returns whether the auction referred to the user at all.
+    pub fn anonymize_user(&mut self, user: &UserId, pseudonym: &UserId) -> bool {
+        fn anonymize_bid(bid: &mut Bid, user: &UserId, pseudonym: &UserId) -> bool {
+            if bid.data.user != *user {
+                return false;
+            }
+            bid.data.user = pseudonym.clone();
+            if let Some(metadata) = bid.data.metadata.as_mut() {
+                metadata.user_agent = None;
+                metadata.ip_hash = None;
+            }
+            true
+        }
+        let mut referred = false;
+        let base = self.base_mut();
+        if base.user == *user {
+            base.user = pseudonym.clone();
+            base.seller_ip_hash = None;
+            referred = true;
+        }
+        for bid in base.bids.iter_mut() {
+            referred |= anonymize_bid(bid, user, pseudonym);
+        }
+        for retracted in base.retracted_bids.iter_mut() {
+            referred |= anonymize_bid(&mut retracted.bid, user, pseudonym);
+        }
+        for voided in base.voided_bids.iter_mut() {
+            referred |= anonymize_bid(&mut voided.bid, user, pseudonym);
+            if voided.voided_by == *user {
+                voided.voided_by = pseudonym.clone();
+                referred = true;
+            }
+        }
+        if let Auction::TimedAscending { max_bids, .. } = self {
+            for max_bid in max_bids.iter_mut().filter(|max_bid| max_bid.user == *user) {
+                max_bid.user = pseudonym.clone();
+                referred = true;
+            }
+        }
+        referred
+    }
+
+    /// Registers the most the bidder is willing to pay (`bid.amount`) and bids on their behalf,
+    /// raising by the minimum whenever they are outbid, until that amount is reached.
+    /// Only single unit timed ascending auctions support proxy bids.
+    pub fn try_add_proxy_bid(&mut self, time: DateTime<Utc>, bid: BidData) -> Result<Vec<DomainEvent>, Errors> {
+        if self.is_paused() {
+            return Err(Errors::AuctionIsPaused);
+        }
+        if !self.lots().is_empty() {
+            return Err(Errors::ProxyBidNotAllowed);
+        }
+        let errors = self.validate_bid(&bid);
+        if !errors.is_none() {
+            return Err(errors);
+        }
+        self.check_accepts_bids(time)?;
+        let before = self.progress();
 
-                // Check if bid is higher than current highest bid
-                if !base.bids.is_empty() {
-                    let highest_bid = base.bids
-                        .iter()
-                        .max_by_key(|b| b.amount().value())
-                        .unwrap();
-                    
-                    if bid.amount.value() <= highest_bid.amount().value() {
-                        return Err(Errors::MustPlaceBidOverHighestBid);
+        let added = match self {
+            Auction::SingleSealedBid { .. } => Err(Errors::ProxyBidNotAllowed),
+            Auction::TimedAscending { base, options, ends_at, max_bids } => {
+                if base.quantity != 1 || bid.quantity != 1 {
+                    return Err(Errors::ProxyBidNotAllowed);
+                }
+                let is_leader = base
+                    .bids
+                    .iter()
+                    .max_by_key(|b| b.amount().value())
+                    .is_some_and(|b| b.data.user == bid.user);
+                if is_leader {
+                    // Raising the maximum does not raise the standing bid
+                    if time > ends_at.unwrap_or(base.expiry) || bought_now(base, options) {
+                        return Err(Errors::AuctionHasEnded);
                     }
-                    
-                    if bid.amount.value() < highest_bid.amount().value() + options.min_raise {
-                        return Err(Errors::MustRaiseWithAtLeast);
+                    if time < base.starts_at {
+                        return Err(Errors::AuctionHasNotStarted);
                     }
+                } else {
+                    check_timed_bid(base, options, ends_at, time, &bid)?;
                 }
 
-                // Update the auction end time
-                let time_extended = time + options.time_frame;
-                let current_end = *ends_at.as_ref().unwrap_or(&base.expiry);
-                let new_end = if time_extended > current_end {
-                    time_extended
-                } else {
-                    current_end
-                };
-                *ends_at = Some(new_end);
+                max_bids.retain(|max_bid| max_bid.user != bid.user);
+                max_bids.push(MaxBid {
+                    user: bid.user,
+                    amount: bid.amount,
+                    at: bid.at,
+                });
+                resolve_proxy_bids(base, options, ends_at, max_bids, time);
 
-                // Add bid
-                let next_id = base.bids.len() as i64 + 1;
-                let bid_entity = Bid::new(
-                    next_id,
-                    bid.user.clone(),
-                    bid.amount.clone(),
-                    bid.at,
-                );
-                base.bids.push(bid_entity);
-                
-                Ok(true)
+                Ok(())
             },
-        }
+        };
+        self.advance(time);
+        added.map(|()| self.events_since(before, time))
     }
 
     pub fn get_bids(&self, time: DateTime<Utc>) -> Option<&Vec<Bid>> {
         match self {
             Auction::SingleSealedBid { base, .. } => {
-                if time < base.starts_at || time > base.expiry {
+                if time < base.starts_at || time > self.effective_end() {
                     return None;
                 }
                 
@@ -318,85 +1324,432 @@ impl Auction {
         }
     }
 
+    /// The highest winner, see [`Auction::try_get_winners`].
     pub fn try_get_amount_and_winner(&self, time: DateTime<Utc>) -> Option<(Amount, UserId)> {
+        self.try_get_winners(time)
+            .into_iter()
+            .next()
+            .map(|(amount, user, _)| (amount, user))
+    }
+
+    /// Price per unit, winner and number of units won, highest bids first.
+    /// Units go to the highest bids until the quantity is exhausted.
+    pub fn try_get_winners(&self, time: DateTime<Utc>) -> Vec<(Amount, UserId, i32)> {
+        // Cancelled (voided) auctions have no winner
+        if self.status_at(time) != AuctionStatus::Closed {
+            return Vec::new();
+        }
+        // Each lot has its own winners, listed lot by lot
+        if !self.lots().is_empty() {
+            return self
+                .lots()
+                .iter()
+                .filter_map(|lot| self.lot_view(lot.id))
+                .flat_map(|view| view.try_get_winners(time))
+                .collect();
+        }
         match self {
-            Auction::SingleSealedBid { base, options } => {
-                // Only return winner after auction has ended
-                if time <= base.expiry || base.bids.is_empty() {
-                    return None;
+            Auction::SingleSealedBid { base, options, tie_break, reserve_price, pricing } => {
+                // Only return winners after auction has ended
+                if time <= self.effective_end() || base.bids.is_empty() {
+                    return Vec::new();
                 }
-                
+
+                // Bids below the reserve do not win
+                let reserve = reserve_price.unwrap_or(0);
+                let allocation: Vec<_> = allocate_units(&base.bids, base.quantity, *tie_break)
+                    .into_iter()
+                    .filter(|(b, _)| b.amount().value() >= reserve)
+                    .collect();
                 match options {
-                    SingleSealedBidOptions::Blind => {
-                        // First price sealed bid - highest bidder wins and pays their bid
-                        base.bids
-                            .iter()
-                            .max_by_key(|b| b.amount().value())
-                            .map(|b| (b.amount(), b.user()))
+                    SingleSealedBidOptions::Blind | SingleSealedBidOptions::AllPay => {
+                        // First price sealed bid - winners pay their bid
+                        allocation
+                            .into_iter()
+                            .map(|(b, units)| (b.amount().clone(), b.user().clone(), units))
+                            .collect()
                     },
                     SingleSealedBidOptions::Vickrey => {
-                        // Second price sealed bid - highest bidder wins but pays second highest bid
-                        if base.bids.len() == 1 {
-                            let bid = &base.bids[0];
-                            return Some((bid.amount(), bid.user()));
-                        }
-                        
-                        let mut bids: Vec<_> = base.bids.iter().collect();
-                        bids.sort_by(|a, b| b.amount().value().cmp(&a.amount().value()));
-                        
-                        // Highest bidder wins but pays second-highest price
-                        Some((bids[1].amount(), bids[0].user()))
+                        // Second price sealed bid - winners pay the highest losing bid (at least
+                        // the reserve) as priced by the auction, or their own bid when nobody lost
+                        let highest_losing_bid = ranked_bids(&base.bids, *tie_break)
+                            .into_iter()
+                            .find(|b| allocation.iter().all(|(winning, _)| winning.id != b.id))
+                            .map(|b| b.amount().value().max(reserve));
+                        allocation
+                            .into_iter()
+                            .map(|(b, units)| {
+                                let price = highest_losing_bid
+                                    .map_or(b.amount().value(), |second| pricing.price(second, b.amount().value()));
+                                (Amount::new(price, b.amount().currency()), b.user().clone(), units)
+                            })
+                            .collect()
                     },
                 }
             },
             Auction::TimedAscending { base, options, .. } => {
-                // Only return winner after auction has ended
-                if time <= base.expiry || base.bids.is_empty() {
-                    return None;
-                }
-                
-                // Find highest bid
-                let highest_bid = base.bids
-                    .iter()
-                    .max_by_key(|b| b.amount().value())
-                    .unwrap();
-                
-                // Check reserve price
-                if highest_bid.amount().value() >= options.reserve_price {
-                    Some((highest_bid.amount(), highest_bid.user()))
-                } else {
-                    None
+                // Only return winners after auction has ended, including any extension by late bids
+                if (time <= self.effective_end() && !bought_now(base, options)) || base.bids.is_empty() {
+                    return Vec::new();
                 }
+
+                // Winners pay their bid, provided it meets the reserve price
+                allocate_units(&base.bids, base.quantity, TieBreak::EarliestBid)
+                    .into_iter()
+                    .filter(|(b, _)| b.amount().value() >= options.reserve_price)
+                    .map(|(b, units)| (b.amount().clone(), b.user().clone(), units))
+                    .collect()
             },
         }
     }
 
+    /// Total amount charged to each bidder once there are winners, highest bids first.
+    /// Winners pay for the units they won. In all-pay auctions every bidder pays their bid.
+    pub fn try_get_charges(&self, time: DateTime<Utc>) -> Vec<(Amount, UserId)> {
+        let winners = self.try_get_winners(time);
+        let total = |price: &Amount, units: i32| Amount::new(price.value() * units as i64, price.currency());
+        match self {
+            Auction::SingleSealedBid { base, options: SingleSealedBidOptions::AllPay, tie_break, .. }
+                if !winners.is_empty() =>
+            {
+                ranked_bids(&base.bids, *tie_break)
+                    .into_iter()
+                    .map(|b| (total(b.amount(), b.quantity()), b.user().clone()))
+                    .collect()
+            },
+            _ => winners
+                .into_iter()
+                .map(|(price, user, units)| (total(&price, units), user))
+                .collect(),
+        }
+    }
+
     pub fn has_ended(&self, time: DateTime<Utc>) -> bool {
+        self.status_at(time).has_ended()
+    }
+
+    /// Started, and neither ended nor cancelled at `time`.
+    pub fn is_active(&self, time: DateTime<Utc>) -> bool {
+        self.status_at(time) == AuctionStatus::Open
+    }
+
+    fn is_past_end(&self, time: DateTime<Utc>) -> bool {
+        if self.is_paused() {
+            return false;
+        }
         match self {
-            Auction::SingleSealedBid { base, .. } => time > base.expiry,
-            Auction::TimedAscending { base, ends_at, .. } => {
-                time > ends_at.unwrap_or(base.expiry)
+            Auction::SingleSealedBid { .. } => time > self.effective_end(),
+            Auction::TimedAscending { base, options, .. } => {
+                bought_now(base, options) || time > self.effective_end()
             },
         }
     }
 }
 
+fn bought_now(base: &AuctionBase, options: &TimedAscendingOptions) -> bool {
+    base.bids.iter().any(|b| options.is_buy_now(&b.data.amount))
+}
+
+fn check_timed_bid(
+    base: &AuctionBase,
+    options: &TimedAscendingOptions,
+    ends_at: &Option<DateTime<Utc>>,
+    time: DateTime<Utc>,
+    bid: &BidData,
+) -> Result<(), Errors> {
+    if time > ends_at.unwrap_or(base.expiry) || bought_now(base, options) {
+        return Err(Errors::AuctionHasEnded);
+    }
+
+    if time < base.starts_at {
+        return Err(Errors::AuctionHasNotStarted);
+    }
+
+    // Once all units are taken, a bid has to beat the lowest winning bid
+    // (with a single unit, that is the highest bid)
+    let allocation = allocate_units(&base.bids, base.quantity, TieBreak::EarliestBid);
+    let allocated: i32 = allocation.iter().map(|(_, units)| units).sum();
+    if allocated >= base.quantity {
+        let lowest_winning_bid = allocation.last().unwrap().0.amount().value();
+
+        if bid.amount.value() <= lowest_winning_bid {
+            return Err(Errors::MustPlaceBidOverHighestBid);
+        }
+
+        if bid.amount.value() < lowest_winning_bid + options.minimum_raise(lowest_winning_bid) {
+            return Err(Errors::MustRaiseWithAtLeast);
+        }
+    } else if bid.amount.value() < options.starting_price {
+        // Opening bids for free units have to meet the starting price
+        return Err(Errors::BidBelowStartingPrice);
+    }
+    Ok(())
+}
+
+fn push_timed_bid(
+    base: &mut AuctionBase,
+    options: &TimedAscendingOptions,
+    ends_at: &mut Option<DateTime<Utc>>,
+    time: DateTime<Utc>,
+    bid: BidData,
+) {
+    // Update the auction end time
+    if options.is_buy_now(&bid.amount) {
+        // Bought outright, the auction closes now
+        base.expiry = time;
+        *ends_at = Some(time);
+    } else {
+        let mut time_extended = time + options.time_frame;
+        if let Some(max_extension) = options.max_extension {
+            // Hard close: extensions stop at the cap
+            time_extended = time_extended.min(base.expiry + max_extension);
+        }
+        let current_end = *ends_at.as_ref().unwrap_or(&base.expiry);
+        let new_end = if time_extended > current_end {
+            time_extended
+        } else {
+            current_end
+        };
+        *ends_at = Some(new_end);
+    }
+
+    // Add bid
+    let id = next_bid_id(base);
+    base.bids.push(Bid { id, lot: None, data: bid });
+}
+
+/// The end time as extended by the standing bids, for when a bid no longer counts. Each bid
+/// extends to `time_frame` past when it was placed, moved on by any pause since then.
+fn extended_end(base: &AuctionBase, options: &TimedAscendingOptions) -> DateTime<Utc> {
+    let paused_since = |at: DateTime<Utc>| {
+        base.pauses
+            .iter()
+            .filter(|pause| pause.paused_at >= at)
+            .filter_map(|pause| pause.resumed_at.map(|resumed_at| resumed_at - pause.paused_at))
+            .fold(chrono::Duration::zero(), |total, paused_for| total + paused_for)
+    };
+    base.bids
+        .iter()
+        .map(|bid| {
+            let extended = bid.at() + options.time_frame + paused_since(bid.at());
+            match options.max_extension {
+                Some(max_extension) => extended.min(base.expiry + max_extension),
+                None => extended,
+            }
+        })
+        .fold(base.expiry, |end, extended| end.max(extended))
+}
+
+/// Ids are never reused, retracted and voided bids keep theirs. Auctions stored before the
+/// counter was kept continue after the highest id among their bids.
+fn next_bid_id(base: &mut AuctionBase) -> i64 {
+    let highest = base
+        .bids
+        .iter()
+        .chain(base.retracted_bids.iter().map(|retracted| &retracted.bid))
+        .chain(base.voided_bids.iter().map(|voided| &voided.bid))
+        .map(|bid| bid.id)
+        .max()
+        .unwrap_or(0);
+    base.last_bid_id = base.last_bid_id.max(highest) + 1;
+    base.last_bid_id
+}
+
+/// Places the bids that the maximum bids call for. The strongest maximum ends up leading, at the
+/// minimum raise over the runner-up (or at the opening price without competition). The runner-up
+/// is shown bidding its full maximum. A maximum that cannot beat the standing bid by the minimum
+/// raise does not bid.
+fn resolve_proxy_bids(
+    base: &mut AuctionBase,
+    options: &TimedAscendingOptions,
+    ends_at: &mut Option<DateTime<Utc>>,
+    max_bids: &[MaxBid],
+    time: DateTime<Utc>,
+) {
+    let mut ranked: Vec<&MaxBid> = max_bids.iter().collect();
+    ranked.sort_by(|a, b| {
+        b.amount
+            .value()
+            .cmp(&a.amount.value())
+            .then(a.at.cmp(&b.at))
+    });
+    let Some(top) = ranked.first().copied() else {
+        return;
+    };
+    let runner_up = ranked.iter().copied().find(|m| m.user != top.user);
+
+    let proxy_bid = |max_bid: &MaxBid, value: i64| BidData {
+        user: max_bid.user.clone(),
+        amount: Amount::new(value, max_bid.amount.currency()),
+        at: time,
+        quantity: 1,
+        metadata: None,
+    };
+    fn standing(base: &AuctionBase) -> Option<(i64, &UserId)> {
+        base.bids
+            .iter()
+            .max_by_key(|b| b.amount().value())
+            .map(|b| (b.amount().value(), b.user()))
+    }
+    let minimum_next = |highest: Option<i64>| options.minimum_next_bid(highest);
+
+    if let Some(runner_up) = runner_up {
+        let highest = standing(base).map(|(value, _)| value);
+        if runner_up.amount.value() >= minimum_next(highest) {
+            push_timed_bid(base, options, ends_at, time, proxy_bid(runner_up, runner_up.amount.value()));
+        }
+    }
+    if bought_now(base, options) {
+        return;
+    }
+
+    let (highest, leader) = standing(base).unzip();
+    if leader != Some(&top.user) {
+        let next = minimum_next(highest);
+        if top.amount.value() >= next {
+            push_timed_bid(base, options, ends_at, time, proxy_bid(top, next));
+        }
+    }
+}
+
+/// The latest bid of each bidder, highest first, with ties settled by `tie_break`.
+fn ranked_bids(bids: &[Bid], tie_break: TieBreak) -> Vec<&Bid> {
+    let mut latest: HashMap<&UserId, &Bid> = HashMap::new();
+    for bid in bids {
+        let entry = latest.entry(bid.user()).or_insert(bid);
+        if bid.id > entry.id {
+            *entry = bid;
+        }
+    }
+    let mut ranked: Vec<&Bid> = latest.into_values().collect();
+    ranked.sort_by(|a, b| {
+        b.amount()
+            .value()
+            .cmp(&a.amount().value())
+            .then(match tie_break {
+                TieBreak::EarliestBid => a.id.cmp(&b.id),
+                TieBreak::LatestBid => b.id.cmp(&a.id),
+            })
+    });
+    ranked
+}
+
+/// Hands out units to the highest bids until the quantity is exhausted.
+/// The last bid may be filled only partially.
+fn allocate_units(bids: &[Bid], quantity: i32, tie_break: TieBreak) -> Vec<(&Bid, i32)> {
+    let mut remaining = quantity;
+    let mut allocation = Vec::new();
+    for bid in ranked_bids(bids, tie_break) {
+        if remaining <= 0 {
+            break;
+        }
+        let units = bid.quantity().min(remaining);
+        allocation.push((bid, units));
+        remaining -= units;
+    }
+    allocation
+}
+
+/// The aliases of [`Auction::bidder_aliases`], given the bidders in the order of their bids.
+pub fn bidder_aliases<'a>(bidders: impl IntoIterator<Item = &'a UserId>) -> HashMap<UserId, String> {
+    let mut aliases = HashMap::new();
+    for bidder in bidders {
+        let next = aliases.len();
+        aliases.entry(bidder.clone()).or_insert_with(|| bidder_alias(next));
+    }
+    aliases
+}
+
+fn bidder_alias(index: usize) -> String {
+    // Bijective base-26: A..Z, AA..AZ, BA..
+    let mut letters = Vec::new();
+    let mut n = index + 1;
+    while n > 0 {
+        n -= 1;
+        letters.push((b'A' + (n % 26) as u8) as char);
+        n /= 26;
+    }
+    letters.reverse();
+    format!("Bidder {}", letters.into_iter().collect::<String>())
+}
+
+/// Where the bidding of an auction stood before a change, to tell what the change did.
+#[derive(Debug, Clone, Copy)]
+struct Progress {
+    bids: usize,
+    ends_at: DateTime<Utc>,
+    ended: bool,
+}
+
 pub struct AuctionFactory;
 
 impl AuctionFactory {
+    /// Creates the auction along with its [`DomainEvent::AuctionCreated`], which carries the id
+    /// of the auction once stored, see [`DomainEvent::stored_as`].
+    pub fn create(
+        cmd: CreateAuctionCommand,
+        user_id: UserId,
+    ) -> Result<(Auction, Vec<DomainEvent>), Vec<Violation>> {
+        let auction = Self::create_auction(cmd, user_id)?;
+        let created = DomainEvent::AuctionCreated {
+            auction_id: auction.auction_id(),
+            seller: auction.user().clone(),
+            starts_at: auction.starts_at(),
+            expiry: auction.expiry(),
+        };
+        Ok((auction, vec![created]))
+    }
+
+    /// Fails with every rule the command breaks, not just the first.
     pub fn create_auction(
         cmd: CreateAuctionCommand,
         user_id: UserId,
-    ) -> Result<Auction, &'static str> {
+    ) -> Result<Auction, Vec<Violation>> {
+        let violations = Self::validate(&cmd);
+        if !violations.is_empty() {
+            return Err(violations);
+        }
+        let quantity = cmd.quantity.unwrap_or(1);
+        let lots = cmd
+            .lots
+            .into_iter()
+            .zip(1..)
+            .map(|(title, id)| Lot { id, title })
+            .collect();
         let base = AuctionBase {
             auction_id: AuctionId::new(0),
             title: cmd.title,
+            description: cmd.description,
             starts_at: cmd.starts_at,
             expiry: cmd.ends_at,
             user: user_id.clone(),
             currency: cmd.currency,
             bids: Vec::new(),
             open_bidders: cmd.open_bidders,
+            requires_registration: cmd.requires_registration,
+            deposit: cmd.deposit,
+            quantity,
+            external_reference: cmd.external_reference,
+            seller_ip_hash: cmd.seller_ip_hash,
+            lots,
+            fee_policy: cmd.fee_policy,
+            tax_rate_bps: cmd.tax_rate_bps,
+            voided_at: None,
+            void_reason: None,
+            status: if cmd.draft { AuctionStatus::Draft } else { AuctionStatus::Scheduled },
+            cancelled_at: None,
+            cancel_reason: None,
+            retracted_bids: Vec::new(),
+            voided_bids: Vec::new(),
+            extended_at: None,
+            extension_reason: None,
+            pauses: Vec::new(),
+            relist_policy: cmd.relist_policy,
+            relisted_from: None,
+            relisted_as: None,
+            relist_count: 0,
+            version: 0,
+            last_bid_id: 0,
         };
 
         if let Some(options) = cmd.single_sealed_bid_options {
@@ -404,6 +1757,9 @@ impl AuctionFactory {
             Ok(Auction::SingleSealedBid {
                 base,
                 options,
+                tie_break: cmd.tie_break.unwrap_or_default(),
+                reserve_price: cmd.reserve_price,
+                pricing: cmd.vickrey_pricing.unwrap_or_default(),
             })
         } else {
             // Create a timed ascending auction
@@ -411,13 +1767,122 @@ impl AuctionFactory {
                 min_raise: cmd.min_raise.unwrap_or(0),
                 reserve_price: cmd.reserve_price.unwrap_or(0),
                 time_frame: cmd.time_frame.unwrap_or_else(|| chrono::Duration::seconds(0)),
+                buy_now_price: cmd.buy_now_price,
+                starting_price: cmd.starting_price.unwrap_or(0),
+                max_extension: cmd.max_extension,
+                increments: cmd.increments,
             };
-            
             Ok(Auction::TimedAscending {
                 base,
                 options,
                 ends_at: None,
+                max_bids: Vec::new(),
             })
         }
     }
+
+    fn validate(cmd: &CreateAuctionCommand) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let mut check = |broken: bool, code: &'static str, message: &'static str| {
+            if broken {
+                violations.push(Violation { code, message });
+            }
+        };
+        let quantity = cmd.quantity.unwrap_or(1);
+        check(quantity < 1, "INVALID_QUANTITY", "Quantity must be at least one");
+        check(
+            cmd.deposit.is_some_and(|deposit| deposit <= 0),
+            "INVALID_DEPOSIT",
+            "Deposit must be positive",
+        );
+        check(
+            cmd.relist_policy
+                .as_ref()
+                .is_some_and(|policy| policy.duration_seconds <= 0 || policy.max_relists < 1),
+            "INVALID_RELIST_POLICY",
+            "Relisting needs a positive duration and at least one relist",
+        );
+        check(
+            !cmd.lots.is_empty() && (quantity > 1 || cmd.buy_now_price.is_some()),
+            "INVALID_LOTS",
+            "Auctions with lots sell one of each lot and cannot be bought now",
+        );
+        check(
+            cmd.lots.iter().any(|title| title.trim().is_empty()),
+            "LOT_TITLE_REQUIRED",
+            "Lots need a title",
+        );
+        check(
+            cmd.tax_rate_bps.is_some_and(|rate| rate < 0),
+            "INVALID_TAX_RATE",
+            "Tax rate cannot be negative",
+        );
+        // Drafts may be incomplete until they are published, see [`Auction::publish`]
+        check(
+            !cmd.draft && cmd.title.trim().is_empty(),
+            "TITLE_REQUIRED",
+            "Auctions need a title",
+        );
+
+        if let Some(options) = &cmd.single_sealed_bid_options {
+            check(
+                cmd.starting_price.is_some(),
+                "STARTING_PRICE_NOT_ALLOWED",
+                "Starting price only applies to timed ascending auctions",
+            );
+            check(
+                cmd.reserve_price.is_some_and(|price| price < 0),
+                "INVALID_RESERVE_PRICE",
+                "Reserve price cannot be negative",
+            );
+            let pricing = cmd.vickrey_pricing.unwrap_or_default();
+            check(
+                pricing != VickreyPricing::SecondPrice && *options != SingleSealedBidOptions::Vickrey,
+                "PRICING_NOT_ALLOWED",
+                "Pricing rules only apply to Vickrey auctions",
+            );
+            check(
+                pricing.increment() < 0 || matches!(pricing, VickreyPricing::RoundUp { increment: 0 }),
+                "INVALID_PRICING_INCREMENT",
+                "Pricing increments must be positive",
+            );
+        } else {
+            check(
+                cmd.tie_break.is_some(),
+                "TIE_BREAK_NOT_ALLOWED",
+                "Tie-break rules only apply to single sealed bid auctions",
+            );
+            check(
+                cmd.vickrey_pricing.is_some(),
+                "PRICING_NOT_ALLOWED",
+                "Pricing rules only apply to Vickrey auctions",
+            );
+            let increments_ordered = cmd
+                .increments
+                .windows(2)
+                .all(|steps| steps[0].below < steps[1].below);
+            check(
+                !increments_ordered || cmd.increments.iter().any(|step| step.increment < 0),
+                "INVALID_INCREMENTS",
+                "Increments must be ordered by price and cannot be negative",
+            );
+            check(
+                cmd.max_extension.is_some_and(|d| d < chrono::Duration::zero()),
+                "INVALID_MAX_EXTENSION",
+                "Maximum extension cannot be negative",
+            );
+            check(
+                cmd.starting_price.is_some_and(|price| price < 0),
+                "INVALID_STARTING_PRICE",
+                "Starting price cannot be negative",
+            );
+            check(
+                cmd.buy_now_price
+                    .is_some_and(|price| price < cmd.reserve_price.unwrap_or(0)),
+                "BUY_NOW_BELOW_RESERVE",
+                "Buy-now price must be at least the reserve price",
+            );
+        }
+        violations
+    }
 }
\ No newline at end of file
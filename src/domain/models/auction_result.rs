@@ -0,0 +1,18 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::amount::Amount;
+use super::auction::AuctionId;
+use super::user::UserId;
+
+/// Outcome of a closed auction, recorded once so that every later read reports the same result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuctionResult {
+    pub auction_id: AuctionId,
+    pub winner: Option<UserId>,
+    /// What the winner pays per unit
+    pub amount: Option<Amount>,
+    pub closed_at: DateTime<Utc>,
+    /// Whether the highest bid met the reserve price; without bids it did not
+    pub reserve_met: bool,
+}
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::Errors;
+
+/// Lifecycle of an auction. Scheduled auctions open at `starts_at` and open auctions close when
+/// they end; cancelled auctions (voided ones) have no outcome.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuctionStatus {
+    Draft,
+    #[default]
+    Scheduled,
+    Open,
+    Closed,
+    Cancelled,
+}
+
+impl AuctionStatus {
+    pub fn can_transition_to(self, next: AuctionStatus) -> bool {
+        use AuctionStatus::*;
+        matches!(
+            (self, next),
+            (Draft, Scheduled)
+                | (Draft, Cancelled)
+                | (Scheduled, Open)
+                | (Scheduled, Closed)
+                | (Scheduled, Cancelled)
+                | (Open, Closed)
+                | (Open, Cancelled)
+                | (Closed, Cancelled)
+        )
+    }
+
+    pub fn transition_to(self, next: AuctionStatus) -> Result<AuctionStatus, Errors> {
+        if self.can_transition_to(next) {
+            Ok(next)
+        } else {
+            Err(Errors::IllegalStatusTransition)
+        }
+    }
+
+    pub fn has_ended(self) -> bool {
+        matches!(self, AuctionStatus::Closed | AuctionStatus::Cancelled)
+    }
+}
+
+impl fmt::Display for AuctionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod auction_status_tests {
+    use super::*;
+
+    #[test]
+    fn test_lifecycle_moves_forward_only() {
+        assert_eq!(AuctionStatus::Scheduled.transition_to(AuctionStatus::Open), Ok(AuctionStatus::Open));
+        assert_eq!(AuctionStatus::Open.transition_to(AuctionStatus::Closed), Ok(AuctionStatus::Closed));
+        assert_eq!(
+            AuctionStatus::Closed.transition_to(AuctionStatus::Open),
+            Err(Errors::IllegalStatusTransition)
+        );
+        assert_eq!(
+            AuctionStatus::Open.transition_to(AuctionStatus::Scheduled),
+            Err(Errors::IllegalStatusTransition)
+        );
+    }
+
+    #[test]
+    fn test_cancelled_is_final() {
+        for next in [
+            AuctionStatus::Draft,
+            AuctionStatus::Scheduled,
+            AuctionStatus::Open,
+            AuctionStatus::Closed,
+            AuctionStatus::Cancelled,
+        ] {
+            assert!(!AuctionStatus::Cancelled.can_transition_to(next));
+        }
+    }
+}
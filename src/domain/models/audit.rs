@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::auction::AuctionId;
+use super::user::UserId;
+
+/// Record of an action taken on an auction outside of the normal bidding flow.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub auction_id: AuctionId,
+    pub action: String,
+    pub user: UserId,
+    pub reason: Option<String>,
+    pub at: DateTime<Utc>,
+}
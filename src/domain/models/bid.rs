@@ -1,49 +1,145 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 use super::amount::Amount;
 use super::user::UserId;
 use super::{Auction, Errors};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BidChannel {
+    Api,
+    Web,
+    Clerk,
+}
+
+impl FromStr for BidChannel {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "api" => Ok(BidChannel::Api),
+            "web" => Ok(BidChannel::Web),
+            "clerk" => Ok(BidChannel::Clerk),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Provenance of a bid, kept for fraud investigations and channel analytics.
+/// Not part of the public bid representation.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct BidMetadata {
+    pub channel: Option<BidChannel>,
+    pub user_agent: Option<String>,
+    pub ip_hash: Option<String>,
+    /// Set when screening flagged the bid, see [`ScreeningVerdict`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub screening: Option<ScreeningVerdict>,
+}
+
+/// Pattern suggesting that a bid is a shill bid, placed to drive up the price for the seller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScreeningFlag {
+    /// Placed from the address the seller created the auction from
+    SameIpAsSeller,
+    /// Part of a quick back and forth between the same two bidders
+    RapidAlternatingBids,
+}
+
+/// Outcome of screening a bid as it is placed. Flagged bids are accepted all the same, and kept
+/// with their verdict for support to review.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ScreeningVerdict {
+    pub flags: Vec<ScreeningFlag>,
+}
+
+impl ScreeningVerdict {
+    pub fn is_suspicious(&self) -> bool {
+        !self.flags.is_empty()
+    }
+}
+
+pub(crate) fn default_quantity() -> i32 {
+    1
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BidData {
     pub user: UserId,
+    /// Price per unit
     pub amount: Amount,
     pub at: DateTime<Utc>,
+    #[serde(default = "default_quantity")]
+    pub quantity: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<BidMetadata>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Bid {
     pub id: i64,
+    /// The lot bid on, in an auction with several lots, see [`super::Lot`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lot: Option<i32>,
     #[serde(flatten)]
     pub data: BidData
 }
 
+/// A bid withdrawn by its bidder. Kept for the record, it takes no further part in the auction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetractedBid {
+    #[serde(flatten)]
+    pub bid: Bid,
+    pub retracted_at: DateTime<Utc>,
+}
+
+/// A bid support removed from a live auction, e.g. as fraudulent. Kept for the record, it takes no
+/// further part in the auction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoidedBid {
+    #[serde(flatten)]
+    pub bid: Bid,
+    pub voided_at: DateTime<Utc>,
+    /// The support user who voided the bid
+    pub voided_by: UserId,
+    #[serde(default)]
+    pub void_reason: Option<String>,
+}
+
 impl Bid {
     pub fn new(id: i64, user: UserId, amount: Amount, at: DateTime<Utc>) -> Self {
         Self {
             id,
-            data: BidData {user,amount,at}
+            lot: None,
+            data: BidData {user,amount,at,quantity:1,metadata:None}
         }
     }
 
     pub fn at(&self) -> DateTime<Utc> { self.data.at }
-    pub fn user(&self) -> UserId { self.data.user.clone() }
-    pub fn amount(&self) -> Amount { self.data.amount.clone() }
+    pub fn user(&self) -> &UserId { &self.data.user }
+    pub fn amount(&self) -> &Amount { &self.data.amount }
+    pub fn quantity(&self) -> i32 { self.data.quantity }
 
     pub fn validate(&self, auction: &Auction) -> Errors {
         let mut errors = Errors::None;
-        if self.user() == *auction.user() {
-            errors = errors | Errors::SellerCannotPlaceBids;
+        if self.user() == auction.user() {
+            errors |= Errors::SellerCannotPlaceBids;
+        }
+        if !self.amount().is_positive() {
+            errors |= Errors::MustSpecifyAmount;
         }
         if self.amount().currency() != auction.currency() {
-            errors = errors | Errors::BidCurrencyConversion;
+            errors |= Errors::BidCurrencyConversion;
         }
         if self.at() < auction.starts_at() {
-            errors = errors | Errors::AuctionHasNotStarted;
+            errors |= Errors::AuctionHasNotStarted;
+        }
+        if self.at() > auction.effective_end() {
+            errors |= Errors::AuctionHasEnded;
         }
-        if self.at() > auction.expiry() {
-            errors = errors | Errors::AuctionHasEnded;
+        if self.quantity() < 1 || self.quantity() > auction.quantity() {
+            errors |= Errors::InvalidQuantity;
         }
 
         errors
@@ -1,23 +1,42 @@
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::{fmt, str::FromStr};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Currency, written as its ISO 4217 alphabetic code in the API, in stored JSON and in SQL columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CurrencyCode {
+    /// ISO 4217 `XXX`, no currency
     None,
     VAC = 1001,
     SEK = 752,
     DKK = 208,
+    EUR = 978,
 }
 
-impl fmt::Display for CurrencyCode {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl CurrencyCode {
+    pub fn code(&self) -> &'static str {
         match self {
-            CurrencyCode::None => write!(f, "NONE"),
-            CurrencyCode::VAC => write!(f, "VAC"),
-            CurrencyCode::SEK => write!(f, "SEK"),
-            CurrencyCode::DKK => write!(f, "DKK"),
+            CurrencyCode::None => "XXX",
+            CurrencyCode::VAC => "VAC",
+            CurrencyCode::SEK => "SEK",
+            CurrencyCode::DKK => "DKK",
+            CurrencyCode::EUR => "EUR",
         }
     }
+
+    /// Digits after the decimal point, as in ISO 4217 and the `currencies` table. Amounts are
+    /// held in these minor units, see [`super::Amount`].
+    pub fn minor_units(&self) -> u32 {
+        match self {
+            CurrencyCode::None | CurrencyCode::VAC => 0,
+            CurrencyCode::SEK | CurrencyCode::DKK | CurrencyCode::EUR => 2,
+        }
+    }
+}
+
+impl fmt::Display for CurrencyCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
 }
 
 impl FromStr for CurrencyCode {
@@ -25,16 +44,74 @@ impl FromStr for CurrencyCode {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
+            "XXX" => Ok(CurrencyCode::None),
             "VAC" => Ok(CurrencyCode::VAC),
             "SEK" => Ok(CurrencyCode::SEK),
             "DKK" => Ok(CurrencyCode::DKK),
+            "EUR" => Ok(CurrencyCode::EUR),
             _ => Err(()),
         }
     }
 }
 
+impl Serialize for CurrencyCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for CurrencyCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        code.parse()
+            .map_err(|_| de::Error::custom(format!("unknown currency code: {}", code)))
+    }
+}
+
+/// Reference data about a currency, maintained in the database.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Currency {
+    pub code: CurrencyCode,
+    pub numeric_code: i32,
+    pub name: String,
+    pub minor_units: i16,
+}
+
 impl Default for CurrencyCode {
     fn default() -> Self {
         CurrencyCode::None
     }
 }
+
+#[cfg(test)]
+mod currency_tests {
+    use super::*;
+
+    const ALL: [CurrencyCode; 5] = [
+        CurrencyCode::None,
+        CurrencyCode::VAC,
+        CurrencyCode::SEK,
+        CurrencyCode::DKK,
+        CurrencyCode::EUR,
+    ];
+
+    #[test]
+    fn test_codes_round_trip_through_strings() {
+        for currency in ALL {
+            assert_eq!(currency.to_string().parse::<CurrencyCode>(), Ok(currency));
+        }
+        assert_eq!(CurrencyCode::None.to_string(), "XXX");
+        assert!("sek".parse::<CurrencyCode>().is_err());
+    }
+
+    #[test]
+    fn test_codes_round_trip_through_json() {
+        for currency in ALL {
+            let json = serde_json::to_value(currency).unwrap();
+            assert_eq!(json, serde_json::Value::String(currency.to_string()));
+            assert_eq!(serde_json::from_value::<CurrencyCode>(json).unwrap(), currency);
+        }
+        assert!(serde_json::from_str::<CurrencyCode>("\"None\"").is_err());
+        assert!(serde_json::from_str::<CurrencyCode>("752").is_err());
+    }
+}
@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::amount::Amount;
+use super::auction::AuctionId;
+use super::user::UserId;
+
+/// Amount a bidder has put down to be allowed to bid in an auction that requires a deposit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Deposit {
+    pub auction_id: AuctionId,
+    pub user: UserId,
+    pub amount: Amount,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl Deposit {
+    /// Whether the deposit covers what the auction requires.
+    pub fn covers(&self, required: &Amount) -> bool {
+        self.amount.currency() == required.currency() && self.amount.value() >= required.value()
+    }
+}
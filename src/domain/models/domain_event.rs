@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::auction::AuctionId;
+use super::bid::Bid;
+use super::user::UserId;
+
+/// What an operation on an auction did, returned along with changing the auction so that
+/// handlers can log or publish it without working it out again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DomainEvent {
+    AuctionCreated {
+        auction_id: AuctionId,
+        seller: UserId,
+        starts_at: DateTime<Utc>,
+        expiry: DateTime<Utc>,
+    },
+    /// Placed by the bidder, or on their behalf by their maximum bid
+    BidPlaced { auction_id: AuctionId, bid: Bid },
+    /// A late bid moved the end of the auction
+    AuctionExtended { auction_id: AuctionId, ends_at: DateTime<Utc> },
+    /// The auction closed as a result, e.g. when bought outright
+    AuctionEnded { auction_id: AuctionId, at: DateTime<Utc> },
+}
+
+impl DomainEvent {
+    pub fn auction_id(&self) -> AuctionId {
+        match self {
+            DomainEvent::AuctionCreated { auction_id, .. }
+            | DomainEvent::BidPlaced { auction_id, .. }
+            | DomainEvent::AuctionExtended { auction_id, .. }
+            | DomainEvent::AuctionEnded { auction_id, .. } => *auction_id,
+        }
+    }
+
+    /// The event for the auction as stored, for events of auctions not stored yet.
+    pub fn stored_as(mut self, id: AuctionId) -> Self {
+        match &mut self {
+            DomainEvent::AuctionCreated { auction_id, .. }
+            | DomainEvent::BidPlaced { auction_id, .. }
+            | DomainEvent::AuctionExtended { auction_id, .. }
+            | DomainEvent::AuctionEnded { auction_id, .. } => *auction_id = id,
+        }
+        self
+    }
+}
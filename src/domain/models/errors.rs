@@ -1,56 +1,191 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use thiserror::Error;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Errors {
-    None = 0,
-    UnknownAuction = 1 << 0,
-    AuctionAlreadyExists = 1 << 1,
-    AuctionHasEnded = 1 << 2,
-    AuctionHasNotStarted = 1 << 3,
-    AuctionNotFound = 1 << 4,
-    SellerCannotPlaceBids = 1 << 5,
-    BidCurrencyConversion = 1 << 6,
-    InvalidUserData = 1 << 7,
-    MustPlaceBidOverHighestBid = 1 << 8,
-    AlreadyPlacedBid = 1 << 9,
-    MustRaiseWithAtLeast = 1 << 10,
-    MustSpecifyAmount = 1 << 11,
+/// Declares each flag once, with the stable code and the message reported when it is set.
+macro_rules! errors {
+    ($($name:ident = 1 << $bit:literal => $code:literal, $message:literal,)*) => {
+        #[allow(non_upper_case_globals)]
+        impl Errors {
+            pub const None: Errors = Errors(0);
+            $(pub const $name: Errors = Errors(1 << $bit);)*
+
+            const FLAGS: &'static [Flag] = &[
+                $(Flag { flag: Errors::$name, name: stringify!($name), code: $code, message: $message },)*
+            ];
+        }
+    };
+}
+
+/// A set of validation failures. Checks combine flags with `|` so that every
+/// violated rule is reported at once.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Errors(u32);
+
+struct Flag {
+    flag: Errors,
+    name: &'static str,
+    /// Never changes once released, unlike the name and message.
+    code: &'static str,
+    message: &'static str,
+}
+
+errors! {
+    UnknownAuction = 1 << 0 => "UNKNOWN_AUCTION", "Unknown auction",
+    AuctionAlreadyExists = 1 << 1 => "AUCTION_ALREADY_EXISTS", "Auction already exists",
+    AuctionHasEnded = 1 << 2 => "AUCTION_HAS_ENDED", "Auction has ended",
+    AuctionHasNotStarted = 1 << 3 => "AUCTION_HAS_NOT_STARTED", "Auction has not started",
+    AuctionNotFound = 1 << 4 => "AUCTION_NOT_FOUND", "Auction not found",
+    SellerCannotPlaceBids = 1 << 5 => "SELLER_CANNOT_PLACE_BIDS", "Seller cannot place bids",
+    BidCurrencyConversion = 1 << 6 => "BID_CURRENCY_CONVERSION", "Bid currency conversion error",
+    InvalidUserData = 1 << 7 => "INVALID_USER_DATA", "Invalid user data",
+    MustPlaceBidOverHighestBid = 1 << 8 => "MUST_PLACE_BID_OVER_HIGHEST_BID", "Must place bid over highest bid",
+    AlreadyPlacedBid = 1 << 9 => "ALREADY_PLACED_BID", "Already placed bid",
+    MustRaiseWithAtLeast = 1 << 10 => "MUST_RAISE_WITH_AT_LEAST", "Must raise with at least minimum raise amount",
+    MustSpecifyAmount = 1 << 11 => "MUST_SPECIFY_AMOUNT", "Must specify an amount over zero",
+    InvalidQuantity = 1 << 12 => "INVALID_QUANTITY", "Invalid quantity",
+    BidBelowStartingPrice = 1 << 13 => "BID_BELOW_STARTING_PRICE", "Bid is below the starting price",
+    ProxyBidNotAllowed = 1 << 14 => "PROXY_BID_NOT_ALLOWED", "Proxy bids are only allowed in single unit timed ascending auctions",
+    IllegalStatusTransition = 1 << 15 => "ILLEGAL_STATUS_TRANSITION", "Illegal auction status transition",
+    BidRetractionNotAllowed = 1 << 16 => "BID_RETRACTION_NOT_ALLOWED", "Bids can only be retracted in timed ascending auctions",
+    RetractionWindowHasPassed = 1 << 17 => "RETRACTION_WINDOW_HAS_PASSED", "Bid can no longer be retracted",
+    NoBidToRetract = 1 << 18 => "NO_BID_TO_RETRACT", "No bid to retract",
+    ExpiryMustBeLater = 1 << 19 => "EXPIRY_MUST_BE_LATER", "New expiry must be later than the current end of the auction",
+    AuctionIsPaused = 1 << 20 => "AUCTION_IS_PAUSED", "Auction is paused",
+    AuctionIsNotPaused = 1 << 21 => "AUCTION_IS_NOT_PAUSED", "Auction is not paused",
+    BidderNotApproved = 1 << 22 => "BIDDER_NOT_APPROVED", "Bidder must be approved by the seller to bid in this auction",
+    DepositRequired = 1 << 23 => "DEPOSIT_REQUIRED", "Bidder must record a deposit to bid in this auction",
+    DepositTooLow = 1 << 24 => "DEPOSIT_TOO_LOW", "Deposit must cover the amount required by the auction",
+    AuctionCannotBeRelisted = 1 << 25 => "AUCTION_CANNOT_BE_RELISTED", "Only unsold auctions with a relist policy can be relisted",
+    UnknownBid = 1 << 26 => "UNKNOWN_BID", "Unknown bid",
+    UnknownLot = 1 << 27 => "UNKNOWN_LOT", "Unknown lot",
+    LotRequired = 1 << 28 => "LOT_REQUIRED", "Bids must be placed on one of the lots of the auction",
+    AuctionIncomplete = 1 << 29 => "AUCTION_INCOMPLETE", "Auction needs a title before it can be published",
 }
 
 impl Errors {
     pub fn is_none(&self) -> bool {
         *self == Errors::None
     }
+
+    /// Every flag set in either, usable in constants unlike `|`.
+    pub const fn union(self, other: Errors) -> Errors {
+        Errors(self.0 | other.0)
+    }
+
+    /// Whether every flag in `other` is set.
+    pub fn contains(&self, other: Errors) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Every single flag, in declaration order.
+    pub fn all() -> impl Iterator<Item = Errors> {
+        Self::FLAGS.iter().map(|flag| flag.flag)
+    }
+
+    /// The single flags that are set, in declaration order.
+    pub fn iter(&self) -> impl Iterator<Item = Errors> + '_ {
+        self.flags().map(|flag| flag.flag)
+    }
+
+    fn flags(&self) -> impl Iterator<Item = &'static Flag> + '_ {
+        Self::FLAGS.iter().filter(move |flag| self.contains(flag.flag))
+    }
+
+    fn flag(&self) -> Option<&'static Flag> {
+        Self::FLAGS.iter().find(|flag| flag.flag == *self)
+    }
+
+    /// The name of a single flag, as used in serialized form.
+    pub fn name(&self) -> Option<&'static str> {
+        self.flag().map(|flag| flag.name)
+    }
+
+    /// The stable code of a single flag, such as `AUCTION_HAS_ENDED`.
+    pub fn code(&self) -> Option<&'static str> {
+        self.flag().map(|flag| flag.code)
+    }
+
+    /// The message of a single flag.
+    pub fn message(&self) -> Option<&'static str> {
+        self.flag().map(|flag| flag.message)
+    }
+
+    pub fn from_name(name: &str) -> Option<Errors> {
+        Self::FLAGS.iter().find(|flag| flag.name == name).map(|flag| flag.flag)
+    }
+}
+
+impl Errors {
+    /// One violation per flag that is set.
+    pub fn violations(&self) -> Vec<Violation> {
+        self.flags()
+            .map(|flag| Violation { code: flag.code, message: flag.message })
+            .collect()
+    }
+}
+
+/// A broken rule, with a stable code for clients to act on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    pub code: &'static str,
+    pub message: &'static str,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
 }
 
 impl std::ops::BitOr for Errors {
     type Output = Self;
 
     fn bitor(self, rhs: Self) -> Self::Output {
-        let lhs_val = self as u16;
-        let rhs_val = rhs as u16;
-        unsafe { std::mem::transmute(lhs_val | rhs_val) }
+        self.union(rhs)
+    }
+}
+
+impl std::ops::BitOrAssign for Errors {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl fmt::Debug for Errors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_none() {
+            return write!(f, "None");
+        }
+        let names: Vec<&str> = self.flags().map(|flag| flag.name).collect();
+        write!(f, "{}", names.join(" | "))
     }
 }
 
 impl fmt::Display for Errors {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Errors::None => write!(f, "No error"),
-            Errors::UnknownAuction => write!(f, "Unknown auction"),
-            Errors::AuctionAlreadyExists => write!(f, "Auction already exists"),
-            Errors::AuctionHasEnded => write!(f, "Auction has ended"),
-            Errors::AuctionHasNotStarted => write!(f, "Auction has not started"),
-            Errors::AuctionNotFound => write!(f, "Auction not found"),
-            Errors::SellerCannotPlaceBids => write!(f, "Seller cannot place bids"),
-            Errors::BidCurrencyConversion => write!(f, "Bid currency conversion error"),
-            Errors::InvalidUserData => write!(f, "Invalid user data"),
-            Errors::MustPlaceBidOverHighestBid => write!(f, "Must place bid over highest bid"),
-            Errors::AlreadyPlacedBid => write!(f, "Already placed bid"),
-            Errors::MustRaiseWithAtLeast => write!(f, "Must raise with at least minimum raise amount"),
-            Errors::MustSpecifyAmount => write!(f, "Must specify amount"),
+        if self.is_none() {
+            return write!(f, "No error");
         }
+        let messages: Vec<&str> = self.flags().map(|flag| flag.message).collect();
+        write!(f, "{}", messages.join(", "))
+    }
+}
+
+impl Serialize for Errors {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.flags().map(|flag| flag.name))
+    }
+}
+
+impl<'de> Deserialize<'de> for Errors {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let names = Vec::<String>::deserialize(deserializer)?;
+        names.iter().try_fold(Errors::None, |errors, name| {
+            Errors::from_name(name)
+                .map(|flag| errors | flag)
+                .ok_or_else(|| de::Error::custom(format!("unknown error: {}", name)))
+        })
     }
 }
 
@@ -59,6 +194,9 @@ pub enum Error {
     #[error("Validation error: {0}")]
     Validation(Errors),
 
+    #[error("Invalid: {}", .0.iter().map(|v| v.message).collect::<Vec<_>>().join(", "))]
+    Violations(Vec<Violation>),
+
     #[error("Invalid amount: {0}")]
     InvalidAmount(String),
 
@@ -77,9 +215,107 @@ pub enum Error {
     #[error("Repository error: {0}")]
     Repository(String),
 
+    /// What was stored changed since it was read, so the change was not made.
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
-}
\ No newline at end of file
+}
+
+impl Error {
+    /// The stable codes of the error, one per violated rule for validation errors.
+    pub fn codes(&self) -> Vec<&'static str> {
+        self.violations().into_iter().map(|(code, _)| code).collect()
+    }
+
+    /// The code of the kind of error, for errors that are not about broken rules.
+    fn code(&self) -> &'static str {
+        match self {
+            Error::Validation(_) | Error::Violations(_) => "VALIDATION_FAILED",
+            Error::InvalidAmount(_) => "INVALID_AMOUNT",
+            Error::CurrencyMismatch(_, _) => "CURRENCY_MISMATCH",
+            Error::InvalidUser(_) => "INVALID_USER",
+            Error::Domain(_) => "DOMAIN_ERROR",
+            Error::NotFound(_) => "NOT_FOUND",
+            Error::Repository(_) => "REPOSITORY_ERROR",
+            Error::Conflict(_) => "CONFLICT",
+            Error::Unauthorized(_) => "UNAUTHORIZED",
+            Error::Forbidden(_) => "FORBIDDEN",
+            Error::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+
+    /// The code and message of everything that went wrong, as reported to clients.
+    pub fn violations(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Error::Validation(errors) => errors.flags().map(|flag| (flag.code, flag.message.to_string())).collect(),
+            Error::Violations(violations) => violations
+                .iter()
+                .map(|violation| (violation.code, violation.message.to_string()))
+                .collect(),
+            Error::InvalidAmount(msg)
+            | Error::InvalidUser(msg)
+            | Error::Domain(msg)
+            | Error::NotFound(msg)
+            | Error::Repository(msg)
+            | Error::Conflict(msg)
+            | Error::Unauthorized(msg)
+            | Error::Forbidden(msg)
+            | Error::Internal(msg) => vec![(self.code(), msg.clone())],
+            Error::CurrencyMismatch(_, _) => vec![(self.code(), self.to_string())],
+        }
+    }
+}
+
+#[cfg(test)]
+mod errors_tests {
+    use super::*;
+
+    #[test]
+    fn test_combined_errors_keep_every_flag() {
+        let errors = Errors::AuctionHasEnded | Errors::InvalidQuantity;
+        assert!(errors.contains(Errors::AuctionHasEnded));
+        assert!(errors.contains(Errors::InvalidQuantity));
+        assert!(!errors.contains(Errors::UnknownAuction));
+        assert_eq!(errors.iter().collect::<Vec<_>>(), vec![Errors::AuctionHasEnded, Errors::InvalidQuantity]);
+        assert_eq!(errors.to_string(), "Auction has ended, Invalid quantity");
+    }
+
+    #[test]
+    fn test_errors_serialize_as_flag_names() {
+        let errors = Errors::SellerCannotPlaceBids | Errors::MustSpecifyAmount;
+        let json = serde_json::to_value(errors).unwrap();
+        assert_eq!(json, serde_json::json!(["SellerCannotPlaceBids", "MustSpecifyAmount"]));
+        assert_eq!(serde_json::from_value::<Errors>(json).unwrap(), errors);
+        assert!(serde_json::from_value::<Errors>(serde_json::json!(["Bogus"])).is_err());
+    }
+
+    #[test]
+    fn test_violations_carry_stable_codes() {
+        let violations = (Errors::AuctionHasEnded | Errors::MustRaiseWithAtLeast).violations();
+        assert_eq!(
+            violations,
+            vec![
+                Violation { code: "AUCTION_HAS_ENDED", message: "Auction has ended" },
+                Violation { code: "MUST_RAISE_WITH_AT_LEAST", message: "Must raise with at least minimum raise amount" },
+            ]
+        );
+        assert_eq!(Errors::AuctionHasEnded.code(), Some("AUCTION_HAS_ENDED"));
+        assert_eq!((Errors::AuctionHasEnded | Errors::InvalidQuantity).code(), None);
+    }
+
+    #[test]
+    fn test_every_error_has_a_code() {
+        assert_eq!(Error::Validation(Errors::UnknownAuction).codes(), vec!["UNKNOWN_AUCTION"]);
+        assert_eq!(Error::Unauthorized("who?".to_string()).codes(), vec!["UNAUTHORIZED"]);
+        assert_eq!(Error::Repository("down".to_string()).codes(), vec!["REPOSITORY_ERROR"]);
+        assert_eq!(Error::Conflict("changed".to_string()).codes(), vec!["CONFLICT"]);
+    }
+}
@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::amount::Amount;
+use super::user::UserId;
+
+/// The most a bidder is willing to pay in a timed ascending auction. Kept apart from the visible
+/// bids: the auction places bids on the bidder's behalf, up to this amount, whenever they are outbid.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaxBid {
+    pub user: UserId,
+    pub amount: Amount,
+    pub at: DateTime<Utc>,
+}
@@ -1,13 +1,29 @@
 pub mod amount;
 pub mod auction;
+pub mod auction_result;
+pub mod auction_status;
+pub mod audit;
 pub mod bid;
 pub mod currency;
+pub mod deposit;
+pub mod domain_event;
 pub mod errors;
+pub mod max_bid;
+pub mod registration;
+pub mod settlement;
 pub mod user;
 
 pub use amount::*;
 pub use auction::*;
+pub use auction_result::*;
+pub use auction_status::*;
+pub use audit::*;
 pub use bid::*;
 pub use currency::*;
+pub use deposit::*;
+pub use domain_event::*;
 pub use errors::*;
+pub use max_bid::*;
+pub use registration::*;
+pub use settlement::*;
 pub use user::*;
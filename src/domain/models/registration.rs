@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use super::auction::AuctionId;
+use super::user::UserId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RegistrationStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl fmt::Display for RegistrationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl FromStr for RegistrationStatus {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Pending" => Ok(RegistrationStatus::Pending),
+            "Approved" => Ok(RegistrationStatus::Approved),
+            "Rejected" => Ok(RegistrationStatus::Rejected),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Request of a bidder to take part in an auction that requires the seller's approval.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Registration {
+    pub auction_id: AuctionId,
+    pub user: UserId,
+    pub status: RegistrationStatus,
+    pub registered_at: DateTime<Utc>,
+    /// When the seller approved or rejected the registration
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+impl Registration {
+    pub fn is_approved(&self) -> bool {
+        self.status == RegistrationStatus::Approved
+    }
+}
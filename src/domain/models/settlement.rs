@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+
+use super::amount::Amount;
+use super::currency::CurrencyCode;
+use super::user::UserId;
+
+/// Basis points in a whole, so that 1000 basis points is 10%.
+const BASIS_POINTS: i64 = 10_000;
+
+/// What the house charges on a sale: a premium and fee on top of what the buyer bid, and a
+/// commission and fee taken from what the seller receives. Percentages are in basis points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeePolicy {
+    /// Added to each winning bid, e.g. 1500 for a 15% buyer's premium
+    pub buyers_premium_bps: i64,
+    /// Added once for each buyer
+    pub buyer_fee: i64,
+    /// Taken from the amount sold for
+    pub seller_commission_bps: i64,
+    /// Taken once from the proceeds of an auction that sold
+    pub seller_fee: i64,
+}
+
+impl FeePolicy {
+    /// What each buyer pays and what the seller receives for the charges of an ended auction,
+    /// see [`super::Auction::try_get_charges`]. Tax at `tax_rate_bps` is added to the premium,
+    /// the fees and the commission, the services of the house, but not to the hammer price.
+    /// Percentages are rounded to the nearest whole unit.
+    pub fn settle(&self, currency: CurrencyCode, tax_rate_bps: i64, charges: Vec<(Amount, UserId)>) -> Settlement {
+        let item = |kind, net, taxed: bool| ChargeItem::new(kind, net, if taxed { tax_rate_bps } else { 0 }, currency);
+        let buyers: Vec<BuyerSettlement> = charges
+            .into_iter()
+            .map(|(hammer_price, bidder)| {
+                let premium = percentage(hammer_price.value(), self.buyers_premium_bps);
+                let items = vec![
+                    item(ChargeKind::HammerPrice, hammer_price.value(), false),
+                    item(ChargeKind::BuyersPremium, premium, true),
+                    item(ChargeKind::BuyerFee, self.buyer_fee, true),
+                ];
+                BuyerSettlement {
+                    tax: Amount::new(items.iter().map(|item| item.tax.value()).sum(), currency),
+                    total: Amount::new(items.iter().map(|item| item.gross.value()).sum(), currency),
+                    premium: Amount::new(premium, currency),
+                    fee: Amount::new(self.buyer_fee, currency),
+                    hammer_price,
+                    bidder,
+                    items,
+                }
+            })
+            .collect();
+        let sold_for: i64 = buyers.iter().map(|buyer| buyer.hammer_price.value()).sum();
+        let commission = percentage(sold_for, self.seller_commission_bps);
+        let seller_fee = if buyers.is_empty() { 0 } else { self.seller_fee };
+        let seller_items = vec![
+            item(ChargeKind::SellerCommission, commission, true),
+            item(ChargeKind::SellerFee, seller_fee, true),
+        ];
+        let deducted: i64 = seller_items.iter().map(|item| item.gross.value()).sum();
+        Settlement {
+            buyers,
+            sold_for: Amount::new(sold_for, currency),
+            commission: Amount::new(commission, currency),
+            seller_fee: Amount::new(seller_fee, currency),
+            seller_items,
+            tax_rate_bps,
+            seller_receives: Amount::new(sold_for - deducted, currency),
+        }
+    }
+}
+
+fn percentage(value: i64, bps: i64) -> i64 {
+    (value * bps + BASIS_POINTS / 2).div_euclid(BASIS_POINTS)
+}
+
+/// What a line of an invoice charges for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChargeKind {
+    HammerPrice,
+    BuyersPremium,
+    BuyerFee,
+    SellerCommission,
+    SellerFee,
+}
+
+/// One line of an invoice, with the tax on it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChargeItem {
+    pub kind: ChargeKind,
+    /// Before tax
+    pub net: Amount,
+    pub tax: Amount,
+    /// Net and tax together
+    pub gross: Amount,
+}
+
+impl ChargeItem {
+    pub fn new(kind: ChargeKind, net: i64, tax_rate_bps: i64, currency: CurrencyCode) -> Self {
+        let tax = percentage(net, tax_rate_bps);
+        Self {
+            kind,
+            net: Amount::new(net, currency),
+            tax: Amount::new(tax, currency),
+            gross: Amount::new(net + tax, currency),
+        }
+    }
+}
+
+/// What one buyer pays for what they won.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuyerSettlement {
+    pub bidder: UserId,
+    /// What the buyer bid, or was charged by the rules of the auction
+    pub hammer_price: Amount,
+    pub premium: Amount,
+    pub fee: Amount,
+    /// The hammer price, premium and fee, each with its tax
+    pub items: Vec<ChargeItem>,
+    pub tax: Amount,
+    pub total: Amount,
+}
+
+/// The money side of an ended auction, see [`FeePolicy::settle`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Settlement {
+    pub buyers: Vec<BuyerSettlement>,
+    /// Sum of the hammer prices
+    pub sold_for: Amount,
+    pub commission: Amount,
+    pub seller_fee: Amount,
+    /// The commission and fee, each with its tax, taken from what the seller receives
+    pub seller_items: Vec<ChargeItem>,
+    pub tax_rate_bps: i64,
+    pub seller_receives: Amount,
+}
+
+#[cfg(test)]
+mod settlement_tests {
+    use super::*;
+
+    fn sek(value: i64) -> Amount {
+        Amount::new(value, CurrencyCode::SEK)
+    }
+
+    #[test]
+    fn test_buyer_pays_premium_and_seller_pays_commission() {
+        let policy = FeePolicy {
+            buyers_premium_bps: 1500,
+            buyer_fee: 10,
+            seller_commission_bps: 1000,
+            seller_fee: 25,
+        };
+
+        let settlement = policy.settle(CurrencyCode::SEK, 0, vec![(sek(1003), UserId::new("buyer"))]);
+
+        let buyer = &settlement.buyers[0];
+        // 15% of 1003 is 150.45, rounded to 150
+        assert_eq!(buyer.premium, sek(150));
+        assert_eq!(buyer.total, sek(1003 + 150 + 10));
+        assert_eq!(settlement.commission, sek(100));
+        assert_eq!(settlement.seller_receives, sek(1003 - 100 - 25));
+    }
+
+    #[test]
+    fn test_unsold_auction_costs_the_seller_nothing() {
+        let policy = FeePolicy {
+            seller_fee: 25,
+            ..FeePolicy::default()
+        };
+
+        let settlement = policy.settle(CurrencyCode::SEK, 0, Vec::new());
+
+        assert!(settlement.buyers.is_empty());
+        assert_eq!(settlement.seller_fee, sek(0));
+        assert_eq!(settlement.seller_receives, sek(0));
+    }
+
+    #[test]
+    fn test_tax_is_added_to_the_services_of_the_house() {
+        let policy = FeePolicy {
+            buyers_premium_bps: 1000,
+            seller_commission_bps: 1000,
+            ..FeePolicy::default()
+        };
+
+        let settlement = policy.settle(CurrencyCode::SEK, 2500, vec![(sek(1000), UserId::new("buyer"))]);
+
+        let buyer = &settlement.buyers[0];
+        assert_eq!(buyer.items[0].tax, sek(0));
+        assert_eq!(buyer.items[1].gross, sek(125));
+        assert_eq!(buyer.tax, sek(25));
+        assert_eq!(buyer.total, sek(1125));
+        assert_eq!(settlement.seller_items[0].gross, sek(125));
+        assert_eq!(settlement.seller_receives, sek(875));
+    }
+}
@@ -1,18 +1,44 @@
 use core::fmt;
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
 use super::errors::Error;
 
 
+/// Longest accepted user id, the longest possible email address
+pub const MAX_USER_ID_LENGTH: usize = 254;
+/// Longest accepted display name, in characters
+pub const MAX_DISPLAY_NAME_LENGTH: usize = 100;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct UserId(String);
 
 impl UserId {
+    /// Wraps an id as is. Use [`UserId::parse`] for ids that come from outside the system.
     pub fn new<S: Into<String>>(id: S) -> Self {
         Self(id.into())
     }
 
+    /// Validates an id and returns its canonical form: without surrounding whitespace and in lower
+    /// case. Ids are printable ASCII without spaces, and `|` is reserved by [`User::from_string`].
+    pub fn parse(id: &str) -> Result<Self, Error> {
+        let id = id.trim().to_ascii_lowercase();
+        if id.is_empty() {
+            return Err(Error::InvalidUser("User id cannot be empty".to_string()));
+        }
+        if id.len() > MAX_USER_ID_LENGTH {
+            return Err(Error::InvalidUser(format!(
+                "User id cannot be longer than {} characters",
+                MAX_USER_ID_LENGTH
+            )));
+        }
+        if let Some(c) = id.chars().find(|c| !c.is_ascii_graphic() || *c == '|') {
+            return Err(Error::InvalidUser(format!("User id cannot contain {:?}", c)));
+        }
+        Ok(Self(id))
+    }
+
     pub fn value(&self) -> &str {
         &self.0
     }
@@ -24,9 +50,16 @@ impl fmt::Display for UserId {
     }
 }
 
+/// Written as compact JSON tagged with the kind of user, such as
+/// `{"type":"BuyerOrSeller","id":"seller1@hotmail.com","name":"Sally"}`, see [`User::from_string`].
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
 pub enum User {
-    BuyerOrSeller { id: UserId, name: Option<String> },
+    BuyerOrSeller {
+        id: UserId,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
     Support { id: UserId },
 }
 
@@ -42,6 +75,25 @@ impl User {
         Self::Support { id }
     }
 
+    /// Validates a display name as shown to other users: without surrounding whitespace, not
+    /// empty, and without control characters.
+    pub fn parse_display_name(name: &str) -> Result<String, Error> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(Error::InvalidUser("Display name cannot be empty".to_string()));
+        }
+        if name.chars().count() > MAX_DISPLAY_NAME_LENGTH {
+            return Err(Error::InvalidUser(format!(
+                "Display name cannot be longer than {} characters",
+                MAX_DISPLAY_NAME_LENGTH
+            )));
+        }
+        if let Some(c) = name.chars().find(|c| c.is_control()) {
+            return Err(Error::InvalidUser(format!("Display name cannot contain {:?}", c)));
+        }
+        Ok(name.to_string())
+    }
+
     pub fn id(&self) -> &UserId {
         match self {
             Self::BuyerOrSeller { id, .. } => id,
@@ -49,52 +101,49 @@ impl User {
         }
     }
 
+    /// Reads a user as written by [`Display`](fmt::Display), or in the earlier `|`-separated
+    /// format: `BuyerOrSeller|id|name` or `Support|id`. The id is validated either way.
     pub fn from_string(s: &str) -> Result<Self, Error> {
-        let parts: Vec<&str> = s.split('|').collect();
+        let user = if s.trim_start().starts_with('{') {
+            serde_json::from_str(s).map_err(|e| Error::InvalidUser(format!("Invalid user: {}", e)))?
+        } else {
+            Self::from_delimited_string(s)?
+        };
+        let id = UserId::parse(user.id().value())?;
+        Ok(match user {
+            Self::BuyerOrSeller { name, .. } => Self::BuyerOrSeller { id, name },
+            Self::Support { .. } => Self::Support { id },
+        })
+    }
 
-        if parts.is_empty() || parts[0].is_empty() {
-            return Err(Error::InvalidUser("Invalid user string format".to_string()));
+    fn from_delimited_string(s: &str) -> Result<Self, Error> {
+        // The name is last, so that a name with a `|` in it reads back whole
+        let mut parts = s.splitn(3, '|');
+        let kind = parts.next().filter(|kind| !kind.is_empty());
+        let id = parts.next().map(UserId::new);
+        match (kind, id) {
+            (None, _) => Err(Error::InvalidUser("Invalid user string format".to_string())),
+            (Some("BuyerOrSeller"), Some(id)) => Ok(Self::new_buyer_or_seller(id, parts.next())),
+            (Some("BuyerOrSeller"), None) => Err(Error::InvalidUser("Missing BuyerOrSeller ID".to_string())),
+            (Some("Support"), Some(id)) => Ok(Self::new_support(id)),
+            (Some("Support"), None) => Err(Error::InvalidUser("Missing Support ID".to_string())),
+            (Some(kind), _) => Err(Error::InvalidUser(format!("Unknown user type: {}", kind))),
         }
+    }
+}
 
-        match parts[0] {
-            "BuyerOrSeller" => {
-                if parts.len() < 2 {
-                    return Err(Error::InvalidUser("Missing BuyerOrSeller ID".to_string()));
-                }
-                let id = UserId::new(parts[1]);
-                let name = if parts.len() > 2 {
-                    Some(parts[2].to_string())
-                } else {
-                    None
-                };
-                Ok(Self::new_buyer_or_seller(id, name))
-            }
-            "Support" => {
-                if parts.len() < 2 {
-                    return Err(Error::InvalidUser("Missing Support ID".to_string()));
-                }
-                Ok(Self::new_support(UserId::new(parts[1])))
-            }
-            _ => Err(Error::InvalidUser(format!(
-                "Unknown user type: {}",
-                parts[0]
-            ))),
-        }
+impl FromStr for User {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_string(s)
     }
 }
 
 impl fmt::Display for User {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::BuyerOrSeller { id, name } => {
-                if let Some(name) = name {
-                    write!(f, "BuyerOrSeller|{}|{}", id, name)
-                } else {
-                    write!(f, "BuyerOrSeller|{}", id)
-                }
-            }
-            Self::Support { id } => write!(f, "Support|{}", id),
-        }
+        let json = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        write!(f, "{}", json)
     }
 }
 
@@ -166,7 +215,7 @@ mod user_tests {
 
     #[test]
     fn test_user_from_string_invalid() {
-        let invalid_strs = ["", "Unknown|id", "BuyerOrSeller", "Support"];
+        let invalid_strs = ["", "Unknown|id", "BuyerOrSeller", "Support", "Support| "];
 
         for str in invalid_strs {
             let result = User::from_string(str);
@@ -174,15 +223,88 @@ mod user_tests {
         }
     }
 
+    #[test]
+    fn test_user_id_parse_returns_canonical_form() {
+        assert_eq!(UserId::parse(" Seller1@Hotmail.com\n").unwrap(), UserId::new("seller1@hotmail.com"));
+        assert_eq!(UserId::parse("a1").unwrap().value(), "a1");
+    }
+
+    #[test]
+    fn test_user_id_parse_rejects_invalid_ids() {
+        let too_long = "a".repeat(super::MAX_USER_ID_LENGTH + 1);
+        for id in ["", "   ", "john doe", "user|support", "tab\tid", "bjö@example.com", too_long.as_str()] {
+            assert!(UserId::parse(id).is_err(), "{:?}", id);
+        }
+        assert!(UserId::parse(&"a".repeat(super::MAX_USER_ID_LENGTH)).is_ok());
+    }
+
+    #[test]
+    fn test_display_name_parse() {
+        assert_eq!(User::parse_display_name("  Björn Borg ").unwrap(), "Björn Borg");
+        let too_long = "ö".repeat(super::MAX_DISPLAY_NAME_LENGTH + 1);
+        for name in ["", "  ", "line\nbreak", "nul\0", too_long.as_str()] {
+            assert!(User::parse_display_name(name).is_err(), "{:?}", name);
+        }
+        assert!(User::parse_display_name(&"ö".repeat(super::MAX_DISPLAY_NAME_LENGTH)).is_ok());
+    }
+
     #[test]
     fn test_user_display() {
         let user1 = User::new_buyer_or_seller(UserId::new("user123"), Some("John Doe"));
-        assert_eq!(user1.to_string(), "BuyerOrSeller|user123|John Doe");
+        assert_eq!(user1.to_string(), r#"{"type":"BuyerOrSeller","id":"user123","name":"John Doe"}"#);
 
         let user2 = User::new_buyer_or_seller(UserId::new("user456"), None::<String>);
-        assert_eq!(user2.to_string(), "BuyerOrSeller|user456");
+        assert_eq!(user2.to_string(), r#"{"type":"BuyerOrSeller","id":"user456"}"#);
 
         let user3 = User::new_support(UserId::new("support789"));
-        assert_eq!(user3.to_string(), "Support|support789");
+        assert_eq!(user3.to_string(), r#"{"type":"Support","id":"support789"}"#);
+    }
+
+    #[test]
+    fn test_user_from_string_keeps_names_with_pipes() {
+        let user = User::new_buyer_or_seller(UserId::new("user123"), Some("John | Doe"));
+        assert_eq!(User::from_string(&user.to_string()).unwrap(), user);
+        assert_eq!(User::from_string("BuyerOrSeller|user123|John | Doe").unwrap(), user);
+    }
+
+    #[test]
+    fn test_user_from_string_validates_ids() {
+        assert!(User::from_string(r#"{"type":"Support","id":"not an id"}"#).is_err());
+        assert!(User::from_string(r#"{"type":"Admin","id":"admin"}"#).is_err());
+        let user = User::from_string(r#"{"type":"Support","id":"Support@Hotmail.com"}"#).unwrap();
+        assert_eq!(user, User::new_support(UserId::new("support@hotmail.com")));
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        fn user_id() -> impl Strategy<Value = UserId> {
+            "[a-z0-9._@+-]{1,64}".prop_map(UserId::new)
+        }
+
+        fn user() -> impl Strategy<Value = User> {
+            prop_oneof![
+                (user_id(), proptest::option::of(any::<String>()))
+                    .prop_map(|(id, name)| User::new_buyer_or_seller(id, name)),
+                user_id().prop_map(User::new_support),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            fn test_user_round_trips_through_string(user in user()) {
+                prop_assert_eq!(User::from_string(&user.to_string()).unwrap(), user);
+            }
+
+            #[test]
+            fn test_delimited_format_still_reads(id in user_id(), name in proptest::option::of(any::<String>())) {
+                let delimited = match &name {
+                    Some(name) => format!("BuyerOrSeller|{}|{}", id, name),
+                    None => format!("BuyerOrSeller|{}", id),
+                };
+                prop_assert_eq!(User::from_string(&delimited).unwrap(), User::new_buyer_or_seller(id, name));
+            }
+        }
     }
 }
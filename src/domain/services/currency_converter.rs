@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use dyn_clone::DynClone;
+
+use crate::domain::models::{Amount, CurrencyCode};
+
+/// Converts amounts to other currencies, to show prices in the currency of the viewer. Bids are
+/// still placed in the currency of the auction. Providers of live rates implement [`Self::rate`].
+pub trait CurrencyConverter: Send + Sync + DynClone {
+    /// How much one whole unit of `from` is worth in whole units of `to`, if known.
+    fn rate(&self, from: CurrencyCode, to: CurrencyCode) -> Option<f64>;
+
+    /// `amount` in `currency`, rounded to the nearest minor unit, or `None` without a rate.
+    fn convert(&self, amount: &Amount, currency: CurrencyCode) -> Option<Amount> {
+        if amount.currency() == currency {
+            return Some(amount.clone());
+        }
+        let rate = self.rate(amount.currency(), currency)?;
+        let scale = 10f64.powi(currency.minor_units() as i32 - amount.currency().minor_units() as i32);
+        Some(Amount::new((amount.value() as f64 * rate * scale).round() as i64, currency))
+    }
+}
+
+dyn_clone::clone_trait_object!(CurrencyConverter);
+
+/// Converts by rates fixed at startup, each given as the worth of one unit of a common base
+/// currency, so that with euro as the base SEK might be 11.5 and DKK 7.46.
+#[derive(Clone, Default)]
+pub struct FixedRateCurrencyConverter {
+    rates: HashMap<CurrencyCode, f64>,
+}
+
+impl FixedRateCurrencyConverter {
+    pub fn new(rates: HashMap<CurrencyCode, f64>) -> Self {
+        Self {
+            rates: rates.into_iter().filter(|(_, rate)| *rate > 0.0).collect(),
+        }
+    }
+}
+
+impl CurrencyConverter for FixedRateCurrencyConverter {
+    fn rate(&self, from: CurrencyCode, to: CurrencyCode) -> Option<f64> {
+        Some(self.rates.get(&to)? / self.rates.get(&from)?)
+    }
+}
+
+#[cfg(test)]
+mod currency_converter_tests {
+    use super::*;
+
+    fn converter() -> FixedRateCurrencyConverter {
+        FixedRateCurrencyConverter::new(HashMap::from([
+            (CurrencyCode::EUR, 1.0),
+            (CurrencyCode::SEK, 11.5),
+            (CurrencyCode::VAC, 2.0),
+        ]))
+    }
+
+    #[test]
+    fn test_converts_through_the_base_currency() {
+        let converter = converter();
+
+        // 115 SEK is 10 EUR
+        assert_eq!(
+            converter.convert(&Amount::new(11500, CurrencyCode::SEK), CurrencyCode::EUR),
+            Some(Amount::new(1000, CurrencyCode::EUR))
+        );
+        // Rounded to the nearest cent
+        assert_eq!(
+            converter.convert(&Amount::new(100, CurrencyCode::SEK), CurrencyCode::EUR),
+            Some(Amount::new(9, CurrencyCode::EUR))
+        );
+    }
+
+    #[test]
+    fn test_respects_the_minor_units_of_each_currency() {
+        // VAC has no minor units, 3 VAC is 1.50 EUR
+        assert_eq!(
+            converter().convert(&Amount::new(3, CurrencyCode::VAC), CurrencyCode::EUR),
+            Some(Amount::new(150, CurrencyCode::EUR))
+        );
+    }
+
+    #[test]
+    fn test_cannot_convert_without_a_rate() {
+        let converter = converter();
+        let amount = Amount::new(100, CurrencyCode::DKK);
+
+        assert_eq!(converter.convert(&amount, CurrencyCode::EUR), None);
+        assert_eq!(converter.convert(&amount, CurrencyCode::DKK), Some(amount));
+    }
+}
@@ -1,3 +1,7 @@
+pub mod currency_converter;
+pub mod random_source;
 pub mod system_clock;
 
-pub use system_clock::*;
\ No newline at end of file
+pub use currency_converter::*;
+pub use random_source::*;
+pub use system_clock::*;
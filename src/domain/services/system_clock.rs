@@ -16,4 +16,15 @@ impl SystemClock for RealSystemClock {
     fn now(&self) -> DateTime<Utc> {
         Utc::now()
     }
-}
\ No newline at end of file
+}
+
+/// Always tells the same time, for deterministic runs.
+#[derive(Clone)]
+pub struct FixedSystemClock(pub DateTime<Utc>);
+
+#[async_trait::async_trait]
+impl SystemClock for FixedSystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
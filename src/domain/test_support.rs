@@ -0,0 +1,44 @@
+//! The "Lamp" auction the unit tests share: a week in SEK listed by "seller", with id 1.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::domain::commands::CreateAuctionCommand;
+use crate::domain::models::{Amount, Auction, AuctionFactory, AuctionId, BidData, CurrencyCode, UserId};
+
+pub(crate) fn starts_at() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()
+}
+
+/// The command listing the lamp, for tests to override the fields they care about
+pub(crate) fn lamp() -> CreateAuctionCommand {
+    CreateAuctionCommand {
+        title: "Lamp".to_string(),
+        currency: CurrencyCode::SEK,
+        starts_at: starts_at(),
+        ends_at: starts_at() + Duration::days(7),
+        ..CreateAuctionCommand::default()
+    }
+}
+
+pub(crate) fn auction(command: CreateAuctionCommand) -> Auction {
+    let mut auction = AuctionFactory::create_auction(command, UserId::new("seller")).unwrap();
+    auction.set_auction_id(AuctionId::new(1));
+    auction
+}
+
+pub(crate) fn bid(user: &str, amount: i64, at: DateTime<Utc>) -> BidData {
+    BidData {
+        user: UserId::new(user),
+        amount: Amount::new(amount, CurrencyCode::SEK),
+        at,
+        quantity: 1,
+        metadata: None,
+    }
+}
+
+/// Places a bid an hour into the auction
+pub(crate) fn with_bid(mut auction: Auction, user: &str, amount: i64) -> Auction {
+    let at = starts_at() + Duration::hours(1);
+    auction.try_add_bid(at, bid(user, amount, at)).unwrap();
+    auction
+}
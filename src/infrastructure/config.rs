@@ -1,13 +1,44 @@
 use config::{Config, ConfigError, Environment, File};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::env;
+use std::net::IpAddr;
 use std::time::Duration;
 
+use crate::domain::models::{CurrencyCode, FeePolicy};
+
+/// Where auctions are stored. SQLite is for small deployments and local development, MySQL (or
+/// MariaDB, with the `mysql` cargo feature) and MongoDB (with the `mongodb` cargo feature) for
+/// infrastructure without Postgres: these keep background jobs in memory and have no stats
+/// rollups, everything else is stored.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseKind {
+    #[default]
+    Postgres,
+    Sqlite,
+    Mysql,
+    Mongodb,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct DatabaseConfig {
+    #[serde(default)]
+    pub kind: DatabaseKind,
+    /// With Postgres, stores auctions as streams of events, see
+    /// [`crate::infrastructure::data::EventSourcedAuctionRepository`]
+    #[serde(default)]
+    pub event_sourced: bool,
     pub url: String,
     pub max_connections: u32,
+    /// Seconds to wait for a free connection
     pub connection_timeout: u64,
+    /// Seconds before an idle connection is closed, kept open when left out
+    #[serde(default)]
+    pub idle_timeout: Option<u64>,
+    /// Seconds before a connection is replaced however busy it is, kept when left out
+    #[serde(default)]
+    pub max_lifetime: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -16,11 +47,453 @@ pub struct ServerConfig {
     pub port: u16,
 }
 
+/// Proxies whose `Forwarded` and `X-Forwarded-For` headers are believed when hashing the address
+/// of a client, see [`crate::infrastructure::ip_hash_from_request`]. Otherwise clients could
+/// claim any address.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ClientAddressConfig {
+    pub trusted_proxies: Vec<IpAddr>,
+    // the secret addresses are hashed with, since the whole IPv4 space can be hashed without one
+    pub ip_hash_key: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct BidQueueConfig {
+    pub enabled: bool,
+    pub capacity: usize,
+    pub idle_timeout: u64,
+}
+
+impl Default for BidQueueConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            capacity: 100,
+            idle_timeout: 60,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct BidRetractionConfig {
+    // seconds after placing a bid during which it may be retracted, 0 to disallow retraction
+    pub window: u64,
+}
+
+impl Default for BidRetractionConfig {
+    fn default() -> Self {
+        Self { window: 60 }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct LoadSheddingConfig {
+    pub enabled: bool,
+    pub max_in_flight: usize,
+    pub max_latency_ms: u64,
+    pub max_pool_saturation: f64,
+    // seconds
+    pub retry_after: u64,
+    pub shed_paths: Vec<String>,
+}
+
+impl Default for LoadSheddingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_in_flight: 200,
+            max_latency_ms: 2000,
+            max_pool_saturation: 0.9,
+            retry_after: 5,
+            shed_paths: vec!["/auctions".to_string()],
+        }
+    }
+}
+
+/// How the background jobs of [`crate::infrastructure::services::JobRunner`] are run.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct JobsConfig {
+    pub enabled: bool,
+    // seconds between looking for due jobs
+    pub interval: u64,
+    pub batch_size: i64,
+    pub max_attempts: i32,
+    // seconds to wait before the next attempt, times the attempts so far
+    pub retry_after: u64,
+    // where the results of auctions are posted, if anywhere
+    pub webhook_url: Option<String>,
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: 10,
+            batch_size: 20,
+            max_attempts: 5,
+            retry_after: 30,
+            webhook_url: None,
+        }
+    }
+}
+
+/// Cross-instance bid notifications through Postgres `LISTEN`/`NOTIFY`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct EventListenerConfig {
+    pub enabled: bool,
+    // milliseconds, doubled after every failed attempt up to the maximum
+    pub reconnect_delay_ms: u64,
+    pub max_reconnect_delay_ms: u64,
+}
+
+impl Default for EventListenerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            reconnect_delay_ms: 500,
+            max_reconnect_delay_ms: 30_000,
+        }
+    }
+}
+
+/// Artificial faults for resilience testing. Percentages are of requests (or repository calls).
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct FaultInjectionConfig {
+    pub enabled: bool,
+    pub latency_ms: u64,
+    pub latency_percent: f64,
+    pub error_percent: f64,
+    pub drop_percent: f64,
+    pub repository_latency_percent: f64,
+    pub repository_error_percent: f64,
+    pub exclude_paths: Vec<String>,
+}
+
+impl Default for FaultInjectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            latency_ms: 1000,
+            latency_percent: 0.0,
+            error_percent: 0.0,
+            drop_percent: 0.0,
+            repository_latency_percent: 0.0,
+            repository_error_percent: 0.0,
+            exclude_paths: vec!["/metrics".to_string()],
+        }
+    }
+}
+
+/// Sampled, anonymized recording of requests and responses, kept in memory for support users.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct TrafficCaptureConfig {
+    pub enabled: bool,
+    pub sample_percent: f64,
+    // number of exchanges kept
+    pub capacity: usize,
+    pub max_body_bytes: usize,
+    pub exclude_paths: Vec<String>,
+}
+
+impl Default for TrafficCaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sample_percent: 10.0,
+            capacity: 500,
+            max_body_bytes: 16 * 1024,
+            exclude_paths: vec!["/metrics".to_string(), "/admin/traffic".to_string()],
+        }
+    }
+}
+
+/// Limits on the time clients may ask requests to run for, see `X-Request-Timeout`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct RequestDeadlineConfig {
+    pub enabled: bool,
+    // milliseconds, 0 for no deadline unless the client asks for one
+    pub default_timeout_ms: u64,
+    pub max_timeout_ms: u64,
+}
+
+impl Default for RequestDeadlineConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default_timeout_ms: 0,
+            max_timeout_ms: 60_000,
+        }
+    }
+}
+
+/// Moving auctions that ended long ago out of the tables of running auctions, see `Archiver`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ArchivalConfig {
+    pub enabled: bool,
+    // seconds between runs
+    pub interval: u64,
+    // days after their end that auctions are archived
+    pub after_days: i64,
+    // auctions archived per transaction
+    pub batch_size: i64,
+}
+
+impl Default for ArchivalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: 3600,
+            after_days: 90,
+            batch_size: 100,
+        }
+    }
+}
+
+/// Reads of auctions kept in process memory, see `CachingAuctionRepository`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct AuctionCacheConfig {
+    pub enabled: bool,
+    // milliseconds an auction read by ID is served from memory
+    pub auction_ttl_ms: u64,
+    // milliseconds a listing or count is served from memory
+    pub listing_ttl_ms: u64,
+    // of each kind, auctions and listings
+    pub max_entries: usize,
+}
+
+impl Default for AuctionCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            auction_ttl_ms: 1000,
+            listing_ttl_ms: 5000,
+            max_entries: 1000,
+        }
+    }
+}
+
+/// Auctions and listings cached in Redis for every instance, see `RedisAuctionRepository`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct RedisCacheConfig {
+    pub enabled: bool,
+    pub url: String,
+    // prepended to every key, to share a Redis server
+    pub key_prefix: String,
+    // channel on which writes publish the auctions they changed
+    pub channel: String,
+    pub ttl_secs: u64,
+    pub reconnect_delay_ms: u64,
+}
+
+impl Default for RedisCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: "redis://127.0.0.1:6379".to_string(),
+            key_prefix: "auctions:".to_string(),
+            channel: "auctions:invalidations".to_string(),
+            ttl_secs: 30,
+            reconnect_delay_ms: 1000,
+        }
+    }
+}
+
+/// Polling of the outbox by the `projection-worker` binary.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ProjectionWorkerConfig {
+    pub poll_interval_ms: u64,
+    pub batch_size: i64,
+}
+
+impl Default for ProjectionWorkerConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_ms: 1000,
+            batch_size: 100,
+        }
+    }
+}
+
+/// Scheduled export of auction changes to ClickHouse, run by the `projection-worker` binary.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct WarehouseExportConfig {
+    pub enabled: bool,
+    pub url: String,
+    pub database: String,
+    pub user: Option<String>,
+    pub password: Option<String>,
+    // seconds between exports
+    pub interval: u64,
+    pub batch_size: i64,
+}
+
+impl Default for WarehouseExportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: "http://localhost:8123".to_string(),
+            database: "auctions".to_string(),
+            user: None,
+            password: None,
+            interval: 300,
+            batch_size: 1000,
+        }
+    }
+}
+
+/// Relisting of unsold auctions, run by the `projection-worker` binary.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct RelistingConfig {
+    pub enabled: bool,
+    // seconds between runs
+    pub interval: u64,
+}
+
+impl Default for RelistingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: 60,
+        }
+    }
+}
+
+/// Storing the status of auctions as they open and close, run by the `projection-worker` binary.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct OpeningConfig {
+    pub enabled: bool,
+    // seconds between runs
+    pub interval: u64,
+}
+
+impl Default for OpeningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: 30,
+        }
+    }
+}
+
+/// Fees of auctions that have none of their own, see [`FeePolicy`]. Percentages are in basis
+/// points, so that 1500 is 15%.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct FeesConfig {
+    pub buyers_premium_bps: i64,
+    pub buyer_fee: i64,
+    pub seller_commission_bps: i64,
+    pub seller_fee: i64,
+}
+
+/// How the API writes amounts: `object` as `{"value": "100.50", "currency": "SEK"}`, in whole
+/// units like payloads, or `compact` as `"SEK100.50"` like other implementations of the API.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AmountFormat {
+    #[default]
+    Object,
+    Compact,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ApiConfig {
+    pub amount_format: AmountFormat,
+}
+
+/// Rates for showing prices in other currencies, each the worth of one unit of a common base
+/// currency, see [`crate::domain::services::FixedRateCurrencyConverter`].
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CurrencyConversionConfig {
+    pub rates: HashMap<String, f64>,
+}
+
+/// Privacy protection of the public stats, see [`crate::api::handlers::stats`].
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct StatsPrivacyConfig {
+    pub enabled: bool,
+    // buckets with fewer contributing events are left out
+    pub min_cohort_size: i64,
+    // privacy budget per published value, smaller adds more noise
+    pub epsilon: f64,
+    // largest amount one sale is expected to add to a gmv bucket
+    pub amount_sensitivity: i64,
+}
+
+impl Default for StatsPrivacyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_cohort_size: 10,
+            epsilon: 1.0,
+            amount_sensitivity: 1000,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct Settings {
     pub database: DatabaseConfig,
     pub server: ServerConfig,
     pub environment: String,
+    #[serde(default)]
+    pub client_address: ClientAddressConfig,
+    #[serde(default)]
+    pub bid_queue: BidQueueConfig,
+    #[serde(default)]
+    pub bid_retraction: BidRetractionConfig,
+    #[serde(default)]
+    pub event_listener: EventListenerConfig,
+    #[serde(default)]
+    pub load_shedding: LoadSheddingConfig,
+    #[serde(default)]
+    pub jobs: JobsConfig,
+    #[serde(default)]
+    pub fault_injection: FaultInjectionConfig,
+    #[serde(default)]
+    pub traffic_capture: TrafficCaptureConfig,
+    #[serde(default)]
+    pub request_deadline: RequestDeadlineConfig,
+    #[serde(default)]
+    pub auction_cache: AuctionCacheConfig,
+    #[serde(default)]
+    pub redis_cache: RedisCacheConfig,
+    #[serde(default)]
+    pub projection_worker: ProjectionWorkerConfig,
+    #[serde(default)]
+    pub warehouse_export: WarehouseExportConfig,
+    #[serde(default)]
+    pub stats_privacy: StatsPrivacyConfig,
+    #[serde(default)]
+    pub relisting: RelistingConfig,
+    #[serde(default)]
+    pub archival: ArchivalConfig,
+    #[serde(default)]
+    pub opening: OpeningConfig,
+    #[serde(default)]
+    pub fees: FeesConfig,
+    #[serde(default)]
+    pub currency_conversion: CurrencyConversionConfig,
+    #[serde(default)]
+    pub api: ApiConfig,
 }
 
 impl Settings {
@@ -49,4 +522,36 @@ impl Settings {
         Duration::from_secs(self.database.connection_timeout)
     }
 
+    pub fn bid_queue_idle_timeout(&self) -> Duration {
+        Duration::from_secs(self.bid_queue.idle_timeout)
+    }
+
+    pub fn bid_retraction_window(&self) -> Duration {
+        Duration::from_secs(self.bid_retraction.window)
+    }
+
+    pub fn fee_policy(&self) -> FeePolicy {
+        FeePolicy {
+            buyers_premium_bps: self.fees.buyers_premium_bps,
+            buyer_fee: self.fees.buyer_fee,
+            seller_commission_bps: self.fees.seller_commission_bps,
+            seller_fee: self.fees.seller_fee,
+        }
+    }
+
+    /// The configured exchange rates by currency, leaving out unknown currencies.
+    pub fn exchange_rates(&self) -> HashMap<CurrencyCode, f64> {
+        self.currency_conversion
+            .rates
+            .iter()
+            .filter_map(|(code, rate)| match code.to_ascii_uppercase().parse() {
+                Ok(currency) => Some((currency, *rate)),
+                Err(_) => {
+                    log::warn!("Ignoring the exchange rate of unknown currency {}", code);
+                    None
+                }
+            })
+            .collect()
+    }
+
 }
@@ -0,0 +1,111 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::domain::commands::CreateAuctionCommand;
+use crate::domain::models::{
+    Amount, Auction, AuctionFactory, AuctionId, BidData, Currency, CurrencyCode,
+    SingleSealedBidOptions, UserId,
+};
+
+/// Startup flag that serves the canned dataset below instead of the database.
+pub const CONTRACT_TEST_FLAG: &str = "--contract-test";
+
+/// The fixed "now" of contract test mode.
+pub fn contract_test_now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 1, 15, 12, 0, 0).unwrap()
+}
+
+pub fn contract_test_currencies() -> Vec<Currency> {
+    vec![
+        Currency { code: CurrencyCode::DKK, numeric_code: 208, name: "Danish krone".to_string(), minor_units: 2 },
+        Currency { code: CurrencyCode::SEK, numeric_code: 752, name: "Swedish krona".to_string(), minor_units: 2 },
+        Currency { code: CurrencyCode::VAC, numeric_code: 1001, name: "Virtual auction currency".to_string(), minor_units: 0 },
+    ]
+}
+
+fn command(title: &str, starts_at: DateTime<Utc>, ends_at: DateTime<Utc>) -> CreateAuctionCommand {
+    CreateAuctionCommand {
+        title: title.to_string(),
+        currency: CurrencyCode::SEK,
+        starts_at,
+        ends_at,
+        open_bidders: true,
+        ..CreateAuctionCommand::default()
+    }
+}
+
+fn place_bid(auction: &mut Auction, user: &str, amount: i64, at: DateTime<Utc>) {
+    let bid = BidData {
+        user: UserId::new(user),
+        amount: Amount::new(amount, CurrencyCode::SEK),
+        at,
+        quantity: 1,
+        metadata: None,
+    };
+    auction
+        .try_add_bid(at, bid)
+        .expect("contract test bids are valid");
+}
+
+/// A running English auction with bids, an ended Vickrey auction and an upcoming blind auction.
+/// Always the same, so that responses can be asserted on exactly.
+pub fn contract_test_auctions() -> Vec<Auction> {
+    let now = contract_test_now();
+    let seller = UserId::new("seller@example.com");
+
+    let mut english = AuctionFactory::create_auction(
+        CreateAuctionCommand {
+            min_raise: Some(10),
+            reserve_price: Some(100),
+            time_frame: Some(Duration::minutes(1)),
+            ..command("English auction", now - Duration::days(1), now + Duration::days(1))
+        },
+        seller.clone(),
+    )
+    .unwrap();
+    english.set_auction_id(AuctionId::new(1));
+    place_bid(&mut english, "buyer1@example.com", 100, now - Duration::hours(3));
+    place_bid(&mut english, "buyer2@example.com", 120, now - Duration::hours(2));
+
+    let mut vickrey = AuctionFactory::create_auction(
+        CreateAuctionCommand {
+            single_sealed_bid_options: Some(SingleSealedBidOptions::Vickrey),
+            ..command("Vickrey auction", now - Duration::days(7), now - Duration::days(1))
+        },
+        seller.clone(),
+    )
+    .unwrap();
+    vickrey.set_auction_id(AuctionId::new(2));
+    place_bid(&mut vickrey, "buyer1@example.com", 200, now - Duration::days(3));
+    place_bid(&mut vickrey, "buyer2@example.com", 150, now - Duration::days(2));
+
+    let mut blind = AuctionFactory::create_auction(
+        CreateAuctionCommand {
+            single_sealed_bid_options: Some(SingleSealedBidOptions::Blind),
+            ..command("Blind auction", now + Duration::days(1), now + Duration::days(8))
+        },
+        seller,
+    )
+    .unwrap();
+    blind.set_auction_id(AuctionId::new(3));
+
+    vec![english, vickrey, blind]
+}
+
+#[cfg(test)]
+mod contract_test_tests {
+    use super::*;
+
+    #[test]
+    fn test_dataset_is_deterministic() {
+        assert_eq!(contract_test_auctions(), contract_test_auctions());
+    }
+
+    #[test]
+    fn test_dataset_covers_each_auction_state() {
+        let now = contract_test_now();
+        let auctions = contract_test_auctions();
+        assert!(!auctions[0].has_ended(now) && !auctions[0].bids().is_empty());
+        assert!(auctions[1].try_get_amount_and_winner(now).is_some());
+        assert!(auctions[2].starts_at() > now);
+    }
+}
@@ -0,0 +1,228 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use dyn_clone::DynClone;
+use serde_json::Value;
+use sqlx::{PgConnection, PgPool};
+
+use crate::domain::models::{Auction, AuctionId, Error, UserId};
+use crate::infrastructure::data::auction_repository::{read_auctions, AuctionRow};
+
+dyn_clone::clone_trait_object!(AuctionArchive);
+
+/// Moves auctions that ended long ago out of the tables of running auctions, where the auction
+/// repository still reads them by id.
+#[async_trait]
+pub trait AuctionArchive: Send + Sync + DynClone {
+    /// Archives up to `limit` auctions that [`Auction::can_archive`] at `now`, returning their ids.
+    async fn archive_ended(&self, now: DateTime<Utc>, retention: Duration, limit: i64) -> Result<Vec<AuctionId>, Error>;
+}
+
+/// Keeps archived auctions in `auctions_archive`, one JSON document each with the bids, maximum
+/// bids and pauses that were removed from their tables. Everything else kept about the auction
+/// stays where it is.
+#[derive(Clone)]
+pub struct PgAuctionArchive {
+    pool: PgPool,
+}
+
+impl PgAuctionArchive {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AuctionArchive for PgAuctionArchive {
+    async fn archive_ended(&self, now: DateTime<Utc>, retention: Duration, limit: i64) -> Result<Vec<AuctionId>, Error> {
+        let mut archived = Vec::new();
+        // Candidates that cannot be archived yet, such as auctions paused past their end, are
+        // passed over rather than read again
+        let mut after = 0_i64;
+        while (archived.len() as i64) < limit {
+            let mut tx = self
+                .pool
+                .begin()
+                .await
+                .map_err(|e| Error::Repository(e.to_string()))?;
+            let rows = sqlx::query_as::<_, AuctionRow>(
+                r#"
+                SELECT * FROM auctions
+                WHERE id > $1 AND coalesce(ends_at, expiry) <= $2
+                ORDER BY id
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            "#,
+            )
+            .bind(after)
+            .bind(now - retention)
+            .bind(limit - archived.len() as i64)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+            let Some(last) = rows.last().map(|row| row.id) else {
+                break;
+            };
+            after = last;
+            for auction in read_auctions(&mut tx, rows).await? {
+                if auction.can_archive(now, retention) {
+                    archive_auction(&mut tx, &auction, now).await?;
+                    archived.push(auction.auction_id());
+                }
+            }
+            tx.commit()
+                .await
+                .map_err(|e| Error::Repository(e.to_string()))?;
+        }
+        Ok(archived)
+    }
+}
+
+fn participants(auction: &Auction) -> Vec<String> {
+    let mut participants: Vec<String> = auction.referred_users().iter().map(|user| user.value().to_string()).collect();
+    participants.sort();
+    participants.dedup();
+    participants
+}
+
+fn auction_document(auction: &Auction) -> Result<Value, Error> {
+    serde_json::to_value(auction).map_err(|e| Error::Internal(e.to_string()))
+}
+
+fn archived_auction(json: Value) -> Result<Auction, Error> {
+    serde_json::from_value(json).map_err(|e| Error::Repository(format!("Invalid archived auction: {}", e)))
+}
+
+/// Removing the auction removes its bids, maximum bids and pauses with it.
+async fn archive_auction(conn: &mut PgConnection, auction: &Auction, now: DateTime<Utc>) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO auctions_archive (id, participants, ended_at, archived_at, auction)
+        VALUES ($1, $2, $3, $4, $5)
+    "#,
+    )
+    .bind(auction.auction_id().value())
+    .bind(participants(auction))
+    .bind(auction.effective_end())
+    .bind(now)
+    .bind(auction_document(auction)?)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| Error::Repository(e.to_string()))?;
+    sqlx::query("DELETE FROM auctions WHERE id = $1")
+        .bind(auction.auction_id().value())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+    Ok(())
+}
+
+/// The archived auctions among `auction_ids`, in no particular order.
+pub(crate) async fn fetch_archived_auctions(conn: &mut PgConnection, auction_ids: &[i64]) -> Result<Vec<Auction>, Error> {
+    if auction_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    sqlx::query_scalar::<_, Value>("SELECT auction FROM auctions_archive WHERE id = ANY($1)")
+        .bind(auction_ids)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?
+        .into_iter()
+        .map(archived_auction)
+        .collect()
+}
+
+pub(crate) async fn is_archived(conn: &mut PgConnection, auction_id: AuctionId) -> Result<bool, Error> {
+    sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM auctions_archive WHERE id = $1)")
+        .bind(auction_id.value())
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))
+}
+
+/// Replaces the user in the archived auctions referring to them, see
+/// [`Auction::anonymize_user`].
+pub(crate) async fn anonymize_archived_user(
+    conn: &mut PgConnection,
+    user: &UserId,
+    pseudonym: &UserId,
+) -> Result<Vec<AuctionId>, Error> {
+    let documents = sqlx::query_scalar::<_, Value>(
+        "SELECT auction FROM auctions_archive WHERE participants @> ARRAY[$1::TEXT] ORDER BY id FOR UPDATE",
+    )
+    .bind(user.value())
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| Error::Repository(e.to_string()))?;
+    let mut anonymized = Vec::new();
+    for json in documents {
+        let mut auction = archived_auction(json)?;
+        if auction.anonymize_user(user, pseudonym) {
+            sqlx::query("UPDATE auctions_archive SET participants = $2, auction = $3 WHERE id = $1")
+                .bind(auction.auction_id().value())
+                .bind(participants(&auction))
+                .bind(auction_document(&auction)?)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| Error::Repository(e.to_string()))?;
+            anonymized.push(auction.auction_id());
+        }
+    }
+    Ok(anonymized)
+}
+
+#[cfg(test)]
+mod auction_archive_tests {
+    use super::*;
+    use crate::domain::commands::CreateAuctionCommand;
+    use crate::domain::models::{Amount, AuctionFactory, BidData, CurrencyCode};
+    use crate::infrastructure::data::auction_repository_conformance::{sample_auction, sample_command, starts_at};
+    use crate::infrastructure::data::{run_migrations, AuctionRepository, PgAuctionRepository};
+    use testcontainers_modules::postgres::Postgres;
+    use testcontainers_modules::testcontainers::runners::AsyncRunner;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_archived_auctions_are_read_by_id_with_postgres() {
+        let container = Postgres::default().start().await.unwrap();
+        let host_ip = container.get_host().await.unwrap();
+        let host_port = container.get_host_port_ipv4(5432).await.unwrap();
+        let url = format!("postgresql://postgres:postgres@{}:{}/postgres", host_ip, host_port);
+        let pool = PgPool::connect(&url).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        let repo = PgAuctionRepository::new(pool.clone());
+        let archive = PgAuctionArchive::new(pool);
+
+        let mut ended = repo.create_auction(sample_auction(None)).await.unwrap();
+        let bid = BidData {
+            user: UserId::new("buyer"),
+            amount: Amount::new(10, CurrencyCode::SEK),
+            at: starts_at() + Duration::hours(1),
+            quantity: 1,
+            metadata: None,
+        };
+        ended.try_add_bid(bid.at, bid).unwrap();
+        let ended = repo.update_auction(ended).await.unwrap();
+        let later = CreateAuctionCommand { ends_at: ended.expiry() + Duration::days(30), ..sample_command(None) };
+        let later = repo
+            .create_auction(AuctionFactory::create_auction(later, UserId::new("seller")).unwrap())
+            .await
+            .unwrap();
+
+        let retention = Duration::days(90);
+        let now = ended.effective_end() + retention;
+        assert_eq!(archive.archive_ended(now, retention, 10).await.unwrap(), vec![ended.auction_id()]);
+        assert_eq!(archive.archive_ended(now, retention, 10).await.unwrap(), Vec::new());
+
+        assert_eq!(repo.get_auction(ended.auction_id()).await.unwrap(), Some(ended.clone()));
+        assert!(repo.auction_exists(ended.auction_id()).await.unwrap());
+        assert_eq!(
+            repo.get_auctions_by_ids(&[later.auction_id(), ended.auction_id()]).await.unwrap(),
+            vec![later.clone(), ended.clone()]
+        );
+        assert_eq!(repo.get_auctions().await.unwrap(), vec![later]);
+
+        let anonymized = repo.anonymize_user(&UserId::new("buyer"), &UserId::new("gone")).await.unwrap();
+        assert_eq!(anonymized, vec![ended.auction_id()]);
+        let read = repo.get_auction(ended.auction_id()).await.unwrap().unwrap();
+        assert!(!read.referred_users().contains(&UserId::new("buyer")));
+    }
+}
@@ -0,0 +1,89 @@
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use std::time::Duration;
+
+use crate::domain::models::AuctionId;
+use crate::infrastructure::config::EventListenerConfig;
+use crate::infrastructure::data::AUCTION_EVENTS_CHANNEL;
+use crate::infrastructure::services::BidEvents;
+
+/// Forwards changes made by any API instance to the [`BidEvents`] of this one, so that clients
+/// waiting on an auction are woken up whichever replica took the bid. Runs until the process exits.
+///
+/// Notifications sent while the listener is disconnected are lost. Waiting clients still see those
+/// changes when their poll times out, or on the next notification for the auction.
+pub async fn listen_for_auction_events(pool: PgPool, events: BidEvents, config: EventListenerConfig) {
+    let mut attempt = 0;
+    loop {
+        match connect(&pool).await {
+            Ok(mut listener) => {
+                log::info!("Listening for auction events on {}", AUCTION_EVENTS_CHANNEL);
+                attempt = 0;
+                loop {
+                    match listener.try_recv().await {
+                        Ok(Some(notification)) => match parse_notification(notification.payload()) {
+                            Some(auction_id) => events.publish(auction_id),
+                            None => log::warn!(
+                                "Ignoring auction event notification {:?}",
+                                notification.payload()
+                            ),
+                        },
+                        // The listener reconnects and listens again on the next call
+                        Ok(None) => log::warn!("Auction event listener lost its connection, reconnecting"),
+                        Err(e) => {
+                            log::error!("Auction event listener failed: {}", e);
+                            break;
+                        }
+                    }
+                }
+            }
+            Err(e) => log::error!("Failed to listen for auction events: {}", e),
+        }
+        let delay = reconnect_delay(&config, attempt);
+        attempt += 1;
+        tokio::time::sleep(delay).await;
+    }
+}
+
+async fn connect(pool: &PgPool) -> Result<PgListener, sqlx::Error> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(AUCTION_EVENTS_CHANNEL).await?;
+    Ok(listener)
+}
+
+fn parse_notification(payload: &str) -> Option<AuctionId> {
+    payload.parse().ok().map(AuctionId::new)
+}
+
+/// Exponential backoff, capped at the configured maximum.
+fn reconnect_delay(config: &EventListenerConfig, attempt: u32) -> Duration {
+    let delay = config
+        .reconnect_delay_ms
+        .saturating_mul(2u64.saturating_pow(attempt))
+        .min(config.max_reconnect_delay_ms);
+    Duration::from_millis(delay)
+}
+
+#[cfg(test)]
+mod auction_event_listener_tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_payload_is_the_auction_id() {
+        assert_eq!(parse_notification("42"), Some(AuctionId::new(42)));
+        assert_eq!(parse_notification("auction 42"), None);
+    }
+
+    #[test]
+    fn test_reconnect_delay_doubles_up_to_the_maximum() {
+        let config = EventListenerConfig {
+            enabled: true,
+            reconnect_delay_ms: 500,
+            max_reconnect_delay_ms: 3000,
+        };
+        assert_eq!(reconnect_delay(&config, 0), Duration::from_millis(500));
+        assert_eq!(reconnect_delay(&config, 2), Duration::from_millis(2000));
+        assert_eq!(reconnect_delay(&config, 3), Duration::from_millis(3000));
+        assert_eq!(reconnect_delay(&config, 64), Duration::from_millis(3000));
+    }
+}
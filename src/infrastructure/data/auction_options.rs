@@ -0,0 +1,287 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::domain::models::{
+    Auction, BidIncrement, Error, SingleSealedBidOptions, TimedAscendingOptions,
+};
+
+/// Version of the records written to the `options` column. Bump it together with a new
+/// record type and an upgrade function from the previous version in [`upgrade`].
+pub const OPTIONS_VERSION: i32 = 2;
+
+/// Version 1 is whatever serde emitted for the options of the domain model until the column
+/// was versioned. The records are frozen copies of that shape, so that later changes to the
+/// domain model do not change how old rows read.
+mod v1 {
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub enum SingleSealedBidOptions {
+        Blind,
+        Vickrey,
+        AllPay,
+    }
+
+    #[derive(Deserialize)]
+    pub struct BidIncrement {
+        pub below: i64,
+        pub increment: i64,
+    }
+
+    #[derive(Deserialize)]
+    pub struct TimedAscendingOptions {
+        pub reserve_price: i64,
+        pub min_raise: i64,
+        pub time_frame: chrono::Duration,
+        #[serde(default)]
+        pub buy_now_price: Option<i64>,
+        #[serde(default)]
+        pub starting_price: i64,
+        #[serde(default)]
+        pub max_extension: Option<chrono::Duration>,
+        #[serde(default)]
+        pub increments: Vec<BidIncrement>,
+    }
+}
+
+/// Version 2 spells out every field and keeps durations in whole seconds.
+mod v2 {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    pub enum SealedBidKind {
+        Blind,
+        Vickrey,
+        AllPay,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    pub struct SingleSealedBidOptions {
+        pub kind: SealedBidKind,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    pub struct BidIncrement {
+        pub below: i64,
+        pub increment: i64,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    pub struct TimedAscendingOptions {
+        pub reserve_price: i64,
+        pub min_raise: i64,
+        pub time_frame_seconds: i64,
+        pub buy_now_price: Option<i64>,
+        pub starting_price: i64,
+        pub max_extension_seconds: Option<i64>,
+        pub increments: Vec<BidIncrement>,
+    }
+}
+
+/// Options of either auction type, as stored in the current version.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+enum StoredOptions {
+    SingleSealedBid(v2::SingleSealedBidOptions),
+    TimedAscending(v2::TimedAscendingOptions),
+}
+
+fn invalid(auction_type: &str, version: i32, e: impl std::fmt::Display) -> Error {
+    Error::Repository(format!(
+        "Invalid {} options of version {}: {}",
+        auction_type, version, e
+    ))
+}
+
+fn upgrade_v1(auction_type: &str, stored: Value) -> Result<Value, Error> {
+    let upgraded = match auction_type {
+        "SingleSealedBid" => {
+            let options: v1::SingleSealedBidOptions =
+                serde_json::from_value(stored).map_err(|e| invalid(auction_type, 1, e))?;
+            StoredOptions::SingleSealedBid(v2::SingleSealedBidOptions {
+                kind: match options {
+                    v1::SingleSealedBidOptions::Blind => v2::SealedBidKind::Blind,
+                    v1::SingleSealedBidOptions::Vickrey => v2::SealedBidKind::Vickrey,
+                    v1::SingleSealedBidOptions::AllPay => v2::SealedBidKind::AllPay,
+                },
+            })
+        }
+        "TimedAscending" => {
+            let options: v1::TimedAscendingOptions =
+                serde_json::from_value(stored).map_err(|e| invalid(auction_type, 1, e))?;
+            StoredOptions::TimedAscending(v2::TimedAscendingOptions {
+                reserve_price: options.reserve_price,
+                min_raise: options.min_raise,
+                time_frame_seconds: options.time_frame.num_seconds(),
+                buy_now_price: options.buy_now_price,
+                starting_price: options.starting_price,
+                max_extension_seconds: options.max_extension.map(|d| d.num_seconds()),
+                increments: options
+                    .increments
+                    .into_iter()
+                    .map(|step| v2::BidIncrement {
+                        below: step.below,
+                        increment: step.increment,
+                    })
+                    .collect(),
+            })
+        }
+        other => return Err(invalid(other, 1, "unknown auction type")),
+    };
+    serde_json::to_value(upgraded).map_err(|e| invalid(auction_type, 2, e))
+}
+
+/// Brings stored options up to [`OPTIONS_VERSION`], one version at a time.
+fn upgrade(auction_type: &str, mut version: i32, mut stored: Value) -> Result<Value, Error> {
+    while version < OPTIONS_VERSION {
+        stored = match version {
+            1 => upgrade_v1(auction_type, stored)?,
+            _ => return Err(invalid(auction_type, version, "no upgrade to the next version")),
+        };
+        version += 1;
+    }
+    if version > OPTIONS_VERSION {
+        return Err(invalid(auction_type, version, "written by a newer version of the service"));
+    }
+    Ok(stored)
+}
+
+/// The options of the auction in the current storage format.
+pub fn options_for_storage(auction: &Auction) -> Value {
+    let stored = match auction {
+        Auction::SingleSealedBid { options, .. } => {
+            StoredOptions::SingleSealedBid(v2::SingleSealedBidOptions {
+                kind: match options {
+                    SingleSealedBidOptions::Blind => v2::SealedBidKind::Blind,
+                    SingleSealedBidOptions::Vickrey => v2::SealedBidKind::Vickrey,
+                    SingleSealedBidOptions::AllPay => v2::SealedBidKind::AllPay,
+                },
+            })
+        }
+        Auction::TimedAscending { options, .. } => StoredOptions::TimedAscending(v2::TimedAscendingOptions {
+            reserve_price: options.reserve_price,
+            min_raise: options.min_raise,
+            time_frame_seconds: options.time_frame.num_seconds(),
+            buy_now_price: options.buy_now_price,
+            starting_price: options.starting_price,
+            max_extension_seconds: options.max_extension.map(|d| d.num_seconds()),
+            increments: options
+                .increments
+                .iter()
+                .map(|step| v2::BidIncrement {
+                    below: step.below,
+                    increment: step.increment,
+                })
+                .collect(),
+        }),
+    };
+    serde_json::to_value(stored).expect("options serialize to JSON")
+}
+
+/// Reads stored options of a single sealed bid auction, of any version.
+pub fn single_sealed_bid_options_from_storage(version: i32, stored: Value) -> Result<SingleSealedBidOptions, Error> {
+    let auction_type = "SingleSealedBid";
+    let options: v2::SingleSealedBidOptions = serde_json::from_value(upgrade(auction_type, version, stored)?)
+        .map_err(|e| invalid(auction_type, OPTIONS_VERSION, e))?;
+    Ok(match options.kind {
+        v2::SealedBidKind::Blind => SingleSealedBidOptions::Blind,
+        v2::SealedBidKind::Vickrey => SingleSealedBidOptions::Vickrey,
+        v2::SealedBidKind::AllPay => SingleSealedBidOptions::AllPay,
+    })
+}
+
+/// Reads stored options of a timed ascending auction, of any version.
+pub fn timed_ascending_options_from_storage(version: i32, stored: Value) -> Result<TimedAscendingOptions, Error> {
+    let auction_type = "TimedAscending";
+    let options: v2::TimedAscendingOptions = serde_json::from_value(upgrade(auction_type, version, stored)?)
+        .map_err(|e| invalid(auction_type, OPTIONS_VERSION, e))?;
+    Ok(TimedAscendingOptions {
+        reserve_price: options.reserve_price,
+        min_raise: options.min_raise,
+        time_frame: chrono::Duration::seconds(options.time_frame_seconds),
+        buy_now_price: options.buy_now_price,
+        starting_price: options.starting_price,
+        max_extension: options.max_extension_seconds.map(chrono::Duration::seconds),
+        increments: options
+            .increments
+            .into_iter()
+            .map(|step| BidIncrement {
+                below: step.below,
+                increment: step.increment,
+            })
+            .collect(),
+    })
+}
+
+/// Reads stored options of any version into the serde representation of the domain options.
+pub fn options_from_storage(auction_type: &str, version: i32, stored: Value) -> Result<Value, Error> {
+    let domain = match auction_type {
+        "SingleSealedBid" => serde_json::to_value(single_sealed_bid_options_from_storage(version, stored)?),
+        "TimedAscending" => serde_json::to_value(timed_ascending_options_from_storage(version, stored)?),
+        other => return Err(invalid(other, OPTIONS_VERSION, "unknown auction type")),
+    };
+    domain.map_err(|e| invalid(auction_type, OPTIONS_VERSION, e))
+}
+
+#[cfg(test)]
+mod auction_options_tests {
+    use super::*;
+    use chrono::Duration;
+    use serde_json::json;
+
+    fn timed_ascending_v1() -> Value {
+        // Written before buy-now prices, starting prices and anti-sniping limits existed
+        json!({
+            "reserve_price": 100,
+            "min_raise": 10,
+            "time_frame": Duration::minutes(5),
+        })
+    }
+
+    #[test]
+    fn test_upgrades_legacy_timed_ascending_options() {
+        let domain = options_from_storage("TimedAscending", 1, timed_ascending_v1()).unwrap();
+
+        let options: TimedAscendingOptions = serde_json::from_value(domain).unwrap();
+        assert_eq!(options.reserve_price, 100);
+        assert_eq!(options.min_raise, 10);
+        assert_eq!(options.time_frame, Duration::minutes(5));
+        assert_eq!(options.buy_now_price, None);
+        assert_eq!(options.starting_price, 0);
+        assert_eq!(options.max_extension, None);
+        assert!(options.increments.is_empty());
+    }
+
+    #[test]
+    fn test_upgrades_legacy_single_sealed_bid_options() {
+        let domain = options_from_storage("SingleSealedBid", 1, json!("Vickrey")).unwrap();
+
+        let options: SingleSealedBidOptions = serde_json::from_value(domain).unwrap();
+        assert_eq!(options, SingleSealedBidOptions::Vickrey);
+    }
+
+    #[test]
+    fn test_current_version_is_explicit() {
+        let stored = upgrade("TimedAscending", 1, timed_ascending_v1()).unwrap();
+
+        assert_eq!(
+            stored,
+            json!({
+                "reserve_price": 100,
+                "min_raise": 10,
+                "time_frame_seconds": 300,
+                "buy_now_price": null,
+                "starting_price": 0,
+                "max_extension_seconds": null,
+                "increments": [],
+            })
+        );
+    }
+
+    #[test]
+    fn test_rejects_options_from_newer_versions() {
+        let result = options_from_storage("SingleSealedBid", OPTIONS_VERSION + 1, json!({ "kind": "Blind" }));
+
+        assert!(matches!(result, Err(Error::Repository(_))));
+    }
+}
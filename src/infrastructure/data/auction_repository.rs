@@ -1,18 +1,431 @@
 use async_trait::async_trait;
 use dyn_clone::DynClone;
-use sqlx::PgPool;
-use std::collections::HashSet;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{PgConnection, PgPool};
+use std::collections::{HashMap, HashSet};
 
-use crate::domain::models::{Auction, AuctionId, Error};
+use crate::domain::models::{
+    bidder_aliases, Amount, Auction, AuctionBase, AuctionId, AuctionPause, AuctionRemoval, AuctionStatus, AuctionType, Bid,
+    BidData, CurrencyCode, DomainEvent, Error, Errors, MaxBid, RelistPolicy, RemovalKind, RetractedBid, UserId, VoidedBid,
+};
+use crate::infrastructure::data::{
+    append_event, auction_payload, bid_payload, options_for_storage, single_sealed_bid_options_from_storage,
+    anonymize_archived_user, fetch_archived_auctions, is_archived, timed_ascending_options_from_storage, AuctionEventType,
+    OPTIONS_VERSION,
+};
 
 dyn_clone::clone_trait_object!(AuctionRepository);
 
 #[async_trait]
 pub trait AuctionRepository: Send + Sync + DynClone {
     async fn get_auction(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error>;
+    /// Like [`AuctionRepository::get_auction`], telling auctions that were taken down apart from
+    /// ones that never existed. Taken down auctions are kept, so stores need not tell them apart.
+    async fn look_up_auction(&self, auction_id: AuctionId) -> Result<AuctionLookup, Error> {
+        Ok(match self.get_auction(auction_id).await? {
+            Some(auction) => match auction.removal() {
+                Some(removal) => AuctionLookup::Removed(removal),
+                None => AuctionLookup::Found(Box::new(auction)),
+            },
+            None => AuctionLookup::Missing,
+        })
+    }
+    /// The auction as [`AuctionRepository::get_auction_summaries`] reads it, without its bids.
+    /// Stores that cannot leave out the bids read the whole auction.
+    async fn get_auction_summary(&self, auction_id: AuctionId) -> Result<Option<AuctionSummary>, Error> {
+        Ok(self.get_auction(auction_id).await?.as_ref().map(AuctionSummary::of))
+    }
+    /// The auction without its bids, for showing what does not take them to work out, the bids
+    /// being read a page at a time from [`AuctionRepository::get_bids_page`]. Stores that cannot
+    /// leave out the bids read the whole auction.
+    async fn get_auction_without_bids(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+        self.get_auction(auction_id).await
+    }
+    /// The auction with the bids that decide its outcome and standing, see [`AuctionStandings`],
+    /// for showing who leads or won without reading every bid. Stores that cannot pick out those
+    /// bids read the whole auction.
+    async fn get_auction_standings(&self, auction_id: AuctionId) -> Result<Option<AuctionStandings>, Error> {
+        Ok(self.get_auction(auction_id).await?.map(AuctionStandings::of))
+    }
     async fn get_auctions(&self) -> Result<Vec<Auction>, Error>;
+    /// The auctions matching `filter` ordered by id, at most `limit` of them after skipping
+    /// `offset`. Stores that cannot page by themselves read every auction.
+    async fn get_auctions_page(&self, limit: i64, offset: i64, filter: &AuctionFilter) -> Result<AuctionPage, Error> {
+        let matching: Vec<Auction> =
+            self.get_auctions().await?.into_iter().filter(|auction| filter.matches(auction)).collect();
+        let total = matching.len() as i64;
+        let auctions = matching.into_iter().skip(offset.max(0) as usize).take(limit.max(0) as usize).collect();
+        Ok(AuctionPage::new(auctions, total, offset))
+    }
+    /// A page like [`AuctionRepository::get_auctions_page`], read as summaries without the bids.
+    async fn get_auction_summaries(
+        &self,
+        limit: i64,
+        offset: i64,
+        filter: &AuctionFilter,
+    ) -> Result<AuctionPage<AuctionSummary>, Error> {
+        let page = self.get_auctions_page(limit, offset, filter).await?;
+        Ok(AuctionPage {
+            auctions: page.auctions.iter().map(AuctionSummary::of).collect(),
+            total: page.total,
+            next_offset: page.next_offset,
+        })
+    }
+    /// The auctions taking bids at `now` ordered by id, see [`Auction::is_active`]. Stores that
+    /// cannot tell by themselves read every auction.
+    async fn get_active_auctions(&self, now: DateTime<Utc>) -> Result<Vec<Auction>, Error> {
+        let mut auctions = self.get_auctions().await?;
+        auctions.retain(|auction| auction.is_active(now));
+        Ok(auctions)
+    }
+    /// The standing bids of the auction placed before `after_bid_id`, latest first, at most
+    /// `limit` of them. Empty when there is no such auction.
+    async fn get_bids_page(&self, auction_id: AuctionId, after_bid_id: Option<i64>, limit: i64) -> Result<BidPage, Error> {
+        Ok(self
+            .get_auction(auction_id)
+            .await?
+            .map(|auction| BidPage::of(&auction, after_bid_id, limit))
+            .unwrap_or_default())
+    }
+    /// The auctions of the seller ordered by id, drafts included.
+    async fn get_auctions_by_seller(&self, seller: &UserId) -> Result<Vec<Auction>, Error>;
+    /// The auctions in the order of the ids, leaving out ids that match no auction.
+    async fn get_auctions_by_ids(&self, auction_ids: &[AuctionId]) -> Result<Vec<Auction>, Error>;
+    async fn get_auction_by_external_reference(
+        &self,
+        user: &UserId,
+        external_reference: &str,
+    ) -> Result<Option<Auction>, Error>;
     async fn create_auction(&self, auction: Auction) -> Result<Auction, Error>;
+    /// Stores the auction as changed since it was read, failing with [`Error::Conflict`] when the
+    /// stored auction is no longer at the version of `auction`. Returns it at its new version.
     async fn update_auction(&self, auction: Auction) -> Result<Auction, Error>;
+    /// Stores one bid placed on the auction, and the end the bid moved it to, without
+    /// comparing the whole auction with the stored one like `update_auction` does. Stores
+    /// keeping the auction as a whole replay the bid onto the stored auction. Fails with
+    /// [`Error::Conflict`] when the stored auction is no longer at `version`.
+    async fn add_bid(
+        &self,
+        auction_id: AuctionId,
+        version: i32,
+        bid: &Bid,
+        ends_at: Option<DateTime<Utc>>,
+    ) -> Result<(), Error> {
+        let mut auction = self
+            .get_auction(auction_id)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("Auction with ID {} not found", auction_id)))?;
+        if auction.version() != version {
+            return Err(changed_since_read(auction_id));
+        }
+        auction.replay(&DomainEvent::BidPlaced { auction_id, bid: bid.clone() });
+        if let Some(ends_at) = ends_at {
+            auction.replay(&DomainEvent::AuctionExtended { auction_id, ends_at });
+        }
+        self.update_auction(auction).await.map(|_| ())
+    }
+    /// Places a bid on the auction as stored: `place` adds the bid, and the auction is stored
+    /// with it unless that fails. Stores that can hold the auction while doing so let no other
+    /// change in between, the others fail with [`Error::Conflict`] when one got in first.
+    async fn place_bid(&self, auction_id: AuctionId, place: PlaceBid) -> Result<Vec<DomainEvent>, Error> {
+        let existing = self
+            .get_auction(auction_id)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("Auction with ID {} not found", auction_id)))?;
+        let mut auction = existing.clone();
+        let events = place(&mut auction).map_err(Error::Validation)?;
+        // A bid that changed nothing else is inserted as such, anything more saves the auction
+        match single_bid(&existing, &auction, &events) {
+            Some((bid, ends_at)) => self.add_bid(auction_id, existing.version(), &bid, ends_at).await?,
+            None => {
+                self.update_auction(auction).await?;
+            }
+        }
+        Ok(events)
+    }
+    /// Existence check that does not read the auction itself.
+    async fn auction_exists(&self, auction_id: AuctionId) -> Result<bool, Error>;
+    async fn count_auctions(&self, filter: &AuctionFilter) -> Result<i64, Error>;
+    /// Number of standing bids, not counting retracted or voided ones.
+    async fn count_bids(&self, auction_id: AuctionId) -> Result<i64, Error>;
+    /// Replaces every stored reference to `user` with `pseudonym` at once, see
+    /// [`Auction::anonymize_user`]. Returns the auctions that referred to the user.
+    async fn anonymize_user(&self, user: &UserId, pseudonym: &UserId) -> Result<Vec<AuctionId>, Error>;
+}
+
+/// Adds a bid to the auction, see [`AuctionRepository::place_bid`].
+pub type PlaceBid = Box<dyn FnOnce(&mut Auction) -> Result<Vec<DomainEvent>, Errors> + Send>;
+
+/// The bid placed, and the end it moved the auction to, when that is all that changed: replaying
+/// the events onto the auction as it was gives the auction as it is. Proxy bids, bids answered by
+/// maximum bids and bids closing the auction change more.
+fn single_bid(before: &Auction, after: &Auction, events: &[DomainEvent]) -> Option<(Bid, Option<DateTime<Utc>>)> {
+    let mut bids = events.iter().filter_map(|event| match event {
+        DomainEvent::BidPlaced { bid, .. } => Some(bid.clone()),
+        _ => None,
+    });
+    let (bid, None) = (bids.next()?, bids.next()) else {
+        return None;
+    };
+    let ends_at = events.iter().find_map(|event| match event {
+        DomainEvent::AuctionExtended { ends_at, .. } => Some(*ends_at),
+        _ => None,
+    });
+    let mut replayed = before.clone();
+    let replays = events.iter().all(|event| replayed.replay(event));
+    (replays && replayed == *after).then_some((bid, ends_at))
+}
+
+/// The error of a store asked to change an auction that was changed by someone else since it
+/// was read.
+pub(crate) fn changed_since_read(auction_id: AuctionId) -> Error {
+    Error::Conflict(format!("Auction with ID {} was changed since it was read", auction_id))
+}
+
+/// Criteria for [`AuctionRepository::count_auctions`]; empty criteria match every auction.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuctionFilter {
+    pub seller: Option<UserId>,
+    /// The stored status, see [`Auction::status`]
+    pub status: Option<AuctionStatus>,
+    /// Leaves out drafts, which only their seller gets to see
+    pub published: bool,
+}
+
+impl AuctionFilter {
+    pub fn matches(&self, auction: &Auction) -> bool {
+        self.seller.as_ref().is_none_or(|seller| auction.user() == seller)
+            && self.status.is_none_or(|status| auction.status() == status)
+            && !(self.published && auction.status() == AuctionStatus::Draft)
+    }
+
+    /// The stored status that the filter leaves out, for stores filtering on the status column.
+    pub(crate) fn hidden_status(&self) -> Option<String> {
+        self.published.then(|| AuctionStatus::Draft.to_string())
+    }
+}
+
+/// One page of [`AuctionRepository::get_auctions_page`], or of summaries of the auctions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuctionPage<T = Auction> {
+    pub auctions: Vec<T>,
+    /// Number of auctions matching the filter, on every page
+    pub total: i64,
+    /// Where the next page starts, none on the last page
+    pub next_offset: Option<i64>,
+}
+
+impl<T> AuctionPage<T> {
+    /// The page of `auctions` read at `offset`.
+    pub fn new(auctions: Vec<T>, total: i64, offset: i64) -> Self {
+        let end = offset + auctions.len() as i64;
+        Self { auctions, total, next_offset: (end < total).then_some(end) }
+    }
+}
+
+/// Answer of [`AuctionRepository::look_up_auction`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuctionLookup {
+    Found(Box<Auction>),
+    Removed(AuctionRemoval),
+    Missing,
+}
+
+/// One page of [`AuctionRepository::get_bids_page`], latest bids first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BidPage {
+    pub bids: Vec<Bid>,
+    /// The `after_bid_id` of the next page, none on the last page
+    pub next_after: Option<i64>,
+    /// The aliases of the bidders on the page, see [`Auction::bidder_aliases`]
+    pub bidder_aliases: HashMap<UserId, String>,
+}
+
+impl BidPage {
+    /// The page of the standing bids of the auction placed before `after_bid_id`.
+    pub fn of(auction: &Auction, after_bid_id: Option<i64>, limit: i64) -> Self {
+        let mut bids: Vec<Bid> = auction
+            .bids()
+            .iter()
+            .filter(|bid| bid.id < after_bid_id.unwrap_or(i64::MAX))
+            .cloned()
+            .collect();
+        bids.sort_by_key(|bid| std::cmp::Reverse(bid.id));
+        Self::new(bids, limit, auction.bidder_aliases())
+    }
+
+    /// The first `limit` of `bids`, which are latest first and may go on past the page, with
+    /// the aliases of their bidders among `aliases`.
+    fn new(mut bids: Vec<Bid>, limit: i64, mut aliases: HashMap<UserId, String>) -> Self {
+        let limit = limit.max(1) as usize;
+        let next_after = (bids.len() > limit).then(|| bids[limit - 1].id);
+        bids.truncate(limit);
+        aliases.retain(|bidder, _| bids.iter().any(|bid| bid.user() == bidder));
+        Self { bids, next_after, bidder_aliases: aliases }
+    }
+}
+
+/// Answer of [`AuctionRepository::get_auction_standings`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuctionStandings {
+    /// The auction with at least the latest standing bid of each bidder and the highest standing
+    /// bid, on the auction and on each of its lots, maximum bids possibly left out. Winners,
+    /// charges, results and next minimum bids work out the same as with every bid.
+    pub auction: Auction,
+    /// Number of standing bids on each lot that has any
+    pub lot_bid_counts: HashMap<i32, i64>,
+    /// The aliases of every bidder, see [`Auction::bidder_aliases`]
+    pub bidder_aliases: HashMap<UserId, String>,
+}
+
+impl AuctionStandings {
+    /// The standings of the auction read with every bid.
+    pub fn of(auction: Auction) -> Self {
+        let mut lot_bid_counts = HashMap::new();
+        for lot in auction.bids().iter().filter_map(|bid| bid.lot) {
+            *lot_bid_counts.entry(lot).or_default() += 1;
+        }
+        let bidder_aliases = auction.bidder_aliases();
+        Self { auction, lot_bid_counts, bidder_aliases }
+    }
+}
+
+/// What lists show of an auction, read without its bids, see
+/// [`AuctionRepository::get_auction_summaries`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuctionSummary {
+    pub auction_id: AuctionId,
+    pub title: String,
+    pub seller: UserId,
+    pub currency: CurrencyCode,
+    pub starts_at: DateTime<Utc>,
+    pub expiry: DateTime<Utc>,
+    /// The expiry moved on by late bids, see [`Auction::effective_end`]
+    pub ends_at: DateTime<Utc>,
+    /// The stored status, see [`Auction::status`]
+    pub status: AuctionStatus,
+    pub paused: bool,
+    /// The bids are sealed until the auction has ended
+    pub sealed: bool,
+    /// Bidders are shown as is instead of by alias
+    pub open_bidders: bool,
+    pub quantity: i32,
+    pub requires_registration: bool,
+    pub deposit: Option<Amount>,
+    pub external_reference: Option<String>,
+    /// Number of standing bids, see [`AuctionRepository::count_bids`]
+    pub bid_count: i64,
+    /// The amount of the highest standing bid
+    pub high_bid: Option<Amount>,
+    /// Set once the auction was taken down, see [`Auction::removal`]
+    pub removal: Option<AuctionRemoval>,
+}
+
+impl AuctionSummary {
+    pub fn of(auction: &Auction) -> Self {
+        Self {
+            auction_id: auction.auction_id(),
+            title: auction.title().to_string(),
+            seller: auction.user().clone(),
+            currency: auction.currency(),
+            starts_at: auction.starts_at(),
+            expiry: auction.expiry(),
+            ends_at: auction.effective_end(),
+            status: auction.status(),
+            paused: auction.is_paused(),
+            sealed: matches!(auction, Auction::SingleSealedBid { .. }),
+            open_bidders: auction.open_bidders(),
+            quantity: auction.quantity(),
+            requires_registration: auction.requires_registration(),
+            deposit: auction.deposit(),
+            external_reference: auction.external_reference().map(str::to_string),
+            bid_count: auction.bids().len() as i64,
+            high_bid: auction.highest_bid().map(|bid| bid.amount().clone()),
+            removal: auction.removal(),
+        }
+    }
+
+    /// The status as of `now`, like [`Auction::status_at`]. Auctions bought now were stored
+    /// closed by the bid that bought them.
+    pub fn status_at(&self, now: DateTime<Utc>) -> AuctionStatus {
+        match self.status {
+            AuctionStatus::Scheduled | AuctionStatus::Open if now >= self.starts_at => {
+                if !self.paused && now > self.ends_at {
+                    AuctionStatus::Closed
+                } else {
+                    AuctionStatus::Open
+                }
+            }
+            status => status,
+        }
+    }
+
+    /// Whether the bids are kept hidden at `now`, which sealed bids are until the auction has
+    /// ended.
+    pub fn bids_sealed(&self, now: DateTime<Utc>) -> bool {
+        self.sealed && !self.status_at(now).has_ended()
+    }
+}
+
+/// A row of [`AuctionSummary`], the bids counted by the query.
+#[derive(sqlx::FromRow)]
+struct AuctionSummaryRow {
+    id: i64,
+    title: String,
+    user_id: String,
+    currency: String,
+    starts_at: DateTime<Utc>,
+    expiry: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+    status: String,
+    paused: bool,
+    auction_type: String,
+    open_bidders: bool,
+    quantity: i32,
+    requires_registration: bool,
+    deposit: Option<i64>,
+    external_reference: Option<String>,
+    bid_count: i64,
+    high_bid: Option<i64>,
+    voided_at: Option<DateTime<Utc>>,
+    void_reason: Option<String>,
+    cancelled_at: Option<DateTime<Utc>>,
+    cancel_reason: Option<String>,
+}
+
+impl AuctionSummaryRow {
+    fn into_summary(self) -> Result<AuctionSummary, Error> {
+        let currency = currency_column("auction currency", &self.currency)?;
+        // Like Auction::removal, voiding taking precedence
+        let removal = match (self.voided_at, self.cancelled_at) {
+            (Some(at), _) => Some(AuctionRemoval { kind: RemovalKind::Voided, at, reason: self.void_reason }),
+            (None, Some(at)) => Some(AuctionRemoval { kind: RemovalKind::Cancelled, at, reason: self.cancel_reason }),
+            (None, None) => None,
+        };
+        Ok(AuctionSummary {
+            auction_id: AuctionId::new(self.id),
+            title: self.title,
+            seller: UserId::new(self.user_id),
+            currency,
+            starts_at: self.starts_at,
+            expiry: self.expiry,
+            ends_at: self.ends_at,
+            status: name_column("status", self.status)?,
+            paused: self.paused,
+            sealed: self.auction_type == AuctionType::SingleSealedBid.to_string(),
+            open_bidders: self.open_bidders,
+            quantity: self.quantity,
+            requires_registration: self.requires_registration,
+            deposit: self.deposit.map(|deposit| Amount::new(deposit, currency)),
+            external_reference: self.external_reference,
+            bid_count: self.bid_count,
+            high_bid: self.high_bid.map(|amount| Amount::new(amount, currency)),
+            removal,
+        })
+    }
 }
 
 #[derive(Clone)]
@@ -25,154 +438,1031 @@ impl PgAuctionRepository {
         Self { pool }
     }
 }
-fn build_auction_json_query() -> &'static str {
-    r#"
-        json_build_object(
-            'auction_id', a.id,
-            'title', a.title,
-            'starts_at', a.starts_at,
-            'expiry', a.expiry,
-            'user', a.user_id,
-            'currency', a.currency,
-            'auction_type', a.auction_type,
-            'options', a.options,
-            'expiry', a.expiry,
-            'open_bidders', a.open_bidders,
-            'bids', coalesce( (
-                SELECT json_agg(
-                    json_build_object(
-                        'id', b.id,
-                        'user', b.user_id,
-                        'amount', json_build_object(
-                            'value', b.amount_value,
-                            'currency', b.amount_currency
-                        ),
-                        'at', b.at
-                    )
-                )
-                FROM bids b
-                WHERE b.auction_id = a.id
-            ), '[]'::json)
+/// A row of `auctions`, the options still in their stored format.
+#[derive(sqlx::FromRow)]
+pub(crate) struct AuctionRow {
+    pub(crate) id: i64,
+    title: String,
+    description: Option<String>,
+    starts_at: DateTime<Utc>,
+    expiry: DateTime<Utc>,
+    user_id: String,
+    currency: String,
+    auction_type: String,
+    options: Option<Value>,
+    options_version: i32,
+    ends_at: Option<DateTime<Utc>>,
+    open_bidders: bool,
+    requires_registration: bool,
+    deposit: Option<i64>,
+    relist_duration_seconds: Option<i64>,
+    max_relists: Option<i32>,
+    relisted_from: Option<i64>,
+    relisted_as: Option<i64>,
+    relist_count: i32,
+    quantity: i32,
+    external_reference: Option<String>,
+    seller_ip_hash: Option<String>,
+    lots: Value,
+    fee_policy: Option<Value>,
+    tax_rate_bps: Option<i64>,
+    voided_at: Option<DateTime<Utc>>,
+    void_reason: Option<String>,
+    tie_break: Option<String>,
+    reserve_price: Option<i64>,
+    vickrey_pricing: Option<Value>,
+    status: String,
+    cancelled_at: Option<DateTime<Utc>>,
+    cancel_reason: Option<String>,
+    extended_at: Option<DateTime<Utc>>,
+    extension_reason: Option<String>,
+    version: i32,
+    last_bid_id: i64,
+    // Kept by the database only, read along for `SELECT *` to be checked
+    #[allow(dead_code)]
+    created_at: DateTime<Utc>,
+    #[allow(dead_code)]
+    updated_at: DateTime<Utc>,
+}
+
+/// A row of `bids`, standing, retracted or voided.
+#[derive(sqlx::FromRow)]
+struct BidRow {
+    auction_id: i64,
+    id: i64,
+    lot_id: Option<i32>,
+    user_id: String,
+    amount_value: i64,
+    amount_currency: String,
+    at: DateTime<Utc>,
+    quantity: i32,
+    metadata: Option<Value>,
+    retracted_at: Option<DateTime<Utc>>,
+    voided_at: Option<DateTime<Utc>>,
+    voided_by: Option<String>,
+    void_reason: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct MaxBidRow {
+    auction_id: i64,
+    user_id: String,
+    amount_value: i64,
+    amount_currency: String,
+    at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct PauseRow {
+    auction_id: i64,
+    paused_at: DateTime<Utc>,
+    resumed_at: Option<DateTime<Utc>>,
+}
+
+fn invalid_column(column: &str, e: impl std::fmt::Display) -> Error {
+    Error::Repository(format!("Invalid stored {}: {}", column, e))
+}
+
+fn currency_column(column: &str, code: &str) -> Result<CurrencyCode, Error> {
+    code.parse::<CurrencyCode>()
+        .map_err(|_| invalid_column(column, format!("unknown currency code {}", code)))
+}
+
+/// Enums stored by their variant name, as in `status` and `tie_break`.
+fn name_column<T: DeserializeOwned>(column: &str, name: String) -> Result<T, Error> {
+    serde_json::from_value(Value::String(name)).map_err(|e| invalid_column(column, e))
+}
+
+fn json_column<T: DeserializeOwned>(column: &str, json: Value) -> Result<T, Error> {
+    serde_json::from_value(json).map_err(|e| invalid_column(column, e))
+}
+
+/// The bids of an auction, split like [`AuctionBase`] splits them.
+#[derive(Default)]
+struct StoredBids {
+    bids: Vec<Bid>,
+    retracted_bids: Vec<RetractedBid>,
+    voided_bids: Vec<VoidedBid>,
+}
+
+impl StoredBids {
+    fn add(&mut self, row: BidRow) -> Result<(), Error> {
+        let bid = Bid {
+            id: row.id,
+            lot: row.lot_id,
+            data: BidData {
+                user: UserId::new(row.user_id),
+                amount: Amount::new(row.amount_value, currency_column("bid currency", &row.amount_currency)?),
+                at: row.at,
+                quantity: row.quantity,
+                metadata: row.metadata.map(|metadata| json_column("bid metadata", metadata)).transpose()?,
+            },
+        };
+        if let Some(retracted_at) = row.retracted_at {
+            self.retracted_bids.push(RetractedBid { bid: bid.clone(), retracted_at });
+        }
+        if let Some(voided_at) = row.voided_at {
+            let voided_by = row.voided_by.ok_or_else(|| invalid_column("voided bid", "nobody voided it"))?;
+            self.voided_bids.push(VoidedBid {
+                bid: bid.clone(),
+                voided_at,
+                voided_by: UserId::new(voided_by),
+                void_reason: row.void_reason,
+            });
+        }
+        if row.retracted_at.is_none() && row.voided_at.is_none() {
+            self.bids.push(bid);
+        }
+        Ok(())
+    }
+}
+
+impl AuctionRow {
+    fn into_auction(self, bids: StoredBids, max_bids: Vec<MaxBid>, pauses: Vec<AuctionPause>) -> Result<Auction, Error> {
+        let base = AuctionBase {
+            auction_id: AuctionId::new(self.id),
+            title: self.title,
+            description: self.description,
+            starts_at: self.starts_at,
+            expiry: self.expiry,
+            user: UserId::new(self.user_id),
+            currency: currency_column("auction currency", &self.currency)?,
+            bids: bids.bids,
+            open_bidders: self.open_bidders,
+            requires_registration: self.requires_registration,
+            deposit: self.deposit,
+            quantity: self.quantity,
+            external_reference: self.external_reference,
+            seller_ip_hash: self.seller_ip_hash,
+            lots: json_column("lots", self.lots)?,
+            fee_policy: self.fee_policy.map(|fee_policy| json_column("fee policy", fee_policy)).transpose()?,
+            tax_rate_bps: self.tax_rate_bps,
+            voided_at: self.voided_at,
+            void_reason: self.void_reason,
+            status: name_column("status", self.status)?,
+            cancelled_at: self.cancelled_at,
+            cancel_reason: self.cancel_reason,
+            retracted_bids: bids.retracted_bids,
+            voided_bids: bids.voided_bids,
+            extended_at: self.extended_at,
+            extension_reason: self.extension_reason,
+            pauses,
+            relist_policy: self.relist_duration_seconds.map(|duration_seconds| RelistPolicy {
+                duration_seconds,
+                max_relists: self.max_relists.unwrap_or_default(),
+            }),
+            relisted_from: self.relisted_from.map(AuctionId::new),
+            relisted_as: self.relisted_as.map(AuctionId::new),
+            relist_count: self.relist_count,
+            version: self.version,
+            last_bid_id: self.last_bid_id,
+        };
+        // Stored options are upgraded to the current domain model before the auction is read
+        let options = self.options.unwrap_or_default();
+        match self.auction_type.as_str() {
+            "SingleSealedBid" => Ok(Auction::SingleSealedBid {
+                base,
+                options: single_sealed_bid_options_from_storage(self.options_version, options)?,
+                tie_break: self.tie_break.map(|tie_break| name_column("tie break", tie_break)).transpose()?.unwrap_or_default(),
+                reserve_price: self.reserve_price,
+                pricing: self.vickrey_pricing.map(|pricing| json_column("Vickrey pricing", pricing)).transpose()?.unwrap_or_default(),
+            }),
+            "TimedAscending" => Ok(Auction::TimedAscending {
+                base,
+                options: timed_ascending_options_from_storage(self.options_version, options)?,
+                ends_at: self.ends_at,
+                max_bids,
+            }),
+            other => Err(invalid_column("auction type", other)),
+        }
+    }
+}
+
+/// Reads the bids, maximum bids and pauses of the auctions in one query each, and puts
+/// together the auctions in the order of the rows.
+pub(crate) async fn read_auctions(conn: &mut PgConnection, rows: Vec<AuctionRow>) -> Result<Vec<Auction>, Error> {
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+    let ids: Vec<i64> = rows.iter().map(|row| row.id).collect();
+    let (bids, max_bids) = read_bids(conn, &ids).await?;
+    read_with_bids(conn, rows, bids, max_bids).await
+}
+
+/// The standing, retracted and voided bids and the maximum bids of the auctions.
+async fn read_bids(
+    conn: &mut PgConnection,
+    ids: &[i64],
+) -> Result<(HashMap<i64, StoredBids>, HashMap<i64, Vec<MaxBid>>), Error> {
+    let bid_rows = sqlx::query_as!(
+        BidRow,
+        r#"
+        SELECT auction_id, id, lot_id, user_id, amount_value, amount_currency, at, quantity, metadata,
+            retracted_at, voided_at, voided_by, void_reason
+        FROM bids
+        WHERE auction_id = ANY($1)
+        ORDER BY auction_id, id
+    "#,
+        ids,
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| Error::Repository(e.to_string()))?;
+    let mut bids: HashMap<i64, StoredBids> = HashMap::new();
+    for row in bid_rows {
+        bids.entry(row.auction_id).or_default().add(row)?;
+    }
+
+    let max_bid_rows = sqlx::query_as!(
+        MaxBidRow,
+        r#"
+        SELECT auction_id, user_id, amount_value, amount_currency, at
+        FROM max_bids
+        WHERE auction_id = ANY($1)
+        ORDER BY auction_id, at
+    "#,
+        ids,
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| Error::Repository(e.to_string()))?;
+    let mut max_bids: HashMap<i64, Vec<MaxBid>> = HashMap::new();
+    for row in max_bid_rows {
+        max_bids.entry(row.auction_id).or_default().push(MaxBid {
+            user: UserId::new(row.user_id),
+            amount: Amount::new(row.amount_value, currency_column("maximum bid currency", &row.amount_currency)?),
+            at: row.at,
+        });
+    }
+    Ok((bids, max_bids))
+}
+
+/// The auctions of the rows with the bids read for them, none for the auctions left out.
+async fn read_with_bids(
+    conn: &mut PgConnection,
+    rows: Vec<AuctionRow>,
+    mut bids: HashMap<i64, StoredBids>,
+    mut max_bids: HashMap<i64, Vec<MaxBid>>,
+) -> Result<Vec<Auction>, Error> {
+    let ids: Vec<i64> = rows.iter().map(|row| row.id).collect();
+
+    let pause_rows = sqlx::query_as!(
+        PauseRow,
+        r#"
+        SELECT auction_id, paused_at, resumed_at
+        FROM auction_pauses
+        WHERE auction_id = ANY($1)
+        ORDER BY auction_id, paused_at
+    "#,
+        &ids,
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| Error::Repository(e.to_string()))?;
+    let mut pauses: HashMap<i64, Vec<AuctionPause>> = HashMap::new();
+    for row in pause_rows {
+        pauses.entry(row.auction_id).or_default().push(AuctionPause {
+            paused_at: row.paused_at,
+            resumed_at: row.resumed_at,
+        });
+    }
+
+    rows.into_iter()
+        .map(|row| {
+            let id = row.id;
+            row.into_auction(
+                bids.remove(&id).unwrap_or_default(),
+                max_bids.remove(&id).unwrap_or_default(),
+                pauses.remove(&id).unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+/// The aliases of the bidders of the auction, see [`Auction::bidder_aliases`].
+async fn read_bidder_aliases(conn: &mut PgConnection, auction_id: AuctionId) -> Result<HashMap<UserId, String>, Error> {
+    let bidders: Vec<String> = sqlx::query_scalar!(
+        r#"
+        SELECT user_id
+        FROM bids
+        WHERE auction_id = $1 AND retracted_at IS NULL AND voided_at IS NULL
+        GROUP BY user_id
+        ORDER BY MIN(id)
+    "#,
+        auction_id.value(),
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| Error::Repository(e.to_string()))?;
+    let bidders: Vec<UserId> = bidders.into_iter().map(UserId::new).collect();
+    Ok(bidder_aliases(&bidders))
+}
+
+pub(crate) async fn fetch_auction(
+    conn: &mut PgConnection,
+    auction_id: AuctionId,
+) -> Result<Option<Auction>, Error> {
+    let row = sqlx::query_as!(AuctionRow, "SELECT * FROM auctions WHERE id = $1", auction_id.value())
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+
+    Ok(read_auctions(conn, row.into_iter().collect()).await?.pop())
+}
+
+pub(crate) async fn insert_auction(conn: &mut PgConnection, auction: &Auction) -> Result<AuctionId, Error> {
+    let id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO auctions (
+            title, starts_at, expiry, user_id, currency, 
+            auction_type, options, ends_at, open_bidders, quantity,
+            external_reference, description, tie_break, status, reserve_price,
+            requires_registration, deposit, options_version, relist_duration_seconds, max_relists,
+            relisted_from, relist_count, seller_ip_hash, lots, fee_policy, tax_rate_bps, vickrey_pricing
+        ) 
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20,
+            $21, $22, $23, $24, $25, $26, $27)
+        RETURNING id
+    "#,
+        auction.title(),
+        auction.starts_at(),
+        auction.expiry(),
+        auction.user().value(),
+        auction.currency().to_string(),
+        auction.auction_type().to_string(),
+        options_for_storage(auction),
+        match auction {
+            Auction::TimedAscending { ends_at, .. } => *ends_at,
+            _ => None,
+        },
+        auction.open_bidders(),
+        auction.quantity(),
+        auction.external_reference(),
+        auction.description(),
+        match auction {
+            Auction::SingleSealedBid { tie_break, .. } => Some(format!("{:?}", tie_break)),
+            _ => None,
+        },
+        auction.status().to_string(),
+        match auction {
+            Auction::SingleSealedBid { reserve_price, .. } => *reserve_price,
+            _ => None,
+        },
+        auction.requires_registration(),
+        auction.deposit().map(|deposit| deposit.value()),
+        OPTIONS_VERSION,
+        auction.relist_policy().map(|policy| policy.duration_seconds),
+        auction.relist_policy().map(|policy| policy.max_relists),
+        auction.relisted_from().map(|id| id.value()),
+        auction.relist_count(),
+        auction.seller_ip_hash(),
+        serde_json::to_value(auction.lots()).unwrap_or_default(),
+        auction.fee_policy().map(serde_json::to_value).transpose().unwrap_or_default(),
+        auction.tax_rate_bps(),
+        match auction {
+            Auction::SingleSealedBid { pricing, .. } => serde_json::to_value(pricing).ok(),
+            _ => None,
+        },
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| match e.as_database_error() {
+        // Only the external reference is unique besides the generated id
+        Some(db_error) if db_error.is_unique_violation() => {
+            Error::Validation(Errors::AuctionAlreadyExists)
+        }
+        _ => Error::Repository(e.to_string()),
+    })?;
+
+    Ok(AuctionId::new(id))
+}
+
+pub(crate) async fn insert_bid(
+    conn: &mut PgConnection,
+    auction_id: AuctionId,
+    bid: &Bid,
+) -> Result<(), Error> {
+    let metadata = bid
+        .data
+        .metadata
+        .as_ref()
+        .map(serde_json::to_value)
+        .transpose()
+        .map_err(|e| {
+            Error::Repository(format!(
+                "insert_bid: Failed to serialize bid metadata: {}",
+                e
+            ))
+        })?;
+    sqlx::query!(
+        r#"
+        INSERT INTO bids (
+            auction_id, id, at, amount_value, amount_currency, user_id, quantity, metadata, lot_id
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+    "#,
+        auction_id.value(),
+        bid.id,
+        bid.at(),
+        bid.amount().value(),
+        bid.amount().currency().to_string(),
+        bid.user().value(),
+        bid.quantity(),
+        metadata,
+        bid.lot,
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| Error::Repository(e.to_string()))?;
+    Ok(())
+}
+
+async fn retract_bid(
+    conn: &mut PgConnection,
+    auction_id: AuctionId,
+    retracted: &RetractedBid,
+) -> Result<(), Error> {
+    sqlx::query!(
+        "UPDATE bids SET retracted_at = $3 WHERE auction_id = $1 AND id = $2",
+        auction_id.value(),
+        retracted.bid.id,
+        retracted.retracted_at,
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| Error::Repository(e.to_string()))?;
+    Ok(())
+}
+
+async fn void_bid(
+    conn: &mut PgConnection,
+    auction_id: AuctionId,
+    voided: &VoidedBid,
+) -> Result<(), Error> {
+    sqlx::query!(
+        "UPDATE bids SET voided_at = $3, voided_by = $4, void_reason = $5 WHERE auction_id = $1 AND id = $2",
+        auction_id.value(),
+        voided.bid.id,
+        voided.voided_at,
+        voided.voided_by.value(),
+        voided.void_reason.as_deref(),
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| Error::Repository(e.to_string()))?;
+    Ok(())
+}
+
+/// Stores the current maximum bid of each bidder; max bids are never shown as bids.
+pub(crate) async fn upsert_max_bids(
+    conn: &mut PgConnection,
+    auction_id: AuctionId,
+    max_bids: &[MaxBid],
+) -> Result<(), Error> {
+    // Maximum bids withdrawn together with a retracted bid
+    let users: Vec<String> = max_bids.iter().map(|m| m.user.value().to_string()).collect();
+    sqlx::query!(
+        "DELETE FROM max_bids WHERE auction_id = $1 AND NOT (user_id = ANY($2))",
+        auction_id.value(),
+        &users,
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| Error::Repository(e.to_string()))?;
+    for max_bid in max_bids {
+        sqlx::query!(
+            r#"
+            INSERT INTO max_bids (auction_id, user_id, amount_value, amount_currency, at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (auction_id, user_id) DO UPDATE
+            SET amount_value = EXCLUDED.amount_value,
+                amount_currency = EXCLUDED.amount_currency,
+                at = EXCLUDED.at
+        "#,
+            auction_id.value(),
+            max_bid.user.value(),
+            max_bid.amount.value(),
+            max_bid.amount.currency().to_string(),
+            max_bid.at,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+    }
+    Ok(())
+}
+
+async fn upsert_pauses(
+    conn: &mut PgConnection,
+    auction_id: AuctionId,
+    pauses: &[AuctionPause],
+) -> Result<(), Error> {
+    for pause in pauses {
+        sqlx::query!(
+            r#"
+            INSERT INTO auction_pauses (auction_id, paused_at, resumed_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (auction_id, paused_at) DO UPDATE
+            SET resumed_at = EXCLUDED.resumed_at
+        "#,
+            auction_id.value(),
+            pause.paused_at,
+            pause.resumed_at,
         )
-    "#
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+    }
+    Ok(())
 }
+
+/// Stores what changed from `existing` to `auction` within the transaction, as long as the
+/// stored auction is still at the version of `existing`. Returns the new version.
+async fn store_changes(conn: &mut PgConnection, existing: &Auction, auction: &Auction) -> Result<i32, Error> {
+    // Only updates the auction at the version it was read at
+    let version = sqlx::query_scalar!(
+        r#"
+        UPDATE auctions
+        SET expiry = $2, ends_at = $3, voided_at = $4, void_reason = $5,
+            title = $6, description = $7, status = $8, cancelled_at = $9, cancel_reason = $10,
+            extended_at = $11, extension_reason = $12, relisted_as = $13, version = version + 1,
+            last_bid_id = GREATEST(last_bid_id, $15)
+        WHERE id = $1 AND version = $14
+        RETURNING version
+    "#,
+        auction.auction_id().value(),
+        auction.expiry(),
+        match auction {
+            Auction::TimedAscending { ends_at, .. } => *ends_at,
+            _ => None,
+        },
+        auction.voided_at(),
+        auction.void_reason(),
+        auction.title(),
+        auction.description(),
+        auction.status().to_string(),
+        auction.cancelled_at(),
+        auction.cancel_reason(),
+        auction.extended_at(),
+        auction.extension_reason(),
+        auction.relisted_as().map(|id| id.value()),
+        existing.version(),
+        auction.last_bid_id(),
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| Error::Repository(e.to_string()))?
+    .ok_or_else(|| changed_since_read(auction.auction_id()))?;
+    let existing_ids: HashSet<_> = existing.bids().iter().map(|b| b.id).collect();
+    let incoming_ids: HashSet<_> = auction.bids().iter().map(|b| b.id).collect();
+    let to_delete: Vec<_> = existing_ids.difference(&incoming_ids).collect();
+    log::info!("to_delete {:#?}", to_delete);
+    let mut to_add: Vec<_> = incoming_ids.difference(&existing_ids).collect();
+    // Bid events go to the outbox in the order the bids were placed
+    to_add.sort();
+    log::info!("to_add {:#?}", to_add);
+    // Bids only leave an auction by being retracted or voided, and stay stored as such
+    for &bid_id in to_delete {
+        if let Some(retracted) = auction.retracted_bids().iter().find(|r| r.bid.id == bid_id) {
+            retract_bid(&mut *conn, auction.auction_id(), retracted).await?;
+            append_event(&mut *conn, auction.auction_id(), AuctionEventType::BidRetracted, bid_payload(&retracted.bid)).await?;
+        } else {
+            let voided = auction
+                .voided_bids()
+                .iter()
+                .find(|v| v.bid.id == bid_id)
+                .ok_or_else(|| Error::Internal("Should not be able to delete bids".to_string()))?;
+            void_bid(&mut *conn, auction.auction_id(), voided).await?;
+            append_event(&mut *conn, auction.auction_id(), AuctionEventType::BidVoided, bid_payload(&voided.bid)).await?;
+        }
+    }
+    for &bid_id in to_add {
+        let bid = auction.bids().iter().find(|b| b.id == bid_id).unwrap();
+        insert_bid(&mut *conn, auction.auction_id(), bid).await?;
+        append_event(&mut *conn, auction.auction_id(), AuctionEventType::BidAccepted, bid_payload(bid)).await?;
+    }
+    if existing.pauses() != auction.pauses() {
+        upsert_pauses(&mut *conn, auction.auction_id(), auction.pauses()).await?;
+    }
+    // Extensions, pauses and voided bids are announced too, so that watchers pick up the new expiry
+    if existing.status() != auction.status()
+        || existing.extended_at() != auction.extended_at()
+        || existing.pauses() != auction.pauses()
+        || existing.voided_bids().len() != auction.voided_bids().len()
+    {
+        append_event(&mut *conn, auction.auction_id(), AuctionEventType::AuctionUpdated, auction_payload(auction)).await?;
+    }
+    if let Auction::TimedAscending { max_bids, .. } = auction {
+        upsert_max_bids(&mut *conn, auction.auction_id(), max_bids).await?;
+    }
+    Ok(version)
+}
+
 #[async_trait]
 impl AuctionRepository for PgAuctionRepository {
     async fn get_auction(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
-        let query = format!(
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        match fetch_auction(&mut conn, auction_id).await? {
+            Some(auction) => Ok(Some(auction)),
+            None => Ok(fetch_archived_auctions(&mut conn, &[auction_id.value()]).await?.pop()),
+        }
+    }
+
+    async fn get_auction_without_bids(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        let row = sqlx::query_as!(AuctionRow, "SELECT * FROM auctions WHERE id = $1", auction_id.value())
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        match row {
+            Some(row) => Ok(read_with_bids(&mut conn, vec![row], HashMap::new(), HashMap::new()).await?.pop()),
+            // Archived auctions are kept whole
+            None => Ok(fetch_archived_auctions(&mut conn, &[auction_id.value()]).await?.pop()),
+        }
+    }
+
+    async fn get_auction_standings(&self, auction_id: AuctionId) -> Result<Option<AuctionStandings>, Error> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        let Some(row) = sqlx::query_as!(AuctionRow, "SELECT * FROM auctions WHERE id = $1", auction_id.value())
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?
+        else {
+            // Archived auctions are kept whole
+            return Ok(fetch_archived_auctions(&mut conn, &[auction_id.value()])
+                .await?
+                .pop()
+                .map(AuctionStandings::of));
+        };
+        // The latest bid of each bidder and the highest bid, on the auction and on each lot
+        let rows = sqlx::query_as!(
+            BidRow,
+            r#"
+            SELECT auction_id, id, lot_id, user_id, amount_value, amount_currency, at, quantity, metadata,
+                retracted_at, voided_at, voided_by, void_reason
+            FROM bids
+            WHERE auction_id = $1
+                AND (id IN (
+                    SELECT DISTINCT ON (lot_id, user_id) id
+                    FROM bids
+                    WHERE auction_id = $1 AND retracted_at IS NULL AND voided_at IS NULL
+                    ORDER BY lot_id, user_id, id DESC
+                ) OR id IN (
+                    SELECT DISTINCT ON (lot_id) id
+                    FROM bids
+                    WHERE auction_id = $1 AND retracted_at IS NULL AND voided_at IS NULL
+                    ORDER BY lot_id, amount_value DESC, id DESC
+                ))
+            ORDER BY id
+        "#,
+            auction_id.value(),
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        let mut stored = StoredBids::default();
+        for row in rows {
+            stored.add(row)?;
+        }
+        let lot_counts = sqlx::query!(
+            r#"
+            SELECT lot_id AS "lot_id!", COUNT(*) AS "bid_count!"
+            FROM bids
+            WHERE auction_id = $1 AND lot_id IS NOT NULL AND retracted_at IS NULL AND voided_at IS NULL
+            GROUP BY lot_id
+        "#,
+            auction_id.value(),
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        let bidder_aliases = read_bidder_aliases(&mut conn, auction_id).await?;
+        let auction = read_with_bids(&mut conn, vec![row], HashMap::from([(auction_id.value(), stored)]), HashMap::new())
+            .await?
+            .pop();
+        Ok(auction.map(|auction| AuctionStandings {
+            auction,
+            lot_bid_counts: lot_counts.into_iter().map(|row| (row.lot_id, row.bid_count)).collect(),
+            bidder_aliases,
+        }))
+    }
+
+    async fn get_auction_summary(&self, auction_id: AuctionId) -> Result<Option<AuctionSummary>, Error> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        let row = sqlx::query_as!(
+            AuctionSummaryRow,
             r#"
-            SELECT {} as auction
+            SELECT a.id, a.title, a.user_id, a.currency, a.starts_at, a.expiry,
+                coalesce(a.ends_at, a.expiry) AS "ends_at!", a.status, a.auction_type, a.open_bidders,
+                a.quantity, a.requires_registration, a.deposit, a.external_reference,
+                a.voided_at, a.void_reason, a.cancelled_at, a.cancel_reason,
+                EXISTS (SELECT 1 FROM auction_pauses p WHERE p.auction_id = a.id AND p.resumed_at IS NULL) AS "paused!",
+                standing.bid_count AS "bid_count!", standing.high_bid
             FROM auctions a
+            CROSS JOIN LATERAL (
+                SELECT COUNT(*) AS bid_count, MAX(b.amount_value) AS high_bid
+                FROM bids b
+                WHERE b.auction_id = a.id AND b.retracted_at IS NULL AND b.voided_at IS NULL
+            ) standing
             WHERE a.id = $1
         "#,
-            build_auction_json_query()
-        );
+            auction_id.value(),
+        )
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        match row {
+            Some(row) => row.into_summary().map(Some),
+            None => Ok(fetch_archived_auctions(&mut conn, &[auction_id.value()])
+                .await?
+                .first()
+                .map(AuctionSummary::of)),
+        }
+    }
 
-        // Note: In a real implementation, we'd handle the complex JSON deserialization
-        // This is just a skeleton - real implementation would use proper row mapping
-        let result = sqlx::query_scalar::<_, serde_json::Value>(&query)
-            .bind(auction_id.value())
-            .fetch_optional(&self.pool)
+    async fn get_auctions(&self) -> Result<Vec<Auction>, Error> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        let rows = sqlx::query_as!(AuctionRow, "SELECT * FROM auctions ORDER BY id")
+            .fetch_all(&mut *conn)
             .await
             .map_err(|e| Error::Repository(e.to_string()))?;
 
-        match result {
-            Some(json) => {
-                log::info!("Auction from db {}", json);
-                let auction = serde_json::from_value(json).map_err(|e| {
-                    Error::Repository(format!("get_auction: Failed to deserialize auction: {}", e))
-                })?;
-                Ok(Some(auction))
-            }
-            None => Ok(None),
-        }
+        read_auctions(&mut conn, rows).await
     }
 
-    async fn get_auctions(&self) -> Result<Vec<Auction>, Error> {
-        let query = format!(
+    async fn get_auctions_page(&self, limit: i64, offset: i64, filter: &AuctionFilter) -> Result<AuctionPage, Error> {
+        let total = self.count_auctions(filter).await?;
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        let rows = sqlx::query_as!(
+            AuctionRow,
+            r#"
+            SELECT *
+            FROM auctions
+            WHERE ($1::TEXT IS NULL OR user_id = $1)
+                AND ($2::TEXT IS NULL OR status = $2)
+                AND ($3::TEXT IS NULL OR status <> $3)
+            ORDER BY id
+            LIMIT $4 OFFSET $5
+        "#,
+            filter.seller.as_ref().map(|seller| seller.value()),
+            filter.status.map(|status| status.to_string()),
+            filter.hidden_status(),
+            limit,
+            offset,
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+
+        Ok(AuctionPage::new(read_auctions(&mut conn, rows).await?, total, offset))
+    }
+
+    async fn get_auction_summaries(
+        &self,
+        limit: i64,
+        offset: i64,
+        filter: &AuctionFilter,
+    ) -> Result<AuctionPage<AuctionSummary>, Error> {
+        let total = self.count_auctions(filter).await?;
+        let rows = sqlx::query_as!(
+            AuctionSummaryRow,
             r#"
-            SELECT json_agg(
-                {}
-            ) as auctions
+            SELECT a.id, a.title, a.user_id, a.currency, a.starts_at, a.expiry,
+                coalesce(a.ends_at, a.expiry) AS "ends_at!", a.status, a.auction_type, a.open_bidders,
+                a.quantity, a.requires_registration, a.deposit, a.external_reference,
+                a.voided_at, a.void_reason, a.cancelled_at, a.cancel_reason,
+                EXISTS (SELECT 1 FROM auction_pauses p WHERE p.auction_id = a.id AND p.resumed_at IS NULL) AS "paused!",
+                standing.bid_count AS "bid_count!", standing.high_bid
             FROM auctions a
+            CROSS JOIN LATERAL (
+                SELECT COUNT(*) AS bid_count, MAX(b.amount_value) AS high_bid
+                FROM bids b
+                WHERE b.auction_id = a.id AND b.retracted_at IS NULL AND b.voided_at IS NULL
+            ) standing
+            WHERE ($1::TEXT IS NULL OR a.user_id = $1)
+                AND ($2::TEXT IS NULL OR a.status = $2)
+                AND ($3::TEXT IS NULL OR a.status <> $3)
+            ORDER BY a.id
+            LIMIT $4 OFFSET $5
         "#,
-            build_auction_json_query()
-        );
+            filter.seller.as_ref().map(|seller| seller.value()),
+            filter.status.map(|status| status.to_string()),
+            filter.hidden_status(),
+            limit,
+            offset,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+
+        let summaries = rows.into_iter().map(AuctionSummaryRow::into_summary).collect::<Result<_, _>>()?;
+        Ok(AuctionPage::new(summaries, total, offset))
+    }
 
-        let result = sqlx::query_scalar::<_, Option<serde_json::Value>>(&query)
-            .fetch_one(&self.pool)
+    async fn get_active_auctions(&self, now: DateTime<Utc>) -> Result<Vec<Auction>, Error> {
+        let mut conn = self
+            .pool
+            .acquire()
             .await
             .map_err(|e| Error::Repository(e.to_string()))?;
+        // Auctions stay open past their end while paused
+        let rows = sqlx::query_as!(
+            AuctionRow,
+            r#"
+            SELECT a.*
+            FROM auctions a
+            WHERE a.status IN ($2, $3)
+                AND a.starts_at <= $1
+                AND (coalesce(a.ends_at, a.expiry) >= $1
+                    OR EXISTS (SELECT 1 FROM auction_pauses p WHERE p.auction_id = a.id AND p.resumed_at IS NULL))
+            ORDER BY a.id
+        "#,
+            now,
+            AuctionStatus::Scheduled.to_string(),
+            AuctionStatus::Open.to_string(),
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+
+        // Bought now auctions have ended before their end
+        let mut auctions = read_auctions(&mut conn, rows).await?;
+        auctions.retain(|auction| auction.is_active(now));
+        Ok(auctions)
+    }
 
-        match result {
-            Some(json) => {
-                let auctions = serde_json::from_value(json).map_err(|e| {
-                    Error::Repository(format!(
-                        "get_auctions: Failed to deserialize auctions: {}",
-                        e
-                    ))
-                })?;
-                Ok(auctions)
+    async fn get_bids_page(&self, auction_id: AuctionId, after_bid_id: Option<i64>, limit: i64) -> Result<BidPage, Error> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        // Reading one more than the page tells whether there is a next page
+        let rows = sqlx::query_as!(
+            BidRow,
+            r#"
+            SELECT auction_id, id, lot_id, user_id, amount_value, amount_currency, at, quantity, metadata,
+                retracted_at, voided_at, voided_by, void_reason
+            FROM bids
+            WHERE auction_id = $1 AND ($2::BIGINT IS NULL OR id < $2)
+                AND retracted_at IS NULL AND voided_at IS NULL
+            ORDER BY id DESC
+            LIMIT $3
+        "#,
+            auction_id.value(),
+            after_bid_id,
+            limit.max(1) + 1,
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        if rows.is_empty() {
+            // The bids of archived auctions are kept with the auction, which is only read when
+            // the auction was archived rather than out of bids
+            if !is_archived(&mut conn, auction_id).await? {
+                return Ok(BidPage::default());
             }
-            None => Ok(Vec::new()),
+            return Ok(fetch_archived_auctions(&mut conn, &[auction_id.value()])
+                .await?
+                .pop()
+                .map(|auction| BidPage::of(&auction, after_bid_id, limit))
+                .unwrap_or_default());
         }
+        let mut stored = StoredBids::default();
+        for row in rows {
+            stored.add(row)?;
+        }
+        // Bidders are aliased in the order of their first bid, on any page
+        let aliases = read_bidder_aliases(&mut conn, auction_id).await?;
+        Ok(BidPage::new(stored.bids, limit, aliases))
     }
 
-    async fn create_auction(&self, auction: Auction) -> Result<Auction, Error> {
-        // Start a transaction
-        let mut tx = self
+    async fn get_auctions_by_seller(&self, seller: &UserId) -> Result<Vec<Auction>, Error> {
+        let mut conn = self
             .pool
-            .begin()
+            .acquire()
             .await
             .map_err(|e| Error::Repository(e.to_string()))?;
+        let rows = sqlx::query_as!(
+            AuctionRow,
+            "SELECT * FROM auctions WHERE user_id = $1 ORDER BY id",
+            seller.value(),
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
 
-        // Insert the auction
-        let auction_json = serde_json::to_value(&auction) // TODO: there must be a better way
-            .map_err(|e| {
-                Error::Repository(format!(
-                    "create_auction: Failed to serialize auction: {}",
-                    e
-                ))
-            })?;
+        read_auctions(&mut conn, rows).await
+    }
 
-        let id = sqlx::query_scalar::<_, i64>(
+    async fn get_auctions_by_ids(&self, auction_ids: &[AuctionId]) -> Result<Vec<Auction>, Error> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        let ids: Vec<i64> = auction_ids.iter().map(|id| id.value()).collect();
+        let rows = sqlx::query_as!(
+            AuctionRow,
             r#"
-            INSERT INTO auctions (
-                title, starts_at, expiry, user_id, currency, 
-                auction_type, options, ends_at, open_bidders
-            ) 
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-            RETURNING id
+            SELECT a.*
+            FROM unnest($1::BIGINT[]) WITH ORDINALITY AS requested(id, position)
+            JOIN auctions a ON a.id = requested.id
+            ORDER BY requested.position
         "#,
+            &ids,
         )
-        .bind(auction.title())
-        .bind(auction.starts_at())
-        .bind(auction.expiry())
-        .bind(auction.user().value())
-        .bind(auction.currency().to_string())
-        .bind(auction.auction_type().to_string())
-        .bind(
-            auction_json
-                .get("options")
-                .unwrap_or(&serde_json::Value::Null),
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+
+        let found = rows.len();
+        let auctions = read_auctions(&mut conn, rows).await?;
+        if found == ids.len() {
+            return Ok(auctions);
+        }
+        // The rest may have been archived
+        let missing: Vec<i64> = ids
+            .iter()
+            .copied()
+            .filter(|id| !auctions.iter().any(|auction| auction.auction_id().value() == *id))
+            .collect();
+        let by_id: HashMap<AuctionId, Auction> = auctions
+            .into_iter()
+            .chain(fetch_archived_auctions(&mut conn, &missing).await?)
+            .map(|auction| (auction.auction_id(), auction))
+            .collect();
+        Ok(auction_ids.iter().filter_map(|id| by_id.get(id).cloned()).collect())
+    }
+
+    async fn get_auction_by_external_reference(
+        &self,
+        user: &UserId,
+        external_reference: &str,
+    ) -> Result<Option<Auction>, Error> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        let row = sqlx::query_as!(
+            AuctionRow,
+            "SELECT * FROM auctions WHERE user_id = $1 AND external_reference = $2",
+            user.value(),
+            external_reference,
         )
-        .bind(match &auction {
-            Auction::TimedAscending { ends_at, .. } => ends_at.clone(),
-            _ => None,
-        })
-        .bind(auction.open_bidders())
-        .fetch_one(&mut *tx)
+        .fetch_optional(&mut *conn)
         .await
         .map_err(|e| Error::Repository(e.to_string()))?;
 
+        Ok(read_auctions(&mut conn, row.into_iter().collect()).await?.pop())
+    }
+
+    async fn create_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        // Start a transaction
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+
+        // Insert the auction
+        let id = insert_auction(&mut tx, &auction).await?;
+        append_event(&mut tx, id, AuctionEventType::AuctionCreated, auction_payload(&auction)).await?;
+
         // Commit the transaction
         tx.commit()
             .await
             .map_err(|e| Error::Repository(e.to_string()))?;
 
-        // Return the auction with the assigned ID
+        // Return the auction with the assigned ID, at the version of a new row
         let mut new_auction = auction;
-        new_auction.set_auction_id(AuctionId::new(id));
+        new_auction.set_auction_id(id);
+        new_auction.set_version(0);
 
         Ok(new_auction)
     }
@@ -183,68 +1473,198 @@ impl AuctionRepository for PgAuctionRepository {
             .begin()
             .await
             .map_err(|e| Error::Repository(e.to_string()))?;
-        fn not_found(auction_id: AuctionId) -> Error {
-            Error::NotFound(format!("Auction with ID {} not found", auction_id))
+        let Some(existing) = fetch_auction(&mut tx, auction.auction_id()).await? else {
+            if is_archived(&mut tx, auction.auction_id()).await? {
+                return Err(Error::Conflict(format!("Auction with ID {} is archived", auction.auction_id())));
+            }
+            return Err(Error::NotFound(format!("Auction with ID {} not found", auction.auction_id())));
+        };
+        if existing.version() != auction.version() {
+            return Err(changed_since_read(auction.auction_id()));
+        }
+        if existing.status() != auction.status() && !existing.status().can_transition_to(auction.status()) {
+            return Err(Error::Validation(Errors::IllegalStatusTransition));
+        }
+        let version = store_changes(&mut tx, &existing, &auction).await?;
+        tx.commit()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+
+        let mut auction = auction;
+        auction.set_version(version);
+        Ok(auction)
+    }
+
+    async fn place_bid(&self, auction_id: AuctionId, place: PlaceBid) -> Result<Vec<DomainEvent>, Error> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        // Other bids on the auction wait for this one to be stored before they read it
+        let locked = sqlx::query_scalar!("SELECT id FROM auctions WHERE id = $1 FOR UPDATE", auction_id.value())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        if locked.is_none() {
+            return Err(Error::NotFound(format!("Auction with ID {} not found", auction_id)));
         }
-        let auction_from_db = self
-            .get_auction(auction.auction_id())
+        let existing = fetch_auction(&mut tx, auction_id)
             .await?
-            .ok_or(not_found(auction.auction_id()))?;
-        let updated = sqlx::query(
+            .ok_or_else(|| Error::NotFound(format!("Auction with ID {} not found", auction_id)))?;
+        let mut auction = existing.clone();
+        let events = place(&mut auction).map_err(Error::Validation)?;
+        store_changes(&mut tx, &existing, &auction).await?;
+        tx.commit()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(events)
+    }
+
+    async fn add_bid(
+        &self,
+        auction_id: AuctionId,
+        version: i32,
+        bid: &Bid,
+        ends_at: Option<DateTime<Utc>>,
+    ) -> Result<(), Error> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        let updated = sqlx::query!(
             r#"
             UPDATE auctions
-            SET expiry = $2
-            WHERE id = $1
+            SET ends_at = coalesce($3, ends_at), version = version + 1, last_bid_id = GREATEST(last_bid_id, $4)
+            WHERE id = $1 AND version = $2
         "#,
+            auction_id.value(),
+            version,
+            ends_at,
+            bid.id,
         )
-        .bind(auction.auction_id().value())
-        .bind(auction.expiry())
         .execute(&mut *tx)
         .await
         .map_err(|e| Error::Repository(e.to_string()))?;
-
-        // Check if the auction was updated
         if updated.rows_affected() == 0 {
-            return Err(not_found(auction.auction_id()));
+            return Err(if self.auction_exists(auction_id).await? {
+                changed_since_read(auction_id)
+            } else {
+                Error::NotFound(format!("Auction with ID {} not found", auction_id))
+            });
         }
-        let existing_ids: HashSet<_> = auction_from_db.bids().iter().map(|b| b.id).collect();
-        let incoming_ids: HashSet<_> = auction.bids().iter().map(|b| b.id).collect();
-        let to_delete: Vec<_> = existing_ids.difference(&incoming_ids).collect();
-        log::info!("to_delete {:#?}", to_delete);
-        let to_add: Vec<_> = incoming_ids.difference(&existing_ids).collect();
-        log::info!("to_add {:#?}", to_add);
-        if !to_delete.is_empty() {
-            return Err(Error::Internal(
-                "Should not be able to delete bids".to_string(),
-            ));
-        }
-        for &bid_id in to_add {
-            let bid = auction.bids().iter().find(|b| b.id == bid_id).unwrap();
-            sqlx::query(
-                r#"
-            INSERT INTO bids (
-                auction_id, id, at, amount_value, amount_currency, user_id
-            )
-            VALUES ($1, $2, $3, $4, $5, $6)
+        insert_bid(&mut tx, auction_id, bid).await?;
+        append_event(&mut tx, auction_id, AuctionEventType::BidAccepted, bid_payload(bid)).await?;
+        tx.commit()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn auction_exists(&self, auction_id: AuctionId) -> Result<bool, Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (SELECT 1 FROM auctions WHERE id = $1)
+                OR EXISTS (SELECT 1 FROM auctions_archive WHERE id = $1) AS "exists!"
         "#,
-            )
-            .bind(auction.auction_id().value())
-            .bind(bid.id)
-            .bind(bid.at())
-            .bind(bid.amount().value())
-            .bind(bid.amount().currency().to_string())
-            .bind(bid.user().value())
-            .execute(&mut *tx)
+            auction_id.value(),
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))
+    }
+
+    async fn count_auctions(&self, filter: &AuctionFilter) -> Result<i64, Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM auctions
+            WHERE ($1::TEXT IS NULL OR user_id = $1)
+                AND ($2::TEXT IS NULL OR status = $2)
+                AND ($3::TEXT IS NULL OR status <> $3)
+        "#,
+            filter.seller.as_ref().map(|seller| seller.value()),
+            filter.status.map(|status| status.to_string()),
+            filter.hidden_status(),
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))
+    }
+
+    async fn count_bids(&self, auction_id: AuctionId) -> Result<i64, Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM bids WHERE auction_id = $1 AND retracted_at IS NULL AND voided_at IS NULL"#,
+            auction_id.value(),
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))
+    }
+
+    async fn anonymize_user(&self, user: &UserId, pseudonym: &UserId) -> Result<Vec<AuctionId>, Error> {
+        let mut tx = self
+            .pool
+            .begin()
             .await
             .map_err(|e| Error::Repository(e.to_string()))?;
+        let auction_ids = sqlx::query_scalar!(
+            r#"
+            SELECT id AS "id!" FROM auctions WHERE user_id = $1
+            UNION SELECT auction_id FROM bids WHERE user_id = $1 OR voided_by = $1
+            UNION SELECT auction_id FROM max_bids WHERE user_id = $1
+            ORDER BY 1
+        "#,
+            user.value(),
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        // Where the user bid and sold from goes, the rest of what they did stays under the pseudonym
+        let (user_id, pseudonym_id) = (user.value(), pseudonym.value());
+        let statements = [
+            sqlx::query!(
+                "UPDATE auctions SET user_id = $2, seller_ip_hash = NULL WHERE user_id = $1",
+                user_id,
+                pseudonym_id,
+            ),
+            sqlx::query!(
+                "UPDATE bids SET user_id = $2, metadata = metadata - 'user_agent' - 'ip_hash' WHERE user_id = $1",
+                user_id,
+                pseudonym_id,
+            ),
+            sqlx::query!("UPDATE bids SET voided_by = $2 WHERE voided_by = $1", user_id, pseudonym_id),
+            sqlx::query!("UPDATE max_bids SET user_id = $2 WHERE user_id = $1", user_id, pseudonym_id),
+            sqlx::query!("UPDATE auction_registrations SET user_id = $2 WHERE user_id = $1", user_id, pseudonym_id),
+            sqlx::query!("UPDATE auction_deposits SET user_id = $2 WHERE user_id = $1", user_id, pseudonym_id),
+            sqlx::query!("UPDATE auction_results SET winner = $2 WHERE winner = $1", user_id, pseudonym_id),
+            sqlx::query!("UPDATE auction_audit_log SET user_id = $2 WHERE user_id = $1", user_id, pseudonym_id),
+            sqlx::query!(
+                "UPDATE auction_events SET payload = jsonb_set(payload, '{bidder}', to_jsonb($2::TEXT)) WHERE payload->>'bidder' = $1",
+                user_id,
+                pseudonym_id,
+            ),
+            // Settlements name bidders wherever they appear in the document
+            sqlx::query!(
+                r#"UPDATE settlements
+                   SET settlement = replace(settlement::TEXT, to_jsonb($1::TEXT)::TEXT, to_jsonb($2::TEXT)::TEXT)::JSONB
+                   WHERE strpos(settlement::TEXT, to_jsonb($1::TEXT)::TEXT) > 0"#,
+                user_id,
+                pseudonym_id,
+            ),
+        ];
+        for statement in statements {
+            statement
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::Repository(e.to_string()))?;
         }
-
-        // Commit the transaction
+        let archived = anonymize_archived_user(&mut tx, user, pseudonym).await?;
         tx.commit()
             .await
             .map_err(|e| Error::Repository(e.to_string()))?;
-
-        Ok(auction)
+        Ok(auction_ids.into_iter().map(AuctionId::new).chain(archived).collect())
     }
 }
 
@@ -257,6 +1677,7 @@ mod repository_tests {
     use crate::domain::commands::CreateAuctionCommand;
     use crate::domain::models::{Amount, AuctionFactory, BidData, CurrencyCode, UserId};
     use crate::infrastructure::run_migrations;
+    use crate::infrastructure::data::auction_repository_conformance::check_auction_repository;
 
     fn starts_at() -> DateTime<Utc> {
         Utc.with_ymd_and_hms(2016, 1, 1, 0, 0, 0).unwrap()
@@ -264,6 +1685,38 @@ mod repository_tests {
     fn ends_at() -> DateTime<Utc> {
         Utc.with_ymd_and_hms(2016, 2, 1, 0, 0, 0).unwrap()
     }
+    fn bid_row(id: i64) -> BidRow {
+        BidRow {
+            auction_id: 1,
+            id,
+            lot_id: None,
+            user_id: "buyer".to_string(),
+            amount_value: 100,
+            amount_currency: "SEK".to_string(),
+            at: starts_at(),
+            quantity: 1,
+            metadata: None,
+            retracted_at: None,
+            voided_at: None,
+            voided_by: None,
+            void_reason: None,
+        }
+    }
+
+    #[test]
+    fn test_bid_rows_are_split_into_standing_retracted_and_voided_bids() {
+        let mut bids = StoredBids::default();
+        bids.add(bid_row(1)).unwrap();
+        bids.add(BidRow { retracted_at: Some(starts_at()), ..bid_row(2) }).unwrap();
+        bids.add(BidRow { voided_at: Some(starts_at()), voided_by: Some("support".to_string()), ..bid_row(3) }).unwrap();
+
+        assert_eq!(bids.bids.iter().map(|bid| bid.id).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(bids.retracted_bids.iter().map(|retracted| retracted.bid.id).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(bids.voided_bids.iter().map(|voided| voided.bid.id).collect::<Vec<_>>(), vec![3]);
+        assert_eq!(bids.voided_bids[0].voided_by, UserId::new("support"));
+        assert!(StoredBids::default().add(BidRow { voided_at: Some(starts_at()), ..bid_row(4) }).is_err());
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn test_with_postgres() {
         env_logger::init_from_env(env_logger::Env::default().default_filter_or("info"));
@@ -272,6 +1725,15 @@ mod repository_tests {
         let host_ip = container.get_host().await.unwrap();
         let host_port = container.get_host_port_ipv4(5432).await.unwrap();
 
+        // The behaviour shared with the other repositories, on a database of its own as the checks
+        // start from an empty repository
+        let url = format!("postgresql://postgres:postgres@{}:{}", host_ip, host_port);
+        let pool = PgPool::connect(&format!("{}/postgres", url)).await.unwrap();
+        sqlx::query("CREATE DATABASE conformance").execute(&pool).await.unwrap();
+        let conformance_pool = PgPool::connect(&format!("{}/conformance", url)).await.unwrap();
+        run_migrations(&conformance_pool).await.unwrap();
+        check_auction_repository(&PgAuctionRepository::new(conformance_pool)).await;
+
         fn match_auction(auction: &Auction) {
             assert_eq!(starts_at(), auction.starts_at(), "starts_at should match");
             assert_eq!(ends_at(), auction.expiry(), "ends_at should match");
@@ -301,9 +1763,10 @@ mod repository_tests {
                             currency: CurrencyCode::SEK,
                             min_raise: Some(10),
                             reserve_price: Some(100),
-                            time_frame: None,
-                            single_sealed_bid_options: None,
+                            time_frame: Some(Duration::minutes(1)),
                             open_bidders: true,
+                            external_reference: Some("ref-1".to_string()),
+                            ..CreateAuctionCommand::default()
                         },
                         UserId::new("seller"),
                     )
@@ -317,6 +1780,19 @@ mod repository_tests {
                 "we should be able to find the created auction"
             );
             match_auction(&fetched_auction.unwrap());
+            let by_reference = repo
+                .get_auction_by_external_reference(&UserId::new("seller"), "ref-1")
+                .await?;
+            assert_eq!(
+                by_reference.map(|a| a.auction_id()),
+                Some(auction.auction_id()),
+                "we should be able to find the auction by its external reference"
+            );
+            let duplicate = repo.create_auction(auction.clone()).await;
+            assert!(
+                matches!(duplicate, Err(Error::Validation(Errors::AuctionAlreadyExists))),
+                "the external reference should be unique per seller"
+            );
             let now = auction.starts_at() + Duration::hours(1);
             let res = auction
                 .try_add_bid(
@@ -325,13 +1801,18 @@ mod repository_tests {
                         user: UserId::new("buyer1"),
                         amount: Amount::new(10, CurrencyCode::SEK),
                         at: now,
+                        quantity: 1,
+                        metadata: None,
                     },
                 )
                 .map_err(|e| Error::Validation(e))?;
-            assert_eq!(true, res, "we should be able to add a bid");
-            let updated_auction = repo.update_auction(auction.clone()).await?;
+            assert!(
+                res.iter().any(|event| matches!(event, DomainEvent::BidPlaced { .. })),
+                "we should be able to add a bid"
+            );
+            auction = repo.update_auction(auction).await?;
             assert_eq!(
-                updated_auction.bids().len(),
+                auction.bids().len(),
                 1,
                 "we should be able to update the auction"
             );
@@ -341,6 +1822,67 @@ mod repository_tests {
                 1,
                 "we should still be able to get the bids"
             );
+            assert_eq!(fetched_auction_2.currency(), CurrencyCode::SEK);
+            assert_eq!(fetched_auction_2.bids()[0].amount().currency(), CurrencyCode::SEK);
+            let (currency, bid_currency): (String, String) = sqlx::query_as(
+                "SELECT a.currency, b.amount_currency FROM auctions a JOIN bids b ON b.auction_id = a.id WHERE a.id = $1",
+            )
+            .bind(auction.auction_id().value())
+            .fetch_one(&repo.pool)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+            assert_eq!(
+                (currency.as_str(), bid_currency.as_str()),
+                ("SEK", "SEK"),
+                "currencies should be stored as ISO codes"
+            );
+
+            // A late bid moves the close, which must survive reading the auction again
+            let late = auction.expiry() - Duration::seconds(30);
+            auction
+                .try_add_bid(
+                    late,
+                    BidData {
+                        user: UserId::new("buyer2"),
+                        amount: Amount::new(30, CurrencyCode::SEK),
+                        at: late,
+                        quantity: 1,
+                        metadata: None,
+                    },
+                )
+                .map_err(Error::Validation)?;
+            assert!(auction.effective_end() > auction.expiry(), "the late bid should extend the auction");
+            auction = repo.update_auction(auction).await?;
+            let extended = repo.get_auction(auction.auction_id()).await?.unwrap();
+            assert_eq!(
+                extended.effective_end(),
+                auction.effective_end(),
+                "the extended end should be persisted"
+            );
+            let page = repo.get_bids_page(auction.auction_id(), None, 1).await?;
+            assert_eq!(page.bids, vec![extended.bids()[1].clone()]);
+            assert_eq!(
+                page.bidder_aliases,
+                HashMap::from([(UserId::new("buyer2"), "Bidder B".to_string())]),
+                "bidders should be aliased in the order of their first bid on any page"
+            );
+            assert_eq!(
+                repo.get_auction_summary(auction.auction_id()).await?,
+                Some(AuctionSummary::of(&extended)),
+                "the summary should be read as the auction would summarize"
+            );
+
+            // Voiding the late bid keeps it stored, but no longer as a bid, nor as an extension
+            let late_bid_id = auction.bids().last().unwrap().id;
+            auction
+                .void_bid(late + Duration::seconds(10), late_bid_id, &UserId::new("support"), Some("fraud".to_string()))
+                .map_err(Error::Validation)?;
+            auction = repo.update_auction(auction).await?;
+            let voided = repo.get_auction(auction.auction_id()).await?.unwrap();
+            assert_eq!(voided.bids().len(), 1);
+            assert_eq!(voided.voided_bids(), auction.voided_bids(), "the voided bid should be kept");
+            assert_eq!(voided.effective_end(), voided.expiry());
+            assert_eq!(repo.count_bids(auction.auction_id()).await?, 1);
 
             let auctions = repo.get_auctions().await?;
             let find_auction_among_auctions = auctions
@@ -357,4 +1899,56 @@ mod repository_tests {
             .await
             .unwrap();
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_bids_with_postgres() {
+        let container = Postgres::default().start().await.unwrap();
+        let host_ip = container.get_host().await.unwrap();
+        let host_port = container.get_host_port_ipv4(5432).await.unwrap();
+        let url = format!("postgresql://postgres:postgres@{}:{}/postgres", host_ip, host_port);
+        let pool = PgPool::connect(&url).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+        let repo = PgAuctionRepository::new(pool);
+        let auction = repo
+            .create_auction(crate::infrastructure::data::auction_repository_conformance::sample_auction(None))
+            .await
+            .unwrap();
+        let auction_id = auction.auction_id();
+
+        // Every bidder reads and bids on the same auction at once, in no particular order
+        let at = starts_at() + Duration::hours(1);
+        let bidders = (1..=20).map(|i| {
+            let repo = repo.clone();
+            tokio::spawn(async move {
+                let bid = BidData {
+                    user: UserId::new(format!("buyer{}", i)),
+                    amount: Amount::new(10 * i, CurrencyCode::SEK),
+                    at,
+                    quantity: 1,
+                    metadata: None,
+                };
+                repo.place_bid(auction_id, Box::new(move |auction: &mut Auction| auction.try_add_bid(at, bid)))
+                    .await
+            })
+        });
+        let mut placed = 0;
+        for bidder in bidders.collect::<Vec<_>>() {
+            match bidder.await.unwrap() {
+                Ok(_) => placed += 1,
+                Err(Error::Validation(Errors::MustPlaceBidOverHighestBid)) => {}
+                Err(e) => panic!("bids should only be refused for being too low, not {:?}", e),
+            }
+        }
+
+        let stored = repo.get_auction(auction_id).await.unwrap().unwrap();
+        assert_eq!(repo.count_bids(auction_id).await.unwrap(), placed, "no placed bid should be lost");
+        assert_eq!(stored.bids().len() as i64, placed);
+        let amounts: Vec<i64> = stored.bids().iter().map(|bid| bid.amount().value()).collect();
+        assert!(
+            amounts.windows(2).all(|pair| pair[0] < pair[1]),
+            "every stored bid should be over the one before it, got {:?}",
+            amounts
+        );
+        assert_eq!(amounts.last(), Some(&200), "the highest bid is never refused");
+    }
 }
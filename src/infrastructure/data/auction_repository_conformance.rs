@@ -0,0 +1,202 @@
+//! Behaviour every [`AuctionRepository`] shares, checked against an empty repository.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::domain::commands::CreateAuctionCommand;
+use crate::domain::models::{Amount, Auction, AuctionFactory, AuctionId, AuctionStatus, BidData, CurrencyCode, Error, Errors, UserId};
+use crate::infrastructure::data::{AuctionFilter, AuctionRepository, AuctionSummary, BidPage};
+
+pub(crate) fn starts_at() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2016, 1, 1, 0, 0, 0).unwrap()
+}
+
+pub(crate) fn sample_auction(external_reference: Option<&str>) -> Auction {
+    AuctionFactory::create_auction(sample_command(external_reference), UserId::new("seller")).unwrap()
+}
+
+pub(crate) fn sample_command(external_reference: Option<&str>) -> CreateAuctionCommand {
+    CreateAuctionCommand {
+        title: "title".to_string(),
+        currency: CurrencyCode::SEK,
+        starts_at: starts_at(),
+        ends_at: starts_at() + Duration::days(31),
+        external_reference: external_reference.map(str::to_string),
+        ..CreateAuctionCommand::default()
+    }
+}
+
+pub(crate) async fn check_auction_repository(repo: &dyn AuctionRepository) {
+    // Stores and reads back auctions
+    let created = repo.create_auction(sample_auction(Some("ref-1"))).await.unwrap();
+    let other = repo.create_auction(sample_auction(None)).await.unwrap();
+    assert_ne!(created.auction_id(), other.auction_id());
+    assert_eq!(repo.get_auction(created.auction_id()).await.unwrap(), Some(created.clone()));
+    assert_eq!(repo.get_auctions().await.unwrap().len(), 2);
+    let by_ids = repo
+        .get_auctions_by_ids(&[other.auction_id(), AuctionId::new(999_999), created.auction_id()])
+        .await
+        .unwrap();
+    assert_eq!(by_ids, vec![other.clone(), created.clone()], "in the order asked for");
+    let by_reference = repo
+        .get_auction_by_external_reference(&UserId::new("seller"), "ref-1")
+        .await
+        .unwrap();
+    assert_eq!(by_reference, Some(created.clone()));
+    let by_seller = repo.get_auctions_by_seller(&UserId::new("seller")).await.unwrap();
+    assert_eq!(by_seller, vec![created.clone(), other.clone()]);
+    assert!(repo.get_auctions_by_seller(&UserId::new("buyer")).await.unwrap().is_empty());
+    let running = repo.get_active_auctions(starts_at() + Duration::hours(1)).await.unwrap();
+    assert_eq!(running, vec![created.clone(), other.clone()]);
+    assert!(repo.get_active_auctions(starts_at() - Duration::hours(1)).await.unwrap().is_empty());
+    assert!(repo.get_active_auctions(starts_at() + Duration::days(32)).await.unwrap().is_empty());
+    assert!(repo.auction_exists(other.auction_id()).await.unwrap());
+    assert!(!repo.auction_exists(AuctionId::new(999_999)).await.unwrap());
+
+    // The external reference is unique per seller
+    let duplicate = repo.create_auction(sample_auction(Some("ref-1"))).await;
+    assert!(
+        matches!(duplicate, Err(Error::Validation(Errors::AuctionAlreadyExists))),
+        "the external reference should be unique per seller"
+    );
+
+    // Stores bids
+    let mut auction = other;
+    let at = starts_at() + Duration::hours(1);
+    auction
+        .try_add_bid(
+            at,
+            BidData {
+                user: UserId::new("buyer"),
+                amount: Amount::new(10, CurrencyCode::SEK),
+                at,
+                quantity: 1,
+                metadata: None,
+            },
+        )
+        .unwrap();
+    let updated = repo.update_auction(auction.clone()).await.unwrap();
+    assert_eq!(repo.get_auction(auction.auction_id()).await.unwrap(), Some(updated.clone()));
+    assert_eq!(repo.count_bids(auction.auction_id()).await.unwrap(), 1);
+
+    // Refuses auctions changed by someone else since they were read
+    assert!(
+        matches!(repo.update_auction(auction).await, Err(Error::Conflict(_))),
+        "an auction read before the update should not be stored over it"
+    );
+
+    // Stores a single bid without the rest of the auction
+    let mut auction = updated;
+    let at = at + Duration::hours(1);
+    auction
+        .try_add_bid(
+            at,
+            BidData {
+                user: UserId::new("buyer"),
+                amount: Amount::new(20, CurrencyCode::SEK),
+                at,
+                quantity: 1,
+                metadata: None,
+            },
+        )
+        .unwrap();
+    let bid = auction.bids().last().unwrap().clone();
+    repo.add_bid(auction.auction_id(), auction.version(), &bid, None).await.unwrap();
+    let stored = repo.get_auction(auction.auction_id()).await.unwrap().unwrap();
+    assert_eq!(stored.bids(), auction.bids());
+    assert_eq!(repo.count_bids(auction.auction_id()).await.unwrap(), 2);
+    assert!(
+        matches!(
+            repo.add_bid(auction.auction_id(), auction.version(), &bid, None).await,
+            Err(Error::Conflict(_))
+        ),
+        "a bid on an auction changed since it was read should not be stored"
+    );
+    assert!(
+        matches!(repo.add_bid(AuctionId::new(999_999), 0, &bid, None).await, Err(Error::NotFound(_))),
+        "bids are only stored on existing auctions"
+    );
+
+    // Reads the bids a page at a time, latest first
+    let latest = repo.get_bids_page(auction.auction_id(), None, 1).await.unwrap();
+    assert_eq!(latest.bids, vec![bid.clone()]);
+    assert_eq!(latest.next_after, Some(bid.id));
+    assert_eq!(latest.bidder_aliases, auction.bidder_aliases(), "aliased like the whole auction");
+    let older = repo.get_bids_page(auction.auction_id(), latest.next_after, 1).await.unwrap();
+    assert_eq!(older.bids, vec![auction.bids()[0].clone()]);
+    assert_eq!(older.next_after, None);
+    assert_eq!(repo.get_bids_page(AuctionId::new(999_999), None, 10).await.unwrap(), BidPage::default());
+    // Reads the rest of the auction apart from the bids, or along with them
+    let without_bids = repo.get_auction_without_bids(auction.auction_id()).await.unwrap().unwrap();
+    assert_eq!(without_bids.title(), stored.title());
+    assert_eq!(without_bids.version(), stored.version());
+    assert!(without_bids.bids().is_empty() || without_bids.bids() == stored.bids());
+    assert_eq!(repo.get_auction_without_bids(AuctionId::new(999_999)).await.unwrap(), None);
+    // Reads the bids deciding the outcome, which works out like with every bid
+    let standings = repo.get_auction_standings(auction.auction_id()).await.unwrap().unwrap();
+    let ended = stored.effective_end() + Duration::seconds(1);
+    assert_eq!(standings.auction.try_get_charges(ended), stored.try_get_charges(ended));
+    assert_eq!(standings.auction.result(ended), stored.result(ended));
+    assert_eq!(standings.bidder_aliases, stored.bidder_aliases());
+    assert_eq!(repo.get_auction_standings(AuctionId::new(999_999)).await.unwrap(), None);
+
+    // Summarizes auctions without their bids
+    let summaries = repo.get_auction_summaries(10, 0, &AuctionFilter::default()).await.unwrap();
+    let auctions = repo.get_auctions().await.unwrap();
+    assert_eq!(summaries.auctions, auctions.iter().map(AuctionSummary::of).collect::<Vec<_>>());
+    assert_eq!(summaries.auctions[1].bid_count, 2);
+    assert_eq!(summaries.auctions[1].high_bid, Some(Amount::new(20, CurrencyCode::SEK)));
+    let summary = repo.get_auction_summary(auction.auction_id()).await.unwrap();
+    assert_eq!(summary.as_ref(), summaries.auctions.get(1));
+    assert_eq!(repo.get_auction_summary(AuctionId::new(999_999)).await.unwrap(), None);
+
+    // Counts auctions
+    let by_seller = AuctionFilter {
+        seller: Some(UserId::new("seller")),
+        status: None,
+        published: false,
+    };
+    assert_eq!(repo.count_auctions(&by_seller).await.unwrap(), 2);
+    let drafts = AuctionFilter {
+        seller: None,
+        status: Some(AuctionStatus::Draft),
+        published: false,
+    };
+    assert_eq!(repo.count_auctions(&drafts).await.unwrap(), 0);
+
+    // Reads auctions a page at a time, leaving out drafts when asked to
+    let draft = AuctionFactory::create_auction(CreateAuctionCommand { draft: true, ..sample_command(None) }, UserId::new("seller"));
+    let draft = repo.create_auction(draft.unwrap()).await.unwrap();
+    let first_page = repo.get_auctions_page(1, 0, &by_seller).await.unwrap();
+    assert_eq!(first_page.auctions.iter().map(Auction::auction_id).collect::<Vec<_>>(), vec![created.auction_id()]);
+    assert_eq!((first_page.total, first_page.next_offset), (3, Some(1)));
+    let last_page = repo.get_auctions_page(5, 1, &by_seller).await.unwrap();
+    assert_eq!(
+        last_page.auctions.iter().map(Auction::auction_id).collect::<Vec<_>>(),
+        vec![auction.auction_id(), draft.auction_id()]
+    );
+    assert_eq!(last_page.next_offset, None);
+    let published = AuctionFilter { published: true, ..by_seller.clone() };
+    assert_eq!(repo.count_auctions(&published).await.unwrap(), 2);
+    let published_page = repo.get_auctions_page(5, 0, &published).await.unwrap();
+    assert_eq!(published_page.auctions.len(), 2);
+    assert_eq!((published_page.total, published_page.next_offset), (2, None));
+
+    // Anonymizes users
+    let pseudonym = UserId::new("anonymized-1");
+    let anonymized = repo.anonymize_user(&UserId::new("buyer"), &pseudonym).await.unwrap();
+    assert_eq!(anonymized, vec![auction.auction_id()]);
+    let stored = repo.get_auction(auction.auction_id()).await.unwrap().unwrap();
+    assert_eq!(stored.bids()[0].user(), &pseudonym);
+    let mut anonymized = repo.anonymize_user(&UserId::new("seller"), &pseudonym).await.unwrap();
+    anonymized.sort_by_key(|id| id.value());
+    assert_eq!(anonymized, vec![created.auction_id(), auction.auction_id(), draft.auction_id()]);
+    assert_eq!(repo.count_auctions(&by_seller).await.unwrap(), 0);
+
+    // Summarizes auctions that were taken down with how, when and why
+    let mut cancelled = repo.get_auction(created.auction_id()).await.unwrap().unwrap();
+    cancelled.cancel(starts_at() + Duration::hours(1), Some("withdrawn".to_string())).unwrap();
+    let cancelled = repo.update_auction(cancelled).await.unwrap();
+    let summary = repo.get_auction_summary(cancelled.auction_id()).await.unwrap().unwrap();
+    assert!(summary.removal.is_some());
+    assert_eq!(summary.removal, cancelled.removal());
+}
@@ -0,0 +1,259 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use dyn_clone::DynClone;
+use sqlx::{PgPool, SqlitePool};
+use std::sync::{Arc, Mutex};
+
+use crate::domain::models::{Amount, AuctionId, AuctionResult, Error, UserId};
+
+dyn_clone::clone_trait_object!(AuctionResultRepository);
+
+#[async_trait]
+pub trait AuctionResultRepository: Send + Sync + DynClone {
+    /// The recorded results of the auctions, leaving out auctions without one.
+    async fn get_results(&self, auction_ids: &[AuctionId]) -> Result<Vec<AuctionResult>, Error>;
+    /// Records the result unless the auction already has one, returning the one that is kept.
+    async fn record(&self, result: AuctionResult) -> Result<AuctionResult, Error>;
+}
+
+/// Keeps results in process memory, for running without a database.
+#[derive(Clone, Default)]
+pub struct InMemoryAuctionResultRepository {
+    results: Arc<Mutex<Vec<AuctionResult>>>,
+}
+
+#[async_trait]
+impl AuctionResultRepository for InMemoryAuctionResultRepository {
+    async fn get_results(&self, auction_ids: &[AuctionId]) -> Result<Vec<AuctionResult>, Error> {
+        Ok(self
+            .results
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| auction_ids.contains(&r.auction_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn record(&self, result: AuctionResult) -> Result<AuctionResult, Error> {
+        let mut results = self.results.lock().unwrap();
+        if let Some(existing) = results.iter().find(|r| r.auction_id == result.auction_id) {
+            return Ok(existing.clone());
+        }
+        results.push(result.clone());
+        Ok(result)
+    }
+}
+
+#[derive(Clone)]
+pub struct PgAuctionResultRepository {
+    pool: PgPool,
+}
+
+impl PgAuctionResultRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+type AuctionResultRow = (i64, Option<String>, Option<i64>, Option<String>, DateTime<Utc>, bool);
+
+fn to_result(row: AuctionResultRow) -> Result<AuctionResult, Error> {
+    let (auction_id, winner, amount_value, amount_currency, closed_at, reserve_met) = row;
+    let amount = match (amount_value, amount_currency) {
+        (Some(value), Some(currency)) => Some(Amount::new(
+            value,
+            currency
+                .parse()
+                .map_err(|_| Error::Repository(format!("Unknown currency {}", currency)))?,
+        )),
+        _ => None,
+    };
+    Ok(AuctionResult {
+        auction_id: AuctionId::new(auction_id),
+        winner: winner.map(UserId::new),
+        amount,
+        closed_at,
+        reserve_met,
+    })
+}
+
+#[async_trait]
+impl AuctionResultRepository for PgAuctionResultRepository {
+    async fn get_results(&self, auction_ids: &[AuctionId]) -> Result<Vec<AuctionResult>, Error> {
+        let ids: Vec<i64> = auction_ids.iter().map(|id| id.value()).collect();
+        let rows = sqlx::query_as::<_, AuctionResultRow>(
+            r#"
+            SELECT auction_id, winner, amount_value, amount_currency, closed_at, reserve_met
+            FROM auction_results
+            WHERE auction_id = ANY($1)
+        "#,
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        rows.into_iter().map(to_result).collect()
+    }
+
+    async fn record(&self, result: AuctionResult) -> Result<AuctionResult, Error> {
+        // Concurrent readers may both see the auction closed; the first result recorded stays
+        let row = sqlx::query_as::<_, AuctionResultRow>(
+            r#"
+            WITH inserted AS (
+                INSERT INTO auction_results (auction_id, winner, amount_value, amount_currency, closed_at, reserve_met)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (auction_id) DO NOTHING
+                RETURNING auction_id, winner, amount_value, amount_currency, closed_at, reserve_met
+            )
+            SELECT auction_id, winner, amount_value, amount_currency, closed_at, reserve_met FROM inserted
+            UNION ALL
+            SELECT auction_id, winner, amount_value, amount_currency, closed_at, reserve_met
+            FROM auction_results
+            WHERE auction_id = $1
+        "#,
+        )
+        .bind(result.auction_id.value())
+        .bind(result.winner.as_ref().map(|user| user.value().to_string()))
+        .bind(result.amount.as_ref().map(|amount| amount.value()))
+        .bind(result.amount.as_ref().map(|amount| amount.currency().to_string()))
+        .bind(result.closed_at)
+        .bind(result.reserve_met)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        to_result(row)
+    }
+}
+
+#[derive(Clone)]
+pub struct SqliteAuctionResultRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteAuctionResultRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AuctionResultRepository for SqliteAuctionResultRepository {
+    async fn get_results(&self, auction_ids: &[AuctionId]) -> Result<Vec<AuctionResult>, Error> {
+        let ids: Vec<i64> = auction_ids.iter().map(|id| id.value()).collect();
+        let ids = serde_json::to_string(&ids).map_err(|e| Error::Internal(e.to_string()))?;
+        let rows = sqlx::query_as::<_, AuctionResultRow>(
+            r#"
+            SELECT auction_id, winner, amount_value, amount_currency, closed_at, reserve_met
+            FROM auction_results
+            WHERE auction_id IN (SELECT value FROM json_each(?1))
+        "#,
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        rows.into_iter().map(to_result).collect()
+    }
+
+    async fn record(&self, result: AuctionResult) -> Result<AuctionResult, Error> {
+        // Concurrent readers may both see the auction closed; the first result recorded stays
+        sqlx::query(
+            r#"
+            INSERT INTO auction_results (auction_id, winner, amount_value, amount_currency, closed_at, reserve_met)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT (auction_id) DO NOTHING
+        "#,
+        )
+        .bind(result.auction_id.value())
+        .bind(result.winner.as_ref().map(|user| user.value().to_string()))
+        .bind(result.amount.as_ref().map(|amount| amount.value()))
+        .bind(result.amount.as_ref().map(|amount| amount.currency().to_string()))
+        .bind(result.closed_at)
+        .bind(result.reserve_met)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        let row = sqlx::query_as::<_, AuctionResultRow>(
+            r#"
+            SELECT auction_id, winner, amount_value, amount_currency, closed_at, reserve_met
+            FROM auction_results
+            WHERE auction_id = ?1
+        "#,
+        )
+        .bind(result.auction_id.value())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        to_result(row)
+    }
+}
+
+#[cfg(feature = "mysql")]
+#[derive(Clone)]
+pub struct MySqlAuctionResultRepository {
+    pool: sqlx::MySqlPool,
+}
+
+#[cfg(feature = "mysql")]
+impl MySqlAuctionResultRepository {
+    pub fn new(pool: sqlx::MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[cfg(feature = "mysql")]
+#[async_trait]
+impl AuctionResultRepository for MySqlAuctionResultRepository {
+    async fn get_results(&self, auction_ids: &[AuctionId]) -> Result<Vec<AuctionResult>, Error> {
+        if auction_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut query = sqlx::QueryBuilder::<sqlx::MySql>::new(
+            "SELECT auction_id, winner, amount_value, amount_currency, closed_at, reserve_met FROM auction_results WHERE auction_id IN (",
+        );
+        let mut ids = query.separated(", ");
+        for auction_id in auction_ids {
+            ids.push_bind(auction_id.value());
+        }
+        query.push(")");
+        let rows = query
+            .build_query_as::<AuctionResultRow>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        rows.into_iter().map(to_result).collect()
+    }
+
+    async fn record(&self, result: AuctionResult) -> Result<AuctionResult, Error> {
+        // Concurrent readers may both see the auction closed; the first result recorded stays
+        sqlx::query(
+            r#"
+            INSERT INTO auction_results (auction_id, winner, amount_value, amount_currency, closed_at, reserve_met)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE auction_id = auction_id
+        "#,
+        )
+        .bind(result.auction_id.value())
+        .bind(result.winner.as_ref().map(|user| user.value().to_string()))
+        .bind(result.amount.as_ref().map(|amount| amount.value()))
+        .bind(result.amount.as_ref().map(|amount| amount.currency().to_string()))
+        .bind(result.closed_at)
+        .bind(result.reserve_met)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        let row = sqlx::query_as::<_, AuctionResultRow>(
+            r#"
+            SELECT auction_id, winner, amount_value, amount_currency, closed_at, reserve_met
+            FROM auction_results
+            WHERE auction_id = ?
+        "#,
+        )
+        .bind(result.auction_id.value())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        to_result(row)
+    }
+}
@@ -0,0 +1,132 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+use sqlx::{PgPool, SqlitePool};
+use std::sync::{Arc, Mutex};
+
+use crate::domain::models::{AuditEntry, Error};
+
+dyn_clone::clone_trait_object!(AuditRepository);
+
+#[async_trait]
+pub trait AuditRepository: Send + Sync + DynClone {
+    async fn record(&self, entry: AuditEntry) -> Result<(), Error>;
+}
+
+/// Keeps audit entries in process memory, for running without a database.
+#[derive(Clone, Default)]
+pub struct InMemoryAuditRepository {
+    entries: Arc<Mutex<Vec<AuditEntry>>>,
+}
+
+impl InMemoryAuditRepository {
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl AuditRepository for InMemoryAuditRepository {
+    async fn record(&self, entry: AuditEntry) -> Result<(), Error> {
+        self.entries.lock().unwrap().push(entry);
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct PgAuditRepository {
+    pool: PgPool,
+}
+
+impl PgAuditRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AuditRepository for PgAuditRepository {
+    async fn record(&self, entry: AuditEntry) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO auction_audit_log (auction_id, action, user_id, reason, at)
+            VALUES ($1, $2, $3, $4, $5)
+        "#,
+        )
+        .bind(entry.auction_id.value())
+        .bind(&entry.action)
+        .bind(entry.user.value())
+        .bind(&entry.reason)
+        .bind(entry.at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct SqliteAuditRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteAuditRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AuditRepository for SqliteAuditRepository {
+    async fn record(&self, entry: AuditEntry) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO auction_audit_log (auction_id, action, user_id, reason, at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+        "#,
+        )
+        .bind(entry.auction_id.value())
+        .bind(&entry.action)
+        .bind(entry.user.value())
+        .bind(&entry.reason)
+        .bind(entry.at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "mysql")]
+#[derive(Clone)]
+pub struct MySqlAuditRepository {
+    pool: sqlx::MySqlPool,
+}
+
+#[cfg(feature = "mysql")]
+impl MySqlAuditRepository {
+    pub fn new(pool: sqlx::MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[cfg(feature = "mysql")]
+#[async_trait]
+impl AuditRepository for MySqlAuditRepository {
+    async fn record(&self, entry: AuditEntry) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO auction_audit_log (auction_id, action, user_id, reason, at)
+            VALUES (?, ?, ?, ?, ?)
+        "#,
+        )
+        .bind(entry.auction_id.value())
+        .bind(&entry.action)
+        .bind(entry.user.value())
+        .bind(&entry.reason)
+        .bind(entry.at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,353 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::domain::models::{Auction, AuctionId, Bid, DomainEvent, Error, UserId};
+use crate::infrastructure::config::AuctionCacheConfig;
+use crate::infrastructure::data::{AuctionFilter, AuctionPage, AuctionRepository, AuctionStandings, AuctionSummary, BidPage, PlaceBid};
+
+/// Serves repeated reads of auctions and listings from process memory for a short while, to
+/// take browsing traffic off the store. Writes through the repository forget what they touch
+/// and every listing; writes by other processes show once the entries expire.
+#[derive(Clone)]
+pub struct CachingAuctionRepository {
+    inner: Box<dyn AuctionRepository>,
+    cache: Arc<Mutex<Cache>>,
+    auction_ttl: Duration,
+    listing_ttl: Duration,
+    max_entries: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ListingKey {
+    All,
+    Page(i64, i64, AuctionFilter),
+    Summaries(i64, i64, AuctionFilter),
+    Seller(UserId),
+    Count(AuctionFilter),
+}
+
+#[derive(Clone)]
+enum Listing {
+    Auctions(Vec<Auction>),
+    Page(AuctionPage),
+    Summaries(AuctionPage<AuctionSummary>),
+    Count(i64),
+}
+
+#[derive(Default)]
+struct Cache {
+    auctions: HashMap<AuctionId, (Instant, Option<Auction>)>,
+    listings: Vec<(ListingKey, Instant, Listing)>,
+    /// Moved on by every write, so that a read started before it is not cached after it
+    generation: u64,
+}
+
+impl Cache {
+    fn auction(&mut self, auction_id: AuctionId, now: Instant) -> Option<Option<Auction>> {
+        match self.auctions.get(&auction_id) {
+            Some((expires, auction)) if *expires > now => Some(auction.clone()),
+            Some(_) => {
+                self.auctions.remove(&auction_id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn listing(&mut self, key: &ListingKey, now: Instant) -> Option<Listing> {
+        self.listings.retain(|(_, expires, _)| *expires > now);
+        self.listings.iter().find(|(k, _, _)| k == key).map(|(_, _, listing)| listing.clone())
+    }
+
+    fn store_auction(&mut self, auction_id: AuctionId, auction: Option<Auction>, expires: Instant, max_entries: usize) {
+        if self.auctions.len() >= max_entries && !self.auctions.contains_key(&auction_id) {
+            let now = Instant::now();
+            self.auctions.retain(|_, (expires, _)| *expires > now);
+            if self.auctions.len() >= max_entries {
+                let oldest = self.auctions.iter().min_by_key(|(_, (expires, _))| *expires).map(|(id, _)| *id);
+                if let Some(id) = oldest {
+                    self.auctions.remove(&id);
+                }
+            }
+        }
+        self.auctions.insert(auction_id, (expires, auction));
+    }
+
+    fn store_listing(&mut self, key: ListingKey, listing: Listing, expires: Instant, max_entries: usize) {
+        self.listings.retain(|(k, _, _)| *k != key);
+        if self.listings.len() >= max_entries {
+            // Entries share one time to live, so the first one expires first
+            self.listings.remove(0);
+        }
+        self.listings.push((key, expires, listing));
+    }
+
+    fn forget(&mut self, auction_ids: &[AuctionId]) {
+        for auction_id in auction_ids {
+            self.auctions.remove(auction_id);
+        }
+        self.listings.clear();
+        self.generation += 1;
+    }
+}
+
+impl CachingAuctionRepository {
+    pub fn new(inner: Box<dyn AuctionRepository>, config: &AuctionCacheConfig) -> Self {
+        Self {
+            inner,
+            cache: Arc::new(Mutex::new(Cache::default())),
+            auction_ttl: Duration::from_millis(config.auction_ttl_ms),
+            listing_ttl: Duration::from_millis(config.listing_ttl_ms),
+            max_entries: config.max_entries.max(1),
+        }
+    }
+
+    async fn listing<T: Clone>(
+        &self,
+        key: ListingKey,
+        read: impl Future<Output = Result<T, Error>> + Send,
+        into: fn(T) -> Listing,
+        from: fn(Listing) -> Option<T>,
+    ) -> Result<T, Error> {
+        let generation = {
+            let mut cache = self.cache.lock().unwrap();
+            if let Some(cached) = cache.listing(&key, Instant::now()).and_then(from) {
+                return Ok(cached);
+            }
+            cache.generation
+        };
+        let value = read.await?;
+        let mut cache = self.cache.lock().unwrap();
+        if cache.generation == generation {
+            cache.store_listing(key, into(value.clone()), Instant::now() + self.listing_ttl, self.max_entries);
+        }
+        Ok(value)
+    }
+
+    /// Forgets the auctions and every listing, for writes made by other instances.
+    pub fn invalidate(&self, auction_ids: &[AuctionId]) {
+        self.cache.lock().unwrap().forget(auction_ids);
+    }
+
+    /// Forgets everything, when writes made by other instances may have been missed.
+    pub fn clear(&self) {
+        let mut cache = self.cache.lock().unwrap();
+        cache.auctions.clear();
+        cache.forget(&[]);
+    }
+}
+
+#[async_trait]
+impl AuctionRepository for CachingAuctionRepository {
+    async fn get_auction(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+        let generation = {
+            let mut cache = self.cache.lock().unwrap();
+            if let Some(cached) = cache.auction(auction_id, Instant::now()) {
+                return Ok(cached);
+            }
+            cache.generation
+        };
+        let auction = self.inner.get_auction(auction_id).await?;
+        let mut cache = self.cache.lock().unwrap();
+        if cache.generation == generation {
+            cache.store_auction(auction_id, auction.clone(), Instant::now() + self.auction_ttl, self.max_entries);
+        }
+        Ok(auction)
+    }
+
+    async fn get_auction_without_bids(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+        if let Some(cached) = self.cache.lock().unwrap().auction(auction_id, Instant::now()) {
+            return Ok(cached);
+        }
+        self.inner.get_auction_without_bids(auction_id).await
+    }
+
+    async fn get_auction_standings(&self, auction_id: AuctionId) -> Result<Option<AuctionStandings>, Error> {
+        if let Some(cached) = self.cache.lock().unwrap().auction(auction_id, Instant::now()) {
+            return Ok(cached.map(AuctionStandings::of));
+        }
+        self.inner.get_auction_standings(auction_id).await
+    }
+
+    async fn get_auction_summary(&self, auction_id: AuctionId) -> Result<Option<AuctionSummary>, Error> {
+        if let Some(cached) = self.cache.lock().unwrap().auction(auction_id, Instant::now()) {
+            return Ok(cached.as_ref().map(AuctionSummary::of));
+        }
+        self.inner.get_auction_summary(auction_id).await
+    }
+
+    async fn get_auctions(&self) -> Result<Vec<Auction>, Error> {
+        self.listing(ListingKey::All, self.inner.get_auctions(), Listing::Auctions, |listing| match listing {
+            Listing::Auctions(auctions) => Some(auctions),
+            _ => None,
+        })
+        .await
+    }
+
+    async fn get_auctions_page(&self, limit: i64, offset: i64, filter: &AuctionFilter) -> Result<AuctionPage, Error> {
+        self.listing(
+            ListingKey::Page(limit, offset, filter.clone()),
+            self.inner.get_auctions_page(limit, offset, filter),
+            Listing::Page,
+            |listing| match listing {
+                Listing::Page(page) => Some(page),
+                _ => None,
+            },
+        )
+        .await
+    }
+
+    async fn get_auction_summaries(
+        &self,
+        limit: i64,
+        offset: i64,
+        filter: &AuctionFilter,
+    ) -> Result<AuctionPage<AuctionSummary>, Error> {
+        self.listing(
+            ListingKey::Summaries(limit, offset, filter.clone()),
+            self.inner.get_auction_summaries(limit, offset, filter),
+            Listing::Summaries,
+            |listing| match listing {
+                Listing::Summaries(page) => Some(page),
+                _ => None,
+            },
+        )
+        .await
+    }
+
+    async fn get_active_auctions(&self, now: DateTime<Utc>) -> Result<Vec<Auction>, Error> {
+        self.inner.get_active_auctions(now).await
+    }
+
+    async fn get_bids_page(&self, auction_id: AuctionId, after_bid_id: Option<i64>, limit: i64) -> Result<BidPage, Error> {
+        self.inner.get_bids_page(auction_id, after_bid_id, limit).await
+    }
+
+    async fn get_auctions_by_seller(&self, seller: &UserId) -> Result<Vec<Auction>, Error> {
+        self.listing(
+            ListingKey::Seller(seller.clone()),
+            self.inner.get_auctions_by_seller(seller),
+            Listing::Auctions,
+            |listing| match listing {
+                Listing::Auctions(auctions) => Some(auctions),
+                _ => None,
+            },
+        )
+        .await
+    }
+
+    async fn get_auctions_by_ids(&self, auction_ids: &[AuctionId]) -> Result<Vec<Auction>, Error> {
+        self.inner.get_auctions_by_ids(auction_ids).await
+    }
+
+    async fn get_auction_by_external_reference(
+        &self,
+        user: &UserId,
+        external_reference: &str,
+    ) -> Result<Option<Auction>, Error> {
+        self.inner.get_auction_by_external_reference(user, external_reference).await
+    }
+
+    async fn create_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        let created = self.inner.create_auction(auction).await;
+        let auction_ids: Vec<AuctionId> = created.iter().map(|auction| auction.auction_id()).collect();
+        self.invalidate(&auction_ids);
+        created
+    }
+
+    async fn update_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        let auction_id = auction.auction_id();
+        let updated = self.inner.update_auction(auction).await;
+        self.invalidate(&[auction_id]);
+        updated
+    }
+
+    async fn add_bid(
+        &self,
+        auction_id: AuctionId,
+        version: i32,
+        bid: &Bid,
+        ends_at: Option<DateTime<Utc>>,
+    ) -> Result<(), Error> {
+        let added = self.inner.add_bid(auction_id, version, bid, ends_at).await;
+        self.invalidate(&[auction_id]);
+        added
+    }
+
+    async fn place_bid(&self, auction_id: AuctionId, place: PlaceBid) -> Result<Vec<DomainEvent>, Error> {
+        let placed = self.inner.place_bid(auction_id, place).await;
+        self.invalidate(&[auction_id]);
+        placed
+    }
+
+    async fn auction_exists(&self, auction_id: AuctionId) -> Result<bool, Error> {
+        self.inner.auction_exists(auction_id).await
+    }
+
+    async fn count_auctions(&self, filter: &AuctionFilter) -> Result<i64, Error> {
+        self.listing(
+            ListingKey::Count(filter.clone()),
+            self.inner.count_auctions(filter),
+            Listing::Count,
+            |listing| match listing {
+                Listing::Count(count) => Some(count),
+                _ => None,
+            },
+        )
+        .await
+    }
+
+    async fn count_bids(&self, auction_id: AuctionId) -> Result<i64, Error> {
+        self.inner.count_bids(auction_id).await
+    }
+
+    async fn anonymize_user(&self, user: &UserId, pseudonym: &UserId) -> Result<Vec<AuctionId>, Error> {
+        let anonymized = self.inner.anonymize_user(user, pseudonym).await?;
+        self.invalidate(&anonymized);
+        Ok(anonymized)
+    }
+}
+
+#[cfg(test)]
+mod caching_auction_repository_tests {
+    use super::*;
+    use crate::infrastructure::data::auction_repository_conformance::{check_auction_repository, sample_auction};
+    use crate::infrastructure::data::InMemoryAuctionRepository;
+
+    fn config(listing_ttl_ms: u64) -> AuctionCacheConfig {
+        AuctionCacheConfig { enabled: true, auction_ttl_ms: listing_ttl_ms, listing_ttl_ms, max_entries: 10 }
+    }
+
+    #[tokio::test]
+    async fn test_caching_repository() {
+        check_auction_repository(&CachingAuctionRepository::new(Box::new(InMemoryAuctionRepository::default()), &config(60_000))).await;
+    }
+
+    #[tokio::test]
+    async fn test_serves_listings_until_written_through() {
+        let store = InMemoryAuctionRepository::default();
+        let repo = CachingAuctionRepository::new(Box::new(store.clone()), &config(60_000));
+        assert_eq!(repo.count_auctions(&AuctionFilter::default()).await.unwrap(), 0);
+
+        let behind = store.create_auction(sample_auction(None)).await.unwrap();
+        assert_eq!(repo.count_auctions(&AuctionFilter::default()).await.unwrap(), 0);
+        assert_eq!(repo.get_auction(behind.auction_id()).await.unwrap(), Some(behind.clone()));
+
+        repo.create_auction(sample_auction(None)).await.unwrap();
+        assert_eq!(repo.count_auctions(&AuctionFilter::default()).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_reads_the_store_once_entries_expire() {
+        let store = InMemoryAuctionRepository::default();
+        let repo = CachingAuctionRepository::new(Box::new(store.clone()), &config(0));
+        assert_eq!(repo.get_auctions().await.unwrap(), Vec::new());
+
+        let behind = store.create_auction(sample_auction(None)).await.unwrap();
+        assert_eq!(repo.get_auctions().await.unwrap(), vec![behind]);
+    }
+}
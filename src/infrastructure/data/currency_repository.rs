@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+use sqlx::{PgPool, SqlitePool};
+
+use crate::domain::models::{Currency, CurrencyCode, Error};
+
+dyn_clone::clone_trait_object!(CurrencyRepository);
+
+#[async_trait]
+pub trait CurrencyRepository: Send + Sync + DynClone {
+    async fn get_currencies(&self) -> Result<Vec<Currency>, Error>;
+}
+
+/// Fixed list of currencies, for running without a database.
+#[derive(Clone, Default)]
+pub struct InMemoryCurrencyRepository {
+    currencies: Vec<Currency>,
+}
+
+impl InMemoryCurrencyRepository {
+    pub fn new(currencies: Vec<Currency>) -> Self {
+        Self { currencies }
+    }
+}
+
+#[async_trait]
+impl CurrencyRepository for InMemoryCurrencyRepository {
+    async fn get_currencies(&self) -> Result<Vec<Currency>, Error> {
+        Ok(self.currencies.clone())
+    }
+}
+
+#[derive(Clone)]
+pub struct PgCurrencyRepository {
+    pool: PgPool,
+}
+
+impl PgCurrencyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CurrencyRepository for PgCurrencyRepository {
+    async fn get_currencies(&self) -> Result<Vec<Currency>, Error> {
+        let rows = sqlx::query_as::<_, (String, i32, String, i16)>(
+            r#"
+            SELECT code, numeric_code, name, minor_units
+            FROM currencies
+            WHERE enabled
+            ORDER BY code
+        "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+
+        map_currencies(rows)
+    }
+}
+
+#[derive(Clone)]
+pub struct SqliteCurrencyRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteCurrencyRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CurrencyRepository for SqliteCurrencyRepository {
+    async fn get_currencies(&self) -> Result<Vec<Currency>, Error> {
+        let rows = sqlx::query_as::<_, (String, i32, String, i16)>(
+            r#"
+            SELECT code, numeric_code, name, minor_units
+            FROM currencies
+            WHERE enabled
+            ORDER BY code
+        "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+
+        map_currencies(rows)
+    }
+}
+
+#[cfg(feature = "mysql")]
+#[derive(Clone)]
+pub struct MySqlCurrencyRepository {
+    pool: sqlx::MySqlPool,
+}
+
+#[cfg(feature = "mysql")]
+impl MySqlCurrencyRepository {
+    pub fn new(pool: sqlx::MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[cfg(feature = "mysql")]
+#[async_trait]
+impl CurrencyRepository for MySqlCurrencyRepository {
+    async fn get_currencies(&self) -> Result<Vec<Currency>, Error> {
+        let rows = sqlx::query_as::<_, (String, i32, String, i16)>(
+            r#"
+            SELECT code, numeric_code, name, minor_units
+            FROM currencies
+            WHERE enabled
+            ORDER BY code
+        "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+
+        map_currencies(rows)
+    }
+}
+
+fn map_currencies(rows: Vec<(String, i32, String, i16)>) -> Result<Vec<Currency>, Error> {
+    rows.into_iter()
+        .map(|(code, numeric_code, name, minor_units)| -> Result<Currency, Error> {
+            let code = code.parse::<CurrencyCode>().map_err(|_| {
+                Error::Repository(format!("get_currencies: Unknown currency code: {}", code))
+            })?;
+            Ok(Currency {
+                code,
+                numeric_code,
+                name,
+                minor_units,
+            })
+        })
+        .collect()
+}
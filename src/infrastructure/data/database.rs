@@ -1,10 +1,90 @@
-use sqlx::{postgres::PgPoolOptions, PgPool};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{postgres::PgPoolOptions, PgPool, SqlitePool};
+use std::str::FromStr;
 use std::time::Duration;
 
-pub async fn create_pg_pool(connection_string: &str) -> Result<PgPool, sqlx::Error> {
+use crate::infrastructure::config::DatabaseConfig;
+
+/// Size and timeouts of a connection pool, see [`DatabaseConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolSettings {
+    pub max_connections: u32,
+    pub acquire_timeout: Duration,
+    pub idle_timeout: Option<Duration>,
+    pub max_lifetime: Option<Duration>,
+}
+
+impl Default for PoolSettings {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            acquire_timeout: Duration::from_secs(5),
+            idle_timeout: Some(Duration::from_secs(600)),
+            max_lifetime: Some(Duration::from_secs(1800)),
+        }
+    }
+}
+
+impl From<&DatabaseConfig> for PoolSettings {
+    fn from(config: &DatabaseConfig) -> Self {
+        Self {
+            max_connections: config.max_connections,
+            acquire_timeout: Duration::from_secs(config.connection_timeout),
+            idle_timeout: config.idle_timeout.map(Duration::from_secs),
+            max_lifetime: config.max_lifetime.map(Duration::from_secs),
+        }
+    }
+}
+
+fn log_pool(database: &str, settings: &PoolSettings) {
+    log::info!(
+        "{} pool of at most {} connections, waiting {:?} for one, closing idle ones after {:?} and replacing them after {:?}",
+        database,
+        settings.max_connections,
+        settings.acquire_timeout,
+        settings.idle_timeout,
+        settings.max_lifetime
+    );
+}
+
+pub async fn create_pg_pool(connection_string: &str, settings: PoolSettings) -> Result<PgPool, sqlx::Error> {
+    log_pool("Postgres", &settings);
     PgPoolOptions::new()
-        .max_connections(10)
-        .acquire_timeout(Duration::from_secs(5))
+        .max_connections(settings.max_connections)
+        .acquire_timeout(settings.acquire_timeout)
+        .idle_timeout(settings.idle_timeout)
+        .max_lifetime(settings.max_lifetime)
+        .connect(connection_string)
+        .await
+}
+
+/// Creates the database file when missing. An in-memory database lives in its one connection,
+/// which is therefore never closed.
+pub async fn create_sqlite_pool(connection_string: &str, settings: PoolSettings) -> Result<SqlitePool, sqlx::Error> {
+    let options = SqliteConnectOptions::from_str(connection_string)?.create_if_missing(true);
+    let settings = if connection_string.contains(":memory:") {
+        PoolSettings { max_connections: 1, idle_timeout: None, max_lifetime: None, ..settings }
+    } else {
+        settings
+    };
+    log_pool("SQLite", &settings);
+    SqlitePoolOptions::new()
+        .max_connections(settings.max_connections)
+        .acquire_timeout(settings.acquire_timeout)
+        .idle_timeout(settings.idle_timeout)
+        .max_lifetime(settings.max_lifetime)
+        .connect_with(options)
+        .await
+}
+
+#[cfg(feature = "mysql")]
+pub async fn create_mysql_pool(connection_string: &str, settings: PoolSettings) -> Result<sqlx::MySqlPool, sqlx::Error> {
+    log_pool("MySQL", &settings);
+    sqlx::mysql::MySqlPoolOptions::new()
+        .max_connections(settings.max_connections)
+        .acquire_timeout(settings.acquire_timeout)
+        .idle_timeout(settings.idle_timeout)
+        .max_lifetime(settings.max_lifetime)
         .connect(connection_string)
         .await
 }
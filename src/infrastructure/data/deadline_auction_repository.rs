@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::models::{Auction, AuctionId, Bid, DomainEvent, Error, UserId};
+use crate::infrastructure::data::{AuctionFilter, AuctionPage, AuctionRepository, AuctionStandings, AuctionSummary, BidPage, PlaceBid};
+use crate::infrastructure::services::within_deadline;
+
+/// Bounds auction repository calls by the deadline of the request making them.
+#[derive(Clone)]
+pub struct DeadlineAuctionRepository {
+    inner: Box<dyn AuctionRepository>,
+}
+
+impl DeadlineAuctionRepository {
+    pub fn new(inner: Box<dyn AuctionRepository>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl AuctionRepository for DeadlineAuctionRepository {
+    async fn get_auction(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+        within_deadline("get_auction", self.inner.get_auction(auction_id)).await
+    }
+
+    async fn get_auction_without_bids(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+        within_deadline("get_auction_without_bids", self.inner.get_auction_without_bids(auction_id)).await
+    }
+
+    async fn get_auction_standings(&self, auction_id: AuctionId) -> Result<Option<AuctionStandings>, Error> {
+        within_deadline("get_auction_standings", self.inner.get_auction_standings(auction_id)).await
+    }
+
+    async fn get_auction_summary(&self, auction_id: AuctionId) -> Result<Option<AuctionSummary>, Error> {
+        within_deadline("get_auction_summary", self.inner.get_auction_summary(auction_id)).await
+    }
+
+    async fn get_auctions(&self) -> Result<Vec<Auction>, Error> {
+        within_deadline("get_auctions", self.inner.get_auctions()).await
+    }
+
+    async fn get_auctions_page(&self, limit: i64, offset: i64, filter: &AuctionFilter) -> Result<AuctionPage, Error> {
+        within_deadline("get_auctions_page", self.inner.get_auctions_page(limit, offset, filter)).await
+    }
+
+    async fn get_auction_summaries(
+        &self,
+        limit: i64,
+        offset: i64,
+        filter: &AuctionFilter,
+    ) -> Result<AuctionPage<AuctionSummary>, Error> {
+        within_deadline("get_auction_summaries", self.inner.get_auction_summaries(limit, offset, filter)).await
+    }
+
+    async fn get_active_auctions(&self, now: DateTime<Utc>) -> Result<Vec<Auction>, Error> {
+        within_deadline("get_active_auctions", self.inner.get_active_auctions(now)).await
+    }
+
+    async fn get_bids_page(&self, auction_id: AuctionId, after_bid_id: Option<i64>, limit: i64) -> Result<BidPage, Error> {
+        within_deadline("get_bids_page", self.inner.get_bids_page(auction_id, after_bid_id, limit)).await
+    }
+
+    async fn get_auctions_by_seller(&self, seller: &UserId) -> Result<Vec<Auction>, Error> {
+        within_deadline("get_auctions_by_seller", self.inner.get_auctions_by_seller(seller)).await
+    }
+
+    async fn get_auctions_by_ids(&self, auction_ids: &[AuctionId]) -> Result<Vec<Auction>, Error> {
+        within_deadline("get_auctions_by_ids", self.inner.get_auctions_by_ids(auction_ids)).await
+    }
+
+    async fn get_auction_by_external_reference(
+        &self,
+        user: &UserId,
+        external_reference: &str,
+    ) -> Result<Option<Auction>, Error> {
+        within_deadline(
+            "get_auction_by_external_reference",
+            self.inner
+                .get_auction_by_external_reference(user, external_reference),
+        )
+        .await
+    }
+
+    async fn create_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        within_deadline("create_auction", self.inner.create_auction(auction)).await
+    }
+
+    async fn update_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        within_deadline("update_auction", self.inner.update_auction(auction)).await
+    }
+
+    async fn add_bid(
+        &self,
+        auction_id: AuctionId,
+        version: i32,
+        bid: &Bid,
+        ends_at: Option<DateTime<Utc>>,
+    ) -> Result<(), Error> {
+        within_deadline("add_bid", self.inner.add_bid(auction_id, version, bid, ends_at)).await
+    }
+
+    async fn place_bid(&self, auction_id: AuctionId, place: PlaceBid) -> Result<Vec<DomainEvent>, Error> {
+        within_deadline("place_bid", self.inner.place_bid(auction_id, place)).await
+    }
+
+    async fn auction_exists(&self, auction_id: AuctionId) -> Result<bool, Error> {
+        within_deadline("auction_exists", self.inner.auction_exists(auction_id)).await
+    }
+
+    async fn count_auctions(&self, filter: &AuctionFilter) -> Result<i64, Error> {
+        within_deadline("count_auctions", self.inner.count_auctions(filter)).await
+    }
+
+    async fn count_bids(&self, auction_id: AuctionId) -> Result<i64, Error> {
+        within_deadline("count_bids", self.inner.count_bids(auction_id)).await
+    }
+
+    async fn anonymize_user(&self, user: &UserId, pseudonym: &UserId) -> Result<Vec<AuctionId>, Error> {
+        within_deadline("anonymize_user", self.inner.anonymize_user(user, pseudonym)).await
+    }
+}
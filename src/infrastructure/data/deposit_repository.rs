@@ -0,0 +1,217 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use dyn_clone::DynClone;
+use sqlx::{PgPool, SqlitePool};
+use std::sync::{Arc, Mutex};
+
+use crate::domain::models::{Amount, AuctionId, Deposit, Error, UserId};
+
+dyn_clone::clone_trait_object!(DepositRepository);
+
+#[async_trait]
+pub trait DepositRepository: Send + Sync + DynClone {
+    async fn get_deposit(&self, auction_id: AuctionId, user: &UserId) -> Result<Option<Deposit>, Error>;
+    /// Records the deposit, replacing an earlier one of the same bidder in the same auction.
+    async fn record(&self, deposit: Deposit) -> Result<Deposit, Error>;
+}
+
+/// Keeps deposits in process memory, for running without a database.
+#[derive(Clone, Default)]
+pub struct InMemoryDepositRepository {
+    deposits: Arc<Mutex<Vec<Deposit>>>,
+}
+
+#[async_trait]
+impl DepositRepository for InMemoryDepositRepository {
+    async fn get_deposit(&self, auction_id: AuctionId, user: &UserId) -> Result<Option<Deposit>, Error> {
+        Ok(self
+            .deposits
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|d| d.auction_id == auction_id && &d.user == user)
+            .cloned())
+    }
+
+    async fn record(&self, deposit: Deposit) -> Result<Deposit, Error> {
+        let mut deposits = self.deposits.lock().unwrap();
+        deposits.retain(|d| !(d.auction_id == deposit.auction_id && d.user == deposit.user));
+        deposits.push(deposit.clone());
+        Ok(deposit)
+    }
+}
+
+#[derive(Clone)]
+pub struct PgDepositRepository {
+    pool: PgPool,
+}
+
+impl PgDepositRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+type DepositRow = (i64, String, DateTime<Utc>);
+
+fn to_deposit(auction_id: AuctionId, user: &UserId, row: DepositRow) -> Result<Deposit, Error> {
+    let (value, currency, recorded_at) = row;
+    Ok(Deposit {
+        auction_id,
+        user: user.clone(),
+        amount: Amount::new(
+            value,
+            currency
+                .parse()
+                .map_err(|_| Error::Repository(format!("Unknown currency {}", currency)))?,
+        ),
+        recorded_at,
+    })
+}
+
+#[async_trait]
+impl DepositRepository for PgDepositRepository {
+    async fn get_deposit(&self, auction_id: AuctionId, user: &UserId) -> Result<Option<Deposit>, Error> {
+        let row = sqlx::query_as::<_, DepositRow>(
+            r#"
+            SELECT amount_value, amount_currency, recorded_at
+            FROM auction_deposits
+            WHERE auction_id = $1 AND user_id = $2
+        "#,
+        )
+        .bind(auction_id.value())
+        .bind(user.value())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        row.map(|row| to_deposit(auction_id, user, row)).transpose()
+    }
+
+    async fn record(&self, deposit: Deposit) -> Result<Deposit, Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO auction_deposits (auction_id, user_id, amount_value, amount_currency, recorded_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (auction_id, user_id)
+            DO UPDATE SET amount_value = EXCLUDED.amount_value,
+                amount_currency = EXCLUDED.amount_currency,
+                recorded_at = EXCLUDED.recorded_at
+        "#,
+        )
+        .bind(deposit.auction_id.value())
+        .bind(deposit.user.value())
+        .bind(deposit.amount.value())
+        .bind(deposit.amount.currency().to_string())
+        .bind(deposit.recorded_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(deposit)
+    }
+}
+
+#[derive(Clone)]
+pub struct SqliteDepositRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteDepositRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DepositRepository for SqliteDepositRepository {
+    async fn get_deposit(&self, auction_id: AuctionId, user: &UserId) -> Result<Option<Deposit>, Error> {
+        let row = sqlx::query_as::<_, DepositRow>(
+            r#"
+            SELECT amount_value, amount_currency, recorded_at
+            FROM auction_deposits
+            WHERE auction_id = ?1 AND user_id = ?2
+        "#,
+        )
+        .bind(auction_id.value())
+        .bind(user.value())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        row.map(|row| to_deposit(auction_id, user, row)).transpose()
+    }
+
+    async fn record(&self, deposit: Deposit) -> Result<Deposit, Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO auction_deposits (auction_id, user_id, amount_value, amount_currency, recorded_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT (auction_id, user_id)
+            DO UPDATE SET amount_value = excluded.amount_value,
+                amount_currency = excluded.amount_currency,
+                recorded_at = excluded.recorded_at
+        "#,
+        )
+        .bind(deposit.auction_id.value())
+        .bind(deposit.user.value())
+        .bind(deposit.amount.value())
+        .bind(deposit.amount.currency().to_string())
+        .bind(deposit.recorded_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(deposit)
+    }
+}
+
+#[cfg(feature = "mysql")]
+#[derive(Clone)]
+pub struct MySqlDepositRepository {
+    pool: sqlx::MySqlPool,
+}
+
+#[cfg(feature = "mysql")]
+impl MySqlDepositRepository {
+    pub fn new(pool: sqlx::MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[cfg(feature = "mysql")]
+#[async_trait]
+impl DepositRepository for MySqlDepositRepository {
+    async fn get_deposit(&self, auction_id: AuctionId, user: &UserId) -> Result<Option<Deposit>, Error> {
+        let row = sqlx::query_as::<_, DepositRow>(
+            r#"
+            SELECT amount_value, amount_currency, recorded_at
+            FROM auction_deposits
+            WHERE auction_id = ? AND user_id = ?
+        "#,
+        )
+        .bind(auction_id.value())
+        .bind(user.value())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        row.map(|row| to_deposit(auction_id, user, row)).transpose()
+    }
+
+    async fn record(&self, deposit: Deposit) -> Result<Deposit, Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO auction_deposits (auction_id, user_id, amount_value, amount_currency, recorded_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE amount_value = VALUES(amount_value),
+                amount_currency = VALUES(amount_currency),
+                recorded_at = VALUES(recorded_at)
+        "#,
+        )
+        .bind(deposit.auction_id.value())
+        .bind(deposit.user.value())
+        .bind(deposit.amount.value())
+        .bind(deposit.amount.currency().to_string())
+        .bind(deposit.recorded_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(deposit)
+    }
+}
@@ -0,0 +1,487 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use sqlx::{PgConnection, PgPool};
+
+use crate::domain::models::{Auction, AuctionId, Bid, DomainEvent, Error, Errors, UserId};
+use crate::infrastructure::data::{changed_since_read, AuctionFilter, AuctionRepository, PlaceBid};
+
+/// What is appended to the stream of an auction. Placed bids and extensions are stored as
+/// the events themselves, any other change as the auction after it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum StoredAuctionEvent {
+    AuctionCreated { auction: Auction },
+    BidPlaced { bid: Bid },
+    AuctionExtended { ends_at: DateTime<Utc> },
+    AuctionChanged { auction: Auction },
+}
+
+impl StoredAuctionEvent {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            StoredAuctionEvent::AuctionCreated { .. } => "AuctionCreated",
+            StoredAuctionEvent::BidPlaced { .. } => "BidPlaced",
+            StoredAuctionEvent::AuctionExtended { .. } => "AuctionExtended",
+            StoredAuctionEvent::AuctionChanged { .. } => "AuctionChanged",
+        }
+    }
+
+    /// The events taking `existing` to `updated`. The bids placed and the extension are
+    /// replayed onto `existing`, and only stored as such when that gives `updated`.
+    pub fn between(existing: &Auction, updated: &Auction) -> Vec<StoredAuctionEvent> {
+        if existing == updated {
+            return Vec::new();
+        }
+        let mut replayed = existing.clone();
+        let mut events = Vec::new();
+        if updated.bids().starts_with(existing.bids()) {
+            for bid in &updated.bids()[existing.bids().len()..] {
+                replayed.replay(&DomainEvent::BidPlaced { auction_id: updated.auction_id(), bid: bid.clone() });
+                events.push(StoredAuctionEvent::BidPlaced { bid: bid.clone() });
+            }
+        }
+        if let (Auction::TimedAscending { ends_at: Some(ends_at), .. }, Auction::TimedAscending { ends_at: replayed_ends_at, .. }) =
+            (updated, &replayed)
+        {
+            if *replayed_ends_at != Some(*ends_at) {
+                replayed.replay(&DomainEvent::AuctionExtended { auction_id: updated.auction_id(), ends_at: *ends_at });
+                events.push(StoredAuctionEvent::AuctionExtended { ends_at: *ends_at });
+            }
+        }
+        if replayed == *updated {
+            events
+        } else {
+            vec![StoredAuctionEvent::AuctionChanged { auction: updated.clone() }]
+        }
+    }
+
+    /// Rebuilds the auction from its stream, oldest event first.
+    pub fn rebuild(events: impl IntoIterator<Item = StoredAuctionEvent>) -> Option<Auction> {
+        let mut auction: Option<Auction> = None;
+        for event in events {
+            match (event, auction.as_mut()) {
+                (StoredAuctionEvent::AuctionCreated { auction: stored }, _)
+                | (StoredAuctionEvent::AuctionChanged { auction: stored }, _) => auction = Some(stored),
+                (StoredAuctionEvent::BidPlaced { bid }, Some(current)) => {
+                    current.replay(&DomainEvent::BidPlaced { auction_id: current.auction_id(), bid });
+                }
+                (StoredAuctionEvent::AuctionExtended { ends_at }, Some(current)) => {
+                    current.replay(&DomainEvent::AuctionExtended { auction_id: current.auction_id(), ends_at });
+                }
+                // A stream starts with the auction being created
+                (_, None) => {}
+            }
+        }
+        auction
+    }
+}
+
+/// Stores each auction as a stream of [`StoredAuctionEvent`]s and rebuilds it on read, instead
+/// of working out which bids were added from the tables of [`super::PgAuctionRepository`].
+/// The seller, external reference, status, number of bids and users referred to are kept on
+/// the stream, to look auctions up and count them.
+#[derive(Clone)]
+pub struct EventSourcedAuctionRepository {
+    pool: PgPool,
+}
+
+impl EventSourcedAuctionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn repository_error(e: sqlx::Error) -> Error {
+    Error::Repository(e.to_string())
+}
+
+/// Rebuilds the auction from the events of its stream ordered by version, at the version of
+/// the last event.
+fn rebuild_stream(events: Vec<(i32, Json<StoredAuctionEvent>)>) -> Option<Auction> {
+    let version = events.last().map_or(0, |(version, _)| *version);
+    let mut auction = StoredAuctionEvent::rebuild(events.into_iter().map(|(_, event)| event.0))?;
+    auction.set_version(version);
+    Some(auction)
+}
+
+async fn load_auction(conn: &mut PgConnection, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+    let events = sqlx::query_as::<_, (i32, Json<StoredAuctionEvent>)>(
+        "SELECT version, payload FROM auction_stream_events WHERE auction_id = $1 ORDER BY version",
+    )
+    .bind(auction_id.value())
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(repository_error)?;
+    Ok(rebuild_stream(events))
+}
+
+/// Rebuilds every auction in rows ordered by auction and version.
+fn rebuild_all(rows: Vec<(i64, i32, Json<StoredAuctionEvent>)>) -> Vec<Auction> {
+    let mut auctions = Vec::new();
+    let mut rows = rows.into_iter().peekable();
+    while let Some((auction_id, version, first)) = rows.next() {
+        let mut events = vec![(version, first)];
+        while let Some((_, version, event)) = rows.next_if(|(next_id, _, _)| *next_id == auction_id) {
+            events.push((version, event));
+        }
+        auctions.extend(rebuild_stream(events));
+    }
+    auctions
+}
+
+/// Appends the events after `version`, returning the version of the last one.
+async fn append_events(
+    conn: &mut PgConnection,
+    auction_id: AuctionId,
+    version: i32,
+    events: Vec<StoredAuctionEvent>,
+) -> Result<i32, Error> {
+    let mut version = version;
+    for event in events {
+        version += 1;
+        sqlx::query(
+            "INSERT INTO auction_stream_events (auction_id, version, event_type, payload) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(auction_id.value())
+        .bind(version)
+        .bind(event.event_type())
+        .bind(Json(&event))
+        .execute(&mut *conn)
+        .await
+        .map_err(repository_error)?;
+    }
+    Ok(version)
+}
+
+/// Keeps the columns of the stream in line with the auction.
+async fn update_stream(conn: &mut PgConnection, auction: &Auction, version: i32) -> Result<(), Error> {
+    let participants: Vec<String> = auction.referred_users().iter().map(|user| user.value().to_string()).collect();
+    sqlx::query(
+        r#"
+        UPDATE auction_streams
+        SET user_id = $2, status = $3, bid_count = $4, participants = $5, version = $6
+        WHERE id = $1
+    "#,
+    )
+    .bind(auction.auction_id().value())
+    .bind(auction.user().value())
+    .bind(auction.status().to_string())
+    .bind(auction.bids().len() as i64)
+    .bind(participants)
+    .bind(version)
+    .execute(&mut *conn)
+    .await
+    .map_err(repository_error)?;
+    Ok(())
+}
+
+/// Locks the stream for appending, returning its version.
+async fn lock_stream(conn: &mut PgConnection, auction_id: AuctionId) -> Result<Option<i32>, Error> {
+    sqlx::query_scalar::<_, i32>("SELECT version FROM auction_streams WHERE id = $1 FOR UPDATE")
+        .bind(auction_id.value())
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(repository_error)
+}
+
+#[async_trait]
+impl AuctionRepository for EventSourcedAuctionRepository {
+    async fn get_auction(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+        let mut conn = self.pool.acquire().await.map_err(repository_error)?;
+        load_auction(&mut conn, auction_id).await
+    }
+
+    async fn get_auctions(&self) -> Result<Vec<Auction>, Error> {
+        let rows = sqlx::query_as::<_, (i64, i32, Json<StoredAuctionEvent>)>(
+            "SELECT auction_id, version, payload FROM auction_stream_events ORDER BY auction_id, version",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(repository_error)?;
+        Ok(rebuild_all(rows))
+    }
+
+    async fn get_auctions_by_seller(&self, seller: &UserId) -> Result<Vec<Auction>, Error> {
+        let rows = sqlx::query_as::<_, (i64, i32, Json<StoredAuctionEvent>)>(
+            r#"
+            SELECT e.auction_id, e.version, e.payload
+            FROM auction_streams s
+            JOIN auction_stream_events e ON e.auction_id = s.id
+            WHERE s.user_id = $1
+            ORDER BY e.auction_id, e.version
+        "#,
+        )
+        .bind(seller.value())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(repository_error)?;
+        Ok(rebuild_all(rows))
+    }
+
+    async fn get_auctions_by_ids(&self, auction_ids: &[AuctionId]) -> Result<Vec<Auction>, Error> {
+        let ids: Vec<i64> = auction_ids.iter().map(|id| id.value()).collect();
+        let rows = sqlx::query_as::<_, (i64, i32, Json<StoredAuctionEvent>)>(
+            r#"
+            SELECT auction_id, version, payload
+            FROM auction_stream_events
+            WHERE auction_id = ANY($1)
+            ORDER BY auction_id, version
+        "#,
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(repository_error)?;
+        let mut found: HashMap<AuctionId, Auction> = rebuild_all(rows)
+            .into_iter()
+            .map(|auction| (auction.auction_id(), auction))
+            .collect();
+        Ok(auction_ids.iter().filter_map(|id| found.remove(id)).collect())
+    }
+
+    async fn get_auction_by_external_reference(
+        &self,
+        user: &UserId,
+        external_reference: &str,
+    ) -> Result<Option<Auction>, Error> {
+        let mut conn = self.pool.acquire().await.map_err(repository_error)?;
+        let id = sqlx::query_scalar::<_, i64>(
+            "SELECT id FROM auction_streams WHERE user_id = $1 AND external_reference = $2",
+        )
+        .bind(user.value())
+        .bind(external_reference)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(repository_error)?;
+        match id {
+            Some(id) => load_auction(&mut conn, AuctionId::new(id)).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn create_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        let mut tx = self.pool.begin().await.map_err(repository_error)?;
+        let id = sqlx::query_scalar::<_, i64>(
+            r#"
+            INSERT INTO auction_streams (user_id, external_reference, status)
+            VALUES ($1, $2, $3)
+            RETURNING id
+        "#,
+        )
+        .bind(auction.user().value())
+        .bind(auction.external_reference())
+        .bind(auction.status().to_string())
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match e.as_database_error() {
+            // Only the external reference is unique besides the generated id
+            Some(db_error) if db_error.is_unique_violation() => {
+                Error::Validation(Errors::AuctionAlreadyExists)
+            }
+            _ => repository_error(e),
+        })?;
+        let mut new_auction = auction;
+        new_auction.set_auction_id(AuctionId::new(id));
+        let created = StoredAuctionEvent::AuctionCreated { auction: new_auction.clone() };
+        let version = append_events(&mut tx, new_auction.auction_id(), 0, vec![created]).await?;
+        update_stream(&mut tx, &new_auction, version).await?;
+        new_auction.set_version(version);
+        tx.commit().await.map_err(repository_error)?;
+        Ok(new_auction)
+    }
+
+    async fn update_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        let not_found = || Error::NotFound(format!("Auction with ID {} not found", auction.auction_id()));
+        let mut tx = self.pool.begin().await.map_err(repository_error)?;
+        let version = lock_stream(&mut tx, auction.auction_id()).await?.ok_or_else(not_found)?;
+        if version != auction.version() {
+            return Err(changed_since_read(auction.auction_id()));
+        }
+        let existing = load_auction(&mut tx, auction.auction_id()).await?.ok_or_else(not_found)?;
+        if existing.status() != auction.status() && !existing.status().can_transition_to(auction.status()) {
+            return Err(Error::Validation(Errors::IllegalStatusTransition));
+        }
+        let events = StoredAuctionEvent::between(&existing, &auction);
+        let mut auction = auction;
+        if !events.is_empty() {
+            let version = append_events(&mut tx, auction.auction_id(), version, events).await?;
+            update_stream(&mut tx, &auction, version).await?;
+            auction.set_version(version);
+        }
+        tx.commit().await.map_err(repository_error)?;
+        Ok(auction)
+    }
+
+    async fn add_bid(
+        &self,
+        auction_id: AuctionId,
+        version: i32,
+        bid: &Bid,
+        ends_at: Option<DateTime<Utc>>,
+    ) -> Result<(), Error> {
+        let not_found = || Error::NotFound(format!("Auction with ID {} not found", auction_id));
+        let mut tx = self.pool.begin().await.map_err(repository_error)?;
+        if lock_stream(&mut tx, auction_id).await?.ok_or_else(not_found)? != version {
+            return Err(changed_since_read(auction_id));
+        }
+        let mut auction = load_auction(&mut tx, auction_id).await?.ok_or_else(not_found)?;
+        let mut events = vec![StoredAuctionEvent::BidPlaced { bid: bid.clone() }];
+        auction.replay(&DomainEvent::BidPlaced { auction_id, bid: bid.clone() });
+        if let Some(ends_at) = ends_at {
+            events.push(StoredAuctionEvent::AuctionExtended { ends_at });
+            auction.replay(&DomainEvent::AuctionExtended { auction_id, ends_at });
+        }
+        let version = append_events(&mut tx, auction_id, version, events).await?;
+        update_stream(&mut tx, &auction, version).await?;
+        tx.commit().await.map_err(repository_error)?;
+        Ok(())
+    }
+
+    async fn place_bid(&self, auction_id: AuctionId, place: PlaceBid) -> Result<Vec<DomainEvent>, Error> {
+        let not_found = || Error::NotFound(format!("Auction with ID {} not found", auction_id));
+        let mut tx = self.pool.begin().await.map_err(repository_error)?;
+        // Other bids on the auction wait for this one to be appended before they read the stream
+        let version = lock_stream(&mut tx, auction_id).await?.ok_or_else(not_found)?;
+        let existing = load_auction(&mut tx, auction_id).await?.ok_or_else(not_found)?;
+        let mut auction = existing.clone();
+        let events = place(&mut auction).map_err(Error::Validation)?;
+        let version = append_events(&mut tx, auction_id, version, StoredAuctionEvent::between(&existing, &auction)).await?;
+        update_stream(&mut tx, &auction, version).await?;
+        tx.commit().await.map_err(repository_error)?;
+        Ok(events)
+    }
+
+    async fn auction_exists(&self, auction_id: AuctionId) -> Result<bool, Error> {
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM auction_streams WHERE id = $1)")
+            .bind(auction_id.value())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(repository_error)
+    }
+
+    async fn count_auctions(&self, filter: &AuctionFilter) -> Result<i64, Error> {
+        sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM auction_streams
+            WHERE ($1::TEXT IS NULL OR user_id = $1)
+                AND ($2::TEXT IS NULL OR status = $2)
+                AND ($3::TEXT IS NULL OR status <> $3)
+        "#,
+        )
+        .bind(filter.seller.as_ref().map(|seller| seller.value()))
+        .bind(filter.status.map(|status| status.to_string()))
+        .bind(filter.hidden_status())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(repository_error)
+    }
+
+    async fn count_bids(&self, auction_id: AuctionId) -> Result<i64, Error> {
+        let count = sqlx::query_scalar::<_, i64>("SELECT bid_count FROM auction_streams WHERE id = $1")
+            .bind(auction_id.value())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(repository_error)?;
+        Ok(count.unwrap_or(0))
+    }
+
+    /// Earlier events would still refer to the user, so the stream of every auction referring
+    /// to them is replaced by the anonymized auction.
+    async fn anonymize_user(&self, user: &UserId, pseudonym: &UserId) -> Result<Vec<AuctionId>, Error> {
+        let mut tx = self.pool.begin().await.map_err(repository_error)?;
+        let streams = sqlx::query_as::<_, (i64, i32)>(
+            "SELECT id, version FROM auction_streams WHERE $1 = ANY(participants) ORDER BY id FOR UPDATE",
+        )
+        .bind(user.value())
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(repository_error)?;
+        let mut anonymized = Vec::new();
+        for (id, version) in streams {
+            let auction_id = AuctionId::new(id);
+            let Some(mut auction) = load_auction(&mut tx, auction_id).await? else {
+                continue;
+            };
+            if !auction.anonymize_user(user, pseudonym) {
+                continue;
+            }
+            sqlx::query("DELETE FROM auction_stream_events WHERE auction_id = $1")
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .map_err(repository_error)?;
+            let changed = StoredAuctionEvent::AuctionChanged { auction: auction.clone() };
+            let version = append_events(&mut tx, auction_id, version, vec![changed]).await?;
+            update_stream(&mut tx, &auction, version).await?;
+            anonymized.push(auction_id);
+        }
+        tx.commit().await.map_err(repository_error)?;
+        Ok(anonymized)
+    }
+}
+
+#[cfg(test)]
+mod event_sourced_repository_tests {
+    use super::*;
+    use chrono::Duration;
+    use testcontainers_modules::postgres::Postgres;
+    use testcontainers_modules::testcontainers::runners::AsyncRunner;
+    use crate::domain::models::{Amount, BidData, CurrencyCode};
+    use crate::infrastructure::data::auction_repository_conformance::{check_auction_repository, sample_auction, starts_at};
+    use crate::infrastructure::run_migrations;
+
+    fn bid(user: &str, amount: i64) -> BidData {
+        let at = starts_at() + Duration::hours(1);
+        BidData {
+            user: UserId::new(user),
+            amount: Amount::new(amount, CurrencyCode::SEK),
+            at,
+            quantity: 1,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_placed_bids_are_stored_as_events() {
+        let existing = sample_auction(None);
+        let mut updated = existing.clone();
+        updated.try_add_bid(starts_at() + Duration::hours(1), bid("buyer1", 10)).unwrap();
+        updated.try_add_bid(starts_at() + Duration::hours(1), bid("buyer2", 20)).unwrap();
+
+        let events = StoredAuctionEvent::between(&existing, &updated);
+
+        let types: Vec<&str> = events.iter().map(|event| event.event_type()).collect();
+        assert_eq!(types, vec!["BidPlaced", "BidPlaced"]);
+        let created = StoredAuctionEvent::AuctionCreated { auction: existing };
+        assert_eq!(StoredAuctionEvent::rebuild(std::iter::once(created).chain(events)), Some(updated));
+    }
+
+    #[test]
+    fn test_other_changes_are_stored_as_the_changed_auction() {
+        let existing = sample_auction(None);
+        let mut updated = existing.clone();
+        updated.cancel(starts_at() - Duration::hours(1), Some("Withdrawn".to_string())).unwrap();
+
+        let events = StoredAuctionEvent::between(&existing, &updated);
+
+        assert_eq!(events, vec![StoredAuctionEvent::AuctionChanged { auction: updated.clone() }]);
+        assert!(StoredAuctionEvent::between(&updated, &updated).is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_with_postgres() {
+        let container = Postgres::default().start().await.unwrap();
+        let host_ip = container.get_host().await.unwrap();
+        let host_port = container.get_host_port_ipv4(5432).await.unwrap();
+        let url = format!("postgresql://postgres:postgres@{}:{}/postgres", host_ip, host_port);
+        let pool = PgPool::connect(&url).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+
+        check_auction_repository(&EventSourcedAuctionRepository::new(pool)).await;
+    }
+}
@@ -0,0 +1,182 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::models::{Auction, AuctionId, Bid, DomainEvent, Error, UserId};
+use crate::infrastructure::data::{AuctionFilter, AuctionPage, AuctionRepository, AuctionStandings, AuctionSummary, BidPage, PlaceBid};
+use crate::infrastructure::services::FaultInjector;
+
+/// Injects latency and repository errors in front of another auction repository.
+#[derive(Clone)]
+pub struct FaultInjectingAuctionRepository {
+    inner: Box<dyn AuctionRepository>,
+    faults: FaultInjector,
+}
+
+impl FaultInjectingAuctionRepository {
+    pub fn new(inner: Box<dyn AuctionRepository>, faults: FaultInjector) -> Self {
+        Self { inner, faults }
+    }
+
+    async fn inject(&self, operation: &str) -> Result<(), Error> {
+        if self.faults.delay_repository_call() {
+            tokio::time::sleep(self.faults.latency()).await;
+        }
+        if self.faults.fail_repository_call() {
+            log::warn!("Fault injection: failing repository call {}", operation);
+            return Err(Error::Repository(format!("Injected fault in {}", operation)));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AuctionRepository for FaultInjectingAuctionRepository {
+    async fn get_auction(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+        self.inject("get_auction").await?;
+        self.inner.get_auction(auction_id).await
+    }
+
+    async fn get_auction_without_bids(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+        self.inject("get_auction_without_bids").await?;
+        self.inner.get_auction_without_bids(auction_id).await
+    }
+
+    async fn get_auction_standings(&self, auction_id: AuctionId) -> Result<Option<AuctionStandings>, Error> {
+        self.inject("get_auction_standings").await?;
+        self.inner.get_auction_standings(auction_id).await
+    }
+
+    async fn get_auction_summary(&self, auction_id: AuctionId) -> Result<Option<AuctionSummary>, Error> {
+        self.inject("get_auction_summary").await?;
+        self.inner.get_auction_summary(auction_id).await
+    }
+
+    async fn get_auctions(&self) -> Result<Vec<Auction>, Error> {
+        self.inject("get_auctions").await?;
+        self.inner.get_auctions().await
+    }
+
+    async fn get_auctions_page(&self, limit: i64, offset: i64, filter: &AuctionFilter) -> Result<AuctionPage, Error> {
+        self.inject("get_auctions_page").await?;
+        self.inner.get_auctions_page(limit, offset, filter).await
+    }
+
+    async fn get_auction_summaries(
+        &self,
+        limit: i64,
+        offset: i64,
+        filter: &AuctionFilter,
+    ) -> Result<AuctionPage<AuctionSummary>, Error> {
+        self.inject("get_auction_summaries").await?;
+        self.inner.get_auction_summaries(limit, offset, filter).await
+    }
+
+    async fn get_active_auctions(&self, now: DateTime<Utc>) -> Result<Vec<Auction>, Error> {
+        self.inject("get_active_auctions").await?;
+        self.inner.get_active_auctions(now).await
+    }
+
+    async fn get_bids_page(&self, auction_id: AuctionId, after_bid_id: Option<i64>, limit: i64) -> Result<BidPage, Error> {
+        self.inject("get_bids_page").await?;
+        self.inner.get_bids_page(auction_id, after_bid_id, limit).await
+    }
+
+    async fn get_auctions_by_seller(&self, seller: &UserId) -> Result<Vec<Auction>, Error> {
+        self.inject("get_auctions_by_seller").await?;
+        self.inner.get_auctions_by_seller(seller).await
+    }
+
+    async fn get_auctions_by_ids(&self, auction_ids: &[AuctionId]) -> Result<Vec<Auction>, Error> {
+        self.inject("get_auctions_by_ids").await?;
+        self.inner.get_auctions_by_ids(auction_ids).await
+    }
+
+    async fn get_auction_by_external_reference(
+        &self,
+        user: &UserId,
+        external_reference: &str,
+    ) -> Result<Option<Auction>, Error> {
+        self.inject("get_auction_by_external_reference").await?;
+        self.inner
+            .get_auction_by_external_reference(user, external_reference)
+            .await
+    }
+
+    async fn create_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        self.inject("create_auction").await?;
+        self.inner.create_auction(auction).await
+    }
+
+    async fn update_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        self.inject("update_auction").await?;
+        self.inner.update_auction(auction).await
+    }
+
+    async fn add_bid(
+        &self,
+        auction_id: AuctionId,
+        version: i32,
+        bid: &Bid,
+        ends_at: Option<DateTime<Utc>>,
+    ) -> Result<(), Error> {
+        self.inject("add_bid").await?;
+        self.inner.add_bid(auction_id, version, bid, ends_at).await
+    }
+
+    async fn place_bid(&self, auction_id: AuctionId, place: PlaceBid) -> Result<Vec<DomainEvent>, Error> {
+        self.inject("place_bid").await?;
+        self.inner.place_bid(auction_id, place).await
+    }
+
+    async fn auction_exists(&self, auction_id: AuctionId) -> Result<bool, Error> {
+        self.inject("auction_exists").await?;
+        self.inner.auction_exists(auction_id).await
+    }
+
+    async fn count_auctions(&self, filter: &AuctionFilter) -> Result<i64, Error> {
+        self.inject("count_auctions").await?;
+        self.inner.count_auctions(filter).await
+    }
+
+    async fn count_bids(&self, auction_id: AuctionId) -> Result<i64, Error> {
+        self.inject("count_bids").await?;
+        self.inner.count_bids(auction_id).await
+    }
+
+    async fn anonymize_user(&self, user: &UserId, pseudonym: &UserId) -> Result<Vec<AuctionId>, Error> {
+        self.inject("anonymize_user").await?;
+        self.inner.anonymize_user(user, pseudonym).await
+    }
+}
+
+#[cfg(test)]
+mod fault_injecting_repository_tests {
+    use super::*;
+    use crate::domain::services::OsRandomSource;
+    use crate::infrastructure::config::FaultInjectionConfig;
+    use crate::infrastructure::data::InMemoryAuctionRepository;
+
+    fn repository(repository_error_percent: f64) -> FaultInjectingAuctionRepository {
+        FaultInjectingAuctionRepository::new(
+            Box::new(InMemoryAuctionRepository::default()),
+            FaultInjector::new(
+                FaultInjectionConfig {
+                    enabled: true,
+                    repository_error_percent,
+                    ..FaultInjectionConfig::default()
+                },
+                Box::new(OsRandomSource),
+            ),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_calls_fail_when_injected() {
+        assert!(matches!(repository(100.0).get_auctions().await, Err(Error::Repository(_))));
+    }
+
+    #[tokio::test]
+    async fn test_calls_pass_through_otherwise() {
+        assert_eq!(repository(0.0).get_auctions().await.unwrap(), Vec::new());
+    }
+}
@@ -0,0 +1,275 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+
+use crate::domain::models::{Auction, AuctionId, Bid, DomainEvent, Error, Errors, UserId};
+use crate::infrastructure::data::{changed_since_read, AuctionFilter, AuctionRepository, PlaceBid};
+
+/// Keeps auctions in process memory, for running without a database (e.g. contract tests).
+#[derive(Clone, Default)]
+pub struct InMemoryAuctionRepository {
+    auctions: Arc<Mutex<Vec<Auction>>>,
+}
+
+impl InMemoryAuctionRepository {
+    pub fn new(auctions: Vec<Auction>) -> Self {
+        Self {
+            auctions: Arc::new(Mutex::new(auctions)),
+        }
+    }
+}
+
+#[async_trait]
+impl AuctionRepository for InMemoryAuctionRepository {
+    async fn get_auction(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+        let auctions = self.auctions.lock().unwrap();
+        Ok(auctions.iter().find(|a| a.auction_id() == auction_id).cloned())
+    }
+
+    async fn get_auctions(&self) -> Result<Vec<Auction>, Error> {
+        Ok(self.auctions.lock().unwrap().clone())
+    }
+
+    async fn get_auctions_by_seller(&self, seller: &UserId) -> Result<Vec<Auction>, Error> {
+        let auctions = self.auctions.lock().unwrap();
+        Ok(auctions.iter().filter(|a| a.user() == seller).cloned().collect())
+    }
+
+    async fn get_auctions_by_ids(&self, auction_ids: &[AuctionId]) -> Result<Vec<Auction>, Error> {
+        let auctions = self.auctions.lock().unwrap();
+        Ok(auction_ids
+            .iter()
+            .filter_map(|id| auctions.iter().find(|a| a.auction_id() == *id).cloned())
+            .collect())
+    }
+
+    async fn get_auction_by_external_reference(
+        &self,
+        user: &UserId,
+        external_reference: &str,
+    ) -> Result<Option<Auction>, Error> {
+        let auctions = self.auctions.lock().unwrap();
+        Ok(auctions
+            .iter()
+            .find(|a| a.user() == user && a.external_reference() == Some(external_reference))
+            .cloned())
+    }
+
+    async fn create_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        let mut auctions = self.auctions.lock().unwrap();
+        if let Some(reference) = auction.external_reference() {
+            let duplicate = auctions
+                .iter()
+                .any(|a| a.user() == auction.user() && a.external_reference() == Some(reference));
+            if duplicate {
+                return Err(Error::Validation(Errors::AuctionAlreadyExists));
+            }
+        }
+        let next_id = auctions
+            .iter()
+            .map(|a| a.auction_id().value())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let mut new_auction = auction;
+        new_auction.set_auction_id(AuctionId::new(next_id));
+        new_auction.set_version(0);
+        auctions.push(new_auction.clone());
+        Ok(new_auction)
+    }
+
+    async fn update_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        let mut auctions = self.auctions.lock().unwrap();
+        match auctions.iter_mut().find(|a| a.auction_id() == auction.auction_id()) {
+            Some(existing) => {
+                if existing.version() != auction.version() {
+                    return Err(changed_since_read(auction.auction_id()));
+                }
+                if existing.status() != auction.status()
+                    && !existing.status().can_transition_to(auction.status())
+                {
+                    return Err(Error::Validation(Errors::IllegalStatusTransition));
+                }
+                let mut auction = auction;
+                auction.set_version(existing.version() + 1);
+                *existing = auction.clone();
+                Ok(auction)
+            }
+            None => Err(Error::NotFound(format!(
+                "Auction with ID {} not found",
+                auction.auction_id()
+            ))),
+        }
+    }
+
+    async fn add_bid(
+        &self,
+        auction_id: AuctionId,
+        version: i32,
+        bid: &Bid,
+        ends_at: Option<DateTime<Utc>>,
+    ) -> Result<(), Error> {
+        let mut auctions = self.auctions.lock().unwrap();
+        let auction = auctions
+            .iter_mut()
+            .find(|a| a.auction_id() == auction_id)
+            .ok_or_else(|| Error::NotFound(format!("Auction with ID {} not found", auction_id)))?;
+        if auction.version() != version {
+            return Err(changed_since_read(auction_id));
+        }
+        auction.set_version(version + 1);
+        auction.replay(&DomainEvent::BidPlaced { auction_id, bid: bid.clone() });
+        if let Some(ends_at) = ends_at {
+            auction.replay(&DomainEvent::AuctionExtended { auction_id, ends_at });
+        }
+        Ok(())
+    }
+
+    async fn place_bid(&self, auction_id: AuctionId, place: PlaceBid) -> Result<Vec<DomainEvent>, Error> {
+        let mut auctions = self.auctions.lock().unwrap();
+        let existing = auctions
+            .iter_mut()
+            .find(|a| a.auction_id() == auction_id)
+            .ok_or_else(|| Error::NotFound(format!("Auction with ID {} not found", auction_id)))?;
+        let mut auction = existing.clone();
+        let events = place(&mut auction).map_err(Error::Validation)?;
+        auction.set_version(existing.version() + 1);
+        *existing = auction;
+        Ok(events)
+    }
+
+    async fn auction_exists(&self, auction_id: AuctionId) -> Result<bool, Error> {
+        let auctions = self.auctions.lock().unwrap();
+        Ok(auctions.iter().any(|a| a.auction_id() == auction_id))
+    }
+
+    async fn count_auctions(&self, filter: &AuctionFilter) -> Result<i64, Error> {
+        let auctions = self.auctions.lock().unwrap();
+        Ok(auctions.iter().filter(|a| filter.matches(a)).count() as i64)
+    }
+
+    async fn count_bids(&self, auction_id: AuctionId) -> Result<i64, Error> {
+        let auctions = self.auctions.lock().unwrap();
+        Ok(auctions
+            .iter()
+            .find(|a| a.auction_id() == auction_id)
+            .map_or(0, |a| a.bids().len() as i64))
+    }
+
+    async fn anonymize_user(&self, user: &UserId, pseudonym: &UserId) -> Result<Vec<AuctionId>, Error> {
+        let mut auctions = self.auctions.lock().unwrap();
+        Ok(auctions
+            .iter_mut()
+            .filter_map(|a| a.anonymize_user(user, pseudonym).then(|| a.auction_id()))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod in_memory_repository_tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use crate::domain::commands::CreateAuctionCommand;
+    use crate::domain::models::{Amount, AuctionFactory, AuctionStatus, BidData, CurrencyCode};
+    use crate::infrastructure::data::auction_repository_conformance::check_auction_repository;
+
+    fn sample_auction(external_reference: Option<&str>) -> Auction {
+        AuctionFactory::create_auction(
+            CreateAuctionCommand {
+                title: "title".to_string(),
+                currency: CurrencyCode::SEK,
+                starts_at: Utc.with_ymd_and_hms(2016, 1, 1, 0, 0, 0).unwrap(),
+                ends_at: Utc.with_ymd_and_hms(2016, 2, 1, 0, 0, 0).unwrap(),
+                external_reference: external_reference.map(str::to_string),
+                ..CreateAuctionCommand::default()
+            },
+            UserId::new("seller"),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_conforms_to_auction_repository() {
+        check_auction_repository(&InMemoryAuctionRepository::new(Vec::new())).await;
+    }
+
+    #[tokio::test]
+    async fn test_create_assigns_ids_and_update_replaces() {
+        let repo = InMemoryAuctionRepository::default();
+        let first = repo.create_auction(sample_auction(None)).await.unwrap();
+        let mut second = repo.create_auction(sample_auction(None)).await.unwrap();
+        assert_eq!(first.auction_id(), AuctionId::new(1));
+        assert_eq!(second.auction_id(), AuctionId::new(2));
+
+        second.set_open_bidders(true);
+        let second = repo.update_auction(second).await.unwrap();
+        assert_eq!(repo.get_auction(second.auction_id()).await.unwrap(), Some(second));
+        assert_eq!(repo.get_auctions().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_external_reference_is_unique_per_seller() {
+        let repo = InMemoryAuctionRepository::default();
+        let created = repo.create_auction(sample_auction(Some("ref"))).await.unwrap();
+
+        let duplicate = repo.create_auction(sample_auction(Some("ref"))).await;
+        assert!(matches!(duplicate, Err(Error::Validation(Errors::AuctionAlreadyExists))));
+        let found = repo
+            .get_auction_by_external_reference(&UserId::new("seller"), "ref")
+            .await
+            .unwrap();
+        assert_eq!(found, Some(created));
+    }
+
+    #[tokio::test]
+    async fn test_counts_without_reading_auctions() {
+        let mut auction = sample_auction(None);
+        auction
+            .try_add_bid(
+                Utc.with_ymd_and_hms(2016, 1, 2, 0, 0, 0).unwrap(),
+                BidData {
+                    user: UserId::new("buyer"),
+                    amount: Amount::new(10, CurrencyCode::SEK),
+                    at: Utc.with_ymd_and_hms(2016, 1, 2, 0, 0, 0).unwrap(),
+                    quantity: 1,
+                    metadata: None,
+                },
+            )
+            .unwrap();
+        let repo = InMemoryAuctionRepository::default();
+        let first = repo.create_auction(auction).await.unwrap();
+        repo.create_auction(sample_auction(None)).await.unwrap();
+
+        assert!(repo.auction_exists(first.auction_id()).await.unwrap());
+        assert!(!repo.auction_exists(AuctionId::new(42)).await.unwrap());
+        assert_eq!(repo.count_bids(first.auction_id()).await.unwrap(), 1);
+        assert_eq!(repo.count_bids(AuctionId::new(42)).await.unwrap(), 0);
+        assert_eq!(repo.count_auctions(&AuctionFilter::default()).await.unwrap(), 2);
+        let by_seller = AuctionFilter {
+            seller: Some(UserId::new("seller")),
+            // Taking the bid opened the first auction
+            status: Some(AuctionStatus::Open),
+            published: true,
+        };
+        assert_eq!(repo.count_auctions(&by_seller).await.unwrap(), 1);
+        let by_other = AuctionFilter {
+            seller: Some(UserId::new("other")),
+            ..AuctionFilter::default()
+        };
+        assert_eq!(repo.count_auctions(&by_other).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_gets_auctions_by_ids_in_requested_order() {
+        let repo = InMemoryAuctionRepository::default();
+        let first = repo.create_auction(sample_auction(None)).await.unwrap();
+        let second = repo.create_auction(sample_auction(None)).await.unwrap();
+
+        let found = repo
+            .get_auctions_by_ids(&[second.auction_id(), AuctionId::new(42), first.auction_id()])
+            .await
+            .unwrap();
+
+        assert_eq!(found, vec![second, first]);
+    }
+}
@@ -0,0 +1,476 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use dyn_clone::DynClone;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use crate::domain::models::{AuctionId, Error};
+
+dyn_clone::clone_trait_object!(JobRepository);
+
+/// What a background job does for its auction, see [`crate::infrastructure::services::JobRunner`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobKind {
+    /// Works out the result of an auction once it has ended
+    Finalization,
+    /// Tells the seller and the winner about the result
+    Notification,
+    /// Posts the result to the configured webhook
+    Webhook,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobState {
+    Pending,
+    Running,
+    Succeeded,
+    /// Ran out of attempts, or failed in a way that retrying does not help
+    Failed,
+    Cancelled,
+}
+
+impl JobState {
+    /// A job left running by an instance that stopped is stuck, so it can be retried as well
+    pub const RETRYABLE: &'static [JobState] = &[JobState::Failed, JobState::Cancelled, JobState::Running];
+    pub const CANCELLABLE: &'static [JobState] = &[JobState::Pending, JobState::Failed];
+    pub const RESCHEDULABLE: &'static [JobState] = &[JobState::Pending, JobState::Failed, JobState::Cancelled];
+}
+
+impl fmt::Display for JobKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl FromStr for JobKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Finalization" => Ok(JobKind::Finalization),
+            "Notification" => Ok(JobKind::Notification),
+            "Webhook" => Ok(JobKind::Webhook),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for JobState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl FromStr for JobState {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Pending" => Ok(JobState::Pending),
+            "Running" => Ok(JobState::Running),
+            "Succeeded" => Ok(JobState::Succeeded),
+            "Failed" => Ok(JobState::Failed),
+            "Cancelled" => Ok(JobState::Cancelled),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Job {
+    pub id: i64,
+    pub kind: JobKind,
+    pub auction_id: AuctionId,
+    pub state: JobState,
+    /// When a pending job is due
+    pub run_at: DateTime<Utc>,
+    /// How many times the job was started
+    pub attempts: i32,
+    /// Error of the last attempt, `None` when it succeeded
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait JobRepository: Send + Sync + DynClone {
+    async fn enqueue(&self, kind: JobKind, auction_id: AuctionId, run_at: DateTime<Utc>, now: DateTime<Utc>)
+        -> Result<Job, Error>;
+    /// Jobs by id, optionally only those in `state`.
+    async fn get_jobs(&self, state: Option<JobState>) -> Result<Vec<Job>, Error>;
+    async fn get_job(&self, id: i64) -> Result<Option<Job>, Error>;
+    /// Marks up to `limit` pending jobs that are due as running and returns them, so that no
+    /// other runner picks them up.
+    async fn claim_due(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<Job>, Error>;
+    async fn complete(&self, id: i64, now: DateTime<Utc>) -> Result<(), Error>;
+    async fn fail(&self, id: i64, error: String, now: DateTime<Utc>) -> Result<(), Error>;
+    /// Puts a running job back as pending until `run_at`, keeping the error of the attempt.
+    async fn release(&self, id: i64, run_at: DateTime<Utc>, error: Option<String>, now: DateTime<Utc>)
+        -> Result<(), Error>;
+    /// Moves a job in one of the `from` states to `to`, due at `run_at` when given. `None` when
+    /// there is no such job, or it is in another state.
+    async fn transition(
+        &self,
+        id: i64,
+        from: &[JobState],
+        to: JobState,
+        run_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Job>, Error>;
+
+    /// Runs a failed, cancelled or stuck job again right away.
+    async fn retry(&self, id: i64, now: DateTime<Utc>) -> Result<Option<Job>, Error> {
+        self.transition(id, JobState::RETRYABLE, JobState::Pending, Some(now), now).await
+    }
+
+    async fn cancel(&self, id: i64, now: DateTime<Utc>) -> Result<Option<Job>, Error> {
+        self.transition(id, JobState::CANCELLABLE, JobState::Cancelled, None, now).await
+    }
+
+    async fn reschedule(&self, id: i64, run_at: DateTime<Utc>, now: DateTime<Utc>) -> Result<Option<Job>, Error> {
+        self.transition(id, JobState::RESCHEDULABLE, JobState::Pending, Some(run_at), now).await
+    }
+}
+
+/// Keeps jobs in process memory, so they are lost on restart.
+#[derive(Clone, Default)]
+pub struct InMemoryJobRepository {
+    jobs: Arc<Mutex<BTreeMap<i64, Job>>>,
+}
+
+impl InMemoryJobRepository {
+    fn update(&self, id: i64, now: DateTime<Utc>, change: impl FnOnce(&mut Job)) -> Result<(), Error> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs
+            .get_mut(&id)
+            .ok_or_else(|| Error::NotFound(format!("Job with ID {} not found", id)))?;
+        change(job);
+        job.updated_at = now;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl JobRepository for InMemoryJobRepository {
+    async fn enqueue(&self, kind: JobKind, auction_id: AuctionId, run_at: DateTime<Utc>, now: DateTime<Utc>)
+        -> Result<Job, Error> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let id = jobs.keys().next_back().map_or(1, |id| id + 1);
+        let job = Job {
+            id,
+            kind,
+            auction_id,
+            state: JobState::Pending,
+            run_at,
+            attempts: 0,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        };
+        jobs.insert(id, job.clone());
+        Ok(job)
+    }
+
+    async fn get_jobs(&self, state: Option<JobState>) -> Result<Vec<Job>, Error> {
+        Ok(self
+            .jobs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|job| state.is_none_or(|state| job.state == state))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_job(&self, id: i64) -> Result<Option<Job>, Error> {
+        Ok(self.jobs.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn claim_due(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<Job>, Error> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let mut due: Vec<&mut Job> = jobs
+            .values_mut()
+            .filter(|job| job.state == JobState::Pending && job.run_at <= now)
+            .collect();
+        due.sort_by_key(|job| job.run_at);
+        Ok(due
+            .into_iter()
+            .take(limit.max(0) as usize)
+            .map(|job| {
+                job.state = JobState::Running;
+                job.attempts += 1;
+                job.updated_at = now;
+                job.clone()
+            })
+            .collect())
+    }
+
+    async fn complete(&self, id: i64, now: DateTime<Utc>) -> Result<(), Error> {
+        self.update(id, now, |job| {
+            job.state = JobState::Succeeded;
+            job.last_error = None;
+        })
+    }
+
+    async fn fail(&self, id: i64, error: String, now: DateTime<Utc>) -> Result<(), Error> {
+        self.update(id, now, |job| {
+            job.state = JobState::Failed;
+            job.last_error = Some(error);
+        })
+    }
+
+    async fn release(&self, id: i64, run_at: DateTime<Utc>, error: Option<String>, now: DateTime<Utc>)
+        -> Result<(), Error> {
+        self.update(id, now, |job| {
+            job.state = JobState::Pending;
+            job.run_at = run_at;
+            job.last_error = error;
+        })
+    }
+
+    async fn transition(
+        &self,
+        id: i64,
+        from: &[JobState],
+        to: JobState,
+        run_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Job>, Error> {
+        let mut jobs = self.jobs.lock().unwrap();
+        Ok(jobs.get_mut(&id).filter(|job| from.contains(&job.state)).map(|job| {
+            job.state = to;
+            if let Some(run_at) = run_at {
+                job.run_at = run_at;
+            }
+            job.updated_at = now;
+            job.clone()
+        }))
+    }
+}
+
+#[derive(Clone)]
+pub struct PgJobRepository {
+    pool: PgPool,
+}
+
+impl PgJobRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+type JobRow = (i64, String, i64, String, DateTime<Utc>, i32, Option<String>, DateTime<Utc>, DateTime<Utc>);
+
+fn to_job(row: JobRow) -> Result<Job, Error> {
+    let (id, kind, auction_id, state, run_at, attempts, last_error, created_at, updated_at) = row;
+    Ok(Job {
+        id,
+        kind: kind
+            .parse()
+            .map_err(|_| Error::Repository(format!("Unknown job kind {}", kind)))?,
+        auction_id: AuctionId::new(auction_id),
+        state: state
+            .parse()
+            .map_err(|_| Error::Repository(format!("Unknown job state {}", state)))?,
+        run_at,
+        attempts,
+        last_error,
+        created_at,
+        updated_at,
+    })
+}
+
+#[async_trait]
+impl JobRepository for PgJobRepository {
+    async fn enqueue(&self, kind: JobKind, auction_id: AuctionId, run_at: DateTime<Utc>, now: DateTime<Utc>)
+        -> Result<Job, Error> {
+        let row = sqlx::query_as::<_, JobRow>(
+            r#"
+            INSERT INTO background_jobs (kind, auction_id, state, run_at, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $5)
+            RETURNING id, kind, auction_id, state, run_at, attempts, last_error, created_at, updated_at
+        "#,
+        )
+        .bind(kind.to_string())
+        .bind(auction_id.value())
+        .bind(JobState::Pending.to_string())
+        .bind(run_at)
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        to_job(row)
+    }
+
+    async fn get_jobs(&self, state: Option<JobState>) -> Result<Vec<Job>, Error> {
+        let rows = sqlx::query_as::<_, JobRow>(
+            r#"
+            SELECT id, kind, auction_id, state, run_at, attempts, last_error, created_at, updated_at
+            FROM background_jobs
+            WHERE $1::TEXT IS NULL OR state = $1
+            ORDER BY id
+        "#,
+        )
+        .bind(state.map(|state| state.to_string()))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        rows.into_iter().map(to_job).collect()
+    }
+
+    async fn get_job(&self, id: i64) -> Result<Option<Job>, Error> {
+        let row = sqlx::query_as::<_, JobRow>(
+            "SELECT id, kind, auction_id, state, run_at, attempts, last_error, created_at, updated_at FROM background_jobs WHERE id = $1",
+        )
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        row.map(to_job).transpose()
+    }
+
+    async fn claim_due(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<Job>, Error> {
+        // Skipping locked rows lets several instances run jobs without waiting on each other
+        let rows = sqlx::query_as::<_, JobRow>(
+            r#"
+            UPDATE background_jobs
+            SET state = $2, attempts = attempts + 1, updated_at = $1
+            WHERE id IN (
+                SELECT id FROM background_jobs
+                WHERE state = $3 AND run_at <= $1
+                ORDER BY run_at
+                LIMIT $4
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, kind, auction_id, state, run_at, attempts, last_error, created_at, updated_at
+        "#,
+        )
+        .bind(now)
+        .bind(JobState::Running.to_string())
+        .bind(JobState::Pending.to_string())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        rows.into_iter().map(to_job).collect()
+    }
+
+    async fn complete(&self, id: i64, now: DateTime<Utc>) -> Result<(), Error> {
+        sqlx::query("UPDATE background_jobs SET state = $2, last_error = NULL, updated_at = $3 WHERE id = $1")
+            .bind(id)
+            .bind(JobState::Succeeded.to_string())
+            .bind(now)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn fail(&self, id: i64, error: String, now: DateTime<Utc>) -> Result<(), Error> {
+        sqlx::query("UPDATE background_jobs SET state = $2, last_error = $3, updated_at = $4 WHERE id = $1")
+            .bind(id)
+            .bind(JobState::Failed.to_string())
+            .bind(error)
+            .bind(now)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn release(&self, id: i64, run_at: DateTime<Utc>, error: Option<String>, now: DateTime<Utc>)
+        -> Result<(), Error> {
+        sqlx::query(
+            "UPDATE background_jobs SET state = $2, run_at = $3, last_error = $4, updated_at = $5 WHERE id = $1",
+        )
+        .bind(id)
+        .bind(JobState::Pending.to_string())
+        .bind(run_at)
+        .bind(error)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn transition(
+        &self,
+        id: i64,
+        from: &[JobState],
+        to: JobState,
+        run_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Job>, Error> {
+        let row = sqlx::query_as::<_, JobRow>(
+            r#"
+            UPDATE background_jobs
+            SET state = $3, run_at = COALESCE($4, run_at), updated_at = $5
+            WHERE id = $1 AND state = ANY($2)
+            RETURNING id, kind, auction_id, state, run_at, attempts, last_error, created_at, updated_at
+        "#,
+        )
+        .bind(id)
+        .bind(from.iter().map(|state| state.to_string()).collect::<Vec<_>>())
+        .bind(to.to_string())
+        .bind(run_at)
+        .bind(now)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        row.map(to_job).transpose()
+    }
+}
+
+#[cfg(test)]
+mod job_repository_tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2016, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_claims_due_jobs_once() {
+        let repository = InMemoryJobRepository::default();
+        let due = repository.enqueue(JobKind::Finalization, AuctionId::new(1), now(), now()).await.unwrap();
+        repository
+            .enqueue(JobKind::Finalization, AuctionId::new(2), now() + Duration::hours(1), now())
+            .await
+            .unwrap();
+
+        let claimed = repository.claim_due(now(), 10).await.unwrap();
+        assert_eq!(claimed.iter().map(|job| job.id).collect::<Vec<_>>(), vec![due.id]);
+        assert_eq!(claimed[0].state, JobState::Running);
+        assert_eq!(claimed[0].attempts, 1);
+        assert!(repository.claim_due(now(), 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_support_actions_depend_on_the_state() {
+        let repository = InMemoryJobRepository::default();
+        let job = repository.enqueue(JobKind::Webhook, AuctionId::new(1), now(), now()).await.unwrap();
+        let later = now() + Duration::hours(1);
+
+        // Only jobs that are not going to run can be retried
+        assert_eq!(repository.retry(job.id, now()).await.unwrap(), None);
+        let cancelled = repository.cancel(job.id, now()).await.unwrap().unwrap();
+        assert_eq!(cancelled.state, JobState::Cancelled);
+        assert_eq!(repository.cancel(job.id, now()).await.unwrap(), None);
+
+        let rescheduled = repository.reschedule(job.id, later, now()).await.unwrap().unwrap();
+        assert_eq!((rescheduled.state, rescheduled.run_at), (JobState::Pending, later));
+
+        repository.claim_due(later, 10).await.unwrap();
+        repository.fail(job.id, "unreachable".to_string(), later).await.unwrap();
+        let retried = repository.retry(job.id, later).await.unwrap().unwrap();
+        assert_eq!((retried.state, retried.last_error.as_deref()), (JobState::Pending, Some("unreachable")));
+
+        assert_eq!(repository.retry(42, now()).await.unwrap(), None);
+    }
+}
@@ -1,7 +1,86 @@
-use sqlx::{migrate::MigrateError, PgPool};
+use sqlx::migrate::{Migrate, MigrateError, Migrator};
+use sqlx::{PgPool, SqlitePool};
+
+/// Startup flag that rolls back the last migration applied to Postgres and exits, see
+/// [`rollback_last_migration`].
+pub const ROLLBACK_LAST_MIGRATION_FLAG: &str = "--rollback-last-migration";
+
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
 
 pub async fn run_migrations(pool: &PgPool) -> Result<(), MigrateError> {
-    return sqlx::migrate!("./migrations")
+    return MIGRATOR
         .run(pool)
         .await;
-}
\ No newline at end of file
+}
+
+/// Reverts the last migration applied, by its `.down.sql`, returning its version, or `None` when
+/// no migration has been applied. Meant for rolling back a broken deploy to the schema the
+/// previous release expects, one migration at a time.
+pub async fn rollback_last_migration(pool: &PgPool) -> Result<Option<i64>, MigrateError> {
+    let mut conn = pool.acquire().await?;
+    conn.ensure_migrations_table().await?;
+    let mut applied = conn.list_applied_migrations().await?;
+    drop(conn);
+    let Some(last) = applied.pop() else {
+        return Ok(None);
+    };
+    // Undoing only reverts migrations that have a down migration, and would succeed without it
+    if !MIGRATOR
+        .iter()
+        .any(|migration| migration.version == last.version && migration.migration_type.is_down_migration())
+    {
+        return Err(MigrateError::Source(
+            format!("migration {} has no down migration", last.version).into(),
+        ));
+    }
+    let target = applied.last().map_or(0, |migration| migration.version);
+    MIGRATOR.undo(pool, target).await?;
+    Ok(Some(last.version))
+}
+
+/// SQLite has a schema of its own, see [`super::SqliteAuctionRepository`].
+pub async fn run_sqlite_migrations(pool: &SqlitePool) -> Result<(), MigrateError> {
+    sqlx::migrate!("./migrations_sqlite").run(pool).await
+}
+
+/// MySQL and MariaDB have a schema of their own, see [`super::MySqlAuctionRepository`].
+#[cfg(feature = "mysql")]
+pub async fn run_mysql_migrations(pool: &sqlx::MySqlPool) -> Result<(), MigrateError> {
+    sqlx::migrate!("./migrations_mysql").run(pool).await
+}
+
+#[cfg(test)]
+mod migrations_tests {
+    use super::*;
+    use testcontainers_modules::postgres::Postgres;
+    use testcontainers_modules::testcontainers::runners::AsyncRunner;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_rolls_back_every_migration_with_postgres() {
+        let container = Postgres::default().start().await.unwrap();
+        let host_ip = container.get_host().await.unwrap();
+        let host_port = container.get_host_port_ipv4(5432).await.unwrap();
+        let url = format!("postgresql://postgres:postgres@{}:{}/postgres", host_ip, host_port);
+        let pool = PgPool::connect(&url).await.unwrap();
+        assert_eq!(rollback_last_migration(&pool).await.unwrap(), None);
+        run_migrations(&pool).await.unwrap();
+
+        let mut rolled_back = Vec::new();
+        while let Some(version) = rollback_last_migration(&pool).await.unwrap() {
+            rolled_back.push(version);
+        }
+        let mut versions: Vec<i64> = MIGRATOR
+            .iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .map(|migration| migration.version)
+            .collect();
+        versions.reverse();
+        assert_eq!(rolled_back, versions);
+
+        // Rolled back migrations apply again, on new sessions like those of the next deploy, as
+        // the temporary functions of a migration live on in the session that applied it
+        pool.close().await;
+        let pool = PgPool::connect(&url).await.unwrap();
+        run_migrations(&pool).await.unwrap();
+    }
+}
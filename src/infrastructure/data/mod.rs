@@ -1,7 +1,63 @@
+pub mod auction_archive;
+pub mod auction_event_listener;
+pub mod auction_options;
 pub mod auction_repository;
+#[cfg(test)]
+pub(crate) mod auction_repository_conformance;
+pub mod auction_result_repository;
+pub mod audit_repository;
+pub mod caching_auction_repository;
+pub mod currency_repository;
 pub mod database;
+pub mod job_repository;
+pub mod deposit_repository;
+pub mod deadline_auction_repository;
+pub mod event_sourced_auction_repository;
+pub mod fault_injecting_auction_repository;
+pub mod in_memory_auction_repository;
 pub mod migrations;
+#[cfg(feature = "mongodb")]
+pub mod mongo_auction_repository;
+#[cfg(feature = "mysql")]
+pub mod mysql_auction_repository;
+pub mod outbox;
+#[cfg(feature = "redis")]
+pub mod redis_auction_repository;
+pub mod registration_repository;
+#[cfg(test)]
+pub(crate) mod repository_conformance;
+pub mod rollup_repository;
+pub mod self_check;
+pub mod settlement_repository;
+pub mod sqlite_auction_repository;
+pub mod user_repository;
 
+pub use auction_archive::*;
+pub use auction_event_listener::*;
+pub use auction_options::*;
 pub use auction_repository::*;
+pub use auction_result_repository::*;
+pub use audit_repository::*;
+pub use caching_auction_repository::*;
+pub use currency_repository::*;
 pub use database::*;
+pub use deposit_repository::*;
+pub use deadline_auction_repository::*;
+pub use event_sourced_auction_repository::*;
+pub use fault_injecting_auction_repository::*;
+pub use in_memory_auction_repository::*;
+pub use job_repository::*;
 pub use migrations::*;
+#[cfg(feature = "mongodb")]
+pub use mongo_auction_repository::*;
+#[cfg(feature = "mysql")]
+pub use mysql_auction_repository::*;
+pub use outbox::*;
+#[cfg(feature = "redis")]
+pub use redis_auction_repository::*;
+pub use registration_repository::*;
+pub use rollup_repository::*;
+pub use self_check::*;
+pub use settlement_repository::*;
+pub use sqlite_auction_repository::*;
+pub use user_repository::*;
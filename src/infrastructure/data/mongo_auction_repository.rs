@@ -0,0 +1,682 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mongodb::bson::{bson, doc, from_document, to_bson, to_document, Bson, Document};
+use mongodb::error::{ErrorKind, WriteFailure};
+use mongodb::options::{IndexOptions, ReturnDocument};
+use mongodb::{Collection, Database, IndexModel};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::domain::models::{
+    Auction, AuctionId, AuctionResult, AuditEntry, Currency, CurrencyCode, Deposit, Error, Errors, Registration,
+    Settlement, User, UserId,
+};
+use crate::infrastructure::data::user_repository::{display_name, role};
+use crate::infrastructure::data::{
+    changed_since_read, AuctionFilter, AuctionRepository, AuctionResultRepository, AuditRepository, CurrencyRepository,
+    DepositRepository, RegistrationRepository, SettlementRepository, UserRepository,
+};
+
+/// Stores each auction as one document in the `auctions` collection, with the seller, external
+/// reference, status, number of bids and the users it refers to next to the aggregate, to look
+/// auctions up and count them. Ids come from a counter, as MongoDB does not generate numbers.
+///
+/// Documents are written one at a time without transactions, which would need a replica set.
+#[derive(Clone)]
+pub struct MongoAuctionRepository {
+    auctions: Collection<Document>,
+    counters: Collection<Document>,
+}
+
+impl MongoAuctionRepository {
+    pub fn new(database: &Database) -> Self {
+        Self {
+            auctions: database.collection("auctions"),
+            counters: database.collection("counters"),
+        }
+    }
+
+    async fn next_id(&self) -> Result<i64, Error> {
+        let counter = self
+            .counters
+            .find_one_and_update(doc! { "_id": "auctions" }, doc! { "$inc": { "seq": 1_i64 } })
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .await
+            .map_err(repository_error)?
+            .ok_or_else(|| Error::Repository("The auction id counter is missing".to_string()))?;
+        counter.get_i64("seq").map_err(|e| Error::Repository(e.to_string()))
+    }
+
+    async fn find(&self, filter: Document) -> Result<Vec<Auction>, Error> {
+        let mut cursor = self
+            .auctions
+            .find(filter)
+            .sort(doc! { "_id": 1 })
+            .await
+            .map_err(repository_error)?;
+        let mut auctions = Vec::new();
+        while cursor.advance().await.map_err(repository_error)? {
+            let stored = cursor.deserialize_current().map_err(repository_error)?;
+            auctions.push(read_auction(stored)?);
+        }
+        Ok(auctions)
+    }
+}
+
+/// Creates the indexes and seeds the currencies, the counterpart of the SQL migrations.
+pub async fn prepare_mongo_database(database: &Database) -> Result<(), Error> {
+    let auctions = database.collection::<Document>("auctions");
+    let indexes = [
+        IndexModel::builder().keys(doc! { "user_id": 1 }).build(),
+        IndexModel::builder().keys(doc! { "participants": 1 }).build(),
+        // Auctions without an external reference leave the field out, and do not collide
+        IndexModel::builder()
+            .keys(doc! { "user_id": 1, "external_reference": 1 })
+            .options(
+                IndexOptions::builder()
+                    .unique(true)
+                    .partial_filter_expression(doc! { "external_reference": { "$type": "string" } })
+                    .build(),
+            )
+            .build(),
+    ];
+    auctions.create_indexes(indexes).await.map_err(repository_error)?;
+    for name in ["registrations", "deposits"] {
+        database
+            .collection::<Document>(name)
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "auction_id": 1, "user": 1 })
+                    .options(IndexOptions::builder().unique(true).build())
+                    .build(),
+            )
+            .await
+            .map_err(repository_error)?;
+    }
+    database
+        .collection::<Document>("audit_log")
+        .create_index(IndexModel::builder().keys(doc! { "auction_id": 1 }).build())
+        .await
+        .map_err(repository_error)?;
+
+    let currencies = database.collection::<Document>("currencies");
+    let seed = [
+        ("VAC", 1001, "Virtual auction currency", 0),
+        ("SEK", 752, "Swedish krona", 2),
+        ("DKK", 208, "Danish krone", 2),
+        ("EUR", 978, "Euro", 2),
+    ];
+    for (code, numeric_code, name, minor_units) in seed {
+        currencies
+            .update_one(
+                doc! { "_id": code },
+                doc! { "$setOnInsert": { "numeric_code": numeric_code, "name": name, "minor_units": minor_units, "enabled": true } },
+            )
+            .upsert(true)
+            .await
+            .map_err(repository_error)?;
+    }
+    Ok(())
+}
+
+fn repository_error(e: mongodb::error::Error) -> Error {
+    Error::Repository(e.to_string())
+}
+
+fn is_duplicate_key(e: &mongodb::error::Error) -> bool {
+    matches!(e.kind.as_ref(), ErrorKind::Write(WriteFailure::WriteError(write_error)) if write_error.code == 11000)
+}
+
+/// Records kept next to auctions are stored as they serialize.
+fn read_record<T: DeserializeOwned>(stored: Document) -> Result<T, Error> {
+    from_document(stored).map_err(|e| Error::Repository(format!("Invalid stored document: {}", e)))
+}
+
+fn write_record<T: Serialize>(record: &T) -> Result<Document, Error> {
+    to_document(record).map_err(|e| Error::Internal(e.to_string()))
+}
+
+fn read_auction(mut stored: Document) -> Result<Auction, Error> {
+    let auction = stored
+        .remove("auction")
+        .and_then(|auction| auction.as_document().cloned())
+        .ok_or_else(|| Error::Repository("Invalid stored auction: the document is missing".to_string()))?;
+    from_document(auction).map_err(|e| Error::Repository(format!("Invalid stored auction: {}", e)))
+}
+
+/// The aggregate and the fields derived from it.
+fn write_auction(auction: &Auction) -> Result<Document, Error> {
+    let mut stored = doc! {
+        "_id": auction.auction_id().value(),
+        "user_id": auction.user().value(),
+        "status": auction.status().to_string(),
+        "bid_count": auction.bids().len() as i64,
+        "participants": auction.referred_users().iter().map(|user| user.value()).collect::<Vec<_>>(),
+        "auction": to_document(auction).map_err(|e| Error::Internal(e.to_string()))?,
+    };
+    if let Some(external_reference) = auction.external_reference() {
+        stored.insert("external_reference", external_reference);
+    }
+    Ok(stored)
+}
+
+/// Matches the stored auction only while it is at the version of `auction`. Auctions stored
+/// before they had a version leave it out of the document.
+fn stored_at(auction: &Auction) -> Document {
+    let version = match auction.version() {
+        0 => bson!({ "$in": [0, Bson::Null] }),
+        version => bson!(version),
+    };
+    doc! { "_id": auction.auction_id().value(), "auction.version": version }
+}
+
+#[async_trait]
+impl AuctionRepository for MongoAuctionRepository {
+    async fn get_auction(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+        let stored = self
+            .auctions
+            .find_one(doc! { "_id": auction_id.value() })
+            .await
+            .map_err(repository_error)?;
+        stored.map(read_auction).transpose()
+    }
+
+    async fn get_auctions(&self) -> Result<Vec<Auction>, Error> {
+        self.find(doc! {}).await
+    }
+
+    async fn get_auctions_by_seller(&self, seller: &UserId) -> Result<Vec<Auction>, Error> {
+        self.find(doc! { "user_id": seller.value() }).await
+    }
+
+    async fn get_auctions_by_ids(&self, auction_ids: &[AuctionId]) -> Result<Vec<Auction>, Error> {
+        let ids: Vec<i64> = auction_ids.iter().map(|id| id.value()).collect();
+        let mut found: HashMap<AuctionId, Auction> = self
+            .find(doc! { "_id": { "$in": ids } })
+            .await?
+            .into_iter()
+            .map(|auction| (auction.auction_id(), auction))
+            .collect();
+        Ok(auction_ids.iter().filter_map(|id| found.remove(id)).collect())
+    }
+
+    async fn get_auction_by_external_reference(
+        &self,
+        user: &UserId,
+        external_reference: &str,
+    ) -> Result<Option<Auction>, Error> {
+        let stored = self
+            .auctions
+            .find_one(doc! { "user_id": user.value(), "external_reference": external_reference })
+            .await
+            .map_err(repository_error)?;
+        stored.map(read_auction).transpose()
+    }
+
+    async fn create_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        let mut new_auction = auction;
+        new_auction.set_auction_id(AuctionId::new(self.next_id().await?));
+        new_auction.set_version(0);
+        self.auctions
+            .insert_one(write_auction(&new_auction)?)
+            .await
+            .map_err(|e| {
+                // Only the external reference is unique besides the generated id
+                if is_duplicate_key(&e) {
+                    Error::Validation(Errors::AuctionAlreadyExists)
+                } else {
+                    repository_error(e)
+                }
+            })?;
+        Ok(new_auction)
+    }
+
+    async fn update_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        let existing = self
+            .get_auction(auction.auction_id())
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("Auction with ID {} not found", auction.auction_id())))?;
+        if existing.version() != auction.version() {
+            return Err(changed_since_read(auction.auction_id()));
+        }
+        if existing.status() != auction.status() && !existing.status().can_transition_to(auction.status()) {
+            return Err(Error::Validation(Errors::IllegalStatusTransition));
+        }
+        // Only replaces the auction at the version it was read at
+        let mut auction = auction;
+        auction.set_version(existing.version() + 1);
+        let replaced = self
+            .auctions
+            .replace_one(stored_at(&existing), write_auction(&auction)?)
+            .await
+            .map_err(repository_error)?;
+        if replaced.matched_count == 0 {
+            return Err(changed_since_read(auction.auction_id()));
+        }
+        Ok(auction)
+    }
+
+    async fn auction_exists(&self, auction_id: AuctionId) -> Result<bool, Error> {
+        let count = self
+            .auctions
+            .count_documents(doc! { "_id": auction_id.value() })
+            .await
+            .map_err(repository_error)?;
+        Ok(count > 0)
+    }
+
+    async fn count_auctions(&self, filter: &AuctionFilter) -> Result<i64, Error> {
+        let mut query = doc! {};
+        if let Some(seller) = &filter.seller {
+            query.insert("user_id", seller.value());
+        }
+        let mut status = doc! {};
+        if let Some(equal_to) = filter.status {
+            status.insert("$eq", equal_to.to_string());
+        }
+        if let Some(hidden) = filter.hidden_status() {
+            status.insert("$ne", hidden);
+        }
+        if !status.is_empty() {
+            query.insert("status", status);
+        }
+        let count = self.auctions.count_documents(query).await.map_err(repository_error)?;
+        Ok(count as i64)
+    }
+
+    async fn count_bids(&self, auction_id: AuctionId) -> Result<i64, Error> {
+        let stored = self
+            .auctions
+            .find_one(doc! { "_id": auction_id.value() })
+            .projection(doc! { "bid_count": 1 })
+            .await
+            .map_err(repository_error)?;
+        Ok(stored.and_then(|stored| stored.get_i64("bid_count").ok()).unwrap_or(0))
+    }
+
+    async fn anonymize_user(&self, user: &UserId, pseudonym: &UserId) -> Result<Vec<AuctionId>, Error> {
+        let mut anonymized = Vec::new();
+        for mut auction in self.find(doc! { "participants": user.value() }).await? {
+            if auction.anonymize_user(user, pseudonym) {
+                auction.set_version(auction.version() + 1);
+                self.auctions
+                    .replace_one(doc! { "_id": auction.auction_id().value() }, write_auction(&auction)?)
+                    .await
+                    .map_err(repository_error)?;
+                anonymized.push(auction.auction_id());
+            }
+        }
+        Ok(anonymized)
+    }
+}
+
+#[derive(Clone)]
+pub struct MongoCurrencyRepository {
+    currencies: Collection<Document>,
+}
+
+impl MongoCurrencyRepository {
+    pub fn new(database: &Database) -> Self {
+        Self {
+            currencies: database.collection("currencies"),
+        }
+    }
+}
+
+#[async_trait]
+impl CurrencyRepository for MongoCurrencyRepository {
+    async fn get_currencies(&self) -> Result<Vec<Currency>, Error> {
+        let mut cursor = self
+            .currencies
+            .find(doc! { "enabled": true })
+            .sort(doc! { "_id": 1 })
+            .await
+            .map_err(repository_error)?;
+        let mut currencies = Vec::new();
+        while cursor.advance().await.map_err(repository_error)? {
+            let stored = cursor.deserialize_current().map_err(repository_error)?;
+            let field_error = |e: mongodb::bson::document::ValueAccessError| Error::Repository(e.to_string());
+            let code = stored.get_str("_id").map_err(field_error)?;
+            currencies.push(Currency {
+                code: code.parse::<CurrencyCode>().map_err(|_| {
+                    Error::Repository(format!("get_currencies: Unknown currency code: {}", code))
+                })?,
+                numeric_code: stored.get_i32("numeric_code").map_err(field_error)?,
+                name: stored.get_str("name").map_err(field_error)?.to_string(),
+                minor_units: stored.get_i32("minor_units").map_err(field_error)? as i16,
+            });
+        }
+        Ok(currencies)
+    }
+}
+
+#[derive(Clone)]
+pub struct MongoRegistrationRepository {
+    registrations: Collection<Document>,
+}
+
+impl MongoRegistrationRepository {
+    pub fn new(database: &Database) -> Self {
+        Self {
+            registrations: database.collection("registrations"),
+        }
+    }
+}
+
+#[async_trait]
+impl RegistrationRepository for MongoRegistrationRepository {
+    async fn get_registration(&self, auction_id: AuctionId, user: &UserId) -> Result<Option<Registration>, Error> {
+        let stored = self
+            .registrations
+            .find_one(doc! { "auction_id": auction_id.value(), "user": user.value() })
+            .projection(doc! { "_id": 0 })
+            .await
+            .map_err(repository_error)?;
+        stored.map(read_record).transpose()
+    }
+
+    async fn get_registrations(&self, auction_id: AuctionId) -> Result<Vec<Registration>, Error> {
+        let mut cursor = self
+            .registrations
+            .find(doc! { "auction_id": auction_id.value() })
+            .projection(doc! { "_id": 0 })
+            .sort(doc! { "registered_at": 1, "user": 1 })
+            .await
+            .map_err(repository_error)?;
+        let mut registrations = Vec::new();
+        while cursor.advance().await.map_err(repository_error)? {
+            registrations.push(read_record(cursor.deserialize_current().map_err(repository_error)?)?);
+        }
+        Ok(registrations)
+    }
+
+    async fn save(&self, registration: Registration) -> Result<Registration, Error> {
+        self.registrations
+            .replace_one(
+                doc! { "auction_id": registration.auction_id.value(), "user": registration.user.value() },
+                write_record(&registration)?,
+            )
+            .upsert(true)
+            .await
+            .map_err(repository_error)?;
+        Ok(registration)
+    }
+}
+
+#[derive(Clone)]
+pub struct MongoDepositRepository {
+    deposits: Collection<Document>,
+}
+
+impl MongoDepositRepository {
+    pub fn new(database: &Database) -> Self {
+        Self {
+            deposits: database.collection("deposits"),
+        }
+    }
+}
+
+#[async_trait]
+impl DepositRepository for MongoDepositRepository {
+    async fn get_deposit(&self, auction_id: AuctionId, user: &UserId) -> Result<Option<Deposit>, Error> {
+        let stored = self
+            .deposits
+            .find_one(doc! { "auction_id": auction_id.value(), "user": user.value() })
+            .projection(doc! { "_id": 0 })
+            .await
+            .map_err(repository_error)?;
+        stored.map(read_record).transpose()
+    }
+
+    async fn record(&self, deposit: Deposit) -> Result<Deposit, Error> {
+        self.deposits
+            .replace_one(
+                doc! { "auction_id": deposit.auction_id.value(), "user": deposit.user.value() },
+                write_record(&deposit)?,
+            )
+            .upsert(true)
+            .await
+            .map_err(repository_error)?;
+        Ok(deposit)
+    }
+}
+
+/// Keeps results by the id of their auction, which makes the first one recorded stay.
+#[derive(Clone)]
+pub struct MongoAuctionResultRepository {
+    results: Collection<Document>,
+}
+
+impl MongoAuctionResultRepository {
+    pub fn new(database: &Database) -> Self {
+        Self {
+            results: database.collection("auction_results"),
+        }
+    }
+}
+
+#[async_trait]
+impl AuctionResultRepository for MongoAuctionResultRepository {
+    async fn get_results(&self, auction_ids: &[AuctionId]) -> Result<Vec<AuctionResult>, Error> {
+        let ids: Vec<i64> = auction_ids.iter().map(|id| id.value()).collect();
+        let mut cursor = self
+            .results
+            .find(doc! { "_id": { "$in": ids } })
+            .projection(doc! { "_id": 0 })
+            .await
+            .map_err(repository_error)?;
+        let mut results = Vec::new();
+        while cursor.advance().await.map_err(repository_error)? {
+            results.push(read_record(cursor.deserialize_current().map_err(repository_error)?)?);
+        }
+        Ok(results)
+    }
+
+    async fn record(&self, result: AuctionResult) -> Result<AuctionResult, Error> {
+        let auction_id = result.auction_id.value();
+        let mut stored = write_record(&result)?;
+        stored.insert("_id", auction_id);
+        // Concurrent readers may both see the auction closed; the first result recorded stays
+        match self.results.insert_one(stored).await {
+            Ok(_) => Ok(result),
+            Err(e) if is_duplicate_key(&e) => {
+                let stored = self
+                    .results
+                    .find_one(doc! { "_id": auction_id })
+                    .projection(doc! { "_id": 0 })
+                    .await
+                    .map_err(repository_error)?
+                    .ok_or_else(|| Error::Repository(format!("The result of auction {} is missing", auction_id)))?;
+                read_record(stored)
+            }
+            Err(e) => Err(repository_error(e)),
+        }
+    }
+}
+
+/// Keeps settlements by the id of their auction, which makes the first one recorded stay.
+#[derive(Clone)]
+pub struct MongoSettlementRepository {
+    settlements: Collection<Document>,
+}
+
+impl MongoSettlementRepository {
+    pub fn new(database: &Database) -> Self {
+        Self {
+            settlements: database.collection("settlements"),
+        }
+    }
+
+    async fn find(&self, auction_id: AuctionId) -> Result<Option<Settlement>, Error> {
+        let stored = self
+            .settlements
+            .find_one(doc! { "_id": auction_id.value() })
+            .await
+            .map_err(repository_error)?;
+        stored
+            .map(|mut stored| {
+                let settlement = stored
+                    .remove("settlement")
+                    .and_then(|settlement| settlement.as_document().cloned())
+                    .ok_or_else(|| Error::Repository("Unreadable settlement: the document is missing".to_string()))?;
+                read_record(settlement)
+            })
+            .transpose()
+    }
+}
+
+#[async_trait]
+impl SettlementRepository for MongoSettlementRepository {
+    async fn get_settlement(&self, auction_id: AuctionId) -> Result<Option<Settlement>, Error> {
+        self.find(auction_id).await
+    }
+
+    async fn record(
+        &self,
+        auction_id: AuctionId,
+        settlement: Settlement,
+        settled_at: DateTime<Utc>,
+    ) -> Result<Settlement, Error> {
+        let stored = doc! {
+            "_id": auction_id.value(),
+            "settlement": write_record(&settlement)?,
+            "settled_at": to_bson(&settled_at).map_err(|e| Error::Internal(e.to_string()))?,
+        };
+        // Concurrent readers may both work out a settlement; the first one recorded stays
+        match self.settlements.insert_one(stored).await {
+            Ok(_) => Ok(settlement),
+            Err(e) if is_duplicate_key(&e) => self
+                .find(auction_id)
+                .await?
+                .ok_or_else(|| Error::Repository(format!("The settlement of auction {} is missing", auction_id))),
+            Err(e) => Err(repository_error(e)),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct MongoAuditRepository {
+    entries: Collection<Document>,
+}
+
+impl MongoAuditRepository {
+    pub fn new(database: &Database) -> Self {
+        Self {
+            entries: database.collection("audit_log"),
+        }
+    }
+}
+
+#[async_trait]
+impl AuditRepository for MongoAuditRepository {
+    async fn record(&self, entry: AuditEntry) -> Result<(), Error> {
+        self.entries
+            .insert_one(write_record(&entry)?)
+            .await
+            .map_err(repository_error)?;
+        Ok(())
+    }
+}
+
+/// Keeps users by their id, with the display name left out until one is known.
+#[derive(Clone)]
+pub struct MongoUserRepository {
+    users: Collection<Document>,
+}
+
+impl MongoUserRepository {
+    pub fn new(database: &Database) -> Self {
+        Self {
+            users: database.collection("users"),
+        }
+    }
+}
+
+#[async_trait]
+impl UserRepository for MongoUserRepository {
+    async fn upsert(&self, user: &User, at: DateTime<Utc>) -> Result<(), Error> {
+        let mut set = doc! { "role": role(user) };
+        if let Some(name) = display_name(user) {
+            set.insert("display_name", name);
+        }
+        let created_at = to_bson(&at).map_err(|e| Error::Internal(e.to_string()))?;
+        self.users
+            .update_one(
+                doc! { "_id": user.id().value() },
+                doc! { "$set": set, "$setOnInsert": { "created_at": created_at } },
+            )
+            .upsert(true)
+            .await
+            .map_err(repository_error)?;
+        Ok(())
+    }
+
+    async fn get_display_names(&self, users: &[UserId]) -> Result<HashMap<UserId, String>, Error> {
+        let ids: Vec<&str> = users.iter().map(|user| user.value()).collect();
+        let mut cursor = self
+            .users
+            .find(doc! { "_id": { "$in": ids }, "display_name": { "$type": "string" } })
+            .await
+            .map_err(repository_error)?;
+        let mut names = HashMap::new();
+        while cursor.advance().await.map_err(repository_error)? {
+            let stored = cursor.deserialize_current().map_err(repository_error)?;
+            let field_error = |e: mongodb::bson::document::ValueAccessError| Error::Repository(e.to_string());
+            names.insert(
+                UserId::new(stored.get_str("_id").map_err(field_error)?),
+                stored.get_str("display_name").map_err(field_error)?.to_string(),
+            );
+        }
+        Ok(names)
+    }
+
+    async fn remove(&self, user: &UserId) -> Result<(), Error> {
+        self.users
+            .delete_one(doc! { "_id": user.value() })
+            .await
+            .map_err(repository_error)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod mongo_repository_tests {
+    use super::*;
+    use mongodb::Client;
+    use testcontainers_modules::mongo::Mongo;
+    use testcontainers_modules::testcontainers::runners::AsyncRunner;
+    use crate::infrastructure::data::auction_repository_conformance::check_auction_repository;
+    use crate::infrastructure::data::repository_conformance::{
+        check_auction_result_repository, check_audit_repository, check_deposit_repository,
+        check_registration_repository, check_settlement_repository, check_user_repository,
+    };
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_with_mongo() {
+        let container = Mongo::default().start().await.unwrap();
+        let host_ip = container.get_host().await.unwrap();
+        let host_port = container.get_host_port_ipv4(27017).await.unwrap();
+        let client = Client::with_uri_str(format!("mongodb://{}:{}", host_ip, host_port)).await.unwrap();
+        let database = client.database("auctions");
+        prepare_mongo_database(&database).await.unwrap();
+        // Preparing again leaves the seeded currencies alone
+        prepare_mongo_database(&database).await.unwrap();
+
+        check_auction_repository(&MongoAuctionRepository::new(&database)).await;
+
+        check_registration_repository(&MongoRegistrationRepository::new(&database)).await;
+        check_deposit_repository(&MongoDepositRepository::new(&database)).await;
+        check_auction_result_repository(&MongoAuctionResultRepository::new(&database)).await;
+        check_settlement_repository(&MongoSettlementRepository::new(&database)).await;
+        check_audit_repository(&MongoAuditRepository::new(&database)).await;
+        check_user_repository(&MongoUserRepository::new(&database)).await;
+
+        let currencies = MongoCurrencyRepository::new(&database).get_currencies().await.unwrap();
+        let codes: Vec<CurrencyCode> = currencies.iter().map(|currency| currency.code).collect();
+        assert_eq!(codes, vec![CurrencyCode::DKK, CurrencyCode::EUR, CurrencyCode::SEK, CurrencyCode::VAC]);
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use sqlx::{MySql, MySqlConnection, MySqlPool, QueryBuilder};
+
+use crate::domain::models::{Auction, AuctionId, Error, Errors, UserId};
+use crate::infrastructure::data::{changed_since_read, AuctionFilter, AuctionRepository};
+
+/// Stores each auction as one JSON document, for infrastructure where MySQL or MariaDB is the
+/// only database. Laid out like [`super::SqliteAuctionRepository`]: the seller, external
+/// reference, status and number of bids are kept in columns next to the document.
+#[derive(Clone)]
+pub struct MySqlAuctionRepository {
+    pool: MySqlPool,
+}
+
+impl MySqlAuctionRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn repository_error(e: sqlx::Error) -> Error {
+    Error::Repository(e.to_string())
+}
+
+fn deserialize_auction(json: &str) -> Result<Auction, Error> {
+    serde_json::from_str(json).map_err(|e| Error::Repository(format!("Invalid stored auction: {}", e)))
+}
+
+fn serialize_auction(auction: &Auction) -> Result<String, Error> {
+    serde_json::to_string(auction).map_err(|e| Error::Internal(e.to_string()))
+}
+
+/// Writes the document and the columns derived from it.
+async fn write_auction(conn: &mut MySqlConnection, auction: &Auction) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+        UPDATE auctions
+        SET user_id = ?, external_reference = ?, status = ?, bid_count = ?, auction = ?
+        WHERE id = ?
+    "#,
+    )
+    .bind(auction.user().value())
+    .bind(auction.external_reference())
+    .bind(auction.status().to_string())
+    .bind(auction.bids().len() as i64)
+    .bind(serialize_auction(auction)?)
+    .bind(auction.auction_id().value())
+    .execute(&mut *conn)
+    .await
+    .map_err(repository_error)?;
+    Ok(())
+}
+
+#[async_trait]
+impl AuctionRepository for MySqlAuctionRepository {
+    async fn get_auction(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+        let json = sqlx::query_scalar::<_, String>("SELECT auction FROM auctions WHERE id = ?")
+            .bind(auction_id.value())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(repository_error)?;
+        json.as_deref().map(deserialize_auction).transpose()
+    }
+
+    async fn get_auctions(&self) -> Result<Vec<Auction>, Error> {
+        let rows = sqlx::query_scalar::<_, String>("SELECT auction FROM auctions ORDER BY id")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(repository_error)?;
+        rows.iter().map(|json| deserialize_auction(json)).collect()
+    }
+
+    async fn get_auctions_by_seller(&self, seller: &UserId) -> Result<Vec<Auction>, Error> {
+        let rows = sqlx::query_scalar::<_, String>("SELECT auction FROM auctions WHERE user_id = ? ORDER BY id")
+            .bind(seller.value())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(repository_error)?;
+        rows.iter().map(|json| deserialize_auction(json)).collect()
+    }
+
+    async fn get_auctions_by_ids(&self, auction_ids: &[AuctionId]) -> Result<Vec<Auction>, Error> {
+        if auction_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut query = QueryBuilder::<MySql>::new("SELECT id, auction FROM auctions WHERE id IN (");
+        let mut ids = query.separated(", ");
+        for auction_id in auction_ids {
+            ids.push_bind(auction_id.value());
+        }
+        query.push(")");
+        let mut found: HashMap<i64, String> = query
+            .build_query_as::<(i64, String)>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(repository_error)?
+            .into_iter()
+            .collect();
+        // IN (...) does not keep the order asked for
+        auction_ids
+            .iter()
+            .filter_map(|id| found.remove(&id.value()))
+            .map(|json| deserialize_auction(&json))
+            .collect()
+    }
+
+    async fn get_auction_by_external_reference(
+        &self,
+        user: &UserId,
+        external_reference: &str,
+    ) -> Result<Option<Auction>, Error> {
+        let json = sqlx::query_scalar::<_, String>(
+            "SELECT auction FROM auctions WHERE user_id = ? AND external_reference = ?",
+        )
+        .bind(user.value())
+        .bind(external_reference)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(repository_error)?;
+        json.as_deref().map(deserialize_auction).transpose()
+    }
+
+    async fn create_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        let mut tx = self.pool.begin().await.map_err(repository_error)?;
+        // The id is generated by the insert and then written into the document
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO auctions (user_id, external_reference, status, auction)
+            VALUES (?, ?, ?, '{}')
+        "#,
+        )
+        .bind(auction.user().value())
+        .bind(auction.external_reference())
+        .bind(auction.status().to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e.as_database_error() {
+            // Only the external reference is unique besides the generated id
+            Some(db_error) if db_error.is_unique_violation() => {
+                Error::Validation(Errors::AuctionAlreadyExists)
+            }
+            _ => repository_error(e),
+        })?;
+        let mut new_auction = auction;
+        new_auction.set_auction_id(AuctionId::new(inserted.last_insert_id() as i64));
+        new_auction.set_version(0);
+        write_auction(&mut tx, &new_auction).await?;
+        tx.commit().await.map_err(repository_error)?;
+        Ok(new_auction)
+    }
+
+    async fn update_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        let mut tx = self.pool.begin().await.map_err(repository_error)?;
+        // MySQL counts only changed rows as affected, so the row is locked and checked up front
+        let json = sqlx::query_scalar::<_, String>("SELECT auction FROM auctions WHERE id = ? FOR UPDATE")
+            .bind(auction.auction_id().value())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(repository_error)?
+            .ok_or_else(|| Error::NotFound(format!("Auction with ID {} not found", auction.auction_id())))?;
+        let existing = deserialize_auction(&json)?;
+        if existing.version() != auction.version() {
+            return Err(changed_since_read(auction.auction_id()));
+        }
+        if existing.status() != auction.status() && !existing.status().can_transition_to(auction.status()) {
+            return Err(Error::Validation(Errors::IllegalStatusTransition));
+        }
+        let mut auction = auction;
+        auction.set_version(existing.version() + 1);
+        write_auction(&mut tx, &auction).await?;
+        tx.commit().await.map_err(repository_error)?;
+        Ok(auction)
+    }
+
+    async fn auction_exists(&self, auction_id: AuctionId) -> Result<bool, Error> {
+        let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM auctions WHERE id = ?")
+            .bind(auction_id.value())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(repository_error)?;
+        Ok(count > 0)
+    }
+
+    async fn count_auctions(&self, filter: &AuctionFilter) -> Result<i64, Error> {
+        let seller = filter.seller.as_ref().map(|seller| seller.value());
+        let status = filter.status.map(|status| status.to_string());
+        let hidden = filter.hidden_status();
+        sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM auctions
+            WHERE (? IS NULL OR user_id = ?)
+                AND (? IS NULL OR status = ?)
+                AND (? IS NULL OR status <> ?)
+        "#,
+        )
+        .bind(seller)
+        .bind(seller)
+        .bind(status.as_deref())
+        .bind(status.as_deref())
+        .bind(hidden.as_deref())
+        .bind(hidden.as_deref())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(repository_error)
+    }
+
+    async fn count_bids(&self, auction_id: AuctionId) -> Result<i64, Error> {
+        let count = sqlx::query_scalar::<_, i64>("SELECT bid_count FROM auctions WHERE id = ?")
+            .bind(auction_id.value())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(repository_error)?;
+        Ok(count.unwrap_or(0))
+    }
+
+    async fn anonymize_user(&self, user: &UserId, pseudonym: &UserId) -> Result<Vec<AuctionId>, Error> {
+        let mut tx = self.pool.begin().await.map_err(repository_error)?;
+        // Only documents mentioning the user can refer to them
+        let needle = serde_json::to_string(user.value()).map_err(|e| Error::Internal(e.to_string()))?;
+        let rows = sqlx::query_scalar::<_, String>(
+            "SELECT auction FROM auctions WHERE INSTR(auction, ?) > 0 ORDER BY id FOR UPDATE",
+        )
+        .bind(needle)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(repository_error)?;
+        let mut anonymized = Vec::new();
+        for json in rows {
+            let mut auction = deserialize_auction(&json)?;
+            if auction.anonymize_user(user, pseudonym) {
+                auction.set_version(auction.version() + 1);
+                write_auction(&mut tx, &auction).await?;
+                anonymized.push(auction.auction_id());
+            }
+        }
+        tx.commit().await.map_err(repository_error)?;
+        Ok(anonymized)
+    }
+}
+
+#[cfg(test)]
+mod mysql_repository_tests {
+    use super::*;
+    use testcontainers_modules::mysql::Mysql;
+    use testcontainers_modules::testcontainers::runners::AsyncRunner;
+    use crate::domain::models::CurrencyCode;
+    use crate::infrastructure::data::auction_repository_conformance::check_auction_repository;
+    use crate::infrastructure::data::repository_conformance::{
+        check_auction_result_repository, check_audit_repository, check_deposit_repository,
+        check_registration_repository, check_settlement_repository, check_user_repository,
+    };
+    use crate::infrastructure::data::{create_mysql_pool, run_mysql_migrations, PoolSettings, CurrencyRepository, MySqlCurrencyRepository};
+    use crate::infrastructure::data::{
+        MySqlAuctionResultRepository, MySqlAuditRepository, MySqlDepositRepository, MySqlRegistrationRepository,
+        MySqlSettlementRepository, MySqlUserRepository,
+    };
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_with_mysql() {
+        let container = Mysql::default().start().await.unwrap();
+        let host_ip = container.get_host().await.unwrap();
+        let host_port = container.get_host_port_ipv4(3306).await.unwrap();
+        let pool = create_mysql_pool(&format!("mysql://root@{}:{}/test", host_ip, host_port), PoolSettings::default())
+            .await
+            .unwrap();
+        run_mysql_migrations(&pool).await.unwrap();
+
+        check_auction_repository(&MySqlAuctionRepository::new(pool.clone())).await;
+
+        check_registration_repository(&MySqlRegistrationRepository::new(pool.clone())).await;
+        check_deposit_repository(&MySqlDepositRepository::new(pool.clone())).await;
+        check_auction_result_repository(&MySqlAuctionResultRepository::new(pool.clone())).await;
+        check_settlement_repository(&MySqlSettlementRepository::new(pool.clone())).await;
+        check_audit_repository(&MySqlAuditRepository::new(pool.clone())).await;
+        check_user_repository(&MySqlUserRepository::new(pool.clone())).await;
+
+        let currencies = MySqlCurrencyRepository::new(pool).get_currencies().await.unwrap();
+        let codes: Vec<CurrencyCode> = currencies.iter().map(|currency| currency.code).collect();
+        assert_eq!(codes, vec![CurrencyCode::DKK, CurrencyCode::EUR, CurrencyCode::SEK, CurrencyCode::VAC]);
+    }
+}
@@ -0,0 +1,206 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{FromRow, PgConnection, PgPool};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::domain::models::{Auction, AuctionId, Bid, Error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuctionEventType {
+    AuctionCreated,
+    BidAccepted,
+    BidRetracted,
+    BidVoided,
+    AuctionUpdated,
+}
+
+impl fmt::Display for AuctionEventType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl FromStr for AuctionEventType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "AuctionCreated" => Ok(AuctionEventType::AuctionCreated),
+            "BidAccepted" => Ok(AuctionEventType::BidAccepted),
+            "BidRetracted" => Ok(AuctionEventType::BidRetracted),
+            "BidVoided" => Ok(AuctionEventType::BidVoided),
+            "AuctionUpdated" => Ok(AuctionEventType::AuctionUpdated),
+            _ => Err(Error::Repository(format!("Unknown auction event type {}", s))),
+        }
+    }
+}
+
+/// A change to an auction, as stored in the outbox.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuctionEvent {
+    /// Position in the outbox, increasing in commit order per writer
+    pub id: i64,
+    pub auction_id: AuctionId,
+    pub event_type: AuctionEventType,
+    pub payload: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(FromRow)]
+struct AuctionEventRow {
+    id: i64,
+    auction_id: i64,
+    event_type: String,
+    payload: Value,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<AuctionEventRow> for AuctionEvent {
+    type Error = Error;
+
+    fn try_from(row: AuctionEventRow) -> Result<Self, Self::Error> {
+        Ok(AuctionEvent {
+            id: row.id,
+            auction_id: AuctionId::new(row.auction_id),
+            event_type: row.event_type.parse()?,
+            payload: row.payload,
+            created_at: row.created_at,
+        })
+    }
+}
+
+pub(crate) fn auction_payload(auction: &Auction) -> Value {
+    json!({
+        "status": auction.status().to_string(),
+        "title": auction.title(),
+        "currency": auction.currency().to_string(),
+    })
+}
+
+pub(crate) fn bid_payload(bid: &Bid) -> Value {
+    json!({
+        "bidId": bid.id,
+        "bidder": bid.user().value(),
+        "amountValue": bid.amount().value(),
+        "amountCurrency": bid.amount().currency().to_string(),
+        "at": bid.at(),
+    })
+}
+
+/// Channel notified with the auction id on every event, see [`crate::infrastructure::data::listen_for_auction_events`]
+pub const AUCTION_EVENTS_CHANNEL: &str = "auction_events";
+
+/// Appends an event, on the connection of the transaction making the change.
+/// Listeners are notified once the transaction commits.
+pub(crate) async fn append_event(
+    conn: &mut PgConnection,
+    auction_id: AuctionId,
+    event_type: AuctionEventType,
+    payload: Value,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO auction_events (auction_id, event_type, payload)
+        VALUES ($1, $2, $3)
+    "#,
+    )
+    .bind(auction_id.value())
+    .bind(event_type.to_string())
+    .bind(payload)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| Error::Repository(e.to_string()))?;
+    sqlx::query("SELECT pg_notify($1, $2)")
+        .bind(AUCTION_EVENTS_CHANNEL)
+        .bind(auction_id.value().to_string())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+    Ok(())
+}
+
+/// Reads the outbox and keeps track of how far each projection has come.
+#[derive(Clone)]
+pub struct PgOutbox {
+    pool: PgPool,
+}
+
+impl PgOutbox {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    pub async fn read_after(&self, position: i64, limit: i64) -> Result<Vec<AuctionEvent>, Error> {
+        let rows = sqlx::query_as::<_, AuctionEventRow>(
+            r#"
+            SELECT id, auction_id, event_type, payload, created_at
+            FROM auction_events
+            WHERE id > $1
+            ORDER BY id
+            LIMIT $2
+        "#,
+        )
+        .bind(position)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        rows.into_iter().map(AuctionEvent::try_from).collect()
+    }
+
+    pub async fn checkpoint(&self, projection: &str) -> Result<i64, Error> {
+        let position = sqlx::query_scalar::<_, i64>(
+            "SELECT position FROM projection_checkpoints WHERE name = $1",
+        )
+        .bind(projection)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(position.unwrap_or(0))
+    }
+}
+
+/// Stores the checkpoint in the transaction that applied the events, so each event is applied once.
+pub(crate) async fn save_checkpoint(
+    conn: &mut PgConnection,
+    projection: &str,
+    position: i64,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO projection_checkpoints (name, position)
+        VALUES ($1, $2)
+        ON CONFLICT (name) DO UPDATE SET position = EXCLUDED.position
+    "#,
+    )
+    .bind(projection)
+    .bind(position)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| Error::Repository(e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod outbox_tests {
+    use super::*;
+
+    #[test]
+    fn test_event_type_round_trips_through_its_name() {
+        for event_type in [
+            AuctionEventType::AuctionCreated,
+            AuctionEventType::BidAccepted,
+            AuctionEventType::BidRetracted,
+            AuctionEventType::AuctionUpdated,
+        ] {
+            assert_eq!(event_type.to_string().parse::<AuctionEventType>().unwrap(), event_type);
+        }
+        assert!("BidRejected".parse::<AuctionEventType>().is_err());
+    }
+}
@@ -0,0 +1,342 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use redis::aio::{ConnectionManager, PubSub};
+use redis::{RedisResult, Script};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::domain::models::{Auction, AuctionId, Bid, DomainEvent, Error, UserId};
+use crate::infrastructure::config::RedisCacheConfig;
+use crate::infrastructure::data::{
+    AuctionFilter, AuctionPage, AuctionRepository, AuctionStandings, AuctionSummary, BidPage, CachingAuctionRepository,
+    PlaceBid,
+};
+
+/// Stores an entry only while the listings generation is the one read before reading the store,
+/// so that a read racing a write does not put back what the write removed.
+const STORE_SCRIPT: &str = r"
+if (redis.call('GET', KEYS[1]) or '0') == ARGV[1] then
+    redis.call('SET', KEYS[2], ARGV[2], 'EX', ARGV[3])
+end
+return 0
+";
+
+/// Shares auctions, and the summaries listing them with their current prices, between the
+/// instances of the server through Redis. Writes remove the auctions they touch, start a new
+/// generation of listings and publish the ids on a channel, see
+/// [`listen_for_cache_invalidations`].
+///
+/// Redis is a cache only: reads fall back on the store when it cannot be reached, and a write
+/// that fails to invalidate leaves the older entries until they expire.
+#[derive(Clone)]
+pub struct RedisAuctionRepository {
+    inner: Box<dyn AuctionRepository>,
+    connection: ConnectionManager,
+    key_prefix: String,
+    channel: String,
+    ttl_secs: u64,
+}
+
+impl RedisAuctionRepository {
+    pub async fn connect(inner: Box<dyn AuctionRepository>, config: &RedisCacheConfig) -> RedisResult<Self> {
+        let client = redis::Client::open(config.url.as_str())?;
+        Ok(Self {
+            inner,
+            connection: ConnectionManager::new(client).await?,
+            key_prefix: config.key_prefix.clone(),
+            channel: config.channel.clone(),
+            ttl_secs: config.ttl_secs,
+        })
+    }
+
+    fn auction_key(&self, auction_id: AuctionId) -> String {
+        format!("{}auction:{}", self.key_prefix, auction_id)
+    }
+
+    fn generation_key(&self) -> String {
+        format!("{}listings", self.key_prefix)
+    }
+
+    /// The seller goes last, as it may contain the separator.
+    fn summaries_key(&self, generation: i64, limit: i64, offset: i64, filter: &AuctionFilter) -> String {
+        format!(
+            "{}summaries:{}:{}:{}:{}:{}:{}",
+            self.key_prefix,
+            generation,
+            limit,
+            offset,
+            filter.published,
+            filter.status.map(|status| status.to_string()).unwrap_or_default(),
+            filter.seller.as_ref().map_or("", |seller| seller.value())
+        )
+    }
+
+    /// The entry under `key` and the current listings generation, or `None` when Redis cannot be
+    /// reached.
+    async fn read<T: DeserializeOwned>(&self, key: &str) -> Option<(Option<T>, i64)> {
+        let mut connection = self.connection.clone();
+        let read: RedisResult<(Option<String>, Option<i64>)> = redis::pipe()
+            .get(key)
+            .get(self.generation_key())
+            .query_async(&mut connection)
+            .await;
+        match read {
+            Ok((json, generation)) => {
+                let cached = json.and_then(|json| match serde_json::from_str(&json) {
+                    Ok(value) => Some(value),
+                    Err(e) => {
+                        log::warn!("Ignoring cached {}: {}", key, e);
+                        None
+                    }
+                });
+                Some((cached, generation.unwrap_or(0)))
+            }
+            Err(e) => {
+                log::warn!("Failed to read {} from Redis: {}", key, e);
+                None
+            }
+        }
+    }
+
+    async fn generation(&self) -> Option<i64> {
+        let mut connection = self.connection.clone();
+        let generation: RedisResult<Option<i64>> =
+            redis::cmd("GET").arg(self.generation_key()).query_async(&mut connection).await;
+        match generation {
+            Ok(generation) => Some(generation.unwrap_or(0)),
+            Err(e) => {
+                log::warn!("Failed to read the listings generation from Redis: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn store<T: Serialize>(&self, key: &str, value: &T, generation: i64) {
+        let json = match serde_json::to_string(value) {
+            Ok(json) => json,
+            Err(e) => {
+                log::warn!("Failed to cache {}: {}", key, e);
+                return;
+            }
+        };
+        let mut connection = self.connection.clone();
+        let stored: RedisResult<()> = Script::new(STORE_SCRIPT)
+            .key(self.generation_key())
+            .key(key)
+            .arg(generation)
+            .arg(json)
+            .arg(self.ttl_secs)
+            .invoke_async(&mut connection)
+            .await;
+        if let Err(e) = stored {
+            log::warn!("Failed to cache {} in Redis: {}", key, e);
+        }
+    }
+
+    async fn invalidate(&self, auction_ids: &[AuctionId]) {
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for auction_id in auction_ids {
+            pipe.del(self.auction_key(*auction_id)).ignore();
+        }
+        pipe.incr(self.generation_key(), 1).ignore();
+        pipe.publish(&self.channel, invalidation_message(auction_ids)).ignore();
+        let mut connection = self.connection.clone();
+        let invalidated: RedisResult<()> = pipe.query_async(&mut connection).await;
+        if let Err(e) = invalidated {
+            log::warn!("Failed to invalidate cached auctions {:?}: {}", auction_ids, e);
+        }
+    }
+}
+
+#[async_trait]
+impl AuctionRepository for RedisAuctionRepository {
+    async fn get_auction(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+        let key = self.auction_key(auction_id);
+        let generation = match self.read::<Auction>(&key).await {
+            Some((Some(auction), _)) => return Ok(Some(auction)),
+            Some((None, generation)) => Some(generation),
+            None => None,
+        };
+        let auction = self.inner.get_auction(auction_id).await?;
+        if let (Some(auction), Some(generation)) = (&auction, generation) {
+            self.store(&key, auction, generation).await;
+        }
+        Ok(auction)
+    }
+
+    async fn get_auction_without_bids(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+        self.inner.get_auction_without_bids(auction_id).await
+    }
+
+    async fn get_auction_standings(&self, auction_id: AuctionId) -> Result<Option<AuctionStandings>, Error> {
+        self.inner.get_auction_standings(auction_id).await
+    }
+
+    async fn get_auction_summary(&self, auction_id: AuctionId) -> Result<Option<AuctionSummary>, Error> {
+        self.inner.get_auction_summary(auction_id).await
+    }
+
+    async fn get_auctions(&self) -> Result<Vec<Auction>, Error> {
+        self.inner.get_auctions().await
+    }
+
+    async fn get_auctions_page(&self, limit: i64, offset: i64, filter: &AuctionFilter) -> Result<AuctionPage, Error> {
+        self.inner.get_auctions_page(limit, offset, filter).await
+    }
+
+    async fn get_auction_summaries(
+        &self,
+        limit: i64,
+        offset: i64,
+        filter: &AuctionFilter,
+    ) -> Result<AuctionPage<AuctionSummary>, Error> {
+        let Some(generation) = self.generation().await else {
+            return self.inner.get_auction_summaries(limit, offset, filter).await;
+        };
+        let key = self.summaries_key(generation, limit, offset, filter);
+        let reachable = match self.read::<AuctionPage<AuctionSummary>>(&key).await {
+            Some((Some(page), _)) => return Ok(page),
+            Some((None, _)) => true,
+            None => false,
+        };
+        let page = self.inner.get_auction_summaries(limit, offset, filter).await?;
+        if reachable {
+            self.store(&key, &page, generation).await;
+        }
+        Ok(page)
+    }
+
+    async fn get_active_auctions(&self, now: DateTime<Utc>) -> Result<Vec<Auction>, Error> {
+        self.inner.get_active_auctions(now).await
+    }
+
+    async fn get_bids_page(&self, auction_id: AuctionId, after_bid_id: Option<i64>, limit: i64) -> Result<BidPage, Error> {
+        self.inner.get_bids_page(auction_id, after_bid_id, limit).await
+    }
+
+    async fn get_auctions_by_seller(&self, seller: &UserId) -> Result<Vec<Auction>, Error> {
+        self.inner.get_auctions_by_seller(seller).await
+    }
+
+    async fn get_auctions_by_ids(&self, auction_ids: &[AuctionId]) -> Result<Vec<Auction>, Error> {
+        self.inner.get_auctions_by_ids(auction_ids).await
+    }
+
+    async fn get_auction_by_external_reference(
+        &self,
+        user: &UserId,
+        external_reference: &str,
+    ) -> Result<Option<Auction>, Error> {
+        self.inner.get_auction_by_external_reference(user, external_reference).await
+    }
+
+    async fn create_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        let created = self.inner.create_auction(auction).await?;
+        self.invalidate(&[created.auction_id()]).await;
+        Ok(created)
+    }
+
+    async fn update_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        let auction_id = auction.auction_id();
+        let updated = self.inner.update_auction(auction).await;
+        self.invalidate(&[auction_id]).await;
+        updated
+    }
+
+    async fn add_bid(
+        &self,
+        auction_id: AuctionId,
+        version: i32,
+        bid: &Bid,
+        ends_at: Option<DateTime<Utc>>,
+    ) -> Result<(), Error> {
+        let added = self.inner.add_bid(auction_id, version, bid, ends_at).await;
+        self.invalidate(&[auction_id]).await;
+        added
+    }
+
+    async fn place_bid(&self, auction_id: AuctionId, place: PlaceBid) -> Result<Vec<DomainEvent>, Error> {
+        let placed = self.inner.place_bid(auction_id, place).await;
+        self.invalidate(&[auction_id]).await;
+        placed
+    }
+
+    async fn auction_exists(&self, auction_id: AuctionId) -> Result<bool, Error> {
+        self.inner.auction_exists(auction_id).await
+    }
+
+    async fn count_auctions(&self, filter: &AuctionFilter) -> Result<i64, Error> {
+        self.inner.count_auctions(filter).await
+    }
+
+    async fn count_bids(&self, auction_id: AuctionId) -> Result<i64, Error> {
+        self.inner.count_bids(auction_id).await
+    }
+
+    async fn anonymize_user(&self, user: &UserId, pseudonym: &UserId) -> Result<Vec<AuctionId>, Error> {
+        let anonymized = self.inner.anonymize_user(user, pseudonym).await?;
+        self.invalidate(&anonymized).await;
+        Ok(anonymized)
+    }
+}
+
+fn invalidation_message(auction_ids: &[AuctionId]) -> String {
+    auction_ids.iter().map(|auction_id| auction_id.to_string()).collect::<Vec<_>>().join(",")
+}
+
+fn parse_invalidation(payload: &str) -> Option<Vec<AuctionId>> {
+    payload
+        .split(',')
+        .filter(|id| !id.is_empty())
+        .map(|id| id.parse().ok().map(AuctionId::new))
+        .collect()
+}
+
+/// Makes the in-process `cache` forget what any instance changed, as published by
+/// [`RedisAuctionRepository`]. Runs until the process exits.
+///
+/// Invalidations published while disconnected are lost, so the cache is cleared on subscribing.
+pub async fn listen_for_cache_invalidations(config: RedisCacheConfig, cache: CachingAuctionRepository) {
+    loop {
+        match subscribe(&config).await {
+            Ok(mut pubsub) => {
+                log::info!("Listening for cache invalidations on {}", config.channel);
+                cache.clear();
+                let mut messages = pubsub.on_message();
+                while let Some(message) = messages.next().await {
+                    match message.get_payload::<String>().ok().and_then(|payload| parse_invalidation(&payload)) {
+                        Some(auction_ids) => cache.invalidate(&auction_ids),
+                        None => log::warn!("Ignoring cache invalidation {:?}", message.get_payload_bytes()),
+                    }
+                }
+                log::warn!("Cache invalidation listener lost its connection, reconnecting");
+            }
+            Err(e) => log::error!("Failed to listen for cache invalidations: {}", e),
+        }
+        tokio::time::sleep(Duration::from_millis(config.reconnect_delay_ms)).await;
+    }
+}
+
+async fn subscribe(config: &RedisCacheConfig) -> RedisResult<PubSub> {
+    let client = redis::Client::open(config.url.as_str())?;
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(&config.channel).await?;
+    Ok(pubsub)
+}
+
+#[cfg(test)]
+mod redis_auction_repository_tests {
+    use super::*;
+
+    #[test]
+    fn test_invalidations_carry_the_auction_ids() {
+        let auction_ids = vec![AuctionId::new(1), AuctionId::new(42)];
+        assert_eq!(invalidation_message(&auction_ids), "1,42");
+        assert_eq!(parse_invalidation("1,42"), Some(auction_ids));
+        assert_eq!(parse_invalidation(""), Some(Vec::new()));
+        assert_eq!(parse_invalidation("1,auction"), None);
+    }
+}
@@ -0,0 +1,269 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use dyn_clone::DynClone;
+use sqlx::{PgPool, SqlitePool};
+use std::sync::{Arc, Mutex};
+
+use crate::domain::models::{AuctionId, Error, Registration, UserId};
+
+dyn_clone::clone_trait_object!(RegistrationRepository);
+
+#[async_trait]
+pub trait RegistrationRepository: Send + Sync + DynClone {
+    async fn get_registration(&self, auction_id: AuctionId, user: &UserId) -> Result<Option<Registration>, Error>;
+    async fn get_registrations(&self, auction_id: AuctionId) -> Result<Vec<Registration>, Error>;
+    /// Inserts the registration, or replaces the one of the same bidder in the same auction.
+    async fn save(&self, registration: Registration) -> Result<Registration, Error>;
+}
+
+/// Keeps registrations in process memory, for running without a database.
+#[derive(Clone, Default)]
+pub struct InMemoryRegistrationRepository {
+    registrations: Arc<Mutex<Vec<Registration>>>,
+}
+
+#[async_trait]
+impl RegistrationRepository for InMemoryRegistrationRepository {
+    async fn get_registration(&self, auction_id: AuctionId, user: &UserId) -> Result<Option<Registration>, Error> {
+        Ok(self
+            .registrations
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|r| r.auction_id == auction_id && &r.user == user)
+            .cloned())
+    }
+
+    async fn get_registrations(&self, auction_id: AuctionId) -> Result<Vec<Registration>, Error> {
+        Ok(self
+            .registrations
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.auction_id == auction_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn save(&self, registration: Registration) -> Result<Registration, Error> {
+        let mut registrations = self.registrations.lock().unwrap();
+        registrations.retain(|r| !(r.auction_id == registration.auction_id && r.user == registration.user));
+        registrations.push(registration.clone());
+        Ok(registration)
+    }
+}
+
+#[derive(Clone)]
+pub struct PgRegistrationRepository {
+    pool: PgPool,
+}
+
+impl PgRegistrationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+type RegistrationRow = (i64, String, String, DateTime<Utc>, Option<DateTime<Utc>>);
+
+fn to_registration(row: RegistrationRow) -> Result<Registration, Error> {
+    let (auction_id, user_id, status, registered_at, decided_at) = row;
+    Ok(Registration {
+        auction_id: AuctionId::new(auction_id),
+        user: UserId::new(user_id),
+        status: status
+            .parse()
+            .map_err(|_| Error::Repository(format!("Unknown registration status {}", status)))?,
+        registered_at,
+        decided_at,
+    })
+}
+
+#[async_trait]
+impl RegistrationRepository for PgRegistrationRepository {
+    async fn get_registration(&self, auction_id: AuctionId, user: &UserId) -> Result<Option<Registration>, Error> {
+        let row = sqlx::query_as::<_, RegistrationRow>(
+            r#"
+            SELECT auction_id, user_id, status, registered_at, decided_at
+            FROM auction_registrations
+            WHERE auction_id = $1 AND user_id = $2
+        "#,
+        )
+        .bind(auction_id.value())
+        .bind(user.value())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        row.map(to_registration).transpose()
+    }
+
+    async fn get_registrations(&self, auction_id: AuctionId) -> Result<Vec<Registration>, Error> {
+        let rows = sqlx::query_as::<_, RegistrationRow>(
+            r#"
+            SELECT auction_id, user_id, status, registered_at, decided_at
+            FROM auction_registrations
+            WHERE auction_id = $1
+            ORDER BY registered_at, user_id
+        "#,
+        )
+        .bind(auction_id.value())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        rows.into_iter().map(to_registration).collect()
+    }
+
+    async fn save(&self, registration: Registration) -> Result<Registration, Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO auction_registrations (auction_id, user_id, status, registered_at, decided_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (auction_id, user_id)
+            DO UPDATE SET status = EXCLUDED.status, decided_at = EXCLUDED.decided_at
+        "#,
+        )
+        .bind(registration.auction_id.value())
+        .bind(registration.user.value())
+        .bind(registration.status.to_string())
+        .bind(registration.registered_at)
+        .bind(registration.decided_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(registration)
+    }
+}
+
+#[derive(Clone)]
+pub struct SqliteRegistrationRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRegistrationRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RegistrationRepository for SqliteRegistrationRepository {
+    async fn get_registration(&self, auction_id: AuctionId, user: &UserId) -> Result<Option<Registration>, Error> {
+        let row = sqlx::query_as::<_, RegistrationRow>(
+            r#"
+            SELECT auction_id, user_id, status, registered_at, decided_at
+            FROM auction_registrations
+            WHERE auction_id = ?1 AND user_id = ?2
+        "#,
+        )
+        .bind(auction_id.value())
+        .bind(user.value())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        row.map(to_registration).transpose()
+    }
+
+    async fn get_registrations(&self, auction_id: AuctionId) -> Result<Vec<Registration>, Error> {
+        let rows = sqlx::query_as::<_, RegistrationRow>(
+            r#"
+            SELECT auction_id, user_id, status, registered_at, decided_at
+            FROM auction_registrations
+            WHERE auction_id = ?1
+            ORDER BY registered_at, user_id
+        "#,
+        )
+        .bind(auction_id.value())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        rows.into_iter().map(to_registration).collect()
+    }
+
+    async fn save(&self, registration: Registration) -> Result<Registration, Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO auction_registrations (auction_id, user_id, status, registered_at, decided_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT (auction_id, user_id)
+            DO UPDATE SET status = excluded.status, decided_at = excluded.decided_at
+        "#,
+        )
+        .bind(registration.auction_id.value())
+        .bind(registration.user.value())
+        .bind(registration.status.to_string())
+        .bind(registration.registered_at)
+        .bind(registration.decided_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(registration)
+    }
+}
+
+#[cfg(feature = "mysql")]
+#[derive(Clone)]
+pub struct MySqlRegistrationRepository {
+    pool: sqlx::MySqlPool,
+}
+
+#[cfg(feature = "mysql")]
+impl MySqlRegistrationRepository {
+    pub fn new(pool: sqlx::MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[cfg(feature = "mysql")]
+#[async_trait]
+impl RegistrationRepository for MySqlRegistrationRepository {
+    async fn get_registration(&self, auction_id: AuctionId, user: &UserId) -> Result<Option<Registration>, Error> {
+        let row = sqlx::query_as::<_, RegistrationRow>(
+            r#"
+            SELECT auction_id, user_id, status, registered_at, decided_at
+            FROM auction_registrations
+            WHERE auction_id = ? AND user_id = ?
+        "#,
+        )
+        .bind(auction_id.value())
+        .bind(user.value())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        row.map(to_registration).transpose()
+    }
+
+    async fn get_registrations(&self, auction_id: AuctionId) -> Result<Vec<Registration>, Error> {
+        let rows = sqlx::query_as::<_, RegistrationRow>(
+            r#"
+            SELECT auction_id, user_id, status, registered_at, decided_at
+            FROM auction_registrations
+            WHERE auction_id = ?
+            ORDER BY registered_at, user_id
+        "#,
+        )
+        .bind(auction_id.value())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        rows.into_iter().map(to_registration).collect()
+    }
+
+    async fn save(&self, registration: Registration) -> Result<Registration, Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO auction_registrations (auction_id, user_id, status, registered_at, decided_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE status = VALUES(status), decided_at = VALUES(decided_at)
+        "#,
+        )
+        .bind(registration.auction_id.value())
+        .bind(registration.user.value())
+        .bind(registration.status.to_string())
+        .bind(registration.registered_at)
+        .bind(registration.decided_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(registration)
+    }
+}
@@ -0,0 +1,146 @@
+//! Behaviour the repositories kept next to auctions share, checked against empty repositories:
+//! registrations, deposits, results, settlements, audit entries and users.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::domain::models::{
+    Amount, AuctionId, AuctionResult, AuditEntry, CurrencyCode, Deposit, FeePolicy, Registration, RegistrationStatus,
+    User, UserId,
+};
+use crate::infrastructure::data::{
+    AuctionResultRepository, AuditRepository, DepositRepository, RegistrationRepository, SettlementRepository,
+    UserRepository,
+};
+
+fn at() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2016, 1, 1, 0, 0, 0).unwrap()
+}
+
+pub(crate) async fn check_registration_repository(repo: &dyn RegistrationRepository) {
+    let auction_id = AuctionId::new(1);
+    let pending = |user: &str, registered_at| Registration {
+        auction_id,
+        user: UserId::new(user),
+        status: RegistrationStatus::Pending,
+        registered_at,
+        decided_at: None,
+    };
+    repo.save(pending("bidder-2", at() + Duration::minutes(1))).await.unwrap();
+    repo.save(pending("bidder-1", at())).await.unwrap();
+    assert_eq!(repo.get_registration(auction_id, &UserId::new("bidder-1")).await.unwrap(), Some(pending("bidder-1", at())));
+    assert_eq!(repo.get_registration(AuctionId::new(2), &UserId::new("bidder-1")).await.unwrap(), None);
+
+    // Saving again decides the registration of the bidder
+    let approved = Registration {
+        status: RegistrationStatus::Approved,
+        decided_at: Some(at() + Duration::hours(1)),
+        ..pending("bidder-1", at())
+    };
+    repo.save(approved.clone()).await.unwrap();
+    let mut registrations = repo.get_registrations(auction_id).await.unwrap();
+    registrations.sort_by(|a, b| a.user.value().cmp(b.user.value()));
+    assert_eq!(registrations, vec![approved, pending("bidder-2", at() + Duration::minutes(1))]);
+    assert!(repo.get_registrations(AuctionId::new(2)).await.unwrap().is_empty());
+}
+
+pub(crate) async fn check_deposit_repository(repo: &dyn DepositRepository) {
+    let deposit = |value| Deposit {
+        auction_id: AuctionId::new(1),
+        user: UserId::new("bidder"),
+        amount: Amount::new(value, CurrencyCode::SEK),
+        recorded_at: at(),
+    };
+    assert_eq!(repo.get_deposit(AuctionId::new(1), &UserId::new("bidder")).await.unwrap(), None);
+    repo.record(deposit(100)).await.unwrap();
+    repo.record(deposit(250)).await.unwrap();
+    assert_eq!(repo.get_deposit(AuctionId::new(1), &UserId::new("bidder")).await.unwrap(), Some(deposit(250)));
+    assert_eq!(repo.get_deposit(AuctionId::new(1), &UserId::new("other")).await.unwrap(), None);
+}
+
+pub(crate) async fn check_auction_result_repository(repo: &dyn AuctionResultRepository) {
+    let sold = AuctionResult {
+        auction_id: AuctionId::new(1),
+        winner: Some(UserId::new("buyer")),
+        amount: Some(Amount::new(100, CurrencyCode::SEK)),
+        closed_at: at(),
+        reserve_met: true,
+    };
+    let unsold = AuctionResult {
+        auction_id: AuctionId::new(2),
+        winner: None,
+        amount: None,
+        closed_at: at(),
+        reserve_met: false,
+    };
+    assert_eq!(repo.record(sold.clone()).await.unwrap(), sold);
+    assert_eq!(repo.record(unsold.clone()).await.unwrap(), unsold);
+
+    // The first result recorded stays
+    let later = AuctionResult { winner: Some(UserId::new("other")), ..sold.clone() };
+    assert_eq!(repo.record(later).await.unwrap(), sold);
+
+    let mut results = repo.get_results(&[AuctionId::new(2), AuctionId::new(1), AuctionId::new(3)]).await.unwrap();
+    results.sort_by_key(|result| result.auction_id.value());
+    assert_eq!(results, vec![sold, unsold]);
+    assert!(repo.get_results(&[]).await.unwrap().is_empty());
+}
+
+pub(crate) async fn check_settlement_repository(repo: &dyn SettlementRepository) {
+    let policy = FeePolicy { seller_fee: 25, ..FeePolicy::default() };
+    let settlement = policy.settle(CurrencyCode::SEK, 2500, vec![(Amount::new(1000, CurrencyCode::SEK), UserId::new("buyer"))]);
+    assert_eq!(repo.get_settlement(AuctionId::new(1)).await.unwrap(), None);
+    assert_eq!(repo.record(AuctionId::new(1), settlement.clone(), at()).await.unwrap(), settlement);
+
+    // The first settlement recorded stays, even after the fees change
+    let changed = FeePolicy::default().settle(CurrencyCode::SEK, 0, Vec::new());
+    assert_eq!(repo.record(AuctionId::new(1), changed, at()).await.unwrap(), settlement);
+    assert_eq!(repo.get_settlement(AuctionId::new(1)).await.unwrap(), Some(settlement));
+}
+
+pub(crate) async fn check_audit_repository(repo: &dyn AuditRepository) {
+    let entry = |reason: Option<&str>| AuditEntry {
+        auction_id: AuctionId::new(1),
+        action: "void".to_string(),
+        user: UserId::new("support"),
+        reason: reason.map(str::to_string),
+        at: at(),
+    };
+    repo.record(entry(Some("fraud"))).await.unwrap();
+    repo.record(entry(None)).await.unwrap();
+}
+
+pub(crate) async fn check_user_repository(repo: &dyn UserRepository) {
+    let alice = UserId::new("alice");
+    let bob = UserId::new("bob");
+    repo.upsert(&User::new_buyer_or_seller(alice.clone(), Some("Alice")), at()).await.unwrap();
+    repo.upsert(&User::new_buyer_or_seller(bob.clone(), None::<String>), at()).await.unwrap();
+    // Coming back without a display name keeps the one known
+    repo.upsert(&User::new_buyer_or_seller(alice.clone(), None::<String>), at()).await.unwrap();
+
+    let names = repo.get_display_names(&[alice.clone(), bob.clone(), UserId::new("carol")]).await.unwrap();
+    assert_eq!(names.len(), 1);
+    assert_eq!(names.get(&alice).map(String::as_str), Some("Alice"));
+    assert!(repo.get_display_names(&[]).await.unwrap().is_empty());
+
+    repo.remove(&alice).await.unwrap();
+    assert!(repo.get_display_names(&[alice]).await.unwrap().is_empty());
+}
+
+#[cfg(test)]
+mod repository_conformance_tests {
+    use super::*;
+    use crate::infrastructure::data::{
+        InMemoryAuctionResultRepository, InMemoryAuditRepository, InMemoryDepositRepository,
+        InMemoryRegistrationRepository, InMemorySettlementRepository, InMemoryUserRepository,
+    };
+
+    #[tokio::test]
+    async fn test_in_memory_repositories() {
+        check_registration_repository(&InMemoryRegistrationRepository::default()).await;
+        check_deposit_repository(&InMemoryDepositRepository::default()).await;
+        check_auction_result_repository(&InMemoryAuctionResultRepository::default()).await;
+        check_settlement_repository(&InMemorySettlementRepository::default()).await;
+        check_audit_repository(&InMemoryAuditRepository::default()).await;
+        check_user_repository(&InMemoryUserRepository::default()).await;
+    }
+}
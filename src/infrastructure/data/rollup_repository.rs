@@ -0,0 +1,222 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use dyn_clone::DynClone;
+use sqlx::{PgConnection, PgPool};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::domain::models::{CurrencyCode, Error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollupMetric {
+    AuctionsListed,
+    BidsPlaced,
+    /// Value of sold items, per currency
+    Gmv,
+}
+
+impl fmt::Display for RollupMetric {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RollupMetric::AuctionsListed => write!(f, "auctions_listed"),
+            RollupMetric::BidsPlaced => write!(f, "bids_placed"),
+            RollupMetric::Gmv => write!(f, "gmv"),
+        }
+    }
+}
+
+impl FromStr for RollupMetric {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auctions_listed" => Ok(RollupMetric::AuctionsListed),
+            "bids_placed" => Ok(RollupMetric::BidsPlaced),
+            "gmv" => Ok(RollupMetric::Gmv),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollupGranularity {
+    Hour,
+    Day,
+    Month,
+}
+
+impl RollupGranularity {
+    pub const ALL: [RollupGranularity; 3] = [
+        RollupGranularity::Hour,
+        RollupGranularity::Day,
+        RollupGranularity::Month,
+    ];
+}
+
+impl fmt::Display for RollupGranularity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RollupGranularity::Hour => write!(f, "hour"),
+            RollupGranularity::Day => write!(f, "day"),
+            RollupGranularity::Month => write!(f, "month"),
+        }
+    }
+}
+
+impl FromStr for RollupGranularity {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hour" => Ok(RollupGranularity::Hour),
+            "day" => Ok(RollupGranularity::Day),
+            "month" => Ok(RollupGranularity::Month),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollupPoint {
+    pub bucket: DateTime<Utc>,
+    pub currency: Option<CurrencyCode>,
+    pub value: i64,
+    /// Number of events (listings, bids or sales) summed into the value
+    pub contributors: i64,
+}
+
+dyn_clone::clone_trait_object!(RollupRepository);
+
+#[async_trait]
+pub trait RollupRepository: Send + Sync + DynClone {
+    /// Points of a metric, oldest first, with buckets starting within `from..to`.
+    async fn get_rollups(
+        &self,
+        metric: RollupMetric,
+        granularity: RollupGranularity,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<RollupPoint>, Error>;
+}
+
+/// No rollups, for running without a database.
+#[derive(Clone, Default)]
+pub struct InMemoryRollupRepository;
+
+#[async_trait]
+impl RollupRepository for InMemoryRollupRepository {
+    async fn get_rollups(
+        &self,
+        _metric: RollupMetric,
+        _granularity: RollupGranularity,
+        _from: Option<DateTime<Utc>>,
+        _to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<RollupPoint>, Error> {
+        Ok(Vec::new())
+    }
+}
+
+#[derive(Clone)]
+pub struct PgRollupRepository {
+    pool: PgPool,
+}
+
+impl PgRollupRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RollupRepository for PgRollupRepository {
+    async fn get_rollups(
+        &self,
+        metric: RollupMetric,
+        granularity: RollupGranularity,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<RollupPoint>, Error> {
+        let rows = sqlx::query_as::<_, (DateTime<Utc>, String, i64, i64)>(
+            r#"
+            SELECT bucket, currency, value, contributors
+            FROM rollups
+            WHERE metric = $1 AND granularity = $2
+              AND ($3::timestamptz IS NULL OR bucket >= $3)
+              AND ($4::timestamptz IS NULL OR bucket < $4)
+            ORDER BY bucket, currency
+        "#,
+        )
+        .bind(metric.to_string())
+        .bind(granularity.to_string())
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+
+        rows.into_iter()
+            .map(|(bucket, currency, value, contributors)| -> Result<RollupPoint, Error> {
+                let currency = match currency.as_str() {
+                    "" => None,
+                    code => Some(code.parse::<CurrencyCode>().map_err(|_| {
+                        Error::Repository(format!("get_rollups: Unknown currency code: {}", code))
+                    })?),
+                };
+                Ok(RollupPoint {
+                    bucket,
+                    currency,
+                    value,
+                    contributors,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Adds `delta` to the buckets of every granularity that `at` falls in. Each call counts as one
+/// contributor, or takes one away when `delta` is negative.
+pub(crate) async fn add_to_rollups(
+    conn: &mut PgConnection,
+    metric: RollupMetric,
+    at: DateTime<Utc>,
+    currency: Option<&str>,
+    delta: i64,
+) -> Result<(), Error> {
+    let granularities: Vec<String> = RollupGranularity::ALL.iter().map(|g| g.to_string()).collect();
+    sqlx::query(
+        r#"
+        INSERT INTO rollups (metric, granularity, bucket, currency, value, contributors)
+        SELECT $1, g, date_trunc(g, $3::timestamptz, 'UTC'), $4, $5, $6
+        FROM unnest($2::text[]) AS g
+        ON CONFLICT (metric, granularity, bucket, currency)
+        DO UPDATE SET value = rollups.value + EXCLUDED.value,
+                      contributors = rollups.contributors + EXCLUDED.contributors
+    "#,
+    )
+    .bind(metric.to_string())
+    .bind(&granularities)
+    .bind(at)
+    .bind(currency.unwrap_or(""))
+    .bind(delta)
+    .bind(delta.signum())
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| Error::Repository(e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod rollup_repository_tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_and_granularity_names_round_trip() {
+        for metric in [RollupMetric::AuctionsListed, RollupMetric::BidsPlaced, RollupMetric::Gmv] {
+            assert_eq!(metric.to_string().parse::<RollupMetric>(), Ok(metric));
+        }
+        for granularity in RollupGranularity::ALL {
+            assert_eq!(granularity.to_string().parse::<RollupGranularity>(), Ok(granularity));
+        }
+        assert!("week".parse::<RollupGranularity>().is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::{Duration, TimeZone, Utc};
+use sqlx::PgPool;
+
+use crate::domain::models::{
+    Amount, Auction, AuctionBase, AuctionId, AuctionStatus, Bid, BidIncrement, CurrencyCode,
+    Error, MaxBid, SingleSealedBidOptions, TieBreak, TimedAscendingOptions, UserId, VickreyPricing,
+};
+use crate::infrastructure::data::auction_repository::{
+    fetch_auction, insert_auction, insert_bid, upsert_max_bids,
+};
+
+fn sample_auctions() -> Vec<Auction> {
+    let starts_at = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+    let expiry = starts_at + Duration::days(1);
+    let base = |bids: Vec<Bid>| AuctionBase {
+        auction_id: AuctionId::new(0),
+        title: "schema self-check".to_string(),
+        description: Some("round-trip of the auction format".to_string()),
+        starts_at,
+        expiry,
+        user: UserId::new("self-check-seller"),
+        currency: CurrencyCode::SEK,
+        bids,
+        open_bidders: false,
+        requires_registration: false,
+        deposit: None,
+        quantity: 1,
+        external_reference: None,
+        seller_ip_hash: None,
+        lots: Vec::new(),
+        fee_policy: None,
+        tax_rate_bps: None,
+        voided_at: None,
+        void_reason: None,
+        status: AuctionStatus::Open,
+        cancelled_at: None,
+        cancel_reason: None,
+        retracted_bids: Vec::new(),
+        voided_bids: Vec::new(),
+        extended_at: None,
+        extension_reason: None,
+        pauses: Vec::new(),
+        relist_policy: None,
+        relisted_from: None,
+        relisted_as: None,
+        relist_count: 0,
+        version: 0,
+        last_bid_id: 0,
+    };
+    let bid = |id: i64, value: i64| {
+        Bid::new(
+            id,
+            UserId::new(format!("self-check-buyer{}", id)),
+            Amount::new(value, CurrencyCode::SEK),
+            starts_at + Duration::hours(id),
+        )
+    };
+
+    vec![
+        Auction::TimedAscending {
+            base: base(vec![bid(1, 10), bid(2, 20)]),
+            options: TimedAscendingOptions {
+                reserve_price: 15,
+                min_raise: 5,
+                time_frame: Duration::minutes(1),
+                buy_now_price: Some(1000),
+                starting_price: 5,
+                max_extension: Some(Duration::minutes(30)),
+                increments: vec![BidIncrement { below: 100, increment: 5 }],
+            },
+            ends_at: Some(expiry + Duration::minutes(1)),
+            max_bids: vec![MaxBid {
+                user: UserId::new("self-check-buyer2"),
+                amount: Amount::new(40, CurrencyCode::SEK),
+                at: starts_at + Duration::hours(2),
+            }],
+        },
+        Auction::SingleSealedBid {
+            base: base(vec![bid(1, 10), bid(2, 20)]),
+            options: SingleSealedBidOptions::Vickrey,
+            tie_break: TieBreak::LatestBid,
+            reserve_price: Some(15),
+            pricing: VickreyPricing::RoundUp { increment: 5 },
+        },
+    ]
+}
+
+/// Round-trips representative auctions through the live schema and the JSON query path inside a
+/// transaction that is always rolled back. Reports every auction type that no longer survives
+/// the round trip, so that format drift fails at startup instead of on the first request.
+pub async fn check_schema_compatibility(pool: &PgPool) -> Result<(), Error> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+    let mut problems = Vec::new();
+
+    for sample in sample_auctions() {
+        let auction_type = sample.auction_type();
+        let id = insert_auction(&mut tx, &sample).await?;
+        for bid in sample.bids() {
+            insert_bid(&mut tx, id, bid).await?;
+        }
+        if let Auction::TimedAscending { max_bids, .. } = &sample {
+            upsert_max_bids(&mut tx, id, max_bids).await?;
+        }
+        let mut expected = sample;
+        expected.set_auction_id(id);
+
+        match fetch_auction(&mut tx, id).await {
+            Ok(Some(actual)) if actual == expected => {}
+            Ok(Some(actual)) => problems.push(format!(
+                "{}: read back as {:?}, expected {:?}",
+                auction_type, actual, expected
+            )),
+            Ok(None) => problems.push(format!("{}: could not be read back", auction_type)),
+            Err(e) => problems.push(format!("{}: {}", auction_type, e)),
+        }
+    }
+
+    // Never keep the samples
+    tx.rollback()
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Internal(format!(
+            "Schema and domain serialization are out of sync:\n{}",
+            problems.join("\n")
+        )))
+    }
+}
@@ -0,0 +1,226 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use dyn_clone::DynClone;
+use sqlx::{PgPool, SqlitePool};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::domain::models::{AuctionId, Error, Settlement};
+
+dyn_clone::clone_trait_object!(SettlementRepository);
+
+/// Settlements as they were first worked out, so that invoices can be reproduced after the fees
+/// or tax rates change.
+#[async_trait]
+pub trait SettlementRepository: Send + Sync + DynClone {
+    async fn get_settlement(&self, auction_id: AuctionId) -> Result<Option<Settlement>, Error>;
+    /// Records the settlement unless the auction already has one, returning the one that is kept.
+    async fn record(
+        &self,
+        auction_id: AuctionId,
+        settlement: Settlement,
+        settled_at: DateTime<Utc>,
+    ) -> Result<Settlement, Error>;
+}
+
+/// Keeps settlements in process memory, for running without a database.
+#[derive(Clone, Default)]
+pub struct InMemorySettlementRepository {
+    settlements: Arc<Mutex<HashMap<AuctionId, Settlement>>>,
+}
+
+#[async_trait]
+impl SettlementRepository for InMemorySettlementRepository {
+    async fn get_settlement(&self, auction_id: AuctionId) -> Result<Option<Settlement>, Error> {
+        Ok(self.settlements.lock().unwrap().get(&auction_id).cloned())
+    }
+
+    async fn record(
+        &self,
+        auction_id: AuctionId,
+        settlement: Settlement,
+        _settled_at: DateTime<Utc>,
+    ) -> Result<Settlement, Error> {
+        Ok(self
+            .settlements
+            .lock()
+            .unwrap()
+            .entry(auction_id)
+            .or_insert(settlement)
+            .clone())
+    }
+}
+
+#[derive(Clone)]
+pub struct PgSettlementRepository {
+    pool: PgPool,
+}
+
+impl PgSettlementRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn to_settlement(value: serde_json::Value) -> Result<Settlement, Error> {
+    serde_json::from_value(value).map_err(|e| Error::Repository(format!("Unreadable settlement: {}", e)))
+}
+
+#[async_trait]
+impl SettlementRepository for PgSettlementRepository {
+    async fn get_settlement(&self, auction_id: AuctionId) -> Result<Option<Settlement>, Error> {
+        let row = sqlx::query_scalar::<_, serde_json::Value>(
+            r#"
+            SELECT settlement FROM settlements WHERE auction_id = $1
+        "#,
+        )
+        .bind(auction_id.value())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        row.map(to_settlement).transpose()
+    }
+
+    async fn record(
+        &self,
+        auction_id: AuctionId,
+        settlement: Settlement,
+        settled_at: DateTime<Utc>,
+    ) -> Result<Settlement, Error> {
+        let value = serde_json::to_value(&settlement).map_err(|e| Error::Repository(e.to_string()))?;
+        // Concurrent readers may both work out a settlement; the first one recorded stays
+        let row = sqlx::query_scalar::<_, serde_json::Value>(
+            r#"
+            WITH inserted AS (
+                INSERT INTO settlements (auction_id, settlement, settled_at)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (auction_id) DO NOTHING
+                RETURNING settlement
+            )
+            SELECT settlement FROM inserted
+            UNION ALL
+            SELECT settlement FROM settlements WHERE auction_id = $1
+        "#,
+        )
+        .bind(auction_id.value())
+        .bind(value)
+        .bind(settled_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        to_settlement(row)
+    }
+}
+
+/// Keeps each settlement as JSON text.
+#[derive(Clone)]
+pub struct SqliteSettlementRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteSettlementRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+fn read_settlement(json: &str) -> Result<Settlement, Error> {
+    serde_json::from_str(json).map_err(|e| Error::Repository(format!("Unreadable settlement: {}", e)))
+}
+
+#[async_trait]
+impl SettlementRepository for SqliteSettlementRepository {
+    async fn get_settlement(&self, auction_id: AuctionId) -> Result<Option<Settlement>, Error> {
+        let row = sqlx::query_scalar::<_, String>("SELECT settlement FROM settlements WHERE auction_id = ?1")
+            .bind(auction_id.value())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        row.map(|json| read_settlement(&json)).transpose()
+    }
+
+    async fn record(
+        &self,
+        auction_id: AuctionId,
+        settlement: Settlement,
+        settled_at: DateTime<Utc>,
+    ) -> Result<Settlement, Error> {
+        let json = serde_json::to_string(&settlement).map_err(|e| Error::Repository(e.to_string()))?;
+        // Concurrent readers may both work out a settlement; the first one recorded stays
+        sqlx::query(
+            r#"
+            INSERT INTO settlements (auction_id, settlement, settled_at)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT (auction_id) DO NOTHING
+        "#,
+        )
+        .bind(auction_id.value())
+        .bind(json)
+        .bind(settled_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        let row = sqlx::query_scalar::<_, String>("SELECT settlement FROM settlements WHERE auction_id = ?1")
+            .bind(auction_id.value())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        read_settlement(&row)
+    }
+}
+
+/// Keeps each settlement as JSON text, like [`SqliteSettlementRepository`].
+#[cfg(feature = "mysql")]
+#[derive(Clone)]
+pub struct MySqlSettlementRepository {
+    pool: sqlx::MySqlPool,
+}
+
+#[cfg(feature = "mysql")]
+impl MySqlSettlementRepository {
+    pub fn new(pool: sqlx::MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[cfg(feature = "mysql")]
+#[async_trait]
+impl SettlementRepository for MySqlSettlementRepository {
+    async fn get_settlement(&self, auction_id: AuctionId) -> Result<Option<Settlement>, Error> {
+        let row = sqlx::query_scalar::<_, String>("SELECT settlement FROM settlements WHERE auction_id = ?")
+            .bind(auction_id.value())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        row.map(|json| read_settlement(&json)).transpose()
+    }
+
+    async fn record(
+        &self,
+        auction_id: AuctionId,
+        settlement: Settlement,
+        settled_at: DateTime<Utc>,
+    ) -> Result<Settlement, Error> {
+        let json = serde_json::to_string(&settlement).map_err(|e| Error::Repository(e.to_string()))?;
+        // Concurrent readers may both work out a settlement; the first one recorded stays
+        sqlx::query(
+            r#"
+            INSERT INTO settlements (auction_id, settlement, settled_at)
+            VALUES (?, ?, ?)
+            ON DUPLICATE KEY UPDATE auction_id = auction_id
+        "#,
+        )
+        .bind(auction_id.value())
+        .bind(json)
+        .bind(settled_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        let row = sqlx::query_scalar::<_, String>("SELECT settlement FROM settlements WHERE auction_id = ?")
+            .bind(auction_id.value())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        read_settlement(&row)
+    }
+}
@@ -0,0 +1,300 @@
+use async_trait::async_trait;
+use sqlx::{SqliteConnection, SqlitePool};
+
+use crate::domain::models::{Auction, AuctionId, Error, Errors, UserId};
+use crate::infrastructure::data::{changed_since_read, AuctionFilter, AuctionPage, AuctionRepository};
+
+/// Stores each auction as one JSON document, for small deployments and local development
+/// without Postgres. The seller, external reference, status and number of bids are kept in
+/// columns next to the document, to look auctions up and count them.
+#[derive(Clone)]
+pub struct SqliteAuctionRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteAuctionRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+fn repository_error(e: sqlx::Error) -> Error {
+    Error::Repository(e.to_string())
+}
+
+fn deserialize_auction(json: &str) -> Result<Auction, Error> {
+    serde_json::from_str(json).map_err(|e| Error::Repository(format!("Invalid stored auction: {}", e)))
+}
+
+fn serialize_auction(auction: &Auction) -> Result<String, Error> {
+    serde_json::to_string(auction).map_err(|e| Error::Internal(e.to_string()))
+}
+
+/// Writes the document and the columns derived from it, over the document at `version` only.
+async fn write_auction(conn: &mut SqliteConnection, auction: &Auction, version: i32) -> Result<bool, Error> {
+    let updated = sqlx::query(
+        r#"
+        UPDATE auctions
+        SET user_id = ?2, external_reference = ?3, status = ?4, bid_count = ?5, auction = ?6
+        WHERE id = ?1 AND coalesce(json_extract(auction, '$.version'), 0) = ?7
+    "#,
+    )
+    .bind(auction.auction_id().value())
+    .bind(auction.user().value())
+    .bind(auction.external_reference())
+    .bind(auction.status().to_string())
+    .bind(auction.bids().len() as i64)
+    .bind(serialize_auction(auction)?)
+    .bind(version)
+    .execute(&mut *conn)
+    .await
+    .map_err(repository_error)?;
+    Ok(updated.rows_affected() > 0)
+}
+
+#[async_trait]
+impl AuctionRepository for SqliteAuctionRepository {
+    async fn get_auction(&self, auction_id: AuctionId) -> Result<Option<Auction>, Error> {
+        let json = sqlx::query_scalar::<_, String>("SELECT auction FROM auctions WHERE id = ?1")
+            .bind(auction_id.value())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(repository_error)?;
+        json.as_deref().map(deserialize_auction).transpose()
+    }
+
+    async fn get_auctions(&self) -> Result<Vec<Auction>, Error> {
+        let rows = sqlx::query_scalar::<_, String>("SELECT auction FROM auctions ORDER BY id")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(repository_error)?;
+        rows.iter().map(|json| deserialize_auction(json)).collect()
+    }
+
+    async fn get_auctions_page(&self, limit: i64, offset: i64, filter: &AuctionFilter) -> Result<AuctionPage, Error> {
+        let total = self.count_auctions(filter).await?;
+        let rows = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT auction
+            FROM auctions
+            WHERE (?1 IS NULL OR user_id = ?1)
+                AND (?2 IS NULL OR status = ?2)
+                AND (?3 IS NULL OR status <> ?3)
+            ORDER BY id
+            LIMIT ?4 OFFSET ?5
+        "#,
+        )
+        .bind(filter.seller.as_ref().map(|seller| seller.value()))
+        .bind(filter.status.map(|status| status.to_string()))
+        .bind(filter.hidden_status())
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(repository_error)?;
+        let auctions = rows.iter().map(|json| deserialize_auction(json)).collect::<Result<_, _>>()?;
+        Ok(AuctionPage::new(auctions, total, offset))
+    }
+
+    async fn get_auctions_by_seller(&self, seller: &UserId) -> Result<Vec<Auction>, Error> {
+        let rows = sqlx::query_scalar::<_, String>("SELECT auction FROM auctions WHERE user_id = ?1 ORDER BY id")
+            .bind(seller.value())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(repository_error)?;
+        rows.iter().map(|json| deserialize_auction(json)).collect()
+    }
+
+    async fn get_auctions_by_ids(&self, auction_ids: &[AuctionId]) -> Result<Vec<Auction>, Error> {
+        let ids: Vec<i64> = auction_ids.iter().map(|id| id.value()).collect();
+        let ids = serde_json::to_string(&ids).map_err(|e| Error::Internal(e.to_string()))?;
+        let rows = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT a.auction
+            FROM json_each(?1) AS requested
+            JOIN auctions a ON a.id = requested.value
+            ORDER BY requested.key
+        "#,
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(repository_error)?;
+        rows.iter().map(|json| deserialize_auction(json)).collect()
+    }
+
+    async fn get_auction_by_external_reference(
+        &self,
+        user: &UserId,
+        external_reference: &str,
+    ) -> Result<Option<Auction>, Error> {
+        let json = sqlx::query_scalar::<_, String>(
+            "SELECT auction FROM auctions WHERE user_id = ?1 AND external_reference = ?2",
+        )
+        .bind(user.value())
+        .bind(external_reference)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(repository_error)?;
+        json.as_deref().map(deserialize_auction).transpose()
+    }
+
+    async fn create_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        let mut tx = self.pool.begin().await.map_err(repository_error)?;
+        // The id is generated by the insert and then written into the document
+        let id = sqlx::query_scalar::<_, i64>(
+            r#"
+            INSERT INTO auctions (user_id, external_reference, status, auction)
+            VALUES (?1, ?2, ?3, '{}')
+            RETURNING id
+        "#,
+        )
+        .bind(auction.user().value())
+        .bind(auction.external_reference())
+        .bind(auction.status().to_string())
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match e.as_database_error() {
+            // Only the external reference is unique besides the generated id
+            Some(db_error) if db_error.is_unique_violation() => {
+                Error::Validation(Errors::AuctionAlreadyExists)
+            }
+            _ => repository_error(e),
+        })?;
+        let mut new_auction = auction;
+        new_auction.set_auction_id(AuctionId::new(id));
+        new_auction.set_version(0);
+        write_auction(&mut tx, &new_auction, 0).await?;
+        tx.commit().await.map_err(repository_error)?;
+        Ok(new_auction)
+    }
+
+    async fn update_auction(&self, auction: Auction) -> Result<Auction, Error> {
+        let not_found = || Error::NotFound(format!("Auction with ID {} not found", auction.auction_id()));
+        let mut tx = self.pool.begin().await.map_err(repository_error)?;
+        let json = sqlx::query_scalar::<_, String>("SELECT auction FROM auctions WHERE id = ?1")
+            .bind(auction.auction_id().value())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(repository_error)?
+            .ok_or_else(not_found)?;
+        let existing = deserialize_auction(&json)?;
+        if existing.version() != auction.version() {
+            return Err(changed_since_read(auction.auction_id()));
+        }
+        if existing.status() != auction.status() && !existing.status().can_transition_to(auction.status()) {
+            return Err(Error::Validation(Errors::IllegalStatusTransition));
+        }
+        let mut auction = auction;
+        auction.set_version(existing.version() + 1);
+        if !write_auction(&mut tx, &auction, existing.version()).await? {
+            return Err(changed_since_read(auction.auction_id()));
+        }
+        tx.commit().await.map_err(repository_error)?;
+        Ok(auction)
+    }
+
+    async fn auction_exists(&self, auction_id: AuctionId) -> Result<bool, Error> {
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM auctions WHERE id = ?1)")
+            .bind(auction_id.value())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(repository_error)
+    }
+
+    async fn count_auctions(&self, filter: &AuctionFilter) -> Result<i64, Error> {
+        sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM auctions
+            WHERE (?1 IS NULL OR user_id = ?1)
+                AND (?2 IS NULL OR status = ?2)
+                AND (?3 IS NULL OR status <> ?3)
+        "#,
+        )
+        .bind(filter.seller.as_ref().map(|seller| seller.value()))
+        .bind(filter.status.map(|status| status.to_string()))
+        .bind(filter.hidden_status())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(repository_error)
+    }
+
+    async fn count_bids(&self, auction_id: AuctionId) -> Result<i64, Error> {
+        let count = sqlx::query_scalar::<_, i64>("SELECT bid_count FROM auctions WHERE id = ?1")
+            .bind(auction_id.value())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(repository_error)?;
+        Ok(count.unwrap_or(0))
+    }
+
+    async fn anonymize_user(&self, user: &UserId, pseudonym: &UserId) -> Result<Vec<AuctionId>, Error> {
+        let mut tx = self.pool.begin().await.map_err(repository_error)?;
+        // Only documents mentioning the user can refer to them
+        let needle = serde_json::to_string(user.value()).map_err(|e| Error::Internal(e.to_string()))?;
+        let rows = sqlx::query_scalar::<_, String>("SELECT auction FROM auctions WHERE instr(auction, ?1) > 0 ORDER BY id")
+            .bind(needle)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(repository_error)?;
+        let mut anonymized = Vec::new();
+        for json in rows {
+            let mut auction = deserialize_auction(&json)?;
+            if auction.anonymize_user(user, pseudonym) {
+                let version = auction.version();
+                auction.set_version(version + 1);
+                write_auction(&mut tx, &auction, version).await?;
+                anonymized.push(auction.auction_id());
+            }
+        }
+        tx.commit().await.map_err(repository_error)?;
+        Ok(anonymized)
+    }
+}
+
+#[cfg(test)]
+mod sqlite_repository_tests {
+    use super::*;
+    use crate::domain::models::CurrencyCode;
+    use crate::infrastructure::data::auction_repository_conformance::check_auction_repository;
+    use crate::infrastructure::data::repository_conformance::{
+        check_auction_result_repository, check_audit_repository, check_deposit_repository,
+        check_registration_repository, check_settlement_repository, check_user_repository,
+    };
+    use crate::infrastructure::data::{create_sqlite_pool, run_sqlite_migrations, PoolSettings, CurrencyRepository, SqliteCurrencyRepository};
+    use crate::infrastructure::data::{
+        SqliteAuctionResultRepository, SqliteAuditRepository, SqliteDepositRepository, SqliteRegistrationRepository,
+        SqliteSettlementRepository, SqliteUserRepository,
+    };
+
+    async fn pool() -> SqlitePool {
+        let pool = create_sqlite_pool("sqlite::memory:", PoolSettings::default()).await.unwrap();
+        run_sqlite_migrations(&pool).await.unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_auction_repository() {
+        check_auction_repository(&SqliteAuctionRepository::new(pool().await)).await;
+    }
+
+    #[tokio::test]
+    async fn test_currencies_are_seeded() {
+        let currencies = SqliteCurrencyRepository::new(pool().await).get_currencies().await.unwrap();
+
+        let codes: Vec<CurrencyCode> = currencies.iter().map(|currency| currency.code).collect();
+        assert_eq!(codes, vec![CurrencyCode::DKK, CurrencyCode::EUR, CurrencyCode::SEK, CurrencyCode::VAC]);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_repositories_next_to_auctions() {
+        let pool = pool().await;
+        check_registration_repository(&SqliteRegistrationRepository::new(pool.clone())).await;
+        check_deposit_repository(&SqliteDepositRepository::new(pool.clone())).await;
+        check_auction_result_repository(&SqliteAuctionResultRepository::new(pool.clone())).await;
+        check_settlement_repository(&SqliteSettlementRepository::new(pool.clone())).await;
+        check_audit_repository(&SqliteAuditRepository::new(pool.clone())).await;
+        check_user_repository(&SqliteUserRepository::new(pool)).await;
+    }
+}
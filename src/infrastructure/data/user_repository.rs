@@ -0,0 +1,266 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use dyn_clone::DynClone;
+use sqlx::{PgPool, SqlitePool};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::domain::models::{Error, User, UserId};
+
+dyn_clone::clone_trait_object!(UserRepository);
+
+#[async_trait]
+pub trait UserRepository: Send + Sync + DynClone {
+    /// Inserts the user, or updates the role and display name of a known one. A display name is
+    /// kept when the user comes back without one.
+    async fn upsert(&self, user: &User, at: DateTime<Utc>) -> Result<(), Error>;
+    /// The display names of those of the users that have one.
+    async fn get_display_names(&self, users: &[UserId]) -> Result<HashMap<UserId, String>, Error>;
+    async fn remove(&self, user: &UserId) -> Result<(), Error>;
+}
+
+pub(crate) fn role(user: &User) -> &'static str {
+    match user {
+        User::BuyerOrSeller { .. } => "BuyerOrSeller",
+        User::Support { .. } => "Support",
+    }
+}
+
+pub(crate) fn display_name(user: &User) -> Option<&str> {
+    match user {
+        User::BuyerOrSeller { name, .. } => name.as_deref(),
+        User::Support { .. } => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct StoredUser {
+    display_name: Option<String>,
+    role: &'static str,
+    created_at: DateTime<Utc>,
+}
+
+/// Keeps users in process memory, for running without a database.
+#[derive(Clone, Default)]
+pub struct InMemoryUserRepository {
+    users: Arc<Mutex<HashMap<UserId, StoredUser>>>,
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn upsert(&self, user: &User, at: DateTime<Utc>) -> Result<(), Error> {
+        let mut users = self.users.lock().unwrap();
+        let stored = users.entry(user.id().clone()).or_insert_with(|| StoredUser {
+            display_name: None,
+            role: role(user),
+            created_at: at,
+        });
+        stored.role = role(user);
+        if let Some(name) = display_name(user) {
+            stored.display_name = Some(name.to_string());
+        }
+        Ok(())
+    }
+
+    async fn get_display_names(&self, users: &[UserId]) -> Result<HashMap<UserId, String>, Error> {
+        let stored = self.users.lock().unwrap();
+        Ok(users
+            .iter()
+            .filter_map(|user| {
+                let name = stored.get(user)?.display_name.clone()?;
+                Some((user.clone(), name))
+            })
+            .collect())
+    }
+
+    async fn remove(&self, user: &UserId) -> Result<(), Error> {
+        self.users.lock().unwrap().remove(user);
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct PgUserRepository {
+    pool: PgPool,
+}
+
+impl PgUserRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UserRepository for PgUserRepository {
+    async fn upsert(&self, user: &User, at: DateTime<Utc>) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, display_name, role, created_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (id)
+            DO UPDATE SET display_name = COALESCE(EXCLUDED.display_name, users.display_name),
+                role = EXCLUDED.role
+        "#,
+        )
+        .bind(user.id().value())
+        .bind(display_name(user))
+        .bind(role(user))
+        .bind(at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_display_names(&self, users: &[UserId]) -> Result<HashMap<UserId, String>, Error> {
+        if users.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let ids: Vec<&str> = users.iter().map(|user| user.value()).collect();
+        let rows = sqlx::query_as::<_, (String, String)>(
+            r#"
+            SELECT id, display_name
+            FROM users
+            WHERE id = ANY($1) AND display_name IS NOT NULL
+        "#,
+        )
+        .bind(&ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(rows.into_iter().map(|(id, name)| (UserId::new(id), name)).collect())
+    }
+
+    async fn remove(&self, user: &UserId) -> Result<(), Error> {
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(user.value())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct SqliteUserRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteUserRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UserRepository for SqliteUserRepository {
+    async fn upsert(&self, user: &User, at: DateTime<Utc>) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, display_name, role, created_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (id)
+            DO UPDATE SET display_name = coalesce(excluded.display_name, users.display_name),
+                role = excluded.role
+        "#,
+        )
+        .bind(user.id().value())
+        .bind(display_name(user))
+        .bind(role(user))
+        .bind(at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_display_names(&self, users: &[UserId]) -> Result<HashMap<UserId, String>, Error> {
+        let ids: Vec<&str> = users.iter().map(|user| user.value()).collect();
+        let ids = serde_json::to_string(&ids).map_err(|e| Error::Internal(e.to_string()))?;
+        let rows = sqlx::query_as::<_, (String, String)>(
+            r#"
+            SELECT id, display_name
+            FROM users
+            WHERE id IN (SELECT value FROM json_each(?1)) AND display_name IS NOT NULL
+        "#,
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(rows.into_iter().map(|(id, name)| (UserId::new(id), name)).collect())
+    }
+
+    async fn remove(&self, user: &UserId) -> Result<(), Error> {
+        sqlx::query("DELETE FROM users WHERE id = ?1")
+            .bind(user.value())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "mysql")]
+#[derive(Clone)]
+pub struct MySqlUserRepository {
+    pool: sqlx::MySqlPool,
+}
+
+#[cfg(feature = "mysql")]
+impl MySqlUserRepository {
+    pub fn new(pool: sqlx::MySqlPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[cfg(feature = "mysql")]
+#[async_trait]
+impl UserRepository for MySqlUserRepository {
+    async fn upsert(&self, user: &User, at: DateTime<Utc>) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, display_name, role, created_at)
+            VALUES (?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE display_name = COALESCE(VALUES(display_name), display_name),
+                role = VALUES(role)
+        "#,
+        )
+        .bind(user.id().value())
+        .bind(display_name(user))
+        .bind(role(user))
+        .bind(at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_display_names(&self, users: &[UserId]) -> Result<HashMap<UserId, String>, Error> {
+        if users.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let mut query =
+            sqlx::QueryBuilder::<sqlx::MySql>::new("SELECT id, display_name FROM users WHERE display_name IS NOT NULL AND id IN (");
+        let mut ids = query.separated(", ");
+        for user in users {
+            ids.push_bind(user.value());
+        }
+        query.push(")");
+        let rows = query
+            .build_query_as::<(String, String)>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(rows.into_iter().map(|(id, name)| (UserId::new(id), name)).collect())
+    }
+
+    async fn remove(&self, user: &UserId) -> Result<(), Error> {
+        sqlx::query("DELETE FROM users WHERE id = ?")
+            .bind(user.value())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -2,8 +2,10 @@ pub mod data;
 pub mod services;
 pub mod web;
 pub mod config;
+pub mod contract_test;
 
 pub use data::*;
 pub use services::*;
 pub use web::*;
 pub use config::*;
+pub use contract_test::*;
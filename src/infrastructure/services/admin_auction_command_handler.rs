@@ -0,0 +1,146 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+
+use crate::domain::commands::AdminAuctionCommand;
+use crate::domain::models::{Auction, AuditEntry, Error, Errors, User};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::data::{AuctionRepository, AuditRepository};
+
+#[async_trait]
+pub trait AdminAuctionCommandHandler: Send + Sync + DynClone {
+    async fn handle(&self, user: Option<User>, command: AdminAuctionCommand) -> Result<Auction, Error>;
+}
+
+dyn_clone::clone_trait_object!(AdminAuctionCommandHandler);
+
+#[derive(Clone)]
+pub struct DefaultAdminAuctionCommandHandler {
+    repository: Box<dyn AuctionRepository>,
+    audit_repository: Box<dyn AuditRepository>,
+    system_clock: Box<dyn SystemClock>,
+}
+
+impl DefaultAdminAuctionCommandHandler {
+    pub fn new(
+        repository: Box<dyn AuctionRepository>,
+        audit_repository: Box<dyn AuditRepository>,
+        system_clock: Box<dyn SystemClock>,
+    ) -> Self {
+        Self {
+            repository,
+            audit_repository,
+            system_clock,
+        }
+    }
+}
+
+#[async_trait]
+impl AdminAuctionCommandHandler for DefaultAdminAuctionCommandHandler {
+    async fn handle(&self, user: Option<User>, command: AdminAuctionCommand) -> Result<Auction, Error> {
+        let user = user
+            .ok_or_else(|| Error::Unauthorized("User must be logged in to administer auctions".to_string()))?;
+        let support_id = match user {
+            User::Support { id } => id,
+            User::BuyerOrSeller { .. } => {
+                return Err(Error::Forbidden("Only support users may administer auctions".to_string()))
+            }
+        };
+
+        let mut auction = match self.repository.get_auction(command.auction_id()).await? {
+            Some(auction) => auction,
+            None => return Err(Error::Validation(Errors::UnknownAuction)),
+        };
+        let now = self.system_clock.now();
+
+        match &command {
+            AdminAuctionCommand::ForceEnd { .. } => {
+                auction.force_end(now).map_err(Error::Validation)?
+            }
+            AdminAuctionCommand::Void { reason, .. } => {
+                auction.void(now, reason.clone()).map_err(Error::Validation)?
+            }
+            AdminAuctionCommand::Pause { .. } => auction.pause(now).map_err(Error::Validation)?,
+            AdminAuctionCommand::Resume { .. } => auction.resume(now).map_err(Error::Validation)?,
+            AdminAuctionCommand::VoidBid { bid_id, reason, .. } => {
+                auction
+                    .void_bid(now, *bid_id, &support_id, reason.clone())
+                    .map_err(Error::Validation)?;
+            }
+        }
+        let auction = self.repository.update_auction(auction).await?;
+
+        self.audit_repository
+            .record(AuditEntry {
+                auction_id: auction.auction_id(),
+                action: command.action().to_string(),
+                user: support_id.clone(),
+                reason: command.reason().map(|r| r.to_string()),
+                at: now,
+            })
+            .await?;
+        log::warn!(
+            "Auction {} {} by support user {}",
+            auction.auction_id(),
+            command.action(),
+            support_id
+        );
+
+        Ok(auction)
+    }
+}
+
+#[cfg(test)]
+mod admin_auction_command_handler_tests {
+    use super::*;
+    use crate::domain::models::{AuctionId, UserId};
+    use crate::domain::services::FixedSystemClock;
+    use crate::domain::test_support::{self, lamp, starts_at, with_bid};
+    use crate::infrastructure::data::{InMemoryAuctionRepository, InMemoryAuditRepository};
+    use chrono::Duration;
+
+    fn auction() -> Auction {
+        with_bid(test_support::auction(lamp()), "buyer", 100)
+    }
+
+    fn handler(audit: InMemoryAuditRepository) -> DefaultAdminAuctionCommandHandler {
+        DefaultAdminAuctionCommandHandler::new(
+            Box::new(InMemoryAuctionRepository::new(vec![auction()])),
+            Box::new(audit),
+            Box::new(FixedSystemClock(starts_at() + Duration::hours(2))),
+        )
+    }
+
+    fn void_bid() -> AdminAuctionCommand {
+        AdminAuctionCommand::VoidBid {
+            auction_id: AuctionId::new(1),
+            bid_id: 1,
+            reason: Some("fraud".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_support_voids_bid_and_is_audited() {
+        let audit = InMemoryAuditRepository::default();
+        let support = User::new_support(UserId::new("support"));
+
+        let auction = handler(audit.clone()).handle(Some(support), void_bid()).await.unwrap();
+
+        assert!(auction.bids().is_empty());
+        let entries = audit.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].user, UserId::new("support"));
+    }
+
+    #[tokio::test]
+    async fn test_only_support_voids_bids() {
+        let audit = InMemoryAuditRepository::default();
+        for user in ["seller", "buyer"] {
+            let user = User::new_buyer_or_seller(UserId::new(user), None::<String>);
+            let result = handler(audit.clone()).handle(Some(user), void_bid()).await;
+            assert!(matches!(result, Err(Error::Forbidden(_))));
+        }
+        let result = handler(audit.clone()).handle(None, void_bid()).await;
+        assert!(matches!(result, Err(Error::Unauthorized(_))));
+        assert!(audit.entries().is_empty());
+    }
+}
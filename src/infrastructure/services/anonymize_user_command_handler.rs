@@ -0,0 +1,184 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+
+use crate::domain::commands::AnonymizeUserCommand;
+use crate::domain::models::{AuctionId, AuditEntry, Error, User, UserId};
+use crate::domain::services::{RandomSource, SystemClock};
+use crate::infrastructure::data::{AuctionRepository, AuditRepository};
+use crate::infrastructure::services::UserDirectory;
+
+/// The user as they appear from now on, and the auctions they took part in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnonymizedUser {
+    pub pseudonym: UserId,
+    pub auctions: Vec<AuctionId>,
+}
+
+#[async_trait]
+pub trait AnonymizeUserCommandHandler: Send + Sync + DynClone {
+    async fn handle(&self, user: Option<User>, command: AnonymizeUserCommand) -> Result<AnonymizedUser, Error>;
+}
+
+dyn_clone::clone_trait_object!(AnonymizeUserCommandHandler);
+
+#[derive(Clone)]
+pub struct DefaultAnonymizeUserCommandHandler {
+    repository: Box<dyn AuctionRepository>,
+    audit_repository: Box<dyn AuditRepository>,
+    users: UserDirectory,
+    random_source: Box<dyn RandomSource>,
+    system_clock: Box<dyn SystemClock>,
+}
+
+impl DefaultAnonymizeUserCommandHandler {
+    pub fn new(
+        repository: Box<dyn AuctionRepository>,
+        audit_repository: Box<dyn AuditRepository>,
+        users: UserDirectory,
+        random_source: Box<dyn RandomSource>,
+        system_clock: Box<dyn SystemClock>,
+    ) -> Self {
+        Self {
+            repository,
+            audit_repository,
+            users,
+            random_source,
+            system_clock,
+        }
+    }
+
+    /// Random rather than derived from the user id, which could be guessed back from a hash.
+    fn pseudonym(&self) -> UserId {
+        UserId::new(format!(
+            "anonymized-{:016x}{:016x}",
+            self.random_source.next_u64(),
+            self.random_source.next_u64()
+        ))
+    }
+}
+
+#[async_trait]
+impl AnonymizeUserCommandHandler for DefaultAnonymizeUserCommandHandler {
+    async fn handle(&self, user: Option<User>, command: AnonymizeUserCommand) -> Result<AnonymizedUser, Error> {
+        let user = user
+            .ok_or_else(|| Error::Unauthorized("User must be logged in to anonymize users".to_string()))?;
+        let support_id = match user {
+            User::Support { id } => id,
+            User::BuyerOrSeller { .. } => {
+                return Err(Error::Forbidden("Only support users may anonymize users".to_string()))
+            }
+        };
+
+        let pseudonym = self.pseudonym();
+        let auctions = self.repository.anonymize_user(&command.user_id, &pseudonym).await?;
+        self.users.forget(&command.user_id).await?;
+
+        // The audit log names the pseudonym only, so that it does not undo the anonymization
+        let now = self.system_clock.now();
+        for auction_id in &auctions {
+            self.audit_repository
+                .record(AuditEntry {
+                    auction_id: *auction_id,
+                    action: "anonymize_user".to_string(),
+                    user: support_id.clone(),
+                    reason: Some(format!("Anonymized as {}", pseudonym)),
+                    at: now,
+                })
+                .await?;
+        }
+        log::warn!(
+            "User anonymized as {} in {} auctions by support user {}",
+            pseudonym,
+            auctions.len(),
+            support_id
+        );
+
+        Ok(AnonymizedUser { pseudonym, auctions })
+    }
+}
+
+#[cfg(test)]
+mod anonymize_user_command_handler_tests {
+    use super::*;
+    use crate::domain::commands::CreateAuctionCommand;
+    use crate::domain::models::{Amount, Auction, CurrencyCode};
+    use crate::domain::services::{FixedSystemClock, SeededRandomSource};
+    use crate::domain::test_support::{self, lamp, starts_at, with_bid};
+    use crate::infrastructure::data::{InMemoryAuctionRepository, InMemoryAuditRepository, InMemoryUserRepository};
+    use chrono::Duration;
+
+    fn auction() -> Auction {
+        let auction = test_support::auction(CreateAuctionCommand { open_bidders: true, ..lamp() });
+        with_bid(with_bid(auction, "buyer", 100), "other", 200)
+    }
+
+    fn handler(repository: InMemoryAuctionRepository, audit: InMemoryAuditRepository) -> DefaultAnonymizeUserCommandHandler {
+        let clock = FixedSystemClock(starts_at() + Duration::hours(2));
+        DefaultAnonymizeUserCommandHandler::new(
+            Box::new(repository),
+            Box::new(audit),
+            UserDirectory::new(Box::new(InMemoryUserRepository::default()), Box::new(clock.clone())),
+            Box::new(SeededRandomSource::new(0)),
+            Box::new(clock),
+        )
+    }
+
+    fn anonymize(user: &str) -> AnonymizeUserCommand {
+        AnonymizeUserCommand {
+            user_id: UserId::new(user),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_support_anonymizes_bidder_and_is_audited() {
+        let repository = InMemoryAuctionRepository::new(vec![auction()]);
+        let audit = InMemoryAuditRepository::default();
+        let support = User::new_support(UserId::new("support"));
+
+        let anonymized = handler(repository.clone(), audit.clone())
+            .handle(Some(support), anonymize("buyer"))
+            .await
+            .unwrap();
+
+        assert_eq!(anonymized.auctions, vec![AuctionId::new(1)]);
+        let auction = repository.get_auction(AuctionId::new(1)).await.unwrap().unwrap();
+        let bidders: Vec<&UserId> = auction.bids().iter().map(|bid| bid.user()).collect();
+        assert_eq!(bidders, vec![&anonymized.pseudonym, &UserId::new("other")]);
+        assert_eq!(auction.bids()[0].amount(), &Amount::new(100, CurrencyCode::SEK));
+        let entries = audit.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].user, UserId::new("support"));
+        assert!(!format!("{:?}", entries[0]).contains("buyer"));
+    }
+
+    #[tokio::test]
+    async fn test_seller_keeps_their_auction_under_pseudonym() {
+        let repository = InMemoryAuctionRepository::new(vec![auction()]);
+        let support = User::new_support(UserId::new("support"));
+
+        let anonymized = handler(repository.clone(), InMemoryAuditRepository::default())
+            .handle(Some(support), anonymize("seller"))
+            .await
+            .unwrap();
+
+        let auction = repository.get_auction(AuctionId::new(1)).await.unwrap().unwrap();
+        assert_eq!(auction.user(), &anonymized.pseudonym);
+        assert_eq!(auction.bids().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_only_support_anonymizes_users() {
+        let repository = InMemoryAuctionRepository::new(vec![auction()]);
+        let audit = InMemoryAuditRepository::default();
+        let buyer = User::new_buyer_or_seller(UserId::new("buyer"), None::<String>);
+
+        let result = handler(repository.clone(), audit.clone()).handle(Some(buyer), anonymize("buyer")).await;
+        assert!(matches!(result, Err(Error::Forbidden(_))));
+        let result = handler(repository.clone(), audit.clone()).handle(None, anonymize("buyer")).await;
+        assert!(matches!(result, Err(Error::Unauthorized(_))));
+
+        let auction = repository.get_auction(AuctionId::new(1)).await.unwrap().unwrap();
+        assert_eq!(auction.bids()[0].user(), &UserId::new("buyer"));
+        assert!(audit.entries().is_empty());
+    }
+}
@@ -0,0 +1,101 @@
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::domain::models::{AuctionId, Error};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::config::ArchivalConfig;
+use crate::infrastructure::data::AuctionArchive;
+
+/// Archives auctions that ended more than `after_days` ago, run by the `projection-worker`
+/// binary, so that the tables of running auctions stay small.
+#[derive(Clone)]
+pub struct Archiver {
+    archive: Box<dyn AuctionArchive>,
+    system_clock: Box<dyn SystemClock>,
+    config: ArchivalConfig,
+}
+
+impl Archiver {
+    pub fn new(archive: Box<dyn AuctionArchive>, system_clock: Box<dyn SystemClock>, config: ArchivalConfig) -> Self {
+        Self {
+            archive,
+            system_clock,
+            config,
+        }
+    }
+
+    /// Archives every `interval` until cancelled. Errors are logged and retried on the next run.
+    pub async fn run(&self, shutdown: CancellationToken) {
+        let interval = Duration::from_secs(self.config.interval);
+        while !shutdown.is_cancelled() {
+            if let Err(e) = self.run_once().await {
+                log::error!("Archiving auctions failed: {}", e);
+            }
+            tokio::select! {
+                _ = shutdown.cancelled() => {}
+                _ = tokio::time::sleep(interval) => {}
+            }
+        }
+    }
+
+    /// Archives the auctions that are due a batch at a time, returning their ids.
+    pub async fn run_once(&self) -> Result<Vec<AuctionId>, Error> {
+        let now = self.system_clock.now();
+        let retention = chrono::Duration::days(self.config.after_days);
+        let mut archived = Vec::new();
+        loop {
+            let batch = self.archive.archive_ended(now, retention, self.config.batch_size).await?;
+            let done = (batch.len() as i64) < self.config.batch_size;
+            archived.extend(batch);
+            if done {
+                break;
+            }
+        }
+        if !archived.is_empty() {
+            log::info!("Archived {} auctions that ended before {}", archived.len(), now - retention);
+        }
+        Ok(archived)
+    }
+}
+
+#[cfg(test)]
+mod archiver_tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::{DateTime, TimeZone, Utc};
+    use std::sync::{Arc, Mutex};
+
+    use crate::domain::services::FixedSystemClock;
+
+    /// Hands out the ids of `due` auctions `limit` at a time.
+    #[derive(Clone)]
+    struct FakeArchive {
+        due: Arc<Mutex<Vec<AuctionId>>>,
+    }
+
+    #[async_trait]
+    impl AuctionArchive for FakeArchive {
+        async fn archive_ended(
+            &self,
+            _now: DateTime<Utc>,
+            _retention: chrono::Duration,
+            limit: i64,
+        ) -> Result<Vec<AuctionId>, Error> {
+            let mut due = self.due.lock().unwrap();
+            let batch = due.len().min(limit as usize);
+            Ok(due.drain(..batch).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_archives_batches_until_none_are_due() {
+        let due: Vec<AuctionId> = (1..=5).map(AuctionId::new).collect();
+        let archiver = Archiver::new(
+            Box::new(FakeArchive { due: Arc::new(Mutex::new(due.clone())) }),
+            Box::new(FixedSystemClock(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap())),
+            ArchivalConfig { batch_size: 2, ..ArchivalConfig::default() },
+        );
+        assert_eq!(archiver.run_once().await.unwrap(), due);
+        assert_eq!(archiver.run_once().await.unwrap(), Vec::new());
+    }
+}
@@ -0,0 +1,105 @@
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::domain::models::{Auction, Error};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::config::OpeningConfig;
+use crate::infrastructure::data::AuctionRepository;
+
+/// Stores the status of scheduled auctions that have opened, and of open auctions that have
+/// closed, run by the `projection-worker` binary.
+///
+/// Auctions read their status as of now regardless, see [`Auction::status_at`]; this keeps the
+/// stored status, which listings filter on, in step with it.
+#[derive(Clone)]
+pub struct AuctionOpener {
+    repository: Box<dyn AuctionRepository>,
+    system_clock: Box<dyn SystemClock>,
+    config: OpeningConfig,
+}
+
+impl AuctionOpener {
+    pub fn new(
+        repository: Box<dyn AuctionRepository>,
+        system_clock: Box<dyn SystemClock>,
+        config: OpeningConfig,
+    ) -> Self {
+        Self {
+            repository,
+            system_clock,
+            config,
+        }
+    }
+
+    /// Opens every `interval` until cancelled. Errors are logged and retried on the next run.
+    pub async fn run(&self, shutdown: CancellationToken) {
+        let interval = Duration::from_secs(self.config.interval);
+        while !shutdown.is_cancelled() {
+            if let Err(e) = self.run_once().await {
+                log::error!("Opening auctions failed: {}", e);
+            }
+            tokio::select! {
+                _ = shutdown.cancelled() => {}
+                _ = tokio::time::sleep(interval) => {}
+            }
+        }
+    }
+
+    /// Stores the status of the auctions whose status has moved on, returning those auctions.
+    pub async fn run_once(&self) -> Result<Vec<Auction>, Error> {
+        let now = self.system_clock.now();
+        let mut advanced = Vec::new();
+        for mut auction in self.repository.get_auctions().await? {
+            let stored = auction.status();
+            if auction.advance(now) == stored {
+                continue;
+            }
+            let auction = self.repository.update_auction(auction).await?;
+            log::info!("Auction {} is now {}", auction.auction_id(), auction.status());
+            advanced.push(auction);
+        }
+        Ok(advanced)
+    }
+}
+
+#[cfg(test)]
+mod auction_opener_tests {
+    use super::*;
+    use crate::domain::commands::CreateAuctionCommand;
+    use crate::domain::models::{AuctionId, AuctionStatus};
+    use crate::domain::services::FixedSystemClock;
+    use crate::domain::test_support::{self, lamp, starts_at};
+    use crate::infrastructure::data::InMemoryAuctionRepository;
+
+    fn auction(id: i64, draft: bool) -> Auction {
+        let mut auction = test_support::auction(CreateAuctionCommand { draft, ..lamp() });
+        auction.set_auction_id(AuctionId::new(id));
+        auction
+    }
+
+    fn opener(repository: InMemoryAuctionRepository, days: i64) -> AuctionOpener {
+        AuctionOpener::new(
+            Box::new(repository),
+            Box::new(FixedSystemClock(starts_at() + chrono::Duration::days(days))),
+            OpeningConfig::default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_opens_started_auctions_but_not_drafts() {
+        let repository = InMemoryAuctionRepository::new(vec![auction(1, false), auction(2, true)]);
+
+        let opened = opener(repository.clone(), 1).run_once().await.unwrap();
+
+        assert_eq!(opened.len(), 1);
+        let stored = repository.get_auction(AuctionId::new(1)).await.unwrap().unwrap();
+        assert_eq!(stored.status(), AuctionStatus::Open);
+        let draft = repository.get_auction(AuctionId::new(2)).await.unwrap().unwrap();
+        assert_eq!(draft.status(), AuctionStatus::Draft);
+
+        // Nothing has moved on since
+        assert!(opener(repository.clone(), 1).run_once().await.unwrap().is_empty());
+        let closed = opener(repository, 8).run_once().await.unwrap();
+        assert_eq!(closed[0].status(), AuctionStatus::Closed);
+    }
+}
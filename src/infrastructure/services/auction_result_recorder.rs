@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::models::{Auction, AuctionId, AuctionResult, AuctionStatus, Error};
+use crate::infrastructure::data::AuctionResultRepository;
+
+/// Records the result of an auction the first time it is read closed, and serves the recorded
+/// result from then on rather than working it out from the bids again.
+#[derive(Clone)]
+pub struct AuctionResultRecorder {
+    repository: Box<dyn AuctionResultRepository>,
+}
+
+impl AuctionResultRecorder {
+    pub fn new(repository: Box<dyn AuctionResultRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// The results of the closed auctions among `auctions`, recording the missing ones.
+    pub async fn results(
+        &self,
+        auctions: &[Auction],
+        now: DateTime<Utc>,
+    ) -> Result<HashMap<AuctionId, AuctionResult>, Error> {
+        let closed: Vec<&Auction> = auctions
+            .iter()
+            .filter(|auction| auction.status_at(now) == AuctionStatus::Closed)
+            .collect();
+        if closed.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let ids: Vec<AuctionId> = closed.iter().map(|auction| auction.auction_id()).collect();
+        let mut results: HashMap<AuctionId, AuctionResult> = self
+            .repository
+            .get_results(&ids)
+            .await?
+            .into_iter()
+            .map(|result| (result.auction_id, result))
+            .collect();
+
+        for auction in closed {
+            if results.contains_key(&auction.auction_id()) {
+                continue;
+            }
+            if let Some(result) = auction.result(now) {
+                let recorded = self.repository.record(result).await?;
+                log::info!("Recorded the result of auction {}", recorded.auction_id);
+                results.insert(recorded.auction_id, recorded);
+            }
+        }
+        Ok(results)
+    }
+
+    /// The results already recorded of the auctions, for when the auctions were read without
+    /// their bids and the missing results cannot be worked out.
+    pub async fn recorded(&self, auction_ids: &[AuctionId]) -> Result<HashMap<AuctionId, AuctionResult>, Error> {
+        let results = self.repository.get_results(auction_ids).await?;
+        Ok(results.into_iter().map(|result| (result.auction_id, result)).collect())
+    }
+}
+
+#[cfg(test)]
+mod auction_result_recorder_tests {
+    use super::*;
+    use crate::domain::commands::CreateAuctionCommand;
+    use crate::domain::models::{Amount, CurrencyCode, UserId};
+    use crate::domain::test_support::{auction, lamp, starts_at, with_bid};
+    use crate::infrastructure::data::InMemoryAuctionResultRepository;
+    use chrono::Duration;
+
+    fn auction_with_bid(amount: i64) -> Auction {
+        with_bid(auction(CreateAuctionCommand { reserve_price: Some(100), ..lamp() }), "buyer", amount)
+    }
+
+    #[tokio::test]
+    async fn test_records_the_result_once_the_auction_has_closed() {
+        let recorder = AuctionResultRecorder::new(Box::new(InMemoryAuctionResultRepository::default()));
+        let auction = auction_with_bid(150);
+
+        let running = recorder.results(std::slice::from_ref(&auction), starts_at() + Duration::days(1)).await.unwrap();
+        assert!(running.is_empty());
+
+        let closed_at = starts_at() + Duration::days(8);
+        let results = recorder.results(std::slice::from_ref(&auction), closed_at).await.unwrap();
+        let result = &results[&AuctionId::new(1)];
+        assert_eq!(result.winner, Some(UserId::new("buyer")));
+        assert_eq!(result.amount, Some(Amount::new(150, CurrencyCode::SEK)));
+        assert_eq!(result.closed_at, starts_at() + Duration::days(7));
+        assert!(result.reserve_met);
+    }
+
+    #[tokio::test]
+    async fn test_serves_the_recorded_result() {
+        let recorder = AuctionResultRecorder::new(Box::new(InMemoryAuctionResultRepository::default()));
+        let closed_at = starts_at() + Duration::days(8);
+        recorder.results(&[auction_with_bid(150)], closed_at).await.unwrap();
+
+        // Even if the auction read later tells a different story
+        let results = recorder.results(&[auction_with_bid(50)], closed_at).await.unwrap();
+
+        assert_eq!(results[&AuctionId::new(1)].amount, Some(Amount::new(150, CurrencyCode::SEK)));
+    }
+
+    #[tokio::test]
+    async fn test_reads_recorded_results_without_the_auctions() {
+        let recorder = AuctionResultRecorder::new(Box::new(InMemoryAuctionResultRepository::default()));
+        assert!(recorder.recorded(&[AuctionId::new(1)]).await.unwrap().is_empty());
+
+        recorder.results(&[auction_with_bid(150)], starts_at() + Duration::days(8)).await.unwrap();
+
+        let results = recorder.recorded(&[AuctionId::new(1)]).await.unwrap();
+        assert_eq!(results[&AuctionId::new(1)].amount, Some(Amount::new(150, CurrencyCode::SEK)));
+    }
+
+    #[tokio::test]
+    async fn test_records_unmet_reserve() {
+        let recorder = AuctionResultRecorder::new(Box::new(InMemoryAuctionResultRepository::default()));
+
+        let results = recorder
+            .results(&[auction_with_bid(50)], starts_at() + Duration::days(8))
+            .await
+            .unwrap();
+
+        let result = &results[&AuctionId::new(1)];
+        assert_eq!(result.winner, None);
+        assert!(!result.reserve_met);
+    }
+}
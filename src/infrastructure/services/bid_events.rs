@@ -0,0 +1,54 @@
+use tokio::sync::broadcast;
+
+use crate::domain::models::AuctionId;
+
+/// In-process notifications of accepted bids, for clients waiting on updates of an auction.
+/// Subscribers are told which auction changed and read the bids themselves.
+#[derive(Clone)]
+pub struct BidEvents {
+    sender: broadcast::Sender<AuctionId>,
+}
+
+impl BidEvents {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    pub fn publish(&self, auction_id: AuctionId) {
+        // Nobody listening is fine
+        let _ = self.sender.send(auction_id);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<AuctionId> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for BidEvents {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+#[cfg(test)]
+mod bid_events_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_subscribers_receive_published_auction_ids() {
+        let events = BidEvents::new(8);
+        let mut receiver = events.subscribe();
+
+        events.publish(AuctionId::new(1));
+        events.publish(AuctionId::new(2));
+
+        assert_eq!(receiver.recv().await.unwrap(), AuctionId::new(1));
+        assert_eq!(receiver.recv().await.unwrap(), AuctionId::new(2));
+    }
+
+    #[test]
+    fn test_publish_without_subscribers_is_ignored() {
+        BidEvents::new(8).publish(AuctionId::new(1));
+    }
+}
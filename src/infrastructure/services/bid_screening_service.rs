@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use dyn_clone::DynClone;
+
+use crate::domain::models::{Auction, BidData, Error, ScreeningFlag, ScreeningVerdict, UserId};
+
+/// Screens bids for shill bidding as they are placed, see [`ScreeningVerdict`].
+#[async_trait]
+pub trait BidScreeningService: Send + Sync + DynClone {
+    /// Screens `bid` before it is added to `auction`.
+    async fn screen(&self, auction: &Auction, bid: &BidData) -> Result<ScreeningVerdict, Error>;
+}
+
+dyn_clone::clone_trait_object!(BidScreeningService);
+
+/// Flags bids placed from the address the seller created the auction from, and quick back and
+/// forth between the same two bidders.
+#[derive(Clone)]
+pub struct HeuristicBidScreeningService {
+    /// Longest time between two bids that still counts as quick
+    rapid_gap: Duration,
+    /// Number of alternating bids, the new one included, that makes a back and forth suspicious
+    alternating_bids: usize,
+}
+
+impl HeuristicBidScreeningService {
+    pub fn new(rapid_gap: Duration, alternating_bids: usize) -> Self {
+        Self {
+            rapid_gap,
+            alternating_bids,
+        }
+    }
+
+    fn same_ip_as_seller(auction: &Auction, bid: &BidData) -> bool {
+        let bidder_ip = bid.metadata.as_ref().and_then(|metadata| metadata.ip_hash.as_deref());
+        matches!((bidder_ip, auction.seller_ip_hash()), (Some(bidder), Some(seller)) if bidder == seller)
+    }
+
+    fn alternates_rapidly(&self, auction: &Auction, bid: &BidData) -> bool {
+        if self.alternating_bids < 2 {
+            return false;
+        }
+        // Bids are kept in the order they were placed
+        let mut bids: Vec<(&UserId, DateTime<Utc>)> = auction.bids().iter().map(|b| (b.user(), b.at())).collect();
+        bids.push((&bid.user, bid.at));
+        if bids.len() < self.alternating_bids {
+            return false;
+        }
+        let recent = &bids[bids.len() - self.alternating_bids..];
+        let (first, second) = (recent[0].0, recent[1].0);
+        first != second
+            && recent
+                .iter()
+                .enumerate()
+                .all(|(i, (user, _))| *user == if i % 2 == 0 { first } else { second })
+            && recent.windows(2).all(|pair| pair[1].1 - pair[0].1 <= self.rapid_gap)
+    }
+}
+
+impl Default for HeuristicBidScreeningService {
+    fn default() -> Self {
+        Self::new(Duration::seconds(30), 4)
+    }
+}
+
+#[async_trait]
+impl BidScreeningService for HeuristicBidScreeningService {
+    async fn screen(&self, auction: &Auction, bid: &BidData) -> Result<ScreeningVerdict, Error> {
+        let mut flags = Vec::new();
+        if Self::same_ip_as_seller(auction, bid) {
+            flags.push(ScreeningFlag::SameIpAsSeller);
+        }
+        if self.alternates_rapidly(auction, bid) {
+            flags.push(ScreeningFlag::RapidAlternatingBids);
+        }
+        Ok(ScreeningVerdict { flags })
+    }
+}
+
+#[cfg(test)]
+mod bid_screening_service_tests {
+    use super::*;
+    use crate::domain::commands::CreateAuctionCommand;
+    use crate::domain::models::{Amount, BidMetadata, CurrencyCode};
+    use crate::domain::test_support::{self, lamp, starts_at};
+
+    fn auction() -> Auction {
+        test_support::auction(CreateAuctionCommand { seller_ip_hash: Some("seller-ip".to_string()), ..lamp() })
+    }
+
+    fn bid(user: &str, amount: i64, at: DateTime<Utc>, ip_hash: &str) -> BidData {
+        BidData {
+            user: UserId::new(user),
+            amount: Amount::new(amount, CurrencyCode::SEK),
+            at,
+            quantity: 1,
+            metadata: Some(BidMetadata {
+                ip_hash: Some(ip_hash.to_string()),
+                ..BidMetadata::default()
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flags_bids_from_the_seller_address() {
+        let screening = HeuristicBidScreeningService::default();
+        let auction = auction();
+        let at = starts_at() + Duration::hours(1);
+
+        let verdict = screening.screen(&auction, &bid("buyer", 10, at, "seller-ip")).await.unwrap();
+        assert_eq!(verdict.flags, vec![ScreeningFlag::SameIpAsSeller]);
+
+        let verdict = screening.screen(&auction, &bid("buyer", 10, at, "buyer-ip")).await.unwrap();
+        assert!(!verdict.is_suspicious());
+    }
+
+    #[tokio::test]
+    async fn test_flags_quick_back_and_forth_between_two_bidders() {
+        let screening = HeuristicBidScreeningService::default();
+        let mut auction = auction();
+        let at = starts_at() + Duration::hours(1);
+        for (i, user) in ["a", "b", "a"].into_iter().enumerate() {
+            let at = at + Duration::seconds(10 * i as i64);
+            auction.try_add_bid(at, bid(user, 10 * (i as i64 + 1), at, user)).unwrap();
+        }
+
+        let quick = at + Duration::seconds(30);
+        let verdict = screening.screen(&auction, &bid("b", 40, quick, "b")).await.unwrap();
+        assert_eq!(verdict.flags, vec![ScreeningFlag::RapidAlternatingBids]);
+
+        // Neither a third bidder nor a slow reply is a back and forth
+        let verdict = screening.screen(&auction, &bid("c", 40, quick, "c")).await.unwrap();
+        assert!(!verdict.is_suspicious());
+        let slow = at + Duration::minutes(5);
+        let verdict = screening.screen(&auction, &bid("b", 40, slow, "b")).await.unwrap();
+        assert!(!verdict.is_suspicious());
+    }
+}
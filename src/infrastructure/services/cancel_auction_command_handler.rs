@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+
+use crate::domain::commands::CancelAuctionCommand;
+use crate::domain::models::{Auction, AuditEntry, Error, Errors, User};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::data::{AuctionRepository, AuditRepository};
+
+#[async_trait]
+pub trait CancelAuctionCommandHandler: Send + Sync + DynClone {
+    async fn handle(&self, user: Option<User>, command: CancelAuctionCommand) -> Result<Auction, Error>;
+}
+
+dyn_clone::clone_trait_object!(CancelAuctionCommandHandler);
+
+#[derive(Clone)]
+pub struct DefaultCancelAuctionCommandHandler {
+    repository: Box<dyn AuctionRepository>,
+    audit_repository: Box<dyn AuditRepository>,
+    system_clock: Box<dyn SystemClock>,
+}
+
+impl DefaultCancelAuctionCommandHandler {
+    pub fn new(
+        repository: Box<dyn AuctionRepository>,
+        audit_repository: Box<dyn AuditRepository>,
+        system_clock: Box<dyn SystemClock>,
+    ) -> Self {
+        Self {
+            repository,
+            audit_repository,
+            system_clock,
+        }
+    }
+}
+
+#[async_trait]
+impl CancelAuctionCommandHandler for DefaultCancelAuctionCommandHandler {
+    async fn handle(&self, user: Option<User>, command: CancelAuctionCommand) -> Result<Auction, Error> {
+        let user = user
+            .ok_or_else(|| Error::Unauthorized("User must be logged in to cancel an auction".to_string()))?;
+
+        let mut auction = match self.repository.get_auction(command.auction_id).await? {
+            Some(auction) => auction,
+            None => return Err(Error::Validation(Errors::UnknownAuction)),
+        };
+        let is_support = match &user {
+            User::Support { .. } => true,
+            User::BuyerOrSeller { id, .. } if id == auction.user() => {
+                if !auction.bids().is_empty() {
+                    return Err(Error::Forbidden(
+                        "Auctions with bids can only be cancelled by support".to_string(),
+                    ));
+                }
+                false
+            }
+            User::BuyerOrSeller { .. } => {
+                return Err(Error::Forbidden("Only the seller may cancel an auction".to_string()))
+            }
+        };
+        let now = self.system_clock.now();
+
+        auction
+            .cancel(now, command.reason.clone())
+            .map_err(Error::Validation)?;
+        let auction = self.repository.update_auction(auction).await?;
+
+        if is_support {
+            self.audit_repository
+                .record(AuditEntry {
+                    auction_id: auction.auction_id(),
+                    action: "cancel".to_string(),
+                    user: user.id().clone(),
+                    reason: command.reason,
+                    at: now,
+                })
+                .await?;
+        }
+        log::info!("Auction {} cancelled by {}", auction.auction_id(), user);
+
+        Ok(auction)
+    }
+}
+
+#[cfg(test)]
+mod cancel_auction_command_handler_tests {
+    use super::*;
+    use crate::domain::models::{AuctionId, AuctionStatus, UserId};
+    use crate::domain::services::FixedSystemClock;
+    use crate::domain::test_support::{self, lamp, starts_at};
+    use crate::infrastructure::data::{InMemoryAuctionRepository, InMemoryAuditRepository};
+    use chrono::Duration;
+
+    fn auction(with_bid: bool) -> Auction {
+        let auction = test_support::auction(lamp());
+        if with_bid { test_support::with_bid(auction, "buyer", 10) } else { auction }
+    }
+
+    fn handler(auction: Auction, audit: InMemoryAuditRepository) -> DefaultCancelAuctionCommandHandler {
+        DefaultCancelAuctionCommandHandler::new(
+            Box::new(InMemoryAuctionRepository::new(vec![auction])),
+            Box::new(audit),
+            Box::new(FixedSystemClock(starts_at() + Duration::hours(2))),
+        )
+    }
+
+    fn command() -> CancelAuctionCommand {
+        CancelAuctionCommand {
+            auction_id: AuctionId::new(1),
+            reason: Some("sold elsewhere".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_seller_cancels_auction_without_bids() {
+        let seller = User::new_buyer_or_seller(UserId::new("seller"), None::<String>);
+
+        let cancelled = handler(auction(false), InMemoryAuditRepository::default())
+            .handle(Some(seller), command())
+            .await
+            .unwrap();
+
+        assert_eq!(cancelled.status(), AuctionStatus::Cancelled);
+        assert_eq!(cancelled.cancel_reason(), Some("sold elsewhere"));
+    }
+
+    #[tokio::test]
+    async fn test_only_support_cancels_auction_with_bids() {
+        let seller = User::new_buyer_or_seller(UserId::new("seller"), None::<String>);
+        let result = handler(auction(true), InMemoryAuditRepository::default())
+            .handle(Some(seller), command())
+            .await;
+        assert!(matches!(result, Err(Error::Forbidden(_))));
+
+        let audit = InMemoryAuditRepository::default();
+        let support = User::new_support(UserId::new("support"));
+        let cancelled = handler(auction(true), audit.clone())
+            .handle(Some(support), command())
+            .await
+            .unwrap();
+        assert_eq!(cancelled.status(), AuctionStatus::Cancelled);
+        assert_eq!(audit.entries().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_other_users_cannot_cancel() {
+        let buyer = User::new_buyer_or_seller(UserId::new("buyer"), None::<String>);
+        let result = handler(auction(false), InMemoryAuditRepository::default())
+            .handle(Some(buyer), command())
+            .await;
+        assert!(matches!(result, Err(Error::Forbidden(_))));
+    }
+}
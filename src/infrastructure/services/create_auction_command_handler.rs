@@ -2,9 +2,9 @@ use async_trait::async_trait;
 use dyn_clone::DynClone;
 
 use crate::domain::commands::CreateAuctionCommand;
-use crate::domain::models::{Auction, Error, UserId};
+use crate::domain::models::{Auction, Error, Errors, UserId, Violation};
 use crate::domain::models::auction::AuctionFactory;
-use crate::infrastructure::data::AuctionRepository;
+use crate::infrastructure::data::{AuctionRepository, CurrencyRepository};
 
 #[async_trait]
 pub trait CreateAuctionCommandHandler: Send + Sync + DynClone {
@@ -16,14 +16,17 @@ dyn_clone::clone_trait_object!(CreateAuctionCommandHandler);
 #[derive(Clone)]
 pub struct DefaultCreateAuctionCommandHandler {
     repository: Box<dyn AuctionRepository>,
+    currencies: Box<dyn CurrencyRepository>,
 }
 
 impl DefaultCreateAuctionCommandHandler {
     pub fn new(
         repository: Box<dyn AuctionRepository>,
+        currencies: Box<dyn CurrencyRepository>,
     ) -> Self {
         Self {
             repository,
+            currencies,
         }
     }
 }
@@ -34,13 +37,90 @@ impl CreateAuctionCommandHandler for DefaultCreateAuctionCommandHandler {
         let user_id = user_id
             .ok_or_else(|| Error::Unauthorized("User must be logged in to create an auction".to_string()))?;
 
+        // Creating again with the same external reference returns the existing auction
+        let external_reference = command.external_reference.clone();
+        if let Some(reference) = &external_reference {
+            if let Some(existing) = self
+                .repository
+                .get_auction_by_external_reference(&user_id, reference)
+                .await?
+            {
+                return Ok(existing);
+            }
+        }
+
+        // Only the currencies enabled in the reference table are accepted
+        let currencies = self.currencies.get_currencies().await?;
+        if !currencies.iter().any(|currency| currency.code == command.currency) {
+            return Err(Error::Violations(vec![Violation {
+                code: "CURRENCY_NOT_ACCEPTED",
+                message: "The currency is not accepted for auctions",
+            }]));
+        }
+
         // Create the auction using the factory
-        let auction = AuctionFactory::create_auction(command, user_id)
-            .map_err(|e| Error::Domain(e.to_string()))?;
+        let (auction, events) = AuctionFactory::create(command, user_id.clone())
+            .map_err(Error::Violations)?;
             
         // Save to repository
-        let saved_auction = self.repository.create_auction(auction).await?;
-        
-        Ok(saved_auction)
+        match self.repository.create_auction(auction).await {
+            Ok(saved_auction) => {
+                for event in events {
+                    log::info!("{:?}", event.stored_as(saved_auction.auction_id()));
+                }
+                Ok(saved_auction)
+            }
+            // A concurrent request with the same reference got there first
+            Err(Error::Validation(Errors::AuctionAlreadyExists)) => {
+                let reference = external_reference.unwrap_or_default();
+                self.repository
+                    .get_auction_by_external_reference(&user_id, &reference)
+                    .await?
+                    .ok_or(Error::Validation(Errors::AuctionAlreadyExists))
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod create_auction_command_handler_tests {
+    use super::*;
+    use crate::domain::models::{Currency, CurrencyCode};
+    use crate::domain::test_support::lamp;
+    use crate::infrastructure::data::{InMemoryAuctionRepository, InMemoryCurrencyRepository};
+
+    fn handler() -> DefaultCreateAuctionCommandHandler {
+        DefaultCreateAuctionCommandHandler::new(
+            Box::new(InMemoryAuctionRepository::default()),
+            Box::new(InMemoryCurrencyRepository::new(vec![Currency {
+                code: CurrencyCode::SEK,
+                numeric_code: 752,
+                name: "Swedish krona".to_string(),
+                minor_units: 2,
+            }])),
+        )
+    }
+
+    fn command(currency: CurrencyCode) -> CreateAuctionCommand {
+        CreateAuctionCommand { currency, ..lamp() }
+    }
+
+    #[tokio::test]
+    async fn test_creates_auctions_in_enabled_currencies() {
+        let created = handler().handle(Some(UserId::new("seller")), command(CurrencyCode::SEK)).await.unwrap();
+
+        assert_eq!(created.currency(), CurrencyCode::SEK);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_currencies_that_are_not_enabled() {
+        let result = handler().handle(Some(UserId::new("seller")), command(CurrencyCode::DKK)).await;
+
+        assert!(
+            matches!(&result, Err(Error::Violations(violations)) if violations[0].code == "CURRENCY_NOT_ACCEPTED"),
+            "{:?}",
+            result.map(|auction| auction.auction_id())
+        );
     }
 }
@@ -2,9 +2,10 @@ use async_trait::async_trait;
 use dyn_clone::DynClone;
 
 use crate::domain::commands::CreateBidCommand;
-use crate::domain::models::{BidData, Error, Errors, UserId};
+use crate::domain::models::{Auction, BidData, BidMetadata, Error, Errors, UserId};
 use crate::domain::services::SystemClock;
-use crate::infrastructure::data::AuctionRepository;
+use crate::infrastructure::data::{AuctionRepository, DepositRepository, RegistrationRepository};
+use crate::infrastructure::services::BidScreeningService;
 
 #[async_trait]
 pub trait CreateBidCommandHandler: Send + Sync + DynClone {
@@ -13,54 +14,314 @@ pub trait CreateBidCommandHandler: Send + Sync + DynClone {
 
 dyn_clone::clone_trait_object!(CreateBidCommandHandler);
 
+/// How many times a bid is placed when other changes to the auction keep getting in first.
+const PLACE_ATTEMPTS: usize = 3;
+
 #[derive(Clone)]
 pub struct DefaultCreateBidCommandHandler {
     repository: Box<dyn AuctionRepository>,
+    registration_repository: Box<dyn RegistrationRepository>,
+    deposit_repository: Box<dyn DepositRepository>,
+    screening: Box<dyn BidScreeningService>,
     system_clock: Box<dyn SystemClock>,
 }
 
 impl DefaultCreateBidCommandHandler{
     pub fn new(
         repository: Box<dyn AuctionRepository>,
+        registration_repository: Box<dyn RegistrationRepository>,
+        deposit_repository: Box<dyn DepositRepository>,
+        screening: Box<dyn BidScreeningService>,
         system_clock: Box<dyn SystemClock>,
     ) -> Self {
         Self {
             repository,
+            registration_repository,
+            deposit_repository,
+            screening,
             system_clock,
         }
     }
-}
 
-#[async_trait]
-impl CreateBidCommandHandler for DefaultCreateBidCommandHandler {
-    async fn handle(&self, user_id: Option<UserId>, command: CreateBidCommand) -> Result<(), Error> {
+    /// Screening never stops a bid: a flagged bid is taken with its verdict, for support to review.
+    async fn screen(&self, auction: &Auction, bid: &mut BidData) {
+        match self.screening.screen(auction, bid).await {
+            Ok(verdict) if verdict.is_suspicious() => {
+                log::warn!(
+                    "Bid by {} on auction {} flagged by screening: {:?}",
+                    bid.user,
+                    auction.auction_id(),
+                    verdict.flags
+                );
+                bid.metadata.get_or_insert_with(BidMetadata::default).screening = Some(verdict);
+            }
+            Ok(_) => {}
+            Err(e) => log::error!("Screening a bid on auction {} failed: {}", auction.auction_id(), e),
+        }
+    }
+
+    /// Places the bid on the auction as stored, failing with [`Error::Conflict`] when the store
+    /// could not hold the auction and someone else changed it before the bid was stored.
+    async fn place(&self, user_id: Option<UserId>, command: CreateBidCommand) -> Result<(), Error> {
         // Get the auction
-        let mut auction = match self.repository.get_auction(command.auction_id).await? {
+        let auction = match self.repository.get_auction(command.auction_id).await? {
             Some(auction) => auction,
             None => return Result::Err(Error::Validation(Errors::UnknownAuction)),
         };
         let user_id = user_id
             .ok_or_else(|| Error::Unauthorized("User must be logged in to place a bid".to_string()))?;
-
+        if auction.requires_registration() {
+            let approved = self
+                .registration_repository
+                .get_registration(command.auction_id, &user_id)
+                .await?
+                .is_some_and(|registration| registration.is_approved());
+            if !approved {
+                return Err(Error::Validation(Errors::BidderNotApproved));
+            }
+        }
+        if let Some(required) = auction.deposit() {
+            let covered = self
+                .deposit_repository
+                .get_deposit(command.auction_id, &user_id)
+                .await?
+                .is_some_and(|deposit| deposit.covers(&required));
+            if !covered {
+                return Err(Error::Validation(Errors::DepositRequired));
+            }
+        }
 
         // Create bid
-        let bid = BidData {
+        let mut bid = BidData {
             user: user_id.clone(),
             amount: command.amount,
             at: self.system_clock.now(),
+            quantity: command.quantity.unwrap_or(1),
+            metadata: command.metadata,
         };
+        // Bids on a lot are screened against the other bids on that lot
+        match command.lot.and_then(|lot| auction.lot_view(lot)) {
+            Some(view) => self.screen(&view, &mut bid).await,
+            None => self.screen(&auction, &mut bid).await,
+        }
         
-        // Try to add bid to auction
-        let result = match auction.try_add_bid(self.system_clock.now(), bid) {
-            Ok(_) => {
-                // Save updated auction
-                self.repository.update_auction(auction).await?;
-                Ok(())
-            },
-            Err(errors) => Err(errors),
-        }.map_err(|e| Error::Validation(e))?;
-        
-        Ok(result)
+        // Add the bid to the auction as stored by then, which may have taken other bids since it was read
+        let now = self.system_clock.now();
+        let (lot, max_bid) = (command.lot, command.max_bid);
+        let events = self
+            .repository
+            .place_bid(
+                command.auction_id,
+                Box::new(move |auction: &mut Auction| match (lot, max_bid) {
+                    (Some(_), true) => Err(Errors::ProxyBidNotAllowed),
+                    (Some(lot), false) => auction.try_add_lot_bid(now, lot, bid),
+                    (None, true) => auction.try_add_proxy_bid(now, bid),
+                    (None, false) => auction.try_add_bid(now, bid),
+                }),
+            )
+            .await?;
+        for event in events {
+            log::info!("{:?}", event);
+        }
+        Ok(())
     }
 }
 
+#[async_trait]
+impl CreateBidCommandHandler for DefaultCreateBidCommandHandler {
+    async fn handle(&self, user_id: Option<UserId>, command: CreateBidCommand) -> Result<(), Error> {
+        // A bid that lost the race with another is placed again, against the bid that won
+        let mut attempt = 1;
+        loop {
+            match self.place(user_id.clone(), command.clone()).await {
+                Err(Error::Conflict(reason)) if attempt < PLACE_ATTEMPTS => {
+                    log::info!("Placing the bid on auction {} again: {}", command.auction_id, reason);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod create_bid_command_handler_tests {
+    use super::*;
+    use crate::domain::commands::CreateAuctionCommand;
+    use crate::domain::models::{
+        Amount, Auction, AuctionId, CurrencyCode, Deposit, Registration,
+        RegistrationStatus, ScreeningFlag, ScreeningVerdict,
+    };
+    use crate::domain::services::FixedSystemClock;
+    use crate::domain::test_support::{self, lamp, starts_at};
+    use crate::infrastructure::data::{
+        InMemoryAuctionRepository, InMemoryDepositRepository, InMemoryRegistrationRepository,
+    };
+    use crate::infrastructure::services::HeuristicBidScreeningService;
+    use chrono::Duration;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    fn auction(requires_registration: bool, deposit: Option<i64>) -> Auction {
+        test_support::auction(CreateAuctionCommand {
+            requires_registration,
+            deposit,
+            seller_ip_hash: Some("seller-ip".to_string()),
+            ..lamp()
+        })
+    }
+
+    fn bid() -> CreateBidCommand {
+        CreateBidCommand {
+            auction_id: AuctionId::new(1),
+            amount: Amount::new(10, CurrencyCode::SEK),
+            lot: None,
+            quantity: None,
+            max_bid: false,
+            metadata: None,
+        }
+    }
+
+    fn handler(
+        auction: Auction,
+        registrations: InMemoryRegistrationRepository,
+        deposits: InMemoryDepositRepository,
+    ) -> DefaultCreateBidCommandHandler {
+        DefaultCreateBidCommandHandler::new(
+            Box::new(InMemoryAuctionRepository::new(vec![auction])),
+            Box::new(registrations),
+            Box::new(deposits),
+            Box::new(HeuristicBidScreeningService::default()),
+            Box::new(FixedSystemClock(starts_at() + Duration::hours(1))),
+        )
+    }
+
+    async fn register(registrations: &InMemoryRegistrationRepository, user: &str, status: RegistrationStatus) {
+        registrations
+            .save(Registration {
+                auction_id: AuctionId::new(1),
+                user: UserId::new(user),
+                status,
+                registered_at: starts_at(),
+                decided_at: Some(starts_at()),
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_only_approved_bidders_bid_when_registration_is_required() {
+        let registrations = InMemoryRegistrationRepository::default();
+        register(&registrations, "approved", RegistrationStatus::Approved).await;
+        register(&registrations, "rejected", RegistrationStatus::Rejected).await;
+        let handler = handler(auction(true, None), registrations, InMemoryDepositRepository::default());
+
+        for user in ["rejected", "unregistered"] {
+            let result = handler.handle(Some(UserId::new(user)), bid()).await;
+            assert!(matches!(result, Err(Error::Validation(Errors::BidderNotApproved))), "{}", user);
+        }
+        assert!(handler.handle(Some(UserId::new("approved")), bid()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_bidders_must_cover_the_deposit() {
+        let deposits = InMemoryDepositRepository::default();
+        for (user, value) in [("covered", 100), ("short", 50)] {
+            deposits
+                .record(Deposit {
+                    auction_id: AuctionId::new(1),
+                    user: UserId::new(user),
+                    amount: Amount::new(value, CurrencyCode::SEK),
+                    recorded_at: starts_at(),
+                })
+                .await
+                .unwrap();
+        }
+        let handler = handler(auction(false, Some(100)), InMemoryRegistrationRepository::default(), deposits);
+
+        for user in ["short", "without-deposit"] {
+            let result = handler.handle(Some(UserId::new(user)), bid()).await;
+            assert!(matches!(result, Err(Error::Validation(Errors::DepositRequired))), "{}", user);
+        }
+        assert!(handler.handle(Some(UserId::new("covered")), bid()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_keeps_flagged_bids_with_their_verdict() {
+        let repository = InMemoryAuctionRepository::new(vec![auction(false, None)]);
+        let handler = DefaultCreateBidCommandHandler::new(
+            Box::new(repository.clone()),
+            Box::new(InMemoryRegistrationRepository::default()),
+            Box::new(InMemoryDepositRepository::default()),
+            Box::new(HeuristicBidScreeningService::default()),
+            Box::new(FixedSystemClock(starts_at() + Duration::hours(1))),
+        );
+        let from_seller_address = CreateBidCommand {
+            metadata: Some(BidMetadata {
+                ip_hash: Some("seller-ip".to_string()),
+                ..BidMetadata::default()
+            }),
+            ..bid()
+        };
+
+        assert!(handler.handle(Some(UserId::new("buyer")), from_seller_address).await.is_ok());
+
+        let auction = repository.get_auction(AuctionId::new(1)).await.unwrap().unwrap();
+        let screening = auction.bids()[0].data.metadata.as_ref().and_then(|m| m.screening.as_ref());
+        assert_eq!(screening.map(|verdict| verdict.flags.clone()), Some(vec![ScreeningFlag::SameIpAsSeller]));
+    }
+
+    /// Places a bid of its own while the first bid it screens is being placed, as a bidder
+    /// racing that bid would.
+    #[derive(Clone)]
+    struct RacingBidder {
+        repository: InMemoryAuctionRepository,
+        raced: Arc<AtomicBool>,
+    }
+
+    #[async_trait]
+    impl BidScreeningService for RacingBidder {
+        async fn screen(&self, auction: &Auction, bid: &BidData) -> Result<ScreeningVerdict, Error> {
+            if !self.raced.swap(true, Ordering::SeqCst) {
+                let mut stored = self.repository.get_auction(auction.auction_id()).await?.unwrap();
+                let rival = BidData {
+                    user: UserId::new("rival"),
+                    ..bid.clone()
+                };
+                stored.try_add_bid(bid.at, rival).map_err(Error::Validation)?;
+                self.repository.update_auction(stored).await?;
+            }
+            Ok(ScreeningVerdict::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bid_that_lost_a_race_is_placed_against_the_winning_bid() {
+        let repository = InMemoryAuctionRepository::new(vec![auction(false, None)]);
+        let handler = DefaultCreateBidCommandHandler::new(
+            Box::new(repository.clone()),
+            Box::new(InMemoryRegistrationRepository::default()),
+            Box::new(InMemoryDepositRepository::default()),
+            Box::new(RacingBidder {
+                repository: repository.clone(),
+                raced: Arc::new(AtomicBool::new(false)),
+            }),
+            Box::new(FixedSystemClock(starts_at() + Duration::hours(1))),
+        );
+
+        let result = handler.handle(Some(UserId::new("buyer")), bid()).await;
+        assert!(
+            matches!(result, Err(Error::Validation(errors)) if errors.contains(Errors::MustPlaceBidOverHighestBid)),
+            "the same amount as the rival should no longer be enough"
+        );
+        let higher = CreateBidCommand {
+            amount: Amount::new(20, CurrencyCode::SEK),
+            ..bid()
+        };
+        assert!(handler.handle(Some(UserId::new("buyer")), higher).await.is_ok());
+
+        let auction = repository.get_auction(AuctionId::new(1)).await.unwrap().unwrap();
+        let bidders: Vec<&str> = auction.bids().iter().map(|bid| bid.user().value()).collect();
+        assert_eq!(bidders, vec!["rival", "buyer"]);
+    }
+}
@@ -0,0 +1,144 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
+
+use crate::domain::models::Error;
+
+tokio::task_local! {
+    /// Deadline of the request being handled by the current task, if it has one.
+    pub static CURRENT_DEADLINE: RequestDeadline;
+}
+
+/// How far a request got before its deadline, returned to the client with the 504.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadlineDiagnostics {
+    pub timeout_ms: u64,
+    pub elapsed_ms: u64,
+    pub completed: Vec<String>,
+    pub pending: Option<String>,
+}
+
+/// Time budget of a single request. Cancelled once the budget is spent, which aborts the
+/// operations still waiting on it.
+#[derive(Clone)]
+pub struct RequestDeadline {
+    started: Instant,
+    timeout: Duration,
+    token: CancellationToken,
+    progress: Arc<Mutex<(Vec<String>, Option<String>)>>,
+}
+
+impl RequestDeadline {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            started: Instant::now(),
+            timeout,
+            token: CancellationToken::new(),
+            progress: Arc::new(Mutex::new((Vec::new(), None))),
+        }
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.timeout.saturating_sub(self.started.elapsed())
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.token.is_cancelled() || self.remaining().is_zero()
+    }
+
+    /// Whether an operation ran out of time, as opposed to the request just finishing late.
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    pub fn diagnostics(&self) -> DeadlineDiagnostics {
+        let (completed, pending) = self.progress.lock().unwrap().clone();
+        DeadlineDiagnostics {
+            timeout_ms: self.timeout.as_millis() as u64,
+            elapsed_ms: self.started.elapsed().as_millis() as u64,
+            completed,
+            pending,
+        }
+    }
+
+    fn start(&self, operation: &str) {
+        self.progress.lock().unwrap().1 = Some(operation.to_string());
+    }
+
+    fn finish(&self, operation: &str, took: Duration) {
+        let mut progress = self.progress.lock().unwrap();
+        progress.0.push(format!("{} ({} ms)", operation, took.as_millis()));
+        progress.1 = None;
+    }
+}
+
+/// Runs `call` within the deadline of the current request, if any. A call that runs out of
+/// time is dropped, which releases whatever it holds (e.g. a database connection).
+pub async fn within_deadline<T>(
+    operation: &str,
+    call: impl Future<Output = Result<T, Error>>,
+) -> Result<T, Error> {
+    let deadline = match CURRENT_DEADLINE.try_with(|deadline| deadline.clone()) {
+        Ok(deadline) => deadline,
+        Err(_) => return call.await,
+    };
+    let exceeded = || Error::Repository(format!("Request deadline exceeded during {}", operation));
+    if deadline.is_exceeded() {
+        return Err(exceeded());
+    }
+
+    let started = Instant::now();
+    deadline.start(operation);
+    tokio::select! {
+        result = call => {
+            deadline.finish(operation, started.elapsed());
+            result
+        }
+        _ = deadline.token.cancelled() => Err(exceeded()),
+        _ = tokio::time::sleep(deadline.remaining()) => {
+            deadline.cancel();
+            Err(exceeded())
+        }
+    }
+}
+
+#[cfg(test)]
+mod deadline_tests {
+    use super::*;
+
+    async fn slow(millis: u64) -> Result<u64, Error> {
+        tokio::time::sleep(Duration::from_millis(millis)).await;
+        Ok(millis)
+    }
+
+    #[tokio::test]
+    async fn test_calls_without_deadline_run_to_completion() {
+        assert_eq!(within_deadline("slow", slow(20)).await.unwrap(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_call_past_deadline_is_cancelled() {
+        let deadline = RequestDeadline::new(Duration::from_millis(50));
+
+        let result = CURRENT_DEADLINE
+            .scope(deadline.clone(), async {
+                within_deadline("fast", slow(1)).await?;
+                within_deadline("slow", slow(5_000)).await
+            })
+            .await;
+
+        assert!(matches!(result, Err(Error::Repository(_))));
+        assert!(deadline.is_exceeded());
+        let diagnostics = deadline.diagnostics();
+        assert_eq!(diagnostics.completed.len(), 1);
+        assert_eq!(diagnostics.pending, Some("slow".to_string()));
+    }
+}
@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+
+use crate::domain::commands::ExtendAuctionCommand;
+use crate::domain::models::{Auction, AuditEntry, Error, Errors, User};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::data::{AuctionRepository, AuditRepository};
+
+#[async_trait]
+pub trait ExtendAuctionCommandHandler: Send + Sync + DynClone {
+    async fn handle(&self, user: Option<User>, command: ExtendAuctionCommand) -> Result<Auction, Error>;
+}
+
+dyn_clone::clone_trait_object!(ExtendAuctionCommandHandler);
+
+#[derive(Clone)]
+pub struct DefaultExtendAuctionCommandHandler {
+    repository: Box<dyn AuctionRepository>,
+    audit_repository: Box<dyn AuditRepository>,
+    system_clock: Box<dyn SystemClock>,
+}
+
+impl DefaultExtendAuctionCommandHandler {
+    pub fn new(
+        repository: Box<dyn AuctionRepository>,
+        audit_repository: Box<dyn AuditRepository>,
+        system_clock: Box<dyn SystemClock>,
+    ) -> Self {
+        Self {
+            repository,
+            audit_repository,
+            system_clock,
+        }
+    }
+}
+
+#[async_trait]
+impl ExtendAuctionCommandHandler for DefaultExtendAuctionCommandHandler {
+    async fn handle(&self, user: Option<User>, command: ExtendAuctionCommand) -> Result<Auction, Error> {
+        let user = user
+            .ok_or_else(|| Error::Unauthorized("User must be logged in to extend an auction".to_string()))?;
+        let support_id = match user {
+            User::Support { id } => id,
+            User::BuyerOrSeller { .. } => {
+                return Err(Error::Forbidden("Only support users may extend auctions".to_string()))
+            }
+        };
+
+        let mut auction = match self.repository.get_auction(command.auction_id).await? {
+            Some(auction) => auction,
+            None => return Err(Error::Validation(Errors::UnknownAuction)),
+        };
+        let now = self.system_clock.now();
+        let previous_expiry = auction.effective_end();
+
+        auction
+            .extend(now, command.expiry, command.reason.clone())
+            .map_err(Error::Validation)?;
+        let auction = self.repository.update_auction(auction).await?;
+
+        self.audit_repository
+            .record(AuditEntry {
+                auction_id: auction.auction_id(),
+                action: "extend".to_string(),
+                user: support_id.clone(),
+                reason: command.reason,
+                at: now,
+            })
+            .await?;
+        log::warn!(
+            "Auction {} extended from {} to {} by support user {}",
+            auction.auction_id(),
+            previous_expiry,
+            command.expiry,
+            support_id
+        );
+
+        Ok(auction)
+    }
+}
+
+#[cfg(test)]
+mod extend_auction_command_handler_tests {
+    use super::*;
+    use crate::domain::models::{AuctionId, UserId};
+    use crate::domain::services::FixedSystemClock;
+    use crate::domain::test_support::{auction, lamp, starts_at};
+    use crate::infrastructure::data::{InMemoryAuctionRepository, InMemoryAuditRepository};
+    use chrono::Duration;
+
+    fn handler(audit: InMemoryAuditRepository) -> DefaultExtendAuctionCommandHandler {
+        DefaultExtendAuctionCommandHandler::new(
+            Box::new(InMemoryAuctionRepository::new(vec![auction(lamp())])),
+            Box::new(audit),
+            Box::new(FixedSystemClock(starts_at() + Duration::hours(2))),
+        )
+    }
+
+    fn command() -> ExtendAuctionCommand {
+        ExtendAuctionCommand {
+            auction_id: AuctionId::new(1),
+            expiry: starts_at() + Duration::days(8),
+            reason: Some("outage".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_support_extends_auction_and_is_audited() {
+        let audit = InMemoryAuditRepository::default();
+        let support = User::new_support(UserId::new("support"));
+
+        let extended = handler(audit.clone())
+            .handle(Some(support), command())
+            .await
+            .unwrap();
+
+        assert_eq!(extended.expiry(), starts_at() + Duration::days(8));
+        assert_eq!(extended.extension_reason(), Some("outage"));
+        let entries = audit.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, "extend");
+        assert_eq!(entries[0].user, UserId::new("support"));
+        assert_eq!(entries[0].reason.as_deref(), Some("outage"));
+    }
+
+    #[tokio::test]
+    async fn test_only_support_extends_auctions() {
+        let audit = InMemoryAuditRepository::default();
+        let seller = User::new_buyer_or_seller(UserId::new("seller"), None::<String>);
+
+        let result = handler(audit.clone()).handle(Some(seller), command()).await;
+
+        assert!(matches!(result, Err(Error::Forbidden(_))));
+        assert!(audit.entries().is_empty());
+        let result = handler(audit).handle(None, command()).await;
+        assert!(matches!(result, Err(Error::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_expiry_cannot_be_moved_earlier() {
+        let support = User::new_support(UserId::new("support"));
+        let command = ExtendAuctionCommand {
+            expiry: starts_at() + Duration::days(6),
+            ..command()
+        };
+
+        let result = handler(InMemoryAuditRepository::default())
+            .handle(Some(support), command)
+            .await;
+
+        assert!(matches!(result, Err(Error::Validation(Errors::ExpiryMustBeLater))));
+    }
+}
@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use crate::domain::services::RandomSource;
+use crate::infrastructure::config::FaultInjectionConfig;
+
+/// Decides, per request or repository call, whether to inject an artificial fault.
+#[derive(Clone)]
+pub struct FaultInjector {
+    config: FaultInjectionConfig,
+    random: Box<dyn RandomSource>,
+}
+
+impl FaultInjector {
+    pub fn new(config: FaultInjectionConfig, random: Box<dyn RandomSource>) -> Self {
+        Self { config, random }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    pub fn applies_to(&self, path: &str) -> bool {
+        self.config.enabled && !self.config.exclude_paths.iter().any(|p| p == path)
+    }
+
+    pub fn latency(&self) -> Duration {
+        Duration::from_millis(self.config.latency_ms)
+    }
+
+    pub fn delay_request(&self) -> bool {
+        self.roll(self.config.latency_percent)
+    }
+
+    pub fn fail_request(&self) -> bool {
+        self.roll(self.config.error_percent)
+    }
+
+    pub fn drop_request(&self) -> bool {
+        self.roll(self.config.drop_percent)
+    }
+
+    pub fn delay_repository_call(&self) -> bool {
+        self.roll(self.config.repository_latency_percent)
+    }
+
+    pub fn fail_repository_call(&self) -> bool {
+        self.roll(self.config.repository_error_percent)
+    }
+
+    fn roll(&self, percent: f64) -> bool {
+        self.config.enabled && percent > 0.0 && self.random.next_f64() * 100.0 < percent
+    }
+}
+
+#[cfg(test)]
+mod fault_injector_tests {
+    use super::*;
+    use crate::domain::services::OsRandomSource;
+
+    fn injector(config: FaultInjectionConfig) -> FaultInjector {
+        FaultInjector::new(config, Box::new(OsRandomSource))
+    }
+
+    #[test]
+    fn test_disabled_injector_never_injects() {
+        let faults = injector(FaultInjectionConfig {
+            enabled: false,
+            error_percent: 100.0,
+            ..FaultInjectionConfig::default()
+        });
+        assert!(!faults.fail_request());
+        assert!(!faults.applies_to("/auctions"));
+    }
+
+    #[test]
+    fn test_percentages_bound_injection() {
+        let faults = injector(FaultInjectionConfig {
+            enabled: true,
+            error_percent: 100.0,
+            drop_percent: 0.0,
+            ..FaultInjectionConfig::default()
+        });
+        for _ in 0..100 {
+            assert!(faults.fail_request());
+            assert!(!faults.drop_request());
+        }
+    }
+
+    #[test]
+    fn test_excluded_paths_are_left_alone() {
+        let faults = injector(FaultInjectionConfig {
+            enabled: true,
+            ..FaultInjectionConfig::default()
+        });
+        assert!(!faults.applies_to("/metrics"));
+        assert!(faults.applies_to("/auctions"));
+    }
+}
@@ -0,0 +1,202 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use dyn_clone::DynClone;
+use serde::Serialize;
+
+use crate::domain::models::{Amount, Auction, Error, UserId};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::config::JobsConfig;
+use crate::infrastructure::data::{AuctionRepository, Job, JobKind, JobRepository};
+
+dyn_clone::clone_trait_object!(Notifier);
+
+/// Tells users about their auctions.
+#[async_trait]
+pub trait Notifier: Send + Sync + DynClone {
+    async fn notify(&self, user: &UserId, message: &str) -> Result<(), Error>;
+}
+
+/// Writes notifications to the log, for when there is no channel to deliver them through.
+#[derive(Clone)]
+pub struct LogNotifier;
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    async fn notify(&self, user: &UserId, message: &str) -> Result<(), Error> {
+        log::info!("Notify {}: {}", user, message);
+        Ok(())
+    }
+}
+
+/// The result of an auction, as posted to the webhook.
+#[derive(Debug, Serialize)]
+struct AuctionResult {
+    #[serde(rename = "auctionId")]
+    auction_id: i64,
+    seller: String,
+    winner: Option<String>,
+    price: Option<Amount>,
+}
+
+/// What came of running a job, other than an error.
+enum Outcome {
+    Done,
+    /// The job is not due yet, for instance because the auction was extended
+    NotBefore(DateTime<Utc>),
+}
+
+/// Runs the due jobs of [`JobRepository`]. Finalizing an ended auction schedules notifying its
+/// seller and winner, and posting the result to the webhook when there is one.
+#[derive(Clone)]
+pub struct JobRunner {
+    jobs: Box<dyn JobRepository>,
+    auctions: Box<dyn AuctionRepository>,
+    clock: Box<dyn SystemClock>,
+    notifier: Box<dyn Notifier>,
+    http: reqwest::Client,
+    config: JobsConfig,
+}
+
+impl JobRunner {
+    pub fn new(
+        jobs: Box<dyn JobRepository>,
+        auctions: Box<dyn AuctionRepository>,
+        clock: Box<dyn SystemClock>,
+        notifier: Box<dyn Notifier>,
+        config: JobsConfig,
+    ) -> Self {
+        Self {
+            jobs,
+            auctions,
+            clock,
+            notifier,
+            http: reqwest::Client::new(),
+            config,
+        }
+    }
+
+    /// Runs due jobs every `interval`. Errors are logged and retried on the next run.
+    pub async fn run(&self) {
+        let interval = Duration::from_secs(self.config.interval);
+        loop {
+            if let Err(e) = self.run_due().await {
+                log::error!("Running background jobs failed: {}", e);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// Runs the jobs that are due now, returning how many were run.
+    pub async fn run_due(&self) -> Result<usize, Error> {
+        let jobs = self.jobs.claim_due(self.clock.now(), self.config.batch_size).await?;
+        for job in &jobs {
+            let result = self.run_job(job).await;
+            let now = self.clock.now();
+            match result {
+                Ok(Outcome::Done) => self.jobs.complete(job.id, now).await?,
+                Ok(Outcome::NotBefore(run_at)) => self.jobs.release(job.id, run_at, None, now).await?,
+                Err(e) if job.attempts < self.config.max_attempts => {
+                    log::warn!("{} job {} failed, retrying: {}", job.kind, job.id, e);
+                    let backoff = chrono::Duration::seconds(self.config.retry_after as i64 * job.attempts as i64);
+                    self.jobs.release(job.id, now + backoff, Some(e.to_string()), now).await?
+                }
+                Err(e) => {
+                    log::error!("{} job {} failed after {} attempts: {}", job.kind, job.id, job.attempts, e);
+                    self.jobs.fail(job.id, e.to_string(), now).await?
+                }
+            }
+        }
+        Ok(jobs.len())
+    }
+
+    async fn run_job(&self, job: &Job) -> Result<Outcome, Error> {
+        let auction = self
+            .auctions
+            .get_auction(job.auction_id)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("Auction with ID {} not found", job.auction_id)))?;
+        let now = self.clock.now();
+        if !auction.has_ended(now) {
+            return Ok(Outcome::NotBefore(auction.effective_end()));
+        }
+        match job.kind {
+            JobKind::Finalization => self.finalize(&auction, now).await?,
+            JobKind::Notification => self.notify(&auction, now).await?,
+            JobKind::Webhook => self.post_result(&auction, now).await?,
+        }
+        Ok(Outcome::Done)
+    }
+
+    async fn finalize(&self, auction: &Auction, now: DateTime<Utc>) -> Result<(), Error> {
+        if auction.cancelled_at().is_some() {
+            // Whoever cancelled it knows, and there is no result to announce
+            log::info!("Auction {} was cancelled", auction.auction_id());
+            return Ok(());
+        }
+        if auction.voided_at().is_some() {
+            log::info!("Auction {} was voided", auction.auction_id());
+        } else {
+            match auction.try_get_amount_and_winner(now) {
+                Some((amount, winner)) => {
+                    log::info!("Auction {} was won by {} for {}", auction.auction_id(), winner, amount)
+                }
+                None => log::info!("Auction {} ended without a winner", auction.auction_id()),
+            }
+        }
+        self.jobs.enqueue(JobKind::Notification, auction.auction_id(), now, now).await?;
+        if self.config.webhook_url.is_some() {
+            self.jobs.enqueue(JobKind::Webhook, auction.auction_id(), now, now).await?;
+        }
+        Ok(())
+    }
+
+    async fn notify(&self, auction: &Auction, now: DateTime<Utc>) -> Result<(), Error> {
+        let title = auction.title();
+        if auction.voided_at().is_some() {
+            // Bidders are not told, as a voided auction has no winner
+            return self
+                .notifier
+                .notify(auction.user(), &format!("Your auction {} was voided by support", title))
+                .await;
+        }
+        match auction.try_get_amount_and_winner(now) {
+            Some((amount, winner)) => {
+                self.notifier
+                    .notify(auction.user(), &format!("Your auction {} was sold for {}", title, amount))
+                    .await?;
+                self.notifier
+                    .notify(&winner, &format!("You won the auction {} for {}", title, amount))
+                    .await
+            }
+            None => {
+                self.notifier
+                    .notify(auction.user(), &format!("Your auction {} ended without a winner", title))
+                    .await
+            }
+        }
+    }
+
+    async fn post_result(&self, auction: &Auction, now: DateTime<Utc>) -> Result<(), Error> {
+        let Some(url) = &self.config.webhook_url else {
+            // The webhook was removed from the settings after the job was scheduled
+            return Ok(());
+        };
+        let winner = auction.try_get_amount_and_winner(now);
+        let result = AuctionResult {
+            auction_id: auction.auction_id().value(),
+            seller: auction.user().to_string(),
+            winner: winner.as_ref().map(|(_, user)| user.to_string()),
+            price: winner.map(|(amount, _)| amount),
+        };
+        self.http
+            .post(url)
+            .json(&result)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| Error::Internal(format!("Posting the result to the webhook failed: {}", e)))?;
+        Ok(())
+    }
+}
@@ -1,5 +1,61 @@
+pub mod admin_auction_command_handler;
+pub mod anonymize_user_command_handler;
+pub mod archiver;
+pub mod auction_opener;
+pub mod auction_result_recorder;
+pub mod bid_events;
+pub mod bid_screening_service;
+pub mod cancel_auction_command_handler;
 pub mod create_auction_command_handler;
 pub mod create_bid_command_handler;
+pub mod job_runner;
+pub mod deadline;
+pub mod extend_auction_command_handler;
+pub mod fault_injector;
+pub mod patch_auction_command_handler;
+pub mod projection_worker;
+pub mod publish_auction_command_handler;
+pub mod publishing_create_bid_command_handler;
+pub mod queued_create_bid_command_handler;
+pub mod scheduling_admin_auction_command_handler;
+pub mod scheduling_create_auction_command_handler;
+pub mod record_deposit_command_handler;
+pub mod registration_command_handler;
+pub mod relister;
+pub mod retract_bid_command_handler;
+pub mod settlement_recorder;
+pub mod stats_privacy;
+pub mod traffic_recorder;
+pub mod user_directory;
+pub mod warehouse_export;
 
+pub use admin_auction_command_handler::*;
+pub use anonymize_user_command_handler::*;
+pub use archiver::*;
+pub use auction_opener::*;
+pub use auction_result_recorder::*;
+pub use bid_events::*;
+pub use bid_screening_service::*;
+pub use cancel_auction_command_handler::*;
 pub use create_auction_command_handler::*;
 pub use create_bid_command_handler::*;
+pub use deadline::*;
+pub use extend_auction_command_handler::*;
+pub use fault_injector::*;
+pub use patch_auction_command_handler::*;
+pub use projection_worker::*;
+pub use publish_auction_command_handler::*;
+pub use publishing_create_bid_command_handler::*;
+pub use job_runner::*;
+pub use queued_create_bid_command_handler::*;
+pub use record_deposit_command_handler::*;
+pub use registration_command_handler::*;
+pub use relister::*;
+pub use retract_bid_command_handler::*;
+pub use settlement_recorder::*;
+pub use stats_privacy::*;
+pub use traffic_recorder::*;
+pub use user_directory::*;
+pub use warehouse_export::*;
+pub use scheduling_admin_auction_command_handler::*;
+pub use scheduling_create_auction_command_handler::*;
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+
+use crate::domain::commands::PatchAuctionCommand;
+use crate::domain::models::{Auction, Error, Errors, UserId};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::data::AuctionRepository;
+
+#[async_trait]
+pub trait PatchAuctionCommandHandler: Send + Sync + DynClone {
+    async fn handle(&self, user_id: Option<UserId>, command: PatchAuctionCommand) -> Result<Auction, Error>;
+}
+
+dyn_clone::clone_trait_object!(PatchAuctionCommandHandler);
+
+#[derive(Clone)]
+pub struct DefaultPatchAuctionCommandHandler {
+    repository: Box<dyn AuctionRepository>,
+    system_clock: Box<dyn SystemClock>,
+}
+
+impl DefaultPatchAuctionCommandHandler {
+    pub fn new(
+        repository: Box<dyn AuctionRepository>,
+        system_clock: Box<dyn SystemClock>,
+    ) -> Self {
+        Self {
+            repository,
+            system_clock,
+        }
+    }
+}
+
+#[async_trait]
+impl PatchAuctionCommandHandler for DefaultPatchAuctionCommandHandler {
+    async fn handle(&self, user_id: Option<UserId>, command: PatchAuctionCommand) -> Result<Auction, Error> {
+        let user_id = user_id
+            .ok_or_else(|| Error::Unauthorized("User must be logged in to change an auction".to_string()))?;
+
+        let mut auction = match self.repository.get_auction(command.auction_id).await? {
+            Some(auction) => auction,
+            None => return Err(Error::Validation(Errors::UnknownAuction)),
+        };
+        if *auction.user() != user_id {
+            return Err(Error::Forbidden("Only the seller may change an auction".to_string()));
+        }
+
+        auction
+            .update_details(self.system_clock.now(), command.title, command.description)
+            .map_err(Error::Validation)?;
+
+        self.repository.update_auction(auction).await
+    }
+}
@@ -0,0 +1,205 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgConnection;
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::domain::models::{AuctionStatus, Error};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::config::ProjectionWorkerConfig;
+use crate::infrastructure::data::{
+    add_to_rollups, save_checkpoint, AuctionEvent, AuctionEventType, AuctionRepository, PgOutbox,
+    RollupMetric,
+};
+
+/// Name of the summary projection in `projection_checkpoints`
+pub const AUCTION_SUMMARY_PROJECTION: &str = "auction_summaries";
+/// Name of the rollup projection in `projection_checkpoints`
+pub const ROLLUP_PROJECTION: &str = "rollups";
+
+/// Applies outbox events to the read models (`auction_summaries` and `rollups`), outside the
+/// HTTP API process.
+///
+/// Each projection keeps its own checkpoint. Events and the checkpoint are written in one
+/// transaction, so a restarted worker resumes where the last committed batch ended without
+/// applying any event twice.
+#[derive(Clone)]
+pub struct ProjectionWorker {
+    outbox: PgOutbox,
+    repository: Box<dyn AuctionRepository>,
+    system_clock: Box<dyn SystemClock>,
+    config: ProjectionWorkerConfig,
+}
+
+impl ProjectionWorker {
+    pub fn new(
+        outbox: PgOutbox,
+        repository: Box<dyn AuctionRepository>,
+        system_clock: Box<dyn SystemClock>,
+        config: ProjectionWorkerConfig,
+    ) -> Self {
+        Self {
+            outbox,
+            repository,
+            system_clock,
+            config,
+        }
+    }
+
+    /// Polls the outbox until cancelled. Errors are logged and retried on the next poll.
+    pub async fn run(&self, shutdown: CancellationToken) {
+        let poll_interval = Duration::from_millis(self.config.poll_interval_ms);
+        while !shutdown.is_cancelled() {
+            let mut backlog = false;
+            for projection in [AUCTION_SUMMARY_PROJECTION, ROLLUP_PROJECTION] {
+                match self.run_once(projection).await {
+                    Ok(applied) => backlog |= applied >= self.config.batch_size as usize,
+                    Err(e) => log::error!("Projection {} failed: {}", projection, e),
+                }
+            }
+            // Keep draining while there is a backlog
+            if !backlog {
+                tokio::select! {
+                    _ = shutdown.cancelled() => {}
+                    _ = tokio::time::sleep(poll_interval) => {}
+                }
+            }
+        }
+        log::info!("Projections stopped");
+    }
+
+    /// Applies the next batch of events to a projection, returning how many were applied.
+    pub async fn run_once(&self, projection: &str) -> Result<usize, Error> {
+        let position = self.outbox.checkpoint(projection).await?;
+        let events = self.outbox.read_after(position, self.config.batch_size).await?;
+        let Some(last) = events.last() else {
+            return Ok(0);
+        };
+
+        let mut tx = self
+            .outbox
+            .pool()
+            .begin()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+        for event in &events {
+            match projection {
+                AUCTION_SUMMARY_PROJECTION => apply_to_summary(&mut tx, event).await?,
+                _ => self.apply_to_rollups(&mut tx, event).await?,
+            }
+        }
+        save_checkpoint(&mut tx, projection, last.id).await?;
+        tx.commit()
+            .await
+            .map_err(|e| Error::Repository(e.to_string()))?;
+
+        Ok(events.len())
+    }
+
+    async fn apply_to_rollups(&self, conn: &mut PgConnection, event: &AuctionEvent) -> Result<(), Error> {
+        let payload = &event.payload;
+        match event.event_type {
+            AuctionEventType::AuctionCreated => {
+                add_to_rollups(conn, RollupMetric::AuctionsListed, event.created_at, None, 1).await
+            }
+            // Bids count when they were placed, also when retracted later
+            AuctionEventType::BidAccepted => {
+                let at = bid_at(event).unwrap_or(event.created_at);
+                add_to_rollups(conn, RollupMetric::BidsPlaced, at, None, 1).await
+            }
+            AuctionEventType::BidRetracted | AuctionEventType::BidVoided => {
+                let at = bid_at(event).unwrap_or(event.created_at);
+                add_to_rollups(conn, RollupMetric::BidsPlaced, at, None, -1).await
+            }
+            AuctionEventType::AuctionUpdated
+                if payload["status"] == AuctionStatus::Closed.to_string() =>
+            {
+                let Some(auction) = self.repository.get_auction(event.auction_id).await? else {
+                    return Ok(());
+                };
+                for (price, _, units) in auction.try_get_winners(self.system_clock.now()) {
+                    let currency = price.currency().to_string();
+                    let value = price.value() * units as i64;
+                    add_to_rollups(conn, RollupMetric::Gmv, event.created_at, Some(&currency), value).await?;
+                }
+                Ok(())
+            }
+            AuctionEventType::AuctionUpdated => Ok(()),
+        }
+    }
+}
+
+fn bid_at(event: &AuctionEvent) -> Option<DateTime<Utc>> {
+    event.payload["at"]
+        .as_str()
+        .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+        .map(|at| at.with_timezone(&Utc))
+}
+
+async fn apply_to_summary(conn: &mut PgConnection, event: &AuctionEvent) -> Result<(), Error> {
+    let payload = &event.payload;
+    let query = match event.event_type {
+        AuctionEventType::AuctionCreated => sqlx::query(
+            r#"
+            INSERT INTO auction_summaries (auction_id, status, updated_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (auction_id) DO NOTHING
+        "#,
+        )
+        .bind(event.auction_id.value())
+        .bind(payload["status"].as_str())
+        .bind(event.created_at),
+        AuctionEventType::BidAccepted => {
+            log::info!(
+                "Notifying watchers of auction {} about bid {}",
+                event.auction_id,
+                payload["bidId"]
+            );
+            sqlx::query(
+                r#"
+                UPDATE auction_summaries
+                SET bid_count = bid_count + 1,
+                    highest_amount_value = GREATEST(highest_amount_value, $2),
+                    highest_amount_currency = $3,
+                    last_bid_at = $4,
+                    updated_at = $5
+                WHERE auction_id = $1
+            "#,
+            )
+            .bind(event.auction_id.value())
+            .bind(payload["amountValue"].as_i64())
+            .bind(payload["amountCurrency"].as_str())
+            .bind(bid_at(event))
+            .bind(event.created_at)
+        }
+        AuctionEventType::BidRetracted | AuctionEventType::BidVoided => sqlx::query(
+            r#"
+            UPDATE auction_summaries
+            SET bid_count = bid_count - 1,
+                highest_amount_value = (
+                    SELECT MAX(amount_value) FROM bids
+                    WHERE auction_id = $1 AND retracted_at IS NULL AND voided_at IS NULL
+                ),
+                updated_at = $2
+            WHERE auction_id = $1
+        "#,
+        )
+        .bind(event.auction_id.value())
+        .bind(event.created_at),
+        AuctionEventType::AuctionUpdated => sqlx::query(
+            r#"
+            UPDATE auction_summaries
+            SET status = $2, updated_at = $3
+            WHERE auction_id = $1
+        "#,
+        )
+        .bind(event.auction_id.value())
+        .bind(payload["status"].as_str())
+        .bind(event.created_at),
+    };
+    query
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| Error::Repository(e.to_string()))?;
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+
+use crate::domain::commands::PublishAuctionCommand;
+use crate::domain::models::{Auction, Error, Errors, UserId};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::data::AuctionRepository;
+
+#[async_trait]
+pub trait PublishAuctionCommandHandler: Send + Sync + DynClone {
+    async fn handle(&self, user_id: Option<UserId>, command: PublishAuctionCommand) -> Result<Auction, Error>;
+}
+
+dyn_clone::clone_trait_object!(PublishAuctionCommandHandler);
+
+#[derive(Clone)]
+pub struct DefaultPublishAuctionCommandHandler {
+    repository: Box<dyn AuctionRepository>,
+    system_clock: Box<dyn SystemClock>,
+}
+
+impl DefaultPublishAuctionCommandHandler {
+    pub fn new(repository: Box<dyn AuctionRepository>, system_clock: Box<dyn SystemClock>) -> Self {
+        Self {
+            repository,
+            system_clock,
+        }
+    }
+}
+
+#[async_trait]
+impl PublishAuctionCommandHandler for DefaultPublishAuctionCommandHandler {
+    async fn handle(&self, user_id: Option<UserId>, command: PublishAuctionCommand) -> Result<Auction, Error> {
+        let user_id = user_id
+            .ok_or_else(|| Error::Unauthorized("User must be logged in to publish an auction".to_string()))?;
+
+        let mut auction = match self.repository.get_auction(command.auction_id).await? {
+            Some(auction) => auction,
+            None => return Err(Error::Validation(Errors::UnknownAuction)),
+        };
+        if auction.user() != &user_id {
+            return Err(Error::Forbidden("Only the seller may publish an auction".to_string()));
+        }
+
+        auction
+            .publish(self.system_clock.now())
+            .map_err(Error::Validation)?;
+        let auction = self.repository.update_auction(auction).await?;
+        log::info!("Auction {} published by {}", auction.auction_id(), user_id);
+
+        Ok(auction)
+    }
+}
+
+#[cfg(test)]
+mod publish_auction_command_handler_tests {
+    use super::*;
+    use crate::domain::commands::CreateAuctionCommand;
+    use crate::domain::models::{AuctionFactory, AuctionId, AuctionStatus, CurrencyCode};
+    use crate::domain::services::FixedSystemClock;
+    use crate::infrastructure::data::InMemoryAuctionRepository;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn starts_at() -> chrono::DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    fn draft(title: &str) -> Auction {
+        let mut auction = AuctionFactory::create_auction(
+            CreateAuctionCommand {
+                title: title.to_string(),
+                currency: CurrencyCode::SEK,
+                starts_at: starts_at(),
+                ends_at: starts_at() + Duration::days(7),
+                draft: true,
+                ..CreateAuctionCommand::default()
+            },
+            UserId::new("seller"),
+        )
+        .unwrap();
+        auction.set_auction_id(AuctionId::new(1));
+        auction
+    }
+
+    fn handler(auction: Auction) -> DefaultPublishAuctionCommandHandler {
+        DefaultPublishAuctionCommandHandler::new(
+            Box::new(InMemoryAuctionRepository::new(vec![auction])),
+            Box::new(FixedSystemClock(starts_at() - Duration::days(1))),
+        )
+    }
+
+    fn command() -> PublishAuctionCommand {
+        PublishAuctionCommand {
+            auction_id: AuctionId::new(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_seller_publishes_draft() {
+        let published = handler(draft("Lamp"))
+            .handle(Some(UserId::new("seller")), command())
+            .await
+            .unwrap();
+
+        assert_eq!(published.status(), AuctionStatus::Scheduled);
+    }
+
+    #[tokio::test]
+    async fn test_drafts_need_a_title_to_be_published() {
+        let result = handler(draft("")).handle(Some(UserId::new("seller")), command()).await;
+
+        assert!(matches!(result, Err(Error::Validation(Errors::AuctionIncomplete))));
+    }
+
+    #[tokio::test]
+    async fn test_other_users_cannot_publish() {
+        let result = handler(draft("Lamp")).handle(Some(UserId::new("buyer")), command()).await;
+
+        assert!(matches!(result, Err(Error::Forbidden(_))));
+    }
+}
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+
+use crate::domain::commands::CreateBidCommand;
+use crate::domain::models::{Error, UserId};
+use crate::infrastructure::services::{BidEvents, CreateBidCommandHandler};
+
+/// Publishes a [`BidEvents`] notification for every bid the inner handler accepts.
+#[derive(Clone)]
+pub struct PublishingCreateBidCommandHandler {
+    inner: Box<dyn CreateBidCommandHandler>,
+    events: BidEvents,
+}
+
+impl PublishingCreateBidCommandHandler {
+    pub fn new(inner: Box<dyn CreateBidCommandHandler>, events: BidEvents) -> Self {
+        Self { inner, events }
+    }
+}
+
+#[async_trait]
+impl CreateBidCommandHandler for PublishingCreateBidCommandHandler {
+    async fn handle(&self, user_id: Option<UserId>, command: CreateBidCommand) -> Result<(), Error> {
+        let auction_id = command.auction_id;
+        self.inner.handle(user_id, command).await?;
+        self.events.publish(auction_id);
+        Ok(())
+    }
+}
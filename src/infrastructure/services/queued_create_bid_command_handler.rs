@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::domain::commands::CreateBidCommand;
+use crate::domain::models::{AuctionId, Error, Errors, UserId};
+use crate::infrastructure::data::AuctionRepository;
+use crate::infrastructure::services::CreateBidCommandHandler;
+
+type BidJob = (Option<UserId>, CreateBidCommand, oneshot::Sender<Result<(), Error>>);
+type BidQueues = Arc<Mutex<HashMap<AuctionId, mpsc::Sender<BidJob>>>>;
+
+/// Serializes bids per auction through a bounded channel, so that bursts close to the end of an
+/// auction are processed one at a time in arrival order instead of being rejected or racing.
+#[derive(Clone)]
+pub struct QueuedCreateBidCommandHandler {
+    inner: Box<dyn CreateBidCommandHandler>,
+    repository: Box<dyn AuctionRepository>,
+    capacity: usize,
+    idle_timeout: Duration,
+    queues: BidQueues,
+}
+
+impl QueuedCreateBidCommandHandler {
+    pub fn new(
+        inner: Box<dyn CreateBidCommandHandler>,
+        repository: Box<dyn AuctionRepository>,
+        capacity: usize,
+        idle_timeout: Duration,
+    ) -> Self {
+        Self {
+            inner,
+            repository,
+            capacity: capacity.max(1),
+            idle_timeout,
+            queues: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Number of bids waiting to be processed, per auction with a non-empty queue.
+    pub fn queue_depths(&self) -> HashMap<AuctionId, usize> {
+        self.queues
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(auction_id, sender)| (*auction_id, self.capacity - sender.capacity()))
+            .filter(|(_, depth)| *depth > 0)
+            .collect()
+    }
+
+    fn has_queue(&self, auction_id: AuctionId) -> bool {
+        self.queues
+            .lock()
+            .unwrap()
+            .get(&auction_id)
+            .is_some_and(|sender| !sender.is_closed())
+    }
+
+    fn sender_for(&self, auction_id: AuctionId) -> mpsc::Sender<BidJob> {
+        let mut queues = self.queues.lock().unwrap();
+        if let Some(sender) = queues.get(&auction_id) {
+            if !sender.is_closed() {
+                return sender.clone();
+            }
+        }
+        let (sender, receiver) = mpsc::channel(self.capacity);
+        queues.insert(auction_id, sender.clone());
+        tokio::spawn(run_worker(
+            auction_id,
+            receiver,
+            self.inner.clone(),
+            self.queues.clone(),
+            self.idle_timeout,
+        ));
+        sender
+    }
+}
+
+async fn run_worker(
+    auction_id: AuctionId,
+    mut receiver: mpsc::Receiver<BidJob>,
+    handler: Box<dyn CreateBidCommandHandler>,
+    queues: BidQueues,
+    idle_timeout: Duration,
+) {
+    loop {
+        match tokio::time::timeout(idle_timeout, receiver.recv()).await {
+            Ok(Some(job)) => process(&*handler, job).await,
+            Ok(None) => break,
+            Err(_) => {
+                // Idle: unregister first so that no new senders are handed out, then drain
+                // whatever got queued in between
+                queues.lock().unwrap().remove(&auction_id);
+                receiver.close();
+                while let Ok(job) = receiver.try_recv() {
+                    process(&*handler, job).await;
+                }
+                break;
+            }
+        }
+    }
+}
+
+async fn process(handler: &dyn CreateBidCommandHandler, (user_id, command, reply): BidJob) {
+    let result = handler.handle(user_id, command).await;
+    // The caller may have stopped waiting for the result
+    let _ = reply.send(result);
+}
+
+#[async_trait]
+impl CreateBidCommandHandler for QueuedCreateBidCommandHandler {
+    async fn handle(&self, user_id: Option<UserId>, command: CreateBidCommand) -> Result<(), Error> {
+        let (reply, response) = oneshot::channel();
+        let auction_id = command.auction_id;
+        // Made-up auction ids must not spawn workers
+        if !self.has_queue(auction_id) && !self.repository.auction_exists(auction_id).await? {
+            return Err(Error::Validation(Errors::UnknownAuction));
+        }
+        let mut job = (user_id, command, reply);
+        loop {
+            match self.sender_for(auction_id).send(job).await {
+                Ok(()) => break,
+                // The worker went idle between lookup and send, hand the bid to a fresh one
+                Err(mpsc::error::SendError(returned)) => job = returned,
+            }
+        }
+        response
+            .await
+            .map_err(|_| Error::Internal("Bid queue worker stopped unexpectedly".to_string()))?
+    }
+}
+
+#[cfg(test)]
+mod queued_create_bid_command_handler_tests {
+    use super::*;
+    use crate::domain::models::{Amount, CurrencyCode};
+    use crate::infrastructure::data::InMemoryAuctionRepository;
+
+    #[derive(Clone)]
+    struct AcceptingHandler;
+
+    #[async_trait]
+    impl CreateBidCommandHandler for AcceptingHandler {
+        async fn handle(&self, _: Option<UserId>, _: CreateBidCommand) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unknown_auctions_get_no_queue() {
+        let queue = QueuedCreateBidCommandHandler::new(
+            Box::new(AcceptingHandler),
+            Box::new(InMemoryAuctionRepository::default()),
+            10,
+            Duration::from_secs(60),
+        );
+        let command = CreateBidCommand {
+            amount: Amount::new(10, CurrencyCode::SEK),
+            auction_id: AuctionId::new(42),
+            lot: None,
+            quantity: None,
+            max_bid: false,
+            metadata: None,
+        };
+
+        let result = queue.handle(Some(UserId::new("buyer")), command).await;
+
+        assert!(matches!(result, Err(Error::Validation(Errors::UnknownAuction))));
+        assert!(queue.queues.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+
+use crate::domain::commands::RecordDepositCommand;
+use crate::domain::models::{Deposit, Error, Errors, UserId};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::data::{AuctionRepository, DepositRepository};
+
+#[async_trait]
+pub trait RecordDepositCommandHandler: Send + Sync + DynClone {
+    async fn handle(&self, user_id: Option<UserId>, command: RecordDepositCommand) -> Result<Deposit, Error>;
+}
+
+dyn_clone::clone_trait_object!(RecordDepositCommandHandler);
+
+#[derive(Clone)]
+pub struct DefaultRecordDepositCommandHandler {
+    repository: Box<dyn AuctionRepository>,
+    deposit_repository: Box<dyn DepositRepository>,
+    system_clock: Box<dyn SystemClock>,
+}
+
+impl DefaultRecordDepositCommandHandler {
+    pub fn new(
+        repository: Box<dyn AuctionRepository>,
+        deposit_repository: Box<dyn DepositRepository>,
+        system_clock: Box<dyn SystemClock>,
+    ) -> Self {
+        Self {
+            repository,
+            deposit_repository,
+            system_clock,
+        }
+    }
+}
+
+#[async_trait]
+impl RecordDepositCommandHandler for DefaultRecordDepositCommandHandler {
+    async fn handle(&self, user_id: Option<UserId>, command: RecordDepositCommand) -> Result<Deposit, Error> {
+        let user_id = user_id
+            .ok_or_else(|| Error::Unauthorized("User must be logged in to record a deposit".to_string()))?;
+        let auction = match self.repository.get_auction(command.auction_id).await? {
+            Some(auction) => auction,
+            None => return Err(Error::Validation(Errors::UnknownAuction)),
+        };
+        if auction.user() == &user_id {
+            return Err(Error::Validation(Errors::SellerCannotPlaceBids));
+        }
+        if command.amount.currency() != auction.currency() {
+            return Err(Error::Validation(Errors::BidCurrencyConversion));
+        }
+
+        let deposit = Deposit {
+            auction_id: command.auction_id,
+            user: user_id,
+            amount: command.amount,
+            recorded_at: self.system_clock.now(),
+        };
+        if auction.deposit().is_some_and(|required| !deposit.covers(&required)) {
+            return Err(Error::Validation(Errors::DepositTooLow));
+        }
+        let deposit = self.deposit_repository.record(deposit).await?;
+        log::info!(
+            "Deposit of {} on auction {} recorded for {}",
+            deposit.amount,
+            deposit.auction_id,
+            deposit.user
+        );
+
+        Ok(deposit)
+    }
+}
+
+#[cfg(test)]
+mod record_deposit_command_handler_tests {
+    use super::*;
+    use crate::domain::commands::CreateAuctionCommand;
+    use crate::domain::models::{Amount, Auction, AuctionId, CurrencyCode};
+    use crate::domain::services::FixedSystemClock;
+    use crate::domain::test_support::{self, lamp, starts_at};
+    use crate::infrastructure::data::{InMemoryAuctionRepository, InMemoryDepositRepository};
+
+    fn auction() -> Auction {
+        test_support::auction(CreateAuctionCommand { deposit: Some(100), ..lamp() })
+    }
+
+    fn handler(deposits: InMemoryDepositRepository) -> DefaultRecordDepositCommandHandler {
+        DefaultRecordDepositCommandHandler::new(
+            Box::new(InMemoryAuctionRepository::new(vec![auction()])),
+            Box::new(deposits),
+            Box::new(FixedSystemClock(starts_at())),
+        )
+    }
+
+    fn command(amount: Amount) -> RecordDepositCommand {
+        RecordDepositCommand {
+            auction_id: AuctionId::new(1),
+            amount,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_records_deposit_covering_the_requirement() {
+        let deposits = InMemoryDepositRepository::default();
+
+        let deposit = handler(deposits.clone())
+            .handle(Some(UserId::new("buyer")), command(Amount::new(100, CurrencyCode::SEK)))
+            .await
+            .unwrap();
+
+        assert_eq!(deposit.recorded_at, starts_at());
+        let stored = deposits.get_deposit(AuctionId::new(1), &UserId::new("buyer")).await.unwrap();
+        assert_eq!(stored, Some(deposit));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_insufficient_deposits() {
+        let handler = handler(InMemoryDepositRepository::default());
+        let buyer = || Some(UserId::new("buyer"));
+
+        let result = handler.handle(buyer(), command(Amount::new(99, CurrencyCode::SEK))).await;
+        assert!(matches!(result, Err(Error::Validation(Errors::DepositTooLow))));
+        let result = handler.handle(buyer(), command(Amount::new(100, CurrencyCode::DKK))).await;
+        assert!(matches!(result, Err(Error::Validation(Errors::BidCurrencyConversion))));
+        let result = handler
+            .handle(Some(UserId::new("seller")), command(Amount::new(100, CurrencyCode::SEK)))
+            .await;
+        assert!(matches!(result, Err(Error::Validation(Errors::SellerCannotPlaceBids))));
+    }
+}
@@ -0,0 +1,189 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+
+use crate::domain::commands::RegistrationCommand;
+use crate::domain::models::{Auction, AuctionId, Error, Errors, Registration, RegistrationStatus, UserId};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::data::{AuctionRepository, RegistrationRepository};
+
+#[async_trait]
+pub trait RegistrationCommandHandler: Send + Sync + DynClone {
+    async fn handle(&self, user_id: Option<UserId>, command: RegistrationCommand) -> Result<Registration, Error>;
+    /// The registrations of an auction, only visible to its seller.
+    async fn registrations(&self, user_id: Option<UserId>, auction_id: AuctionId) -> Result<Vec<Registration>, Error>;
+}
+
+dyn_clone::clone_trait_object!(RegistrationCommandHandler);
+
+#[derive(Clone)]
+pub struct DefaultRegistrationCommandHandler {
+    repository: Box<dyn AuctionRepository>,
+    registration_repository: Box<dyn RegistrationRepository>,
+    system_clock: Box<dyn SystemClock>,
+}
+
+impl DefaultRegistrationCommandHandler {
+    pub fn new(
+        repository: Box<dyn AuctionRepository>,
+        registration_repository: Box<dyn RegistrationRepository>,
+        system_clock: Box<dyn SystemClock>,
+    ) -> Self {
+        Self {
+            repository,
+            registration_repository,
+            system_clock,
+        }
+    }
+
+    async fn get_auction(&self, auction_id: AuctionId) -> Result<Auction, Error> {
+        match self.repository.get_auction(auction_id).await? {
+            Some(auction) => Ok(auction),
+            None => Err(Error::Validation(Errors::UnknownAuction)),
+        }
+    }
+
+    async fn decide(
+        &self,
+        seller: &UserId,
+        auction: &Auction,
+        bidder: UserId,
+        status: RegistrationStatus,
+    ) -> Result<Registration, Error> {
+        if auction.user() != seller {
+            return Err(Error::Forbidden("Only the seller may decide on registrations".to_string()));
+        }
+        let registration = self
+            .registration_repository
+            .get_registration(auction.auction_id(), &bidder)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("{} has not registered for the auction", bidder)))?;
+        self.registration_repository
+            .save(Registration {
+                status,
+                decided_at: Some(self.system_clock.now()),
+                ..registration
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl RegistrationCommandHandler for DefaultRegistrationCommandHandler {
+    async fn handle(&self, user_id: Option<UserId>, command: RegistrationCommand) -> Result<Registration, Error> {
+        let user_id = user_id
+            .ok_or_else(|| Error::Unauthorized("User must be logged in to handle registrations".to_string()))?;
+        let auction = self.get_auction(command.auction_id()).await?;
+
+        match command {
+            RegistrationCommand::Register { auction_id } => {
+                if auction.user() == &user_id {
+                    return Err(Error::Validation(Errors::SellerCannotPlaceBids));
+                }
+                // Registering again keeps the seller's earlier decision
+                if let Some(existing) = self.registration_repository.get_registration(auction_id, &user_id).await? {
+                    return Ok(existing);
+                }
+                self.registration_repository
+                    .save(Registration {
+                        auction_id,
+                        user: user_id,
+                        status: RegistrationStatus::Pending,
+                        registered_at: self.system_clock.now(),
+                        decided_at: None,
+                    })
+                    .await
+            }
+            RegistrationCommand::Approve { bidder, .. } => {
+                self.decide(&user_id, &auction, bidder, RegistrationStatus::Approved).await
+            }
+            RegistrationCommand::Reject { bidder, .. } => {
+                self.decide(&user_id, &auction, bidder, RegistrationStatus::Rejected).await
+            }
+        }
+    }
+
+    async fn registrations(&self, user_id: Option<UserId>, auction_id: AuctionId) -> Result<Vec<Registration>, Error> {
+        let user_id = user_id
+            .ok_or_else(|| Error::Unauthorized("User must be logged in to list registrations".to_string()))?;
+        let auction = self.get_auction(auction_id).await?;
+        if auction.user() != &user_id {
+            return Err(Error::Forbidden("Only the seller may list registrations".to_string()));
+        }
+        self.registration_repository.get_registrations(auction_id).await
+    }
+}
+
+#[cfg(test)]
+mod registration_command_handler_tests {
+    use super::*;
+    use crate::domain::commands::CreateAuctionCommand;
+    use crate::domain::services::FixedSystemClock;
+    use crate::domain::test_support::{self, lamp, starts_at};
+    use crate::infrastructure::data::{InMemoryAuctionRepository, InMemoryRegistrationRepository};
+    use chrono::Duration;
+
+    fn auction() -> Auction {
+        test_support::auction(CreateAuctionCommand { requires_registration: true, ..lamp() })
+    }
+
+    fn handler() -> DefaultRegistrationCommandHandler {
+        DefaultRegistrationCommandHandler::new(
+            Box::new(InMemoryAuctionRepository::new(vec![auction()])),
+            Box::new(InMemoryRegistrationRepository::default()),
+            Box::new(FixedSystemClock(starts_at() + Duration::hours(1))),
+        )
+    }
+
+    fn register() -> RegistrationCommand {
+        RegistrationCommand::Register {
+            auction_id: AuctionId::new(1),
+        }
+    }
+
+    fn approve() -> RegistrationCommand {
+        RegistrationCommand::Approve {
+            auction_id: AuctionId::new(1),
+            bidder: UserId::new("buyer"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_seller_approves_registered_bidder() {
+        let handler = handler();
+
+        let registration = handler.handle(Some(UserId::new("buyer")), register()).await.unwrap();
+        assert_eq!(registration.status, RegistrationStatus::Pending);
+
+        let registration = handler.handle(Some(UserId::new("seller")), approve()).await.unwrap();
+        assert!(registration.is_approved());
+        assert_eq!(registration.decided_at, Some(starts_at() + Duration::hours(1)));
+
+        // Registering again does not undo the approval
+        let registration = handler.handle(Some(UserId::new("buyer")), register()).await.unwrap();
+        assert!(registration.is_approved());
+    }
+
+    #[tokio::test]
+    async fn test_only_seller_decides_on_registrations() {
+        let handler = handler();
+        handler.handle(Some(UserId::new("buyer")), register()).await.unwrap();
+
+        let result = handler.handle(Some(UserId::new("buyer")), approve()).await;
+        assert!(matches!(result, Err(Error::Forbidden(_))));
+        let result = handler.registrations(Some(UserId::new("buyer")), AuctionId::new(1)).await;
+        assert!(matches!(result, Err(Error::Forbidden(_))));
+
+        let registrations = handler.registrations(Some(UserId::new("seller")), AuctionId::new(1)).await.unwrap();
+        assert_eq!(registrations.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_seller_cannot_register_or_approve_unknown_bidders() {
+        let handler = handler();
+
+        let result = handler.handle(Some(UserId::new("seller")), register()).await;
+        assert!(matches!(result, Err(Error::Validation(Errors::SellerCannotPlaceBids))));
+        let result = handler.handle(Some(UserId::new("seller")), approve()).await;
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::domain::models::{Auction, Error};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::config::RelistingConfig;
+use crate::infrastructure::data::AuctionRepository;
+
+/// Relists auctions that ended without a winner when their seller opted in, run by the
+/// `projection-worker` binary.
+///
+/// The relisted auction is created before the original is linked to it. Should linking fail,
+/// the next run relists the original again, so only one worker may run the relister.
+#[derive(Clone)]
+pub struct Relister {
+    repository: Box<dyn AuctionRepository>,
+    system_clock: Box<dyn SystemClock>,
+    config: RelistingConfig,
+}
+
+impl Relister {
+    pub fn new(
+        repository: Box<dyn AuctionRepository>,
+        system_clock: Box<dyn SystemClock>,
+        config: RelistingConfig,
+    ) -> Self {
+        Self {
+            repository,
+            system_clock,
+            config,
+        }
+    }
+
+    /// Relists every `interval` until cancelled. Errors are logged and retried on the next run.
+    pub async fn run(&self, shutdown: CancellationToken) {
+        let interval = Duration::from_secs(self.config.interval);
+        while !shutdown.is_cancelled() {
+            if let Err(e) = self.run_once().await {
+                log::error!("Relisting failed: {}", e);
+            }
+            tokio::select! {
+                _ = shutdown.cancelled() => {}
+                _ = tokio::time::sleep(interval) => {}
+            }
+        }
+    }
+
+    /// Relists the auctions that are due, returning the new auctions.
+    pub async fn run_once(&self) -> Result<Vec<Auction>, Error> {
+        let now = self.system_clock.now();
+        let mut relisted = Vec::new();
+        for mut auction in self.repository.get_auctions().await? {
+            if !auction.can_relist(now) {
+                continue;
+            }
+            let relisting = auction.relist(now).map_err(Error::Validation)?;
+            let created = self.repository.create_auction(relisting).await?;
+            auction.advance(now);
+            auction.set_relisted_as(created.auction_id());
+            self.repository.update_auction(auction.clone()).await?;
+            log::info!(
+                "Auction {} ended unsold and was relisted as {}",
+                auction.auction_id(),
+                created.auction_id()
+            );
+            relisted.push(created);
+        }
+        Ok(relisted)
+    }
+}
+
+#[cfg(test)]
+mod relister_tests {
+    use super::*;
+    use crate::domain::commands::CreateAuctionCommand;
+    use crate::domain::models::{
+        Amount, AuctionId, AuctionStatus, BidData, CurrencyCode, RelistPolicy, UserId,
+    };
+    use crate::domain::services::FixedSystemClock;
+    use crate::domain::test_support::{self, lamp, starts_at};
+    use crate::infrastructure::data::InMemoryAuctionRepository;
+
+    fn auction(id: i64, max_relists: i32) -> Auction {
+        let mut auction = test_support::auction(CreateAuctionCommand {
+            reserve_price: Some(100),
+            relist_policy: Some(RelistPolicy {
+                duration_seconds: 3 * 24 * 3600,
+                max_relists,
+            }),
+            external_reference: Some(format!("lamp-{}", id)),
+            ..lamp()
+        });
+        auction.set_auction_id(AuctionId::new(id));
+        auction
+    }
+
+    fn relister(repository: InMemoryAuctionRepository, days: i64) -> Relister {
+        Relister::new(
+            Box::new(repository),
+            Box::new(FixedSystemClock(starts_at() + chrono::Duration::days(days))),
+            RelistingConfig::default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_relists_unsold_auctions_once() {
+        let mut below_reserve = auction(2, 1);
+        below_reserve
+            .try_add_bid(
+                starts_at() + chrono::Duration::hours(1),
+                BidData {
+                    user: UserId::new("buyer"),
+                    amount: Amount::new(50, CurrencyCode::SEK),
+                    at: starts_at() + chrono::Duration::hours(1),
+                    quantity: 1,
+                    metadata: None,
+                },
+            )
+            .unwrap();
+        let repository = InMemoryAuctionRepository::new(vec![auction(1, 1), below_reserve]);
+
+        let relisted = relister(repository.clone(), 8).run_once().await.unwrap();
+
+        assert_eq!(relisted.len(), 2);
+        let relisted = &relisted[0];
+        assert_eq!(relisted.relisted_from(), Some(AuctionId::new(1)));
+        assert_eq!(relisted.relist_count(), 1);
+        assert_eq!(relisted.starts_at(), starts_at() + chrono::Duration::days(8));
+        assert_eq!(relisted.expiry(), starts_at() + chrono::Duration::days(11));
+        assert!(relisted.bids().is_empty());
+        assert_eq!(relisted.external_reference(), None);
+        let original = repository.get_auction(AuctionId::new(1)).await.unwrap().unwrap();
+        assert_eq!(original.relisted_as(), Some(relisted.auction_id()));
+        assert_eq!(original.status(), AuctionStatus::Closed);
+
+        // Neither the originals nor the relistings, which have used up the relists, are due
+        let again = relister(repository, 12).run_once().await.unwrap();
+        assert!(again.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_leaves_running_auctions_alone() {
+        let repository = InMemoryAuctionRepository::new(vec![auction(1, 3)]);
+
+        let relisted = relister(repository, 3).run_once().await.unwrap();
+
+        assert!(relisted.is_empty());
+    }
+}
@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+use std::time::Duration;
+
+use crate::domain::commands::RetractBidCommand;
+use crate::domain::models::{Auction, Error, Errors, UserId};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::data::AuctionRepository;
+
+#[async_trait]
+pub trait RetractBidCommandHandler: Send + Sync + DynClone {
+    async fn handle(&self, user_id: Option<UserId>, command: RetractBidCommand) -> Result<Auction, Error>;
+}
+
+dyn_clone::clone_trait_object!(RetractBidCommandHandler);
+
+#[derive(Clone)]
+pub struct DefaultRetractBidCommandHandler {
+    repository: Box<dyn AuctionRepository>,
+    system_clock: Box<dyn SystemClock>,
+    window: chrono::Duration,
+}
+
+impl DefaultRetractBidCommandHandler {
+    pub fn new(
+        repository: Box<dyn AuctionRepository>,
+        system_clock: Box<dyn SystemClock>,
+        window: Duration,
+    ) -> Self {
+        Self {
+            repository,
+            system_clock,
+            window: chrono::Duration::from_std(window).unwrap_or(chrono::Duration::MAX),
+        }
+    }
+}
+
+#[async_trait]
+impl RetractBidCommandHandler for DefaultRetractBidCommandHandler {
+    async fn handle(&self, user_id: Option<UserId>, command: RetractBidCommand) -> Result<Auction, Error> {
+        let user_id = user_id
+            .ok_or_else(|| Error::Unauthorized("User must be logged in to retract a bid".to_string()))?;
+
+        let mut auction = match self.repository.get_auction(command.auction_id).await? {
+            Some(auction) => auction,
+            None => return Err(Error::Validation(Errors::UnknownAuction)),
+        };
+        let retracted = auction
+            .retract_bid(self.system_clock.now(), &user_id, self.window)
+            .map_err(Error::Validation)?;
+        let auction = self.repository.update_auction(auction).await?;
+        log::info!(
+            "Bid {} on auction {} retracted by {}",
+            retracted.id,
+            auction.auction_id(),
+            user_id
+        );
+
+        Ok(auction)
+    }
+}
+
+#[cfg(test)]
+mod retract_bid_command_handler_tests {
+    use super::*;
+    use crate::domain::models::AuctionId;
+    use crate::domain::services::FixedSystemClock;
+    use crate::domain::test_support::{self, bid, lamp, starts_at};
+    use crate::infrastructure::data::InMemoryAuctionRepository;
+    use chrono::Utc;
+
+    fn bid_at() -> chrono::DateTime<Utc> {
+        starts_at() + chrono::Duration::hours(12)
+    }
+
+    fn auction() -> Auction {
+        let mut auction = test_support::auction(lamp());
+        auction.try_add_bid(bid_at(), bid("buyer", 10, bid_at())).unwrap();
+        auction
+    }
+
+    fn handler(repository: InMemoryAuctionRepository, seconds_after_bid: i64) -> DefaultRetractBidCommandHandler {
+        DefaultRetractBidCommandHandler::new(
+            Box::new(repository),
+            Box::new(FixedSystemClock(bid_at() + chrono::Duration::seconds(seconds_after_bid))),
+            Duration::from_secs(60),
+        )
+    }
+
+    fn command() -> RetractBidCommand {
+        RetractBidCommand {
+            auction_id: AuctionId::new(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retracts_bid_within_window() {
+        let repository = InMemoryAuctionRepository::new(vec![auction()]);
+
+        handler(repository.clone(), 30)
+            .handle(Some(UserId::new("buyer")), command())
+            .await
+            .unwrap();
+
+        let stored = repository.get_auction(AuctionId::new(1)).await.unwrap().unwrap();
+        assert!(stored.bids().is_empty());
+        assert_eq!(stored.retracted_bids().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_retraction_after_window() {
+        let repository = InMemoryAuctionRepository::new(vec![auction()]);
+
+        let result = handler(repository, 61)
+            .handle(Some(UserId::new("buyer")), command())
+            .await;
+
+        assert!(matches!(result, Err(Error::Validation(Errors::RetractionWindowHasPassed))));
+    }
+
+    #[tokio::test]
+    async fn test_other_bidders_have_nothing_to_retract() {
+        let repository = InMemoryAuctionRepository::new(vec![auction()]);
+
+        let result = handler(repository, 30)
+            .handle(Some(UserId::new("other")), command())
+            .await;
+
+        assert!(matches!(result, Err(Error::Validation(Errors::NoBidToRetract))));
+    }
+}
@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+
+use crate::domain::commands::AdminAuctionCommand;
+use crate::domain::models::{Auction, Error, User};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::data::{JobKind, JobRepository, JobState};
+use crate::infrastructure::services::AdminAuctionCommandHandler;
+
+/// Brings the finalization job of the auctions that `inner` ended forward to now, rather than
+/// when they were meant to end.
+#[derive(Clone)]
+pub struct SchedulingAdminAuctionCommandHandler {
+    inner: Box<dyn AdminAuctionCommandHandler>,
+    jobs: Box<dyn JobRepository>,
+    clock: Box<dyn SystemClock>,
+}
+
+impl SchedulingAdminAuctionCommandHandler {
+    pub fn new(
+        inner: Box<dyn AdminAuctionCommandHandler>,
+        jobs: Box<dyn JobRepository>,
+        clock: Box<dyn SystemClock>,
+    ) -> Self {
+        Self { inner, jobs, clock }
+    }
+}
+
+#[async_trait]
+impl AdminAuctionCommandHandler for SchedulingAdminAuctionCommandHandler {
+    async fn handle(&self, user: Option<User>, command: AdminAuctionCommand) -> Result<Auction, Error> {
+        let auction = self.inner.handle(user, command).await?;
+        let now = self.clock.now();
+        let pending = self.jobs.get_jobs(Some(JobState::Pending)).await?;
+        for job in pending
+            .iter()
+            .filter(|job| job.kind == JobKind::Finalization && job.auction_id == auction.auction_id())
+        {
+            if job.run_at > now {
+                self.jobs.reschedule(job.id, now, now).await?;
+            }
+        }
+        Ok(auction)
+    }
+}
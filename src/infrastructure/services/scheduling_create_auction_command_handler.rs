@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+
+use crate::domain::commands::CreateAuctionCommand;
+use crate::domain::models::{Auction, Error, UserId};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::data::{JobKind, JobRepository};
+use crate::infrastructure::services::CreateAuctionCommandHandler;
+
+/// Schedules the finalization job of the auctions created by `inner` for when they end, see
+/// [`crate::infrastructure::services::JobRunner`].
+#[derive(Clone)]
+pub struct SchedulingCreateAuctionCommandHandler {
+    inner: Box<dyn CreateAuctionCommandHandler>,
+    jobs: Box<dyn JobRepository>,
+    clock: Box<dyn SystemClock>,
+}
+
+impl SchedulingCreateAuctionCommandHandler {
+    pub fn new(
+        inner: Box<dyn CreateAuctionCommandHandler>,
+        jobs: Box<dyn JobRepository>,
+        clock: Box<dyn SystemClock>,
+    ) -> Self {
+        Self { inner, jobs, clock }
+    }
+}
+
+#[async_trait]
+impl CreateAuctionCommandHandler for SchedulingCreateAuctionCommandHandler {
+    async fn handle(&self, user_id: Option<UserId>, command: CreateAuctionCommand) -> Result<Auction, Error> {
+        let auction = self.inner.handle(user_id, command).await?;
+        self.jobs
+            .enqueue(JobKind::Finalization, auction.auction_id(), auction.effective_end(), self.clock.now())
+            .await?;
+        Ok(auction)
+    }
+}
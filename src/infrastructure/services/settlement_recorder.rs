@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::models::{Auction, Error, FeePolicy, Settlement};
+use crate::infrastructure::data::SettlementRepository;
+
+/// Records the settlement of an auction the first time it is asked for after closing, and serves
+/// the recorded settlement from then on, so that a change of fees or tax rate does not rewrite
+/// invoices already sent.
+#[derive(Clone)]
+pub struct SettlementRecorder {
+    repository: Box<dyn SettlementRepository>,
+    default_policy: FeePolicy,
+}
+
+impl SettlementRecorder {
+    pub fn new(repository: Box<dyn SettlementRepository>, default_policy: FeePolicy) -> Self {
+        Self {
+            repository,
+            default_policy,
+        }
+    }
+
+    /// The settlement of the auction, or `None` while it has not closed.
+    pub async fn settlement(&self, auction: &Auction, now: DateTime<Utc>) -> Result<Option<Settlement>, Error> {
+        let Some(settlement) = auction.try_get_settlement(now, &self.default_policy) else {
+            return Ok(None);
+        };
+        if let Some(recorded) = self.repository.get_settlement(auction.auction_id()).await? {
+            return Ok(Some(recorded));
+        }
+        let recorded = self.repository.record(auction.auction_id(), settlement, now).await?;
+        log::info!("Recorded the settlement of auction {}", auction.auction_id());
+        Ok(Some(recorded))
+    }
+}
+
+#[cfg(test)]
+mod settlement_recorder_tests {
+    use super::*;
+    use crate::domain::commands::CreateAuctionCommand;
+    use crate::domain::models::{Amount, CurrencyCode};
+    use crate::domain::test_support::{auction, lamp, starts_at, with_bid};
+    use crate::infrastructure::data::InMemorySettlementRepository;
+    use chrono::Duration;
+
+    fn sold_auction(tax_rate_bps: i64) -> Auction {
+        with_bid(auction(CreateAuctionCommand { tax_rate_bps: Some(tax_rate_bps), ..lamp() }), "buyer", 1000)
+    }
+
+    #[tokio::test]
+    async fn test_serves_the_recorded_settlement_after_the_tax_rate_changes() {
+        let policy = FeePolicy {
+            buyers_premium_bps: 1000,
+            ..FeePolicy::default()
+        };
+        let recorder = SettlementRecorder::new(Box::new(InMemorySettlementRepository::default()), policy);
+        let closed_at = starts_at() + Duration::days(8);
+
+        let running = recorder.settlement(&sold_auction(2500), starts_at()).await.unwrap();
+        assert_eq!(running, None);
+
+        let first = recorder.settlement(&sold_auction(2500), closed_at).await.unwrap().unwrap();
+        assert_eq!(first.buyers[0].tax, Amount::new(25, CurrencyCode::SEK));
+
+        let later = recorder.settlement(&sold_auction(1200), closed_at).await.unwrap().unwrap();
+        assert_eq!(later, first);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::domain::services::RandomSource;
+use crate::infrastructure::config::StatsPrivacyConfig;
+use crate::infrastructure::data::{RollupMetric, RollupPoint};
+
+/// Protects published rollups against re-identification of the few bidders or sellers behind
+/// a bucket: small buckets are held back and the others get Laplace noise.
+#[derive(Clone)]
+pub struct StatsPrivacy {
+    config: StatsPrivacyConfig,
+    random: Box<dyn RandomSource>,
+}
+
+impl StatsPrivacy {
+    pub fn new(config: StatsPrivacyConfig, random: Box<dyn RandomSource>) -> Self {
+        Self { config, random }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// The points that may be published, as is when protection is disabled.
+    pub fn protect(&self, metric: RollupMetric, points: Vec<RollupPoint>) -> Vec<RollupPoint> {
+        if !self.config.enabled {
+            return points;
+        }
+        // One event changes a count by one, and an amount by at most the sensitivity
+        let sensitivity = match metric {
+            RollupMetric::AuctionsListed | RollupMetric::BidsPlaced => 1.0,
+            RollupMetric::Gmv => self.config.amount_sensitivity as f64,
+        };
+        let scale = sensitivity / self.config.epsilon;
+        points
+            .into_iter()
+            .filter(|point| point.contributors >= self.config.min_cohort_size)
+            .map(|point| RollupPoint {
+                value: (point.value as f64 + self.laplace(scale)).round().max(0.0) as i64,
+                ..point
+            })
+            .collect()
+    }
+
+    /// Laplace distributed noise centered on zero, by inverting its distribution function.
+    fn laplace(&self, scale: f64) -> f64 {
+        let u = self.random.next_f64() - 0.5;
+        // Keeps the logarithm finite when u is exactly -0.5
+        let tail = (1.0 - 2.0 * u.abs()).max(f64::MIN_POSITIVE);
+        -scale * u.signum() * tail.ln()
+    }
+}
+
+#[cfg(test)]
+mod stats_privacy_tests {
+    use super::*;
+    use crate::domain::services::SeededRandomSource;
+    use chrono::{TimeZone, Utc};
+
+    fn privacy(enabled: bool) -> StatsPrivacy {
+        StatsPrivacy::new(
+            StatsPrivacyConfig {
+                enabled,
+                min_cohort_size: 5,
+                epsilon: 1.0,
+                amount_sensitivity: 100,
+            },
+            Box::new(SeededRandomSource::new(42)),
+        )
+    }
+
+    fn point(hour: u32, value: i64, contributors: i64) -> RollupPoint {
+        RollupPoint {
+            bucket: Utc.with_ymd_and_hms(2026, 1, 1, hour, 0, 0).unwrap(),
+            currency: None,
+            value,
+            contributors,
+        }
+    }
+
+    #[test]
+    fn test_disabled_protection_publishes_points_as_is() {
+        let points = vec![point(0, 1, 1), point(1, 20, 20)];
+
+        assert_eq!(privacy(false).protect(RollupMetric::BidsPlaced, points.clone()), points);
+    }
+
+    #[test]
+    fn test_small_cohorts_are_held_back() {
+        let points = vec![point(0, 4, 4), point(1, 20, 20), point(2, 300, 2)];
+
+        let published = privacy(true).protect(RollupMetric::BidsPlaced, points);
+
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].bucket, point(1, 0, 0).bucket);
+    }
+
+    #[test]
+    fn test_noise_is_centered_on_the_value() {
+        let privacy = privacy(true);
+        let points: Vec<_> = (0..2000).map(|_| point(0, 1000, 1000)).collect();
+
+        let published = privacy.protect(RollupMetric::BidsPlaced, points);
+
+        let mean = published.iter().map(|p| p.value).sum::<i64>() as f64 / published.len() as f64;
+        assert!((mean - 1000.0).abs() < 0.5, "{}", mean);
+        assert!(published.iter().any(|p| p.value != 1000));
+        assert!(published.iter().all(|p| p.value >= 0));
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::domain::models::UserId;
+use crate::domain::services::{RandomSource, SystemClock};
+use crate::infrastructure::config::TrafficCaptureConfig;
+
+/// JSON fields that identify people. Their values are replaced by a stable pseudonym.
+const PERSONAL_FIELDS: [&str; 5] = ["user", "seller", "bidder", "winner", "leader"];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedExchange {
+    pub at: DateTime<Utc>,
+    pub method: String,
+    pub path: String,
+    pub query: String,
+    /// Pseudonym of the calling user, if any
+    pub user: Option<String>,
+    pub status: u16,
+    pub duration_ms: u64,
+    pub request_body: Option<Value>,
+    pub response_body: Option<Value>,
+}
+
+/// Keeps the most recent sampled exchanges in a ring buffer shared by all workers.
+#[derive(Clone)]
+pub struct TrafficRecorder {
+    config: TrafficCaptureConfig,
+    random: Box<dyn RandomSource>,
+    clock: Box<dyn SystemClock>,
+    exchanges: Arc<Mutex<VecDeque<CapturedExchange>>>,
+}
+
+impl TrafficRecorder {
+    pub fn new(
+        config: TrafficCaptureConfig,
+        random: Box<dyn RandomSource>,
+        clock: Box<dyn SystemClock>,
+    ) -> Self {
+        let exchanges = Arc::new(Mutex::new(VecDeque::with_capacity(config.capacity)));
+        Self {
+            config,
+            random,
+            clock,
+            exchanges,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    /// Whether to record this request. Decided once per request, before it is handled.
+    pub fn should_capture(&self, path: &str) -> bool {
+        self.config.enabled
+            && self.config.capacity > 0
+            && !self.config.exclude_paths.iter().any(|p| path.starts_with(p.as_str()))
+            && self.random.next_f64() * 100.0 < self.config.sample_percent
+    }
+
+    pub fn record(&self, exchange: CapturedExchange) {
+        let mut exchanges = self.exchanges.lock().unwrap();
+        while exchanges.len() >= self.config.capacity {
+            exchanges.pop_front();
+        }
+        exchanges.push_back(exchange);
+    }
+
+    /// Captured exchanges, oldest first.
+    pub fn recent(&self) -> Vec<CapturedExchange> {
+        self.exchanges.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// JSON bodies with personal fields pseudonymized. Other bodies are only described.
+    pub fn anonymize_body(&self, body: &[u8]) -> Option<Value> {
+        if body.is_empty() {
+            return None;
+        }
+        if body.len() > self.config.max_body_bytes {
+            return Some(Value::String(format!("<{} bytes, not captured>", body.len())));
+        }
+        match serde_json::from_slice::<Value>(body) {
+            Ok(mut json) => {
+                pseudonymize(&mut json);
+                Some(json)
+            }
+            Err(_) => Some(Value::String(format!("<{} bytes>", body.len()))),
+        }
+    }
+}
+
+pub fn pseudonym(user: &UserId) -> String {
+    pseudonym_of(user.value())
+}
+
+fn pseudonym_of(value: &str) -> String {
+    let hash = format!("{:x}", Sha256::digest(value.as_bytes()));
+    format!("user-{}", &hash[..12])
+}
+
+fn pseudonymize(json: &mut Value) {
+    match json {
+        Value::Object(fields) => {
+            for (key, value) in fields.iter_mut() {
+                match value {
+                    Value::String(s) if PERSONAL_FIELDS.contains(&key.as_str()) => {
+                        *s = pseudonym_of(s);
+                    }
+                    _ => pseudonymize(value),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(pseudonymize),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod traffic_recorder_tests {
+    use super::*;
+    use crate::domain::services::{FixedSystemClock, OsRandomSource};
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn recorder(capacity: usize) -> TrafficRecorder {
+        TrafficRecorder::new(
+            TrafficCaptureConfig {
+                enabled: true,
+                sample_percent: 100.0,
+                capacity,
+                ..TrafficCaptureConfig::default()
+            },
+            Box::new(OsRandomSource),
+            Box::new(FixedSystemClock(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap())),
+        )
+    }
+
+    fn exchange(path: &str) -> CapturedExchange {
+        CapturedExchange {
+            at: Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+            method: "GET".to_string(),
+            path: path.to_string(),
+            query: String::new(),
+            user: None,
+            status: 200,
+            duration_ms: 1,
+            request_body: None,
+            response_body: None,
+        }
+    }
+
+    #[test]
+    fn test_ring_buffer_keeps_most_recent() {
+        let recorder = recorder(2);
+        recorder.record(exchange("/auctions/1"));
+        recorder.record(exchange("/auctions/2"));
+        recorder.record(exchange("/auctions/3"));
+
+        let paths: Vec<String> = recorder.recent().into_iter().map(|e| e.path).collect();
+        assert_eq!(paths, vec!["/auctions/2", "/auctions/3"]);
+    }
+
+    #[test]
+    fn test_personal_fields_are_pseudonymized() {
+        let body = json!({"amount": 10, "bids": [{"bidder": "buyer1", "amount": 10}], "winner": "buyer1"});
+
+        let anonymized = recorder(1)
+            .anonymize_body(body.to_string().as_bytes())
+            .unwrap();
+
+        let alias = pseudonym(&UserId::new("buyer1"));
+        assert_eq!(anonymized["winner"], json!(alias));
+        assert_eq!(anonymized["bids"][0]["bidder"], json!(alias));
+        assert_eq!(anonymized["amount"], json!(10));
+    }
+
+    #[test]
+    fn test_excluded_paths_are_not_captured() {
+        let recorder = recorder(1);
+        assert!(!recorder.should_capture("/admin/traffic"));
+        assert!(recorder.should_capture("/auctions"));
+    }
+}
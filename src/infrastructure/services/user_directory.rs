@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::domain::models::{Error, User, UserId};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::data::UserRepository;
+
+/// Stores the users that call the API, and looks up their display names. Each user is only
+/// written again when they come back with a different role or display name.
+#[derive(Clone)]
+pub struct UserDirectory {
+    repository: Box<dyn UserRepository>,
+    system_clock: Box<dyn SystemClock>,
+    seen: Arc<Mutex<HashMap<UserId, User>>>,
+}
+
+impl UserDirectory {
+    pub fn new(repository: Box<dyn UserRepository>, system_clock: Box<dyn SystemClock>) -> Self {
+        Self {
+            repository,
+            system_clock,
+            seen: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Failing to store a user does not fail the request they made.
+    pub async fn remember(&self, user: &User) {
+        if self.seen.lock().unwrap().get(user.id()) == Some(user) {
+            return;
+        }
+        match self.repository.upsert(user, self.system_clock.now()).await {
+            Ok(()) => {
+                self.seen.lock().unwrap().insert(user.id().clone(), user.clone());
+            }
+            Err(e) => log::error!("Error storing user {}: {:?}", user.id(), e),
+        }
+    }
+
+    /// Drops the user along with their display name. They are stored again should they come back.
+    pub async fn forget(&self, user: &UserId) -> Result<(), Error> {
+        self.seen.lock().unwrap().remove(user);
+        self.repository.remove(user).await
+    }
+
+    /// Users without a known display name are left out, and shown by their ids.
+    pub async fn display_names(&self, users: &[UserId]) -> HashMap<UserId, String> {
+        self.repository.get_display_names(users).await.unwrap_or_else(|e| {
+            log::error!("Error getting display names: {:?}", e);
+            HashMap::new()
+        })
+    }
+}
+
+#[cfg(test)]
+mod user_directory_tests {
+    use super::*;
+    use crate::domain::services::FixedSystemClock;
+    use crate::infrastructure::data::InMemoryUserRepository;
+    use chrono::{TimeZone, Utc};
+
+    fn directory() -> UserDirectory {
+        UserDirectory::new(
+            Box::new(InMemoryUserRepository::default()),
+            Box::new(FixedSystemClock(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap())),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_display_names_of_remembered_users() {
+        let directory = directory();
+        let seller = UserId::new("seller@hotmail.com");
+        let support = UserId::new("support@hotmail.com");
+        directory.remember(&User::new_buyer_or_seller(seller.clone(), Some("Sally Seller"))).await;
+        directory.remember(&User::new_support(support.clone())).await;
+
+        let names = directory
+            .display_names(&[seller.clone(), support, UserId::new("unknown@hotmail.com")])
+            .await;
+
+        assert_eq!(names.len(), 1);
+        assert_eq!(names.get(&seller).map(String::as_str), Some("Sally Seller"));
+    }
+
+    #[tokio::test]
+    async fn test_forgotten_users_have_no_display_name() {
+        let directory = directory();
+        let seller = UserId::new("seller@hotmail.com");
+        let user = User::new_buyer_or_seller(seller.clone(), Some("Sally Seller"));
+        directory.remember(&user).await;
+
+        directory.forget(&seller).await.unwrap();
+        assert!(directory.display_names(std::slice::from_ref(&seller)).await.is_empty());
+
+        directory.remember(&user).await;
+        assert_eq!(directory.display_names(std::slice::from_ref(&seller)).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_display_name_is_kept_without_a_new_one() {
+        let directory = directory();
+        let seller = UserId::new("seller@hotmail.com");
+        directory.remember(&User::new_buyer_or_seller(seller.clone(), Some("Sally Seller"))).await;
+        directory.remember(&User::new_buyer_or_seller(seller.clone(), None::<String>)).await;
+
+        let names = directory.display_names(std::slice::from_ref(&seller)).await;
+
+        assert_eq!(names.get(&seller).map(String::as_str), Some("Sally Seller"));
+    }
+}
@@ -0,0 +1,283 @@
+use async_trait::async_trait;
+use dyn_clone::DynClone;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::domain::models::{Auction, AuctionStatus, Error};
+use crate::domain::services::SystemClock;
+use crate::infrastructure::config::WarehouseExportConfig;
+use crate::infrastructure::data::{save_checkpoint, AuctionEvent, AuctionEventType, AuctionRepository, PgOutbox};
+
+/// Destination of exported rows, one JSON object per row.
+#[async_trait]
+pub trait WarehouseSink: Send + Sync + DynClone {
+    async fn insert(&self, table: &str, rows: &[Value]) -> Result<(), Error>;
+}
+
+dyn_clone::clone_trait_object!(WarehouseSink);
+
+/// Inserts through the ClickHouse HTTP interface, as `JSONEachRow`.
+#[derive(Clone)]
+pub struct ClickHouseSink {
+    client: reqwest::Client,
+    config: WarehouseExportConfig,
+}
+
+impl ClickHouseSink {
+    pub fn new(config: WarehouseExportConfig) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            config,
+        }
+    }
+}
+
+#[async_trait]
+impl WarehouseSink for ClickHouseSink {
+    async fn insert(&self, table: &str, rows: &[Value]) -> Result<(), Error> {
+        let query = format!("INSERT INTO {}.{} FORMAT JSONEachRow", self.config.database, table);
+        let body = rows.iter().map(Value::to_string).collect::<Vec<_>>().join("\n");
+        let mut request = self.client.post(&self.config.url).query(&[("query", query)]).body(body);
+        if let Some(user) = &self.config.user {
+            request = request
+                .header("X-ClickHouse-User", user)
+                .header("X-ClickHouse-Key", self.config.password.clone().unwrap_or_default());
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Repository(format!("Export of {} failed: {}", table, e)))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let message = response.text().await.unwrap_or_default();
+            return Err(Error::Repository(format!(
+                "Export of {} failed with {}: {}",
+                table, status, message
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Keeps inserted rows in process memory, for tests.
+#[derive(Clone, Default)]
+pub struct InMemoryWarehouseSink {
+    rows: Arc<Mutex<Vec<(String, Value)>>>,
+}
+
+impl InMemoryWarehouseSink {
+    pub fn rows(&self, table: &str) -> Vec<Value> {
+        self.rows
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(t, _)| t == table)
+            .map(|(_, row)| row.clone())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl WarehouseSink for InMemoryWarehouseSink {
+    async fn insert(&self, table: &str, rows: &[Value]) -> Result<(), Error> {
+        let mut stored = self.rows.lock().unwrap();
+        stored.extend(rows.iter().map(|row| (table.to_string(), row.clone())));
+        Ok(())
+    }
+}
+
+/// Exported tables, each with its own high-water mark in `projection_checkpoints`
+pub const WAREHOUSE_TABLES: [&str; 3] = ["auctions", "bids", "settlements"];
+
+/// Incrementally copies auction changes from the outbox to an analytics sink, so that reporting
+/// does not run against the OLTP database.
+///
+/// Settlements are exported when an auction is stored as closed. Auctions that close by running
+/// out of time are only stored as closed on their next change.
+#[derive(Clone)]
+pub struct WarehouseExporter {
+    outbox: PgOutbox,
+    repository: Box<dyn AuctionRepository>,
+    sink: Box<dyn WarehouseSink>,
+    system_clock: Box<dyn SystemClock>,
+    config: WarehouseExportConfig,
+}
+
+impl WarehouseExporter {
+    pub fn new(
+        outbox: PgOutbox,
+        repository: Box<dyn AuctionRepository>,
+        sink: Box<dyn WarehouseSink>,
+        system_clock: Box<dyn SystemClock>,
+        config: WarehouseExportConfig,
+    ) -> Self {
+        Self {
+            outbox,
+            repository,
+            sink,
+            system_clock,
+            config,
+        }
+    }
+
+    /// Exports on the configured interval until cancelled. Failed exports are retried on the next run.
+    pub async fn run(&self, shutdown: CancellationToken) {
+        let interval = Duration::from_secs(self.config.interval);
+        while !shutdown.is_cancelled() {
+            for table in WAREHOUSE_TABLES {
+                if let Err(e) = self.export_table(table).await {
+                    log::error!("Warehouse export of {} failed: {}", table, e);
+                }
+            }
+            tokio::select! {
+                _ = shutdown.cancelled() => {}
+                _ = tokio::time::sleep(interval) => {}
+            }
+        }
+    }
+
+    /// Exports everything after the table's high-water mark, a batch at a time.
+    /// Returns the number of rows exported.
+    pub async fn export_table(&self, table: &str) -> Result<usize, Error> {
+        let checkpoint = format!("warehouse_export.{}", table);
+        let mut exported = 0;
+        loop {
+            let position = self.outbox.checkpoint(&checkpoint).await?;
+            let events = self.outbox.read_after(position, self.config.batch_size).await?;
+            let Some(last) = events.last() else {
+                return Ok(exported);
+            };
+
+            let mut rows = Vec::new();
+            for event in &events {
+                rows.extend(self.rows_for(table, event).await?);
+            }
+            if !rows.is_empty() {
+                self.sink.insert(table, &rows).await?;
+            }
+            // The sink is not transactional: rows may be exported again after a failure here
+            let mut conn = self
+                .outbox
+                .pool()
+                .acquire()
+                .await
+                .map_err(|e| Error::Repository(e.to_string()))?;
+            save_checkpoint(&mut conn, &checkpoint, last.id).await?;
+            exported += rows.len();
+        }
+    }
+
+    async fn rows_for(&self, table: &str, event: &AuctionEvent) -> Result<Vec<Value>, Error> {
+        match (table, event.event_type) {
+            ("auctions", AuctionEventType::AuctionCreated | AuctionEventType::AuctionUpdated) => {
+                Ok(vec![auction_row(event)])
+            }
+            ("bids", AuctionEventType::BidAccepted | AuctionEventType::BidRetracted | AuctionEventType::BidVoided) => {
+                Ok(vec![bid_row(event)])
+            }
+            ("settlements", AuctionEventType::AuctionUpdated)
+                if event.payload["status"] == AuctionStatus::Closed.to_string() =>
+            {
+                let auction = self.repository.get_auction(event.auction_id).await?;
+                Ok(auction
+                    .map(|auction| settlement_rows(&auction, event, self.system_clock.now()))
+                    .unwrap_or_default())
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+}
+
+fn auction_row(event: &AuctionEvent) -> Value {
+    json!({
+        "event_id": event.id,
+        "auction_id": event.auction_id.value(),
+        "status": event.payload["status"],
+        "title": event.payload["title"],
+        "currency": event.payload["currency"],
+        "changed_at": event.created_at,
+    })
+}
+
+fn bid_row(event: &AuctionEvent) -> Value {
+    json!({
+        "event_id": event.id,
+        "auction_id": event.auction_id.value(),
+        "bid_id": event.payload["bidId"],
+        "bidder": event.payload["bidder"],
+        "amount_value": event.payload["amountValue"],
+        "amount_currency": event.payload["amountCurrency"],
+        "at": event.payload["at"],
+        "retracted": event.event_type == AuctionEventType::BidRetracted,
+        "voided": event.event_type == AuctionEventType::BidVoided,
+    })
+}
+
+fn settlement_rows(auction: &Auction, event: &AuctionEvent, now: chrono::DateTime<chrono::Utc>) -> Vec<Value> {
+    auction
+        .try_get_charges(now)
+        .into_iter()
+        .map(|(amount, bidder)| {
+            json!({
+                "event_id": event.id,
+                "auction_id": event.auction_id.value(),
+                "bidder": bidder.value(),
+                "amount_value": amount.value(),
+                "amount_currency": amount.currency().to_string(),
+                "settled_at": event.created_at,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod warehouse_export_tests {
+    use super::*;
+    use crate::domain::models::AuctionId;
+    use chrono::{TimeZone, Utc};
+
+    fn event(event_type: AuctionEventType, payload: Value) -> AuctionEvent {
+        AuctionEvent {
+            id: 7,
+            auction_id: AuctionId::new(1),
+            event_type,
+            payload,
+            created_at: Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_bid_rows_mark_retractions_and_voids() {
+        let payload = json!({"bidId": 3, "bidder": "buyer", "amountValue": 10, "amountCurrency": "SEK"});
+
+        let accepted = bid_row(&event(AuctionEventType::BidAccepted, payload.clone()));
+        let retracted = bid_row(&event(AuctionEventType::BidRetracted, payload.clone()));
+        let voided = bid_row(&event(AuctionEventType::BidVoided, payload));
+
+        assert_eq!(accepted["bid_id"], json!(3));
+        assert_eq!(accepted["retracted"], json!(false));
+        assert_eq!(retracted["retracted"], json!(true));
+        assert_eq!(voided["voided"], json!(true));
+        assert_eq!(voided["retracted"], json!(false));
+    }
+
+    #[test]
+    fn test_auction_rows_carry_the_event_position() {
+        let row = auction_row(&event(AuctionEventType::AuctionCreated, json!({"status": "Scheduled"})));
+
+        assert_eq!(row["event_id"], json!(7));
+        assert_eq!(row["auction_id"], json!(1));
+        assert_eq!(row["status"], json!("Scheduled"));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_sink_keeps_rows_per_table() {
+        let sink = InMemoryWarehouseSink::default();
+        sink.insert("bids", &[json!({"bid_id": 1})]).await.unwrap();
+        sink.insert("auctions", &[json!({"auction_id": 1})]).await.unwrap();
+
+        assert_eq!(sink.rows("bids"), vec![json!({"bid_id": 1})]);
+    }
+}
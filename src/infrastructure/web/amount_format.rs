@@ -0,0 +1,62 @@
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{web, Error};
+
+use crate::infrastructure::config::AmountFormat;
+
+tokio::task_local! {
+    /// How amounts are written in the response to the request handled by the current task.
+    pub static CURRENT_AMOUNT_FORMAT: AmountFormat;
+}
+
+/// Middleware writing the amounts of each response in the `web::Data<AmountFormat>` of the app,
+/// as objects when it has none, see [`crate::api::amount_output`].
+pub async fn amount_format<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<B>, Error> {
+    let format = req
+        .app_data::<web::Data<AmountFormat>>()
+        .map(|format| *format.get_ref())
+        .unwrap_or_default();
+    CURRENT_AMOUNT_FORMAT.scope(format, next.call(req)).await
+}
+
+#[cfg(test)]
+mod amount_format_tests {
+    use super::*;
+    use crate::api::amount_output;
+    use crate::domain::models::{Amount, CurrencyCode};
+    use actix_web::middleware::from_fn;
+    use actix_web::test::{call_and_read_body_json, init_service, TestRequest};
+    use actix_web::{App, HttpResponse};
+    use serde::Serialize;
+    use serde_json::{json, Value};
+
+    #[derive(Serialize)]
+    struct PriceModel {
+        #[serde(with = "amount_output")]
+        price: Amount,
+    }
+
+    async fn price() -> HttpResponse {
+        HttpResponse::Ok().json(PriceModel { price: Amount::new(10050, CurrencyCode::SEK) })
+    }
+
+    async fn price_written_in(format: Option<AmountFormat>) -> Value {
+        let mut app = App::new();
+        if let Some(format) = format {
+            app = app.app_data(web::Data::new(format));
+        }
+        let app = init_service(app.wrap(from_fn(amount_format)).default_service(web::to(price))).await;
+        call_and_read_body_json::<_, _, Value>(&app, TestRequest::get().to_request()).await["price"].clone()
+    }
+
+    #[actix_web::test]
+    async fn test_writes_amounts_in_the_format_of_the_app() {
+        assert_eq!(price_written_in(Some(AmountFormat::Compact)).await, json!("SEK100.50"));
+        assert_eq!(price_written_in(Some(AmountFormat::Object)).await, json!({ "value": "100.50", "currency": "SEK" }));
+        assert_eq!(price_written_in(None).await, json!({ "value": "100.50", "currency": "SEK" }));
+    }
+}
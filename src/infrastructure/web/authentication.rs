@@ -0,0 +1,21 @@
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{Error, HttpResponse};
+use serde_json::json;
+
+use crate::infrastructure::jwt_payload_handling;
+
+/// Rejects requests whose user header is malformed or carries an invalid user id with a 401,
+/// before any handler sees them.
+pub async fn authentication<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    if let Err(message) = jwt_payload_handling::validate_request(req.request()) {
+        log::warn!("Rejecting {} {}: {}", req.method(), req.path(), message);
+        let response = HttpResponse::Unauthorized().json(json!([{ "code": "UNAUTHORIZED", "message": message }]));
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+    Ok(next.call(req).await?.map_into_left_body())
+}
@@ -0,0 +1,124 @@
+use std::net::SocketAddr;
+
+use actix_web::{http::header, web, HttpRequest};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::domain::models::{BidChannel, BidMetadata, User};
+use crate::infrastructure::config::ClientAddressConfig;
+use crate::infrastructure::jwt_payload_handling;
+
+const X_BID_CHANNEL: &str = "X-Bid-Channel";
+const MAX_USER_AGENT_LENGTH: usize = 512;
+
+/// Captures where a bid came from. The client address is only kept as a hash.
+pub fn bid_metadata_from_request(req: &HttpRequest) -> BidMetadata {
+    // Clerks are support users bidding for someone on the phone or in the room, which clients
+    // must not be able to claim to be
+    let channel = match jwt_payload_handling::user_from_request(req) {
+        Some(User::Support { .. }) => Some(BidChannel::Clerk),
+        _ => req
+            .headers()
+            .get(X_BID_CHANNEL)
+            .and_then(|header| header.to_str().ok())
+            .and_then(|s| s.parse().ok())
+            .filter(|channel| *channel != BidChannel::Clerk),
+    };
+    let user_agent = req
+        .headers()
+        .get(header::USER_AGENT)
+        .and_then(|header| header.to_str().ok())
+        .map(|s| s.chars().take(MAX_USER_AGENT_LENGTH).collect());
+    BidMetadata {
+        channel,
+        user_agent,
+        ip_hash: ip_hash_from_request(req),
+        screening: None,
+    }
+}
+
+/// Keyed hash of the address the request came from, so that requests can be correlated without
+/// keeping addresses. Forwarding headers are only believed from a trusted proxy, see
+/// [`ClientAddressConfig`].
+pub fn ip_hash_from_request(req: &HttpRequest) -> Option<String> {
+    let peer = req.peer_addr()?;
+    let config = req.app_data::<web::Data<ClientAddressConfig>>();
+    let key = config.map_or("", |config| config.ip_hash_key.as_str());
+    let from_trusted_proxy = config.is_some_and(|config| config.trusted_proxies.contains(&peer.ip()));
+    if from_trusted_proxy {
+        req.connection_info().realip_remote_addr().map(|addr| hash_ip(key, addr))
+    } else {
+        Some(hash_ip(key, &peer.to_string()))
+    }
+}
+
+fn hash_ip(key: &str, addr: &str) -> String {
+    // The peer address includes the port, which would make the hash useless for correlation
+    let ip = addr
+        .parse::<SocketAddr>()
+        .map(|a| a.ip().to_string())
+        .unwrap_or_else(|_| addr.to_string());
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC takes keys of any length");
+    mac.update(ip.as_bytes());
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod bid_source_tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use base64::prelude::*;
+    use crate::infrastructure::jwt_payload_handling::X_JWT_PAYLOAD;
+
+    #[test]
+    fn test_ip_hash_ignores_port() {
+        assert_eq!(hash_ip("key", "10.0.0.1:1234"), hash_ip("key", "10.0.0.1:5678"));
+        assert_eq!(hash_ip("key", "10.0.0.1:1234"), hash_ip("key", "10.0.0.1"));
+    }
+
+    #[test]
+    fn test_ip_hash_depends_on_the_key() {
+        assert_ne!(hash_ip("one key", "10.0.0.1"), hash_ip("another key", "10.0.0.1"));
+    }
+
+    #[test]
+    fn test_metadata_from_request() {
+        let req = TestRequest::default()
+            .insert_header((X_BID_CHANNEL, "Web"))
+            .insert_header((header::USER_AGENT, "test-agent"))
+            .to_http_request();
+        let metadata = bid_metadata_from_request(&req);
+        assert_eq!(metadata.channel, Some(BidChannel::Web));
+        assert_eq!(metadata.user_agent, Some("test-agent".to_string()));
+    }
+
+    #[test]
+    fn test_only_support_users_bid_as_clerks() {
+        let req = TestRequest::default()
+            .insert_header((X_BID_CHANNEL, "Clerk"))
+            .to_http_request();
+        assert_eq!(bid_metadata_from_request(&req).channel, None);
+
+        let support = BASE64_STANDARD.encode(r#"{"sub":"s1","name":"support","u_typ":"1"}"#);
+        let req = TestRequest::default()
+            .insert_header((X_JWT_PAYLOAD, support))
+            .to_http_request();
+        assert_eq!(bid_metadata_from_request(&req).channel, Some(BidChannel::Clerk));
+    }
+
+    #[test]
+    fn test_forwarded_for_is_only_believed_from_trusted_proxies() {
+        let proxy: SocketAddr = "10.0.0.1:1234".parse().unwrap();
+        let forwarded = |config: ClientAddressConfig| {
+            let req = TestRequest::default()
+                .peer_addr(proxy)
+                .insert_header(("X-Forwarded-For", "192.0.2.7"))
+                .app_data(web::Data::new(config))
+                .to_http_request();
+            ip_hash_from_request(&req)
+        };
+        assert_eq!(forwarded(ClientAddressConfig::default()), Some(hash_ip("", "10.0.0.1")));
+        let trusted = ClientAddressConfig { trusted_proxies: vec![proxy.ip()], ip_hash_key: "key".to_string() };
+        assert_eq!(forwarded(trusted), Some(hash_ip("key", "192.0.2.7")));
+    }
+}
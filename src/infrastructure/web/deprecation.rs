@@ -0,0 +1,135 @@
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::Error;
+use chrono::{DateTime, TimeZone, Utc};
+
+/// A route, or a field in the responses of a route, that clients should stop relying on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deprecation {
+    pub method: &'static str,
+    /// Route pattern as registered, e.g. `/auctions/{auction_id}`
+    pub path: &'static str,
+    /// `None` when the whole route is deprecated
+    pub field: Option<&'static str>,
+    pub deprecated_at: DateTime<Utc>,
+    pub sunset: Option<DateTime<Utc>>,
+    pub description: &'static str,
+}
+
+impl Deprecation {
+    fn applies_to_route(&self, method: &str, pattern: &str) -> bool {
+        self.field.is_none() && self.method.eq_ignore_ascii_case(method) && self.path == pattern
+    }
+}
+
+/// Everything currently deprecated in the API. Add an entry here when deprecating a route or field.
+pub fn api_deprecations() -> Vec<Deprecation> {
+    let winners_introduced = Utc.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap();
+    ["/auctions/{auction_id}"]
+        .into_iter()
+        .flat_map(|path| {
+            [
+                Deprecation {
+                    method: "GET",
+                    path,
+                    field: Some("price"),
+                    deprecated_at: winners_introduced,
+                    sunset: None,
+                    description: "Use the price of the entries in winners, which covers multi-unit auctions",
+                },
+                Deprecation {
+                    method: "GET",
+                    path,
+                    field: Some("winner"),
+                    deprecated_at: winners_introduced,
+                    sunset: None,
+                    description: "Use the winner of the entries in winners, which covers multi-unit auctions",
+                },
+            ]
+        })
+        .collect()
+}
+
+/// Response headers for a request to a deprecated route: `Deprecation` (RFC 9745) and, when the
+/// removal date is known, `Sunset` (RFC 8594).
+pub fn deprecation_headers(
+    deprecations: &[Deprecation],
+    method: &str,
+    pattern: &str,
+) -> Vec<(HeaderName, HeaderValue)> {
+    let Some(deprecation) = deprecations
+        .iter()
+        .find(|d| d.applies_to_route(method, pattern))
+    else {
+        return Vec::new();
+    };
+    let mut headers = vec![(
+        HeaderName::from_static("deprecation"),
+        HeaderValue::from_str(&format!("@{}", deprecation.deprecated_at.timestamp())).unwrap(),
+    )];
+    if let Some(sunset) = deprecation.sunset {
+        headers.push((
+            HeaderName::from_static("sunset"),
+            HeaderValue::from_str(&sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string()).unwrap(),
+        ));
+    }
+    headers
+}
+
+pub async fn deprecation<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<B>, Error> {
+    let mut res = next.call(req).await?;
+    let headers = match res.request().match_pattern() {
+        Some(pattern) => deprecation_headers(
+            &api_deprecations(),
+            res.request().method().as_str(),
+            &pattern,
+        ),
+        None => Vec::new(),
+    };
+    for (name, value) in headers {
+        res.headers_mut().insert(name, value);
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod deprecation_tests {
+    use super::*;
+
+    fn deprecated_route() -> Deprecation {
+        Deprecation {
+            method: "POST",
+            path: "/auction",
+            field: None,
+            deprecated_at: Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+            sunset: Some(Utc.with_ymd_and_hms(2026, 7, 1, 0, 0, 0).unwrap()),
+            description: "Use POST /auctions",
+        }
+    }
+
+    #[test]
+    fn test_deprecated_route_gets_headers() {
+        let headers = deprecation_headers(&[deprecated_route()], "POST", "/auction");
+        assert_eq!(
+            headers,
+            vec![
+                (HeaderName::from_static("deprecation"), HeaderValue::from_static("@1767225600")),
+                (
+                    HeaderName::from_static("sunset"),
+                    HeaderValue::from_static("Wed, 01 Jul 2026 00:00:00 GMT")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_other_routes_and_deprecated_fields_get_no_headers() {
+        assert!(deprecation_headers(&[deprecated_route()], "GET", "/auction").is_empty());
+        assert!(deprecation_headers(&api_deprecations(), "GET", "/auctions").is_empty());
+    }
+}
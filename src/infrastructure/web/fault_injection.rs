@@ -0,0 +1,35 @@
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::ConnectionType;
+use actix_web::middleware::Next;
+use actix_web::{web, Error, HttpResponse};
+use serde_json::json;
+
+use crate::infrastructure::services::FaultInjector;
+
+/// Middleware injecting latency, 500s or closed connections, see `FaultInjectionConfig`.
+/// A dropped request gets an empty 502 response on a connection that is closed afterwards.
+pub async fn fault_injection<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    let faults = match req.app_data::<web::Data<FaultInjector>>() {
+        Some(faults) if faults.applies_to(req.path()) => faults.clone(),
+        _ => return Ok(next.call(req).await?.map_into_left_body()),
+    };
+    if faults.delay_request() {
+        tokio::time::sleep(faults.latency()).await;
+    }
+    if faults.drop_request() {
+        log::warn!("Fault injection: dropping {} {}", req.method(), req.path());
+        let mut response = HttpResponse::BadGateway().finish();
+        response.head_mut().set_connection_type(ConnectionType::Close);
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+    if faults.fail_request() {
+        log::warn!("Fault injection: failing {} {}", req.method(), req.path());
+        let response = HttpResponse::InternalServerError().json(json!([{ "code": "INJECTED_FAULT", "message": "Injected fault" }]));
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+    Ok(next.call(req).await?.map_into_left_body())
+}
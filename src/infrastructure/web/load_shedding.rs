@@ -0,0 +1,190 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::{header, Method};
+use actix_web::middleware::Next;
+use actix_web::{web, Error, HttpResponse};
+use sqlx::PgPool;
+
+use crate::infrastructure::config::LoadSheddingConfig;
+
+/// The latency average halves every so long without a sample, see [`LoadShedder::latency_ms`].
+const LATENCY_HALF_LIFE: Duration = Duration::from_secs(5);
+
+/// Tracks load signals (requests in flight, response latency, connection pool usage) so that
+/// expensive read endpoints can be turned away while bid placement stays responsive.
+pub struct LoadShedder {
+    config: LoadSheddingConfig,
+    pool: Option<PgPool>,
+    max_connections: u32,
+    in_flight: AtomicUsize,
+    latency_ewma_ms: AtomicU64,
+    started: Instant,
+    // milliseconds since started
+    last_sample_ms: AtomicU64,
+}
+
+impl LoadShedder {
+    pub fn new(config: LoadSheddingConfig, pool: Option<PgPool>, max_connections: u32) -> Self {
+        Self {
+            config,
+            pool,
+            max_connections,
+            in_flight: AtomicUsize::new(0),
+            latency_ewma_ms: AtomicU64::new(0),
+            started: Instant::now(),
+            last_sample_ms: AtomicU64::new(0),
+        }
+    }
+
+    fn is_low_priority(&self, req: &ServiceRequest) -> bool {
+        *req.method() == Method::GET && self.config.shed_paths.iter().any(|path| path == req.path())
+    }
+
+    fn is_overloaded(&self, now: Instant) -> bool {
+        self.in_flight.load(Ordering::Relaxed) >= self.config.max_in_flight
+            || self.latency_ms(now) >= self.config.max_latency_ms
+            || self.pool_saturation() >= self.config.max_pool_saturation
+    }
+
+    fn pool_saturation(&self) -> f64 {
+        match &self.pool {
+            Some(pool) if self.max_connections > 0 => {
+                let busy = (pool.size() as usize).saturating_sub(pool.num_idle());
+                busy as f64 / self.max_connections as f64
+            }
+            _ => 0.0,
+        }
+    }
+
+    fn millis_since_started(&self, now: Instant) -> u64 {
+        now.saturating_duration_since(self.started).as_millis() as u64
+    }
+
+    /// The latency average as of `now`, decayed by the time since the last sample. Shed requests
+    /// are not sampled, so without decaying, shedding every read would keep it up for good.
+    fn latency_ms(&self, now: Instant) -> u64 {
+        let ewma = self.latency_ewma_ms.load(Ordering::Relaxed);
+        let idle = self.millis_since_started(now).saturating_sub(self.last_sample_ms.load(Ordering::Relaxed));
+        decayed(ewma, idle)
+    }
+
+    fn record_latency(&self, elapsed: Duration, now: Instant) {
+        let sample = elapsed.as_millis() as u64;
+        let now_ms = self.millis_since_started(now);
+        let last_ms = self.last_sample_ms.swap(now_ms, Ordering::Relaxed);
+        let _ = self
+            .latency_ewma_ms
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |ewma| {
+                Some((decayed(ewma, now_ms.saturating_sub(last_ms)) * 7 + sample) / 8)
+            });
+    }
+}
+
+/// `ewma` halved for every [`LATENCY_HALF_LIFE`] in `idle_ms`.
+fn decayed(ewma: u64, idle_ms: u64) -> u64 {
+    let half_lives = idle_ms as f64 / LATENCY_HALF_LIFE.as_millis() as f64;
+    (ewma as f64 * 0.5f64.powf(half_lives)) as u64
+}
+
+/// Long polls wait for bids by design, so the time they take says nothing about the load.
+fn is_long_poll(path: &str) -> bool {
+    path.ends_with("/bids/poll")
+}
+
+struct InFlight<'a>(&'a AtomicUsize);
+
+impl<'a> InFlight<'a> {
+    fn enter(counter: &'a AtomicUsize) -> Self {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Self(counter)
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Middleware answering 503 with `Retry-After` for low priority requests while overloaded.
+pub async fn load_shedding<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    let shedder = match req.app_data::<web::Data<LoadShedder>>() {
+        Some(shedder) if shedder.config.enabled => shedder.clone(),
+        _ => return Ok(next.call(req).await?.map_into_left_body()),
+    };
+    if shedder.is_low_priority(&req) && shedder.is_overloaded(Instant::now()) {
+        log::warn!("Shedding {} {} under load", req.method(), req.path());
+        let response = HttpResponse::ServiceUnavailable()
+            .insert_header((header::RETRY_AFTER, shedder.config.retry_after.to_string()))
+            .json("Service is under heavy load, please retry later");
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+    let sampled = !is_long_poll(req.path());
+    let _in_flight = InFlight::enter(&shedder.in_flight);
+    let started = Instant::now();
+    let res = next.call(req).await;
+    if sampled {
+        shedder.record_latency(started.elapsed(), Instant::now());
+    }
+    Ok(res?.map_into_left_body())
+}
+
+#[cfg(test)]
+mod load_shedding_tests {
+    use super::*;
+    use actix_web::middleware::from_fn;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::App;
+
+    fn shedder() -> LoadShedder {
+        LoadShedder::new(LoadSheddingConfig { enabled: true, max_latency_ms: 1000, ..LoadSheddingConfig::default() }, None, 0)
+    }
+
+    #[test]
+    fn test_slow_responses_shed_until_the_latency_decays() {
+        let shedder = shedder();
+        let now = shedder.started;
+        for _ in 0..20 {
+            shedder.record_latency(Duration::from_millis(4000), now);
+        }
+        assert!(shedder.is_overloaded(now));
+
+        // Only shed reads came in since, so no sample brings the latency down
+        assert!(shedder.is_overloaded(now + LATENCY_HALF_LIFE));
+        assert!(!shedder.is_overloaded(now + LATENCY_HALF_LIFE * 3));
+    }
+
+    #[test]
+    fn test_latency_halves_every_half_life() {
+        assert_eq!(decayed(800, 0), 800);
+        assert_eq!(decayed(800, LATENCY_HALF_LIFE.as_millis() as u64), 400);
+        assert_eq!(decayed(800, 2 * LATENCY_HALF_LIFE.as_millis() as u64), 200);
+    }
+
+    #[actix_web::test]
+    async fn test_long_polls_are_not_sampled() {
+        let shedder = web::Data::new(shedder());
+        let app = init_service(
+            App::new()
+                .app_data(shedder.clone())
+                .wrap(from_fn(load_shedding))
+                .default_service(web::to(|| async {
+                    tokio::time::sleep(Duration::from_millis(80)).await;
+                    HttpResponse::Ok().finish()
+                })),
+        )
+        .await;
+
+        call_service(&app, TestRequest::get().uri("/auctions/1/bids/poll").to_request()).await;
+        assert_eq!(shedder.latency_ewma_ms.load(Ordering::Relaxed), 0);
+
+        call_service(&app, TestRequest::get().uri("/auctions/1/bids").to_request()).await;
+        assert!(shedder.latency_ewma_ms.load(Ordering::Relaxed) > 0);
+    }
+}
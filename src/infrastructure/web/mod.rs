@@ -1,3 +1,21 @@
+pub mod amount_format;
+pub mod authentication;
+pub mod bid_source;
+pub mod deprecation;
+pub mod fault_injection;
+pub mod load_shedding;
+pub mod request_deadline;
+pub mod traffic_capture;
 pub mod user_context;
+pub mod user_registration;
 
+pub use amount_format::*;
+pub use authentication::*;
+pub use bid_source::*;
+pub use deprecation::*;
+pub use fault_injection::*;
+pub use load_shedding::*;
+pub use request_deadline::*;
+pub use traffic_capture::*;
 pub use user_context::*;
+pub use user_registration::*;
@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::HeaderMap;
+use actix_web::middleware::Next;
+use actix_web::{web, Error, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+use crate::infrastructure::config::RequestDeadlineConfig;
+use crate::infrastructure::services::{RequestDeadline, CURRENT_DEADLINE};
+
+const X_REQUEST_TIMEOUT: &str = "X-Request-Timeout";
+const X_REQUEST_DEADLINE: &str = "X-Request-Deadline";
+
+/// Time budget asked for with `X-Request-Timeout` (milliseconds) or `X-Request-Deadline`
+/// (RFC 3339), capped by the configured maximum.
+pub fn requested_timeout(
+    headers: &HeaderMap,
+    config: &RequestDeadlineConfig,
+    now: DateTime<Utc>,
+) -> Option<Duration> {
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+    let timeout_ms = header(X_REQUEST_TIMEOUT)
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .or_else(|| {
+            header(X_REQUEST_DEADLINE)
+                .and_then(|value| DateTime::parse_from_rfc3339(value.trim()).ok())
+                .map(|deadline| (deadline.with_timezone(&Utc) - now).num_milliseconds().max(0) as u64)
+        })
+        .or(Some(config.default_timeout_ms).filter(|ms| *ms > 0))?;
+    Some(Duration::from_millis(timeout_ms.min(config.max_timeout_ms)))
+}
+
+/// Middleware running the request within its deadline. Repository calls made while handling it
+/// share the deadline; when it runs out the client gets a 504 saying how far the request got.
+pub async fn request_deadline<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    let timeout = match req.app_data::<web::Data<RequestDeadlineConfig>>() {
+        Some(config) if config.enabled => requested_timeout(req.headers(), config, Utc::now()),
+        _ => None,
+    };
+    let Some(timeout) = timeout else {
+        return Ok(next.call(req).await?.map_into_left_body());
+    };
+
+    let deadline = RequestDeadline::new(timeout);
+    let http_req = req.request().clone();
+    let result = CURRENT_DEADLINE
+        .scope(deadline.clone(), tokio::time::timeout(timeout, next.call(req)))
+        .await;
+    match result {
+        Ok(result) if !deadline.is_cancelled() => Ok(result?.map_into_left_body()),
+        _ => {
+            deadline.cancel();
+            log::warn!("Request deadline exceeded for {} {}", http_req.method(), http_req.path());
+            let response = HttpResponse::GatewayTimeout().json(json!({
+                "code": "DEADLINE_EXCEEDED",
+                "error": "Request deadline exceeded",
+                "diagnostics": deadline.diagnostics(),
+            }));
+            Ok(ServiceResponse::new(http_req, response).map_into_right_body())
+        }
+    }
+}
+
+#[cfg(test)]
+mod request_deadline_tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    fn timeout_of(req: TestRequest) -> Option<Duration> {
+        let now = DateTime::parse_from_rfc3339("2026-01-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        requested_timeout(req.to_http_request().headers(), &RequestDeadlineConfig::default(), now)
+    }
+
+    #[test]
+    fn test_timeout_header_in_milliseconds() {
+        let req = TestRequest::default().insert_header((X_REQUEST_TIMEOUT, "250"));
+        assert_eq!(timeout_of(req), Some(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn test_deadline_header_is_relative_to_now() {
+        let req = TestRequest::default().insert_header((X_REQUEST_DEADLINE, "2026-01-01T12:00:02Z"));
+        assert_eq!(timeout_of(req), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_timeout_is_capped_and_optional() {
+        let req = TestRequest::default().insert_header((X_REQUEST_TIMEOUT, "3600000"));
+        assert_eq!(timeout_of(req), Some(Duration::from_millis(60_000)));
+        assert_eq!(timeout_of(TestRequest::default()), None);
+    }
+}
@@ -0,0 +1,55 @@
+use std::time::Instant;
+
+use actix_web::body::{to_bytes, BoxBody, EitherBody, MessageBody};
+use actix_web::dev::{Payload, ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{error, web, Error};
+
+use crate::infrastructure::jwt_payload_handling;
+use crate::infrastructure::services::{pseudonym, CapturedExchange, TrafficRecorder};
+
+/// Middleware recording a sample of anonymized exchanges, see `TrafficCaptureConfig`.
+/// Headers are never recorded; the caller is only kept as a pseudonym.
+pub async fn traffic_capture<B: MessageBody>(
+    mut req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    let recorder = match req.app_data::<web::Data<TrafficRecorder>>() {
+        Some(recorder) if recorder.should_capture(req.path()) => recorder.clone(),
+        _ => return Ok(next.call(req).await?.map_into_left_body()),
+    };
+    let started = Instant::now();
+    let at = recorder.now();
+    let method = req.method().to_string();
+    let path = req.path().to_string();
+    let query = req.query_string().to_string();
+    let user = jwt_payload_handling::from_request(req.request()).map(|user| pseudonym(&user));
+
+    // Buffer the request body and hand it back to the handlers
+    let request_body = req.extract::<web::Bytes>().await?;
+    req.set_payload(Payload::from(request_body.clone()));
+
+    let res = next.call(req).await?;
+    let status = res.status().as_u16();
+    let (http_req, res) = res.into_parts();
+    let (res, body) = res.into_parts();
+    let response_body = to_bytes(body).await.map_err(|e| {
+        let e: Box<dyn std::error::Error> = e.into();
+        error::ErrorInternalServerError(e.to_string())
+    })?;
+
+    recorder.record(CapturedExchange {
+        at,
+        method,
+        path,
+        query,
+        user,
+        status,
+        duration_ms: started.elapsed().as_millis() as u64,
+        request_body: recorder.anonymize_body(&request_body),
+        response_body: recorder.anonymize_body(&response_body),
+    });
+
+    let res = res.set_body(EitherBody::right(BoxBody::new(response_body)));
+    Ok(ServiceResponse::new(http_req, res))
+}
@@ -2,20 +2,68 @@ pub mod jwt_payload_handling {
     use actix_web::HttpRequest;
     use base64::prelude::*;
     use serde::{Deserialize, Serialize};
-    use crate::domain::models::UserId;
+    use crate::domain::models::{User, UserId};
 
-    const X_JWT_PAYLOAD: &str = "X-JWT-PAYLOAD";
+    /// Header the gateway puts the verified token payload in.
+    pub const X_JWT_PAYLOAD: &str = "X-JWT-PAYLOAD";
+    const SUPPORT_USER_TYPE: &str = "1";
+    const BUYER_OR_SELLER_USER_TYPE: &str = "0";
+    /// The id of the caller, for operations open to any user. Operations that depend on the
+    /// role of the caller take the [`User`] from [`user_from_request`].
     pub fn from_request(req: &HttpRequest) -> Option<UserId> {
-        let user_id = req
-            .headers()
+        user_from_request(req).map(|user| user.id().clone())
+    }
+    /// Requests without the header are anonymous. A header that does not carry a valid user id
+    /// is an error, rather than a reason to treat the request as anonymous.
+    pub fn validate_request(req: &HttpRequest) -> Result<(), String> {
+        let Some(header) = req.headers().get(X_JWT_PAYLOAD) else {
+            return Ok(());
+        };
+        let payload = header
+            .to_str()
+            .map_err(|e| e.to_string())
+            .and_then(|s| decode_jwt_payload(s).map_err(|e| e.to_string()))
+            .map_err(|e| format!("Invalid {} header: {}", X_JWT_PAYLOAD, e))?;
+        let name = payload
+            .name
+            .ok_or_else(|| format!("Invalid {} header: missing name", X_JWT_PAYLOAD))?;
+        UserId::parse(&name).map_err(|e| e.to_string())?;
+        if let Some(display_name) = payload.display_name {
+            User::parse_display_name(&display_name).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+    pub fn user_from_request(req: &HttpRequest) -> Option<User> {
+        req.headers()
             .get(X_JWT_PAYLOAD)
             .and_then(|header| header.to_str().ok())
             .and_then(|s| decode_jwt_payload(s).ok())
-            .and_then(|payload| match payload.name {
-                Some(name) => Some(UserId::new(name)),
-                None => None,
-            });
-        user_id
+            .and_then(user_from_payload)
+    }
+    pub fn user_from_payload(payload: JwtPayload) -> Option<User> {
+        let id = UserId::parse(&payload.name?).ok()?;
+        match payload.u_typ.as_deref() {
+            Some(SUPPORT_USER_TYPE) => Some(User::new_support(id)),
+            _ => {
+                let display_name = payload.display_name.and_then(|name| User::parse_display_name(&name).ok());
+                Some(User::new_buyer_or_seller(id, display_name))
+            }
+        }
+    }
+    /// The `X-JWT-PAYLOAD` header value the gateway would send for `user`, for scripts and tools
+    /// that call the API directly.
+    pub fn encode_jwt_payload(user: &User) -> String {
+        let (u_typ, display_name) = match user {
+            User::Support { .. } => (SUPPORT_USER_TYPE, None),
+            User::BuyerOrSeller { name, .. } => (BUYER_OR_SELLER_USER_TYPE, name.clone()),
+        };
+        let payload = JwtPayload {
+            sub: Some(user.id().to_string()),
+            name: Some(user.id().to_string()),
+            u_typ: Some(u_typ.to_string()),
+            display_name,
+        };
+        BASE64_STANDARD.encode(serde_json::to_string(&payload).expect("a token payload serializes"))
     }
     pub fn decode_jwt_payload(payload: &str) -> Result<JwtPayload, Box<dyn std::error::Error>> {
         log::info!("Decoding JWT payload: {}", payload);
@@ -36,6 +84,10 @@ pub mod jwt_payload_handling {
 
         #[serde(rename = "u_typ")]
         pub u_typ: Option<String>,
+
+        /// How the user is shown to others, rather than by their id.
+        #[serde(rename = "display_name", default, skip_serializing_if = "Option::is_none")]
+        pub display_name: Option<String>,
     }
 
     #[cfg(test)]
@@ -71,6 +123,54 @@ pub mod jwt_payload_handling {
             assert_eq!(payload.u_typ, Some("0".to_string()));
         }
         #[test]
+        fn test_encoded_payload_decodes_to_the_same_user() {
+            for user in [
+                User::new_support(UserId::new("support@hotmail.com")),
+                User::new_buyer_or_seller(UserId::new("buyer1@hotmail.com"), None::<String>),
+                User::new_buyer_or_seller(UserId::new("seller1@hotmail.com"), Some("Sally Seller")),
+            ] {
+                let payload = decode_jwt_payload(&encode_jwt_payload(&user)).unwrap();
+                assert_eq!(user_from_payload(payload), Some(user));
+            }
+        }
+        #[test]
+        fn test_support_user_type() {
+            let json = r#"{"sub":"a3","name":"support@hotmail.com","u_typ":"1"}"#;
+            let payload = decode_jwt_payload(&BASE64_STANDARD.encode(json.as_bytes())).unwrap();
+            let user = user_from_payload(payload).unwrap();
+            assert_eq!(user, User::new_support(UserId::new("support@hotmail.com")));
+        }
+        #[test]
+        fn test_buyer_or_seller_user_type() {
+            let token = get_token("a2", "buyer1@hotmail.com");
+            let user = user_from_payload(decode_jwt_payload(&token).unwrap()).unwrap();
+            assert!(matches!(user, User::BuyerOrSeller { .. }));
+            assert_eq!(user.id().value(), "buyer1@hotmail.com");
+        }
+        #[test]
+        fn test_user_ids_are_validated_and_canonical() {
+            let token = get_token("a2", "Buyer1@Hotmail.com");
+            let user = user_from_payload(decode_jwt_payload(&token).unwrap()).unwrap();
+            assert_eq!(user.id().value(), "buyer1@hotmail.com");
+
+            let token = get_token("a2", "not a user id");
+            assert!(user_from_payload(decode_jwt_payload(&token).unwrap()).is_none());
+        }
+        #[test]
+        fn test_requests_with_invalid_display_names_are_rejected() {
+            let request = |json: &str| {
+                actix_web::test::TestRequest::default()
+                    .insert_header((X_JWT_PAYLOAD, BASE64_STANDARD.encode(json.as_bytes())))
+                    .to_http_request()
+            };
+            let valid = request(r#"{"sub":"a2","name":"buyer1@hotmail.com","u_typ":"0","display_name":"Bob"}"#);
+            assert!(validate_request(&valid).is_ok());
+            let invalid = request(r#"{"sub":"a2","name":"buyer1@hotmail.com","u_typ":"0","display_name":"Bob\u0007"}"#);
+            assert!(validate_request(&invalid).is_err());
+            let empty = request(r#"{"sub":"a2","name":"buyer1@hotmail.com","u_typ":"0","display_name":" "}"#);
+            assert!(validate_request(&empty).is_err());
+        }
+        #[test]
         fn test_buyer1() {
             let token = get_token("a2", "buyer1@hotmail.com");
             let payload = decode_jwt_payload(&token).unwrap();
@@ -97,7 +197,7 @@ mod claims_principal_handling {
             .and_then(|header| header.to_str().ok())
             .and_then(|s| decode_jwt_payload(s).ok())
             .and_then(get_name_claim_value)
-            .and_then(|name| Some(UserId::new(name)));
+            .and_then(|name| UserId::parse(&name).ok());
         user_id
     }
 
@@ -0,0 +1,21 @@
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{web, Error};
+
+use crate::infrastructure::jwt_payload_handling;
+use crate::infrastructure::services::UserDirectory;
+
+/// Middleware storing the caller in the users table, so that their display name can be shown
+/// in place of their id.
+pub async fn user_registration<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<B>, Error> {
+    if let Some(directory) = req.app_data::<web::Data<UserDirectory>>() {
+        if let Some(user) = jwt_payload_handling::user_from_request(req.request()) {
+            directory.remember(&user).await;
+        }
+    }
+    next.call(req).await
+}
@@ -1,19 +1,173 @@
 // src/main.rs
-use actix_web::{App, HttpServer, middleware::Logger, web};
+use actix_web::{App, HttpServer, middleware::{from_fn, Logger}, web};
 use dotenv::dotenv;
 
 use auctions_api::{
-    domain::services::{RealSystemClock, SystemClock}, infrastructure::{
-        data::{create_pg_pool, migrations::run_migrations, PgAuctionRepository},
+    api::messages::localize_errors,
+    domain::services::{CurrencyConverter, FixedRateCurrencyConverter, FixedSystemClock, OsRandomSource, RandomSource, RealSystemClock, SeededRandomSource, SystemClock}, infrastructure::{
+        data::{check_schema_compatibility, AuctionResultRepository, InMemoryAuctionResultRepository, PgAuctionResultRepository, SettlementRepository, InMemorySettlementRepository, PgSettlementRepository, listen_for_auction_events, create_pg_pool, migrations::{rollback_last_migration, run_migrations, ROLLBACK_LAST_MIGRATION_FLAG}, CachingAuctionRepository, DeadlineAuctionRepository, EventSourcedAuctionRepository, FaultInjectingAuctionRepository, InMemoryAuctionRepository, InMemoryAuditRepository, InMemoryCurrencyRepository, InMemoryDepositRepository, PgAuctionRepository, InMemoryRegistrationRepository, InMemoryRollupRepository, PgAuditRepository, PgCurrencyRepository, PgDepositRepository, PgRegistrationRepository, PgRollupRepository, InMemoryUserRepository, PgUserRepository, create_sqlite_pool, run_sqlite_migrations, PoolSettings, SqliteAuctionRepository, SqliteCurrencyRepository, InMemoryJobRepository, JobRepository, PgJobRepository, SqliteAuctionResultRepository, SqliteAuditRepository, SqliteDepositRepository, SqliteRegistrationRepository, SqliteSettlementRepository, SqliteUserRepository},
         services::{
-            CreateAuctionCommandHandler, CreateBidCommandHandler, 
-            DefaultCreateAuctionCommandHandler,
-            DefaultCreateBidCommandHandler,
+            AdminAuctionCommandHandler, AnonymizeUserCommandHandler, AuctionResultRecorder, SettlementRecorder, CancelAuctionCommandHandler, CreateAuctionCommandHandler, CreateBidCommandHandler,
+            DefaultAdminAuctionCommandHandler, DefaultAnonymizeUserCommandHandler, DefaultCancelAuctionCommandHandler, DefaultCreateAuctionCommandHandler,
+            DefaultExtendAuctionCommandHandler, DefaultPublishAuctionCommandHandler, ExtendAuctionCommandHandler, PublishAuctionCommandHandler,
+            BidEvents, DefaultCreateBidCommandHandler, FaultInjector, DefaultPatchAuctionCommandHandler, HeuristicBidScreeningService,
+            DefaultRecordDepositCommandHandler, DefaultRegistrationCommandHandler, DefaultRetractBidCommandHandler, PatchAuctionCommandHandler, PublishingCreateBidCommandHandler,
+            QueuedCreateBidCommandHandler, RecordDepositCommandHandler, RegistrationCommandHandler, RetractBidCommandHandler, StatsPrivacy, TrafficRecorder, UserDirectory,
+            JobRunner, LogNotifier, SchedulingCreateAuctionCommandHandler,
+            SchedulingAdminAuctionCommandHandler,
         },
-        AuctionRepository, Settings,
+        amount_format, authentication, contract_test_auctions, contract_test_currencies, contract_test_now, deprecation, fault_injection, load_shedding, request_deadline, traffic_capture, user_registration, AuctionRepository,
+        CONTRACT_TEST_FLAG, AuditRepository, CurrencyRepository, DatabaseKind, DepositRepository, LoadShedder, RegistrationRepository, RollupRepository, Settings, UserRepository,
     }, 
 };
 
+/// The pool, when on Postgres, and what the server reads and stores through.
+struct Backends {
+    db_pool: Option<sqlx::PgPool>,
+    system_clock: Box<dyn SystemClock>,
+    random_source: Box<dyn RandomSource>,
+    auction_repository: Box<dyn AuctionRepository>,
+    currency_repository: Box<dyn CurrencyRepository>,
+    audit_repository: Box<dyn AuditRepository>,
+    job_repository: Box<dyn JobRepository>,
+    rollup_repository: Box<dyn RollupRepository>,
+    registration_repository: Box<dyn RegistrationRepository>,
+    deposit_repository: Box<dyn DepositRepository>,
+    result_repository: Box<dyn AuctionResultRepository>,
+    settlement_repository: Box<dyn SettlementRepository>,
+    user_repository: Box<dyn UserRepository>,
+}
+
+#[cfg(feature = "mongodb")]
+use auctions_api::infrastructure::data::{
+    prepare_mongo_database, MongoAuctionRepository, MongoAuctionResultRepository, MongoAuditRepository, MongoCurrencyRepository,
+    MongoDepositRepository, MongoRegistrationRepository, MongoSettlementRepository, MongoUserRepository,
+};
+#[cfg(feature = "redis")]
+use auctions_api::infrastructure::data::{listen_for_cache_invalidations, RedisAuctionRepository};
+#[cfg(feature = "mysql")]
+use auctions_api::infrastructure::data::{
+    create_mysql_pool, run_mysql_migrations, MySqlAuctionRepository, MySqlAuctionResultRepository, MySqlAuditRepository,
+    MySqlCurrencyRepository, MySqlDepositRepository, MySqlRegistrationRepository, MySqlSettlementRepository, MySqlUserRepository,
+};
+
+/// Shares auction reads between instances through Redis.
+#[cfg(feature = "redis")]
+async fn redis_cached(inner: Box<dyn AuctionRepository>, config: &Settings) -> Box<dyn AuctionRepository> {
+    match RedisAuctionRepository::connect(inner, &config.redis_cache).await {
+        Ok(repository) => {
+            log::info!("Caching auctions in Redis for {} s", config.redis_cache.ttl_secs);
+            Box::new(repository)
+        }
+        Err(e) => {
+            log::error!("Failed to connect to Redis: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(feature = "redis"))]
+async fn redis_cached(_inner: Box<dyn AuctionRepository>, _config: &Settings) -> Box<dyn AuctionRepository> {
+    log::error!("redis_cache is enabled, but the server was built without the redis feature");
+    std::process::exit(1);
+}
+
+/// The databases storing auctions as documents, which keep background jobs in memory and have no
+/// stats rollups.
+async fn document_backends(config: &Settings) -> Backends {
+    match config.database.kind {
+        DatabaseKind::Sqlite => {
+            let sqlite_pool = create_sqlite_pool(&config.database.url, PoolSettings::from(&config.database)).await
+                .expect("Failed to create database pool");
+
+            log::info!("Running SQLite database migrations");
+            if let Err(e) = run_sqlite_migrations(&sqlite_pool).await {
+                log::error!("Failed to run migrations: {}", e);
+                std::process::exit(1);
+            }
+            Backends {
+                db_pool: None,
+                system_clock: Box::new(RealSystemClock),
+                random_source: Box::new(OsRandomSource),
+                auction_repository: Box::new(SqliteAuctionRepository::new(sqlite_pool.clone())),
+                currency_repository: Box::new(SqliteCurrencyRepository::new(sqlite_pool.clone())),
+                audit_repository: Box::new(SqliteAuditRepository::new(sqlite_pool.clone())),
+                job_repository: Box::new(InMemoryJobRepository::default()),
+                rollup_repository: Box::new(InMemoryRollupRepository),
+                registration_repository: Box::new(SqliteRegistrationRepository::new(sqlite_pool.clone())),
+                deposit_repository: Box::new(SqliteDepositRepository::new(sqlite_pool.clone())),
+                result_repository: Box::new(SqliteAuctionResultRepository::new(sqlite_pool.clone())),
+                settlement_repository: Box::new(SqliteSettlementRepository::new(sqlite_pool.clone())),
+                user_repository: Box::new(SqliteUserRepository::new(sqlite_pool)),
+            }
+        }
+        #[cfg(feature = "mysql")]
+        DatabaseKind::Mysql => {
+            let mysql_pool = create_mysql_pool(&config.database.url, PoolSettings::from(&config.database)).await
+                .expect("Failed to create database pool");
+
+            log::info!("Running MySQL database migrations");
+            if let Err(e) = run_mysql_migrations(&mysql_pool).await {
+                log::error!("Failed to run migrations: {}", e);
+                std::process::exit(1);
+            }
+            Backends {
+                db_pool: None,
+                system_clock: Box::new(RealSystemClock),
+                random_source: Box::new(OsRandomSource),
+                auction_repository: Box::new(MySqlAuctionRepository::new(mysql_pool.clone())),
+                currency_repository: Box::new(MySqlCurrencyRepository::new(mysql_pool.clone())),
+                audit_repository: Box::new(MySqlAuditRepository::new(mysql_pool.clone())),
+                job_repository: Box::new(InMemoryJobRepository::default()),
+                rollup_repository: Box::new(InMemoryRollupRepository),
+                registration_repository: Box::new(MySqlRegistrationRepository::new(mysql_pool.clone())),
+                deposit_repository: Box::new(MySqlDepositRepository::new(mysql_pool.clone())),
+                result_repository: Box::new(MySqlAuctionResultRepository::new(mysql_pool.clone())),
+                settlement_repository: Box::new(MySqlSettlementRepository::new(mysql_pool.clone())),
+                user_repository: Box::new(MySqlUserRepository::new(mysql_pool)),
+            }
+        }
+        #[cfg(not(feature = "mysql"))]
+        DatabaseKind::Mysql => {
+            log::error!("database.kind is mysql, but the server was built without the mysql feature");
+            std::process::exit(1);
+        }
+        #[cfg(feature = "mongodb")]
+        DatabaseKind::Mongodb => {
+            let client = mongodb::Client::with_uri_str(&config.database.url).await
+                .expect("Failed to connect to the database");
+            let database = client.default_database().unwrap_or_else(|| client.database("auctions"));
+
+            log::info!("Preparing the MongoDB database");
+            if let Err(e) = prepare_mongo_database(&database).await {
+                log::error!("Failed to prepare the database: {}", e);
+                std::process::exit(1);
+            }
+            Backends {
+                db_pool: None,
+                system_clock: Box::new(RealSystemClock),
+                random_source: Box::new(OsRandomSource),
+                auction_repository: Box::new(MongoAuctionRepository::new(&database)),
+                currency_repository: Box::new(MongoCurrencyRepository::new(&database)),
+                audit_repository: Box::new(MongoAuditRepository::new(&database)),
+                job_repository: Box::new(InMemoryJobRepository::default()),
+                rollup_repository: Box::new(InMemoryRollupRepository),
+                registration_repository: Box::new(MongoRegistrationRepository::new(&database)),
+                deposit_repository: Box::new(MongoDepositRepository::new(&database)),
+                result_repository: Box::new(MongoAuctionResultRepository::new(&database)),
+                settlement_repository: Box::new(MongoSettlementRepository::new(&database)),
+                user_repository: Box::new(MongoUserRepository::new(&database)),
+            }
+        }
+        #[cfg(not(feature = "mongodb"))]
+        DatabaseKind::Mongodb => {
+            log::error!("database.kind is mongodb, but the server was built without the mongodb feature");
+            std::process::exit(1);
+        }
+        DatabaseKind::Postgres => unreachable!("Postgres stores auctions in tables"),
+    }
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     // Load environment variables
@@ -26,43 +180,371 @@ async fn main() -> std::io::Result<()> {
     let config = Settings::new().expect("Failed to load configuration");
     log::info!("Starting server in {} environment", config.environment);
     
-    // Create database connection pool
-    let db_pool = create_pg_pool(&config.database.url).await
-        .expect("Failed to create database pool");
-    
-    // Run database migrations
-    log::info!("Running database migrations");
-    if let Err(e) = run_migrations(&db_pool).await {
-        log::error!("Failed to run migrations: {}", e);
-        std::process::exit(1);
+    // Roll back the last migration, for a broken deploy, instead of serving
+    if std::env::args().any(|arg| arg == ROLLBACK_LAST_MIGRATION_FLAG) {
+        if config.database.kind != DatabaseKind::Postgres {
+            log::error!("database.kind is {:?}, but rolling back migrations is only supported on Postgres", config.database.kind);
+            std::process::exit(1);
+        }
+        let db_pool = create_pg_pool(&config.database.url, PoolSettings::from(&config.database)).await
+            .expect("Failed to create database pool");
+        match rollback_last_migration(&db_pool).await {
+            Ok(Some(version)) => log::info!("Rolled back migration {}", version),
+            Ok(None) => log::info!("No migration to roll back"),
+            Err(e) => {
+                log::error!("Failed to roll back the last migration: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
     }
+
+    // Serve a canned, deterministic dataset without a database
+    let contract_test = std::env::args().any(|arg| arg == CONTRACT_TEST_FLAG);
+
+    let Backends {
+        db_pool,
+        system_clock,
+        random_source,
+        auction_repository,
+        currency_repository,
+        audit_repository,
+        job_repository,
+        rollup_repository,
+        registration_repository,
+        deposit_repository,
+        result_repository,
+        settlement_repository,
+        user_repository,
+    } = if contract_test {
+        log::warn!("Contract test mode: serving a fixed dataset, changes are kept in memory");
+        Backends {
+            db_pool: None,
+            system_clock: Box::new(FixedSystemClock(contract_test_now())),
+            random_source: Box::new(SeededRandomSource::new(0)),
+            auction_repository: Box::new(InMemoryAuctionRepository::new(contract_test_auctions())),
+            currency_repository: Box::new(InMemoryCurrencyRepository::new(contract_test_currencies())),
+            audit_repository: Box::new(InMemoryAuditRepository::default()),
+            job_repository: Box::new(InMemoryJobRepository::default()),
+            rollup_repository: Box::new(InMemoryRollupRepository),
+            registration_repository: Box::new(InMemoryRegistrationRepository::default()),
+            deposit_repository: Box::new(InMemoryDepositRepository::default()),
+            result_repository: Box::new(InMemoryAuctionResultRepository::default()),
+            settlement_repository: Box::new(InMemorySettlementRepository::default()),
+            user_repository: Box::new(InMemoryUserRepository::default()),
+        }
+    } else if config.database.kind != DatabaseKind::Postgres {
+        let backends = document_backends(&config).await;
+        log::warn!("{:?} mode: background jobs are kept in memory and lost on restart, stats rollups are not kept", config.database.kind);
+        backends
+    } else if config.database.event_sourced {
+        let db_pool = create_pg_pool(&config.database.url, PoolSettings::from(&config.database)).await
+            .expect("Failed to create database pool");
+
+        log::info!("Running database migrations");
+        if let Err(e) = run_migrations(&db_pool).await {
+            log::error!("Failed to run migrations: {}", e);
+            std::process::exit(1);
+        }
+        // The other tables no longer refer to rows of the auctions table, which event-sourced
+        // auctions leave empty, see the auction_archive migration
+        Backends {
+            db_pool: Some(db_pool.clone()),
+            system_clock: Box::new(RealSystemClock),
+            random_source: Box::new(OsRandomSource),
+            auction_repository: Box::new(EventSourcedAuctionRepository::new(db_pool.clone())),
+            currency_repository: Box::new(PgCurrencyRepository::new(db_pool.clone())),
+            audit_repository: Box::new(PgAuditRepository::new(db_pool.clone())),
+            job_repository: Box::new(PgJobRepository::new(db_pool.clone())),
+            rollup_repository: Box::new(PgRollupRepository::new(db_pool.clone())),
+            registration_repository: Box::new(PgRegistrationRepository::new(db_pool.clone())),
+            deposit_repository: Box::new(PgDepositRepository::new(db_pool.clone())),
+            result_repository: Box::new(PgAuctionResultRepository::new(db_pool.clone())),
+            settlement_repository: Box::new(PgSettlementRepository::new(db_pool.clone())),
+            user_repository: Box::new(PgUserRepository::new(db_pool)),
+        }
+    } else {
+        // Create database connection pool
+        let db_pool = create_pg_pool(&config.database.url, PoolSettings::from(&config.database)).await
+            .expect("Failed to create database pool");
+
+        // Run database migrations
+        log::info!("Running database migrations");
+        if let Err(e) = run_migrations(&db_pool).await {
+            log::error!("Failed to run migrations: {}", e);
+            std::process::exit(1);
+        }
+
+        // Fail fast if stored auctions no longer deserialize into the domain model
+        log::info!("Checking schema compatibility");
+        if let Err(e) = check_schema_compatibility(&db_pool).await {
+            log::error!("{}", e);
+            std::process::exit(1);
+        }
+
+        Backends {
+            db_pool: Some(db_pool.clone()),
+            system_clock: Box::new(RealSystemClock),
+            random_source: Box::new(OsRandomSource),
+            auction_repository: Box::new(PgAuctionRepository::new(db_pool.clone())),
+            currency_repository: Box::new(PgCurrencyRepository::new(db_pool.clone())),
+            audit_repository: Box::new(PgAuditRepository::new(db_pool.clone())),
+            job_repository: Box::new(PgJobRepository::new(db_pool.clone())),
+            rollup_repository: Box::new(PgRollupRepository::new(db_pool.clone())),
+            registration_repository: Box::new(PgRegistrationRepository::new(db_pool.clone())),
+            deposit_repository: Box::new(PgDepositRepository::new(db_pool.clone())),
+            result_repository: Box::new(PgAuctionResultRepository::new(db_pool.clone())),
+            settlement_repository: Box::new(PgSettlementRepository::new(db_pool.clone())),
+            user_repository: Box::new(PgUserRepository::new(db_pool)),
+        }
+    };
     
-    // Create system clock
-    let system_clock: Box<dyn SystemClock> = Box::new(RealSystemClock);
-    
-    // Create repositories and queries
-    let auction_repository: Box<dyn AuctionRepository> = Box::new(PgAuctionRepository::new(db_pool.clone()));
-    
+    // Prices shown in other currencies, bids stay in the currency of the auction
+    let currency_converter: Box<dyn CurrencyConverter> = Box::new(FixedRateCurrencyConverter::new(config.exchange_rates()));
+
+    // Reads shared between instances through Redis, off unless configured
+    let auction_repository: Box<dyn AuctionRepository> = if config.redis_cache.enabled {
+        redis_cached(auction_repository, &config).await
+    } else {
+        auction_repository
+    };
+    // Repeated reads served from memory for a short while, off unless configured
+    let auction_repository: Box<dyn AuctionRepository> = if config.auction_cache.enabled {
+        log::info!(
+            "Caching auctions for {} ms and listings for {} ms",
+            config.auction_cache.auction_ttl_ms,
+            config.auction_cache.listing_ttl_ms
+        );
+        let cache = CachingAuctionRepository::new(auction_repository, &config.auction_cache);
+        // Writes made by other instances are published through Redis
+        #[cfg(feature = "redis")]
+        if config.redis_cache.enabled {
+            tokio::spawn(listen_for_cache_invalidations(config.redis_cache.clone(), cache.clone()));
+        }
+        Box::new(cache)
+    } else {
+        auction_repository
+    };
+    // Artificial faults for resilience testing, off unless configured
+    let fault_injector = FaultInjector::new(config.fault_injection.clone(), random_source.clone());
+    let auction_repository: Box<dyn AuctionRepository> = if fault_injector.is_enabled() {
+        log::warn!("Fault injection is enabled");
+        Box::new(FaultInjectingAuctionRepository::new(auction_repository, fault_injector.clone()))
+    } else {
+        auction_repository
+    };
+    // Repository calls share the deadline of the request making them
+    let auction_repository: Box<dyn AuctionRepository> = if config.request_deadline.enabled {
+        Box::new(DeadlineAuctionRepository::new(auction_repository))
+    } else {
+        auction_repository
+    };
+    let request_deadline_config = web::Data::new(config.request_deadline.clone());
+
+    let stats_privacy = StatsPrivacy::new(config.stats_privacy.clone(), random_source.clone());
+    if stats_privacy.is_enabled() {
+        log::info!("Public stats are published with privacy protection");
+    }
+
+    // Sampled, anonymized traffic for support users, off unless configured
+    let traffic_recorder = TrafficRecorder::new(
+        config.traffic_capture.clone(),
+        random_source.clone(),
+        system_clock.clone(),
+    );
+
+    // Callers are stored on their first request, to show their display names
+    let user_directory = web::Data::new(UserDirectory::new(user_repository, system_clock.clone()));
+
     // Create command handlers
     let create_auction_handler: Box<dyn CreateAuctionCommandHandler> = Box::new(DefaultCreateAuctionCommandHandler::new(
         auction_repository.clone(),
+        currency_repository.clone(),
+    ));
+    // Auctions are finalized by a background job once they end
+    let create_auction_handler: Box<dyn CreateAuctionCommandHandler> = Box::new(SchedulingCreateAuctionCommandHandler::new(
+        create_auction_handler,
+        job_repository.clone(),
+        system_clock.clone(),
     ));
 
     
     let create_bid_handler: Box<dyn CreateBidCommandHandler> = Box::new(DefaultCreateBidCommandHandler::new(
+        auction_repository.clone(),
+        registration_repository.clone(),
+        deposit_repository.clone(),
+        Box::new(HeuristicBidScreeningService::default()),
+        system_clock.clone(),
+    ));
+    // Notify long-polling clients about accepted bids
+    let bid_events = BidEvents::default();
+    let create_bid_handler: Box<dyn CreateBidCommandHandler> = Box::new(PublishingCreateBidCommandHandler::new(
+        create_bid_handler,
+        bid_events.clone(),
+    ));
+    // Bids taken by other instances reach waiting clients through Postgres
+    if let (Some(pool), true) = (&db_pool, config.event_listener.enabled) {
+        tokio::spawn(listen_for_auction_events(
+            pool.clone(),
+            bid_events.clone(),
+            config.event_listener.clone(),
+        ));
+    }
+
+    let patch_auction_handler: Box<dyn PatchAuctionCommandHandler> = Box::new(DefaultPatchAuctionCommandHandler::new(
         auction_repository.clone(),
         system_clock.clone(),
     ));
+    let admin_auction_handler: Box<dyn AdminAuctionCommandHandler> = Box::new(DefaultAdminAuctionCommandHandler::new(
+        auction_repository.clone(),
+        audit_repository.clone(),
+        system_clock.clone(),
+    ));
+    // Ending an auction early brings its finalization forward
+    let admin_auction_handler: Box<dyn AdminAuctionCommandHandler> = Box::new(SchedulingAdminAuctionCommandHandler::new(
+        admin_auction_handler,
+        job_repository.clone(),
+        system_clock.clone(),
+    ));
+    let anonymize_user_handler: Box<dyn AnonymizeUserCommandHandler> = Box::new(DefaultAnonymizeUserCommandHandler::new(
+        auction_repository.clone(),
+        audit_repository.clone(),
+        user_directory.get_ref().clone(),
+        random_source.clone(),
+        system_clock.clone(),
+    ));
+
+    let cancel_auction_handler: Box<dyn CancelAuctionCommandHandler> = Box::new(DefaultCancelAuctionCommandHandler::new(
+        auction_repository.clone(),
+        audit_repository.clone(),
+        system_clock.clone(),
+    ));
+
+    let publish_auction_handler: Box<dyn PublishAuctionCommandHandler> = Box::new(DefaultPublishAuctionCommandHandler::new(
+        auction_repository.clone(),
+        system_clock.clone(),
+    ));
+
+    let extend_auction_handler: Box<dyn ExtendAuctionCommandHandler> = Box::new(DefaultExtendAuctionCommandHandler::new(
+        auction_repository.clone(),
+        audit_repository.clone(),
+        system_clock.clone(),
+    ));
+
+    let retract_bid_handler: Box<dyn RetractBidCommandHandler> = Box::new(DefaultRetractBidCommandHandler::new(
+        auction_repository.clone(),
+        system_clock.clone(),
+        config.bid_retraction_window(),
+    ));
+
+    let registration_handler: Box<dyn RegistrationCommandHandler> = Box::new(DefaultRegistrationCommandHandler::new(
+        auction_repository.clone(),
+        registration_repository,
+        system_clock.clone(),
+    ));
+
+    let record_deposit_handler: Box<dyn RecordDepositCommandHandler> = Box::new(DefaultRecordDepositCommandHandler::new(
+        auction_repository.clone(),
+        deposit_repository,
+        system_clock.clone(),
+    ));
+
+    // Closed auctions report the result recorded when they were first read closed
+    let result_recorder = AuctionResultRecorder::new(result_repository);
+    // Settlements are kept as first worked out, by the fees configured at the time
+    let settlement_recorder = SettlementRecorder::new(settlement_repository, config.fee_policy());
+
+    // Optionally serialize bids per auction
+    let bid_queue = if config.bid_queue.enabled {
+        log::info!("Queuing bids per auction (capacity {})", config.bid_queue.capacity);
+        Some(QueuedCreateBidCommandHandler::new(
+            create_bid_handler.clone(),
+            auction_repository.clone(),
+            config.bid_queue.capacity,
+            config.bid_queue_idle_timeout(),
+        ))
+    } else {
+        None
+    };
+    let create_bid_handler: Box<dyn CreateBidCommandHandler> = match &bid_queue {
+        Some(queue) => Box::new(queue.clone()),
+        None => create_bid_handler,
+    };
     
+    if config.client_address.ip_hash_key.is_empty() {
+        log::warn!("client_address.ip_hash_key is not set: the hashed client addresses of bids can be reversed");
+    }
+    let client_address_config = web::Data::new(config.client_address.clone());
+    let amount_format_config = web::Data::new(config.api.amount_format);
+
+    // Finalization, notification and webhook jobs, which support can inspect under /admin/jobs
+    if config.jobs.enabled {
+        let runner = JobRunner::new(
+            job_repository.clone(),
+            auction_repository.clone(),
+            system_clock.clone(),
+            Box::new(LogNotifier),
+            config.jobs.clone(),
+        );
+        tokio::spawn(async move { runner.run().await });
+    }
+
+    // Shared across workers so that load is measured for the whole server
+    let load_shedder = web::Data::new(LoadShedder::new(
+        config.load_shedding.clone(),
+        db_pool,
+        config.database.max_connections,
+    ));
+
     // Start HTTP server
     log::info!("Starting HTTP server on {}:{}", config.server.host, config.server.port);
     HttpServer::new(move || {
         App::new()
+            .wrap(from_fn(amount_format))
+            .wrap(from_fn(user_registration))
+            .wrap(from_fn(authentication))
+            .wrap(from_fn(localize_errors))
+            .wrap(from_fn(request_deadline))
             .wrap(Logger::default())
+            .app_data(client_address_config.clone())
+            .wrap(from_fn(load_shedding))
+            .wrap(from_fn(deprecation))
+            .wrap(from_fn(fault_injection))
+            .wrap(from_fn(traffic_capture))
+            .app_data(load_shedder.clone())
+            .app_data(request_deadline_config.clone())
+            .app_data(amount_format_config.clone())
+            .app_data(user_directory.clone())
             .app_data(web::Data::new(create_auction_handler.clone()))
             .app_data(web::Data::new(create_bid_handler.clone()))
+            .app_data(web::Data::new(patch_auction_handler.clone()))
+            .app_data(web::Data::new(admin_auction_handler.clone()))
+            .app_data(web::Data::new(anonymize_user_handler.clone()))
+            .app_data(web::Data::new(cancel_auction_handler.clone()))
+            .app_data(web::Data::new(publish_auction_handler.clone()))
+            .app_data(web::Data::new(extend_auction_handler.clone()))
+            .app_data(web::Data::new(retract_bid_handler.clone()))
+            .app_data(web::Data::new(registration_handler.clone()))
+            .app_data(web::Data::new(record_deposit_handler.clone()))
             .app_data(web::Data::new(system_clock.clone()))
+            .app_data(web::Data::new(random_source.clone()))
             .app_data(web::Data::new(auction_repository.clone()))
+            .app_data(web::Data::new(job_repository.clone()))
+            .app_data(web::Data::new(result_recorder.clone()))
+            .app_data(web::Data::new(currency_repository.clone()))
+            .app_data(web::Data::new(currency_converter.clone()))
+            .app_data(web::Data::new(rollup_repository.clone()))
+            .app_data(web::Data::new(stats_privacy.clone()))
+            .app_data(web::Data::new(settlement_recorder.clone()))
+            .app_data(web::Data::new(bid_queue.clone()))
+            .app_data(web::Data::new(bid_events.clone()))
+            .app_data(web::Data::new(fault_injector.clone()))
+            .app_data(web::Data::new(traffic_recorder.clone()))
+            .service(auctions_api::api::handlers::metrics::get_metrics)
+            .service(auctions_api::api::handlers::api_changes::get_api_changes)
+            .service(auctions_api::api::handlers::currencies::get_currencies)
+            .service(auctions_api::api::handlers::stats::get_rollups)
+            .service(auctions_api::api::handlers::admin::get_scope())
             .service(auctions_api::api::handlers::auctions::get_scope())
     })
     .bind(format!("{}:{}", config.server.host, config.server.port))?
@@ -1,6 +1,7 @@
 use auctions_api::domain::models::{
-    Amount, Auction, AuctionBase, AuctionId, Bid, BidData, CurrencyCode, Errors,
-    SingleSealedBidOptions, TimedAscendingOptions, UserId,
+    Amount, Auction, AuctionBase, AuctionId, AuctionRemoval, AuctionStatus, Bid, BidData,
+    BidIncrement, CurrencyCode, DomainEvent, Errors, FeePolicy, Lot, RemovalKind,
+    SingleSealedBidOptions, TieBreak, TimedAscendingOptions, UserId, VickreyPricing,
 };
 use chrono::Duration;
 use chrono::{DateTime, TimeZone, Utc};
@@ -38,19 +39,49 @@ pub fn get_english_auction() -> Auction {
         base: AuctionBase {
             auction_id: auction_id(),
             title: title().to_string(),
+            description: None,
             starts_at: starts_at(),
             expiry: ends_at(),
             user: seller(),
             currency: CurrencyCode::SEK,
             bids: Vec::new(),
             open_bidders: true,
+            requires_registration: false,
+            deposit: None,
+            quantity: 1,
+            external_reference: None,
+            seller_ip_hash: None,
+            lots: Vec::new(),
+            fee_policy: None,
+            tax_rate_bps: None,
+            voided_at: None,
+            void_reason: None,
+            status: AuctionStatus::Scheduled,
+            cancelled_at: None,
+            cancel_reason: None,
+            retracted_bids: Vec::new(),
+            voided_bids: Vec::new(),
+            extended_at: None,
+            extension_reason: None,
+            pauses: Vec::new(),
+            relist_policy: None,
+            relisted_from: None,
+            relisted_as: None,
+            relist_count: 0,
+            version: 0,
+            last_bid_id: 0,
         },
         options: TimedAscendingOptions {
             min_raise: 10,
             time_frame: Duration::minutes(1),
             reserve_price: 150,
+            buy_now_price: None,
+            starting_price: 0,
+            max_extension: None,
+            increments: Vec::new(),
         },
         ends_at: None,
+        max_bids: Vec::new(),
     }
 }
 
@@ -59,14 +90,42 @@ pub fn vickrey_auction() -> Auction {
         base: AuctionBase {
             auction_id: auction_id(),
             title: title().to_string(),
+            description: None,
             starts_at: starts_at(),
             expiry: ends_at(),
             user: seller(),
             currency: CurrencyCode::SEK,
             open_bidders: true,
+            requires_registration: false,
+            deposit: None,
             bids: Vec::new(),
+            quantity: 1,
+            external_reference: None,
+            seller_ip_hash: None,
+            lots: Vec::new(),
+            fee_policy: None,
+            tax_rate_bps: None,
+            voided_at: None,
+            void_reason: None,
+            status: AuctionStatus::Scheduled,
+            cancelled_at: None,
+            cancel_reason: None,
+            retracted_bids: Vec::new(),
+            voided_bids: Vec::new(),
+            extended_at: None,
+            extension_reason: None,
+            pauses: Vec::new(),
+            relist_policy: None,
+            relisted_from: None,
+            relisted_as: None,
+            relist_count: 0,
+            version: 0,
+            last_bid_id: 0,
         },
         options: SingleSealedBidOptions::Vickrey,
+        tie_break: TieBreak::EarliestBid,
+        reserve_price: None,
+        pricing: VickreyPricing::SecondPrice,
     }
 }
 
@@ -75,14 +134,42 @@ pub fn blind_auction() -> Auction {
         base: AuctionBase {
             auction_id: auction_id(),
             title: title().to_string(),
+            description: None,
             starts_at: starts_at(),
             expiry: ends_at(),
             user: seller(),
             currency: CurrencyCode::SEK,
             open_bidders: true,
+            requires_registration: false,
+            deposit: None,
             bids: Vec::new(),
+            quantity: 1,
+            external_reference: None,
+            seller_ip_hash: None,
+            lots: Vec::new(),
+            fee_policy: None,
+            tax_rate_bps: None,
+            voided_at: None,
+            void_reason: None,
+            status: AuctionStatus::Scheduled,
+            cancelled_at: None,
+            cancel_reason: None,
+            retracted_bids: Vec::new(),
+            voided_bids: Vec::new(),
+            extended_at: None,
+            extension_reason: None,
+            pauses: Vec::new(),
+            relist_policy: None,
+            relisted_from: None,
+            relisted_as: None,
+            relist_count: 0,
+            version: 0,
+            last_bid_id: 0,
         },
         options: SingleSealedBidOptions::Blind,
+        tie_break: TieBreak::EarliestBid,
+        reserve_price: None,
+        pricing: VickreyPricing::SecondPrice,
     }
 }
 
@@ -103,6 +190,8 @@ pub fn bid1() -> BidData {
         user: buyer1(),
         amount: sek(10),
         at: starts_at() + Duration::hours(2),
+        quantity: 1,
+        metadata: None,
     }
 }
 
@@ -111,6 +200,8 @@ pub fn bid2() -> BidData {
         user: buyer2(),
         amount: sek(12),
         at: starts_at() + Duration::hours(2),
+        quantity: 1,
+        metadata: None,
     }
 }
 
@@ -119,6 +210,8 @@ fn create_sample_bid(user_id: &str, amount: i64, hours_after_start: i64) -> BidD
         user: UserId::new(user_id),
         amount: sek(amount),
         at: starts_at() + Duration::hours(hours_after_start),
+        quantity: 1,
+        metadata: None,
     }
 }
 
@@ -166,6 +259,19 @@ fn test_timed_ascending_auction_add_bid_after_end() {
     assert_eq!(result.unwrap_err(), Errors::AuctionHasEnded);
 }
 
+#[test]
+fn test_timed_ascending_auction_rejects_bids_without_positive_amount() {
+    let mut auction = get_english_auction();
+    let now = auction.starts_at() + Duration::hours(1);
+
+    let result = auction.try_add_bid(now, create_sample_bid("buyer1", 0, 1));
+    assert_eq!(result.unwrap_err(), Errors::MustSpecifyAmount);
+
+    let result = auction.try_add_bid(now, create_sample_bid("buyer1", -150, 1));
+    assert_eq!(result.unwrap_err(), Errors::MustSpecifyAmount);
+    assert!(auction.bids().is_empty());
+}
+
 #[test]
 fn test_timed_ascending_auction_add_bid_min_raise() {
     let mut auction = get_english_auction();
@@ -344,3 +450,1097 @@ fn test_bid_validation_auction_timing() {
     let errors = after_bid.validate(&auction);
     assert_eq!(errors, Errors::AuctionHasEnded);
 }
+
+#[test]
+fn test_combined_validation_errors_serialize_as_flag_names() {
+    let auction = blind_auction();
+
+    // The seller bidding in another currency after the end breaks three rules at once
+    let bid = Bid::new(
+        5,
+        seller(),
+        Amount::new(100, CurrencyCode::VAC),
+        ends_at().checked_add_signed(Duration::seconds(1)).unwrap(),
+    );
+
+    let errors = bid.validate(&auction);
+    assert_eq!(
+        errors,
+        Errors::SellerCannotPlaceBids | Errors::BidCurrencyConversion | Errors::AuctionHasEnded
+    );
+    let json = serde_json::to_value(errors).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!(["AuctionHasEnded", "SellerCannotPlaceBids", "BidCurrencyConversion"])
+    );
+    assert_eq!(serde_json::from_value::<Errors>(json).unwrap(), errors);
+}
+
+#[test]
+fn test_bidder_aliases_are_stable_per_bidder() {
+    let mut auction = get_english_auction();
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 100, 1)).is_ok());
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 120, 1)).is_ok());
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 140, 1)).is_ok());
+
+    let aliases = auction.bidder_aliases();
+    assert_eq!(aliases.len(), 2);
+    assert_eq!(aliases[&UserId::new("buyer1")], "Bidder A");
+    assert_eq!(aliases[&UserId::new("buyer2")], "Bidder B");
+    assert_eq!(auction.bidder_aliases(), aliases);
+}
+
+#[test]
+fn test_effective_end_includes_extension() {
+    let mut auction = get_english_auction();
+    assert_eq!(auction.effective_end(), ends_at());
+
+    // A bid in the last seconds extends the auction by the time frame
+    let now = ends_at() - Duration::seconds(30);
+    let mut bid = create_sample_bid("buyer1", 150, 0);
+    bid.at = now;
+    assert!(auction.try_add_bid(now, bid).is_ok());
+
+    assert_eq!(auction.effective_end(), now + Duration::minutes(1));
+    assert_eq!(auction.highest_bid().map(|b| b.amount()), Some(&sek(150)));
+}
+
+#[test]
+fn test_extended_auction_has_no_winner_until_the_extended_end() {
+    let mut auction = get_english_auction();
+    let at = ends_at() - Duration::seconds(30);
+    assert!(auction.try_add_bid(at, create_bid_at("buyer1", 150, at)).is_ok());
+    let extended_end = at + Duration::minutes(1);
+
+    // Past the scheduled expiry, but within the extension
+    let during_extension = ends_at() + Duration::seconds(10);
+    assert!(!auction.has_ended(during_extension));
+    assert_eq!(auction.try_get_amount_and_winner(during_extension), None);
+    assert_eq!(auction.get_bids(during_extension).map(Vec::len), Some(1));
+
+    // A bid during the extension outbids the first one and wins
+    let at = ends_at() + Duration::seconds(20);
+    assert!(auction.try_add_bid(at, create_bid_at("buyer2", 160, at)).is_ok());
+    assert!(auction.effective_end() > extended_end);
+    assert_eq!(auction.try_get_amount_and_winner(extended_end), None);
+
+    let after = auction.effective_end() + Duration::seconds(1);
+    assert!(auction.has_ended(after));
+    assert_eq!(auction.try_get_amount_and_winner(after), Some((sek(160), UserId::new("buyer2"))));
+}
+
+#[test]
+fn test_winner_is_known_right_after_an_unextended_end() {
+    let mut auction = get_english_auction();
+    assert!(auction.try_add_bid(starts_at(), create_sample_bid("buyer1", 150, 0)).is_ok());
+
+    assert_eq!(auction.try_get_amount_and_winner(ends_at()), None);
+    let after = ends_at() + Duration::seconds(1);
+    assert_eq!(auction.try_get_amount_and_winner(after), Some((sek(150), UserId::new("buyer1"))));
+}
+
+#[test]
+fn test_force_end_auction() {
+    let mut auction = get_english_auction();
+    let now = auction.starts_at() + Duration::hours(1);
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 150, 1)).is_ok());
+
+    assert!(auction.force_end(now).is_ok());
+    assert!(auction.has_ended(now + Duration::seconds(1)));
+    assert_eq!(auction.force_end(now + Duration::seconds(1)), Err(Errors::AuctionHasEnded));
+
+    let (amount, winner) = auction.try_get_amount_and_winner(now + Duration::seconds(1)).unwrap();
+    assert_eq!(amount, sek(150));
+    assert_eq!(winner.value(), "buyer1");
+}
+
+#[test]
+fn test_voided_auction_has_no_winner() {
+    let mut auction = blind_auction();
+    let now = auction.starts_at() + Duration::hours(1);
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 150, 1)).is_ok());
+
+    assert!(auction.void(now, Some("listing error".to_string())).is_ok());
+
+    assert!(auction.has_ended(now + Duration::seconds(1)));
+    assert_eq!(auction.voided_at(), Some(now));
+    assert_eq!(auction.void_reason(), Some("listing error"));
+    assert!(auction.try_get_amount_and_winner(ends_at() + Duration::hours(1)).is_none());
+}
+
+#[test]
+fn test_voided_auction_is_removed() {
+    let mut auction = blind_auction();
+    let now = auction.starts_at() + Duration::hours(1);
+    assert_eq!(auction.removal(), None);
+
+    auction.void(now, Some("listing error".to_string())).unwrap();
+
+    assert_eq!(
+        auction.removal(),
+        Some(AuctionRemoval { kind: RemovalKind::Voided, at: now, reason: Some("listing error".to_string()) })
+    );
+}
+
+fn with_quantity(mut auction: Auction, quantity: i32) -> Auction {
+    match &mut auction {
+        Auction::SingleSealedBid { base, .. } => base.quantity = quantity,
+        Auction::TimedAscending { base, .. } => base.quantity = quantity,
+    }
+    auction
+}
+
+fn create_sample_bid_for_units(user_id: &str, amount: i64, quantity: i32) -> BidData {
+    BidData {
+        quantity,
+        ..create_sample_bid(user_id, amount, 1)
+    }
+}
+
+#[test]
+fn test_multi_unit_blind_auction_allocates_to_highest_bids() {
+    let mut auction = with_quantity(blind_auction(), 3);
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.try_add_bid(now, create_sample_bid_for_units("buyer1", 100, 2)).is_ok());
+    assert!(auction.try_add_bid(now, create_sample_bid_for_units("buyer2", 150, 1)).is_ok());
+    assert!(auction.try_add_bid(now, create_sample_bid_for_units("buyer3", 120, 2)).is_ok());
+
+    let winners = auction.try_get_winners(ends_at() + Duration::hours(1));
+    assert_eq!(
+        winners,
+        vec![
+            (sek(150), UserId::new("buyer2"), 1),
+            (sek(120), UserId::new("buyer3"), 2),
+        ]
+    );
+}
+
+#[test]
+fn test_multi_unit_auction_fills_last_bid_partially() {
+    let mut auction = with_quantity(blind_auction(), 3);
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.try_add_bid(now, create_sample_bid_for_units("buyer1", 100, 2)).is_ok());
+    assert!(auction.try_add_bid(now, create_sample_bid_for_units("buyer2", 150, 2)).is_ok());
+
+    let winners = auction.try_get_winners(ends_at() + Duration::hours(1));
+    assert_eq!(
+        winners,
+        vec![
+            (sek(150), UserId::new("buyer2"), 2),
+            (sek(100), UserId::new("buyer1"), 1),
+        ]
+    );
+}
+
+#[test]
+fn test_multi_unit_vickrey_auction_winners_pay_highest_losing_bid() {
+    let mut auction = with_quantity(vickrey_auction(), 2);
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 100, 1)).is_ok());
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 150, 1)).is_ok());
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer3", 120, 1)).is_ok());
+
+    let winners = auction.try_get_winners(ends_at() + Duration::hours(1));
+    assert_eq!(
+        winners,
+        vec![
+            (sek(100), UserId::new("buyer2"), 1),
+            (sek(100), UserId::new("buyer3"), 1),
+        ]
+    );
+}
+
+#[test]
+fn test_multi_unit_timed_ascending_auction_must_beat_lowest_winning_bid() {
+    let mut auction = with_quantity(get_english_auction(), 2);
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 200, 1)).is_ok());
+    // A unit is still free, so any bid is accepted
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 160, 1)).is_ok());
+    assert_eq!(
+        auction.try_add_bid(now, create_sample_bid("buyer3", 165, 1)),
+        Err(Errors::MustRaiseWithAtLeast)
+    );
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer3", 170, 1)).is_ok());
+
+    let winners = auction.try_get_winners(ends_at() + Duration::hours(1));
+    assert_eq!(
+        winners,
+        vec![
+            (sek(200), UserId::new("buyer1"), 1),
+            (sek(170), UserId::new("buyer3"), 1),
+        ]
+    );
+}
+
+#[test]
+fn test_bid_for_more_units_than_offered_is_rejected() {
+    let mut auction = with_quantity(blind_auction(), 2);
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert_eq!(
+        auction.try_add_bid(now, create_sample_bid_for_units("buyer1", 100, 3)),
+        Err(Errors::InvalidQuantity)
+    );
+    assert_eq!(
+        auction.try_add_bid(now, create_sample_bid_for_units("buyer1", 100, 0)),
+        Err(Errors::InvalidQuantity)
+    );
+}
+
+fn with_buy_now_price(mut auction: Auction, price: i64) -> Auction {
+    if let Auction::TimedAscending { options, .. } = &mut auction {
+        options.buy_now_price = Some(price);
+    }
+    auction
+}
+
+#[test]
+fn test_buy_now_bid_ends_timed_ascending_auction() {
+    let mut auction = with_buy_now_price(get_english_auction(), 500);
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 200, 1)).is_ok());
+    assert!(!auction.has_ended(now));
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 500, 1)).is_ok());
+
+    assert!(auction.has_ended(now));
+    assert_eq!(auction.effective_end(), now);
+    assert_eq!(
+        auction.try_add_bid(now, create_sample_bid("buyer1", 600, 1)),
+        Err(Errors::AuctionHasEnded)
+    );
+    assert_eq!(
+        auction.try_get_amount_and_winner(now),
+        Some((sek(500), UserId::new("buyer2")))
+    );
+}
+
+#[test]
+fn test_bid_below_buy_now_price_extends_as_usual() {
+    let mut auction = with_buy_now_price(get_english_auction(), 500);
+    let now = ends_at() - Duration::seconds(30);
+    let mut bid = create_sample_bid("buyer1", 499, 0);
+    bid.at = now;
+
+    assert!(auction.try_add_bid(now, bid).is_ok());
+    assert!(!auction.has_ended(now));
+    assert_eq!(auction.effective_end(), now + Duration::minutes(1));
+}
+
+#[test]
+fn test_first_bid_below_starting_price_is_rejected() {
+    let mut auction = get_english_auction();
+    if let Auction::TimedAscending { options, .. } = &mut auction {
+        options.starting_price = 100;
+    }
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert_eq!(
+        auction.try_add_bid(now, create_sample_bid("buyer1", 99, 1)),
+        Err(Errors::BidBelowStartingPrice)
+    );
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 100, 1)).is_ok());
+    // Later bids only have to beat the highest bid
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 110, 1)).is_ok());
+}
+
+fn create_sample_max_bid(auction: &mut Auction, user_id: &str, max: i64) -> Result<Vec<DomainEvent>, Errors> {
+    let now = auction.starts_at() + Duration::hours(1);
+    auction.try_add_proxy_bid(now, create_sample_bid(user_id, max, 1))
+}
+
+#[test]
+fn test_proxy_bid_opens_at_starting_price() {
+    let mut auction = get_english_auction();
+
+    let events = create_sample_max_bid(&mut auction, "buyer1", 300).unwrap();
+    assert!(matches!(&events[..], [DomainEvent::BidPlaced { .. }]));
+
+    assert_eq!(auction.bids().len(), 1);
+    assert_eq!(auction.highest_bid().map(|b| (b.amount(), b.user())), Some((&sek(1), &UserId::new("buyer1"))));
+}
+
+#[test]
+fn test_proxy_bid_responds_to_being_outbid() {
+    let mut auction = get_english_auction();
+    let now = auction.starts_at() + Duration::hours(1);
+    assert!(create_sample_max_bid(&mut auction, "buyer1", 300).is_ok());
+
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 100, 1)).is_ok());
+
+    let highest = auction.highest_bid().unwrap();
+    assert_eq!((highest.amount(), highest.user()), (&sek(110), &UserId::new("buyer1")));
+}
+
+#[test]
+fn test_competing_proxy_bids_resolve_to_the_higher_maximum() {
+    let mut auction = get_english_auction();
+    assert!(create_sample_max_bid(&mut auction, "buyer1", 300).is_ok());
+
+    assert!(create_sample_max_bid(&mut auction, "buyer2", 200).is_ok());
+
+    // The runner-up shows its full maximum, the leader beats it by the minimum raise
+    let highest = auction.highest_bid().unwrap();
+    assert_eq!((highest.amount(), highest.user()), (&sek(210), &UserId::new("buyer1")));
+    assert!(auction.bids().iter().any(|b| *b.user() == UserId::new("buyer2") && *b.amount() == sek(200)));
+}
+
+#[test]
+fn test_proxy_bid_is_exhausted_by_higher_bid() {
+    let mut auction = get_english_auction();
+    let now = auction.starts_at() + Duration::hours(1);
+    assert!(create_sample_max_bid(&mut auction, "buyer1", 300).is_ok());
+
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 400, 1)).is_ok());
+
+    let highest = auction.highest_bid().unwrap();
+    assert_eq!((highest.amount(), highest.user()), (&sek(400), &UserId::new("buyer2")));
+}
+
+#[test]
+fn test_proxy_bid_not_allowed_in_sealed_bid_auction() {
+    let mut auction = blind_auction();
+    assert_eq!(create_sample_max_bid(&mut auction, "buyer1", 300), Err(Errors::ProxyBidNotAllowed));
+}
+
+#[test]
+fn test_anonymized_bidder_keeps_bids_and_max_bid_under_pseudonym() {
+    let mut auction = get_english_auction();
+    let now = auction.starts_at() + Duration::hours(1);
+    assert!(create_sample_max_bid(&mut auction, "buyer1", 300).is_ok());
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 100, 1)).is_ok());
+    let pseudonym = UserId::new("anonymized-1");
+
+    assert!(auction.anonymize_user(&UserId::new("buyer1"), &pseudonym));
+
+    let highest = auction.highest_bid().unwrap();
+    assert_eq!((highest.amount(), highest.user()), (&sek(110), &pseudonym));
+    assert!(auction.bids().iter().all(|b| *b.user() != UserId::new("buyer1")));
+    // The maximum bid still answers being outbid
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 200, 1)).is_ok());
+    let highest = auction.highest_bid().unwrap();
+    assert_eq!((highest.amount(), highest.user()), (&sek(210), &pseudonym));
+    assert!(!auction.anonymize_user(&UserId::new("buyer1"), &pseudonym));
+}
+
+#[test]
+fn test_referred_users_are_the_ones_anonymizing_replaces() {
+    let mut auction = get_english_auction();
+    let now = auction.starts_at() + Duration::hours(1);
+    assert!(create_sample_max_bid(&mut auction, "buyer1", 300).is_ok());
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 100, 1)).is_ok());
+    let seller = auction.user().clone();
+
+    let referred = auction.referred_users();
+    assert_eq!(referred.len(), 3);
+    assert!([seller, UserId::new("buyer1"), UserId::new("buyer2")].iter().all(|user| referred.contains(user)));
+
+    assert!(auction.anonymize_user(&UserId::new("buyer1"), &UserId::new("anonymized-1")));
+    assert!(!auction.referred_users().contains(&UserId::new("buyer1")));
+    assert!(auction.referred_users().contains(&UserId::new("anonymized-1")));
+}
+
+fn with_max_extension(mut auction: Auction, max_extension: Duration) -> Auction {
+    if let Auction::TimedAscending { options, .. } = &mut auction {
+        options.max_extension = Some(max_extension);
+    }
+    auction
+}
+
+fn create_bid_at(user_id: &str, amount: i64, at: DateTime<Utc>) -> BidData {
+    BidData {
+        at,
+        ..create_sample_bid(user_id, amount, 0)
+    }
+}
+
+#[test]
+fn test_late_bids_stop_extending_at_max_extension() {
+    let mut auction = with_max_extension(get_english_auction(), Duration::minutes(2));
+
+    let at = ends_at() - Duration::seconds(30);
+    assert!(auction.try_add_bid(at, create_bid_at("buyer1", 200, at)).is_ok());
+    assert_eq!(auction.effective_end(), ends_at() + Duration::seconds(30));
+
+    // Bids during the extension are accepted and extend further
+    let at = ends_at() + Duration::seconds(20);
+    assert!(auction.try_add_bid(at, create_bid_at("buyer2", 210, at)).is_ok());
+    assert_eq!(auction.effective_end(), ends_at() + Duration::seconds(80));
+
+    // ... but never past the cap
+    let at = ends_at() + Duration::seconds(75);
+    assert!(auction.try_add_bid(at, create_bid_at("buyer1", 220, at)).is_ok());
+    assert_eq!(auction.effective_end(), ends_at() + Duration::minutes(2));
+
+    let at = ends_at() + Duration::minutes(2);
+    assert!(auction.try_add_bid(at, create_bid_at("buyer2", 230, at)).is_ok());
+    assert_eq!(auction.effective_end(), ends_at() + Duration::minutes(2));
+
+    let at = ends_at() + Duration::minutes(2) + Duration::seconds(1);
+    assert_eq!(
+        auction.try_add_bid(at, create_bid_at("buyer1", 240, at)),
+        Err(Errors::AuctionHasEnded)
+    );
+    assert_eq!(
+        auction.try_get_amount_and_winner(at),
+        Some((sek(230), UserId::new("buyer2")))
+    );
+}
+
+#[test]
+fn test_max_extension_survives_serialization() {
+    let auction = with_max_extension(get_english_auction(), Duration::minutes(5));
+
+    let json = serde_json::to_value(&auction).unwrap();
+    let read_back: Auction = serde_json::from_value(json).unwrap();
+
+    assert_eq!(read_back, auction);
+}
+
+#[test]
+fn test_options_without_max_extension_still_deserialize() {
+    let mut json = serde_json::to_value(get_english_auction()).unwrap();
+    json["options"].as_object_mut().unwrap().remove("max_extension");
+
+    let read_back: Auction = serde_json::from_value(json).unwrap();
+
+    assert_eq!(read_back, get_english_auction());
+}
+
+fn with_increments(mut auction: Auction, increments: Vec<BidIncrement>) -> Auction {
+    if let Auction::TimedAscending { options, .. } = &mut auction {
+        options.increments = increments;
+    }
+    auction
+}
+
+#[test]
+fn test_increment_ladder_sets_minimum_raise_by_price() {
+    let mut auction = with_increments(
+        get_english_auction(),
+        vec![
+            BidIncrement { below: 1000, increment: 10 },
+            BidIncrement { below: 5000, increment: 50 },
+        ],
+    );
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 990, 1)).is_ok());
+    assert_eq!(
+        auction.try_add_bid(now, create_sample_bid("buyer2", 999, 1)),
+        Err(Errors::MustRaiseWithAtLeast)
+    );
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 1000, 1)).is_ok());
+    // From 1000 the next tier applies
+    assert_eq!(
+        auction.try_add_bid(now, create_sample_bid("buyer1", 1049, 1)),
+        Err(Errors::MustRaiseWithAtLeast)
+    );
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 1050, 1)).is_ok());
+}
+
+#[test]
+fn test_next_minimum_bid_follows_the_ladder() {
+    let mut auction = with_increments(
+        get_english_auction(),
+        vec![BidIncrement { below: 1000, increment: 10 }],
+    );
+    let now = auction.starts_at() + Duration::hours(1);
+    assert_eq!(auction.next_minimum_bid(now), Some(sek(1)));
+
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 990, 1)).is_ok());
+    assert_eq!(auction.next_minimum_bid(now), Some(sek(1000)));
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 1000, 1)).is_ok());
+    // Above the ladder the min raise of 10 applies
+    assert_eq!(auction.next_minimum_bid(now), Some(sek(1010)));
+    let next = auction.next_minimum_bid(now).unwrap().value();
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", next, 1)).is_ok());
+
+    assert_eq!(auction.next_minimum_bid(ends_at() + Duration::hours(1)), None);
+}
+
+#[test]
+fn test_next_minimum_bid_beats_the_lowest_winning_bid() {
+    let mut auction = with_quantity(get_english_auction(), 2);
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 200, 1)).is_ok());
+    assert_eq!(auction.next_minimum_bid(now), Some(sek(1)));
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 160, 1)).is_ok());
+    assert_eq!(auction.next_minimum_bid(now), Some(sek(170)));
+}
+
+#[test]
+fn test_sealed_bid_auction_has_no_next_minimum_bid() {
+    let auction = blind_auction();
+
+    assert_eq!(auction.next_minimum_bid(auction.starts_at() + Duration::hours(1)), None);
+}
+
+#[test]
+fn test_min_raise_applies_above_the_ladder() {
+    let auction = with_increments(
+        get_english_auction(),
+        vec![BidIncrement { below: 1000, increment: 50 }],
+    );
+    let Auction::TimedAscending { options, .. } = &auction else {
+        unreachable!()
+    };
+
+    assert_eq!(options.minimum_raise(999), 50);
+    assert_eq!(options.minimum_raise(1000), 10);
+    assert_eq!(options.minimum_next_bid(Some(1000)), 1010);
+    assert_eq!(options.minimum_next_bid(None), 1);
+}
+
+fn with_tie_break(mut auction: Auction, rule: TieBreak) -> Auction {
+    if let Auction::SingleSealedBid { tie_break, .. } = &mut auction {
+        *tie_break = rule;
+    }
+    auction
+}
+
+fn with_tied_bids(mut auction: Auction) -> Auction {
+    let now = auction.starts_at() + Duration::hours(1);
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 200, 1)).is_ok());
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 200, 2)).is_ok());
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer3", 150, 3)).is_ok());
+    auction
+}
+
+#[test]
+fn test_earliest_of_equal_bids_wins_by_default() {
+    for auction in [blind_auction(), vickrey_auction()] {
+        let auction = with_tied_bids(auction);
+        let after_end = auction.expiry() + Duration::hours(1);
+
+        let (_, winner) = auction.try_get_amount_and_winner(after_end).unwrap();
+        assert_eq!(winner.value(), "buyer1");
+    }
+}
+
+#[test]
+fn test_latest_of_equal_bids_wins_when_configured() {
+    let auction = with_tied_bids(with_tie_break(blind_auction(), TieBreak::LatestBid));
+    let after_end = auction.expiry() + Duration::hours(1);
+
+    assert_eq!(
+        auction.try_get_amount_and_winner(after_end),
+        Some((sek(200), UserId::new("buyer2")))
+    );
+}
+
+#[test]
+fn test_vickrey_tie_pays_the_tied_bid() {
+    let auction = with_tied_bids(with_tie_break(vickrey_auction(), TieBreak::LatestBid));
+    let after_end = auction.expiry() + Duration::hours(1);
+
+    // The losing tied bid is the highest losing bid
+    assert_eq!(
+        auction.try_get_amount_and_winner(after_end),
+        Some((sek(200), UserId::new("buyer2")))
+    );
+}
+
+fn with_pricing(mut auction: Auction, rule: VickreyPricing) -> Auction {
+    if let Auction::SingleSealedBid { pricing, .. } = &mut auction {
+        *pricing = rule;
+    }
+    auction
+}
+
+fn vickrey_winner_with_pricing(rule: VickreyPricing, winning_bid: i64) -> Option<(Amount, UserId)> {
+    let mut auction = with_pricing(vickrey_auction(), rule);
+    let now = auction.starts_at() + Duration::hours(1);
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 137, 1)).is_ok());
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", winning_bid, 2)).is_ok());
+    auction.try_get_amount_and_winner(auction.expiry() + Duration::hours(1))
+}
+
+#[test]
+fn test_vickrey_pricing_rounds_the_second_price() {
+    let buyer2 = UserId::new("buyer2");
+
+    assert_eq!(vickrey_winner_with_pricing(VickreyPricing::SecondPrice, 200), Some((sek(137), buyer2.clone())));
+    assert_eq!(
+        vickrey_winner_with_pricing(VickreyPricing::RoundUp { increment: 50 }, 200),
+        Some((sek(150), buyer2.clone()))
+    );
+    assert_eq!(
+        vickrey_winner_with_pricing(VickreyPricing::IncrementOver { increment: 10 }, 200),
+        Some((sek(147), buyer2.clone()))
+    );
+    // Never more than the winning bid
+    assert_eq!(
+        vickrey_winner_with_pricing(VickreyPricing::RoundUp { increment: 50 }, 140),
+        Some((sek(140), buyer2))
+    );
+}
+
+#[test]
+fn test_tie_break_survives_serialization() {
+    let auction = with_tied_bids(with_tie_break(vickrey_auction(), TieBreak::LatestBid));
+    let after_end = auction.expiry() + Duration::hours(1);
+
+    let json = serde_json::to_value(&auction).unwrap();
+    let read_back: Auction = serde_json::from_value(json).unwrap();
+
+    assert_eq!(read_back, auction);
+    assert_eq!(
+        read_back.try_get_amount_and_winner(after_end),
+        auction.try_get_amount_and_winner(after_end)
+    );
+}
+
+#[test]
+fn test_status_follows_the_clock() {
+    let auction = get_english_auction();
+
+    assert_eq!(auction.status(), AuctionStatus::Scheduled);
+    assert_eq!(auction.status_at(starts_at() - Duration::hours(1)), AuctionStatus::Scheduled);
+    assert_eq!(auction.status_at(starts_at()), AuctionStatus::Open);
+    assert_eq!(auction.status_at(ends_at() + Duration::seconds(1)), AuctionStatus::Closed);
+}
+
+#[test]
+fn test_accepted_bid_stores_status() {
+    let mut auction = with_buy_now_price(get_english_auction(), 500);
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 200, 1)).is_ok());
+    assert_eq!(auction.status(), AuctionStatus::Open);
+
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 500, 1)).is_ok());
+    assert_eq!(auction.status(), AuctionStatus::Closed);
+}
+
+#[test]
+fn test_voided_auction_is_cancelled_for_good() {
+    let mut auction = get_english_auction();
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.void(now, None).is_ok());
+
+    assert_eq!(auction.status(), AuctionStatus::Cancelled);
+    assert_eq!(auction.force_end(now), Err(Errors::AuctionHasEnded));
+    assert_eq!(auction.void(now, None), Err(Errors::IllegalStatusTransition));
+    assert_eq!(
+        auction.try_add_bid(now, create_sample_bid("buyer1", 200, 1)),
+        Err(Errors::AuctionHasEnded)
+    );
+}
+
+#[test]
+fn test_extended_auction_takes_bids_until_the_new_expiry() {
+    let mut auction = get_english_auction();
+    let now = auction.starts_at() + Duration::hours(1);
+    let expiry = auction.expiry() + Duration::hours(2);
+
+    assert_eq!(
+        auction.extend(now, auction.expiry(), None),
+        Err(Errors::ExpiryMustBeLater)
+    );
+    assert!(auction.extend(now, expiry, Some("outage".to_string())).is_ok());
+
+    assert_eq!(auction.expiry(), expiry);
+    assert_eq!(auction.extended_at(), Some(now));
+    assert_eq!(auction.extension_reason(), Some("outage"));
+    let late = expiry - Duration::minutes(30);
+    assert!(auction.try_add_bid(late, create_sample_bid("buyer1", 200, 1)).is_ok());
+    assert!(!auction.has_ended(late));
+    assert_eq!(
+        auction.extend(expiry + Duration::seconds(1), expiry + Duration::hours(1), None),
+        Err(Errors::AuctionHasEnded)
+    );
+}
+
+#[test]
+fn test_paused_auction_takes_no_bids_and_is_extended_on_resume() {
+    let mut auction = get_english_auction();
+    let paused_at = auction.starts_at() + Duration::hours(1);
+    let expiry = auction.expiry();
+
+    assert_eq!(auction.resume(paused_at), Err(Errors::AuctionIsNotPaused));
+    assert!(auction.pause(paused_at).is_ok());
+    assert_eq!(auction.pause(paused_at), Err(Errors::AuctionIsPaused));
+
+    assert_eq!(auction.paused_at(), Some(paused_at));
+    assert_eq!(
+        auction.try_add_bid(paused_at, create_sample_bid("buyer1", 200, 1)),
+        Err(Errors::AuctionIsPaused)
+    );
+    // The auction does not end while paused
+    let resumed_at = expiry + Duration::hours(1);
+    assert!(!auction.has_ended(resumed_at));
+
+    assert!(auction.resume(resumed_at).is_ok());
+    assert_eq!(auction.expiry(), expiry + (resumed_at - paused_at));
+    assert!(!auction.is_paused());
+    assert!(auction.try_add_bid(resumed_at, create_sample_bid("buyer1", 200, 1)).is_ok());
+}
+
+#[test]
+fn test_cancelled_auction_takes_no_bids() {
+    let mut auction = get_english_auction();
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.cancel(now, Some("withdrawn".to_string())).is_ok());
+
+    assert_eq!(auction.status(), AuctionStatus::Cancelled);
+    assert_eq!(auction.cancelled_at(), Some(now));
+    assert_eq!(auction.cancel_reason(), Some("withdrawn"));
+    assert!(auction.has_ended(now));
+    assert_eq!(
+        auction.try_add_bid(now, create_sample_bid("buyer1", 200, 1)),
+        Err(Errors::AuctionHasEnded)
+    );
+    assert_eq!(auction.cancel(now, None), Err(Errors::IllegalStatusTransition));
+}
+
+#[test]
+fn test_cancelled_auction_is_removed() {
+    let mut auction = get_english_auction();
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.cancel(now, Some("withdrawn".to_string())).is_ok());
+
+    assert_eq!(
+        auction.removal(),
+        Some(AuctionRemoval { kind: RemovalKind::Cancelled, at: now, reason: Some("withdrawn".to_string()) })
+    );
+}
+
+#[test]
+fn test_auction_is_active_from_start_until_it_ends() {
+    let auction = get_english_auction();
+    assert!(!auction.is_active(auction.starts_at() - Duration::seconds(1)));
+    assert!(auction.is_active(auction.starts_at()));
+    assert!(auction.is_active(ends_at()));
+    assert!(!auction.is_active(ends_at() + Duration::seconds(1)));
+}
+
+#[test]
+fn test_auction_can_be_archived_once_ended_for_the_retention() {
+    let auction = get_english_auction();
+    let retention = Duration::days(90);
+    assert!(!auction.can_archive(ends_at(), retention));
+    assert!(!auction.can_archive(ends_at() + retention - Duration::seconds(1), retention));
+    assert!(auction.can_archive(ends_at() + retention, retention));
+}
+
+#[test]
+fn test_retracted_bid_no_longer_stands() {
+    let mut auction = get_english_auction();
+    let first = create_sample_bid("buyer1", 100, 1);
+    let second = create_sample_bid("buyer2", 200, 2);
+    assert!(auction.try_add_bid(first.at, first).is_ok());
+    assert!(auction.try_add_bid(second.at, second.clone()).is_ok());
+
+    let now = second.at + Duration::seconds(30);
+    let retracted = auction.retract_bid(now, &UserId::new("buyer2"), Duration::seconds(60));
+
+    assert_eq!(retracted.map(|b| b.id), Ok(2));
+    assert_eq!(auction.highest_bid().map(|b| b.amount()), Some(&sek(100)));
+    assert_eq!(auction.retracted_bids()[0].retracted_at, now);
+    // Ids of retracted bids are not handed out again
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer3", 150, 2)).is_ok());
+    assert_eq!(auction.bids().last().map(|b| b.id), Some(3));
+}
+
+#[test]
+fn test_bid_cannot_be_retracted_after_the_window() {
+    let mut auction = get_english_auction();
+    let bid = create_sample_bid("buyer1", 100, 1);
+    assert!(auction.try_add_bid(bid.at, bid.clone()).is_ok());
+
+    let later = bid.at + Duration::seconds(61);
+    assert_eq!(
+        auction.retract_bid(later, &UserId::new("buyer1"), Duration::seconds(60)),
+        Err(Errors::RetractionWindowHasPassed)
+    );
+    assert_eq!(
+        auction.retract_bid(later, &UserId::new("buyer2"), Duration::seconds(60)),
+        Err(Errors::NoBidToRetract)
+    );
+}
+
+#[test]
+fn test_sealed_bids_cannot_be_retracted() {
+    let mut auction = blind_auction();
+    let bid = create_sample_bid("buyer1", 100, 1);
+    assert!(auction.try_add_bid(bid.at, bid.clone()).is_ok());
+
+    assert_eq!(
+        auction.retract_bid(bid.at, &UserId::new("buyer1"), Duration::seconds(60)),
+        Err(Errors::BidRetractionNotAllowed)
+    );
+}
+
+#[test]
+fn test_voided_bid_no_longer_stands_nor_extends() {
+    let mut auction = get_english_auction();
+    let first = create_sample_bid("buyer1", 100, 1);
+    assert!(auction.try_add_bid(first.at, first).is_ok());
+    // A late bid extends the auction
+    let late = ends_at() - Duration::seconds(30);
+    assert!(auction.try_add_bid(late, create_bid_at("buyer2", 200, late)).is_ok());
+    assert_eq!(auction.effective_end(), late + Duration::minutes(1));
+
+    let now = late + Duration::seconds(10);
+    let support = UserId::new("support");
+    let voided = auction.void_bid(now, 2, &support, Some("fraud".to_string()));
+
+    assert_eq!(voided.map(|b| b.id), Ok(2));
+    assert_eq!(auction.highest_bid().map(|b| b.amount()), Some(&sek(100)));
+    assert_eq!(auction.effective_end(), ends_at());
+    assert_eq!(auction.voided_bids()[0].voided_by, support);
+    assert_eq!(auction.voided_bids()[0].void_reason.as_deref(), Some("fraud"));
+    // Ids of voided bids are not handed out again
+    assert!(auction.try_add_bid(now, create_bid_at("buyer3", 150, now)).is_ok());
+    assert_eq!(auction.bids().last().map(|b| b.id), Some(3));
+}
+
+#[test]
+fn test_bids_are_numbered_after_the_last_id_assigned_by_the_store() {
+    let mut auction = get_english_auction();
+    // Another writer already handed out ids up to 5
+    auction.set_last_bid_id(5);
+    let first = create_sample_bid("buyer1", 100, 1);
+    assert!(auction.try_add_bid(first.at, first).is_ok());
+    assert_eq!(auction.bids().last().map(|b| b.id), Some(6));
+    assert_eq!(auction.last_bid_id(), 6);
+    // The counter never moves back
+    auction.set_last_bid_id(2);
+    assert_eq!(auction.last_bid_id(), 6);
+}
+
+#[test]
+fn test_only_standing_bids_of_running_auctions_can_be_voided() {
+    let mut auction = get_english_auction();
+    let bid = create_sample_bid("buyer1", 100, 1);
+    assert!(auction.try_add_bid(bid.at, bid.clone()).is_ok());
+    let support = UserId::new("support");
+
+    assert_eq!(auction.void_bid(bid.at, 7, &support, None), Err(Errors::UnknownBid));
+    assert_eq!(
+        auction.void_bid(ends_at() + Duration::seconds(1), 1, &support, None),
+        Err(Errors::AuctionHasEnded)
+    );
+}
+
+fn all_pay_auction() -> Auction {
+    match blind_auction() {
+        Auction::SingleSealedBid { base, tie_break, reserve_price, pricing, .. } => Auction::SingleSealedBid {
+            base,
+            options: SingleSealedBidOptions::AllPay,
+            tie_break,
+            reserve_price,
+            pricing,
+        },
+        other => other,
+    }
+}
+
+#[test]
+fn test_all_pay_auction_charges_every_bidder() {
+    let mut auction = all_pay_auction();
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 100, 1)).is_ok());
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 150, 1)).is_ok());
+
+    let after_end = ends_at() + Duration::hours(1);
+    assert_eq!(
+        auction.try_get_winners(after_end),
+        vec![(sek(150), UserId::new("buyer2"), 1)]
+    );
+    assert_eq!(
+        auction.try_get_charges(after_end),
+        vec![(sek(150), UserId::new("buyer2")), (sek(100), UserId::new("buyer1"))]
+    );
+    assert!(auction.try_get_charges(now).is_empty());
+}
+
+#[test]
+fn test_only_winners_are_charged_outside_all_pay_auctions() {
+    let mut auction = with_quantity(vickrey_auction(), 2);
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 100, 1)).is_ok());
+    assert!(auction.try_add_bid(now, create_sample_bid_for_units("buyer2", 150, 2)).is_ok());
+
+    assert_eq!(
+        auction.try_get_charges(ends_at() + Duration::hours(1)),
+        vec![(sek(200), UserId::new("buyer2"))]
+    );
+}
+
+fn with_reserve(auction: Auction, reserve: i64) -> Auction {
+    match auction {
+        Auction::SingleSealedBid { base, options, tie_break, pricing, .. } => Auction::SingleSealedBid {
+            base,
+            options,
+            tie_break,
+            reserve_price: Some(reserve),
+            pricing,
+        },
+        other => other,
+    }
+}
+
+#[test]
+fn test_sealed_bid_below_reserve_is_no_sale() {
+    let mut auction = with_reserve(blind_auction(), 200);
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 150, 1)).is_ok());
+
+    assert!(auction.try_get_winners(ends_at() + Duration::hours(1)).is_empty());
+}
+
+#[test]
+fn test_vickrey_winner_pays_at_least_the_reserve() {
+    let mut auction = with_reserve(vickrey_auction(), 120);
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 100, 1)).is_ok());
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer2", 150, 1)).is_ok());
+
+    assert_eq!(
+        auction.try_get_amount_and_winner(ends_at() + Duration::hours(1)),
+        Some((sek(120), UserId::new("buyer2")))
+    );
+}
+
+fn with_lots(mut auction: Auction, titles: &[&str]) -> Auction {
+    let lots = titles
+        .iter()
+        .zip(1..)
+        .map(|(title, id)| Lot { id, title: title.to_string() })
+        .collect();
+    match &mut auction {
+        Auction::TimedAscending { base, .. } => base.lots = lots,
+        Auction::SingleSealedBid { base, .. } => base.lots = lots,
+    }
+    auction
+}
+
+#[test]
+fn test_lots_take_bids_and_have_winners_of_their_own() {
+    let mut auction = with_lots(get_english_auction(), &["Lamp", "Chair"]);
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert_eq!(auction.try_add_bid(now, create_sample_bid("buyer1", 150, 1)), Err(Errors::LotRequired));
+    assert_eq!(auction.try_add_lot_bid(now, 3, create_sample_bid("buyer1", 150, 1)), Err(Errors::UnknownLot));
+    assert!(auction.try_add_lot_bid(now, 1, create_sample_bid("buyer1", 150, 1)).is_ok());
+    // Lower than the bid on the other lot, but the lots are bid on separately
+    assert!(auction.try_add_lot_bid(now, 2, create_sample_bid("buyer2", 140, 1)).is_ok());
+    assert_eq!(
+        auction.try_add_lot_bid(now, 1, create_sample_bid("buyer2", 155, 1)),
+        Err(Errors::MustRaiseWithAtLeast)
+    );
+    assert!(auction.try_add_lot_bid(now, 1, create_sample_bid("buyer2", 200, 1)).is_ok());
+    assert!(auction.try_add_lot_bid(now, 2, create_sample_bid("buyer1", 160, 1)).is_ok());
+
+    let ids: Vec<(i64, Option<i32>)> = auction.bids().iter().map(|bid| (bid.id, bid.lot)).collect();
+    assert_eq!(ids, vec![(1, Some(1)), (2, Some(2)), (3, Some(1)), (4, Some(2))]);
+    assert_eq!(
+        auction.try_get_winners(ends_at() + Duration::hours(1)),
+        vec![(sek(200), UserId::new("buyer2"), 1), (sek(160), UserId::new("buyer1"), 1)]
+    );
+}
+
+#[test]
+fn test_late_bid_on_a_lot_extends_the_whole_auction() {
+    let mut auction = with_lots(get_english_auction(), &["Lamp", "Chair"]);
+    let first = create_sample_bid("buyer1", 150, 1);
+    assert!(auction.try_add_lot_bid(first.at, 1, first).is_ok());
+
+    let late = ends_at() - Duration::seconds(30);
+    assert!(auction.try_add_lot_bid(late, 2, create_bid_at("buyer2", 150, late)).is_ok());
+
+    assert_eq!(auction.effective_end(), late + Duration::minutes(1));
+    // The other lots take bids until the extended end too
+    let after_expiry = ends_at() + Duration::seconds(10);
+    assert!(auction.try_add_lot_bid(after_expiry, 1, create_bid_at("buyer2", 200, after_expiry)).is_ok());
+}
+
+#[test]
+fn test_draft_takes_no_bids_until_published() {
+    let mut auction = get_english_auction();
+    match &mut auction {
+        Auction::TimedAscending { base, .. } => base.status = AuctionStatus::Draft,
+        Auction::SingleSealedBid { base, .. } => base.status = AuctionStatus::Draft,
+    }
+    let now = auction.starts_at() + Duration::hours(1);
+
+    assert_eq!(auction.status_at(now), AuctionStatus::Draft);
+    assert_eq!(auction.try_add_bid(now, create_sample_bid("buyer1", 200, 1)), Err(Errors::AuctionHasNotStarted));
+
+    assert_eq!(auction.publish(now), Ok(()));
+    assert_eq!(auction.status_at(now), AuctionStatus::Open);
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 200, 1)).is_ok());
+    assert_eq!(auction.publish(now), Err(Errors::IllegalStatusTransition));
+}
+
+#[test]
+fn test_draft_ending_in_the_past_cannot_be_published() {
+    let mut auction = get_english_auction();
+    match &mut auction {
+        Auction::TimedAscending { base, .. } => base.status = AuctionStatus::Draft,
+        Auction::SingleSealedBid { base, .. } => base.status = AuctionStatus::Draft,
+    }
+
+    assert_eq!(auction.publish(ends_at() + Duration::hours(1)), Err(Errors::ExpiryMustBeLater));
+}
+
+#[test]
+fn test_bids_return_what_they_did() {
+    let mut auction = with_buy_now_price(get_english_auction(), 500);
+    let now = auction.starts_at() + Duration::hours(1);
+    let events = auction.try_add_bid(now, create_sample_bid("buyer1", 200, 1)).unwrap();
+    assert_eq!(
+        events,
+        vec![DomainEvent::BidPlaced { auction_id: auction_id(), bid: auction.bids()[0].clone() }]
+    );
+
+    let late = ends_at() - Duration::seconds(30);
+    let events = auction.try_add_bid(late, create_bid_at("buyer2", 300, late)).unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(
+        events[1],
+        DomainEvent::AuctionExtended { auction_id: auction_id(), ends_at: late + Duration::minutes(1) }
+    );
+
+    let events = auction.try_add_bid(late, create_bid_at("buyer1", 500, late)).unwrap();
+    assert_eq!(events.last(), Some(&DomainEvent::AuctionEnded { auction_id: auction_id(), at: late }));
+}
+
+#[test]
+fn test_closed_auction_settles_by_its_own_fees_or_the_default() {
+    let mut auction = get_english_auction();
+    let now = auction.starts_at() + Duration::hours(1);
+    assert!(auction.try_add_bid(now, create_sample_bid("buyer1", 200, 1)).is_ok());
+    let default_policy = FeePolicy { buyers_premium_bps: 1000, ..FeePolicy::default() };
+
+    assert_eq!(auction.try_get_settlement(now, &default_policy), None);
+    let closed = ends_at() + Duration::hours(1);
+    let settlement = auction.try_get_settlement(closed, &default_policy).unwrap();
+    assert_eq!(settlement.buyers[0].total, sek(220));
+    assert_eq!(settlement.seller_receives, sek(200));
+
+    match &mut auction {
+        Auction::TimedAscending { base, .. } => base.fee_policy = Some(FeePolicy { seller_fee: 20, ..FeePolicy::default() }),
+        Auction::SingleSealedBid { base, .. } => base.fee_policy = Some(FeePolicy { seller_fee: 20, ..FeePolicy::default() }),
+    }
+    let settlement = auction.try_get_settlement(closed, &default_policy).unwrap();
+    assert_eq!(settlement.buyers[0].total, sek(200));
+    assert_eq!(settlement.seller_receives, sek(180));
+}